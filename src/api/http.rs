@@ -0,0 +1,218 @@
+//! Minimal HTTP/1.1 request parsing and response writing for the API server
+
+use std::net::{IpAddr, SocketAddr};
+use std::time::Duration;
+
+use serde_json::Value;
+use tokio::io::{AsyncRead, AsyncReadExt};
+
+/// Largest accepted request line plus headers
+const MAX_HEAD: usize = 64 * 1024;
+
+/// Largest accepted request body
+const MAX_BODY: usize = 1024 * 1024;
+
+/// How long a client gets to send its whole request
+const READ_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Response head that opens a server-sent event stream
+pub const SSE_HEAD: &str = "HTTP/1.1 200 OK\r\n\
+    Content-Type: text/event-stream\r\n\
+    Cache-Control: no-cache\r\n\
+    Connection: close\r\n\r\n";
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Request {
+    pub method: String,
+    /// Path without the query string
+    pub path: String,
+    /// Header names are lowercased
+    pub headers: Vec<(String, String)>,
+    pub body: Vec<u8>,
+}
+
+impl Request {
+    pub fn header(&self, name: &str) -> Option<&str> {
+        self.headers
+            .iter()
+            .find(|(key, _)| key == name)
+            .map(|(_, value)| value.as_str())
+    }
+}
+
+/// Read one request: the head up to the blank line, then `Content-Length`
+/// bytes of body. A client that doesn't finish in time is dropped.
+pub async fn read_request<R: AsyncRead + Unpin>(reader: &mut R) -> Result<Request, String> {
+    tokio::time::timeout(READ_TIMEOUT, read(reader))
+        .await
+        .map_err(|_| "Timed out waiting for the request".to_string())?
+}
+
+async fn read<R: AsyncRead + Unpin>(reader: &mut R) -> Result<Request, String> {
+    let mut buf = Vec::new();
+    let mut chunk = [0u8; 4096];
+    let head_end = loop {
+        if let Some(pos) = buf.windows(4).position(|w| w == b"\r\n\r\n") {
+            break pos;
+        }
+        if buf.len() > MAX_HEAD {
+            return Err("Request headers too large".to_string());
+        }
+        let n = reader.read(&mut chunk).await.map_err(|e| e.to_string())?;
+        if n == 0 {
+            return Err("Connection closed before the request was complete".to_string());
+        }
+        buf.extend_from_slice(&chunk[..n]);
+    };
+
+    let head = std::str::from_utf8(&buf[..head_end]).map_err(|_| "Headers are not UTF-8")?;
+    let mut request = parse_head(head)?;
+
+    let length = match request.header("content-length") {
+        Some(value) => value
+            .trim()
+            .parse::<usize>()
+            .map_err(|_| format!("Invalid Content-Length: {}", value))?,
+        None => 0,
+    };
+    if length > MAX_BODY {
+        return Err(format!("Request body over {} bytes", MAX_BODY));
+    }
+    let mut body = buf[head_end + 4..].to_vec();
+    while body.len() < length {
+        let n = reader.read(&mut chunk).await.map_err(|e| e.to_string())?;
+        if n == 0 {
+            return Err("Connection closed before the body was complete".to_string());
+        }
+        body.extend_from_slice(&chunk[..n]);
+    }
+    body.truncate(length);
+    request.body = body;
+    Ok(request)
+}
+
+/// Whether `host` (a `Host` header) names this machine: a loopback name or
+/// address, or the address the server listens on. Anything else is a
+/// rebound DNS name pointing a web page at the API.
+pub fn host_allowed(host: &str, local: Option<SocketAddr>) -> bool {
+    let name = match host.strip_prefix('[') {
+        Some(rest) => rest.split(']').next().unwrap_or(rest),
+        None => host.rsplit_once(':').map_or(host, |(name, _)| name),
+    };
+    if name.eq_ignore_ascii_case("localhost") {
+        return true;
+    }
+    match name.parse::<IpAddr>() {
+        Ok(ip) => ip.is_loopback() || local.is_some_and(|local| local.ip() == ip),
+        Err(_) => false,
+    }
+}
+
+/// Parse the request line and headers
+pub fn parse_head(head: &str) -> Result<Request, String> {
+    let mut lines = head.split("\r\n");
+    let request_line = lines.next().unwrap_or_default();
+    let mut parts = request_line.split_whitespace();
+    let (Some(method), Some(target), Some(_version)) = (parts.next(), parts.next(), parts.next())
+    else {
+        return Err(format!("Malformed request line: {}", request_line));
+    };
+    let path = target.split('?').next().unwrap_or(target).to_string();
+
+    let mut headers = Vec::new();
+    for line in lines.filter(|l| !l.is_empty()) {
+        let Some((name, value)) = line.split_once(':') else {
+            return Err(format!("Malformed header: {}", line));
+        };
+        headers.push((name.trim().to_ascii_lowercase(), value.trim().to_string()));
+    }
+
+    Ok(Request {
+        method: method.to_string(),
+        path,
+        headers,
+        body: Vec::new(),
+    })
+}
+
+/// A complete JSON response
+pub fn json_response(status: u16, body: &Value) -> Vec<u8> {
+    let body = body.to_string();
+    format!(
+        "HTTP/1.1 {} {}\r\n\
+         Content-Type: application/json\r\n\
+         Content-Length: {}\r\n\
+         Connection: close\r\n\r\n{}",
+        status,
+        reason_phrase(status),
+        body.len(),
+        body
+    )
+    .into_bytes()
+}
+
+/// One server-sent event with a JSON payload
+pub fn sse_event(event: &str, data: &Value) -> String {
+    format!("event: {}\ndata: {}\n\n", event, data)
+}
+
+fn reason_phrase(status: u16) -> &'static str {
+    match status {
+        200 => "OK",
+        400 => "Bad Request",
+        401 => "Unauthorized",
+        403 => "Forbidden",
+        404 => "Not Found",
+        405 => "Method Not Allowed",
+        415 => "Unsupported Media Type",
+        500 => "Internal Server Error",
+        _ => "",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[tokio::test]
+    async fn reads_head_and_body() {
+        let raw = b"POST /v1/commands/run?x=1 HTTP/1.1\r\n\
+            Host: localhost\r\n\
+            Authorization: Bearer s3cret\r\n\
+            Content-Length: 20\r\n\r\n\
+            {\"path\": \"App/Up\"}  trailing";
+        let request = read_request(&mut &raw[..]).await.unwrap();
+        assert_eq!(request.method, "POST");
+        assert_eq!(request.path, "/v1/commands/run");
+        assert_eq!(request.header("authorization"), Some("Bearer s3cret"));
+        assert_eq!(request.body, b"{\"path\": \"App/Up\"}  ");
+
+        assert!(read_request(&mut &b"GET / HTTP/1.1\r\nHost"[..])
+            .await
+            .is_err());
+        assert!(parse_head("garbage").is_err());
+    }
+
+    #[test]
+    fn only_local_hosts_are_allowed() {
+        let local: SocketAddr = "192.168.1.5:7373".parse().unwrap();
+        for host in [
+            "localhost:7373",
+            "127.0.0.1:7373",
+            "[::1]:7373",
+            "192.168.1.5:7373",
+        ] {
+            assert!(host_allowed(host, Some(local)), "{}", host);
+        }
+        for host in ["evil.example:7373", "127.0.0.1.nip.io", "10.0.0.1:7373", ""] {
+            assert!(!host_allowed(host, Some(local)), "{}", host);
+        }
+    }
+
+    #[test]
+    fn formats_events_on_one_data_line() {
+        let event = sse_event("line", &json!({ "content": "a\nb" }));
+        assert_eq!(event, "event: line\ndata: {\"content\":\"a\\nb\"}\n\n");
+    }
+}
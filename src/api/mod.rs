@@ -0,0 +1,396 @@
+//! Local HTTP API for editor extensions and dashboards
+//!
+//! Off unless `api.listen` is set (or `k3dev serve` is run). Endpoints:
+//!
+//! - `GET /v1/status` — cluster container status and whether the API answers
+//! - `GET /v1/commands` — runnable commands, as `k3dev list-commands --json`
+//! - `POST /v1/commands/run` — run `{"path": "App/Migrate", "inputs": {...}}`;
+//!   output streams back as server-sent events: a `line` event per output
+//!   line, then one `done` event with the outcome
+//!
+//! Every request needs the bearer token: `api.token`, or one generated into
+//! a private file (see [`token`]). Requests from browsers (an `Origin`
+//! header) or for another host name are refused, so web pages can't reach
+//! the API, and a run needs a JSON body.
+//!
+//! The config is re-read on every request, so edits show up without a
+//! restart. Each connection serves one request and is then closed.
+
+mod http;
+pub mod token;
+
+use std::collections::HashMap;
+use std::net::SocketAddr;
+use std::path::{Path, PathBuf};
+use std::sync::Arc;
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
+use serde_json::{json, Value};
+use tokio::io::AsyncWriteExt;
+use tokio::net::{TcpListener, TcpStream};
+use tokio::sync::mpsc;
+
+use crate::cluster::{ClusterConfig, ClusterManager, ClusterStatus};
+use crate::commands::{
+    command_catalog, prepare_client_command, run_exec, CommandOutcome, OutputClassifier,
+    PrepareError,
+};
+use crate::config::{Config, ConfigLoader, ExecutionTarget, HookEvent};
+use crate::hooks::HookExecutor;
+use crate::k8s::K8sClient;
+use crate::ui::components::{OutputLine, OutputType};
+
+use http::{host_allowed, json_response, read_request, sse_event, Request, SSE_HEAD};
+
+/// Listen address of `k3dev serve` when neither `--listen` nor `api.listen` is set
+pub const DEFAULT_API_LISTEN: &str = "127.0.0.1:7373";
+
+/// Body of `POST /v1/commands/run`
+#[derive(Debug, Deserialize)]
+struct RunRequest {
+    path: String,
+    /// Placeholder values; unset ones fall back to the input's default
+    #[serde(default)]
+    inputs: HashMap<String, String>,
+}
+
+pub struct ApiServer {
+    listener: TcpListener,
+    config_path: Option<String>,
+    token: String,
+    /// Where the token was generated, when `api.token` isn't set
+    token_file: Option<PathBuf>,
+}
+
+impl ApiServer {
+    /// Bind the listen address; requests are served by [`ApiServer::run`].
+    /// Without a `token`, the generated one is loaded or created first.
+    pub async fn bind(
+        listen: &str,
+        config_path: Option<&str>,
+        token: Option<String>,
+    ) -> Result<Self> {
+        let (token, token_file) = match token.filter(|t| !t.is_empty()) {
+            Some(token) => (token, None),
+            None => {
+                let path = token::token_path()
+                    .context("No data directory to keep the API token in; set api.token")?;
+                (token::load_or_create(&path)?, Some(path))
+            }
+        };
+        let listener = TcpListener::bind(listen)
+            .await
+            .with_context(|| format!("Failed to listen on {}", listen))?;
+        Ok(Self {
+            listener,
+            config_path: config_path.map(str::to_string),
+            token,
+            token_file,
+        })
+    }
+
+    pub fn local_addr(&self) -> Option<SocketAddr> {
+        self.listener.local_addr().ok()
+    }
+
+    /// The file holding the generated token; None when `api.token` is used
+    pub fn token_file(&self) -> Option<&Path> {
+        self.token_file.as_deref()
+    }
+
+    /// Accept connections until the listener fails
+    pub async fn run(self) -> Result<()> {
+        let handler = Arc::new(Handler {
+            config_path: self.config_path,
+            token: self.token,
+            local_addr: self.listener.local_addr().ok(),
+        });
+        loop {
+            let (stream, _) = self.listener.accept().await?;
+            let handler = Arc::clone(&handler);
+            tokio::spawn(async move { handler.serve(stream).await });
+        }
+    }
+}
+
+struct Handler {
+    config_path: Option<String>,
+    token: String,
+    local_addr: Option<SocketAddr>,
+}
+
+impl Handler {
+    async fn serve(&self, mut stream: TcpStream) {
+        let request = match read_request(&mut stream).await {
+            Ok(request) => request,
+            Err(e) => {
+                let _ = stream.write_all(&error_response(400, &e)).await;
+                return;
+            }
+        };
+
+        if request.header("origin").is_some() {
+            let _ = stream
+                .write_all(&error_response(
+                    403,
+                    "Requests from browsers are not allowed",
+                ))
+                .await;
+            return;
+        }
+        if !host_allowed(request.header("host").unwrap_or_default(), self.local_addr) {
+            let _ = stream
+                .write_all(&error_response(403, "Host is not this machine"))
+                .await;
+            return;
+        }
+        let expected = format!("Bearer {}", self.token);
+        if request.header("authorization") != Some(expected.as_str()) {
+            let _ = stream
+                .write_all(&error_response(401, "Missing or wrong bearer token"))
+                .await;
+            return;
+        }
+
+        let config = match ConfigLoader::new(self.config_path.as_deref()).load() {
+            Ok(config) => config,
+            Err(e) => {
+                let message = format!("Failed to load config: {:#}", e);
+                let _ = stream.write_all(&error_response(500, &message)).await;
+                return;
+            }
+        };
+
+        let response = match (request.method.as_str(), request.path.as_str()) {
+            ("GET", "/v1/status") => json_response(200, &status(&config).await),
+            ("GET", "/v1/commands") => {
+                json_response(200, &json!(command_catalog(&config.commands)))
+            }
+            ("POST", "/v1/commands/run") => {
+                run_command(&config, &request, &mut stream).await;
+                return;
+            }
+            (_, "/v1/status" | "/v1/commands" | "/v1/commands/run") => {
+                error_response(405, "Method not allowed")
+            }
+            (_, path) => error_response(404, &format!("No such endpoint: {}", path)),
+        };
+        let _ = stream.write_all(&response).await;
+    }
+}
+
+/// Cluster container status, plus whether the Kubernetes API answers
+async fn status(config: &Config) -> Value {
+    let cluster_config = Arc::new(ClusterConfig::from(config.infrastructure.clone()));
+    let status = match ClusterManager::new(Arc::clone(&cluster_config)).await {
+        Ok(manager) => manager.get_status().await,
+        Err(_) => ClusterStatus::Unknown,
+    };
+    let api_reachable = match status {
+        ClusterStatus::Running => match k8s_client(config).await {
+            Some(client) => client.is_connected().await,
+            None => false,
+        },
+        _ => false,
+    };
+    json!({
+        "cluster": cluster_config.container_name,
+        "status": status_label(status),
+        "api_reachable": api_reachable,
+        "version": env!("CARGO_PKG_VERSION"),
+    })
+}
+
+fn status_label(status: ClusterStatus) -> &'static str {
+    match status {
+        ClusterStatus::Running => "running",
+        ClusterStatus::Stopped => "stopped",
+        ClusterStatus::Starting => "starting",
+        ClusterStatus::Paused => "paused",
+        ClusterStatus::NotCreated => "not_created",
+        ClusterStatus::RuntimeNotRunning => "runtime_not_running",
+        ClusterStatus::Unknown => "unknown",
+    }
+}
+
+/// Validate the request, then stream the command's output as events
+async fn run_command(config: &Config, request: &Request, stream: &mut TcpStream) {
+    // Forms can post anything but JSON without a preflight
+    let json = request
+        .header("content-type")
+        .and_then(|value| value.split(';').next())
+        .is_some_and(|mime| mime.trim().eq_ignore_ascii_case("application/json"));
+    if !json {
+        let _ = stream
+            .write_all(&error_response(
+                415,
+                "Content-Type must be application/json",
+            ))
+            .await;
+        return;
+    }
+    let run: RunRequest = match serde_json::from_slice(&request.body) {
+        Ok(run) => run,
+        Err(e) => {
+            let message = format!("Expected {{\"path\": ..., \"inputs\": {{...}}}}: {}", e);
+            let _ = stream.write_all(&error_response(400, &message)).await;
+            return;
+        }
+    };
+    let prepared = match prepare_client_command(&config.commands, &run.path, run.inputs) {
+        Ok(prepared) => prepared,
+        Err(e) => {
+            let (status, body) = match e {
+                PrepareError::NotFound => (
+                    404,
+                    json!({ "error": format!("Command not found: {}", run.path) }),
+                ),
+                PrepareError::Submenu(children) => (
+                    400,
+                    json!({
+                        "error": format!("'{}' is a submenu, not a command", run.path),
+                        "children": children,
+                    }),
+                ),
+                PrepareError::Missing(names) => (
+                    400,
+                    json!({
+                        "error": format!("Missing inputs for '{}'", run.path),
+                        "missing": names,
+                    }),
+                ),
+            };
+            let _ = stream.write_all(&json_response(status, &body)).await;
+            return;
+        }
+    };
+
+    // From here on the client sees events; a write error means it went away,
+    // and the command is left to finish on its own
+    let mut events = EventStream::new(stream);
+    events.send_head().await;
+    for key in &prepared.ignored {
+        events
+            .line(&OutputLine::warning(format!(
+                "'{}' does not use @{}; ignoring it",
+                prepared.entry.name, key
+            )))
+            .await;
+    }
+
+    let k8s = match prepared.exec.target {
+        ExecutionTarget::Script | ExecutionTarget::Lua | ExecutionTarget::Kubernetes { .. } => {
+            k8s_client(config).await
+        }
+        _ => None,
+    };
+    let mut classifier = OutputClassifier::new(&prepared.entry.success);
+    let (line_tx, mut line_rx) = mpsc::channel::<OutputLine>(100);
+    let exec = prepared.exec.clone();
+    let runner = tokio::spawn(async move { run_exec(&exec, k8s.as_ref(), line_tx).await });
+    while let Some(line) = line_rx.recv().await {
+        events.line(&classifier.classify(line)).await;
+    }
+    let result = runner
+        .await
+        .unwrap_or_else(|e| Err(format!("Command task failed: {}", e)));
+
+    let outcome = match result {
+        Ok(exit_code) => classifier.outcome(exit_code),
+        Err(reason) => CommandOutcome::Failed {
+            exit_code: None,
+            reason,
+        },
+    };
+
+    if outcome.is_failure() && !config.hooks.on_command_failed.is_empty() {
+        let (hook_tx, mut hook_rx) = mpsc::channel::<OutputLine>(100);
+        let hooks = HookExecutor::new(config.hooks.clone()).with_streamed_output();
+        let env = outcome.hook_env(&prepared.entry.name);
+        let hook_runner = tokio::spawn(async move {
+            let _ = hooks
+                .execute_hooks_with_env(HookEvent::OnCommandFailed, &env, hook_tx)
+                .await;
+        });
+        while let Some(line) = hook_rx.recv().await {
+            events.line(&line).await;
+        }
+        let _ = hook_runner.await;
+    }
+
+    let (result, exit_code, warnings) = match &outcome {
+        CommandOutcome::Success {
+            exit_code,
+            warnings,
+        } => ("success", Some(*exit_code), *warnings),
+        CommandOutcome::Failed { exit_code, .. } => ("failed", *exit_code, 0),
+    };
+    events
+        .send(
+            "done",
+            &json!({
+                "outcome": result,
+                "exit_code": exit_code,
+                "warnings": warnings,
+                "summary": outcome.summary_line().content,
+            }),
+        )
+        .await;
+}
+
+/// Server-sent events over a connection that stops writing once the client is gone
+struct EventStream<'a> {
+    stream: &'a mut TcpStream,
+    open: bool,
+}
+
+impl<'a> EventStream<'a> {
+    fn new(stream: &'a mut TcpStream) -> Self {
+        Self { stream, open: true }
+    }
+
+    async fn send_head(&mut self) {
+        self.write(SSE_HEAD.as_bytes()).await;
+    }
+
+    async fn line(&mut self, line: &OutputLine) {
+        let level = match line.output_type {
+            OutputType::Info => "info",
+            OutputType::Success => "success",
+            OutputType::Warning => "warning",
+            OutputType::Error => "error",
+            OutputType::Hint => "hint",
+        };
+        self.send(
+            "line",
+            &json!({
+                "level": level,
+                "content": line.content,
+                "time": line.timestamp.to_rfc3339(),
+            }),
+        )
+        .await;
+    }
+
+    async fn send(&mut self, event: &str, data: &Value) {
+        self.write(sse_event(event, data).as_bytes()).await;
+    }
+
+    async fn write(&mut self, bytes: &[u8]) {
+        if self.open && self.stream.write_all(bytes).await.is_err() {
+            self.open = false;
+        }
+    }
+}
+
+fn error_response(status: u16, message: &str) -> Vec<u8> {
+    json_response(status, &json!({ "error": message }))
+}
+
+/// Client for the configured kubeconfig and context; None when it can't be built
+async fn k8s_client(config: &Config) -> Option<K8sClient> {
+    let kubeconfig = Some(config.cluster.kubeconfig.as_str()).filter(|s| !s.is_empty());
+    let context = Some(config.cluster.context.as_str()).filter(|s| !s.is_empty());
+    K8sClient::new(kubeconfig, context).await.ok()
+}
@@ -0,0 +1,107 @@
+//! The bearer token the API requires
+//!
+//! Without `api.token`, the server generates one on first use and keeps it in
+//! a file only the user can read, so clients on the same account can pick it
+//! up and other users (or web pages) can't.
+
+use std::io::{Read, Write};
+use std::path::{Path, PathBuf};
+
+use anyhow::{bail, Context, Result};
+
+/// Where the generated token is kept
+pub fn token_path() -> Option<PathBuf> {
+    dirs::data_local_dir().map(|d| d.join("k3dev").join("api-token"))
+}
+
+/// The token in `path`, written there first if the file doesn't exist yet
+pub fn load_or_create(path: &Path) -> Result<String> {
+    match std::fs::symlink_metadata(path) {
+        Ok(metadata) => {
+            check_private(path, &metadata)?;
+            let token = std::fs::read_to_string(path)
+                .with_context(|| format!("Failed to read {}", path.display()))?;
+            let token = token.trim();
+            if token.is_empty() {
+                bail!("{} is empty; delete it to get a new token", path.display());
+            }
+            Ok(token.to_string())
+        }
+        Err(e) if e.kind() == std::io::ErrorKind::NotFound => {
+            if let Some(dir) = path.parent() {
+                std::fs::create_dir_all(dir)
+                    .with_context(|| format!("Failed to create {}", dir.display()))?;
+            }
+            let token = generate()?;
+            let mut options = std::fs::OpenOptions::new();
+            options.write(true).create_new(true);
+            #[cfg(unix)]
+            {
+                use std::os::unix::fs::OpenOptionsExt;
+                options.mode(0o600);
+            }
+            options
+                .open(path)
+                .and_then(|mut file| file.write_all(token.as_bytes()))
+                .with_context(|| format!("Failed to write {}", path.display()))?;
+            Ok(token)
+        }
+        Err(e) => Err(e).with_context(|| format!("Failed to read {}", path.display())),
+    }
+}
+
+/// Refuse a token file others could have planted or read
+fn check_private(path: &Path, metadata: &std::fs::Metadata) -> Result<()> {
+    if !metadata.is_file() {
+        bail!("{} is not a regular file", path.display());
+    }
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::MetadataExt;
+        if metadata.uid() != nix::unistd::getuid().as_raw() {
+            bail!("{} belongs to another user", path.display());
+        }
+        if metadata.mode() & 0o077 != 0 {
+            bail!(
+                "{} can be read by other users; delete it to get a new token",
+                path.display()
+            );
+        }
+    }
+    Ok(())
+}
+
+/// 32 random bytes as hex
+fn generate() -> Result<String> {
+    let mut bytes = [0u8; 32];
+    std::fs::File::open("/dev/urandom")
+        .and_then(|mut random| random.read_exact(&mut bytes))
+        .context("Failed to read /dev/urandom")?;
+    Ok(bytes.iter().map(|b| format!("{:02x}", b)).collect())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn token_is_generated_once_and_kept_private() {
+        let dir = std::env::temp_dir().join(format!("k3dev-api-token-{}", std::process::id()));
+        let path = dir.join("nested").join("api-token");
+        let _ = std::fs::remove_dir_all(&dir);
+
+        let token = load_or_create(&path).unwrap();
+        assert_eq!(token.len(), 64);
+        assert_eq!(load_or_create(&path).unwrap(), token);
+
+        #[cfg(unix)]
+        {
+            use std::os::unix::fs::PermissionsExt;
+            let mode = std::fs::metadata(&path).unwrap().permissions().mode();
+            assert_eq!(mode & 0o777, 0o600);
+            std::fs::set_permissions(&path, std::fs::Permissions::from_mode(0o644)).unwrap();
+            assert!(load_or_create(&path).is_err());
+        }
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
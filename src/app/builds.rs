@@ -0,0 +1,90 @@
+//! Image builds from `builds:`
+//!
+//! A build is a job like any popup command, so it can be cancelled and its
+//! output reopened; while it runs, the build panel shows its stages.
+
+use std::collections::HashMap;
+use std::time::Duration;
+
+use tokio_util::sync::CancellationToken;
+
+use super::jobs::{JobId, JobKind};
+use super::{App, AppMode, PendingForm};
+use crate::cluster::image_build;
+use crate::commands::CommandContext;
+use crate::config::{BuildConfig, InputDefinition, InputSpec};
+use crate::ui::components::OutputLine;
+
+impl App {
+    /// Build the configured image; with several, ask which one
+    pub(super) fn open_build_form(&mut self) {
+        let options: Vec<String> = self.config.builds.iter().map(|b| b.name.clone()).collect();
+        match options.len() {
+            0 => {
+                self.output
+                    .add_error("No builds configured; add them under builds: in the config");
+            }
+            1 => self.start_build(self.config.builds[0].clone()),
+            _ => {
+                let order = vec!["build".to_string()];
+                let inputs: HashMap<String, InputDefinition> = HashMap::from([(
+                    "build".to_string(),
+                    InputDefinition::Detailed(InputSpec::Select {
+                        prompt: "Image:".to_string(),
+                        options,
+                        default: None,
+                    }),
+                )]);
+                self.input_form.setup("Build Image", &inputs, &order);
+                self.pending_form = Some(PendingForm::Build);
+                self.mode = AppMode::Input;
+            }
+        }
+    }
+
+    pub(super) fn submit_build_form(&mut self, values: &HashMap<String, String>) {
+        let build = values
+            .get("build")
+            .and_then(|name| self.config.builds.iter().find(|b| &b.name == name))
+            .cloned();
+        match build {
+            Some(build) => self.start_build(build),
+            None => self.mode = AppMode::Normal,
+        }
+    }
+
+    /// Start a build next to other jobs and show the build panel
+    fn start_build(&mut self, build: BuildConfig) {
+        let title = format!("Build {} ({})", build.name, build.tag);
+        self.build_view.reset(title.clone());
+        self.submit_job(title, JobKind::Build(Box::new(build)), true);
+        self.mode = AppMode::Build;
+    }
+
+    pub(super) fn spawn_build(
+        &mut self,
+        job: JobId,
+        cancel: CancellationToken,
+        build: BuildConfig,
+    ) {
+        self.build_job = Some(job);
+        // Builds take as long as they take; Ctrl+C cancels them
+        let (ctx, tx) = CommandContext::new(self.message_tx.clone(), job, cancel, Duration::MAX);
+        let message_tx = self.message_tx.clone();
+
+        tokio::spawn(async move {
+            ctx.execute(move |_output_tx| async move {
+                image_build::run_build(&build, job, &tx, &message_tx).await
+            })
+            .await;
+        });
+    }
+
+    /// Switch from the build panel to the build's full output
+    pub(super) fn show_build_output(&mut self) {
+        match self.build_job {
+            Some(job) => self.show_job_output(job),
+            None => self.toasts.push(OutputLine::info("No build output yet")),
+        }
+    }
+}
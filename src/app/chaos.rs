@@ -0,0 +1,171 @@
+//! Chaos palette tools
+//!
+//! Cordon, uncordon and the clear tools run as jobs right away; memory
+//! pressure and latency ask for their values first. The pod killer is a job
+//! that runs until toggled off or cancelled.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::Duration;
+
+use anyhow::Context;
+use tokio_util::sync::CancellationToken;
+
+use super::jobs::{JobId, JobKind};
+use super::{App, AppMode, PendingForm};
+use crate::cluster::{parse_memory_limit, ChaosAction, ChaosTool, ClusterManager};
+use crate::commands::CommandContext;
+use crate::config::{InputDefinition, InputSpec};
+use crate::error::K3devError;
+use crate::k8s::pod_killer;
+use crate::ui::components::OutputLine;
+
+/// Memory limit the form suggests
+const DEFAULT_MEMORY_LIMIT: &str = "1Gi";
+/// Delay the form suggests, in milliseconds
+const DEFAULT_DELAY_MS: &str = "200";
+
+fn text_input(prompt: &str, default: &str) -> InputDefinition {
+    InputDefinition::Detailed(InputSpec::Text {
+        prompt: prompt.to_string(),
+        default: default.to_string(),
+        required: true,
+    })
+}
+
+impl App {
+    pub(super) fn run_chaos_tool(&mut self, tool: ChaosTool) {
+        match tool {
+            ChaosTool::Cordon => self.submit_chaos(ChaosAction::Cordon),
+            ChaosTool::Uncordon => self.submit_chaos(ChaosAction::Uncordon),
+            ChaosTool::ClearMemoryPressure => self.submit_chaos(ChaosAction::ClearMemoryLimit),
+            ChaosTool::ClearLatency => self.submit_chaos(ChaosAction::ClearLatency),
+            ChaosTool::PodKiller => self.toggle_pod_killer(),
+            ChaosTool::MemoryPressure => {
+                let order = vec!["limit".to_string()];
+                let inputs = HashMap::from([(
+                    "limit".to_string(),
+                    text_input("Memory limit (e.g. 512Mi):", DEFAULT_MEMORY_LIMIT),
+                )]);
+                self.input_form.setup(tool.label(), &inputs, &order);
+                self.pending_form = Some(PendingForm::MemoryPressure);
+                self.mode = AppMode::Input;
+            }
+            ChaosTool::Latency => {
+                let order = vec![
+                    "namespace".to_string(),
+                    "service".to_string(),
+                    "delay".to_string(),
+                ];
+                let inputs = HashMap::from([
+                    (
+                        "namespace".to_string(),
+                        text_input("Namespace:", &self.config.cluster.default_namespace),
+                    ),
+                    ("service".to_string(), text_input("Service:", "")),
+                    (
+                        "delay".to_string(),
+                        text_input("Delay (ms):", DEFAULT_DELAY_MS),
+                    ),
+                ]);
+                self.input_form.setup(tool.label(), &inputs, &order);
+                self.pending_form = Some(PendingForm::Latency);
+                self.mode = AppMode::Input;
+            }
+        }
+    }
+
+    pub(super) fn submit_memory_pressure_form(&mut self, values: &HashMap<String, String>) {
+        let limit = values.get("limit").map(|v| v.trim()).unwrap_or_default();
+        match parse_memory_limit(limit) {
+            Ok(bytes) => self.submit_chaos(ChaosAction::LimitMemory(bytes)),
+            Err(e) => {
+                self.mode = AppMode::Normal;
+                self.toasts.push(OutputLine::error(format!("{:#}", e)));
+            }
+        }
+    }
+
+    pub(super) fn submit_latency_form(&mut self, values: &HashMap<String, String>) {
+        let value = |key: &str| values.get(key).map(|v| v.trim()).unwrap_or_default();
+        let (namespace, service) = (value("namespace"), value("service"));
+        let delay_ms = value("delay").trim_end_matches("ms").parse::<u32>();
+        match delay_ms {
+            Ok(delay_ms) if delay_ms > 0 && !namespace.is_empty() && !service.is_empty() => self
+                .submit_chaos(ChaosAction::AddLatency {
+                    namespace: namespace.to_string(),
+                    service: service.to_string(),
+                    delay_ms,
+                }),
+            _ => {
+                self.mode = AppMode::Normal;
+                self.toasts.push(OutputLine::error(
+                    "Latency needs a namespace, a service and a delay in milliseconds",
+                ));
+            }
+        }
+    }
+
+    fn submit_chaos(&mut self, action: ChaosAction) {
+        self.submit_job(action.title(), JobKind::Chaos(action), false);
+    }
+
+    pub(super) fn spawn_chaos(
+        &mut self,
+        job: JobId,
+        cancel: CancellationToken,
+        action: ChaosAction,
+    ) {
+        let timeout = self.refresh_config.cluster_operation_timeout;
+        let (ctx, tx) = CommandContext::new(self.message_tx.clone(), job, cancel, timeout);
+        let cluster_config = Arc::clone(&self.cluster_config);
+        let clients = self.clients.clone();
+
+        tokio::spawn(async move {
+            ctx.execute(move |_output_tx| async move {
+                let manager = ClusterManager::for_action(cluster_config, &clients)
+                    .await
+                    .context("Manager error")?;
+                manager.chaos(&action, tx).await
+            })
+            .await;
+        });
+    }
+
+    /// Start the pod killer, or stop the running one
+    fn toggle_pod_killer(&mut self) {
+        let running = self
+            .jobs
+            .active()
+            .find(|j| matches!(j.kind, JobKind::PodKiller))
+            .map(|j| j.id);
+        if let Some(id) = running {
+            self.cancel_job(id);
+            self.toasts.push(OutputLine::info("Pod killer stopped"));
+            return;
+        }
+        if self.config.chaos.pod_killer.is_none() {
+            self.toasts.push(OutputLine::warning(
+                "Set chaos.pod_killer in the config to choose the pods to kill",
+            ));
+            return;
+        }
+        self.submit_job("Pod killer".to_string(), JobKind::PodKiller, true);
+    }
+
+    pub(super) fn spawn_pod_killer(&mut self, job: JobId, cancel: CancellationToken) {
+        let (ctx, tx) = CommandContext::new(self.message_tx.clone(), job, cancel, Duration::MAX);
+        let client = self.k8s_client.clone();
+        let config = self.config.chaos.pod_killer.clone();
+        let default_namespace = self.config.cluster.default_namespace.clone();
+
+        tokio::spawn(async move {
+            ctx.execute(move |_output_tx| async move {
+                let client = client.ok_or(K3devError::ClusterNotRunning)?;
+                let config = config.context("chaos.pod_killer is not set")?;
+                pod_killer::run_pod_killer(&client, &config, &default_namespace, &tx).await
+            })
+            .await;
+        });
+    }
+}
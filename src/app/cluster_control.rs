@@ -0,0 +1,164 @@
+//! Shared cluster control
+//!
+//! Only one k3dev controls a cluster at a time. The others attach read-only:
+//! they show everything but refuse to start, stop or redeploy the cluster,
+//! edit /etc/hosts or touch snapshots, and take control when asked to or
+//! when the instance that had it exits.
+
+use std::time::Duration;
+
+use super::messages::AppMessage;
+use super::App;
+use crate::cluster::instance_lock::{self, Control, HandoverRequest};
+use crate::ui::components::OutputLine;
+
+/// Wait after handing over before looking for the new owner, so it can
+/// bind the socket first
+const REATTACH_DELAY: Duration = Duration::from_secs(1);
+
+impl App {
+    /// Take control of the cluster, or attach to the instance that has it
+    pub(super) async fn claim_control(&mut self) {
+        let result = instance_lock::acquire(&self.cluster_config.container_name)
+            .await
+            .map_err(|e| format!("{:#}", e));
+        self.apply_control(result);
+    }
+
+    /// Act on who controls the cluster now
+    pub(super) fn apply_control(&mut self, result: Result<Control, String>) {
+        if let Some(task) = self.control_task.take() {
+            task.abort();
+        }
+        match result {
+            Ok(Control::Owner(lock)) => {
+                let had_owner = self.control_owner.take();
+                let message_tx = self.message_tx.clone();
+                let mirror = self.output_mirror.clone();
+                self.control_task = Some(tokio::spawn(lock.serve(mirror, move |request| {
+                    let _ = message_tx.try_send(AppMessage::ControlRequested(request));
+                })));
+                if let Some(pid) = had_owner {
+                    self.report(OutputLine::success(format!(
+                        "This instance now controls cluster '{}' (was pid {})",
+                        self.cluster_config.cluster_name(),
+                        pid
+                    )));
+                }
+            }
+            Ok(Control::Attached { pid, watch }) => {
+                self.control_owner = Some(pid);
+                let message_tx = self.message_tx.clone();
+                self.control_task = Some(tokio::spawn(async move {
+                    instance_lock::watch(watch).await;
+                    let _ = message_tx.send(AppMessage::ControlFreed).await;
+                }));
+                let line = OutputLine::warning(format!(
+                    "k3dev (pid {}) controls cluster '{}'; attached read-only. \
+                     Take Over Cluster Control in the palette asks for it.",
+                    pid,
+                    self.cluster_config.cluster_name()
+                ));
+                self.toasts.push(line.clone());
+                self.report(line);
+            }
+            Err(e) => {
+                // Without the lock k3dev behaves as it did before it had one
+                self.control_owner = None;
+                self.report(OutputLine::warning(format!(
+                    "Can't check for other k3dev instances: {}",
+                    e
+                )));
+            }
+        }
+        self.update_on_demand();
+    }
+
+    /// True when this instance may change the cluster; otherwise reports
+    /// who may
+    pub(super) fn check_control(&mut self, action: &str) -> bool {
+        let Some(pid) = self.control_owner else {
+            return true;
+        };
+        self.report(OutputLine::warning(format!(
+            "{}: k3dev (pid {}) controls this cluster. Take Over Cluster Control first.",
+            action, pid
+        )));
+        false
+    }
+
+    /// Ask the controlling instance to hand over
+    pub(super) fn request_control(&mut self) {
+        let Some(pid) = self.control_owner else {
+            self.report(OutputLine::info(
+                "This instance already controls the cluster",
+            ));
+            return;
+        };
+        self.report(OutputLine::info(format!(
+            "Asking k3dev (pid {}) to hand over control...",
+            pid
+        )));
+        let container_name = self.cluster_config.container_name.clone();
+        let message_tx = self.message_tx.clone();
+        tokio::spawn(async move {
+            let message = match instance_lock::request_control(&container_name).await {
+                Ok(true) => AppMessage::ControlFound(
+                    instance_lock::acquire(&container_name)
+                        .await
+                        .map_err(|e| format!("{:#}", e)),
+                ),
+                Ok(false) => AppMessage::OutputLine(OutputLine::warning(format!(
+                    "k3dev (pid {}) is busy with a cluster job; try again when it finishes",
+                    pid
+                ))),
+                Err(e) => AppMessage::OutputLine(OutputLine::error(format!(
+                    "Couldn't take over control: {:#}",
+                    e
+                ))),
+            };
+            let _ = message_tx.send(message).await;
+        });
+    }
+
+    /// Another instance asks for control: hand it over unless a job of ours
+    /// that needs control is still running
+    pub(super) fn handle_control_request(&mut self, request: HandoverRequest) {
+        let busy = self.jobs.active().any(|job| job.kind.needs_control());
+        if busy {
+            let _ = request.reply.send(false);
+            self.report(OutputLine::warning(format!(
+                "k3dev (pid {}) asked for control of the cluster; refused while a cluster job runs",
+                request.pid
+            )));
+            return;
+        }
+        if request.reply.send(true).is_err() {
+            return;
+        }
+        self.control_task = None;
+        self.control_owner = Some(request.pid);
+        self.update_on_demand();
+        let line = OutputLine::warning(format!(
+            "Handed control of the cluster to k3dev (pid {}); this instance is read-only now",
+            request.pid
+        ));
+        self.toasts.push(line.clone());
+        self.report(line);
+        self.find_control_owner(REATTACH_DELAY);
+    }
+
+    /// Look for whoever controls the cluster after `delay`, taking control
+    /// if nobody does
+    pub(super) fn find_control_owner(&mut self, delay: Duration) {
+        let container_name = self.cluster_config.container_name.clone();
+        let message_tx = self.message_tx.clone();
+        tokio::spawn(async move {
+            tokio::time::sleep(delay).await;
+            let result = instance_lock::acquire(&container_name)
+                .await
+                .map_err(|e| format!("{:#}", e));
+            let _ = message_tx.send(AppMessage::ControlFound(result)).await;
+        });
+    }
+}
@@ -3,32 +3,50 @@
 //! This module contains command execution functions including cluster actions,
 //! pod commands, and palette command handling.
 
+use anyhow::Context;
 use std::collections::{HashMap, HashSet};
 use std::path::PathBuf;
 use std::sync::Arc;
+use std::time::Duration;
+use tokio::sync::mpsc;
 use tokio_util::sync::CancellationToken;
 
+use crate::cluster::degraded::FailingComponent;
 use crate::cluster::diagnostics::{run_all_diagnostics, run_preflight_checks};
-use crate::cluster::DockerManager;
-use crate::cluster::{ClusterManager, HostsUpdateResult, IngressManager};
-use crate::commands::{CommandContext, PaletteCommandId};
+use crate::cluster::k3s_versions::ReleaseIndex;
+use crate::cluster::{
+    hosts_file, server_logs, ClusterComponent, ClusterInput, ClusterManager, ClusterState,
+    HostsUpdateResult, IngressManager, K3sManager, PvDirectory,
+};
+use crate::commands::{
+    run_docker_command, run_host_command, run_lua_command, run_script_command, CommandContext,
+    CommandOutcome, OutputClassifier, PaletteCommandId,
+};
 use crate::config::{
-    get_exec_placeholders, CommandEntry, ExecutionTarget, InputDefinition, RefreshTask,
+    find_command_by_path, get_exec_placeholders, substitute_exec_placeholders,
+    write_infrastructure_value, Concurrency, ConfigLoader, ExecutionTarget, HookEvent,
+    InputDefinition, RefreshTask, StarterConfig,
 };
-use crate::k8s::PodExecutor;
-use crate::ui::components::{ClusterAction, DetailTab};
+use crate::crash;
+use crate::error::K3devError;
+use crate::hooks::HookExecutor;
+use crate::k8s::{manifest_diff, CanIQuery, PodExecutor};
+use crate::keybindings::{KeyAction, KeyContext};
+use crate::ui::components::{ClusterAction, DetailTab, OutputLine, PodSort};
 
-use super::{App, AppMessage, AppMode, FocusArea};
+use super::jobs::{JobId, JobKind};
+use super::{App, AppMessage, AppMode, FocusArea, PendingForm};
 
 impl App {
-    /// Open a URL in the default browser
+    /// Open a URL in the configured browser
     pub(super) fn open_url(&mut self, url: &str) {
-        match open::that(url) {
+        match crate::browser::open_url(&self.config.browser, url) {
             Ok(_) => {
                 self.output.add_info(format!("Opening: {}", url));
             }
             Err(e) => {
-                self.output.add_error(format!("Failed to open URL: {}", e));
+                self.output
+                    .add_error(format!("Failed to open URL: {:#}", e));
             }
         }
     }
@@ -50,25 +68,71 @@ impl App {
         match cmd_id {
             PaletteCommandId::AppRefresh => {
                 self.spawn_status_check();
-                self.spawn_ingress_refresh();
-                self.spawn_missing_hosts_check();
-                self.spawn_port_forwards_check();
-                // Reset scheduler timers for tasks we just triggered
                 self.scheduler
-                    .mark_run_multiple(&[RefreshTask::IngressRefresh, RefreshTask::HostsCheck]);
+                    .request_multiple(&[RefreshTask::IngressRefresh, RefreshTask::HostsCheck]);
             }
             PaletteCommandId::AppUpdateHosts => self.trigger_manual_hosts_update(),
-            PaletteCommandId::AppHelp => self.mode = AppMode::Help,
+            PaletteCommandId::AppRestoreHosts => self.submit_job(
+                "Restoring /etc/hosts".to_string(),
+                JobKind::HostsRestore,
+                false,
+            ),
+            PaletteCommandId::ClusterTakeControl => self.request_control(),
+            PaletteCommandId::ClusterSharePort => self.open_share_form(),
+            PaletteCommandId::ClusterStopSharing => self.stop_sharing(),
+            PaletteCommandId::ClusterOpenTunnel => self.open_tunnel_form(),
+            PaletteCommandId::ClusterCloseTunnels => {
+                if self.tunnels.is_empty() {
+                    self.toasts.push(OutputLine::info("No tunnels are open"));
+                }
+                self.close_tunnels();
+            }
+            PaletteCommandId::ClusterEditResource => self.open_resource_edit_form(),
+            PaletteCommandId::ClusterServices => self.open_services(),
+            PaletteCommandId::ClusterNetworkPolicies => self.open_network_policies(),
+            PaletteCommandId::ClusterDependencyMap => self.open_dependency_map(),
+            PaletteCommandId::ClusterRbac => self.open_rbac(),
+            PaletteCommandId::ClusterCreateNamespace => self.open_create_namespace_form(),
+            PaletteCommandId::ClusterDeleteNamespace => self.open_delete_namespace_form(),
+            PaletteCommandId::ClusterInstallMesh => self.run_mesh_command(true),
+            PaletteCommandId::ClusterUninstallMesh => self.run_mesh_command(false),
+            PaletteCommandId::ClusterInstallPolicyEngine => self.run_policy_engine_command(true),
+            PaletteCommandId::ClusterUninstallPolicyEngine => self.run_policy_engine_command(false),
+            PaletteCommandId::ClusterPolicyViolations => self.open_policy_violations(),
+            PaletteCommandId::ClusterQuotas => self.open_quotas(),
+            PaletteCommandId::ClusterRuntime => self.open_runtime(),
+            PaletteCommandId::ClusterPvBrowser => self.open_pv_browser(),
+            PaletteCommandId::ClusterSnapshots => self.open_snapshots(),
+            PaletteCommandId::ClusterK3sVersion => self.open_k3s_versions(),
+            PaletteCommandId::ClusterBuildImage => self.open_build_form(),
+            PaletteCommandId::ClusterPullSecret => self.open_pull_secret_form(),
+            PaletteCommandId::ClusterRedeploy(component) => self.submit_job(
+                component.action_label().to_string(),
+                JobKind::Redeploy(component),
+                false,
+            ),
+            PaletteCommandId::LoadSeed(name) => self.load_seed(&name),
+            PaletteCommandId::Chaos(tool) => self.run_chaos_tool(tool),
+            PaletteCommandId::ClusterDiffManifests => {
+                self.submit_job("Diff Manifests".to_string(), JobKind::ManifestDiff, true)
+            }
+            PaletteCommandId::AppEditConfig => self.edit_config(),
+            PaletteCommandId::AppHelp => self.open_help(KeyContext::Normal),
+            PaletteCommandId::AppNotifications => self.show_notification_history(),
+            PaletteCommandId::AppRefreshTasks => self.mode = AppMode::RefreshTasks,
+            PaletteCommandId::AppHookRuns => self.mode = AppMode::HookRuns,
             PaletteCommandId::AppQuit => self.should_quit = true,
             PaletteCommandId::NavFocusMenu => self.focus = FocusArea::Content,
             PaletteCommandId::NavFocusActions => self.focus = FocusArea::ActionBar,
+            PaletteCommandId::NavPodsByCpu => self.focus_pods_sorted(PodSort::Cpu),
+            PaletteCommandId::NavPodsByMemory => self.focus_pods_sorted(PodSort::Memory),
             _ => {}
         }
     }
 
     /// Execute a custom command by path (e.g., "Group Name/Command Name")
     pub(super) fn execute_custom_command(&mut self, path: &str) {
-        if let Some(cmd) = self.find_command_by_path(path) {
+        if let Some(cmd) = find_command_by_path(&self.config.commands, path).cloned() {
             self.execute_command(cmd);
         } else {
             self.output
@@ -76,41 +140,6 @@ impl App {
         }
     }
 
-    /// Find a command by its path (e.g., "Group Name/Command Name" or "Group/Subgroup/Command")
-    fn find_command_by_path(&self, path: &str) -> Option<CommandEntry> {
-        let parts: Vec<&str> = path.split('/').collect();
-        if parts.is_empty() {
-            return None;
-        }
-
-        // Find the group
-        let group_name = parts[0].trim();
-        let group = self
-            .config
-            .commands
-            .iter()
-            .find(|g| g.name.eq_ignore_ascii_case(group_name))?;
-
-        // Navigate through the remaining path parts
-        let mut commands = &group.commands;
-        for (i, part) in parts[1..].iter().enumerate() {
-            let part = part.trim();
-            let is_last = i == parts.len() - 2;
-
-            let found = commands
-                .iter()
-                .find(|c| c.name.eq_ignore_ascii_case(part))?;
-
-            if is_last {
-                return Some(found.clone());
-            } else {
-                commands = &found.commands;
-            }
-        }
-
-        None
-    }
-
     pub(super) fn execute_cluster_action(&mut self, action: ClusterAction) {
         // Diagnostics and preflight use the diagnostics overlay, not the output popup
         if action == ClusterAction::Diagnostics {
@@ -121,6 +150,15 @@ impl App {
             self.run_preflight_check();
             return;
         }
+        if action == ClusterAction::Logs {
+            self.follow_server_logs();
+            return;
+        }
+        // Info has its own live view
+        if action == ClusterAction::Info {
+            self.open_cluster_info();
+            return;
+        }
 
         // Show confirmation for destroy action
         if action == ClusterAction::Destroy {
@@ -129,6 +167,8 @@ impl App {
                 "Destroy Cluster",
                 "This will permanently destroy the cluster. This cannot be undone.",
             );
+            self.confirm_popup
+                .set_alternative("e", "Export volumes, then destroy");
             self.mode = AppMode::ConfirmDestroy;
             return;
         }
@@ -149,58 +189,210 @@ impl App {
 
     /// Handle confirmation for destroy action
     pub(super) fn confirm_destroy(&mut self) {
+        if let Some(name) = self.pending_namespace_delete.take() {
+            self.delete_namespace(name, false);
+            return;
+        }
+        if let Some(dir) = self.pending_pv_wipe.take() {
+            self.mode = AppMode::PvBrowser;
+            self.wipe_pv_directory(dir);
+            return;
+        }
+        if let Some(image) = self.pending_snapshot_start.take() {
+            self.mode = AppMode::Normal;
+            self.start_from_snapshot(image);
+            return;
+        }
         if let Some(action) = self.pending_cluster_action.take() {
             self.mode = AppMode::Normal;
             self.do_execute_cluster_action(action);
         }
     }
 
+    /// Destroy the cluster after saving its data volumes to the working directory
+    pub(super) fn confirm_destroy_with_export(&mut self) {
+        if self.pending_cluster_action != Some(ClusterAction::Destroy) {
+            return;
+        }
+        self.pending_cluster_action = None;
+        self.mode = AppMode::Normal;
+        let dest = crate::cluster::default_archive_path(self.cluster_config.cluster_name());
+        let dest = std::path::absolute(&dest).unwrap_or(dest);
+        self.submit_job(
+            "Cluster export and destroy".to_string(),
+            JobKind::ExportAndDestroy(dest),
+            false,
+        );
+    }
+
     /// Cancel destroy confirmation
     pub(super) fn cancel_destroy(&mut self) {
         self.pending_cluster_action = None;
         self.mode = AppMode::Normal;
+        if self.pending_namespace_delete.take().is_some() {
+            self.output.add_info("Namespace deletion cancelled");
+            return;
+        }
+        if self.pending_pv_wipe.take().is_some() {
+            self.mode = AppMode::PvBrowser;
+            return;
+        }
+        if self.pending_snapshot_start.take().is_some() {
+            self.mode = AppMode::Snapshots;
+            return;
+        }
         self.output.add_info("Destroy cancelled");
     }
 
     pub(super) fn do_execute_cluster_action(&mut self, action: ClusterAction) {
-        self.output.clear();
-        self.output
-            .set_title(format!("Cluster {}", action.as_str()));
-        self.output_popup.clear();
-        self.output_popup
-            .set_title(format!("Cluster {}", action.as_str()));
-        self.is_executing = true;
-        // Show output popup immediately when command starts
-        self.mode = super::AppMode::OutputPopup;
+        self.submit_job(
+            format!("Cluster {}", action.as_str()),
+            JobKind::Cluster(action),
+            false,
+        );
+    }
 
-        let cancel_token = CancellationToken::new();
-        self.cancel_token = Some(cancel_token.clone());
+    /// Start a queued job once [`Jobs::take_startable`](super::jobs::Jobs::take_startable) lets it run
+    pub(super) fn spawn_job(&mut self, job: JobId) {
+        let Some((kind, cancel)) = self
+            .jobs
+            .get(job)
+            .map(|j| (j.kind.clone(), j.cancel.clone()))
+        else {
+            return;
+        };
+        match kind {
+            JobKind::Cluster(action) => self.spawn_cluster_action(job, cancel, action),
+            JobKind::Redeploy(component) => self.spawn_redeploy(job, cancel, component),
+            JobKind::Command(cmd) => match cmd.exec.as_ref().map(|e| &e.target) {
+                Some(ExecutionTarget::Host) => self.execute_host_command(job, cancel, &cmd),
+                Some(ExecutionTarget::Script) => self.execute_script_command(job, cancel, &cmd),
+                Some(ExecutionTarget::Lua) => self.execute_lua_command(job, cancel, &cmd),
+                Some(ExecutionTarget::Docker { .. }) => {
+                    self.execute_docker_command(job, cancel, &cmd)
+                }
+                // Pod commands run in the shell tab and never become jobs
+                Some(ExecutionTarget::Kubernetes { .. }) | None => {}
+            },
+            JobKind::HostsUpdate => self.spawn_hosts_update(job, cancel),
+            JobKind::HostsRestore => self.spawn_hosts_restore(job, cancel),
+            JobKind::ManifestDiff => self.spawn_manifest_diff(job, cancel),
+            JobKind::ServerLogs => self.spawn_server_logs(job, cancel),
+            JobKind::Build(build) => self.spawn_build(job, cancel, *build),
+            JobKind::PullSecret(namespaces) => self.spawn_pull_secret(job, cancel, namespaces),
+            JobKind::Seed(seed) => self.spawn_seed(job, cancel, *seed),
+            JobKind::Chaos(action) => self.spawn_chaos(job, cancel, action),
+            JobKind::PodKiller => self.spawn_pod_killer(job, cancel),
+            JobKind::SaveSnapshot(name) => self.spawn_save_snapshot(job, cancel, name),
+            JobKind::StartFromSnapshot(image) => self.spawn_start_from_snapshot(job, cancel, image),
+            JobKind::ExportAndDestroy(dest) => self.spawn_export_and_destroy(job, cancel, dest),
+        }
+    }
+
+    fn spawn_redeploy(
+        &mut self,
+        job: JobId,
+        cancel: CancellationToken,
+        component: ClusterComponent,
+    ) {
+        let timeout_duration = self.refresh_config.cluster_operation_timeout;
+        let (ctx, tx) = CommandContext::new(self.message_tx.clone(), job, cancel, timeout_duration);
+
+        let cluster_config = Arc::clone(&self.cluster_config);
+        let clients = self.clients.clone();
+
+        tokio::spawn(async move {
+            ctx.execute(move |_output_tx| async move {
+                let mut manager = ClusterManager::for_action(cluster_config, &clients)
+                    .await
+                    .context("Manager error")?;
+                let result = manager.redeploy(component, tx).await;
+                if component == ClusterComponent::Kubeconfig {
+                    clients.reset_kube().await;
+                }
+                result
+            })
+            .await;
+        });
+    }
 
+    fn spawn_cluster_action(
+        &mut self,
+        job: JobId,
+        cancel: CancellationToken,
+        action: ClusterAction,
+    ) {
+        let on_demand = match action {
+            ClusterAction::Start | ClusterAction::Restart => self.begin_start(),
+            ClusterAction::Stop | ClusterAction::Destroy => {
+                self.advance_lifecycle(ClusterInput::StopBegun);
+                None
+            }
+            _ => None,
+        };
         let timeout_duration = self.refresh_config.cluster_operation_timeout;
-        let (ctx, tx) = CommandContext::new(self.message_tx.clone(), timeout_duration);
+        let (ctx, tx) = CommandContext::new(self.message_tx.clone(), job, cancel, timeout_duration);
 
         let cluster_config = Arc::clone(&self.cluster_config);
+        let clients = self.clients.clone();
 
         tokio::spawn(async move {
+            // The container can't publish ports 80/443 while k3dev holds them
+            if let Some(listener) = on_demand {
+                let _ = listener.await;
+            }
             ctx.execute(move |_output_tx| async move {
-                let mut manager = ClusterManager::new(cluster_config)
+                let mut manager = ClusterManager::for_action(cluster_config, &clients)
                     .await
-                    .map_err(|e| format!("Manager error: {}", e))?;
+                    .context("Manager error")?;
 
                 let action_result = match action {
                     ClusterAction::Start => manager.start(tx).await,
                     ClusterAction::Stop => manager.stop(tx).await,
                     ClusterAction::Restart => manager.restart(tx).await,
                     ClusterAction::Destroy => manager.delete(tx).await,
-                    ClusterAction::Info => manager.info(tx).await,
                     ClusterAction::DeleteSnapshots => manager.delete_snapshots(tx).await,
-                    // Diagnostics and PreflightCheck are handled before reaching here
-                    ClusterAction::Diagnostics | ClusterAction::PreflightCheck => {
+                    // Info, Diagnostics and PreflightCheck are handled before reaching here
+                    ClusterAction::Info
+                    | ClusterAction::Logs
+                    | ClusterAction::Diagnostics
+                    | ClusterAction::PreflightCheck => {
                         unreachable!()
                     }
                 };
 
-                action_result.map_err(|e| format!("Error: {}", e))
+                // The kubeconfig may point at a new API server now
+                clients.reset_kube().await;
+                action_result
+            })
+            .await;
+        });
+    }
+
+    fn spawn_export_and_destroy(&mut self, job: JobId, cancel: CancellationToken, dest: PathBuf) {
+        self.advance_lifecycle(ClusterInput::StopBegun);
+        let timeout_duration = self.refresh_config.cluster_operation_timeout;
+        let (ctx, tx) = CommandContext::new(self.message_tx.clone(), job, cancel, timeout_duration);
+
+        let cluster_config = Arc::clone(&self.cluster_config);
+        let clients = self.clients.clone();
+
+        tokio::spawn(async move {
+            ctx.execute(move |_output_tx| async move {
+                let mut manager = ClusterManager::for_action(cluster_config, &clients)
+                    .await
+                    .context("Manager error")?;
+                let result = manager.export_and_delete(&dest, tx.clone()).await;
+                if result.is_ok() {
+                    let _ = tx
+                        .send(OutputLine::success(format!(
+                            "Volumes saved to {}",
+                            dest.display()
+                        )))
+                        .await;
+                }
+                clients.reset_kube().await;
+                result
             })
             .await;
         });
@@ -238,6 +430,24 @@ impl App {
 
     pub(super) fn submit_input(&mut self) {
         let values = self.input_form.get_values();
+        if let Some(form) = self.pending_form.take() {
+            self.input_form.clear();
+            match form {
+                PendingForm::ResourceEdit => self.submit_resource_edit_form(&values),
+                PendingForm::CreateNamespace => self.submit_create_namespace_form(&values),
+                PendingForm::DeleteNamespace => self.submit_delete_namespace_form(&values),
+                PendingForm::ImageOverride => self.submit_image_override_form(&values),
+                PendingForm::HostMount => self.submit_host_mount_form(&values),
+                PendingForm::Build => self.submit_build_form(&values),
+                PendingForm::PullSecret => self.submit_pull_secret_form(&values),
+                PendingForm::SaveSnapshot => self.submit_save_snapshot_form(&values),
+                PendingForm::MemoryPressure => self.submit_memory_pressure_form(&values),
+                PendingForm::Latency => self.submit_latency_form(&values),
+                PendingForm::Share => self.submit_share_form(&values),
+                PendingForm::Tunnel => self.submit_tunnel_form(&values),
+            }
+            return;
+        }
         let cmd = match self.pending_command.take() {
             Some(c) => c,
             None => return,
@@ -249,29 +459,7 @@ impl App {
         // Substitute placeholders
         let mut cmd = cmd.clone();
         if let Some(exec) = &mut cmd.exec {
-            let subst = |s: &mut String| {
-                for (key, value) in &values {
-                    let pattern = format!("@{}", key);
-                    *s = s.replace(&pattern, value);
-                }
-            };
-            match &mut exec.target {
-                ExecutionTarget::Host => {}
-                ExecutionTarget::Docker { container } => subst(container),
-                ExecutionTarget::Kubernetes {
-                    namespace,
-                    selector,
-                    pod_name,
-                    container,
-                } => {
-                    subst(namespace);
-                    subst(selector);
-                    subst(pod_name);
-                    subst(container);
-                }
-            }
-            subst(&mut exec.workdir);
-            subst(&mut exec.cmd);
+            substitute_exec_placeholders(exec, &values);
         }
 
         self.dispatch_command(&cmd);
@@ -284,11 +472,23 @@ impl App {
             None => return,
         };
 
-        match &exec.target {
-            ExecutionTarget::Kubernetes { .. } => self.execute_pod_command(cmd),
-            ExecutionTarget::Host => self.execute_host_command(cmd),
-            ExecutionTarget::Docker { .. } => self.execute_docker_command(cmd),
-        }
+        let title = match &exec.target {
+            ExecutionTarget::Kubernetes { .. } => {
+                self.execute_pod_command(cmd);
+                return;
+            }
+            ExecutionTarget::Host => format!("Host: {}", cmd.name),
+            ExecutionTarget::Script => format!("Script: {}", cmd.name),
+            ExecutionTarget::Lua => format!("Lua: {}", cmd.name),
+            ExecutionTarget::Docker { container } => {
+                format!("Docker [{}]: {}", container, cmd.name)
+            }
+        };
+        self.submit_job(
+            title,
+            JobKind::Command(Box::new(cmd.clone())),
+            cmd.concurrency == Concurrency::Parallel,
+        );
     }
 
     fn execute_pod_command(&mut self, cmd: &crate::config::CommandEntry) {
@@ -359,28 +559,101 @@ impl App {
     }
 
     /// Run a command on the user's host shell, streaming output to the popup.
-    fn execute_host_command(&mut self, cmd: &crate::config::CommandEntry) {
+    fn execute_host_command(
+        &mut self,
+        job: JobId,
+        cancel: CancellationToken,
+        cmd: &crate::config::CommandEntry,
+    ) {
         let exec = match &cmd.exec {
             Some(e) => e,
             None => return,
         };
         let command = exec.cmd.clone();
         let workdir = exec.workdir.clone();
-        let title = format!("Host: {}", cmd.name);
+        let name = cmd.name.clone();
+        let classifier = OutputClassifier::new(&cmd.success);
 
-        self.start_popup_command(title);
         let timeout_duration = self.refresh_config.cluster_operation_timeout;
-        let (ctx, output_tx) = CommandContext::new(self.message_tx.clone(), timeout_duration);
+        let (ctx, output_tx) =
+            CommandContext::new(self.message_tx.clone(), job, cancel, timeout_duration);
 
         tokio::spawn(async move {
-            ctx.execute(move |tx| async move { run_host_command(&command, &workdir, tx).await })
-                .await;
+            ctx.execute_classified(name, classifier, move |tx| async move {
+                run_host_command(&command, &workdir, tx).await
+            })
+            .await;
+            drop(output_tx);
+        });
+    }
+
+    /// Run a script on the host, answering its API calls and streaming output to the popup.
+    fn execute_script_command(
+        &mut self,
+        job: JobId,
+        cancel: CancellationToken,
+        cmd: &crate::config::CommandEntry,
+    ) {
+        let exec = match &cmd.exec {
+            Some(e) => e,
+            None => return,
+        };
+        let command = exec.cmd.clone();
+        let workdir = exec.workdir.clone();
+        let name = cmd.name.clone();
+        let classifier = OutputClassifier::new(&cmd.success);
+        let k8s_client = self.k8s_client.clone();
+
+        let timeout_duration = self.refresh_config.cluster_operation_timeout;
+        let (ctx, output_tx) =
+            CommandContext::new(self.message_tx.clone(), job, cancel, timeout_duration);
+
+        tokio::spawn(async move {
+            ctx.execute_classified(name, classifier, move |tx| async move {
+                run_script_command(&command, &workdir, k8s_client.as_ref(), tx).await
+            })
+            .await;
+            drop(output_tx);
+        });
+    }
+
+    /// Run a Lua script in the sandbox, streaming output to the popup.
+    fn execute_lua_command(
+        &mut self,
+        job: JobId,
+        cancel: CancellationToken,
+        cmd: &crate::config::CommandEntry,
+    ) {
+        let exec = match &cmd.exec {
+            Some(e) => e,
+            None => return,
+        };
+        let command = exec.cmd.clone();
+        let workdir = exec.workdir.clone();
+        let name = cmd.name.clone();
+        let classifier = OutputClassifier::new(&cmd.success);
+        let k8s_client = self.k8s_client.clone();
+
+        let timeout_duration = self.refresh_config.cluster_operation_timeout;
+        let (ctx, output_tx) =
+            CommandContext::new(self.message_tx.clone(), job, cancel, timeout_duration);
+
+        tokio::spawn(async move {
+            ctx.execute_classified(name, classifier, move |tx| async move {
+                run_lua_command(&command, &workdir, k8s_client.as_ref(), tx).await
+            })
+            .await;
             drop(output_tx);
         });
     }
 
     /// Run a command in a docker container via `docker exec`, streaming output to the popup.
-    fn execute_docker_command(&mut self, cmd: &crate::config::CommandEntry) {
+    fn execute_docker_command(
+        &mut self,
+        job: JobId,
+        cancel: CancellationToken,
+        cmd: &crate::config::CommandEntry,
+    ) {
         let exec = match &cmd.exec {
             Some(e) => e,
             None => return,
@@ -391,14 +664,15 @@ impl App {
         };
         let command = exec.cmd.clone();
         let workdir = exec.workdir.clone();
-        let title = format!("Docker [{}]: {}", container, cmd.name);
+        let name = cmd.name.clone();
+        let classifier = OutputClassifier::new(&cmd.success);
 
-        self.start_popup_command(title);
         let timeout_duration = self.refresh_config.cluster_operation_timeout;
-        let (ctx, output_tx) = CommandContext::new(self.message_tx.clone(), timeout_duration);
+        let (ctx, output_tx) =
+            CommandContext::new(self.message_tx.clone(), job, cancel, timeout_duration);
 
         tokio::spawn(async move {
-            ctx.execute(move |tx| async move {
+            ctx.execute_classified(name, classifier, move |tx| async move {
                 run_docker_command(&container, &command, &workdir, tx).await
             })
             .await;
@@ -406,39 +680,141 @@ impl App {
         });
     }
 
-    fn start_popup_command(&mut self, title: String) {
-        self.output.clear();
-        self.output.set_title(title.clone());
-        self.output_popup.clear();
-        self.output_popup.set_title(title);
-        self.is_executing = true;
-        self.mode = super::AppMode::OutputPopup;
+    /// Run `on_command_failed` hooks, streaming their output to the failed job
+    pub(super) fn run_command_failed_hooks(
+        &mut self,
+        job: JobId,
+        command: &str,
+        outcome: &CommandOutcome,
+    ) {
+        if self.config.hooks.on_command_failed.is_empty() {
+            return;
+        }
+        let env = outcome.hook_env(command);
+        let executor = HookExecutor::new(self.config.hooks.clone());
 
-        let cancel_token = CancellationToken::new();
-        self.cancel_token = Some(cancel_token);
+        let (output_tx, mut output_rx) = mpsc::channel::<OutputLine>(100);
+        let message_tx = self.message_tx.clone();
+        tokio::spawn(async move {
+            while let Some(line) = output_rx.recv().await {
+                let _ = message_tx.send(AppMessage::RunOutput { job, line }).await;
+            }
+        });
+        tokio::spawn(async move {
+            let _ = executor
+                .execute_hooks_with_env(HookEvent::OnCommandFailed, &env, output_tx)
+                .await;
+        });
     }
 
-    pub(super) fn trigger_manual_hosts_update(&mut self) {
-        self.output.clear();
-        self.output.set_title("Updating /etc/hosts".to_string());
-        self.output_popup.clear();
+    /// Run `on_cluster_degraded` hooks, their output going to the main output
+    pub(super) fn run_cluster_degraded_hooks(&mut self, reason: &str) {
+        if self.config.hooks.on_cluster_degraded.is_empty() {
+            return;
+        }
+        let env = HashMap::from([
+            (
+                "K3DEV_CLUSTER_STATE".to_string(),
+                ClusterState::Degraded.label().to_string(),
+            ),
+            ("K3DEV_DEGRADED_REASON".to_string(), reason.to_string()),
+        ]);
+        let executor = HookExecutor::new(self.config.hooks.clone());
+
+        let (output_tx, mut output_rx) = mpsc::channel::<OutputLine>(100);
+        let message_tx = self.message_tx.clone();
+        tokio::spawn(async move {
+            while let Some(line) = output_rx.recv().await {
+                let _ = message_tx.send(AppMessage::OutputLine(line)).await;
+            }
+        });
+        tokio::spawn(async move {
+            let _ = executor
+                .execute_hooks_with_env(HookEvent::OnClusterDegraded, &env, output_tx)
+                .await;
+        });
+    }
+
+    /// Write the config collected by the setup wizard, load it, and
+    /// optionally start the cluster.
+    pub(super) fn finish_setup(&mut self, starter: StarterConfig, path: PathBuf, start: bool) {
+        if let Err(e) = starter.write(&path) {
+            self.setup_wizard.set_error(e.to_string());
+            return;
+        }
+        self.mode = AppMode::Normal;
+
+        let path_str = path.to_string_lossy().to_string();
+        match ConfigLoader::new(Some(&path_str)).load_with_path() {
+            Ok((config, config_path)) => {
+                self.apply_config(config, config_path);
+                self.output
+                    .add_success(format!("Wrote config to {}", path.display()));
+            }
+            Err(e) => {
+                self.output
+                    .add_error(format!("Failed to load new config: {:#}", e));
+                return;
+            }
+        }
+
+        if start {
+            self.execute_cluster_action(ClusterAction::Start);
+        }
+    }
+
+    /// Edit the active config file; without one, start the setup wizard instead.
+    fn edit_config(&mut self) {
+        if self.config_file_path.is_some() {
+            self.pending_config_edit = true;
+        } else {
+            self.setup_wizard
+                .open(ConfigLoader::new(None).setup_target_path());
+            self.mode = AppMode::SetupWizard;
+            self.load_k3s_versions();
+        }
+    }
+
+    /// Show past toast notifications in the output popup
+    fn show_notification_history(&mut self) {
+        let lines: Vec<OutputLine> = self.toasts.history().cloned().collect();
+
+        self.clear_output_popup();
         self.output_popup
-            .set_title("Updating /etc/hosts".to_string());
-        self.is_executing = true;
-        self.mode = super::AppMode::OutputPopup;
+            .set_title("Notification History".to_string());
+        if lines.is_empty() {
+            self.output_popup
+                .add_line(OutputLine::info("No notifications yet"));
+        }
+        for line in lines {
+            self.output_popup.add_line(line);
+        }
+        self.output_popup.scroll_to_bottom();
+        self.mode = AppMode::OutputPopup;
+    }
 
+    pub(super) fn trigger_manual_hosts_update(&mut self) {
+        self.submit_job(
+            "Updating /etc/hosts".to_string(),
+            JobKind::HostsUpdate,
+            false,
+        );
+    }
+
+    fn spawn_hosts_update(&mut self, job: JobId, cancel: CancellationToken) {
         let timeout = self.refresh_config.manual_hosts_timeout;
-        let (ctx, tx) = CommandContext::new(self.message_tx.clone(), timeout);
+        let (ctx, tx) = CommandContext::new(self.message_tx.clone(), job, cancel, timeout);
         let message_tx = self.message_tx.clone();
         let domain = self.cluster_config.domain.clone();
+        let cluster = self.cluster_config.cluster_name().to_string();
 
         tokio::spawn(async move {
             ctx.execute(move |_output_tx| async move {
-                let mut ingress_manager = IngressManager::with_domain(domain);
+                let mut ingress_manager = IngressManager::with_domain(domain).for_cluster(cluster);
                 let result = ingress_manager
                     .update_hosts(Some(tx))
                     .await
-                    .map_err(|e| format!("Failed to update /etc/hosts: {}", e))?;
+                    .context("Failed to update /etc/hosts")?;
 
                 // If sudo is needed, send message back to main thread for interactive handling
                 if let HostsUpdateResult::NeedsSudo { content, count } = result {
@@ -453,6 +829,83 @@ impl App {
         });
     }
 
+    fn spawn_hosts_restore(&mut self, job: JobId, cancel: CancellationToken) {
+        let timeout = self.refresh_config.manual_hosts_timeout;
+        let (ctx, tx) = CommandContext::new(self.message_tx.clone(), job, cancel, timeout);
+        let message_tx = self.message_tx.clone();
+
+        tokio::spawn(async move {
+            ctx.execute(move |_output_tx| async move {
+                let result = hosts_file::restore_latest_backup(Some(tx))
+                    .await
+                    .context("Failed to restore /etc/hosts")?;
+
+                if let HostsUpdateResult::NeedsSudo { content, count } = result {
+                    let _ = message_tx
+                        .send(AppMessage::NeedsSudoHostsWrite { content, count })
+                        .await;
+                }
+
+                Ok(())
+            })
+            .await;
+        });
+    }
+
+    fn spawn_manifest_diff(&mut self, job: JobId, cancel: CancellationToken) {
+        let timeout = self.refresh_config.cluster_operation_timeout;
+        let (ctx, tx) = CommandContext::new(self.message_tx.clone(), job, cancel, timeout);
+        let client = self.k8s_client.as_ref().map(|c| c.client().clone());
+        let dirs = self.config.manifest_dirs();
+        let namespace = self.config.cluster.default_namespace.clone();
+
+        tokio::spawn(async move {
+            ctx.execute(move |_output_tx| async move {
+                let client = client.ok_or(K3devError::ClusterNotRunning)?;
+                manifest_diff::diff_manifests(&client, &dirs, &namespace, &tx)
+                    .await
+                    .map(|_| ())
+                    .context("Diff failed")
+            })
+            .await;
+        });
+    }
+
+    /// Follow the k3s container's logs in the output popup; it runs next to
+    /// other jobs until cancelled
+    pub(super) fn follow_server_logs(&mut self) {
+        self.submit_job(
+            format!("{} logs", self.cluster_config.container_name),
+            JobKind::ServerLogs,
+            true,
+        );
+    }
+
+    fn spawn_server_logs(&mut self, job: JobId, cancel: CancellationToken) {
+        let (ctx, tx) = CommandContext::new(self.message_tx.clone(), job, cancel, Duration::MAX);
+        let container = self.cluster_config.container_name.clone();
+
+        tokio::spawn(async move {
+            ctx.execute(move |_output_tx| async move {
+                server_logs::follow_server_logs(&container, &tx).await
+            })
+            .await;
+        });
+    }
+
+    /// Write the panic the crash popup shows as an issue body
+    pub(super) fn write_crash_report(&mut self) {
+        let Some(report) = self.crash_view.current() else {
+            return;
+        };
+        let path = crash::default_report_path(report);
+        let result = report
+            .write(&path)
+            .map(|_| path)
+            .map_err(|e| format!("{:#}", e));
+        self.crash_view.set_written(result);
+    }
+
     /// Run cluster diagnostics
     pub(super) fn run_diagnostics(&mut self) {
         self.diagnostics_overlay.reset();
@@ -466,6 +919,465 @@ impl App {
         });
     }
 
+    /// Open the cluster info view; it refreshes itself while open
+    pub(super) fn open_cluster_info(&mut self) {
+        self.cluster_info_view.reset();
+        self.mode = AppMode::ClusterInfo;
+        self.scheduler.request(RefreshTask::ClusterInfoRefresh);
+    }
+
+    /// Show the first component the cluster is degraded by: a pod of a
+    /// failing deployment in the detail panel, else the cluster info view
+    pub(super) fn open_failing_component(&mut self) {
+        let pod_index = self.degraded_components().into_iter().find_map(|problem| {
+            let FailingComponent::Deployment { namespace, name } = problem.component else {
+                return None;
+            };
+            let prefix = format!("{}-", name);
+            self.pod_stats
+                .pods()
+                .iter()
+                .position(|p| p.namespace == namespace && p.name.starts_with(&prefix))
+        });
+        let Some(index) = pod_index else {
+            self.open_cluster_info();
+            return;
+        };
+        self.pod_stats.select_index(index);
+        self.focus = FocusArea::PodStats;
+        self.open_or_switch_detail_tab(DetailTab::Describe);
+        self.update_pod_highlights();
+    }
+
+    /// List how the live Traefik config differs from k3dev's
+    pub(super) fn show_traefik_drift(&mut self) {
+        let Some(drift) = &self.traefik_drift else {
+            return;
+        };
+        let mut lines = vec![OutputLine::warning("Traefik config drifted:")];
+        lines.extend(
+            drift
+                .details()
+                .into_iter()
+                .map(|detail| OutputLine::info(format!("  {}", detail))),
+        );
+        let key = self
+            .keybinding_resolver
+            .get_binding_display(&KeyAction::ReconcileTraefik)
+            .unwrap_or("F");
+        lines.push(OutputLine::info(format!("Press {} to reconcile", key)));
+        for line in lines {
+            self.report(line);
+        }
+    }
+
+    /// Apply k3dev's Traefik config again over a drifted one
+    pub(super) fn reconcile_traefik(&mut self) {
+        if self.traefik_drift.is_none() {
+            self.report(OutputLine::info("Traefik config matches k3dev's"));
+            return;
+        }
+        let component = ClusterComponent::Traefik;
+        self.submit_job(
+            component.action_label().to_string(),
+            JobKind::Redeploy(component),
+            false,
+        );
+    }
+
+    /// Open the services view; it refreshes itself while open
+    pub(super) fn open_services(&mut self) {
+        self.services_view.reset();
+        self.mode = AppMode::Services;
+        self.scheduler.request(RefreshTask::ServicesRefresh);
+    }
+
+    /// Open the network policy view
+    pub(super) fn open_network_policies(&mut self) {
+        self.network_policy_view
+            .reset(self.cluster_config.network_policy);
+        self.mode = AppMode::NetworkPolicies;
+        self.load_network_policies();
+    }
+
+    pub(super) fn load_network_policies(&mut self) {
+        let Some(client) = self.k8s_client.as_ref().map(|c| c.client().clone()) else {
+            self.network_policy_view
+                .update(Err("Cluster is not running".to_string()));
+            return;
+        };
+        let message_tx = self.message_tx.clone();
+        tokio::spawn(async move {
+            let result = crate::k8s::get_network_policies(&client)
+                .await
+                .map_err(|e| format!("{:#}", e));
+            let _ = message_tx
+                .send(AppMessage::NetworkPoliciesLoaded(result))
+                .await;
+        });
+    }
+
+    /// Open the service dependency map
+    pub(super) fn open_dependency_map(&mut self) {
+        self.dependency_map_view.reset();
+        self.mode = AppMode::DependencyMap;
+        self.load_dependency_map();
+    }
+
+    pub(super) fn load_dependency_map(&mut self) {
+        let Some(client) = self.k8s_client.as_ref().map(|c| c.client().clone()) else {
+            self.dependency_map_view
+                .update(Err("Cluster is not running".to_string()));
+            return;
+        };
+        let message_tx = self.message_tx.clone();
+        tokio::spawn(async move {
+            let result = crate::k8s::get_dependency_map(&client)
+                .await
+                .map_err(|e| format!("{:#}", e));
+            let _ = message_tx
+                .send(AppMessage::DependencyMapLoaded(result))
+                .await;
+        });
+    }
+
+    /// Install or remove the configured service mesh as a host command job
+    fn run_mesh_command(&mut self, install: bool) {
+        if self.k8s_client.is_none() {
+            self.output.add_error("Cluster is not running");
+            return;
+        }
+        let mesh = self.cluster_config.service_mesh;
+        let cmd = if install {
+            crate::cluster::mesh::install_command(mesh)
+        } else {
+            crate::cluster::mesh::uninstall_command(mesh)
+        };
+        self.dispatch_command(&cmd);
+    }
+
+    /// Install or remove the configured policy engine as a host command job
+    fn run_policy_engine_command(&mut self, install: bool) {
+        if self.k8s_client.is_none() {
+            self.output.add_error("Cluster is not running");
+            return;
+        }
+        let engine = self.cluster_config.policy_engine;
+        let cmd = if install {
+            crate::cluster::policy_engine::install_command(engine)
+        } else {
+            crate::cluster::policy_engine::uninstall_command(engine)
+        };
+        self.dispatch_command(&cmd);
+    }
+
+    /// Open the policy violations view
+    pub(super) fn open_policy_violations(&mut self) {
+        self.policy_view.reset();
+        self.mode = AppMode::PolicyViolations;
+        self.load_policy_violations();
+    }
+
+    pub(super) fn load_policy_violations(&mut self) {
+        let Some(client) = self.k8s_client.as_ref().map(|c| c.client().clone()) else {
+            self.policy_view
+                .update(Err("Cluster is not running".to_string()));
+            return;
+        };
+        let message_tx = self.message_tx.clone();
+        tokio::spawn(async move {
+            let result = crate::k8s::get_policy_violations(&client)
+                .await
+                .map_err(|e| format!("{:#}", e));
+            let _ = message_tx
+                .send(AppMessage::PolicyViolationsLoaded(result))
+                .await;
+        });
+    }
+
+    /// Open the resource quotas view
+    pub(super) fn open_quotas(&mut self) {
+        self.quotas_view.reset();
+        self.mode = AppMode::Quotas;
+        self.load_quotas();
+    }
+
+    pub(super) fn load_quotas(&mut self) {
+        let Some(client) = self.k8s_client.as_ref().map(|c| c.client().clone()) else {
+            self.quotas_view
+                .update(Err("Cluster is not running".to_string()));
+            return;
+        };
+        let message_tx = self.message_tx.clone();
+        tokio::spawn(async move {
+            let result = crate::k8s::get_quotas(&client)
+                .await
+                .map_err(|e| format!("{:#}", e));
+            let _ = message_tx.send(AppMessage::QuotasLoaded(result)).await;
+        });
+    }
+
+    /// Open the container runtime inspector
+    pub(super) fn open_runtime(&mut self) {
+        self.runtime_view.reset();
+        self.mode = AppMode::Runtime;
+        self.load_runtime();
+    }
+
+    pub(super) fn load_runtime(&mut self) {
+        let message_tx = self.message_tx.clone();
+        let cluster_config = Arc::clone(&self.cluster_config);
+        let clients = self.clients.clone();
+        let timeout = self.refresh_config.services_timeout;
+        tokio::spawn(async move {
+            let result = tokio::time::timeout(timeout, async {
+                let mut manager = ClusterManager::with_clients(cluster_config, &clients).await?;
+                manager.runtime().await
+            })
+            .await;
+            let entries = match result {
+                Ok(Ok(entries)) => Ok(entries),
+                Ok(Err(e)) => Err(format!("{:#}", e)),
+                Err(_) => Err(format!("Asking crictl timed out after {:?}", timeout)),
+            };
+            let _ = message_tx.send(AppMessage::RuntimeLoaded(entries)).await;
+        });
+    }
+
+    /// Open the PV data browser
+    pub(super) fn open_pv_browser(&mut self) {
+        self.pv_browser_view
+            .reset(&K3sManager::configured_pv_storage_path(
+                &self.cluster_config,
+            ));
+        self.mode = AppMode::PvBrowser;
+        self.load_pv_directories();
+    }
+
+    pub(super) fn load_pv_directories(&mut self) {
+        if !self.cluster_is_running() {
+            self.pv_browser_view
+                .update(Err("Cluster is not running".to_string()));
+            return;
+        }
+        let message_tx = self.message_tx.clone();
+        let clients = self.clients.clone();
+        let container = self.cluster_config.container_name.clone();
+        let storage_path = K3sManager::configured_pv_storage_path(&self.cluster_config);
+        let timeout = self.refresh_config.volume_timeout;
+        tokio::spawn(async move {
+            let result = tokio::time::timeout(timeout, async {
+                let docker = clients.docker().await?;
+                docker.list_pv_directories(&container, &storage_path).await
+            })
+            .await;
+            let dirs = match result {
+                Ok(Ok(dirs)) => Ok(dirs),
+                Ok(Err(e)) => Err(format!("{:#}", e)),
+                Err(_) => Err(format!("Listing PV data timed out after {:?}", timeout)),
+            };
+            let _ = message_tx.send(AppMessage::PvDirectoriesLoaded(dirs)).await;
+        });
+    }
+
+    /// Copy the selected PV directory into the current directory
+    pub(super) fn download_selected_pv(&mut self) {
+        let Some(dir) = self.pv_browser_view.selected().cloned() else {
+            return;
+        };
+        let dest = dir.default_download_dir();
+        self.report(OutputLine::info(format!(
+            "Copying PV data of {} to {}...",
+            dir.label(),
+            dest.display()
+        )));
+        let message_tx = self.message_tx.clone();
+        let clients = self.clients.clone();
+        let container = self.cluster_config.container_name.clone();
+        let storage_path = K3sManager::configured_pv_storage_path(&self.cluster_config);
+        tokio::spawn(async move {
+            let result = async {
+                let docker = clients.docker().await?;
+                docker
+                    .download_pv_directory(&container, &storage_path, &dir.dir_name, &dest)
+                    .await
+            }
+            .await;
+            let line = match result {
+                Ok(files) => OutputLine::success(format!(
+                    "Copied {} file(s) of {} to {}",
+                    files,
+                    dir.label(),
+                    dest.display()
+                )),
+                Err(e) => OutputLine::error(format!(
+                    "Copying PV data of {} failed: {:#}",
+                    dir.label(),
+                    e
+                )),
+            };
+            let _ = message_tx.send(AppMessage::PvActionFinished(line)).await;
+        });
+    }
+
+    /// Ask before wiping the selected PV directory
+    pub(super) fn confirm_wipe_selected_pv(&mut self) {
+        let Some(dir) = self.pv_browser_view.selected().cloned() else {
+            return;
+        };
+        self.confirm_popup.set_content(
+            "Wipe PV Data",
+            &format!(
+                "This will delete all files of {} ({}). Pods using it keep running. This cannot be undone.",
+                dir.label(),
+                dir.dir_name
+            ),
+        );
+        self.pending_pv_wipe = Some(dir);
+        self.mode = AppMode::ConfirmDestroy;
+    }
+
+    fn wipe_pv_directory(&mut self, dir: PvDirectory) {
+        let message_tx = self.message_tx.clone();
+        let clients = self.clients.clone();
+        let container = self.cluster_config.container_name.clone();
+        let storage_path = K3sManager::configured_pv_storage_path(&self.cluster_config);
+        tokio::spawn(async move {
+            let result = async {
+                let docker = clients.docker().await?;
+                docker
+                    .wipe_pv_directory(&container, &storage_path, &dir.dir_name)
+                    .await
+            }
+            .await;
+            let line = match result {
+                Ok(()) => OutputLine::success(format!("Wiped PV data of {}", dir.label())),
+                Err(e) => {
+                    OutputLine::error(format!("Wiping PV data of {} failed: {:#}", dir.label(), e))
+                }
+            };
+            let _ = message_tx.send(AppMessage::PvActionFinished(line)).await;
+        });
+    }
+
+    /// Open the k3s version picker
+    fn open_k3s_versions(&mut self) {
+        self.k3s_version_view
+            .reset(&self.config.infrastructure.k3s_version);
+        self.mode = AppMode::K3sVersions;
+        self.load_k3s_versions();
+    }
+
+    /// Ask Docker Hub for recent k3s releases
+    pub(super) fn load_k3s_versions(&mut self) {
+        let message_tx = self.message_tx.clone();
+        let clients = self.clients.clone();
+        tokio::spawn(async move {
+            let index = ReleaseIndex::new();
+            let (choices, docker) =
+                tokio::join!(async { index?.choices().await }, clients.docker());
+            let arch = match docker {
+                Ok(docker) => docker.platform_architecture().await,
+                Err(_) => None,
+            };
+            let choices = choices.map_err(|e| format!("{:#}", e));
+            let _ = message_tx
+                .send(AppMessage::K3sVersionsLoaded { choices, arch })
+                .await;
+        });
+    }
+
+    /// Write the release picked in the k3s version picker to the config
+    pub(super) fn use_selected_k3s_version(&mut self) {
+        let Some(choice) = self.k3s_version_view.selected() else {
+            return;
+        };
+        let version = choice.version.to_string();
+        let Some(path) = self.config_file_path.clone() else {
+            self.output
+                .add_error("No config file to write k3s_version to; create one with Edit Config");
+            return;
+        };
+        self.mode = AppMode::Normal;
+        if version == self.config.infrastructure.k3s_version {
+            self.output
+                .add_info(format!("k3s {} is already configured", version));
+            return;
+        }
+        let value = format!("\"{}\"", version);
+        if let Err(e) = write_infrastructure_value(&path, "k3s_version", &value) {
+            self.output.add_error(format!("{:#}", e));
+            return;
+        }
+        self.reload_config(&path);
+        self.output.add_info(format!(
+            "k3s {} applies to new clusters; Destroy and Start to switch",
+            version
+        ));
+    }
+
+    /// Open the RBAC inspector
+    pub(super) fn open_rbac(&mut self) {
+        self.rbac_view.reset(&self.config.cluster.default_namespace);
+        self.mode = AppMode::Rbac;
+        self.load_rbac();
+    }
+
+    pub(super) fn load_rbac(&mut self) {
+        let Some(client) = self.k8s_client.as_ref().map(|c| c.client().clone()) else {
+            self.rbac_view
+                .update(Err("Cluster is not running".to_string()));
+            return;
+        };
+        let message_tx = self.message_tx.clone();
+        tokio::spawn(async move {
+            let result = crate::k8s::get_rbac(&client)
+                .await
+                .map_err(|e| format!("{:#}", e));
+            let _ = message_tx.send(AppMessage::RbacLoaded(result)).await;
+        });
+    }
+
+    /// Run the query typed in the RBAC inspector's can-i prompt
+    pub(super) fn run_can_i(&mut self, input: String) {
+        self.rbac_view.start_check(&input);
+        let query = match CanIQuery::parse(&input, self.rbac_view.namespace()) {
+            Ok(query) => query,
+            Err(e) => {
+                self.rbac_view.finish_check(&input, Err(e));
+                return;
+            }
+        };
+        let Some(client) = self.k8s_client.clone() else {
+            self.rbac_view
+                .finish_check(&input, Err("Cluster is not running".to_string()));
+            return;
+        };
+        let message_tx = self.message_tx.clone();
+        tokio::spawn(async move {
+            let answer = crate::k8s::can_i(&client, &query)
+                .await
+                .map_err(|e| format!("{:#}", e));
+            let _ = message_tx
+                .send(AppMessage::CanIChecked {
+                    query: input,
+                    answer,
+                })
+                .await;
+        });
+    }
+
+    /// Open the top popup for the selected pod; it refreshes itself while open
+    pub(super) fn open_pod_top(&mut self) {
+        let Some(pod) = self.pod_stats.selected_pod() else {
+            return;
+        };
+        let (pod, namespace) = (pod.name.clone(), pod.namespace.clone());
+        self.pod_top_view.open(&pod, &namespace);
+        self.mode = AppMode::PodTop;
+        self.scheduler.request(RefreshTask::PodTopRefresh);
+    }
+
     /// Run preflight checks (can run without a started cluster)
     pub(super) fn run_preflight_check(&mut self) {
         self.diagnostics_overlay.reset();
@@ -479,6 +1391,20 @@ impl App {
         });
     }
 
+    /// Focus the pod list sorted by a metric; selecting the active sort again
+    /// restores the default order
+    pub(super) fn focus_pods_sorted(&mut self, sort: PodSort) {
+        let sort = if self.pod_stats.sort() == sort {
+            PodSort::Name
+        } else {
+            sort
+        };
+        self.pod_stats.set_sort(sort);
+        self.focus = FocusArea::PodStats;
+        self.ensure_detail_panel_synced();
+        self.update_pod_highlights();
+    }
+
     /// Auto-open detail panel when a pod is selected, close when no pods
     pub(super) fn ensure_detail_panel_synced(&mut self) {
         if let Some(pod) = self.pod_stats.selected_pod() {
@@ -963,103 +1889,3 @@ fn matches_target(target: &KubernetesTargetOwned, pod: &crate::ui::components::P
     // If only namespace specified, match all pods in that namespace
     !target.namespace.is_empty()
 }
-
-/// Run a host-side shell command, streaming combined stdout+stderr to the popup.
-async fn run_host_command(
-    command: &str,
-    workdir: &str,
-    output_tx: tokio::sync::mpsc::Sender<crate::ui::components::OutputLine>,
-) -> Result<(), String> {
-    use std::process::Stdio;
-    use tokio::io::{AsyncBufReadExt, BufReader};
-    use tokio::process::Command;
-
-    let mut cmd = Command::new("sh");
-    cmd.arg("-c").arg(command);
-    if !workdir.is_empty() {
-        cmd.current_dir(workdir);
-    }
-    cmd.stdout(Stdio::piped());
-    cmd.stderr(Stdio::piped());
-
-    let mut child = cmd
-        .spawn()
-        .map_err(|e| format!("Failed to spawn host command: {}", e))?;
-
-    let stdout = child.stdout.take();
-    let stderr = child.stderr.take();
-
-    let stdout_tx = output_tx.clone();
-    let stdout_handle = tokio::spawn(async move {
-        if let Some(out) = stdout {
-            let mut reader = BufReader::new(out).lines();
-            while let Ok(Some(line)) = reader.next_line().await {
-                let _ = stdout_tx
-                    .send(crate::ui::components::OutputLine::info(line))
-                    .await;
-            }
-        }
-    });
-
-    let stderr_tx = output_tx.clone();
-    let stderr_handle = tokio::spawn(async move {
-        if let Some(err) = stderr {
-            let mut reader = BufReader::new(err).lines();
-            while let Ok(Some(line)) = reader.next_line().await {
-                let _ = stderr_tx
-                    .send(crate::ui::components::OutputLine::error(line))
-                    .await;
-            }
-        }
-    });
-
-    let status = child
-        .wait()
-        .await
-        .map_err(|e| format!("Failed to wait on host command: {}", e))?;
-
-    let _ = stdout_handle.await;
-    let _ = stderr_handle.await;
-
-    if status.success() {
-        Ok(())
-    } else {
-        Err(format!(
-            "Host command exited with code {}",
-            status.code().unwrap_or(-1)
-        ))
-    }
-}
-
-/// Run a one-shot command inside a docker container, sending output to the popup.
-async fn run_docker_command(
-    container: &str,
-    command: &str,
-    workdir: &str,
-    output_tx: tokio::sync::mpsc::Sender<crate::ui::components::OutputLine>,
-) -> Result<(), String> {
-    let docker = DockerManager::from_default_socket()
-        .map_err(|e| format!("Failed to connect to docker: {}", e))?;
-
-    // Compose `cd <workdir> && <cmd>` so the user's workdir is honored.
-    let full_cmd = if workdir.is_empty() {
-        command.to_string()
-    } else {
-        format!("cd {} && {}", workdir, command)
-    };
-
-    match docker
-        .exec_in_container(container, &["sh", "-c", &full_cmd])
-        .await
-    {
-        Ok(out) => {
-            for line in out.lines() {
-                let _ = output_tx
-                    .send(crate::ui::components::OutputLine::info(line.to_string()))
-                    .await;
-            }
-            Ok(())
-        }
-        Err(e) => Err(format!("docker exec failed: {}", e)),
-    }
-}
@@ -0,0 +1,58 @@
+//! External editor support
+//!
+//! Suspends the TUI, runs the user's editor on a file, and restores the
+//! terminal afterwards.
+
+use crossterm::event::{DisableMouseCapture, EnableMouseCapture};
+use crossterm::terminal::{
+    disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen,
+};
+use ratatui::{backend::CrosstermBackend, Terminal};
+use std::io::Stdout;
+use std::path::Path;
+use std::process::{Command, ExitStatus};
+
+/// Editor command line from `$VISUAL` / `$EDITOR`, split on whitespace so
+/// values like `code --wait` work. Falls back to `vi` (`notepad` on Windows).
+pub(super) fn editor_command() -> Vec<String> {
+    ["VISUAL", "EDITOR"]
+        .iter()
+        .filter_map(|var| std::env::var(var).ok())
+        .map(|value| {
+            value
+                .split_whitespace()
+                .map(str::to_string)
+                .collect::<Vec<_>>()
+        })
+        .find(|parts| !parts.is_empty())
+        .unwrap_or_else(|| {
+            if cfg!(windows) {
+                vec!["notepad".to_string()]
+            } else {
+                vec!["vi".to_string()]
+            }
+        })
+}
+
+/// Open `path` in the user's editor with the TUI suspended and wait for it to exit.
+pub(super) fn edit_file(
+    terminal: &mut Terminal<CrosstermBackend<Stdout>>,
+    path: &Path,
+) -> std::io::Result<ExitStatus> {
+    let editor = editor_command();
+
+    // Leave raw mode / alternate screen so the editor owns the terminal
+    let _ = disable_raw_mode();
+    let _ = crossterm::execute!(std::io::stdout(), LeaveAlternateScreen, DisableMouseCapture);
+
+    let status = Command::new(&editor[0])
+        .args(&editor[1..])
+        .arg(path)
+        .status();
+
+    let _ = crossterm::execute!(std::io::stdout(), EnterAlternateScreen, EnableMouseCapture);
+    let _ = enable_raw_mode();
+    let _ = terminal.clear();
+
+    status
+}
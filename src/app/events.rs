@@ -6,15 +6,19 @@ use crossterm::event::{KeyCode, KeyModifiers, MouseButton, MouseEvent, MouseEven
 
 use crate::cluster::ClusterStatus;
 use crate::config::RefreshTask;
-use crate::keybindings::KeyAction;
-use crate::ui::components::DetailTab;
+use crate::keybindings::{KeyAction, KeyContext};
+use crate::ui::components::{BarLink, DetailTab, OutputLine, WizardOutcome};
 
+use super::panes::PaneCommand;
 use super::{App, AppMode, FocusArea};
 
 impl App {
     pub(super) fn handle_key(&mut self, code: KeyCode, modifiers: KeyModifiers) {
-        // Use keybinding resolver to determine action
-        let action = self.keybinding_resolver.resolve(code, modifiers);
+        // Use keybinding resolver to determine action in the current context
+        let context = self.key_context();
+        let action = self
+            .keybinding_resolver
+            .resolve_in(context, code, modifiers);
 
         // Handle Quit action first - should always work regardless of mode
         if matches!(action, KeyAction::Quit) && self.mode == AppMode::Normal {
@@ -43,21 +47,66 @@ impl App {
             return;
         }
 
-        // Handle Cancel action (Ctrl+C) - cancels execution or quits
-        if matches!(action, KeyAction::Cancel) {
-            if let Some(token) = self.cancel_token.take() {
+        // Handle Cancel action (Ctrl+C) - cancels execution or quits.
+        // Global unless the current context binds the key to something else.
+        let is_cancel = match action {
+            KeyAction::Cancel => true,
+            KeyAction::None => matches!(
+                self.keybinding_resolver.resolve(code, modifiers),
+                KeyAction::Cancel
+            ),
+            _ => false,
+        };
+        if is_cancel {
+            if let Some(job) = self.cancellable_job() {
+                self.cancel_job(job);
+            } else if let Some(token) = self.cancel_token.take() {
                 token.cancel();
                 self.output.add_warning("Cancelling...");
-                self.is_executing = false;
             } else {
                 self.should_quit = true;
             }
             return;
         }
 
-        // Handle input mode separately (modal - doesn't use resolver)
+        // Handle first-run setup wizard (modal)
+        if self.mode == AppMode::SetupWizard {
+            let outcome = match code {
+                KeyCode::Esc => self.setup_wizard.back(),
+                KeyCode::Enter => self.setup_wizard.advance(),
+                KeyCode::Left | KeyCode::Right | KeyCode::Tab => {
+                    self.setup_wizard.toggle();
+                    WizardOutcome::Pending
+                }
+                KeyCode::Backspace => {
+                    self.setup_wizard.handle_backspace();
+                    WizardOutcome::Pending
+                }
+                KeyCode::Char(c) => {
+                    self.setup_wizard.handle_char(c);
+                    WizardOutcome::Pending
+                }
+                _ => WizardOutcome::Pending,
+            };
+            match outcome {
+                WizardOutcome::Pending => {}
+                WizardOutcome::Cancelled => {
+                    self.mode = AppMode::Normal;
+                    self.output
+                        .add_info("Setup skipped — running with default settings");
+                }
+                WizardOutcome::Finished {
+                    config,
+                    path,
+                    start,
+                } => self.finish_setup(config, path, start),
+            }
+            return;
+        }
+
+        // Handle input mode (modal)
         if self.mode == AppMode::Input {
-            self.handle_input_key(code, modifiers);
+            self.handle_input_key(code, action);
             return;
         }
 
@@ -71,7 +120,7 @@ impl App {
 
             match code {
                 KeyCode::Char('?') | KeyCode::Esc | KeyCode::Char('q') => {
-                    self.mode = AppMode::Normal;
+                    self.mode = self.help_return_mode;
                     self.help_overlay.reset_scroll();
                 }
                 KeyCode::Up | KeyCode::Char('k') => {
@@ -96,21 +145,46 @@ impl App {
 
         // Handle output popup mode (modal)
         if self.mode == AppMode::OutputPopup {
-            match code {
-                KeyCode::Esc | KeyCode::Enter | KeyCode::Char('q') => {
+            if self.handle_workspace_key(code, &action) {
+                return;
+            }
+            match action {
+                KeyAction::Close => {
                     self.mode = AppMode::Normal;
+                    self.staged_resource_edit = None;
+                    self.stuck_namespace = None;
+                    self.output_popup.set_actions(None);
                 }
-                KeyCode::Up | KeyCode::Char('k') => {
+                KeyAction::Apply if self.staged_resource_edit.is_some() => {
+                    self.apply_staged_resource_edit(false);
+                }
+                KeyAction::ForceApply if self.staged_resource_edit.is_some() => {
+                    self.apply_staged_resource_edit(true);
+                }
+                KeyAction::ForceApply if self.stuck_namespace.is_some() => {
+                    if let Some(name) = self.stuck_namespace.take() {
+                        self.delete_namespace(name, true);
+                    }
+                }
+                KeyAction::Help => self.open_help(context),
+                KeyAction::SortTable => self.output_popup.cycle_sort(),
+                KeyAction::ReverseSort => self.output_popup.reverse_sort(),
+                KeyAction::ToggleRaw => self.output_popup.toggle_raw(),
+                KeyAction::ZoomPanel => self.output_popup.toggle_zoom(),
+                KeyAction::MoveUp => {
                     self.output_popup.scroll_up();
                 }
-                KeyCode::Down | KeyCode::Char('j') => {
-                    // Get visible lines for scroll calculation
-                    let visible = self
-                        .current_layout
-                        .as_ref()
-                        .map(|_| 20) // approximate visible lines
-                        .unwrap_or(20);
-                    self.output_popup.scroll_down(visible);
+                KeyAction::MoveDown => {
+                    self.output_popup.scroll_down();
+                }
+                KeyAction::MoveLeft | KeyAction::MoveRight => {
+                    let forward = action == KeyAction::MoveRight;
+                    let next = self
+                        .popup_job
+                        .and_then(|id| self.jobs.neighbour(id, forward));
+                    if let Some(id) = next {
+                        self.show_job_output(id);
+                    }
                 }
                 _ => {}
             }
@@ -119,12 +193,17 @@ impl App {
 
         // Handle command palette mode (modal)
         if self.mode == AppMode::CommandPalette {
-            match code {
-                KeyCode::Esc => {
+            match action {
+                KeyAction::Close => {
                     self.mode = AppMode::Normal;
                     self.command_palette.reset();
                 }
-                KeyCode::Enter => {
+                KeyAction::Help => {
+                    self.command_palette.reset();
+                    self.mode = AppMode::Normal;
+                    self.open_help(context);
+                }
+                KeyAction::Execute => {
                     if let Some(cmd) = self.command_palette.selected_command() {
                         let cmd_id = cmd.id.clone();
                         // Record execution for recent commands before resetting
@@ -134,22 +213,52 @@ impl App {
                         self.execute_palette_command(cmd_id);
                     }
                 }
-                KeyCode::Up => self.command_palette.move_up(),
-                KeyCode::Down => self.command_palette.move_down(),
-                KeyCode::Backspace => self.command_palette.handle_backspace(),
-                KeyCode::Char('j') if modifiers.contains(KeyModifiers::CONTROL) => {
-                    self.command_palette.move_down();
-                }
-                KeyCode::Char('k') if modifiers.contains(KeyModifiers::CONTROL) => {
-                    self.command_palette.move_up();
+                KeyAction::MoveUp => self.command_palette.move_up(),
+                KeyAction::MoveDown => self.command_palette.move_down(),
+                _ => match code {
+                    KeyCode::Backspace => self.command_palette.handle_backspace(),
+                    KeyCode::Char(c) if !modifiers.contains(KeyModifiers::CONTROL) => {
+                        self.command_palette.handle_char(c)
+                    }
+                    _ => {}
+                },
+            }
+            return;
+        }
+
+        // Handle breadcrumb jump picker (modal)
+        if self.mode == AppMode::BreadcrumbJump {
+            match code {
+                KeyCode::Esc | KeyCode::Char('q') => self.mode = AppMode::Normal,
+                KeyCode::Char('k') | KeyCode::Up => self.breadcrumb_picker.select_up(),
+                KeyCode::Char('j') | KeyCode::Down => self.breadcrumb_picker.select_down(),
+                KeyCode::Enter => {
+                    if let Some(path) = self.breadcrumb_picker.selected_path() {
+                        let path = path.to_vec();
+                        self.menu.jump_to(&path);
+                        self.focus = FocusArea::Content;
+                        self.update_pod_highlights();
+                    }
+                    self.mode = AppMode::Normal;
                 }
-                KeyCode::Char('n') if modifiers.contains(KeyModifiers::CONTROL) => {
-                    self.command_palette.move_down();
+                _ => {}
+            }
+            return;
+        }
+
+        // Handle command preview (modal)
+        if self.mode == AppMode::CommandPreview {
+            match code {
+                KeyCode::Esc | KeyCode::Char('q') => {
+                    self.command_preview.take();
+                    self.mode = AppMode::Normal;
                 }
-                KeyCode::Char('p') if modifiers.contains(KeyModifiers::CONTROL) => {
-                    self.command_palette.move_up();
+                KeyCode::Enter => {
+                    self.mode = AppMode::Normal;
+                    if let Some(cmd) = self.command_preview.take() {
+                        self.execute_command(cmd);
+                    }
                 }
-                KeyCode::Char(c) => self.command_palette.handle_char(c),
                 _ => {}
             }
             return;
@@ -161,6 +270,9 @@ impl App {
                 KeyCode::Char('y') | KeyCode::Char('Y') => {
                     self.confirm_destroy();
                 }
+                KeyCode::Char('e') | KeyCode::Char('E') if self.confirm_popup.has_alternative() => {
+                    self.confirm_destroy_with_export();
+                }
                 KeyCode::Char('n') | KeyCode::Char('N') | KeyCode::Esc => {
                     self.cancel_destroy();
                 }
@@ -189,8 +301,248 @@ impl App {
             return;
         }
 
-        // Don't handle other keys while executing
-        if self.is_executing {
+        // Handle cluster info view (modal)
+        if self.mode == AppMode::ClusterInfo {
+            match code {
+                KeyCode::Esc | KeyCode::Char('q') => {
+                    self.mode = AppMode::Normal;
+                }
+                KeyCode::Char('r') => {
+                    self.scheduler.request(RefreshTask::ClusterInfoRefresh);
+                }
+                _ => {}
+            }
+            return;
+        }
+
+        // Handle services view (modal)
+        if self.mode == AppMode::Services {
+            match code {
+                KeyCode::Esc | KeyCode::Char('q') => {
+                    self.mode = AppMode::Normal;
+                }
+                KeyCode::Char('k') | KeyCode::Up => self.services_view.scroll_up(),
+                KeyCode::Char('j') | KeyCode::Down => self.services_view.scroll_down(),
+                KeyCode::Char('r') => {
+                    self.scheduler.request(RefreshTask::ServicesRefresh);
+                }
+                _ => {}
+            }
+            return;
+        }
+
+        // Handle network policy view (modal)
+        if self.mode == AppMode::NetworkPolicies {
+            match code {
+                KeyCode::Esc | KeyCode::Char('q') => {
+                    self.mode = AppMode::Normal;
+                }
+                KeyCode::Char('l') | KeyCode::Right | KeyCode::Tab => {
+                    self.network_policy_view.next_namespace()
+                }
+                KeyCode::Char('h') | KeyCode::Left | KeyCode::BackTab => {
+                    self.network_policy_view.prev_namespace()
+                }
+                KeyCode::Char('r') => self.load_network_policies(),
+                _ => {}
+            }
+            return;
+        }
+
+        // Handle dependency map view (modal)
+        if self.mode == AppMode::DependencyMap {
+            match code {
+                KeyCode::Esc | KeyCode::Char('q') => {
+                    self.mode = AppMode::Normal;
+                }
+                KeyCode::Char('k') | KeyCode::Up => self.dependency_map_view.scroll_up(),
+                KeyCode::Char('j') | KeyCode::Down => self.dependency_map_view.scroll_down(),
+                KeyCode::Char('r') => self.load_dependency_map(),
+                _ => {}
+            }
+            return;
+        }
+
+        // Handle policy violations view (modal)
+        if self.mode == AppMode::PolicyViolations {
+            match code {
+                KeyCode::Esc | KeyCode::Char('q') => {
+                    self.mode = AppMode::Normal;
+                }
+                KeyCode::Char('k') | KeyCode::Up => self.policy_view.scroll_up(),
+                KeyCode::Char('j') | KeyCode::Down => self.policy_view.scroll_down(),
+                KeyCode::Char('r') => self.load_policy_violations(),
+                _ => {}
+            }
+            return;
+        }
+
+        // Handle resource quotas view (modal)
+        if self.mode == AppMode::Quotas {
+            match code {
+                KeyCode::Esc | KeyCode::Char('q') => {
+                    self.mode = AppMode::Normal;
+                }
+                KeyCode::Char('k') | KeyCode::Up => self.quotas_view.scroll_up(),
+                KeyCode::Char('j') | KeyCode::Down => self.quotas_view.scroll_down(),
+                KeyCode::Char('r') => self.load_quotas(),
+                _ => {}
+            }
+            return;
+        }
+
+        // Handle container runtime view (modal)
+        if self.mode == AppMode::Runtime {
+            match code {
+                KeyCode::Esc | KeyCode::Char('q') => {
+                    self.mode = AppMode::Normal;
+                }
+                KeyCode::Char('k') | KeyCode::Up => self.runtime_view.scroll_up(),
+                KeyCode::Char('j') | KeyCode::Down => self.runtime_view.scroll_down(),
+                KeyCode::Char('m') => self.runtime_view.toggle_mismatches_only(),
+                KeyCode::Char('r') => self.load_runtime(),
+                _ => {}
+            }
+            return;
+        }
+
+        // Handle PV data browser (modal)
+        if self.mode == AppMode::PvBrowser {
+            match code {
+                KeyCode::Esc | KeyCode::Char('q') => {
+                    self.mode = AppMode::Normal;
+                }
+                KeyCode::Char('k') | KeyCode::Up => self.pv_browser_view.select_up(),
+                KeyCode::Char('j') | KeyCode::Down => self.pv_browser_view.select_down(),
+                KeyCode::Char('d') => self.download_selected_pv(),
+                KeyCode::Char('W') => self.confirm_wipe_selected_pv(),
+                KeyCode::Char('r') => self.load_pv_directories(),
+                _ => {}
+            }
+            return;
+        }
+
+        // Handle snapshots view (modal)
+        if self.mode == AppMode::Snapshots {
+            match code {
+                KeyCode::Esc | KeyCode::Char('q') => {
+                    self.mode = AppMode::Normal;
+                }
+                KeyCode::Char('k') | KeyCode::Up => self.snapshots_view.select_up(),
+                KeyCode::Char('j') | KeyCode::Down => self.snapshots_view.select_down(),
+                KeyCode::Enter => self.confirm_start_from_selected_snapshot(),
+                KeyCode::Char('s') => self.open_save_snapshot_form(),
+                KeyCode::Char('r') => self.load_snapshots(),
+                _ => {}
+            }
+            return;
+        }
+
+        // Handle hooks view (modal)
+        if self.mode == AppMode::HookRuns {
+            match code {
+                KeyCode::Esc | KeyCode::Char('q') => self.mode = AppMode::Normal,
+                KeyCode::Char('k') | KeyCode::Up => self.hook_runs_view.select_up(),
+                KeyCode::Char('j') | KeyCode::Down => self.hook_runs_view.select_down(),
+                KeyCode::Enter => self.show_selected_hook_run(),
+                _ => {}
+            }
+            return;
+        }
+
+        // Handle refresh tasks popup (modal)
+        if self.mode == AppMode::RefreshTasks {
+            if matches!(code, KeyCode::Esc | KeyCode::Char('q')) {
+                self.mode = AppMode::Normal;
+            }
+            return;
+        }
+
+        // Handle k3s version picker (modal)
+        if self.mode == AppMode::K3sVersions {
+            match code {
+                KeyCode::Esc | KeyCode::Char('q') => self.mode = AppMode::Normal,
+                KeyCode::Char('k') | KeyCode::Up => self.k3s_version_view.select_up(),
+                KeyCode::Char('j') | KeyCode::Down => self.k3s_version_view.select_down(),
+                KeyCode::Enter => self.use_selected_k3s_version(),
+                _ => {}
+            }
+            return;
+        }
+
+        // Handle build panel (modal); Ctrl+C cancels the build above
+        if self.mode == AppMode::Build {
+            match code {
+                KeyCode::Esc | KeyCode::Char('q') => self.mode = AppMode::Normal,
+                KeyCode::Char('o') => self.show_build_output(),
+                _ => {}
+            }
+            return;
+        }
+
+        // Handle crash popup (modal)
+        if self.mode == AppMode::Crash {
+            match code {
+                KeyCode::Esc | KeyCode::Char('q') => {
+                    let more = self.crash_view.dismiss();
+                    if !more {
+                        self.mode = self.crash_return_mode;
+                    }
+                }
+                KeyCode::Char('k') | KeyCode::Up => self.crash_view.scroll_up(),
+                KeyCode::Char('j') | KeyCode::Down => self.crash_view.scroll_down(),
+                KeyCode::Char('w') => self.write_crash_report(),
+                _ => {}
+            }
+            return;
+        }
+
+        // Handle RBAC inspector (modal); its can-i prompt takes all keys
+        if self.mode == AppMode::Rbac {
+            if self.rbac_view.is_prompting() {
+                match code {
+                    KeyCode::Esc => self.rbac_view.close_prompt(),
+                    KeyCode::Enter => {
+                        if let Some(query) = self.rbac_view.take_query() {
+                            self.run_can_i(query);
+                        }
+                    }
+                    KeyCode::Backspace => self.rbac_view.handle_backspace(),
+                    KeyCode::Char(c) => self.rbac_view.handle_char(c),
+                    _ => {}
+                }
+                return;
+            }
+            match code {
+                KeyCode::Esc | KeyCode::Char('q') => {
+                    self.mode = AppMode::Normal;
+                }
+                KeyCode::Char('l') | KeyCode::Right | KeyCode::Tab => {
+                    self.rbac_view.next_namespace()
+                }
+                KeyCode::Char('h') | KeyCode::Left | KeyCode::BackTab => {
+                    self.rbac_view.prev_namespace()
+                }
+                KeyCode::Char('c') | KeyCode::Char('/') => self.rbac_view.open_prompt(),
+                KeyCode::Char('r') => self.load_rbac(),
+                _ => {}
+            }
+            return;
+        }
+
+        // Handle pod top popup (modal)
+        if self.mode == AppMode::PodTop {
+            match code {
+                KeyCode::Esc | KeyCode::Char('q') => {
+                    self.mode = AppMode::Normal;
+                }
+                KeyCode::Char('k') | KeyCode::Up => self.pod_top_view.scroll_up(),
+                KeyCode::Char('j') | KeyCode::Down => self.pod_top_view.scroll_down(),
+                KeyCode::Char('r') => {
+                    self.scheduler.request(RefreshTask::PodTopRefresh);
+                }
+                _ => {}
+            }
             return;
         }
 
@@ -239,15 +591,18 @@ impl App {
             return;
         }
 
+        // Workspace tabs (gt / gT / gn / gc)
+        if self.handle_workspace_key(code, &action) {
+            return;
+        }
+
         // Skip panel-specific shortcuts when cluster is stopped
         if self.is_cluster_stopped() {
             // In stopped screen, only handle keybinding actions (up/down/enter/help/etc.)
             match action {
-                KeyAction::Quit => {
-                    self.should_quit = true;
-                }
+                KeyAction::Quit => self.request_quit(),
                 KeyAction::Help => {
-                    self.mode = AppMode::Help;
+                    self.open_help(KeyContext::Normal);
                 }
                 KeyAction::CommandPalette => {
                     self.command_palette.reset();
@@ -265,63 +620,83 @@ impl App {
                 KeyAction::Refresh => {
                     self.spawn_status_check();
                 }
+                KeyAction::ShowOutput => {
+                    self.show_run_output();
+                }
                 _ => {}
             }
             return;
         }
 
-        // Handle panel resize shortcuts (+/- keys)
-        if let KeyCode::Char(c) = code {
-            match c {
-                '+' | '=' => {
-                    // Increase menu width (clamp to reasonable range)
-                    self.menu_width_offset = (self.menu_width_offset + 2).min(40);
-                    return;
-                }
-                '-' | '_' => {
-                    // Decrease menu width (clamp to reasonable range)
-                    self.menu_width_offset = (self.menu_width_offset - 2).max(-20);
-                    return;
-                }
-                _ => {}
+        // Handle panel resize shortcuts (+/- by default)
+        match action {
+            KeyAction::GrowMenu => {
+                // Increase menu width (clamp to reasonable range)
+                self.menu_width_offset = (self.menu_width_offset + 2).min(40);
+                return;
+            }
+            KeyAction::ShrinkMenu => {
+                // Decrease menu width (clamp to reasonable range)
+                self.menu_width_offset = (self.menu_width_offset - 2).max(-20);
+                return;
+            }
+            KeyAction::CycleLayout => {
+                self.layout_preset = self.layout_preset.next();
+                self.toasts.push(OutputLine::info(format!(
+                    "Layout: {}",
+                    self.layout_preset.name()
+                )));
+                return;
             }
+            KeyAction::ZoomPanel => {
+                self.zoomed = !self.zoomed;
+                return;
+            }
+            KeyAction::JumpBreadcrumb => {
+                self.open_breadcrumb_jump();
+                return;
+            }
+            KeyAction::PreviewCommand => {
+                self.open_command_preview();
+                return;
+            }
+            KeyAction::ExpandAll => {
+                self.menu.expand_all();
+                self.update_pod_highlights();
+                return;
+            }
+            KeyAction::CollapseAll => {
+                self.menu.collapse_all();
+                self.update_pod_highlights();
+                return;
+            }
+            _ => {}
         }
 
-        // Handle quick focus keys (1/2/3) - only if not building a count prefix
-        if let KeyCode::Char(c) = code {
-            if self.pending_count.is_empty() {
-                match c {
-                    '1' => {
-                        self.focus = FocusArea::Content;
-                        self.update_pod_highlights();
-                        return;
-                    }
-                    '2' => {
-                        self.focus = FocusArea::PodStats;
-                        self.ensure_detail_panel_synced();
-                        self.update_pod_highlights();
-                        return;
-                    }
-                    '3' => {
-                        self.focus = FocusArea::ActionBar;
-                        self.update_pod_highlights();
-                        return;
-                    }
-                    _ => {}
+        // Handle quick focus keys (1/2/3 by default) - only if not building a count prefix
+        let is_focus_key = matches!(
+            action,
+            KeyAction::FocusMenu | KeyAction::FocusPods | KeyAction::FocusActions
+        );
+        if is_focus_key && self.pending_count.is_empty() {
+            match action {
+                KeyAction::FocusMenu => self.focus = FocusArea::Content,
+                KeyAction::FocusPods => {
+                    self.focus = FocusArea::PodStats;
+                    self.ensure_detail_panel_synced();
                 }
+                _ => self.focus = FocusArea::ActionBar,
             }
+            self.update_pod_highlights();
+            return;
         }
 
-        // Handle digit keys for vim-style count prefix (4-9, 0)
-        // 1-3 are handled above as quick focus when not in count mode
+        // Handle digit keys for vim-style count prefix
+        // Digits bound to quick focus start a count only when one is already pending
         if let KeyCode::Char(c) = code {
-            if c.is_ascii_digit() {
-                // If we already have digits, allow any digit
-                // Otherwise, only allow 4-9, 0 (1-3 are quick focus)
-                if !self.pending_count.is_empty() || !matches!(c, '1' | '2' | '3') {
-                    self.pending_count.push(c);
-                    return;
-                }
+            if c.is_ascii_digit() && (!self.pending_count.is_empty() || !is_focus_key) {
+                self.pending_count.push(c);
+                return;
             }
         }
 
@@ -355,31 +730,59 @@ impl App {
                 }
             }
 
-            if let KeyCode::Char(c) = code {
-                match c {
-                    'l' | 'd' | 't' | 'v' | 'e' | 'c' => {
-                        let tab = match c {
-                            'l' => DetailTab::Logs,
-                            'd' => DetailTab::Describe,
-                            't' => DetailTab::Timeline,
-                            'v' => DetailTab::Volumes,
-                            'e' => DetailTab::Shell,
-                            _ => DetailTab::Capture,
-                        };
-                        self.open_or_switch_detail_tab(tab);
-                        return;
-                    }
-                    _ => {}
-                }
+            let tab = match action {
+                KeyAction::PodLogs => Some(DetailTab::Logs),
+                KeyAction::PodDescribe => Some(DetailTab::Describe),
+                KeyAction::PodTimeline => Some(DetailTab::Timeline),
+                KeyAction::PodVolumes => Some(DetailTab::Volumes),
+                KeyAction::PodShell => Some(DetailTab::Shell),
+                KeyAction::PodCapture => Some(DetailTab::Capture),
+                _ => None,
+            };
+            if let Some(tab) = tab {
+                self.open_or_switch_detail_tab(tab);
+                return;
+            }
+            if action == KeyAction::EditResource {
+                self.edit_selected_pod_owner();
+                return;
+            }
+            if action == KeyAction::OverrideImage {
+                self.override_selected_pod_image();
+                return;
+            }
+            if action == KeyAction::MountHostPath {
+                self.mount_into_selected_pod_owner();
+                return;
+            }
+            if action == KeyAction::UnmountHostPath {
+                self.unmount_selected_pod_owner();
+                return;
+            }
+            if action == KeyAction::DiagnosePod {
+                self.diagnose_selected_pod();
+                return;
+            }
+            if action == KeyAction::PodTop {
+                self.open_pod_top();
+                return;
+            }
+            if action == KeyAction::PodShellPane {
+                self.open_selected_pod_in_pane(PaneCommand::Shell);
+                return;
+            }
+            if action == KeyAction::PodLogsPane {
+                self.open_selected_pod_in_pane(PaneCommand::Logs);
+                return;
             }
             // Detail panel open: PageUp/PageDown scroll
             if self.pod_detail_panel.is_open() {
-                match code {
-                    KeyCode::PageUp => {
+                match action {
+                    KeyAction::PageUp => {
                         self.pod_detail_panel.scroll_up();
                         return;
                     }
-                    KeyCode::PageDown => {
+                    KeyAction::PageDown => {
                         let h = self.detail_visible_height();
                         self.pod_detail_panel.scroll_down(h);
                         return;
@@ -401,20 +804,19 @@ impl App {
 
         // Handle actions via keybinding resolver
         match action {
-            KeyAction::Quit => {
-                self.should_quit = true;
-            }
+            KeyAction::Quit => self.request_quit(),
             KeyAction::Help => {
-                self.mode = AppMode::Help;
+                self.open_help(context);
             }
             KeyAction::Refresh => {
                 self.spawn_status_check();
-                self.spawn_ingress_refresh();
-                self.spawn_missing_hosts_check();
-                self.spawn_port_forwards_check();
-                // Reset scheduler timers for tasks we just triggered
                 self.scheduler
-                    .mark_run_multiple(&[RefreshTask::IngressRefresh, RefreshTask::HostsCheck]);
+                    .request_multiple(&[RefreshTask::IngressRefresh, RefreshTask::HostsCheck]);
+                // Nothing else refreshes pods and volumes in safe mode
+                if self.safe_mode {
+                    self.scheduler
+                        .request_multiple(&[RefreshTask::StatsRefresh, RefreshTask::VolumeRefresh]);
+                }
             }
             KeyAction::CommandPalette => {
                 self.command_palette.reset();
@@ -423,6 +825,15 @@ impl App {
             KeyAction::UpdateHosts => {
                 self.trigger_manual_hosts_update();
             }
+            KeyAction::ShowOutput => {
+                self.show_run_output();
+            }
+            KeyAction::ReconcileTraefik => {
+                self.reconcile_traefik();
+            }
+            KeyAction::OpenWhenReady => {
+                self.open_selected_ingress_when_ready();
+            }
             KeyAction::MoveUp => {
                 for _ in 0..count {
                     self.handle_up();
@@ -445,23 +856,59 @@ impl App {
             KeyAction::Execute => {
                 self.handle_enter();
             }
+            KeyAction::Search => {
+                if self.focus == FocusArea::Content {
+                    self.menu.enter_search_mode();
+                }
+            }
             KeyAction::CustomCommand(path) => {
                 self.execute_custom_command(&path);
             }
-            KeyAction::Cancel | KeyAction::None => {}
-        }
-
-        // Handle '/' to enter search mode in menu (after action handling to avoid conflicts)
-        if let KeyCode::Char('/') = code {
-            if self.focus == FocusArea::Content {
-                self.menu.enter_search_mode();
-            }
+            // Handled above before count prefixes / panel shortcuts
+            KeyAction::PageUp
+            | KeyAction::PageDown
+            | KeyAction::GrowMenu
+            | KeyAction::ShrinkMenu
+            | KeyAction::CycleLayout
+            | KeyAction::ZoomPanel
+            | KeyAction::JumpBreadcrumb
+            | KeyAction::PreviewCommand
+            | KeyAction::ExpandAll
+            | KeyAction::CollapseAll
+            | KeyAction::FocusMenu
+            | KeyAction::FocusPods
+            | KeyAction::FocusActions
+            | KeyAction::PodLogs
+            | KeyAction::PodDescribe
+            | KeyAction::PodTimeline
+            | KeyAction::PodVolumes
+            | KeyAction::PodShell
+            | KeyAction::PodCapture
+            | KeyAction::EditResource
+            | KeyAction::OverrideImage
+            | KeyAction::MountHostPath
+            | KeyAction::UnmountHostPath
+            | KeyAction::DiagnosePod
+            | KeyAction::PodTop
+            | KeyAction::PodShellPane
+            | KeyAction::PodLogsPane
+            // Only bound in popup / form contexts
+            | KeyAction::Close
+            | KeyAction::NextField
+            | KeyAction::PrevField
+            | KeyAction::Apply
+            | KeyAction::ForceApply
+            | KeyAction::SortTable
+            | KeyAction::ReverseSort
+            | KeyAction::ToggleRaw
+            | KeyAction::Cancel
+            | KeyAction::None => {}
         }
     }
 
     pub(super) fn handle_mouse(&mut self, mouse: MouseEvent) {
         // Only handle clicks in normal mode
-        if self.mode != AppMode::Normal || self.is_executing {
+        if self.mode != AppMode::Normal {
             return;
         }
 
@@ -479,6 +926,32 @@ impl App {
 
         // Check if click is in action bar
         if y >= layout.action_bar.y && y < layout.action_bar.y + layout.action_bar.height {
+            let bar_x = x.saturating_sub(layout.action_bar.x) as usize;
+            match self
+                .action_bar
+                .link_at_x(bar_x, layout.action_bar.width as usize)
+            {
+                Some(BarLink::Sort(sort)) => {
+                    self.focus_pods_sorted(sort);
+                    return;
+                }
+                Some(BarLink::ClusterHealth) => {
+                    self.open_failing_component();
+                    return;
+                }
+                Some(BarLink::TraefikDrift) => {
+                    self.show_traefik_drift();
+                    return;
+                }
+                None => {}
+            }
+            if let Some(index) = self
+                .action_bar
+                .workspace_at_x(x.saturating_sub(layout.action_bar.x) as usize)
+            {
+                self.switch_workspace_to(index);
+                return;
+            }
             self.focus = FocusArea::ActionBar;
             if let Some(action_index) = self
                 .action_bar
@@ -498,7 +971,10 @@ impl App {
         {
             self.focus = FocusArea::Content;
             let menu_y = (y - layout.menu.y).saturating_sub(1) as usize;
-            if self.menu.select_at_row(menu_y) {
+            // The breadcrumb line opens the jump picker
+            if menu_y == 0 && !self.menu.is_search_mode() {
+                self.open_breadcrumb_jump();
+            } else if self.menu.select_at_row(menu_y) {
                 if let Some(item) = self.menu.selected_item() {
                     if item.has_children {
                         self.menu.toggle();
@@ -510,37 +986,106 @@ impl App {
         }
     }
 
-    fn handle_input_key(&mut self, code: KeyCode, _modifiers: KeyModifiers) {
-        match code {
-            KeyCode::Esc => {
+    /// Offer the groups and the breadcrumb's siblings to jump to
+    fn open_breadcrumb_jump(&mut self) {
+        let targets = self.menu.jump_targets();
+        if targets.is_empty() {
+            return;
+        }
+        self.breadcrumb_picker.open(targets);
+        self.mode = AppMode::BreadcrumbJump;
+    }
+
+    /// Show what the selected command will run, to run it from there
+    fn open_command_preview(&mut self) {
+        if self.focus != FocusArea::Content {
+            return;
+        }
+        let Some(cmd) = self.menu.selected_item().and_then(|i| i.command.clone()) else {
+            return;
+        };
+        self.command_preview.open(cmd);
+        self.mode = AppMode::CommandPreview;
+    }
+
+    /// Context used to resolve keys in the current mode and focus
+    fn key_context(&self) -> KeyContext {
+        match self.mode {
+            AppMode::OutputPopup => KeyContext::OutputPopup,
+            AppMode::Input => KeyContext::Input,
+            AppMode::CommandPalette => KeyContext::Palette,
+            AppMode::Normal if self.focus == FocusArea::PodStats && !self.is_cluster_stopped() => {
+                KeyContext::PodStats
+            }
+            _ => KeyContext::Normal,
+        }
+    }
+
+    /// Quit, unless popup commands are still running or queued
+    fn request_quit(&mut self) {
+        let active: Vec<String> = self.jobs.active().map(|j| j.title().to_string()).collect();
+        if let Some(latest) = active.last() {
+            let key = self
+                .keybinding_resolver
+                .get_binding_display(&KeyAction::Cancel)
+                .unwrap_or("Ctrl+c")
+                .to_string();
+            let what = if active.len() == 1 {
+                format!("'{}' is", latest)
+            } else {
+                format!("{} commands are", active.len())
+            };
+            self.toasts.push(OutputLine::warning(format!(
+                "{} still running; cancel with {} before quitting",
+                what, key
+            )));
+            return;
+        }
+        self.should_quit = true;
+    }
+
+    /// Show help for a context, returning to the current mode when closed
+    pub(super) fn open_help(&mut self, context: KeyContext) {
+        self.help_overlay.set_context(context);
+        self.help_return_mode = if self.mode == AppMode::OutputPopup {
+            AppMode::OutputPopup
+        } else {
+            AppMode::Normal
+        };
+        self.mode = AppMode::Help;
+    }
+
+    fn handle_input_key(&mut self, code: KeyCode, action: KeyAction) {
+        match action {
+            KeyAction::Close => {
                 self.mode = AppMode::Normal;
                 self.input_form.clear();
                 self.pending_command = None;
+                self.pending_form = None;
                 self.output.add_info("Input cancelled");
             }
-            KeyCode::Tab => self.input_form.focus_next(),
-            KeyCode::BackTab => self.input_form.focus_prev(),
-            KeyCode::Up => {
+            KeyAction::NextField => self.input_form.focus_next(),
+            KeyAction::PrevField => self.input_form.focus_prev(),
+            KeyAction::MoveUp => {
                 if self.input_form.focused_field_uses_vertical_keys() {
                     self.input_form.move_option_up();
                 } else {
                     self.input_form.focus_prev();
                 }
             }
-            KeyCode::Down => {
+            KeyAction::MoveDown => {
                 if self.input_form.focused_field_uses_vertical_keys() {
                     self.input_form.move_option_down();
                 } else {
                     self.input_form.focus_next();
                 }
             }
-            KeyCode::Left => self.input_form.move_cursor_left(),
-            KeyCode::Right => self.input_form.move_cursor_right(),
-            KeyCode::Backspace => self.input_form.handle_backspace(),
-            KeyCode::Char(' ') if self.input_form.focused_field_is_multi_select() => {
-                self.input_form.toggle_multi_select();
+            KeyAction::MoveLeft => self.input_form.move_cursor_left(),
+            KeyAction::MoveRight => self.input_form.move_cursor_right(),
+            KeyAction::Help => {
+                // Leaving the form for help would drop the typed values
             }
-            KeyCode::Enter => {
+            KeyAction::Execute => {
                 if self.input_form.is_submit_focused() {
                     if self.input_form.validate() {
                         self.submit_input();
@@ -551,8 +1096,14 @@ impl App {
                     self.input_form.focus_next();
                 }
             }
-            KeyCode::Char(c) => self.input_form.handle_char(c),
-            _ => {}
+            _ => match code {
+                KeyCode::Backspace => self.input_form.handle_backspace(),
+                KeyCode::Char(' ') if self.input_form.focused_field_is_multi_select() => {
+                    self.input_form.toggle_multi_select();
+                }
+                KeyCode::Char(c) => self.input_form.handle_char(c),
+                _ => {}
+            },
         }
     }
 
@@ -722,7 +1273,7 @@ impl App {
     }
 
     /// Check if the cluster is in a stopped state (not running or starting)
-    fn is_cluster_stopped(&self) -> bool {
+    pub(super) fn is_cluster_stopped(&self) -> bool {
         !matches!(
             self.cluster_status,
             ClusterStatus::Running | ClusterStatus::Starting
@@ -0,0 +1,52 @@
+//! Hooks view actions and `on_file_change` watchers
+
+use tokio::sync::mpsc;
+
+use super::{App, AppMessage, AppMode};
+use crate::hooks::FileWatchers;
+use crate::ui::components::OutputLine;
+
+impl App {
+    /// Show the selected hook run's own output in the output popup
+    pub(super) fn show_selected_hook_run(&mut self) {
+        let Some(run) = self.hook_runs_view.selected().cloned() else {
+            return;
+        };
+        self.clear_output_popup();
+        self.output_popup.set_title(format!(
+            "Hook {} ({}, {})",
+            run.name,
+            run.event.as_str(),
+            run.started.format("%H:%M:%S")
+        ));
+        self.output_popup.set_actions(None);
+        if run.output.is_empty() {
+            self.output_popup.add_line(OutputLine::info("(no output)"));
+        }
+        for line in run.output {
+            self.output_popup.add_line(line);
+        }
+        self.mode = AppMode::OutputPopup;
+    }
+
+    /// Watch the files of the config's `on_file_change` hooks, replacing
+    /// the previous watchers. Hook results go to the main output.
+    pub(super) fn start_file_watchers(&mut self) {
+        self.file_watchers = None;
+        if self.config.hooks.on_file_change.is_empty() {
+            return;
+        }
+        let (output_tx, mut output_rx) = mpsc::channel::<OutputLine>(100);
+        let message_tx = self.message_tx.clone();
+        tokio::spawn(async move {
+            while let Some(line) = output_rx.recv().await {
+                let _ = message_tx.send(AppMessage::OutputLine(line)).await;
+            }
+        });
+        let (watchers, warnings) = FileWatchers::start(&self.config.hooks, output_tx);
+        for warning in warnings {
+            self.report(OutputLine::warning(warning));
+        }
+        self.file_watchers = Some(watchers);
+    }
+}
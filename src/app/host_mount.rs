@@ -0,0 +1,249 @@
+//! Host directory mounts for the Deployment owning the selected pod
+//!
+//! Asks for the container, the host directory and the path to mount it at,
+//! validates the directory against `host_mounts.allowed_roots`, patches the
+//! Deployment and follows the rollout in the output popup. Unmounting
+//! removes every mount added this way.
+
+use std::collections::HashMap;
+
+use super::image_override::follow_rollout;
+use super::{App, AppMessage, AppMode, PendingForm};
+use crate::config::{InputDefinition, InputSpec};
+use crate::k8s::host_mount::{
+    mount_host_path, unmount_host_paths, validate_host_path, validate_mount_path,
+};
+use crate::k8s::image_override::deployment_images;
+use crate::k8s::resource_edit::owning_deployment;
+use crate::k8s::ResourceRef;
+use crate::ui::components::OutputLine;
+
+/// Mount path offered in the form
+const DEFAULT_MOUNT_PATH: &str = "/app";
+
+/// Deployment and containers waiting for the form to be submitted
+pub(super) struct PendingHostMount {
+    deployment: ResourceRef,
+    containers: Vec<String>,
+}
+
+impl App {
+    /// Look up the selected pod's Deployment and its containers
+    pub(super) fn mount_into_selected_pod_owner(&mut self) {
+        let Some(pod) = self.pod_stats.selected_pod() else {
+            return;
+        };
+        let (pod_name, namespace) = (pod.name.clone(), pod.namespace.clone());
+        self.open_host_mount_popup();
+        let Some(client) = self.k8s_client.as_ref().map(|c| c.client().clone()) else {
+            self.report(OutputLine::error("Cluster is not running"));
+            return;
+        };
+        self.report(OutputLine::info(format!(
+            "Looking up the Deployment owning {}/{}...",
+            namespace, pod_name
+        )));
+
+        let message_tx = self.message_tx.clone();
+        tokio::spawn(async move {
+            let result = async {
+                let deployment = owning_deployment(&client, &namespace, &pod_name).await?;
+                let containers = deployment_images(&client, &deployment)
+                    .await?
+                    .into_iter()
+                    .map(|c| c.container)
+                    .collect();
+                anyhow::Ok((deployment, containers))
+            }
+            .await
+            .map_err(|e| format!("{:#}", e));
+            let _ = message_tx
+                .send(AppMessage::HostMountTargetsLoaded(result))
+                .await;
+        });
+    }
+
+    /// Ask where to mount what
+    pub(super) fn handle_host_mount_targets(
+        &mut self,
+        result: Result<(ResourceRef, Vec<String>), String>,
+    ) {
+        let (deployment, containers) = match result {
+            Ok(loaded) => loaded,
+            Err(e) => {
+                self.report(OutputLine::error(e));
+                return;
+            }
+        };
+        let current_dir = std::env::current_dir()
+            .map(|d| d.display().to_string())
+            .unwrap_or_default();
+        let mut order = Vec::new();
+        let mut inputs: HashMap<String, InputDefinition> = HashMap::new();
+        if containers.len() > 1 {
+            order.push("container".to_string());
+            inputs.insert(
+                "container".to_string(),
+                InputDefinition::Detailed(InputSpec::Select {
+                    prompt: "Container:".to_string(),
+                    options: containers.clone(),
+                    default: None,
+                }),
+            );
+        }
+        order.push("host_path".to_string());
+        inputs.insert(
+            "host_path".to_string(),
+            InputDefinition::Detailed(InputSpec::Text {
+                prompt: "Host directory:".to_string(),
+                default: current_dir,
+                required: true,
+            }),
+        );
+        order.push("mount_path".to_string());
+        inputs.insert(
+            "mount_path".to_string(),
+            InputDefinition::Detailed(InputSpec::Text {
+                prompt: "Mount at:".to_string(),
+                default: DEFAULT_MOUNT_PATH.to_string(),
+                required: true,
+            }),
+        );
+        self.input_form
+            .setup(format!("Mount Host Path · {}", deployment), &inputs, &order);
+        self.pending_host_mount = Some(PendingHostMount {
+            deployment,
+            containers,
+        });
+        self.pending_form = Some(PendingForm::HostMount);
+        self.mode = AppMode::Input;
+    }
+
+    /// Validate the paths, patch the mount in and follow the rollout
+    pub(super) fn submit_host_mount_form(&mut self, values: &HashMap<String, String>) {
+        let Some(pending) = self.pending_host_mount.take() else {
+            return;
+        };
+        let container = values
+            .get("container")
+            .cloned()
+            .or_else(|| pending.containers.first().cloned())
+            .unwrap_or_default();
+        self.open_host_mount_popup();
+        let roots = self.config.host_mounts.roots();
+        let paths = validate_host_path(
+            values
+                .get("host_path")
+                .map(String::as_str)
+                .unwrap_or_default(),
+            &roots,
+        )
+        .and_then(|host| {
+            let mount = validate_mount_path(
+                values
+                    .get("mount_path")
+                    .map(String::as_str)
+                    .unwrap_or_default(),
+            )?;
+            Ok((host, mount))
+        });
+        let (host_path, mount_path) = match paths {
+            Ok(paths) => paths,
+            Err(e) => {
+                self.report(OutputLine::error(format!("{:#}", e)));
+                return;
+            }
+        };
+        let Some(client) = self.k8s_client.as_ref().map(|c| c.client().clone()) else {
+            self.report(OutputLine::error("Cluster is not running"));
+            return;
+        };
+        let deployment = pending.deployment;
+        self.report(OutputLine::info(format!(
+            "Mounting {} at {} in container {} of {}...",
+            host_path.display(),
+            mount_path,
+            container,
+            deployment
+        )));
+
+        let message_tx = self.message_tx.clone();
+        tokio::spawn(async move {
+            if let Err(e) =
+                mount_host_path(&client, &deployment, &container, &host_path, &mount_path).await
+            {
+                let _ = message_tx
+                    .send(AppMessage::OutputLine(OutputLine::error(format!(
+                        "{:#}",
+                        e
+                    ))))
+                    .await;
+                return;
+            }
+            let done = format!(
+                "{} rolled out with {} mounted at {}",
+                deployment,
+                host_path.display(),
+                mount_path
+            );
+            follow_rollout(&client, &deployment, &message_tx, done).await;
+        });
+    }
+
+    /// Remove the host mounts of the selected pod's Deployment
+    pub(super) fn unmount_selected_pod_owner(&mut self) {
+        let Some(pod) = self.pod_stats.selected_pod() else {
+            return;
+        };
+        let (pod_name, namespace) = (pod.name.clone(), pod.namespace.clone());
+        self.open_host_mount_popup();
+        let Some(client) = self.k8s_client.as_ref().map(|c| c.client().clone()) else {
+            self.report(OutputLine::error("Cluster is not running"));
+            return;
+        };
+
+        let message_tx = self.message_tx.clone();
+        tokio::spawn(async move {
+            let send = |line: OutputLine| {
+                let message_tx = message_tx.clone();
+                async move {
+                    let _ = message_tx.send(AppMessage::OutputLine(line)).await;
+                }
+            };
+            let result = async {
+                let deployment = owning_deployment(&client, &namespace, &pod_name).await?;
+                let removed = unmount_host_paths(&client, &deployment).await?;
+                anyhow::Ok((deployment, removed))
+            }
+            .await;
+            match result {
+                Err(e) => send(OutputLine::error(format!("{:#}", e))).await,
+                Ok((deployment, 0)) => {
+                    send(OutputLine::info(format!(
+                        "{} has no host mounts",
+                        deployment
+                    )))
+                    .await
+                }
+                Ok((deployment, removed)) => {
+                    send(OutputLine::info(format!(
+                        "Removed {} host mount(s) from {}",
+                        removed, deployment
+                    )))
+                    .await;
+                    let done = format!("{} rolled out without host mounts", deployment);
+                    follow_rollout(&client, &deployment, &message_tx, done).await;
+                }
+            }
+        });
+    }
+
+    fn open_host_mount_popup(&mut self) {
+        self.staged_resource_edit = None;
+        self.stuck_namespace = None;
+        self.clear_output_popup();
+        self.output_popup.set_title("Mount Host Path".to_string());
+        self.output_popup.set_actions(None);
+        self.mode = AppMode::OutputPopup;
+    }
+}
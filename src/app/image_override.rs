@@ -0,0 +1,247 @@
+//! Image override for the Deployment owning the selected pod
+//!
+//! Offers the tags of the container's image repository that exist on the
+//! local Docker daemon, patches the Deployment and follows the rollout in
+//! the output popup.
+
+use std::collections::HashMap;
+use std::time::Duration;
+
+use tokio::sync::mpsc;
+
+use super::{App, AppMessage, AppMode, PendingForm};
+use crate::cluster::DockerManager;
+use crate::config::{InputDefinition, InputSpec};
+use crate::k8s::image_override::{
+    deployment_images, image_repository, local_image_name, rollout_status, set_container_image,
+    RolloutStatus,
+};
+use crate::k8s::resource_edit::owning_deployment;
+use crate::k8s::ResourceRef;
+use crate::ui::components::OutputLine;
+
+/// How long to follow a rollout before giving up
+const ROLLOUT_TIMEOUT: Duration = Duration::from_secs(180);
+
+/// How often the rollout status is polled
+const ROLLOUT_POLL: Duration = Duration::from_secs(2);
+
+/// A container and the local tags it can be switched to
+#[derive(Debug, Clone)]
+pub struct ImageChoice {
+    pub container: String,
+    pub image: String,
+    pub local_tags: Vec<String>,
+}
+
+/// Picker entries waiting for the form to be submitted
+pub(super) struct PendingImageOverride {
+    deployment: ResourceRef,
+    /// (label shown in the form, container, image)
+    options: Vec<(String, String, String)>,
+}
+
+impl App {
+    /// Look up the selected pod's Deployment and the local tags of its images
+    pub(super) fn override_selected_pod_image(&mut self) {
+        let Some(pod) = self.pod_stats.selected_pod() else {
+            return;
+        };
+        let (pod_name, namespace) = (pod.name.clone(), pod.namespace.clone());
+        self.open_image_override_popup();
+        let Some(client) = self.k8s_client.as_ref().map(|c| c.client().clone()) else {
+            self.report(OutputLine::error("Cluster is not running"));
+            return;
+        };
+        self.report(OutputLine::info(format!(
+            "Looking up the Deployment owning {}/{}...",
+            namespace, pod_name
+        )));
+
+        let message_tx = self.message_tx.clone();
+        tokio::spawn(async move {
+            let result = async {
+                let deployment = owning_deployment(&client, &namespace, &pod_name).await?;
+                let images = deployment_images(&client, &deployment).await?;
+                let docker = DockerManager::from_default_socket()?;
+                let mut choices = Vec::new();
+                for c in images {
+                    let pattern = format!("{}:", image_repository(&c.image));
+                    let mut local_tags = docker.list_images_by_pattern(&pattern).await?;
+                    local_tags.sort();
+                    local_tags.dedup();
+                    choices.push(ImageChoice {
+                        container: c.container,
+                        image: c.image,
+                        local_tags,
+                    });
+                }
+                anyhow::Ok((deployment, choices))
+            }
+            .await
+            .map_err(|e| format!("{:#}", e));
+            let _ = message_tx
+                .send(AppMessage::ImageChoicesLoaded(result))
+                .await;
+        });
+    }
+
+    /// Offer the local tags in a form, or explain why there are none
+    pub(super) fn handle_image_choices(
+        &mut self,
+        result: Result<(ResourceRef, Vec<ImageChoice>), String>,
+    ) {
+        let (deployment, choices) = match result {
+            Ok(loaded) => loaded,
+            Err(e) => {
+                self.report(OutputLine::error(e));
+                return;
+            }
+        };
+        let single = choices.len() == 1;
+        let mut options = Vec::new();
+        for choice in &choices {
+            let current = local_image_name(&choice.image);
+            for tag in choice.local_tags.iter().filter(|t| **t != current) {
+                let label = if single {
+                    tag.clone()
+                } else {
+                    format!("{} → {}", choice.container, tag)
+                };
+                options.push((label, choice.container.clone(), tag.clone()));
+            }
+        }
+
+        if options.is_empty() {
+            for choice in &choices {
+                self.report(OutputLine::warning(format!(
+                    "No other local tags of {} (container {} runs {})",
+                    image_repository(&choice.image),
+                    choice.container,
+                    choice.image
+                )));
+            }
+            self.report(OutputLine::info(
+                "Build and tag an image locally first, e.g. docker build -t <repo>:dev .",
+            ));
+            return;
+        }
+
+        let order = vec!["image".to_string()];
+        let inputs: HashMap<String, InputDefinition> = HashMap::from([(
+            "image".to_string(),
+            InputDefinition::Detailed(InputSpec::Select {
+                prompt: if single {
+                    format!("Image for {}:", choices[0].container)
+                } else {
+                    "Container → image:".to_string()
+                },
+                options: options.iter().map(|(label, ..)| label.clone()).collect(),
+                default: None,
+            }),
+        )]);
+        self.input_form
+            .setup(format!("Override Image · {}", deployment), &inputs, &order);
+        self.pending_image_override = Some(PendingImageOverride {
+            deployment,
+            options,
+        });
+        self.pending_form = Some(PendingForm::ImageOverride);
+        self.mode = AppMode::Input;
+    }
+
+    /// Patch the chosen image in and follow the rollout
+    pub(super) fn submit_image_override_form(&mut self, values: &HashMap<String, String>) {
+        let Some(pending) = self.pending_image_override.take() else {
+            return;
+        };
+        let selected = values.get("image").map(String::as_str).unwrap_or_default();
+        let Some((_, container, image)) = pending
+            .options
+            .into_iter()
+            .find(|(label, ..)| label == selected)
+        else {
+            self.mode = AppMode::Normal;
+            return;
+        };
+        self.open_image_override_popup();
+        let Some(client) = self.k8s_client.as_ref().map(|c| c.client().clone()) else {
+            self.report(OutputLine::error("Cluster is not running"));
+            return;
+        };
+        let deployment = pending.deployment;
+        self.report(OutputLine::info(format!(
+            "Setting container {} of {} to {}...",
+            container, deployment, image
+        )));
+
+        let message_tx = self.message_tx.clone();
+        tokio::spawn(async move {
+            let send = |line: OutputLine| {
+                let message_tx = message_tx.clone();
+                async move {
+                    let _ = message_tx.send(AppMessage::OutputLine(line)).await;
+                }
+            };
+            if let Err(e) = set_container_image(&client, &deployment, &container, &image).await {
+                send(OutputLine::error(format!("{:#}", e))).await;
+                return;
+            }
+            let done = format!("{} rolled out with {}", deployment, image);
+            follow_rollout(&client, &deployment, &message_tx, done).await;
+        });
+    }
+
+    fn open_image_override_popup(&mut self) {
+        self.staged_resource_edit = None;
+        self.stuck_namespace = None;
+        self.clear_output_popup();
+        self.output_popup.set_title("Override Image".to_string());
+        self.output_popup.set_actions(None);
+        self.mode = AppMode::OutputPopup;
+    }
+}
+
+/// Report a Deployment's rollout progress until it completes or times out
+pub(super) async fn follow_rollout(
+    client: &kube::Client,
+    deployment: &ResourceRef,
+    message_tx: &mpsc::Sender<AppMessage>,
+    done: String,
+) {
+    let send = |line: OutputLine| async move {
+        let _ = message_tx.send(AppMessage::OutputLine(line)).await;
+    };
+    send(OutputLine::info("Waiting for the rollout...")).await;
+
+    let deadline = tokio::time::Instant::now() + ROLLOUT_TIMEOUT;
+    let mut last: Option<RolloutStatus> = None;
+    loop {
+        match rollout_status(client, deployment).await {
+            Ok(status) if status.is_complete() => {
+                send(OutputLine::success(done)).await;
+                return;
+            }
+            Ok(status) => {
+                if last != Some(status) {
+                    send(OutputLine::info(status.describe())).await;
+                    last = Some(status);
+                }
+            }
+            Err(e) => {
+                send(OutputLine::error(format!("{:#}", e))).await;
+                return;
+            }
+        }
+        if tokio::time::Instant::now() >= deadline {
+            send(OutputLine::warning(format!(
+                "Rollout of {} is not done after {}s; check the new pods for pull or crash errors",
+                deployment,
+                ROLLOUT_TIMEOUT.as_secs()
+            )))
+            .await;
+            return;
+        }
+        tokio::time::sleep(ROLLOUT_POLL).await;
+    }
+}
@@ -0,0 +1,307 @@
+//! Popup commands as jobs
+//!
+//! Every command that streams into the output popup (cluster actions, custom
+//! commands, the /etc/hosts update) is a job with its own output and
+//! cancellation token. Jobs run one at a time in the order they were
+//! started, except commands with `concurrency: parallel`, which start right
+//! away next to whatever is running.
+
+use std::path::PathBuf;
+
+use tokio_util::sync::CancellationToken;
+
+use crate::cluster::{ChaosAction, ClusterComponent};
+use crate::config::{BuildConfig, CommandEntry, SeedConfig};
+use crate::ui::components::{ClusterAction, Output, OutputLimit, OutputLine};
+
+/// Finished jobs kept so their output can be reopened
+const MAX_FINISHED_JOBS: usize = 10;
+
+/// Identifies a job for its output and completion messages
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub struct JobId(u64);
+
+/// What a job runs once it leaves the queue
+#[derive(Debug, Clone)]
+pub(super) enum JobKind {
+    Cluster(ClusterAction),
+    /// A setup step repeated on the running cluster
+    Redeploy(ClusterComponent),
+    /// A custom command with its placeholders filled in
+    Command(Box<CommandEntry>),
+    HostsUpdate,
+    HostsRestore,
+    ManifestDiff,
+    /// An image from `builds:`
+    Build(Box<BuildConfig>),
+    /// Namespaces to copy the registry pull secret into
+    PullSecret(Vec<String>),
+    /// A bundle from `seeds:`
+    Seed(Box<SeedConfig>),
+    /// A tool from the Chaos group
+    Chaos(ChaosAction),
+    /// Kills pods from `chaos.pod_killer` until cancelled
+    PodKiller,
+    /// Save the running cluster as a snapshot with this name
+    SaveSnapshot(String),
+    /// Recreate the cluster from this snapshot image
+    StartFromSnapshot(String),
+    /// Save the data volumes to this archive, then destroy the cluster
+    ExportAndDestroy(PathBuf),
+    /// Follows until cancelled or the container stops
+    ServerLogs,
+}
+
+impl JobKind {
+    /// Changes the cluster's lifecycle, /etc/hosts or snapshots, which only
+    /// the instance in control of the cluster may do
+    pub(super) fn needs_control(&self) -> bool {
+        match self {
+            JobKind::Cluster(action) => matches!(
+                action,
+                ClusterAction::Start
+                    | ClusterAction::Stop
+                    | ClusterAction::Restart
+                    | ClusterAction::Destroy
+                    | ClusterAction::DeleteSnapshots
+            ),
+            JobKind::Redeploy(_)
+            | JobKind::HostsUpdate
+            | JobKind::HostsRestore
+            | JobKind::SaveSnapshot(_)
+            | JobKind::StartFromSnapshot(_)
+            | JobKind::ExportAndDestroy(_) => true,
+            _ => false,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum JobState {
+    Queued,
+    Running,
+    Succeeded,
+    Failed,
+    Cancelled,
+}
+
+impl JobState {
+    pub(super) fn label(self) -> &'static str {
+        match self {
+            JobState::Queued => "queued",
+            JobState::Running => "running",
+            JobState::Succeeded => "done",
+            JobState::Failed => "failed",
+            JobState::Cancelled => "cancelled",
+        }
+    }
+}
+
+pub(super) struct Job {
+    pub id: JobId,
+    pub kind: JobKind,
+    /// Runs next to other jobs instead of waiting for them
+    pub parallel: bool,
+    pub state: JobState,
+    /// Output lines; the title is the job's name
+    pub output: Output,
+    pub cancel: CancellationToken,
+}
+
+impl Job {
+    pub(super) fn title(&self) -> &str {
+        self.output.title()
+    }
+
+    /// Queued or running
+    pub(super) fn is_active(&self) -> bool {
+        matches!(self.state, JobState::Queued | JobState::Running)
+    }
+}
+
+/// Jobs in the order they were started
+#[derive(Default)]
+pub(super) struct Jobs {
+    jobs: Vec<Job>,
+    next_id: u64,
+}
+
+impl Jobs {
+    /// Add a queued job; [`Jobs::take_startable`] says when it may run
+    pub(super) fn submit(
+        &mut self,
+        title: String,
+        kind: JobKind,
+        parallel: bool,
+        limit: OutputLimit,
+    ) -> JobId {
+        let id = JobId(self.next_id);
+        self.next_id += 1;
+        let mut output = Output::new();
+        output.set_title(title);
+        output.set_limit(limit);
+        self.jobs.push(Job {
+            id,
+            kind,
+            parallel,
+            state: JobState::Queued,
+            output,
+            cancel: CancellationToken::new(),
+        });
+        id
+    }
+
+    /// Mark the queued jobs that may start now as running and return them:
+    /// parallel jobs always, the oldest other one when no other one runs
+    pub(super) fn take_startable(&mut self) -> Vec<JobId> {
+        let mut lane_busy = self
+            .jobs
+            .iter()
+            .any(|j| j.state == JobState::Running && !j.parallel);
+        let mut started = Vec::new();
+        for job in self.jobs.iter_mut() {
+            if job.state != JobState::Queued || (!job.parallel && lane_busy) {
+                continue;
+            }
+            lane_busy |= !job.parallel;
+            job.state = JobState::Running;
+            started.push(job.id);
+        }
+        started
+    }
+
+    /// Record how a job ended and forget the oldest finished ones
+    pub(super) fn finish(&mut self, id: JobId, state: JobState) {
+        if let Some(job) = self.get_mut(id) {
+            job.state = state;
+        }
+        let finished = self.jobs.iter().filter(|j| !j.is_active()).count();
+        let mut excess = finished.saturating_sub(MAX_FINISHED_JOBS);
+        self.jobs.retain(|j| {
+            if excess > 0 && !j.is_active() {
+                excess -= 1;
+                false
+            } else {
+                true
+            }
+        });
+    }
+
+    pub(super) fn get(&self, id: JobId) -> Option<&Job> {
+        self.jobs.iter().find(|j| j.id == id)
+    }
+
+    pub(super) fn get_mut(&mut self, id: JobId) -> Option<&mut Job> {
+        self.jobs.iter_mut().find(|j| j.id == id)
+    }
+
+    pub(super) fn add_line(&mut self, id: JobId, line: OutputLine) {
+        if let Some(job) = self.get_mut(id) {
+            job.output.add_line(line);
+        }
+    }
+
+    /// Queued and running jobs, oldest first
+    pub(super) fn active(&self) -> impl Iterator<Item = &Job> {
+        self.jobs.iter().filter(|j| j.is_active())
+    }
+
+    pub(super) fn latest(&self) -> Option<&Job> {
+        self.jobs.last()
+    }
+
+    /// The job before or after `id`, wrapping around
+    pub(super) fn neighbour(&self, id: JobId, forward: bool) -> Option<JobId> {
+        let index = self.jobs.iter().position(|j| j.id == id)?;
+        let len = self.jobs.len();
+        let next = if forward {
+            (index + 1) % len
+        } else {
+            (index + len - 1) % len
+        };
+        Some(self.jobs[next].id)
+    }
+
+    /// 1-based position of a job and the number of jobs
+    pub(super) fn position(&self, id: JobId) -> Option<(usize, usize)> {
+        let index = self.jobs.iter().position(|j| j.id == id)?;
+        Some((index + 1, self.jobs.len()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn submit(jobs: &mut Jobs, parallel: bool) -> JobId {
+        jobs.submit(
+            String::new(),
+            JobKind::HostsUpdate,
+            parallel,
+            OutputLimit::default(),
+        )
+    }
+
+    fn state(jobs: &Jobs, id: JobId) -> JobState {
+        jobs.get(id).unwrap().state
+    }
+
+    #[test]
+    fn serial_jobs_wait_for_the_running_one_in_order() {
+        let mut jobs = Jobs::default();
+        let first = submit(&mut jobs, false);
+        let second = submit(&mut jobs, false);
+        let third = submit(&mut jobs, false);
+
+        assert_eq!(jobs.take_startable(), [first]);
+        assert!(jobs.take_startable().is_empty());
+        assert_eq!(state(&jobs, second), JobState::Queued);
+
+        jobs.finish(first, JobState::Succeeded);
+        assert_eq!(jobs.take_startable(), [second]);
+        jobs.finish(second, JobState::Failed);
+        assert_eq!(jobs.take_startable(), [third]);
+    }
+
+    #[test]
+    fn parallel_jobs_start_next_to_a_serial_one() {
+        let mut jobs = Jobs::default();
+        let serial = submit(&mut jobs, false);
+        let waiting = submit(&mut jobs, false);
+        let parallel = submit(&mut jobs, true);
+        assert_eq!(jobs.take_startable(), [serial, parallel]);
+
+        let later = submit(&mut jobs, true);
+        assert_eq!(jobs.take_startable(), [later]);
+        assert_eq!(state(&jobs, waiting), JobState::Queued);
+
+        // A running parallel job doesn't hold up the serial lane
+        jobs.finish(serial, JobState::Succeeded);
+        assert_eq!(jobs.take_startable(), [waiting]);
+        assert_eq!(state(&jobs, parallel), JobState::Running);
+    }
+
+    #[test]
+    fn pruning_keeps_active_jobs() {
+        let mut jobs = Jobs::default();
+        let running = submit(&mut jobs, false);
+        assert_eq!(jobs.take_startable(), [running]);
+        let queued = submit(&mut jobs, false);
+
+        let mut finished = Vec::new();
+        for _ in 0..MAX_FINISHED_JOBS + 3 {
+            let id = submit(&mut jobs, true);
+            jobs.take_startable();
+            jobs.finish(id, JobState::Succeeded);
+            finished.push(id);
+        }
+
+        assert_eq!(state(&jobs, running), JobState::Running);
+        assert_eq!(state(&jobs, queued), JobState::Queued);
+        assert_eq!(jobs.active().count(), 2);
+        // The oldest finished jobs are the ones dropped
+        assert!(finished[..3].iter().all(|&id| jobs.get(id).is_none()));
+        assert!(finished[3..].iter().all(|&id| jobs.get(id).is_some()));
+        assert_eq!(jobs.position(running), Some((1, MAX_FINISHED_JOBS + 2)));
+    }
+}
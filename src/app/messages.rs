@@ -2,35 +2,112 @@
 //!
 //! This module defines the AppMessage enum and the handle_message implementation.
 
+use crate::cluster::degraded::{self, HealthProblem};
 use crate::cluster::diagnostics::DiagnosticsReport;
+use crate::cluster::image_build::BuildProgress;
+use crate::cluster::instance_lock::{Control, HandoverRequest};
+use crate::cluster::k3s_versions::VersionChoice;
+use crate::cluster::runtime::RuntimeEntry;
+use crate::cluster::traefik_drift::TraefikDrift;
 use crate::cluster::{
-    ClusterStatus, ContainerPullProgress, ContainerStats, IngressEntry, IngressHealthStatus,
+    ClusterComponent, ClusterInfo, ClusterInput, ClusterState, ClusterStatus, ClusterTransition,
+    ContainerEvent, ContainerPullProgress, ContainerStats, HealthCheck, HostCapacity, IngressEntry,
+    IngressHealthStatus, PodProcess, PvDirectory, ReadinessState, ReadinessUpdate, ServiceInfo,
+    SnapshotInfo,
+};
+use crate::commands::CommandOutcome;
+use crate::config::{NotificationEvent, RefreshTask};
+use crate::crash::PanicReport;
+use crate::hooks::{HookCompletion, HookRun};
+use crate::k8s::{
+    ApplyOutcome, CanIAnswer, NamespaceDeletion, NamespaceDependencies, NamespacePolicies,
+    NamespaceQuotas, NamespaceRbac, PendingPodInfo, PodTimeline, PolicyViolation, PvcInfo,
+    ResourceRef, ShellSessionHandle,
 };
-use crate::config::RefreshTask;
-use crate::k8s::{PendingPodInfo, PodTimeline, PvcInfo, ShellSessionHandle};
 use crate::ui::components::{
-    ActivePortForward, ContainerPullInfo, DetailTab, OutputLine, PodStat, PodState,
+    ActivePortForward, ContainerPullInfo, DetailTab, OutputLine, PodStat, PodState, ResourceUsage,
 };
 use std::collections::{HashMap, HashSet};
+use std::time::{Duration, Instant};
 
-use super::{App, AppMode};
+use super::image_override::ImageChoice;
+use super::jobs::{JobKind, JobState};
+use super::{App, AppMode, JobId};
+use crate::k8s::pod_diagnosis::PodDiagnosis;
+use crate::smoke::SmokeReport;
 
 /// Async message types for communication between tasks and the app
 pub enum AppMessage {
-    /// Output line from a command
+    /// Output line for the output popup
     OutputLine(OutputLine),
 
-    /// Command completed with exit code
-    CommandComplete(i32),
+    /// Output line of a popup command job
+    RunOutput { job: JobId, line: OutputLine },
+
+    /// Job completed with exit code
+    CommandComplete { job: JobId, exit_code: i32 },
+
+    /// Custom command job finished and was judged by its success criteria
+    CommandFinished {
+        job: JobId,
+        command: String,
+        outcome: CommandOutcome,
+    },
 
     /// Cluster status update
     ClusterStatusUpdate(ClusterStatus),
 
+    /// The cluster lifecycle moved to another state
+    ClusterTransition(ClusterTransition),
+
+    /// Core deployments and nodes checked; the components that are down
+    ClusterHealthChecked(Vec<HealthProblem>),
+
+    /// The health check got no answer from the API, with why
+    ClusterApiFailed(String),
+
+    /// Something connected to an ingress port while the cluster was down,
+    /// or the ports couldn't be held
+    OnDemandConnection(Result<u16, String>),
+
+    /// Another k3dev asks this one to hand over control of the cluster
+    ControlRequested(HandoverRequest),
+
+    /// The k3dev this one attached to exited or handed over
+    ControlFreed,
+
+    /// Who controls the cluster, looked up again
+    ControlFound(Result<Control, String>),
+
+    /// An SSH forward to the share host closed, or failed
+    ShareEnded {
+        remote: String,
+        result: Result<(), String>,
+    },
+
+    /// An awaited ingress URL turned healthy, or the wait gave up
+    AwaitedUrlChecked { url: String, ready: bool },
+
+    /// A tunnel client reported the public URL of an ingress host
+    TunnelOpened { host: String, url: String },
+
+    /// A tunnel client stopped, or never came up
+    TunnelClosed {
+        host: String,
+        result: Result<(), String>,
+    },
+
+    /// Live Traefik config compared with k3dev's; how it differs, if it does
+    TraefikDriftChecked(Option<TraefikDrift>),
+
+    /// Containers started or stopped, as reported by the Docker events stream
+    ContainersChanged(Vec<ContainerEvent>),
+
     /// Ingress entries loaded
     IngressEntriesLoaded(Vec<IngressEntry>),
 
     /// Ingress health status updated
-    IngressHealthUpdated(HashMap<String, IngressHealthStatus>),
+    IngressHealthUpdated(HashMap<String, HealthCheck>),
 
     /// Missing hosts from /etc/hosts
     MissingHostsUpdated(HashSet<String>),
@@ -38,9 +115,15 @@ pub enum AppMessage {
     /// Pod stats updated (per-container stats)
     PodStatsUpdated(Vec<ContainerStats>),
 
+    /// Docker host capacity for the usage gauges
+    HostCapacityLoaded(HostCapacity),
+
     /// Active port forwards detected
     ActivePortForwardsUpdated(Vec<ActivePortForward>),
 
+    /// Host port the cluster container publishes the API on
+    ApiHostPortResolved(u16),
+
     /// Pending pods (waiting for image pulls, etc.)
     PendingPodsUpdated(Vec<PendingPodInfo>),
 
@@ -53,6 +136,100 @@ pub enum AppMessage {
     /// Diagnostics state update (sent incrementally as tests complete)
     DiagnosticsUpdated(DiagnosticsReport),
 
+    /// Cluster info snapshot for the Info view (None when gathering failed)
+    ClusterInfoLoaded(Option<Box<ClusterInfo>>),
+
+    /// Processes of the pod shown in the top popup
+    PodTopLoaded {
+        pod: String,
+        namespace: String,
+        processes: Result<Vec<PodProcess>, String>,
+    },
+
+    /// Services for the Services view
+    ServicesLoaded(Result<Vec<ServiceInfo>, String>),
+
+    /// Per-namespace policy evaluation for the Network Policies view
+    NetworkPoliciesLoaded(Result<Vec<NamespacePolicies>, String>),
+
+    /// Workloads and their dependencies for the Dependency Map view
+    DependencyMapLoaded(Result<Vec<NamespaceDependencies>, String>),
+
+    /// What the policy engine reports for the Policy Violations view
+    PolicyViolationsLoaded(Result<Vec<PolicyViolation>, String>),
+
+    /// ResourceQuotas and LimitRanges for the Resource Quotas view
+    QuotasLoaded(Result<Vec<NamespaceQuotas>, String>),
+
+    /// crictl's and Docker's containers for the Container Runtime view
+    RuntimeLoaded(Result<Vec<RuntimeEntry>, String>),
+
+    /// PV directories for the PV Data view
+    PvDirectoriesLoaded(Result<Vec<PvDirectory>, String>),
+    /// Snapshot images for the Snapshots view
+    SnapshotsLoaded(Result<Vec<SnapshotInfo>, String>),
+
+    /// A PV download or wipe finished; the line reports the result
+    PvActionFinished(OutputLine),
+
+    /// Recent k3s releases for the version picker and the setup wizard, and
+    /// the Docker host's architecture
+    K3sVersionsLoaded {
+        choices: Result<Vec<VersionChoice>, String>,
+        arch: Option<String>,
+    },
+
+    /// Stage progress of a running image build
+    BuildProgress { job: JobId, progress: BuildProgress },
+
+    /// The background work of a refresh task is done
+    RefreshFinished {
+        task: RefreshTask,
+        error: Option<String>,
+    },
+
+    /// A background task panicked
+    TaskPanicked(Box<PanicReport>),
+
+    /// ServiceAccounts, Roles and bindings for the RBAC inspector
+    RbacLoaded(Result<Vec<NamespaceRbac>, String>),
+
+    /// Namespaces to offer in the Delete Namespace form
+    NamespacesListed(Result<Vec<String>, String>),
+
+    /// Namespace created from the palette
+    NamespaceCreated {
+        name: String,
+        result: Result<(), String>,
+    },
+
+    /// Namespace deletion finished or gave up waiting
+    NamespaceDeleted {
+        name: String,
+        result: Result<NamespaceDeletion, String>,
+    },
+
+    /// Deployment owning the selected pod and the local tags of its images
+    ImageChoicesLoaded(Result<(ResourceRef, Vec<ImageChoice>), String>),
+
+    /// Deployment owning the selected pod and its containers, for a host mount
+    HostMountTargetsLoaded(Result<(ResourceRef, Vec<String>), String>),
+
+    /// Namespaces to offer in the pull secret form
+    PullSecretNamespacesListed(Result<Vec<String>, String>),
+
+    /// `commands_source` catalogs fetched: (label, changed or error)
+    CatalogsUpdated(Vec<(String, Result<bool, String>)>),
+
+    /// Crash-loop diagnosis of the selected pod
+    PodDiagnosed(Result<PodDiagnosis, String>),
+
+    /// Answer to a can-i check typed in the RBAC inspector
+    CanIChecked {
+        query: String,
+        answer: Result<CanIAnswer, String>,
+    },
+
     /// Pod startup timeline loaded
     PodTimelineLoaded(PodTimeline),
 
@@ -86,6 +263,9 @@ pub enum AppMessage {
     /// K8s client initialized (lazy, triggered when cluster becomes running)
     K8sClientReady(Option<crate::k8s::K8sClient>),
 
+    /// Seed last applied to the cluster (None: never seeded)
+    SeedRecordLoaded(Option<crate::k8s::seeds::SeedRecord>),
+
     /// Image architecture data for pods (pod_key → architecture string)
     ImageArchUpdated(HashMap<String, String>),
 
@@ -108,6 +288,30 @@ pub enum AppMessage {
     /// Capture failed (orchestrator/Docker error).
     CaptureFailed(String),
 
+    /// All hooks for a lifecycle event finished running
+    HookFinished(HookCompletion),
+
+    /// A single hook command finished, with its output
+    HookRunFinished(HookRun),
+
+    /// Smoke tests finished after services were deployed
+    SmokeTestsFinished(SmokeReport),
+
+    /// A kube-system component tracked after start changed readiness
+    ReadinessUpdated(ReadinessUpdate),
+
+    /// Resource YAML fetched for editing
+    ResourceYamlLoaded { resource: ResourceRef, yaml: String },
+
+    /// Fetching a resource for editing failed
+    ResourceEditFailed(String),
+
+    /// Server-side apply of an edited resource finished
+    ResourceApplied {
+        resource: ResourceRef,
+        result: Result<ApplyOutcome, String>,
+    },
+
     /// Error message
     Error(String),
 
@@ -144,47 +348,78 @@ impl App {
     pub(super) fn handle_message(&mut self, msg: AppMessage) {
         match msg {
             AppMessage::OutputLine(line) => {
-                // Log the output line based on its type
-                use crate::ui::components::OutputType;
-                match line.output_type {
-                    OutputType::Info => tracing::info!("{}", line.content),
-                    OutputType::Warning => tracing::warn!("{}", line.content),
-                    OutputType::Error => tracing::error!("{}", line.content),
-                    OutputType::Success => tracing::info!(event = "success", "{}", line.content),
-                }
+                log_output_line(&line);
 
                 // Forward to both output (internal buffer) and output_popup
                 self.output.add_line(line.clone());
                 self.output_popup.add_line(line);
             }
-            AppMessage::CommandComplete(exit_code) => {
+            AppMessage::RunOutput { job, line } => {
+                log_output_line(&line);
+                self.report_job(job, line);
+            }
+            AppMessage::CommandComplete { job, exit_code } => {
                 tracing::info!(exit_code = %exit_code, "Command completed");
 
-                self.is_executing = false;
-                self.cancel_token = None;
-
-                if exit_code == 0 {
-                    self.output.add_success("Command completed successfully");
-                    self.output_popup
-                        .add_line(crate::ui::components::OutputLine::success(
-                            "Command completed successfully",
-                        ));
-                } else {
-                    self.output
-                        .add_error(format!("Command exited with code {}", exit_code));
-                    self.output_popup
-                        .add_line(crate::ui::components::OutputLine::error(format!(
-                            "Command exited with code {}",
-                            exit_code
-                        )));
+                let cluster_action = matches!(
+                    self.jobs.get(job).map(|j| &j.kind),
+                    Some(
+                        JobKind::Cluster(_)
+                            | JobKind::StartFromSnapshot(_)
+                            | JobKind::ExportAndDestroy(_)
+                    )
+                );
+                if cluster_action {
+                    self.advance_lifecycle(ClusterInput::ActionEnded);
+                    self.watchdog.restart_finished(Instant::now());
+                }
+                let traefik_redeployed = matches!(
+                    self.jobs.get(job).map(|j| &j.kind),
+                    Some(JobKind::Redeploy(ClusterComponent::Traefik))
+                );
+                if traefik_redeployed {
+                    self.scheduler.request(RefreshTask::TraefikDrift);
                 }
 
-                // Scroll to bottom to show completion message
-                self.output_popup.scroll_to_bottom();
+                let state = self.ended_state(job, exit_code != 0);
+                let line = match state {
+                    JobState::Cancelled => OutputLine::warning("Cancelled"),
+                    _ if exit_code == 0 => OutputLine::success("Command completed successfully"),
+                    _ => OutputLine::error(format!("Command exited with code {}", exit_code)),
+                };
+                self.finish_job(job, line, state);
 
                 // Refresh status check
                 self.spawn_status_check();
             }
+            AppMessage::CommandFinished {
+                job,
+                command,
+                outcome,
+            } => {
+                tracing::info!(
+                    command = %command,
+                    exit_code = outcome.exit_code(),
+                    "Command finished"
+                );
+
+                let state = self.ended_state(job, outcome.is_failure());
+                self.finish_job(job, outcome.summary_line(), state);
+
+                if state == JobState::Failed {
+                    self.run_command_failed_hooks(job, &command, &outcome);
+                }
+                self.spawn_status_check();
+            }
+            AppMessage::ContainersChanged(events) => {
+                let cluster_container = &self.cluster_config.container_name;
+                if events.iter().any(|e| &e.name == cluster_container) {
+                    self.spawn_status_check();
+                }
+                if events.iter().any(|e| e.is_pod_container()) {
+                    self.scheduler.request(RefreshTask::StatsRefresh);
+                }
+            }
             AppMessage::ClusterStatusUpdate(status) => {
                 let was_running = matches!(self.cluster_status, ClusterStatus::Running);
                 let is_running = matches!(status, ClusterStatus::Running);
@@ -199,18 +434,34 @@ impl App {
                 }
 
                 self.cluster_status = status;
+                self.advance_lifecycle(ClusterInput::Observed(status));
+
+                // A started or stopped cluster invalidates the shared
+                // Kubernetes client (new address and certificates)
+                if was_running != is_running {
+                    let clients = self.clients.clone();
+                    tokio::spawn(async move { clients.reset_kube().await });
+                }
 
                 // Toggle action bar: diagnostics only when running, preflight when not
                 self.action_bar
                     .set_action_enabled("diagnostics", is_running);
                 self.action_bar.set_action_enabled("preflight", !is_running);
+                if was_running && !is_running {
+                    self.action_bar.clear_readiness();
+                    self.health_problems.clear();
+                    self.set_traefik_drift(None);
+                    self.set_seed_record(None);
+                }
 
                 // Auto-trigger preflight checks on stopped screen
-                if !is_running && !self.preflight_auto_triggered {
+                if !is_running && !self.preflight_auto_triggered && !self.safe_mode {
                     self.preflight_auto_triggered = true;
+                    let previous_mode = self.mode;
                     self.run_preflight_check();
-                    // Reset mode back to Normal since run_preflight_check sets Diagnostics mode
-                    self.mode = AppMode::Normal;
+                    // Restore the mode since run_preflight_check sets Diagnostics mode
+                    // (keeps the setup wizard open on first run)
+                    self.mode = previous_mode;
                 }
                 // Reset auto-trigger when cluster starts (so it re-triggers next time it stops)
                 if is_running {
@@ -219,22 +470,19 @@ impl App {
 
                 // If cluster just became running, trigger refresh and show ports
                 if is_running && !was_running {
-                    // Set forwarded ports from config
-                    let mut all_ports = vec![
-                        (self.cluster_config.http_port, self.cluster_config.http_port),
-                        (
-                            self.cluster_config.https_port,
-                            self.cluster_config.https_port,
-                        ),
-                        (self.cluster_config.api_port, self.cluster_config.api_port),
-                    ];
-                    all_ports.extend(self.cluster_config.additional_ports.clone());
-                    self.menu.set_forwarded_ports(all_ports);
-
-                    self.spawn_ingress_refresh();
-                    self.spawn_missing_hosts_check();
-                    self.spawn_port_forwards_check();
-                    self.spawn_volume_stats_check();
+                    // Set forwarded ports from config; the API port is
+                    // corrected once the container's mapping is read
+                    self.set_forwarded_ports(self.cluster_config.api_port);
+                    self.spawn_api_port_check();
+                    if !self.safe_mode {
+                        self.scheduler.request_multiple(&[
+                            RefreshTask::IngressRefresh,
+                            RefreshTask::HostsCheck,
+                            RefreshTask::VolumeRefresh,
+                            RefreshTask::HealthCheck,
+                            RefreshTask::TraefikDrift,
+                        ]);
+                    }
                     // Lazily init K8s client now that cluster is running
                     if self.k8s_client.is_none() {
                         let tx = self.message_tx.clone();
@@ -247,12 +495,6 @@ impl App {
                             let _ = tx.send(AppMessage::K8sClientReady(client)).await;
                         });
                     }
-                    // Reset scheduler timers for tasks we just triggered
-                    self.scheduler.mark_run_multiple(&[
-                        RefreshTask::IngressRefresh,
-                        RefreshTask::HostsCheck,
-                        RefreshTask::VolumeRefresh,
-                    ]);
                 }
 
                 // If cluster stopped running, clear cluster-specific data
@@ -260,20 +502,33 @@ impl App {
                     self.menu.set_forwarded_ports(Vec::new());
                     self.menu.set_active_port_forwards(Vec::new());
                     self.menu.set_ingress_entries(Vec::new());
-                    self.menu
-                        .set_ingress_health(std::collections::HashMap::new());
+                    self.menu.clear_ingress_health();
+                    self.close_tunnels();
+                    self.cancel_awaited_url();
                     self.menu
                         .set_missing_hosts(std::collections::HashSet::new());
                     self.pod_stats.set_pods(Vec::new());
                     self.volume_entries_cache.clear();
+                    self.crash_looping_containers.clear();
+                    self.last_pvc_phases = None;
+                    self.last_ingress_health = None;
+                    self.ingress_backoff.clear();
                 }
             }
             AppMessage::IngressEntriesLoaded(entries) => {
                 self.menu.set_ingress_entries(entries);
                 self.spawn_ingress_health_check();
-                self.spawn_missing_hosts_check();
+                if !self.safe_mode {
+                    self.spawn_missing_hosts_check();
+                }
             }
             AppMessage::IngressHealthUpdated(health) => {
+                let now = std::time::Instant::now();
+                let policy = self.refresh_config.ingress_backoff();
+                for (key, check) in &health {
+                    self.ingress_backoff.record(key, check.status, now, &policy);
+                }
+                self.notify_ingress_health_changes(&health);
                 self.menu.set_ingress_health(health);
             }
             AppMessage::MissingHostsUpdated(missing) => {
@@ -284,7 +539,12 @@ impl App {
                 self.running_pods_cache = stats;
                 self.merge_and_update_pod_stats();
             }
+            AppMessage::HostCapacityLoaded(capacity) => {
+                self.host_capacity = Some(capacity);
+            }
             AppMessage::PendingPodsUpdated(pending) => {
+                self.notify_crash_loops(&pending);
+
                 // Cache the pending pods and merge with running
                 self.pending_pods_cache = pending;
 
@@ -344,6 +604,111 @@ impl App {
             AppMessage::DiagnosticsUpdated(report) => {
                 self.diagnostics_overlay.update(report);
             }
+            AppMessage::ClusterInfoLoaded(info) => {
+                if let Some(info) = info {
+                    self.cluster_info_view.update(*info);
+                }
+            }
+            AppMessage::PodTopLoaded {
+                pod,
+                namespace,
+                processes,
+            } => {
+                // Ignore results for a pod the popup no longer shows
+                if self.pod_top_view.pod() == (pod.as_str(), namespace.as_str()) {
+                    self.pod_top_view.update(processes);
+                }
+            }
+            AppMessage::ServicesLoaded(services) => {
+                self.services_view.update(services);
+            }
+            AppMessage::NetworkPoliciesLoaded(namespaces) => {
+                self.network_policy_view.update(namespaces);
+            }
+            AppMessage::DependencyMapLoaded(namespaces) => {
+                self.dependency_map_view.update(namespaces);
+            }
+            AppMessage::PolicyViolationsLoaded(violations) => {
+                self.policy_view.update(violations);
+            }
+            AppMessage::QuotasLoaded(namespaces) => {
+                self.quotas_view.update(namespaces);
+            }
+            AppMessage::RuntimeLoaded(entries) => {
+                self.runtime_view.update(entries);
+            }
+            AppMessage::PvDirectoriesLoaded(dirs) => {
+                self.pv_browser_view.update(dirs);
+            }
+            AppMessage::SnapshotsLoaded(snapshots) => {
+                self.snapshots_view.update(snapshots);
+            }
+            AppMessage::PvActionFinished(line) => {
+                self.toasts.push(line.clone());
+                self.report(line);
+                if self.mode == AppMode::PvBrowser {
+                    self.load_pv_directories();
+                }
+            }
+            AppMessage::K3sVersionsLoaded { choices, arch } => {
+                if let Ok(list) = &choices {
+                    self.setup_wizard
+                        .set_versions(list.iter().map(|c| c.version.to_string()).collect());
+                }
+                self.k3s_version_view.update(choices, arch);
+            }
+            AppMessage::BuildProgress { job, progress } => {
+                if self.build_job == Some(job) {
+                    self.build_view.update(progress);
+                }
+            }
+            AppMessage::RefreshFinished { task, error } => {
+                if let Some(error) = &error {
+                    tracing::debug!(?task, %error, "Refresh failed");
+                }
+                if let (RefreshTask::RecurringHook(index), Some(error)) = (task, &error) {
+                    let name = self
+                        .config
+                        .hooks
+                        .recurring
+                        .get(index)
+                        .map_or("?", |r| r.hook.name.as_str());
+                    let line = OutputLine::error(format!(
+                        "Recurring hook {} failed: {} (output in the Hooks view)",
+                        name, error
+                    ));
+                    self.report(line.clone());
+                    self.notify(NotificationEvent::HookFinished, line);
+                }
+                self.scheduler.finish(task, error);
+            }
+            AppMessage::TaskPanicked(report) => {
+                self.crash_view.push(*report);
+                if self.mode != AppMode::Crash {
+                    self.crash_return_mode = self.mode;
+                    self.mode = AppMode::Crash;
+                }
+            }
+            AppMessage::RbacLoaded(namespaces) => {
+                self.rbac_view.update(namespaces);
+            }
+            AppMessage::NamespacesListed(result) => self.handle_namespaces_listed(result),
+            AppMessage::NamespaceCreated { name, result } => {
+                self.handle_namespace_created(name, result)
+            }
+            AppMessage::NamespaceDeleted { name, result } => {
+                self.handle_namespace_deleted(name, result)
+            }
+            AppMessage::ImageChoicesLoaded(result) => self.handle_image_choices(result),
+            AppMessage::HostMountTargetsLoaded(result) => self.handle_host_mount_targets(result),
+            AppMessage::PullSecretNamespacesListed(result) => {
+                self.handle_pull_secret_namespaces(result)
+            }
+            AppMessage::CatalogsUpdated(results) => self.handle_catalogs_updated(results),
+            AppMessage::PodDiagnosed(result) => self.handle_pod_diagnosed(result),
+            AppMessage::CanIChecked { query, answer } => {
+                self.rbac_view.finish_check(&query, answer);
+            }
             AppMessage::PodTimelineLoaded(timeline) => {
                 // Only apply if the detail panel is open for this pod
                 if self.pod_detail_panel.is_open()
@@ -365,7 +730,13 @@ impl App {
             AppMessage::ActivePortForwardsUpdated(forwards) => {
                 self.menu.set_active_port_forwards(forwards);
             }
+            AppMessage::ApiHostPortResolved(port) => {
+                if matches!(self.cluster_status, ClusterStatus::Running) {
+                    self.set_forwarded_ports(port);
+                }
+            }
             AppMessage::VolumeStatsUpdated(entries) => {
+                self.notify_pvc_bound(&entries);
                 self.volume_entries_cache = entries;
                 self.update_detail_panel_volumes();
             }
@@ -485,6 +856,92 @@ impl App {
             }
             AppMessage::K8sClientReady(client) => {
                 self.k8s_client = client;
+                self.spawn_seed_record_load();
+            }
+            AppMessage::SeedRecordLoaded(record) => self.set_seed_record(record),
+            AppMessage::HookFinished(completion) => {
+                let line = if completion.success {
+                    OutputLine::success(format!(
+                        "{} hooks finished ({})",
+                        completion.event.as_str(),
+                        completion.hook_count
+                    ))
+                } else {
+                    OutputLine::error(format!("{} hooks failed", completion.event.as_str()))
+                };
+                self.notify(NotificationEvent::HookFinished, line);
+            }
+            AppMessage::HookRunFinished(run) => self.hook_runs_view.push(run),
+            AppMessage::SmokeTestsFinished(report) => self.show_smoke_report(report),
+            AppMessage::ClusterTransition(transition) => {
+                self.handle_cluster_transition(transition);
+            }
+            AppMessage::OnDemandConnection(result) => {
+                self.handle_on_demand_connection(result);
+            }
+            AppMessage::ControlRequested(request) => {
+                self.handle_control_request(request);
+            }
+            AppMessage::ControlFreed => {
+                // Spread out so the instances left don't all claim it at once
+                let spread = Duration::from_millis(u64::from(std::process::id() % 500));
+                self.find_control_owner(spread);
+            }
+            AppMessage::ControlFound(result) => {
+                self.apply_control(result);
+            }
+            AppMessage::ShareEnded { remote, result } => {
+                self.handle_share_ended(remote, result);
+            }
+            AppMessage::AwaitedUrlChecked { url, ready } => {
+                self.handle_awaited_url_checked(url, ready);
+            }
+            AppMessage::TunnelOpened { host, url } => {
+                self.handle_tunnel_opened(host, url);
+            }
+            AppMessage::TunnelClosed { host, result } => {
+                self.handle_tunnel_closed(host, result);
+            }
+            AppMessage::ClusterHealthChecked(problems) => {
+                self.watchdog.api_ok(Instant::now());
+                self.health_problems = problems;
+                self.update_cluster_health();
+            }
+            AppMessage::ClusterApiFailed(error) => {
+                // A start or restart brings the API up on its own time
+                let cluster_job = self.jobs.active().any(|j| {
+                    matches!(
+                        j.kind,
+                        JobKind::Cluster(_)
+                            | JobKind::StartFromSnapshot(_)
+                            | JobKind::ExportAndDestroy(_)
+                    )
+                });
+                if !cluster_job {
+                    let failure = self.watchdog.api_failed(&error, Instant::now());
+                    self.watchdog_failed(failure);
+                }
+            }
+            AppMessage::TraefikDriftChecked(drift) => {
+                self.set_traefik_drift(drift);
+            }
+            AppMessage::ReadinessUpdated(update) => {
+                self.action_bar.set_readiness(update);
+                if update.state != ReadinessState::Waiting {
+                    self.update_cluster_health();
+                }
+            }
+            AppMessage::ResourceYamlLoaded { resource, yaml } => {
+                // Only open the editor if the user is still waiting on the popup
+                if self.mode == AppMode::OutputPopup {
+                    self.pending_resource_edit = Some((resource, yaml));
+                }
+            }
+            AppMessage::ResourceEditFailed(msg) => {
+                self.report(OutputLine::error(msg));
+            }
+            AppMessage::ResourceApplied { resource, result } => {
+                self.handle_resource_applied(resource, result);
             }
             AppMessage::Error(msg) => {
                 tracing::error!("{}", msg);
@@ -658,13 +1115,6 @@ impl App {
             });
         }
 
-        // Sort by namespace first, then by pod name within each namespace
-        pod_stats.sort_by(|a, b| {
-            a.namespace
-                .cmp(&b.namespace)
-                .then_with(|| a.name.cmp(&b.name))
-        });
-
         // Check if any running pods need architecture info (avoid duplicate checks)
         if !self.image_arch_check_pending {
             let has_uncached = self.running_pods_cache.iter().any(|s| {
@@ -677,6 +1127,13 @@ impl App {
             }
         }
 
+        // Usage gauges only count pods with live stats
+        let usage = self
+            .host_capacity
+            .filter(|_| !self.running_pods_cache.is_empty())
+            .map(|capacity| ResourceUsage::from_pods(&pod_stats, capacity));
+        self.action_bar.set_usage(usage);
+
         self.pod_stats.set_pods(pod_stats);
 
         // Auto-open detail panel when a pod is selected
@@ -690,4 +1147,251 @@ impl App {
                 .set_volume_entries(self.volume_entries_cache.clone());
         }
     }
+
+    /// How a job ended; a cancelled job counts as cancelled whatever it returned
+    fn ended_state(&self, job: JobId, failed: bool) -> JobState {
+        let cancelled = self.jobs.get(job).is_some_and(|j| j.cancel.is_cancelled());
+        if cancelled {
+            JobState::Cancelled
+        } else if failed {
+            JobState::Failed
+        } else {
+            JobState::Succeeded
+        }
+    }
+
+    /// Show a toast if the event passes the configured notification filter
+    /// Feed the cluster state machine and publish the transition it makes
+    pub(super) fn advance_lifecycle(&mut self, input: ClusterInput) {
+        if let Some(transition) = self.lifecycle.apply(input) {
+            let _ = self
+                .message_tx
+                .try_send(AppMessage::ClusterTransition(transition));
+        }
+    }
+
+    /// Components the cluster is degraded by: what the health check found
+    /// plus readiness gates that timed out
+    pub(super) fn degraded_components(&self) -> Vec<HealthProblem> {
+        let mut problems = self.health_problems.clone();
+        for gate in self.action_bar.timed_out_readiness() {
+            let problem = HealthProblem::deployment(gate.name(), "not ready");
+            if !problems.iter().any(|p| p.component == problem.component) {
+                problems.push(problem);
+            }
+        }
+        problems
+    }
+
+    /// Degrade or recover the cluster from the components that are down
+    fn update_cluster_health(&mut self) {
+        let problems = self.degraded_components();
+        let input = if problems.is_empty() {
+            ClusterInput::Healthy
+        } else {
+            ClusterInput::Unhealthy(degraded::summarize(&problems))
+        };
+        self.advance_lifecycle(input);
+        // A cluster that stays degraded for another reason has no transition
+        self.action_bar.set_cluster_state(
+            self.lifecycle.state(),
+            self.lifecycle.degraded_reason().map(str::to_string),
+        );
+    }
+
+    /// Record the Traefik drift check's result; newly found drift is
+    /// announced once
+    fn set_traefik_drift(&mut self, drift: Option<TraefikDrift>) {
+        if let Some(found) = &drift {
+            if self.traefik_drift.is_none() {
+                self.notify(
+                    NotificationEvent::TraefikDrift,
+                    OutputLine::warning(format!("Traefik config drifted: {}", found)),
+                );
+            }
+        }
+        self.action_bar
+            .set_traefik_drift(drift.as_ref().map(ToString::to_string));
+        self.traefik_drift = drift;
+    }
+
+    /// Badge, toast and hooks for a cluster state change
+    fn handle_cluster_transition(&mut self, transition: ClusterTransition) {
+        tracing::info!(
+            from = transition.from.label(),
+            to = transition.to.label(),
+            reason = transition.reason.as_deref().unwrap_or(""),
+            "Cluster state changed"
+        );
+        // The machine may have moved on while the message was queued
+        self.action_bar.set_cluster_state(
+            self.lifecycle.state(),
+            self.lifecycle.degraded_reason().map(str::to_string),
+        );
+
+        self.update_on_demand();
+
+        // The first poll only tells where the cluster already was
+        if transition.from == ClusterState::Unknown {
+            return;
+        }
+        if transition.to == ClusterState::Stopping {
+            self.watchdog.reset();
+        }
+        // A stop k3dev runs goes through Stopping first
+        if transition.from.is_up() && transition.to == ClusterState::Stopped {
+            let failure = self.watchdog.exited(Instant::now());
+            self.watchdog_failed(failure);
+        }
+        let line = match transition.to {
+            ClusterState::Running if transition.from == ClusterState::Degraded => {
+                OutputLine::success("Cluster recovered")
+            }
+            ClusterState::Running => OutputLine::success("Cluster running"),
+            ClusterState::Degraded => OutputLine::warning(format!(
+                "Cluster degraded: {}",
+                transition.reason.as_deref().unwrap_or("unknown cause")
+            )),
+            ClusterState::RuntimeDown => OutputLine::error("Docker is not reachable"),
+            ClusterState::Stopped | ClusterState::NotCreated | ClusterState::Paused => {
+                OutputLine::info(format!("Cluster {}", transition.to.label()))
+            }
+            // Started by the user or not yet known; nothing to announce
+            ClusterState::Starting | ClusterState::Stopping | ClusterState::Unknown => return,
+        };
+        self.notify(NotificationEvent::ClusterState, line);
+
+        if transition.to == ClusterState::Degraded {
+            let reason = transition.reason.unwrap_or_default();
+            self.run_cluster_degraded_hooks(&reason);
+        }
+    }
+
+    /// Show the results in a popup, unless the user is busy with another
+    /// one; the summary always goes to a toast
+    fn show_smoke_report(&mut self, report: SmokeReport) {
+        let summary = if report.all_passed() {
+            OutputLine::success(report.summary())
+        } else {
+            OutputLine::error(report.summary())
+        };
+        self.toasts.push(summary);
+
+        let viewing_job = self.mode == AppMode::OutputPopup && self.popup_job.is_some();
+        if self.mode != AppMode::Normal && !viewing_job {
+            return;
+        }
+        self.clear_output_popup();
+        self.output_popup
+            .set_title(format!("Smoke Tests: {}", report.summary()));
+        self.output_popup.set_actions(None);
+        for result in &report.results {
+            self.output_popup.add_line(result.output_line());
+        }
+        self.mode = AppMode::OutputPopup;
+    }
+
+    fn notify(&mut self, event: NotificationEvent, line: OutputLine) {
+        if self.config.notifications.allows(event) {
+            self.toasts.push(line);
+        }
+    }
+
+    /// Toast for containers that newly entered CrashLoopBackOff
+    fn notify_crash_loops(&mut self, pending: &[PendingPodInfo]) {
+        let current: HashSet<String> = pending
+            .iter()
+            .flat_map(|p| {
+                p.containers
+                    .iter()
+                    .filter(|c| c.reason == "CrashLoopBackOff")
+                    .map(move |c| format!("{}/{}/{}", p.namespace, p.name, c.name))
+            })
+            .collect();
+
+        let mut new_keys: Vec<&String> =
+            current.difference(&self.crash_looping_containers).collect();
+        new_keys.sort();
+        let lines: Vec<OutputLine> = new_keys
+            .into_iter()
+            .map(|key| OutputLine::error(format!("{} entered CrashLoopBackOff", key)))
+            .collect();
+        for line in lines {
+            self.notify(NotificationEvent::PodCrashLoop, line);
+        }
+
+        self.crash_looping_containers = current;
+    }
+
+    /// Toast for PVCs that became Bound since the previous refresh
+    fn notify_pvc_bound(&mut self, entries: &[PvcInfo]) {
+        let current: HashMap<String, String> = entries
+            .iter()
+            .map(|e| (format!("{}/{}", e.namespace, e.name), e.phase.clone()))
+            .collect();
+
+        // The first snapshot only seeds state — everything in it is pre-existing
+        if let Some(previous) = self.last_pvc_phases.take() {
+            let mut bound: Vec<&String> = current
+                .iter()
+                .filter(|(key, phase)| {
+                    phase.as_str() == "Bound"
+                        && previous.get(*key).map(|p| p != "Bound").unwrap_or(true)
+                })
+                .map(|(key, _)| key)
+                .collect();
+            bound.sort();
+            let lines: Vec<OutputLine> = bound
+                .into_iter()
+                .map(|key| OutputLine::success(format!("PVC {} bound", key)))
+                .collect();
+            for line in lines {
+                self.notify(NotificationEvent::PvcBound, line);
+            }
+        }
+
+        self.last_pvc_phases = Some(current);
+    }
+
+    /// Toast for ingress endpoints that turned healthy since the previous check
+    fn notify_ingress_health_changes(&mut self, checks: &HashMap<String, HealthCheck>) {
+        let health: HashMap<String, IngressHealthStatus> = checks
+            .iter()
+            .map(|(key, check)| (key.clone(), check.status))
+            .collect();
+        if let Some(previous) = &self.last_ingress_health {
+            let mut recovered: Vec<&String> = health
+                .iter()
+                .filter(|(key, status)| {
+                    **status == IngressHealthStatus::Healthy
+                        && previous.get(*key) != Some(&IngressHealthStatus::Healthy)
+                })
+                .map(|(key, _)| key)
+                .collect();
+            recovered.sort();
+            let lines: Vec<OutputLine> = recovered
+                .into_iter()
+                .map(|key| OutputLine::success(format!("Ingress {} is healthy", key)))
+                .collect();
+            for line in lines {
+                self.notify(NotificationEvent::IngressHealthy, line);
+            }
+        }
+
+        // Rounds skip endpoints that are backing off; keep their last status
+        self.last_ingress_health
+            .get_or_insert_with(HashMap::new)
+            .extend(health);
+    }
+}
+
+/// Log an output line at the level matching its type
+fn log_output_line(line: &OutputLine) {
+    use crate::ui::components::OutputType;
+    match line.output_type {
+        OutputType::Info | OutputType::Hint => tracing::info!("{}", line.content),
+        OutputType::Warning => tracing::warn!("{}", line.content),
+        OutputType::Error => tracing::error!("{}", line.content),
+        OutputType::Success => tracing::info!(event = "success", "{}", line.content),
+    }
 }
@@ -3,19 +3,42 @@
 //! This module contains the main App struct and its supporting types,
 //! split into focused submodules for maintainability.
 
+mod builds;
+mod chaos;
+mod cluster_control;
 mod commands;
+mod editor;
 mod events;
+mod hook_runs;
+mod host_mount;
+mod image_override;
+mod jobs;
 pub(crate) mod messages;
+mod namespaces;
+mod on_demand;
+mod open_when_ready;
+mod panes;
+mod pod_diagnosis;
+mod pull_secret;
 mod refresh;
+mod resource_edit;
+mod seeds;
+mod share;
+mod snapshots;
+mod tunnels;
+mod watchdog;
+mod workspaces;
 
 use anyhow::Result;
-use crossterm::event::{self, Event};
+use crossterm::event::{Event, EventStream};
+use futures::StreamExt;
 use ratatui::{
     backend::CrosstermBackend,
     layout::{Constraint, Layout},
     Terminal,
 };
 use std::io::Stdout;
+use std::path::PathBuf;
 use std::sync::Arc;
 use std::time::{Duration, Instant};
 use tokio::sync::mpsc;
@@ -23,23 +46,46 @@ use tokio_util::sync::CancellationToken;
 
 use bollard::Docker;
 
-use crate::cluster::{ClusterConfig, ClusterStatus, ContainerPullProgress, ContainerStats};
+use crate::api::ApiServer;
+use crate::cluster::degraded::HealthProblem;
+use crate::cluster::health_backoff::HealthBackoff;
+use crate::cluster::traefik_drift::TraefikDrift;
+use crate::cluster::watchdog::Watchdog;
+use crate::cluster::{
+    ClusterConfig, ClusterLifecycle, ClusterStatus, ContainerPullProgress, ContainerStats,
+    HostCapacity, IngressHealthStatus, PvDirectory, SharedClients,
+};
 use crate::config::{
-    Config, ConfigLoader, ConfigValidator, InfoBlock, RefreshConfig, RefreshScheduler, RefreshTask,
-    VisibleCheck,
+    Config, ConfigLoader, ConfigValidator, InfoBlock, LayoutPreset, OutputOverride, RefreshConfig,
+    RefreshScheduler, VisibleCheck,
 };
 use crate::k8s::PendingPodInfo;
-use crate::k8s::{K8sClient, ShellSessionHandle};
-use crate::keybindings::KeybindingResolver;
+use crate::k8s::{K8sClient, ResourceRef, ShellSessionHandle};
+use crate::keybindings::{KeyAction, KeybindingResolver};
 use crate::ui::components::{
-    ActionBar, ClusterAction, CommandPalette, ConfirmPopup, DetailTab, DiagnosticsOverlay,
-    HelpOverlay, InputForm, Menu, Output, OutputPopup, PodDetailPanel, PodStats,
+    ActionBar, BreadcrumbPicker, BuildView, ClusterAction, ClusterInfoView, CommandPalette,
+    CommandPreview, ConfirmPopup, CrashView, DependencyMapView, DetailTab, DiagnosticsOverlay,
+    HelpOverlay, HookRunsView, InputForm, K3sVersionView, Menu, MenuState, NetworkPolicyView,
+    Output, OutputLine, OutputMirror, OutputPopup, PodDetailPanel, PodStats, PodTopView,
+    PolicyView, PvBrowserView, QuotasView, RbacView, RefreshTasksView, RuntimeView, ServicesView,
+    SetupWizard, SnapshotsView, ToastOverlay, Transcript, SPINNER_INTERVAL,
 };
-use crate::ui::{AppLayout, Styles};
+use crate::ui::{AppLayout, Panel, Styles};
 use std::collections::{HashMap, HashSet};
 
+pub use jobs::JobId;
 pub use messages::{AppMessage, InfoBlockResult, InfoBlockStatus};
 
+use jobs::{JobKind, JobState, Jobs};
+use workspaces::Workspaces;
+
+/// Shortest time between two redraws (about 60 frames per second)
+const FRAME_INTERVAL: Duration = Duration::from_millis(16);
+
+/// Longest the event loop sleeps, so info blocks, visibility checks, toasts
+/// and "updated Ns ago" labels keep moving while nothing else happens
+const IDLE_TICK: Duration = Duration::from_secs(1);
+
 /// Per-block runtime state for scheduling info block refreshes.
 pub(super) struct InfoBlockRuntime {
     pub(super) cfg: InfoBlock,
@@ -82,10 +128,46 @@ pub enum AppMode {
     Input,
     Help,
     CommandPalette,
+    BreadcrumbJump,
+    CommandPreview,
     OutputPopup,
     ConfirmDestroy,
     Diagnostics,
+    ClusterInfo,
+    PodTop,
+    Services,
+    NetworkPolicies,
+    DependencyMap,
+    PolicyViolations,
+    Quotas,
+    Runtime,
+    PvBrowser,
+    Snapshots,
+    RefreshTasks,
+    HookRuns,
+    K3sVersions,
+    Build,
+    Rbac,
+    Crash,
     Shell,
+    SetupWizard,
+}
+
+/// Built-in form the input form is collecting values for
+#[derive(Debug, Clone, Copy, PartialEq)]
+enum PendingForm {
+    ResourceEdit,
+    CreateNamespace,
+    DeleteNamespace,
+    ImageOverride,
+    HostMount,
+    Build,
+    PullSecret,
+    SaveSnapshot,
+    MemoryPressure,
+    Latency,
+    Share,
+    Tunnel,
 }
 
 /// Main application
@@ -107,16 +189,70 @@ pub struct App {
     input_form: InputForm,
     help_overlay: HelpOverlay,
     command_palette: CommandPalette,
+    breadcrumb_picker: BreadcrumbPicker,
+    command_preview: CommandPreview,
     confirm_popup: ConfirmPopup,
     diagnostics_overlay: DiagnosticsOverlay,
+    cluster_info_view: ClusterInfoView,
+    pod_top_view: PodTopView,
+    services_view: ServicesView,
+    network_policy_view: NetworkPolicyView,
+    dependency_map_view: DependencyMapView,
+    policy_view: PolicyView,
+    quotas_view: QuotasView,
+    runtime_view: RuntimeView,
+    pv_browser_view: PvBrowserView,
+    snapshots_view: SnapshotsView,
+    refresh_tasks_view: RefreshTasksView,
+    hook_runs_view: HookRunsView,
+    /// Watchers behind the `on_file_change` hooks
+    file_watchers: Option<crate::hooks::FileWatchers>,
+    k3s_version_view: K3sVersionView,
+    build_view: BuildView,
+    rbac_view: RbacView,
+    crash_view: CrashView,
     pod_detail_panel: PodDetailPanel,
+    setup_wizard: SetupWizard,
+    toasts: ToastOverlay,
     styles: Styles,
 
     // State
     focus: FocusArea,
     mode: AppMode,
+    /// Mode restored when the help overlay closes
+    help_return_mode: AppMode,
+    /// Mode restored once every panic in the crash popup was dismissed
+    crash_return_mode: AppMode,
     cluster_status: ClusterStatus,
-    is_executing: bool,
+    /// Cluster state from polls, actions and component health
+    lifecycle: ClusterLifecycle,
+    /// Core components the last health check found down
+    health_problems: Vec<HealthProblem>,
+    /// How the live Traefik config differs from k3dev's, if it does
+    traefik_drift: Option<TraefikDrift>,
+    /// Notices a dead or hung cluster and schedules restarts
+    watchdog: Watchdog,
+    /// Holds the ingress ports while the cluster is down (`start.on_demand`)
+    on_demand_listener: Option<tokio::task::JoinHandle<()>>,
+    /// Pid of the other k3dev in control of this cluster; None while this
+    /// instance is
+    control_owner: Option<u32>,
+    /// Serves the cluster lock, or watches the instance that holds it
+    control_task: Option<tokio::task::JoinHandle<()>>,
+    /// Ingress ports forwarded to the share host
+    shares: Vec<share::Share>,
+    /// Public tunnels by ingress host
+    tunnels: HashMap<String, tunnels::PublicTunnel>,
+    /// Ingress URL opened once it's healthy, and how to stop waiting
+    awaited_url: Option<(String, CancellationToken)>,
+    /// The output as `k3dev attach` reads it through the control socket
+    output_mirror: OutputMirror,
+    /// Popup commands, queued, running and recently finished
+    jobs: Jobs,
+    /// Job the output popup shows (None for diagnosis, edit diffs, etc.)
+    popup_job: Option<JobId>,
+    /// Job the build panel shows
+    build_job: Option<JobId>,
     should_quit: bool,
 
     // Vim-style number prefix for navigation (e.g., "3j" moves down 3)
@@ -131,8 +267,41 @@ pub struct App {
     // Pending interactive sudo for hosts update (content, host_count)
     pending_sudo_hosts_content: Option<(String, usize)>,
 
+    // Open the config file in $EDITOR on the next loop iteration (needs terminal)
+    pending_config_edit: bool,
+
+    /// Built-in form the input form is collecting values for
+    pending_form: Option<PendingForm>,
+
+    /// Namespace waiting for delete confirmation
+    pending_namespace_delete: Option<String>,
+    /// PV directory waiting for the wipe confirmation
+    pending_pv_wipe: Option<PvDirectory>,
+    /// Snapshot image waiting for the restart confirmation
+    pending_snapshot_start: Option<String>,
+
+    /// Namespace left terminating by its finalizers, offered for a forced finalize
+    stuck_namespace: Option<String>,
+
+    /// Deployment and local images offered in the Override Image form
+    pending_image_override: Option<image_override::PendingImageOverride>,
+
+    /// Deployment and containers offered in the Mount Host Path form
+    pending_host_mount: Option<host_mount::PendingHostMount>,
+
+    /// Fetched resource YAML waiting to be opened in the editor (needs terminal access)
+    pending_resource_edit: Option<(ResourceRef, String)>,
+
+    /// Edited resource waiting for the user to apply it from the output popup
+    staged_resource_edit: Option<resource_edit::StagedResourceEdit>,
+
+    // Path of the loaded config file (None when running on defaults)
+    config_file_path: Option<PathBuf>,
+
     // Cached data for pod stats merging
     running_pods_cache: Vec<ContainerStats>,
+    /// CPUs/memory behind the usage gauges (fetched once from Docker)
+    host_capacity: Option<HostCapacity>,
     pending_pods_cache: Vec<PendingPodInfo>,
     /// Cache of image pull progress (image -> progress)
     pull_progress_cache: HashMap<String, ContainerPullProgress>,
@@ -141,12 +310,24 @@ pub struct App {
     /// Bollard Docker client for spawning pull monitors
     docker_client: Option<Docker>,
 
+    /// Docker and Kubernetes connections shared by background tasks
+    clients: SharedClients,
+
     /// Shared DockerManager for capture sidecars (lazily created on first use).
     docker_manager: Option<Arc<crate::cluster::DockerManager>>,
 
     /// Cache of volume/PVC entries (all volumes, filtered per pod when needed)
     volume_entries_cache: Vec<crate::k8s::PvcInfo>,
 
+    /// Containers last seen in CrashLoopBackOff (namespace/pod/container)
+    crash_looping_containers: HashSet<String>,
+    /// PVC phases from the previous volume refresh (None until the first one)
+    last_pvc_phases: Option<HashMap<String, String>>,
+    /// Ingress health from the previous check (None until the first one)
+    last_ingress_health: Option<HashMap<String, IngressHealthStatus>>,
+    /// Ingress endpoints that keep failing and when to check them again
+    ingress_backoff: HealthBackoff,
+
     /// Cache of pod image architectures (pod_key → architecture)
     image_arch_cache: HashMap<String, String>,
     /// Whether an image arch check is currently in flight
@@ -177,10 +358,21 @@ pub struct App {
 
     // Menu width offset from user adjustments (+/- keys)
     menu_width_offset: i16,
+    /// Panel arrangement, `ui.layout` until cycled with `L`
+    layout_preset: LayoutPreset,
+    /// Focused panel fills the screen (toggled with `z`)
+    zoomed: bool,
+    /// Tabs in the action bar, each with its own view of the main screen
+    workspaces: Workspaces,
+    /// `g` pressed, waiting for the workspace key after it
+    pending_workspace_key: bool,
 
     // Whether auto-preflight has been triggered for stopped screen
     preflight_auto_triggered: bool,
 
+    // `--safe`: no hooks, background refreshes or automatic hosts checks
+    safe_mode: bool,
+
     // Runtime state for user-configured sidebar info blocks
     pub(super) info_blocks: Vec<InfoBlockRuntime>,
 
@@ -195,6 +387,7 @@ pub struct App {
 impl App {
     pub async fn new(config_path: Option<&str>) -> Result<Self> {
         let loader = ConfigLoader::new(config_path);
+        let needs_setup = !loader.config_file_exists();
         let (config, config_file_path) = loader
             .load_with_path()
             .map(|(c, p)| (c, Some(p)))
@@ -207,21 +400,7 @@ impl App {
             .map(|w| format!("Config warning: {}", w))
             .collect();
 
-        let kubeconfig = if config.cluster.kubeconfig.is_empty() {
-            None
-        } else {
-            Some(config.cluster.kubeconfig.clone())
-        };
-        let context = if config.cluster.context.is_empty() {
-            None
-        } else {
-            Some(config.cluster.context.clone())
-        };
-        let cluster_config = Arc::new(
-            ClusterConfig::from(config.infrastructure.clone())
-                .with_hooks(config.hooks.clone())
-                .with_k8s_config(kubeconfig.clone(), context.clone()),
-        );
+        let cluster_config = cluster_config_from(&config);
 
         let _ = crate::logging::init_logging(&config.logging, &config.infrastructure.cluster_name);
 
@@ -231,13 +410,252 @@ impl App {
         let (message_tx, message_rx) = mpsc::channel(100);
         let theme = config.theme;
 
-        let mut menu = Menu::with_theme(theme);
-        menu.build_from_config(&config);
+        let refresh_config = RefreshConfig::default().with_ingress_health(&config.ingress_health);
+        let watchdog = Watchdog::new(&config.watchdog);
+        let mut scheduler = RefreshScheduler::new(&refresh_config);
+        let safe_mode = crate::config::safe_mode();
+        if safe_mode {
+            scheduler.pause_intervals();
+        }
+
+        let mut output = Output::with_theme(theme);
+        let output_mirror = OutputMirror::new();
+        output.mirror_to(output_mirror.clone());
+        if config.transcript.enabled {
+            match Transcript::create(&config.transcript, &config.infrastructure.cluster_name) {
+                Ok(transcript) => {
+                    let path = transcript.path().display().to_string();
+                    output.record_to(transcript);
+                    output.add_info(format!("Recording session to {}", path));
+                }
+                Err(e) => output.add_warning(format!("Session not recorded: {:#}", e)),
+            }
+        }
+        for warning in validation_warnings {
+            output.add_warning(&warning);
+        }
+        if safe_mode {
+            output.add_warning(
+                "Safe mode: hooks, background refreshes and automatic /etc/hosts checks are off; r refreshes",
+            );
+        }
+
+        let mut app = Self {
+            config,
+            cluster_config,
+            refresh_config,
+            k8s_client,
+            action_bar: ActionBar::with_theme(theme),
+            menu: Menu::with_theme(theme),
+            output,
+            output_popup: OutputPopup::with_theme(theme),
+            pod_stats: PodStats::with_theme(theme),
+            input_form: InputForm::with_theme(theme),
+            help_overlay: HelpOverlay::with_theme(theme),
+            command_palette: CommandPalette::with_theme(theme),
+            breadcrumb_picker: BreadcrumbPicker::with_theme(theme),
+            command_preview: CommandPreview::with_theme(theme),
+            confirm_popup: ConfirmPopup::with_theme(theme),
+            diagnostics_overlay: DiagnosticsOverlay::with_theme(theme),
+            cluster_info_view: ClusterInfoView::with_theme(theme),
+            pod_top_view: PodTopView::with_theme(theme),
+            services_view: ServicesView::with_theme(theme),
+            network_policy_view: NetworkPolicyView::with_theme(theme),
+            dependency_map_view: DependencyMapView::with_theme(theme),
+            policy_view: PolicyView::with_theme(theme),
+            quotas_view: QuotasView::with_theme(theme),
+            runtime_view: RuntimeView::with_theme(theme),
+            pv_browser_view: PvBrowserView::with_theme(theme),
+            snapshots_view: SnapshotsView::with_theme(theme),
+            refresh_tasks_view: RefreshTasksView::with_theme(theme),
+            hook_runs_view: HookRunsView::with_theme(theme),
+            file_watchers: None,
+            k3s_version_view: K3sVersionView::with_theme(theme),
+            build_view: BuildView::with_theme(theme),
+            rbac_view: RbacView::with_theme(theme),
+            crash_view: CrashView::with_theme(theme),
+            pod_detail_panel: PodDetailPanel::with_theme(theme),
+            setup_wizard: SetupWizard::with_theme(theme),
+            toasts: ToastOverlay::with_theme(theme),
+            styles: Styles::from_theme(theme),
+            focus: FocusArea::Content,
+            mode: AppMode::Normal,
+            help_return_mode: AppMode::Normal,
+            crash_return_mode: AppMode::Normal,
+            cluster_status: ClusterStatus::Unknown,
+            lifecycle: ClusterLifecycle::default(),
+            health_problems: Vec::new(),
+            traefik_drift: None,
+            watchdog,
+            on_demand_listener: None,
+            control_owner: None,
+            control_task: None,
+            shares: Vec::new(),
+            tunnels: HashMap::new(),
+            awaited_url: None,
+            output_mirror,
+            jobs: Jobs::default(),
+            popup_job: None,
+            build_job: None,
+            should_quit: false,
+            pending_count: String::new(),
+            pending_command: None,
+            pending_cluster_action: None,
+            pending_sudo_hosts_content: None,
+            pending_config_edit: false,
+            pending_form: None,
+            pending_namespace_delete: None,
+            pending_pv_wipe: None,
+            pending_snapshot_start: None,
+            stuck_namespace: None,
+            pending_image_override: None,
+            pending_host_mount: None,
+            pending_resource_edit: None,
+            staged_resource_edit: None,
+            config_file_path: None,
+            running_pods_cache: Vec::new(),
+            host_capacity: None,
+            pending_pods_cache: Vec::new(),
+            pull_progress_cache: HashMap::new(),
+            active_pull_monitors: HashSet::new(),
+            docker_client: crate::cluster::PlatformInfo::connect_docker().ok(),
+            clients: SharedClients::new(),
+            docker_manager: None,
+            volume_entries_cache: Vec::new(),
+            crash_looping_containers: HashSet::new(),
+            last_pvc_phases: None,
+            last_ingress_health: None,
+            ingress_backoff: HealthBackoff::default(),
+            image_arch_cache: HashMap::new(),
+            image_arch_check_pending: false,
+            shell_session: None,
+            shell_area_size: (0, 0),
+            pending_shell_command: None,
+            message_tx,
+            message_rx,
+            cancel_token: None,
+            scheduler,
+            keybinding_resolver: KeybindingResolver::new(),
+            current_layout: None,
+            menu_width_offset: 0,
+            layout_preset: LayoutPreset::default(),
+            zoomed: false,
+            workspaces: Workspaces::default(),
+            pending_workspace_key: false,
+            preflight_auto_triggered: false,
+            safe_mode,
+            info_blocks: Vec::new(),
+            visibility_tasks: Vec::new(),
+            hidden_command_paths: HashSet::new(),
+            hidden_info_blocks: HashSet::new(),
+        };
+        app.sync_config_state(config_file_path);
+        app.start_api_server(config_path).await;
+
+        // No config anywhere: walk the user through creating one instead of
+        // showing an empty menu.
+        if needs_setup {
+            app.setup_wizard.open(loader.setup_target_path());
+            app.mode = AppMode::SetupWizard;
+            app.load_k3s_versions();
+        }
+
+        Ok(app)
+    }
+
+    /// Serve the HTTP API in the background when `api.listen` is set
+    async fn start_api_server(&mut self, config_path: Option<&str>) {
+        let listen = self.config.api.listen.clone();
+        if listen.is_empty() {
+            return;
+        }
+        match ApiServer::bind(&listen, config_path, self.config.api.token.clone()).await {
+            Ok(server) => {
+                let addr = server.local_addr().map(|a| a.to_string()).unwrap_or(listen);
+                self.output
+                    .add_info(format!("HTTP API listening on http://{}", addr));
+                if let Some(path) = server.token_file() {
+                    self.output
+                        .add_info(format!("HTTP API token: {}", path.display()));
+                }
+                tokio::spawn(async move {
+                    if let Err(e) = server.run().await {
+                        tracing::warn!("API server stopped: {:#}", e);
+                    }
+                });
+            }
+            Err(e) => self.output.add_error(format!("{:#}", e)),
+        }
+    }
+
+    /// Fetch the `commands_source` catalogs; the menu picks up changes once
+    /// they arrive
+    fn spawn_catalog_update(&self) {
+        if self.config.commands_source.is_empty() || self.safe_mode {
+            return;
+        }
+        let sources = self.config.commands_source.clone();
+        let message_tx = self.message_tx.clone();
+        tokio::spawn(async move {
+            let results = crate::config::update_catalogs(&sources).await;
+            let _ = message_tx.send(AppMessage::CatalogsUpdated(results)).await;
+        });
+    }
+
+    fn handle_catalogs_updated(&mut self, results: Vec<(String, Result<bool, String>)>) {
+        let mut changed = false;
+        for (label, result) in results {
+            match result {
+                Ok(true) => changed = true,
+                Ok(false) => {}
+                Err(e) => self.report(OutputLine::warning(format!(
+                    "Command catalog {} not updated: {}",
+                    label, e
+                ))),
+            }
+        }
+        if let (true, Some(path)) = (changed, self.config_file_path.clone()) {
+            self.reload_config(&path);
+        }
+    }
+
+    /// Replace the active config and rebuild everything derived from it.
+    ///
+    /// The theme is fixed at startup; a changed `theme:` applies on next launch.
+    pub(super) fn apply_config(&mut self, config: Config, config_file_path: PathBuf) {
+        self.config = config;
+        self.cluster_config = cluster_config_from(&self.config);
+        self.sync_config_state(Some(config_file_path));
+    }
+
+    /// Rebuild menu, info blocks, visibility probes, palette entries and
+    /// keybindings from `self.config`.
+    fn sync_config_state(&mut self, config_file_path: Option<PathBuf>) {
+        // Keep the tree as it was across reloads; on startup, as it was
+        // when this config was last used
+        let menu_state = if self.menu.flat_items().is_empty() {
+            config_file_path.as_deref().and_then(MenuState::load)
+        } else {
+            Some(self.menu.state())
+        };
+        self.menu.build_from_config(&self.config);
+        self.output.set_limit(self.config.output.default_limit());
+        self.layout_preset = self.config.ui.layout;
+        self.output_popup
+            .set_limit(self.config.output.default_limit());
+
+        self.scheduler.clear_recurring_hooks();
+        for (index, recurring) in self.config.hooks.recurring.iter().enumerate() {
+            self.scheduler
+                .add_recurring_hook(index, &recurring.schedule);
+        }
+        self.start_file_watchers();
 
         // Seed info block runtime + placeholder views so headers render before
         // the first refresh completes.
         let now = Instant::now();
-        let info_blocks: Vec<InfoBlockRuntime> = config
+        self.info_blocks = self
+            .config
             .info_blocks
             .iter()
             .map(|cfg| InfoBlockRuntime {
@@ -248,7 +666,8 @@ impl App {
                 last_output: String::new(),
             })
             .collect();
-        let info_block_views: Vec<crate::ui::components::InfoBlockView> = config
+        let info_block_views: Vec<crate::ui::components::InfoBlockView> = self
+            .config
             .info_blocks
             .iter()
             .map(|cfg| crate::ui::components::InfoBlockView {
@@ -257,9 +676,10 @@ impl App {
                 output: String::new(),
                 status: InfoBlockStatus::Skipped,
                 hidden: false,
+                thresholds: cfg.thresholds,
             })
             .collect();
-        menu.set_info_blocks(info_block_views);
+        self.menu.set_info_blocks(info_block_views);
 
         // Walk the config for any `visible` gates — every gated entry starts
         // hidden until its first probe succeeds.
@@ -267,7 +687,7 @@ impl App {
         let mut hidden_command_paths: HashSet<Vec<usize>> = HashSet::new();
         let mut hidden_info_blocks: HashSet<usize> = HashSet::new();
 
-        for (group_idx, group) in config.commands.iter().enumerate() {
+        for (group_idx, group) in self.config.commands.iter().enumerate() {
             for (entry_idx, entry) in group.commands.iter().enumerate() {
                 let base_path = vec![group_idx, entry_idx];
                 seed_command_visibility(
@@ -279,7 +699,7 @@ impl App {
                 );
             }
         }
-        for (idx, block) in config.info_blocks.iter().enumerate() {
+        for (idx, block) in self.config.info_blocks.iter().enumerate() {
             if let Some(v) = &block.visible {
                 visibility_tasks.push(VisibilityTask {
                     check: v.check.clone(),
@@ -293,85 +713,51 @@ impl App {
             }
         }
 
-        // Propagate initial hidden sets to the UI so the first render filters.
-        menu.set_hidden_command_paths(hidden_command_paths.clone());
-        for idx in &hidden_info_blocks {
-            menu.set_info_block_hidden(*idx, true);
+        // Safe mode runs no probes, so nothing stays hidden waiting for one
+        if self.safe_mode {
+            visibility_tasks.clear();
+            hidden_command_paths.clear();
+            hidden_info_blocks.clear();
         }
 
-        let refresh_config = RefreshConfig::default();
-        let scheduler = RefreshScheduler::new(&refresh_config);
-        let keybinding_resolver = KeybindingResolver::from_config(config.keybindings.as_ref());
-
-        let mut output = Output::with_theme(theme);
-        for warning in validation_warnings {
-            output.add_warning(&warning);
+        // Propagate initial hidden sets to the UI so the first render filters.
+        self.menu
+            .set_hidden_command_paths(hidden_command_paths.clone());
+        if let Some(state) = &menu_state {
+            self.menu.restore_state(state);
         }
+        for idx in &hidden_info_blocks {
+            self.menu.set_info_block_hidden(*idx, true);
+        }
+        self.visibility_tasks = visibility_tasks;
+        self.hidden_command_paths = hidden_command_paths;
+        self.hidden_info_blocks = hidden_info_blocks;
+
+        self.keybinding_resolver =
+            KeybindingResolver::from_config(self.config.keybindings.as_ref());
+        self.help_overlay
+            .update_from_resolver(&self.keybinding_resolver);
+
+        self.command_palette.load_custom_commands(
+            &self.config.commands,
+            &self.hidden_command_paths,
+            self.config.ui.command_icons,
+        );
+        self.command_palette.load_seeds(&self.config.seeds);
 
-        let mut help_overlay = HelpOverlay::with_theme(theme);
-        help_overlay.update_from_resolver(&keybinding_resolver);
-
-        let mut command_palette = CommandPalette::with_theme(theme);
-        command_palette.load_custom_commands(&config.commands, &hidden_command_paths);
-
-        let mut action_bar = ActionBar::with_theme(theme);
-        let cluster_name = context
-            .clone()
-            .or_else(|| Some(cluster_config.container_name.clone()));
-        action_bar.set_cluster_name(cluster_name);
-        action_bar.set_config_path(config_file_path);
+        self.toasts.configure(
+            self.config.notifications.duration,
+            self.config.notifications.history_size,
+        );
 
-        Ok(Self {
-            config,
-            cluster_config,
-            refresh_config,
-            k8s_client,
-            action_bar,
-            menu,
-            output,
-            output_popup: OutputPopup::with_theme(theme),
-            pod_stats: PodStats::with_theme(theme),
-            input_form: InputForm::with_theme(theme),
-            help_overlay,
-            command_palette,
-            confirm_popup: ConfirmPopup::with_theme(theme),
-            diagnostics_overlay: DiagnosticsOverlay::with_theme(theme),
-            pod_detail_panel: PodDetailPanel::with_theme(theme),
-            styles: Styles::from_theme(theme),
-            focus: FocusArea::Content,
-            mode: AppMode::Normal,
-            cluster_status: ClusterStatus::Unknown,
-            is_executing: false,
-            should_quit: false,
-            pending_count: String::new(),
-            pending_command: None,
-            pending_cluster_action: None,
-            pending_sudo_hosts_content: None,
-            running_pods_cache: Vec::new(),
-            pending_pods_cache: Vec::new(),
-            pull_progress_cache: HashMap::new(),
-            active_pull_monitors: HashSet::new(),
-            docker_client: crate::cluster::PlatformInfo::connect_docker().ok(),
-            docker_manager: None,
-            volume_entries_cache: Vec::new(),
-            image_arch_cache: HashMap::new(),
-            image_arch_check_pending: false,
-            shell_session: None,
-            shell_area_size: (0, 0),
-            pending_shell_command: None,
-            message_tx,
-            message_rx,
-            cancel_token: None,
-            scheduler,
-            keybinding_resolver,
-            current_layout: None,
-            menu_width_offset: 0,
-            preflight_auto_triggered: false,
-            info_blocks,
-            visibility_tasks,
-            hidden_command_paths,
-            hidden_info_blocks,
-        })
+        let cluster_name = if self.config.cluster.context.is_empty() {
+            self.cluster_config.container_name.clone()
+        } else {
+            self.config.cluster.context.clone()
+        };
+        self.action_bar.set_cluster_name(Some(cluster_name));
+        self.action_bar.set_config_path(config_file_path.clone());
+        self.config_file_path = config_file_path;
     }
 
     /// Merge a fresh probe result into the task list and, if the flag changed,
@@ -395,8 +781,11 @@ impl App {
                 }
                 self.menu
                     .set_hidden_command_paths(self.hidden_command_paths.clone());
-                self.command_palette
-                    .load_custom_commands(&self.config.commands, &self.hidden_command_paths);
+                self.command_palette.load_custom_commands(
+                    &self.config.commands,
+                    &self.hidden_command_paths,
+                    self.config.ui.command_icons,
+                );
             }
             VisibilityTarget::InfoBlock { index } => {
                 if visible {
@@ -411,80 +800,115 @@ impl App {
 
     /// Run the application event loop
     pub async fn run(&mut self, terminal: &mut Terminal<CrosstermBackend<Stdout>>) -> Result<()> {
+        // Panics in background tasks end up in the crash popup
+        let panic_tx = self.message_tx.clone();
+        crate::crash::set_listener(move |report| {
+            let _ = panic_tx.try_send(AppMessage::TaskPanicked(Box::new(report)));
+        });
+
+        // Before anything can change the cluster
+        self.claim_control().await;
+
         // Initial data load
         self.spawn_status_check();
-
+        crate::hooks::capture_output();
+        self.spawn_hook_completion_listener();
+        self.spawn_hook_run_listener();
+        self.spawn_smoke_report_listener();
+        self.spawn_readiness_listener();
+        self.spawn_docker_event_listener();
+        self.spawn_catalog_update();
+
+        // Sleeps until input, a message or the next scheduled task; redraws
+        // at most once per frame however fast messages arrive
+        let mut events = EventStream::new();
+        let mut dirty = true;
+        let mut last_draw: Option<Instant> = None;
         loop {
-            // Render and capture layout
-            terminal.draw(|frame| {
-                let longest_menu_item = self.menu.longest_item_width();
-                self.current_layout = Some(AppLayout::calculate_with_config(
-                    frame.area(),
-                    &self.config.ui,
-                    longest_menu_item,
-                    self.menu_width_offset,
-                ));
-                self.render(frame);
-            })?;
-
-            // Handle events with timeout for async messages
-            if event::poll(Duration::from_millis(100))? {
-                match event::read()? {
-                    Event::Key(key) => self.handle_key(key.code, key.modifiers),
-                    Event::Mouse(mouse) => self.handle_mouse(mouse),
-                    _ => {}
-                }
+            let now = Instant::now();
+            if dirty && last_draw.is_none_or(|at| now >= at + FRAME_INTERVAL) {
+                // Render and capture layout
+                terminal.draw(|frame| {
+                    self.current_layout = Some(self.main_layout(frame.area()));
+                    self.render(frame);
+                })?;
+                last_draw = Some(now);
+                dirty = false;
+            }
+
+            let mut wake_at = now + IDLE_TICK;
+            if let Some(due) = self.scheduler.next_due() {
+                wake_at = wake_at.min(due);
+            }
+            if self.action_bar.is_animating() {
+                wake_at = wake_at.min(now + SPINNER_INTERVAL);
+            }
+            if let (true, Some(at)) = (dirty, last_draw) {
+                wake_at = wake_at.min(at + FRAME_INTERVAL);
             }
 
-            // Process async messages
-            while let Ok(msg) = self.message_rx.try_recv() {
-                self.handle_message(msg);
+            tokio::select! {
+                event = events.next() => match event {
+                    Some(Ok(Event::Key(key))) => self.handle_key(key.code, key.modifiers),
+                    Some(Ok(Event::Mouse(mouse))) => self.handle_mouse(mouse),
+                    // Resize and focus changes only need a redraw
+                    Some(Ok(_)) => {}
+                    Some(Err(e)) => return Err(e.into()),
+                    None => break,
+                },
+                Some(msg) = self.message_rx.recv() => {
+                    self.handle_message(msg);
+                    while let Ok(msg) = self.message_rx.try_recv() {
+                        self.handle_message(msg);
+                    }
+                }
+                _ = tokio::time::sleep_until(wake_at.into()) => {}
             }
+            dirty = true;
+
+            // Editors and sudo read the terminal themselves; the event
+            // stream's reader thread would take their first keystroke
+            if self.pending_sudo_hosts_content.is_some()
+                || self.pending_config_edit
+                || self.pending_resource_edit.is_some()
+            {
+                drop(events);
+
+                // Handle pending interactive sudo (needs terminal access)
+                if let Some((content, count)) = self.pending_sudo_hosts_content.take() {
+                    self.run_interactive_sudo_hosts_update(terminal, &content, count);
+                }
+
+                // Handle pending config edit (needs terminal access)
+                if std::mem::take(&mut self.pending_config_edit) {
+                    self.run_config_editor(terminal);
+                }
+
+                // Handle pending resource edit (needs terminal access)
+                if let Some((resource, yaml)) = self.pending_resource_edit.take() {
+                    self.run_resource_editor(terminal, resource, yaml);
+                }
 
-            // Handle pending interactive sudo (needs terminal access)
-            if let Some((content, count)) = self.pending_sudo_hosts_content.take() {
-                self.run_interactive_sudo_hosts_update(terminal, &content, count);
+                events = EventStream::new();
             }
 
             // Process scheduled refresh tasks
             for task in self.scheduler.tick() {
-                match task {
-                    RefreshTask::BlinkToggle => {
-                        self.menu.toggle_blink();
-                    }
-                    RefreshTask::IngressRefresh => {
-                        self.spawn_ingress_health_check();
-                        self.spawn_ingress_refresh();
-                        self.spawn_port_forwards_check();
-                    }
-                    RefreshTask::HostsCheck => {
-                        self.spawn_missing_hosts_check();
-                    }
-                    RefreshTask::StatsRefresh => {
-                        self.spawn_pod_stats_check();
-                        self.spawn_pending_pods_check();
-                        self.spawn_pull_progress_check();
-                        // Auto-refresh logs when the Logs tab is visible
-                        if self.pod_detail_panel.is_open()
-                            && self.pod_detail_panel.active_tab() == DetailTab::Logs
-                        {
-                            let pod_name = self.pod_detail_panel.pod_name().to_string();
-                            let namespace = self.pod_detail_panel.namespace().to_string();
-                            self.load_detail_logs(&pod_name, &namespace);
-                        }
-                    }
-                    RefreshTask::VolumeRefresh => {
-                        self.spawn_volume_stats_check();
-                    }
-                }
+                self.run_refresh(task);
             }
 
+            // Drop toasts whose display time is up
+            self.toasts.expire();
+
             // Process per-block info block schedules
             self.info_block_tick();
 
             // Re-evaluate `visible` probes on their own cadence.
             self.visibility_tick();
 
+            // Restart a dead cluster once the watchdog's delay is up
+            self.watchdog_tick();
+
             // Handle shell area resize
             if self.pod_detail_panel.is_open()
                 && self.pod_detail_panel.active_tab() == DetailTab::Shell
@@ -505,11 +929,17 @@ impl App {
             }
         }
 
+        if let Some(path) = &self.config_file_path {
+            if let Err(e) = self.menu.state().save(path) {
+                tracing::warn!("Menu state not saved: {:#}", e);
+            }
+        }
         Ok(())
     }
 
     /// Run sudo interactively by temporarily exiting TUI raw mode.
-    /// This allows native sudo auth (password prompt, TouchID on macOS, etc.)
+    /// This allows native sudo auth (password prompt, TouchID on macOS, etc.);
+    /// the password goes straight to sudo and never passes through k3dev.
     fn run_interactive_sudo_hosts_update(
         &mut self,
         terminal: &mut Terminal<CrosstermBackend<Stdout>>,
@@ -532,9 +962,11 @@ impl App {
             std::path::PathBuf::from("/etc/hosts")
         };
 
-        // Write content to temp file
-        let temp_path = std::env::temp_dir().join("k3dev-hosts");
-        if std::fs::write(&temp_path, content).is_err() {
+        // Write content to a fresh temp file only we can read or replace, so
+        // nothing can swap in other content before sudo copies it
+        let temp_path = std::env::temp_dir().join(format!("k3dev-hosts-{}", std::process::id()));
+        let _ = std::fs::remove_file(&temp_path);
+        if write_private_file(&temp_path, content).is_err() {
             self.output
                 .add_error("Failed to write temporary hosts file");
             return;
@@ -546,7 +978,11 @@ impl App {
 
         // Print a message so the user knows what's happening
         let mut stdout = std::io::stdout();
-        let _ = writeln!(stdout, "\nUpdating /etc/hosts ({} entries)...\n", count);
+        let _ = writeln!(stdout, "\nUpdating /etc/hosts ({} entries)...", count);
+        let _ = writeln!(
+            stdout,
+            "(`k3dev hosts-helper install` makes future updates skip this prompt)\n"
+        );
         let _ = stdout.flush();
 
         // Run sudo cp interactively (allows TouchID, password prompt, etc.)
@@ -610,14 +1046,278 @@ impl App {
         }
     }
 
-    fn render(&mut self, frame: &mut ratatui::Frame) {
-        let longest_menu_item = self.menu.longest_item_width();
+    /// Edit the active config in the user's editor, then reload and re-validate it.
+    fn run_config_editor(&mut self, terminal: &mut Terminal<CrosstermBackend<Stdout>>) {
+        let Some(path) = self.config_file_path.clone() else {
+            return;
+        };
+
+        let status = editor::edit_file(terminal, &path);
+
+        self.clear_output_popup();
+        self.output_popup.set_title("Edit Config".to_string());
+        self.mode = AppMode::OutputPopup;
+
+        match status {
+            Ok(status) if !status.success() => {
+                self.report(OutputLine::warning(format!(
+                    "Editor exited with {}; reloading anyway",
+                    status
+                )));
+            }
+            Ok(_) => {}
+            Err(e) => {
+                self.report(OutputLine::error(format!(
+                    "Failed to launch editor '{}': {}",
+                    editor::editor_command().join(" "),
+                    e
+                )));
+                return;
+            }
+        }
+
+        self.reload_config(&path);
+    }
+
+    /// Reload the config file and apply it, keeping the old config on error.
+    pub(super) fn reload_config(&mut self, path: &std::path::Path) {
+        let path_str = path.to_string_lossy().to_string();
+        match ConfigLoader::new(Some(&path_str)).load_with_path() {
+            Ok((config, config_path)) => {
+                let warnings = ConfigValidator::new(&config).validate().warnings;
+                let theme_changed = config.theme != self.config.theme;
+                self.apply_config(config, config_path);
+
+                self.report(OutputLine::success(format!("Reloaded {}", path.display())));
+                for warning in warnings {
+                    self.report(OutputLine::warning(format!("Config warning: {}", warning)));
+                }
+                if theme_changed {
+                    self.report(OutputLine::info("Theme changes apply after restart"));
+                }
+            }
+            Err(e) => {
+                self.report(OutputLine::error(format!("{:#}", e)));
+                self.report(OutputLine::warning("Keeping the previous config"));
+            }
+        }
+    }
+
+    /// Add a line to both the output buffer and the output popup.
+    fn report(&mut self, line: OutputLine) {
+        self.output.add_line(line.clone());
+        self.output_popup.add_line(line);
+    }
+
+    /// Add a line of a job's output. The popup only gets it while it shows
+    /// that job.
+    fn report_job(&mut self, job: JobId, line: OutputLine) {
+        self.output.add_line(line.clone());
+        self.jobs.add_line(job, line.clone());
+        if self.popup_job == Some(job) {
+            self.output_popup.add_line(line);
+        }
+    }
+
+    /// Whether a job's output is on screen
+    fn job_output_visible(&self, job: JobId) -> bool {
+        (self.mode == AppMode::OutputPopup || self.layout_preset.docks_output())
+            && self.popup_job == Some(job)
+    }
+
+    /// Clear the output popup for something other than a job
+    fn clear_output_popup(&mut self) {
+        self.output_popup.clear();
+        self.popup_job = None;
+    }
+
+    /// Popup title for a job: its name, state and place among the jobs
+    fn job_popup_title(&self, id: JobId) -> Option<String> {
+        let job = self.jobs.get(id)?;
+        let mut title = format!("{} ({})", job.title(), job.state.label());
+        if let Some((index, len)) = self.jobs.position(id).filter(|(_, len)| *len > 1) {
+            title.push_str(&format!(" [{}/{}]", index, len));
+        }
+        Some(title)
+    }
+
+    /// Update the popup title after the shown job changed state
+    fn refresh_job_popup_title(&mut self) {
+        if let Some(title) = self.popup_job.and_then(|id| self.job_popup_title(id)) {
+            self.output_popup.set_title(title);
+        }
+    }
+
+    /// Open the output popup on a job
+    fn show_job_output(&mut self, id: JobId) {
+        let Some(title) = self.job_popup_title(id) else {
+            return;
+        };
+        self.staged_resource_edit = None;
+        self.stuck_namespace = None;
+        self.output_popup.clear();
+        self.output_popup.set_title(title);
+        self.output_popup.set_actions(None);
+        if let Some(job) = self.jobs.get(id) {
+            self.output_popup.show_buffer(job.output.buffer());
+        }
+        self.output_popup.scroll_to_bottom();
+        self.popup_job = Some(id);
+        self.mode = AppMode::OutputPopup;
+    }
+
+    /// Reopen the output popup on the newest running job, or the last one
+    fn show_run_output(&mut self) {
+        let job = self
+            .jobs
+            .active()
+            .last()
+            .or_else(|| self.jobs.latest())
+            .map(|j| j.id);
+        match job {
+            Some(id) => self.show_job_output(id),
+            None => self.toasts.push(OutputLine::info("No command output yet")),
+        }
+    }
+
+    /// Queue a popup command, show its output and start it when its turn comes
+    fn submit_job(&mut self, title: String, kind: JobKind, parallel: bool) {
+        if kind.needs_control() && !self.check_control(&title) {
+            return;
+        }
+        self.output.record_action(&title);
+        self.output.clear();
+        self.output.set_title(title.clone());
+        let overrides = match &kind {
+            JobKind::Command(entry) => entry.output,
+            _ => OutputOverride::default(),
+        };
+        let limit = self.config.output.limit_for(&title, &overrides);
+        let id = self.jobs.submit(title, kind, parallel, limit);
+        self.show_job_output(id);
+        // The docked panel shows it; the popup is one `o` away
+        if self.layout_preset.docks_output() && self.mode == AppMode::OutputPopup {
+            self.mode = AppMode::Normal;
+        }
+        self.start_ready_jobs();
+        if self
+            .jobs
+            .get(id)
+            .is_some_and(|j| j.state == JobState::Queued)
+        {
+            let ahead = self
+                .jobs
+                .active()
+                .find(|j| j.state == JobState::Running && !j.parallel)
+                .map(|j| j.title().to_string())
+                .unwrap_or_default();
+            self.report_job(id, OutputLine::info(format!("Queued behind '{}'", ahead)));
+        }
+    }
+
+    /// Start every queued job whose turn has come
+    fn start_ready_jobs(&mut self) {
+        for id in self.jobs.take_startable() {
+            self.spawn_job(id);
+        }
+        self.refresh_job_popup_title();
+    }
+
+    /// Record the end of a job with its last line and start the next ones.
+    /// A hidden job's result is toasted instead.
+    fn finish_job(&mut self, id: JobId, line: OutputLine, state: JobState) {
+        self.report_job(id, line.clone());
+        let visible = self.job_output_visible(id);
+        let title = self.jobs.get(id).map(|j| j.title().to_string());
+        self.jobs.finish(id, state);
+        if visible {
+            self.refresh_job_popup_title();
+            self.output_popup.scroll_to_bottom();
+        } else if let Some(title) = title {
+            self.toasts.push(OutputLine {
+                content: format!("{}: {}", title, line.content),
+                ..line
+            });
+        }
+        self.start_ready_jobs();
+    }
+
+    /// Cancel a job: a queued one is dropped, a running one is told to stop
+    fn cancel_job(&mut self, id: JobId) {
+        let Some(job) = self.jobs.get(id) else {
+            return;
+        };
+        job.cancel.cancel();
+        if job.state == JobState::Queued {
+            self.finish_job(id, OutputLine::warning("Cancelled"), JobState::Cancelled);
+        } else {
+            self.report_job(id, OutputLine::warning("Cancelling..."));
+        }
+    }
+
+    /// The job Ctrl+C cancels: the one in the popup, else the newest active one
+    fn cancellable_job(&self) -> Option<JobId> {
+        let shown = match self.mode {
+            AppMode::OutputPopup => self.popup_job,
+            AppMode::Build => self.build_job,
+            _ => None,
+        };
+        let shown = shown.filter(|id| self.jobs.get(*id).is_some_and(|j| j.is_active()));
+        shown.or_else(|| self.jobs.active().last().map(|j| j.id))
+    }
+
+    /// Action bar badge for active jobs that are not on screen
+    fn background_jobs_label(&self) -> Option<String> {
+        let hidden: Vec<_> = self
+            .jobs
+            .active()
+            .filter(|j| !self.job_output_visible(j.id))
+            .collect();
+        let latest = hidden.last()?;
+        let key = self
+            .keybinding_resolver
+            .get_binding_display(&KeyAction::ShowOutput)
+            .unwrap_or("o");
+        let summary = match latest.output.lines().last() {
+            Some(line) => format!("{}: {}", latest.title(), line.content),
+            None => latest.title().to_string(),
+        };
+        if hidden.len() == 1 {
+            return Some(format!("{} ({})", summary, key));
+        }
+        let running = hidden
+            .iter()
+            .filter(|j| j.state == JobState::Running)
+            .count();
+        let queued = hidden.len() - running;
+        let mut counts = format!("{} running", running);
+        if queued > 0 {
+            counts.push_str(&format!(", {} queued", queued));
+        }
+        Some(format!("{} · {} ({})", counts, summary, key))
+    }
+
+    /// Panel areas for the current preset, menu width and zoom
+    fn main_layout(&self, area: ratatui::layout::Rect) -> AppLayout {
         let layout = AppLayout::calculate_with_config(
-            frame.area(),
+            area,
             &self.config.ui,
-            longest_menu_item,
+            self.layout_preset,
+            self.menu.longest_item_width(),
             self.menu_width_offset,
         );
+        if !self.zoomed || self.is_cluster_stopped() {
+            return layout;
+        }
+        match self.focus {
+            FocusArea::Content => layout.zoom(Panel::Menu),
+            FocusArea::PodStats => layout.zoom(Panel::PodStats),
+            FocusArea::ActionBar => layout,
+        }
+    }
+
+    fn render(&mut self, frame: &mut ratatui::Frame) {
+        let layout = self.main_layout(frame.area());
 
         let is_cluster_stopped = !matches!(
             self.cluster_status,
@@ -625,6 +1325,10 @@ impl App {
         );
 
         // Render action bar (always visible)
+        let background_run = self.background_jobs_label();
+        self.action_bar.set_background_run(background_run);
+        let (tabs, active_tab) = self.workspace_tabs();
+        self.action_bar.set_workspaces(tabs, active_tab);
         self.action_bar
             .render(frame, layout.action_bar, self.focus == FocusArea::ActionBar);
 
@@ -633,6 +1337,9 @@ impl App {
         } else {
             self.render_running_screen(frame, &layout);
         }
+        if let Some(area) = layout.output {
+            self.output_popup.render_docked(frame, area);
+        }
 
         // Render modal overlays
         if self.mode == AppMode::Help {
@@ -644,6 +1351,12 @@ impl App {
         if self.mode == AppMode::Input {
             self.input_form.render(frame, frame.area());
         }
+        if self.mode == AppMode::BreadcrumbJump {
+            self.breadcrumb_picker.render(frame, frame.area());
+        }
+        if self.mode == AppMode::CommandPreview {
+            self.command_preview.render(frame, frame.area());
+        }
         if self.mode == AppMode::OutputPopup {
             self.output_popup.render(frame, frame.area());
         }
@@ -653,6 +1366,65 @@ impl App {
         if self.mode == AppMode::Diagnostics {
             self.diagnostics_overlay.render(frame, frame.area());
         }
+        if self.mode == AppMode::ClusterInfo {
+            self.cluster_info_view.render(frame, frame.area());
+        }
+        if self.mode == AppMode::PodTop {
+            self.pod_top_view.render(frame, frame.area());
+        }
+        if self.mode == AppMode::Services {
+            self.services_view.render(frame, frame.area());
+        }
+        if self.mode == AppMode::NetworkPolicies {
+            self.network_policy_view.render(frame, frame.area());
+        }
+        if self.mode == AppMode::DependencyMap {
+            self.dependency_map_view.render(frame, frame.area());
+        }
+        if self.mode == AppMode::PolicyViolations {
+            self.policy_view.render(frame, frame.area());
+        }
+        if self.mode == AppMode::Quotas {
+            self.quotas_view.render(frame, frame.area());
+        }
+        if self.mode == AppMode::Runtime {
+            self.runtime_view.render(frame, frame.area());
+        }
+        if self.mode == AppMode::PvBrowser {
+            self.pv_browser_view.render(frame, frame.area());
+        }
+        if self.mode == AppMode::Snapshots {
+            self.snapshots_view.render(frame, frame.area());
+        }
+        if self.mode == AppMode::RefreshTasks {
+            let stats = self.scheduler.stats();
+            self.refresh_tasks_view.render(frame, frame.area(), &stats);
+        }
+        if self.mode == AppMode::HookRuns {
+            self.hook_runs_view.render(frame, frame.area());
+        }
+        if self.mode == AppMode::K3sVersions {
+            self.k3s_version_view.render(frame, frame.area());
+        }
+        if self.mode == AppMode::Build {
+            let state = self
+                .build_job
+                .and_then(|id| self.jobs.get(id))
+                .map_or("queued", |j| j.state.label());
+            self.build_view.render(frame, frame.area(), state);
+        }
+        if self.mode == AppMode::Rbac {
+            self.rbac_view.render(frame, frame.area());
+        }
+        if self.mode == AppMode::SetupWizard {
+            self.setup_wizard.render(frame, frame.area());
+        }
+        if self.mode == AppMode::Crash {
+            self.crash_view.render(frame, frame.area());
+        }
+
+        // Toasts sit above everything else
+        self.toasts.render(frame, frame.area());
     }
 
     /// Render the stopped screen: action list (left) + preflight results (right)
@@ -679,9 +1451,14 @@ impl App {
 
     /// Render the normal running screen: menu (left) + pods (right)
     fn render_running_screen(&mut self, frame: &mut ratatui::Frame, layout: &AppLayout) {
-        // Render menu
-        self.menu
-            .render(frame, layout.menu, self.focus == FocusArea::Content);
+        // Render menu (left without space while the pods are zoomed)
+        if !layout.menu.is_empty() {
+            self.menu
+                .render(frame, layout.menu, self.focus == FocusArea::Content);
+        }
+        if layout.pod_stats.is_empty() {
+            return;
+        }
 
         // Render pod stats panel
         let focused = self.focus == FocusArea::PodStats;
@@ -696,11 +1473,7 @@ impl App {
             } else {
                 ratatui::widgets::BorderType::Rounded
             };
-            let title = if focused {
-                " \u{25b6} Pods \u{25c0} "
-            } else {
-                "   Pods "
-            };
+            let title = self.pod_stats.title(focused);
             let title_style = if focused {
                 self.styles
                     .title
@@ -739,6 +1512,27 @@ impl App {
     }
 }
 
+/// Build the runtime cluster config (infrastructure + hooks + k8s client settings).
+fn cluster_config_from(config: &Config) -> Arc<ClusterConfig> {
+    let kubeconfig = if config.cluster.kubeconfig.is_empty() {
+        None
+    } else {
+        Some(config.cluster.kubeconfig.clone())
+    };
+    let context = if config.cluster.context.is_empty() {
+        None
+    } else {
+        Some(config.cluster.context.clone())
+    };
+    Arc::new(
+        ClusterConfig::from(config.infrastructure.clone())
+            .with_hooks(config.hooks.clone())
+            .with_smoke_tests(config.smoke_tests.clone())
+            .with_start(config.start.clone())
+            .with_k8s_config(kubeconfig, context),
+    )
+}
+
 /// Recursively walk a `CommandEntry`, pushing a `VisibilityTask` for any
 /// `visible` gate found (on the entry itself and on nested children).
 fn seed_command_visibility(
@@ -766,3 +1560,17 @@ fn seed_command_visibility(
         seed_command_visibility(child, child_path, now, tasks, hidden);
     }
 }
+
+/// Create `path` (failing if it exists) readable and writable by the owner only
+fn write_private_file(path: &std::path::Path, content: &str) -> std::io::Result<()> {
+    use std::io::Write;
+
+    let mut options = std::fs::OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    options.open(path)?.write_all(content.as_bytes())
+}
@@ -0,0 +1,198 @@
+//! Namespace create and delete actions
+//!
+//! Deletion asks for confirmation, then waits for the namespace to go away.
+//! When finalizers keep it terminating, the output popup offers to force it.
+
+use std::collections::HashMap;
+
+use super::{App, AppMessage, AppMode, PendingForm};
+use crate::config::{InputDefinition, InputSpec};
+use crate::k8s::namespaces::{
+    create_namespace, delete_namespace, force_finalize_namespace, validate_namespace_name,
+    PROTECTED_NAMESPACES,
+};
+use crate::k8s::NamespaceDeletion;
+use crate::ui::components::OutputLine;
+
+impl App {
+    /// Ask for the name of the namespace to create
+    pub(super) fn open_create_namespace_form(&mut self) {
+        let order = vec!["name".to_string()];
+        let inputs: HashMap<String, InputDefinition> = HashMap::from([(
+            "name".to_string(),
+            InputDefinition::Detailed(InputSpec::Text {
+                prompt: "Name:".to_string(),
+                default: String::new(),
+                required: true,
+            }),
+        )]);
+        self.input_form.setup("Create Namespace", &inputs, &order);
+        self.pending_form = Some(PendingForm::CreateNamespace);
+        self.mode = AppMode::Input;
+    }
+
+    pub(super) fn submit_create_namespace_form(&mut self, values: &HashMap<String, String>) {
+        let name = values.get("name").map(|n| n.trim()).unwrap_or_default();
+        self.open_namespace_popup();
+        if let Err(e) = validate_namespace_name(name) {
+            self.report(OutputLine::error(e));
+            return;
+        }
+        let Some(client) = self.k8s_client.as_ref().map(|c| c.client().clone()) else {
+            self.report(OutputLine::error("Cluster is not running"));
+            return;
+        };
+        self.report(OutputLine::info(format!("Creating namespace {}...", name)));
+
+        let name = name.to_string();
+        let message_tx = self.message_tx.clone();
+        tokio::spawn(async move {
+            let result = create_namespace(&client, &name)
+                .await
+                .map_err(|e| format!("{:#}", e));
+            let _ = message_tx
+                .send(AppMessage::NamespaceCreated { name, result })
+                .await;
+        });
+    }
+
+    pub(super) fn handle_namespace_created(&mut self, name: String, result: Result<(), String>) {
+        match result {
+            Ok(()) => self.report(OutputLine::success(format!("Created namespace {}", name))),
+            Err(e) => self.report(OutputLine::error(e)),
+        }
+    }
+
+    /// List namespaces; the form to pick one for deletion opens once they arrive
+    pub(super) fn open_delete_namespace_form(&mut self) {
+        let Some(client) = self.k8s_client.clone() else {
+            self.open_namespace_popup();
+            self.report(OutputLine::error("Cluster is not running"));
+            return;
+        };
+        let message_tx = self.message_tx.clone();
+        tokio::spawn(async move {
+            let result = client
+                .list_namespaces()
+                .await
+                .map_err(|e| format!("{:#}", e));
+            let _ = message_tx.send(AppMessage::NamespacesListed(result)).await;
+        });
+    }
+
+    pub(super) fn handle_namespaces_listed(&mut self, result: Result<Vec<String>, String>) {
+        let mut options: Vec<String> = match result {
+            Ok(names) => names
+                .into_iter()
+                .filter(|n| !PROTECTED_NAMESPACES.contains(&n.as_str()))
+                .collect(),
+            Err(e) => {
+                self.open_namespace_popup();
+                self.report(OutputLine::error(e));
+                return;
+            }
+        };
+        if options.is_empty() {
+            self.open_namespace_popup();
+            self.report(OutputLine::info("No namespaces to delete"));
+            return;
+        }
+        options.sort();
+
+        let order = vec!["name".to_string()];
+        let inputs: HashMap<String, InputDefinition> = HashMap::from([(
+            "name".to_string(),
+            InputDefinition::Detailed(InputSpec::Select {
+                prompt: "Namespace:".to_string(),
+                options,
+                default: None,
+            }),
+        )]);
+        self.input_form.setup("Delete Namespace", &inputs, &order);
+        self.pending_form = Some(PendingForm::DeleteNamespace);
+        self.mode = AppMode::Input;
+    }
+
+    pub(super) fn submit_delete_namespace_form(&mut self, values: &HashMap<String, String>) {
+        let Some(name) = values.get("name").filter(|n| !n.is_empty()) else {
+            self.mode = AppMode::Normal;
+            return;
+        };
+        self.confirm_popup.set_content(
+            "Delete Namespace",
+            &format!(
+                "This will delete namespace {} and everything in it. This cannot be undone.",
+                name
+            ),
+        );
+        self.pending_namespace_delete = Some(name.clone());
+        self.mode = AppMode::ConfirmDestroy;
+    }
+
+    /// Delete a namespace, or with `force` clear the finalizers keeping it
+    pub(super) fn delete_namespace(&mut self, name: String, force: bool) {
+        self.open_namespace_popup();
+        let Some(client) = self.k8s_client.as_ref().map(|c| c.client().clone()) else {
+            self.report(OutputLine::error("Cluster is not running"));
+            return;
+        };
+        self.report(OutputLine::info(if force {
+            format!("Clearing finalizers of namespace {}...", name)
+        } else {
+            format!("Deleting namespace {}...", name)
+        }));
+
+        let message_tx = self.message_tx.clone();
+        tokio::spawn(async move {
+            let result = if force {
+                force_finalize_namespace(&client, &name).await
+            } else {
+                delete_namespace(&client, &name).await
+            };
+            let _ = message_tx
+                .send(AppMessage::NamespaceDeleted {
+                    name,
+                    result: result.map_err(|e| format!("{:#}", e)),
+                })
+                .await;
+        });
+    }
+
+    /// Report a deletion; a stuck one can be forced from the popup
+    pub(super) fn handle_namespace_deleted(
+        &mut self,
+        name: String,
+        result: Result<NamespaceDeletion, String>,
+    ) {
+        match result {
+            Ok(NamespaceDeletion::Deleted) => {
+                self.report(OutputLine::success(format!("Deleted namespace {}", name)));
+            }
+            Ok(NamespaceDeletion::Stuck(blockers)) => {
+                self.report(OutputLine::warning(format!(
+                    "Namespace {} is still terminating:",
+                    name
+                )));
+                for blocker in blockers {
+                    self.report(OutputLine::warning(format!("  {}", blocker)));
+                }
+                self.report(OutputLine::warning(
+                    "Press [f] to clear its finalizers. Resources they protect may be left behind.",
+                ));
+                self.output_popup
+                    .set_actions(Some("[f] Force finalize".to_string()));
+                self.stuck_namespace = Some(name);
+            }
+            Err(e) => self.report(OutputLine::error(e)),
+        }
+    }
+
+    fn open_namespace_popup(&mut self) {
+        self.staged_resource_edit = None;
+        self.stuck_namespace = None;
+        self.clear_output_popup();
+        self.output_popup.set_title("Namespaces".to_string());
+        self.output_popup.set_actions(None);
+        self.mode = AppMode::OutputPopup;
+    }
+}
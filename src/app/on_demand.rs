@@ -0,0 +1,78 @@
+//! On-demand cluster start
+//!
+//! With `start.on_demand`, k3dev holds the ingress ports whenever the
+//! cluster is down and starts it on the first connection.
+
+use tokio::task::JoinHandle;
+
+use super::messages::AppMessage;
+use super::App;
+use crate::cluster::on_demand::wait_for_connection;
+use crate::cluster::{ClusterInput, ClusterState};
+use crate::ui::components::{ClusterAction, OutputLine};
+
+impl App {
+    /// Listen on the ingress ports while the cluster is down and this
+    /// instance controls it; stop listening as soon as either changes, so
+    /// the container can publish them
+    pub(super) fn update_on_demand(&mut self) {
+        let down = matches!(
+            self.lifecycle.state(),
+            ClusterState::Stopped | ClusterState::NotCreated
+        );
+        if !(down && self.config.start.on_demand && self.control_owner.is_none()) {
+            self.stop_on_demand();
+            return;
+        }
+        if self.on_demand_listener.is_some() {
+            return;
+        }
+        let (http_port, https_port) = (
+            self.cluster_config.http_port,
+            self.cluster_config.https_port,
+        );
+        let message_tx = self.message_tx.clone();
+        self.on_demand_listener = Some(tokio::spawn(async move {
+            let result = wait_for_connection(http_port, https_port)
+                .await
+                .map_err(|e| format!("{:#}", e));
+            let _ = message_tx
+                .send(AppMessage::OnDemandConnection(result))
+                .await;
+        }));
+    }
+
+    /// Mark the cluster starting and stop listening; the listener returned
+    /// has to finish before the container can publish the ingress ports
+    pub(super) fn begin_start(&mut self) -> Option<JoinHandle<()>> {
+        self.advance_lifecycle(ClusterInput::StartBegun);
+        self.stop_on_demand()
+    }
+
+    /// Abort the listener, if any, and hand it back to await
+    fn stop_on_demand(&mut self) -> Option<JoinHandle<()>> {
+        let listener = self.on_demand_listener.take()?;
+        listener.abort();
+        Some(listener)
+    }
+
+    pub(super) fn handle_on_demand_connection(&mut self, result: Result<u16, String>) {
+        self.on_demand_listener = None;
+        match result {
+            Ok(port) => {
+                tracing::info!(port, "Connection while the cluster is down; starting it");
+                let line =
+                    OutputLine::info(format!("Connection on port {}; starting the cluster", port));
+                self.toasts.push(line.clone());
+                self.report(line);
+                self.do_execute_cluster_action(ClusterAction::Start);
+            }
+            Err(e) => {
+                self.report(OutputLine::warning(format!(
+                    "On-demand start unavailable: {}",
+                    e
+                )));
+            }
+        }
+    }
+}
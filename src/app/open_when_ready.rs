@@ -0,0 +1,102 @@
+//! Open an ingress URL once its health check turns green, e.g. right after a
+//! deploy, with a spinner in the action bar until then
+
+use std::time::Duration;
+
+use tokio_util::sync::CancellationToken;
+
+use super::messages::AppMessage;
+use super::App;
+use crate::cluster::{IngressHealthChecker, IngressHealthStatus};
+use crate::ui::components::OutputLine;
+
+/// Time between two checks of the awaited endpoint
+const CHECK_INTERVAL: Duration = Duration::from_secs(2);
+
+/// How long an endpoint gets to turn healthy before the wait gives up
+const GIVE_UP_AFTER: Duration = Duration::from_secs(300);
+
+impl App {
+    /// Wait for the selected ingress path to answer 2xx, then open it. Asking
+    /// again for the same URL stops waiting; another URL replaces it.
+    pub(super) fn open_selected_ingress_when_ready(&mut self) {
+        let Some((host, path)) = self
+            .menu
+            .selected_ingress_endpoint()
+            .map(|(host, path)| (host.to_string(), path.to_string()))
+        else {
+            self.toasts
+                .push(OutputLine::info("Select an ingress path to open"));
+            return;
+        };
+        let url = format!("http://{}{}", host, path);
+
+        if let Some((awaited, stop)) = self.awaited_url.take() {
+            stop.cancel();
+            self.action_bar.set_awaited_url(None);
+            if awaited == url {
+                self.toasts
+                    .push(OutputLine::info(format!("Stopped waiting for {}", url)));
+                return;
+            }
+        }
+
+        let stop = CancellationToken::new();
+        self.awaited_url = Some((url.clone(), stop.clone()));
+        self.action_bar.set_awaited_url(Some(url.clone()));
+
+        let message_tx = self.message_tx.clone();
+        let timeout = self.refresh_config.ingress_health_timeout;
+        tokio::spawn(async move {
+            let wait = async {
+                loop {
+                    let healthy = tokio::time::timeout(
+                        timeout,
+                        IngressHealthChecker::check_endpoint(&host, &path),
+                    )
+                    .await
+                    .is_ok_and(|check| check.status == IngressHealthStatus::Healthy);
+                    if healthy {
+                        return;
+                    }
+                    tokio::time::sleep(CHECK_INTERVAL).await;
+                }
+            };
+            let ready = tokio::select! {
+                result = tokio::time::timeout(GIVE_UP_AFTER, wait) => result.is_ok(),
+                _ = stop.cancelled() => return,
+            };
+            let _ = message_tx
+                .send(AppMessage::AwaitedUrlChecked { url, ready })
+                .await;
+        });
+    }
+
+    pub(super) fn handle_awaited_url_checked(&mut self, url: String, ready: bool) {
+        // A wait that was stopped or replaced in the meantime
+        if self.awaited_url.as_ref().is_none_or(|(u, _)| *u != url) {
+            return;
+        }
+        self.awaited_url = None;
+        self.action_bar.set_awaited_url(None);
+        if ready {
+            self.open_url(&url);
+        } else {
+            let line = OutputLine::warning(format!(
+                "{} didn't turn healthy within {} minutes",
+                url,
+                GIVE_UP_AFTER.as_secs() / 60
+            ));
+            self.toasts.push(line.clone());
+            self.report(line);
+        }
+    }
+
+    /// Stop waiting for a URL (cluster stopped)
+    pub(super) fn cancel_awaited_url(&mut self) {
+        if let Some((_, stop)) = self.awaited_url.take() {
+            stop.cancel();
+            self.action_bar.set_awaited_url(None);
+        }
+    }
+}
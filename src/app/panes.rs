@@ -0,0 +1,66 @@
+//! Pod shells and log follows in tmux or zellij panes
+
+use super::App;
+use crate::multiplexer::Multiplexer;
+use crate::ui::components::OutputLine;
+
+/// What a multiplexer pane runs for the selected pod
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(super) enum PaneCommand {
+    Shell,
+    Logs,
+}
+
+impl App {
+    /// Open a shell in, or the followed logs of, the selected pod in a pane
+    /// of the multiplexer k3dev runs in
+    pub(super) fn open_selected_pod_in_pane(&mut self, what: PaneCommand) {
+        let Some(pod) = self.pod_stats.selected_pod() else {
+            return;
+        };
+        let (pod, namespace) = (pod.name.clone(), pod.namespace.clone());
+        let Some(multiplexer) = Multiplexer::detect() else {
+            self.toasts.push(OutputLine::warning(
+                "Not running inside tmux or zellij; use the detail panel tabs instead",
+            ));
+            return;
+        };
+        let exe = match std::env::current_exe() {
+            Ok(exe) => exe.display().to_string(),
+            Err(e) => {
+                self.report(OutputLine::error(format!(
+                    "Can't find the k3dev binary: {}",
+                    e
+                )));
+                return;
+            }
+        };
+
+        let mut command = vec![exe];
+        if let Some(path) = &self.config_file_path {
+            command.extend(["--config".to_string(), path.display().to_string()]);
+        }
+        let (subcommand, title) = match what {
+            PaneCommand::Shell => ("exec", format!("shell {}", pod)),
+            PaneCommand::Logs => ("logs", format!("logs {}", pod)),
+        };
+        command.extend([
+            subcommand.to_string(),
+            pod.clone(),
+            "--namespace".to_string(),
+            namespace,
+        ]);
+        if what == PaneCommand::Logs {
+            command.push("--follow".to_string());
+        }
+
+        match multiplexer.open(self.config.multiplexer.open_in, &title, &command) {
+            Ok(()) => self.toasts.push(OutputLine::info(format!(
+                "Opened {} in {}",
+                title,
+                multiplexer.name()
+            ))),
+            Err(e) => self.report(OutputLine::error(format!("{:#}", e))),
+        }
+    }
+}
@@ -0,0 +1,166 @@
+//! Diagnose action for the selected pod
+//!
+//! Shows the last exit, termination message, previous logs, probes and recent
+//! events of a crash-looping pod in one output popup, likely causes first.
+
+use super::{App, AppMessage, AppMode};
+use crate::k8s::pod_diagnosis::{diagnose_pod, exit_code_meaning, PodDiagnosis};
+use crate::ui::components::OutputLine;
+
+impl App {
+    pub(super) fn diagnose_selected_pod(&mut self) {
+        let Some(pod) = self.pod_stats.selected_pod() else {
+            return;
+        };
+        let (pod_name, namespace) = (pod.name.clone(), pod.namespace.clone());
+        let Some(client) = self.k8s_client.as_ref().map(|c| c.client().clone()) else {
+            return;
+        };
+
+        self.staged_resource_edit = None;
+        self.stuck_namespace = None;
+        self.clear_output_popup();
+        self.output_popup
+            .set_title(format!("Diagnose {}/{}", namespace, pod_name));
+        self.output_popup.set_actions(None);
+        self.mode = AppMode::OutputPopup;
+        self.report(OutputLine::info("Gathering status, logs and events..."));
+
+        let message_tx = self.message_tx.clone();
+        tokio::spawn(async move {
+            let result = diagnose_pod(&client, &namespace, &pod_name)
+                .await
+                .map_err(|e| format!("{:#}", e));
+            let _ = message_tx.send(AppMessage::PodDiagnosed(result)).await;
+        });
+    }
+
+    pub(super) fn handle_pod_diagnosed(&mut self, result: Result<PodDiagnosis, String>) {
+        let diagnosis = match result {
+            Ok(diagnosis) => diagnosis,
+            Err(e) => {
+                self.report(OutputLine::error(e));
+                return;
+            }
+        };
+        // The details only go to the popup; the output pane gets a summary
+        self.clear_output_popup();
+        for line in diagnosis_lines(&diagnosis) {
+            self.output_popup.add_line(line);
+        }
+        self.output.add_info(format!(
+            "Diagnosed {}/{}: {}",
+            diagnosis.namespace,
+            diagnosis.pod,
+            diagnosis
+                .hints
+                .first()
+                .map(String::as_str)
+                .unwrap_or("no likely cause found")
+        ));
+    }
+}
+
+fn diagnosis_lines(diagnosis: &PodDiagnosis) -> Vec<OutputLine> {
+    let mut lines = Vec::new();
+    lines.push(OutputLine::info(format!(
+        "Pod {} in {} · phase {}",
+        diagnosis.pod, diagnosis.namespace, diagnosis.phase
+    )));
+    if !diagnosis.hints.is_empty() {
+        lines.push(OutputLine::info(""));
+        lines.push(OutputLine::info("── Likely cause ──"));
+        for hint in &diagnosis.hints {
+            lines.push(OutputLine::warning(format!("  {}", hint)));
+        }
+    }
+
+    for container in &diagnosis.containers {
+        lines.push(OutputLine::info(""));
+        lines.push(OutputLine::info(format!(
+            "── Container {} ({}) ──",
+            container.name, container.image
+        )));
+        if let Some((reason, message)) = &container.waiting {
+            let line = if message.is_empty() {
+                format!("  Waiting: {}", reason)
+            } else {
+                format!("  Waiting: {} — {}", reason, message)
+            };
+            lines.push(OutputLine::warning(line));
+        }
+        lines.push(OutputLine::info(format!(
+            "  Restarts: {}",
+            container.restarts
+        )));
+        match &container.last_termination {
+            Some(exit) => {
+                let mut line = format!("  Last exit: code {}", exit.exit_code);
+                if let Some(meaning) = exit_code_meaning(exit.exit_code) {
+                    line.push_str(&format!(" ({})", meaning));
+                }
+                if let Some(reason) = &exit.reason {
+                    line.push_str(&format!(" · {}", reason));
+                }
+                if let Some(at) = &exit.finished_at {
+                    line.push_str(&format!(" · {}", at));
+                }
+                lines.push(if exit.exit_code == 0 {
+                    OutputLine::info(line)
+                } else {
+                    OutputLine::error(line)
+                });
+                if let Some(message) = &exit.message {
+                    lines.push(OutputLine::info("  Termination message:"));
+                    for line in message.lines() {
+                        lines.push(OutputLine::info(format!("    {}", line)));
+                    }
+                }
+            }
+            None => lines.push(OutputLine::info("  Last exit: none recorded")),
+        }
+        if let Some(limit) = &container.memory_limit {
+            lines.push(OutputLine::info(format!("  Memory limit: {}", limit)));
+        }
+        if container.probes.is_empty() {
+            lines.push(OutputLine::info("  Probes: none"));
+        }
+        for probe in &container.probes {
+            lines.push(OutputLine::info(format!("  Probe {}", probe)));
+        }
+        let run = if container.restarts > 0 {
+            "previous run"
+        } else {
+            "current run"
+        };
+        match &container.logs {
+            Ok(log_lines) if log_lines.is_empty() => {
+                lines.push(OutputLine::info(format!("  Logs of the {}: (empty)", run)));
+            }
+            Ok(log_lines) => {
+                lines.push(OutputLine::info(format!(
+                    "  Logs of the {} (last {} lines):",
+                    run,
+                    log_lines.len()
+                )));
+                for line in log_lines {
+                    lines.push(OutputLine::info(format!("    {}", line)));
+                }
+            }
+            Err(e) => lines.push(OutputLine::warning(format!(
+                "  Logs of the {}: unavailable ({})",
+                run, e
+            ))),
+        }
+    }
+
+    lines.push(OutputLine::info(""));
+    lines.push(OutputLine::info("── Recent events ──"));
+    if diagnosis.events.is_empty() {
+        lines.push(OutputLine::info("  (none)"));
+    }
+    for event in &diagnosis.events {
+        lines.push(OutputLine::info(format!("  {}", event)));
+    }
+    lines
+}
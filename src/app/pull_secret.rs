@@ -0,0 +1,113 @@
+//! Provision Pull Secret action
+//!
+//! Lists namespaces, asks which ones should pull private images, then runs a
+//! job that copies the Docker CLI's registry credentials into each of them.
+
+use std::collections::HashMap;
+
+use anyhow::Context;
+use tokio_util::sync::CancellationToken;
+
+use super::jobs::{JobId, JobKind};
+use super::{App, AppMessage, AppMode, PendingForm};
+use crate::commands::CommandContext;
+use crate::config::{InputDefinition, InputSpec};
+use crate::error::K3devError;
+use crate::k8s::namespaces::PROTECTED_NAMESPACES;
+use crate::k8s::pull_secret;
+
+impl App {
+    /// List namespaces; the form to pick them opens once they arrive
+    pub(super) fn open_pull_secret_form(&mut self) {
+        let Some(client) = self.k8s_client.clone() else {
+            self.output.add_error("Cluster is not running");
+            return;
+        };
+        let message_tx = self.message_tx.clone();
+        tokio::spawn(async move {
+            let result = client
+                .list_namespaces()
+                .await
+                .map_err(|e| format!("{:#}", e));
+            let _ = message_tx
+                .send(AppMessage::PullSecretNamespacesListed(result))
+                .await;
+        });
+    }
+
+    pub(super) fn handle_pull_secret_namespaces(&mut self, result: Result<Vec<String>, String>) {
+        let mut options: Vec<String> = match result {
+            Ok(names) => names
+                .into_iter()
+                .filter(|n| !PROTECTED_NAMESPACES.contains(&n.as_str()))
+                .collect(),
+            Err(e) => {
+                self.output.add_error(e);
+                return;
+            }
+        };
+        if options.is_empty() {
+            self.output.add_error("No namespaces to provision");
+            return;
+        }
+        options.sort();
+        let default: Vec<String> = options
+            .iter()
+            .filter(|n| **n == self.config.cluster.default_namespace)
+            .cloned()
+            .collect();
+
+        let order = vec!["namespaces".to_string()];
+        let inputs: HashMap<String, InputDefinition> = HashMap::from([(
+            "namespaces".to_string(),
+            InputDefinition::Detailed(InputSpec::MultiSelect {
+                prompt: "Namespaces:".to_string(),
+                options,
+                default,
+                required: true,
+            }),
+        )]);
+        let title = format!("Provision {}", self.config.pull_secret.name);
+        self.input_form.setup(title, &inputs, &order);
+        self.pending_form = Some(PendingForm::PullSecret);
+        self.mode = AppMode::Input;
+    }
+
+    pub(super) fn submit_pull_secret_form(&mut self, values: &HashMap<String, String>) {
+        let namespaces: Vec<String> = values
+            .get("namespaces")
+            .map(|v| v.split_whitespace().map(str::to_string).collect())
+            .unwrap_or_default();
+        if namespaces.is_empty() {
+            self.mode = AppMode::Normal;
+            return;
+        }
+        self.submit_job(
+            format!("Pull secret {}", self.config.pull_secret.name),
+            JobKind::PullSecret(namespaces),
+            true,
+        );
+    }
+
+    pub(super) fn spawn_pull_secret(
+        &mut self,
+        job: JobId,
+        cancel: CancellationToken,
+        namespaces: Vec<String>,
+    ) {
+        let timeout = self.refresh_config.cluster_operation_timeout;
+        let (ctx, tx) = CommandContext::new(self.message_tx.clone(), job, cancel, timeout);
+        let client = self.k8s_client.as_ref().map(|c| c.client().clone());
+        let config = self.config.pull_secret.clone();
+
+        tokio::spawn(async move {
+            ctx.execute(move |_output_tx| async move {
+                let client = client.ok_or(K3devError::ClusterNotRunning)?;
+                pull_secret::provision_namespaces(&client, &config, &namespaces, &tx)
+                    .await
+                    .context("Pull secret provisioning failed")
+            })
+            .await;
+        });
+    }
+}
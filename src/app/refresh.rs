@@ -3,39 +3,164 @@
 //! This module contains all spawn_* methods for background data refresh.
 
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 
-use tokio::sync::Semaphore;
+use futures_util::StreamExt;
+use tokio::sync::{mpsc, Semaphore};
+use tokio::task::JoinHandle;
 
 use crate::cluster::docker::pull_progress::monitor_image_pull;
 use crate::cluster::{
-    ClusterManager, ClusterStatus, DockerManager, IngressHealthChecker, IngressManager,
-    PortForwardDetector,
+    degraded, traefik_drift, ClusterConfig, ClusterManager, ClusterStatus, IngressEntry,
+    IngressHealthChecker, IngressManager, PortForwardDetector,
 };
 use crate::commands::{capture_exec, check_visible, strip_ansi, trim_output};
-use crate::config::{ExecutionTarget, VisibleCheck};
+use crate::config::{ExecutionTarget, HealthProbe, RefreshTask, VisibleCheck};
+use crate::hooks::HookExecutor;
 use crate::k8s::K8sClient;
+use crate::ui::components::DetailTab;
 
 use super::messages::{InfoBlockResult, InfoBlockStatus};
-use super::{App, AppMessage};
+use super::{App, AppMessage, AppMode};
 
 /// Maximum concurrent manifest fetches across all pull monitors
 static MANIFEST_SEMAPHORE: once_cell::sync::Lazy<Arc<Semaphore>> =
     once_cell::sync::Lazy::new(|| Arc::new(Semaphore::new(5)));
 
+/// How long to gather Docker events before passing them on as one batch
+const DOCKER_EVENT_BATCH_WINDOW: Duration = Duration::from_millis(250);
+
+/// Wait before resubscribing after the Docker events stream ends
+const DOCKER_EVENT_RETRY_DELAY: Duration = Duration::from_secs(5);
+
+/// Background work of a refresh task; resolves to why it failed, if it did
+pub(super) type RefreshHandle = JoinHandle<Result<(), String>>;
+
+/// The app's Kubernetes client, or a new one until it is ready
+async fn shared_or_new(
+    shared: Option<K8sClient>,
+    kubeconfig: Option<String>,
+    context: Option<String>,
+) -> anyhow::Result<K8sClient> {
+    match shared {
+        Some(client) => Ok(client),
+        None => K8sClient::new(kubeconfig.as_deref(), context.as_deref()).await,
+    }
+}
+
+/// Error of a refresh whose `tokio::time::timeout` elapsed
+fn timed_out(what: &str, timeout: Duration) -> String {
+    format!("{} timed out after {:?}", what, timeout)
+}
+
 impl App {
     /// Whether the cluster is fully running (spawn_* helpers guard on this).
-    fn cluster_is_running(&self) -> bool {
+    pub(super) fn cluster_is_running(&self) -> bool {
         matches!(self.cluster_status, ClusterStatus::Running)
     }
 
+    /// Start the work behind a scheduled or requested refresh task
+    pub(super) fn run_refresh(&mut self, task: RefreshTask) {
+        let handles: Vec<RefreshHandle> = match task {
+            RefreshTask::BlinkToggle => {
+                self.menu.toggle_blink();
+                return;
+            }
+            RefreshTask::IngressRefresh => [
+                self.spawn_ingress_health_check(),
+                self.spawn_ingress_refresh(),
+                self.spawn_port_forwards_check(),
+            ]
+            .into_iter()
+            .flatten()
+            .collect(),
+            RefreshTask::HostsCheck => self.spawn_missing_hosts_check().into_iter().collect(),
+            RefreshTask::StatsRefresh => {
+                self.spawn_pull_progress_check();
+                // Auto-refresh logs when the Logs tab is visible
+                if self.pod_detail_panel.is_open()
+                    && self.pod_detail_panel.active_tab() == DetailTab::Logs
+                {
+                    let pod_name = self.pod_detail_panel.pod_name().to_string();
+                    let namespace = self.pod_detail_panel.namespace().to_string();
+                    self.load_detail_logs(&pod_name, &namespace);
+                }
+                [
+                    self.spawn_pod_stats_check(),
+                    self.spawn_pending_pods_check(),
+                ]
+                .into_iter()
+                .flatten()
+                .collect()
+            }
+            RefreshTask::VolumeRefresh => self.spawn_volume_stats_check().into_iter().collect(),
+            RefreshTask::HealthCheck => self.spawn_health_check().into_iter().collect(),
+            RefreshTask::TraefikDrift => self.spawn_traefik_drift_check().into_iter().collect(),
+            RefreshTask::RecurringHook(index) => {
+                self.spawn_recurring_hook(index).into_iter().collect()
+            }
+            RefreshTask::ClusterInfoRefresh if self.mode == AppMode::ClusterInfo => {
+                vec![self.spawn_cluster_info_refresh()]
+            }
+            RefreshTask::PodTopRefresh if self.mode == AppMode::PodTop => {
+                vec![self.spawn_pod_top_refresh()]
+            }
+            RefreshTask::ServicesRefresh if self.mode == AppMode::Services => {
+                vec![self.spawn_services_refresh()]
+            }
+            // Views that aren't open don't refresh
+            RefreshTask::ClusterInfoRefresh
+            | RefreshTask::PodTopRefresh
+            | RefreshTask::ServicesRefresh => return,
+        };
+        self.track_refresh(task, handles);
+    }
+
+    /// Run recurring hook `index`. Its output goes to the Hooks view and a
+    /// failure comes back with the refresh result.
+    fn spawn_recurring_hook(&self, index: usize) -> Option<RefreshHandle> {
+        let hook = self.config.hooks.recurring.get(index)?.hook.clone();
+        let executor = HookExecutor::new(self.config.hooks.clone());
+        Some(tokio::spawn(async move {
+            let (output_tx, _) = mpsc::channel(1);
+            executor
+                .execute_recurring(&hook, output_tx)
+                .await
+                .map_err(|e| e.to_string())
+        }))
+    }
+
+    /// Mark `task` in flight until all of `handles` are done, then report
+    /// the first error with [`AppMessage::RefreshFinished`]
+    fn track_refresh(&mut self, task: RefreshTask, handles: Vec<RefreshHandle>) {
+        if handles.is_empty() {
+            return;
+        }
+        self.scheduler.start(task);
+        let message_tx = self.message_tx.clone();
+        tokio::spawn(async move {
+            let mut error = None;
+            for handle in handles {
+                let result = handle.await.unwrap_or_else(|e| Err(e.to_string()));
+                if let Err(e) = result {
+                    error.get_or_insert(e);
+                }
+            }
+            let _ = message_tx
+                .send(AppMessage::RefreshFinished { task, error })
+                .await;
+        });
+    }
+
     pub(super) fn spawn_status_check(&self) {
         let message_tx = self.message_tx.clone();
         let cluster_config = Arc::clone(&self.cluster_config);
+        let clients = self.clients.clone();
         let timeout = self.refresh_config.status_check_timeout;
 
         tokio::spawn(async move {
             let result = tokio::time::timeout(timeout, async {
-                let manager = match ClusterManager::new(cluster_config).await {
+                let manager = match ClusterManager::with_clients(cluster_config, &clients).await {
                     Ok(m) => m,
                     Err(_) => return ClusterStatus::Unknown,
                 };
@@ -44,103 +169,300 @@ impl App {
             .await;
 
             let status = result.unwrap_or(ClusterStatus::Unknown);
+            // Reconnect next time; Docker may come back on another socket
+            if matches!(
+                status,
+                ClusterStatus::RuntimeNotRunning | ClusterStatus::Unknown
+            ) {
+                clients.reset_docker().await;
+            }
             let _ = message_tx
                 .send(AppMessage::ClusterStatusUpdate(status))
                 .await;
         });
     }
 
-    pub(super) fn spawn_ingress_refresh(&self) {
+    pub(super) fn spawn_ingress_refresh(&self) -> Option<RefreshHandle> {
         if !self.cluster_is_running() {
-            return;
+            return None;
         }
 
         let message_tx = self.message_tx.clone();
         let domain = self.cluster_config.domain.clone();
         let timeout = self.refresh_config.ingress_timeout;
 
-        tokio::spawn(async move {
+        Some(tokio::spawn(async move {
             let result = tokio::time::timeout(timeout, async {
                 let mut ingress_manager = IngressManager::with_domain(domain);
                 ingress_manager.get_ingress_entries().await
             })
             .await;
 
-            if let Ok(Ok(entries)) = result {
-                let _ = message_tx
-                    .send(AppMessage::IngressEntriesLoaded(entries))
-                    .await;
+            match result {
+                Ok(Ok(entries)) => {
+                    let _ = message_tx
+                        .send(AppMessage::IngressEntriesLoaded(entries))
+                        .await;
+                    Ok(())
+                }
+                Ok(Err(e)) => Err(format!("{:#}", e)),
+                Err(_) => Err(timed_out("Listing ingresses", timeout)),
+            }
+        }))
+    }
+
+    /// Forward hook completions (broadcast from inside cluster operations) to the app.
+    pub(super) fn spawn_hook_completion_listener(&self) {
+        let message_tx = self.message_tx.clone();
+        let mut completions = crate::hooks::subscribe_completions();
+
+        tokio::spawn(async move {
+            loop {
+                match completions.recv().await {
+                    Ok(completion) => {
+                        if message_tx
+                            .send(AppMessage::HookFinished(completion))
+                            .await
+                            .is_err()
+                        {
+                            break;
+                        }
+                    }
+                    Err(tokio::sync::broadcast::error::RecvError::Lagged(_)) => continue,
+                    Err(tokio::sync::broadcast::error::RecvError::Closed) => break,
+                }
             }
         });
     }
 
-    pub(super) fn spawn_ingress_health_check(&self) {
+    /// Forward single hook runs, with their output, to the Hooks view.
+    pub(super) fn spawn_hook_run_listener(&self) {
+        let message_tx = self.message_tx.clone();
+        let mut runs = crate::hooks::subscribe_runs();
+
+        tokio::spawn(async move {
+            loop {
+                match runs.recv().await {
+                    Ok(run) => {
+                        if message_tx
+                            .send(AppMessage::HookRunFinished(run))
+                            .await
+                            .is_err()
+                        {
+                            break;
+                        }
+                    }
+                    Err(tokio::sync::broadcast::error::RecvError::Lagged(_)) => continue,
+                    Err(tokio::sync::broadcast::error::RecvError::Closed) => break,
+                }
+            }
+        });
+    }
+
+    /// Forward smoke test reports (broadcast from inside cluster starts) to the app.
+    pub(super) fn spawn_smoke_report_listener(&self) {
+        let message_tx = self.message_tx.clone();
+        let mut reports = crate::smoke::subscribe_reports();
+
+        tokio::spawn(async move {
+            loop {
+                match reports.recv().await {
+                    Ok(report) => {
+                        if message_tx
+                            .send(AppMessage::SmokeTestsFinished(report))
+                            .await
+                            .is_err()
+                        {
+                            break;
+                        }
+                    }
+                    Err(tokio::sync::broadcast::error::RecvError::Lagged(_)) => continue,
+                    Err(tokio::sync::broadcast::error::RecvError::Closed) => break,
+                }
+            }
+        });
+    }
+
+    /// Forward container start/stop events so status and pod stats refresh
+    /// right away. Events arriving together (a pod's containers, a cluster
+    /// start) are batched into one message; the stream is resubscribed if
+    /// the daemon connection drops.
+    pub(super) fn spawn_docker_event_listener(&self) {
+        let message_tx = self.message_tx.clone();
+        let clients = self.clients.clone();
+
+        tokio::spawn(async move {
+            loop {
+                if let Ok(docker) = clients.docker().await {
+                    let mut events = Box::pin(docker.container_events());
+                    while let Some(event) = events.next().await {
+                        let mut batch = vec![event];
+                        let deadline = tokio::time::sleep(DOCKER_EVENT_BATCH_WINDOW);
+                        tokio::pin!(deadline);
+                        loop {
+                            tokio::select! {
+                                _ = &mut deadline => break,
+                                next = events.next() => match next {
+                                    Some(event) => batch.push(event),
+                                    None => break,
+                                },
+                            }
+                        }
+                        if message_tx
+                            .send(AppMessage::ContainersChanged(batch))
+                            .await
+                            .is_err()
+                        {
+                            return;
+                        }
+                    }
+                }
+                if message_tx.is_closed() {
+                    return;
+                }
+                // The stream ended because the daemon went away; connect anew
+                clients.reset_docker().await;
+                tokio::time::sleep(DOCKER_EVENT_RETRY_DELAY).await;
+            }
+        });
+    }
+
+    pub(super) fn spawn_readiness_listener(&self) {
+        let message_tx = self.message_tx.clone();
+        let mut updates = crate::cluster::subscribe_readiness();
+
+        tokio::spawn(async move {
+            loop {
+                match updates.recv().await {
+                    Ok(update) => {
+                        if message_tx
+                            .send(AppMessage::ReadinessUpdated(update))
+                            .await
+                            .is_err()
+                        {
+                            break;
+                        }
+                    }
+                    Err(tokio::sync::broadcast::error::RecvError::Lagged(_)) => continue,
+                    Err(tokio::sync::broadcast::error::RecvError::Closed) => break,
+                }
+            }
+        });
+    }
+
+    pub(super) fn spawn_ingress_health_check(&self) -> Option<RefreshHandle> {
         if !self.cluster_is_running() {
-            return;
+            return None;
         }
 
         let message_tx = self.message_tx.clone();
-        let entries = self.menu.get_ingress_entries().to_vec();
+        let now = Instant::now();
+        // Only the paths that are not backing off after failures
+        let entries: Vec<IngressEntry> = self
+            .menu
+            .get_ingress_entries()
+            .iter()
+            .map(|entry| IngressEntry {
+                host: entry.host.clone(),
+                paths: entry
+                    .paths
+                    .iter()
+                    .filter(|path| {
+                        self.ingress_backoff
+                            .is_due(&format!("{}|{}", entry.host, path), now)
+                    })
+                    .cloned()
+                    .collect(),
+            })
+            .filter(|entry| !entry.paths.is_empty())
+            .collect();
         let timeout = self.refresh_config.ingress_health_timeout;
+        let concurrency = self.refresh_config.ingress_health_concurrency;
 
         if entries.is_empty() {
-            return;
+            return None;
         }
+        // The in-cluster probe needs the API; host checks don't
+        let in_cluster = match self.refresh_config.ingress_health_probe {
+            HealthProbe::Host => None,
+            HealthProbe::Cluster => Some(self.k8s_client.clone()?),
+        };
 
-        tokio::spawn(async move {
-            let result =
-                tokio::time::timeout(timeout, IngressHealthChecker::check_endpoints(&entries))
-                    .await;
+        Some(tokio::spawn(async move {
+            let check = async {
+                match &in_cluster {
+                    Some(k8s) => {
+                        IngressHealthChecker::check_endpoints_in_cluster(k8s, &entries, concurrency)
+                            .await
+                    }
+                    None => IngressHealthChecker::check_endpoints(&entries, concurrency).await,
+                }
+            };
+            let health = tokio::time::timeout(timeout, check)
+                .await
+                .map_err(|_| timed_out("Checking ingress health", timeout))?;
 
-            if let Ok(health) = result {
-                let _ = message_tx
-                    .send(AppMessage::IngressHealthUpdated(health))
-                    .await;
-            }
-        });
+            let _ = message_tx
+                .send(AppMessage::IngressHealthUpdated(health))
+                .await;
+            Ok(())
+        }))
     }
 
-    pub(super) fn spawn_missing_hosts_check(&self) {
+    pub(super) fn spawn_missing_hosts_check(&self) -> Option<RefreshHandle> {
         if !self.cluster_is_running() {
-            return;
+            return None;
         }
 
         let message_tx = self.message_tx.clone();
         let domain = self.cluster_config.domain.clone();
         let timeout = self.refresh_config.ingress_timeout;
 
-        tokio::spawn(async move {
+        Some(tokio::spawn(async move {
             let result = tokio::time::timeout(timeout, async {
                 let mut ingress_manager = IngressManager::with_domain(domain);
                 ingress_manager.get_missing_hosts().await
             })
             .await;
 
-            if let Ok(Ok(missing)) = result {
-                let _ = message_tx
-                    .send(AppMessage::MissingHostsUpdated(missing))
-                    .await;
+            match result {
+                Ok(Ok(missing)) => {
+                    let _ = message_tx
+                        .send(AppMessage::MissingHostsUpdated(missing))
+                        .await;
+                    Ok(())
+                }
+                Ok(Err(e)) => Err(format!("{:#}", e)),
+                Err(_) => Err(timed_out("Checking /etc/hosts", timeout)),
             }
-        });
+        }))
     }
 
-    pub(super) fn spawn_pod_stats_check(&self) {
+    pub(super) fn spawn_pod_stats_check(&self) -> Option<RefreshHandle> {
         if !self.cluster_is_running() {
             let message_tx = self.message_tx.clone();
             tokio::spawn(async move {
                 let _ = message_tx.send(AppMessage::PodStatsUpdated(vec![])).await;
             });
-            return;
+            return None;
         }
 
         let message_tx = self.message_tx.clone();
+        let clients = self.clients.clone();
         let container_name = self.cluster_config.container_name.clone();
         let timeout = self.refresh_config.docker_stats_timeout;
+        let needs_capacity = self.host_capacity.is_none();
 
-        tokio::spawn(async move {
+        Some(tokio::spawn(async move {
             let result = tokio::time::timeout(timeout, async {
-                let docker = DockerManager::from_default_socket()
-                    .map_err(|_| anyhow::anyhow!("Failed to create DockerManager"))?;
+                let docker = clients.docker().await?;
+                if needs_capacity {
+                    if let Some(capacity) = docker.host_capacity().await {
+                        let _ = message_tx
+                            .send(AppMessage::HostCapacityLoaded(capacity))
+                            .await;
+                    }
+                }
                 // Try agent first, fall back to direct cgroup reads
                 match docker.get_pod_stats_via_agent(&container_name).await {
                     Ok(stats) => Ok(stats),
@@ -149,18 +471,20 @@ impl App {
             })
             .await;
 
-            let stats = match result {
-                Ok(Ok(stats)) => stats,
-                _ => vec![],
+            let (stats, outcome) = match result {
+                Ok(Ok(stats)) => (stats, Ok(())),
+                Ok(Err(e)) => (vec![], Err(format!("{:#}", e))),
+                Err(_) => (vec![], Err(timed_out("Reading pod stats", timeout))),
             };
 
             // Send all pod stats - filtering is now done during merge
             // with pending pods data (which has K8s status info)
             let _ = message_tx.send(AppMessage::PodStatsUpdated(stats)).await;
-        });
+            outcome
+        }))
     }
 
-    pub(super) fn spawn_pending_pods_check(&self) {
+    pub(super) fn spawn_pending_pods_check(&self) -> Option<RefreshHandle> {
         if !self.cluster_is_running() {
             let message_tx = self.message_tx.clone();
             tokio::spawn(async move {
@@ -168,30 +492,33 @@ impl App {
                     .send(AppMessage::PendingPodsUpdated(vec![]))
                     .await;
             });
-            return;
+            return None;
         }
 
         let message_tx = self.message_tx.clone();
+        let k8s_client = self.k8s_client.clone();
         let kubeconfig = self.cluster_config.kubeconfig.clone();
         let context = self.cluster_config.context.clone();
         let timeout = self.refresh_config.docker_stats_timeout;
 
-        tokio::spawn(async move {
+        Some(tokio::spawn(async move {
             let result = tokio::time::timeout(timeout, async {
-                let k8s_client = K8sClient::new(kubeconfig.as_deref(), context.as_deref()).await?;
+                let k8s_client = shared_or_new(k8s_client, kubeconfig, context).await?;
                 k8s_client.list_pending_pods().await
             })
             .await;
 
-            let pending = match result {
-                Ok(Ok(pods)) => pods,
-                _ => vec![],
+            let (pending, outcome) = match result {
+                Ok(Ok(pods)) => (pods, Ok(())),
+                Ok(Err(e)) => (vec![], Err(format!("{:#}", e))),
+                Err(_) => (vec![], Err(timed_out("Listing pods", timeout))),
             };
 
             let _ = message_tx
                 .send(AppMessage::PendingPodsUpdated(pending))
                 .await;
-        });
+            outcome
+        }))
     }
 
     /// Spawn streaming monitors for images currently being pulled.
@@ -248,22 +575,179 @@ impl App {
         }
     }
 
-    pub(super) fn spawn_volume_stats_check(&self) {
+    /// Gather a fresh snapshot for the cluster info view
+    fn spawn_cluster_info_refresh(&self) -> RefreshHandle {
+        let message_tx = self.message_tx.clone();
+        let clients = self.clients.clone();
+        let cluster_config = Arc::clone(&self.cluster_config);
+        let timeout = self.refresh_config.cluster_info_timeout;
+
+        tokio::spawn(async move {
+            let result = tokio::time::timeout(timeout, async {
+                let mut manager = ClusterManager::with_clients(cluster_config, &clients)
+                    .await
+                    .map_err(|e| format!("{:#}", e))?;
+                Ok::<_, String>(manager.cluster_info().await)
+            })
+            .await
+            .unwrap_or_else(|_| Err(timed_out("Gathering cluster info", timeout)));
+
+            let (info, outcome) = match result {
+                Ok(info) => (Some(Box::new(info)), Ok(())),
+                Err(e) => (None, Err(e)),
+            };
+            let _ = message_tx.send(AppMessage::ClusterInfoLoaded(info)).await;
+            outcome
+        })
+    }
+
+    /// List and probe services for the Services view
+    fn spawn_services_refresh(&self) -> RefreshHandle {
+        let message_tx = self.message_tx.clone();
+        let clients = self.clients.clone();
+        let cluster_config = Arc::clone(&self.cluster_config);
+        let timeout = self.refresh_config.services_timeout;
+
+        tokio::spawn(async move {
+            let result = tokio::time::timeout(timeout, async {
+                let mut manager = ClusterManager::with_clients(cluster_config, &clients).await?;
+                manager.services().await
+            })
+            .await;
+
+            let services = match result {
+                Ok(Ok(services)) => Ok(services),
+                Ok(Err(e)) => Err(format!("{:#}", e)),
+                Err(_) => Err(timed_out("Listing services", timeout)),
+            };
+            let outcome = services.as_ref().map(|_| ()).map_err(Clone::clone);
+            let _ = message_tx.send(AppMessage::ServicesLoaded(services)).await;
+            outcome
+        })
+    }
+
+    /// Check core deployments and nodes; only a completed check is reported,
+    /// so a slow API server doesn't count as recovered or degraded
+    fn spawn_health_check(&self) -> Option<RefreshHandle> {
         if !self.cluster_is_running() {
-            return;
+            return None;
+        }
+        let message_tx = self.message_tx.clone();
+        let clients = self.clients.clone();
+        let timeout = self.refresh_config.health_check_timeout;
+
+        Some(tokio::spawn(async move {
+            let result = tokio::time::timeout(timeout, async {
+                let mut kube_ops = clients.kube_ops().await;
+                degraded::check_health(&mut kube_ops).await
+            })
+            .await;
+
+            match result {
+                Ok(Ok(problems)) => {
+                    let _ = message_tx
+                        .send(AppMessage::ClusterHealthChecked(problems))
+                        .await;
+                    Ok(())
+                }
+                Ok(Err(e)) => {
+                    let error = format!("{:#}", e);
+                    let _ = message_tx
+                        .send(AppMessage::ClusterApiFailed(error.clone()))
+                        .await;
+                    Err(error)
+                }
+                Err(_) => {
+                    let error = timed_out("Cluster health check", timeout);
+                    let _ = message_tx
+                        .send(AppMessage::ClusterApiFailed(error.clone()))
+                        .await;
+                    Err(error)
+                }
+            }
+        }))
+    }
+
+    /// Compare the live Traefik HelmChartConfig with the one k3dev applies
+    fn spawn_traefik_drift_check(&self) -> Option<RefreshHandle> {
+        if !self.cluster_is_running() {
+            return None;
+        }
+        let message_tx = self.message_tx.clone();
+        let clients = self.clients.clone();
+        let cluster_config = self.cluster_config.clone();
+        let timeout = self.refresh_config.traefik_drift_timeout;
+
+        Some(tokio::spawn(async move {
+            let result = tokio::time::timeout(timeout, async {
+                let mut kube_ops = clients.kube_ops().await;
+                traefik_drift::check_drift(&cluster_config, &mut kube_ops).await
+            })
+            .await;
+
+            match result {
+                Ok(Ok(drift)) => {
+                    let _ = message_tx
+                        .send(AppMessage::TraefikDriftChecked(drift))
+                        .await;
+                    Ok(())
+                }
+                Ok(Err(e)) => Err(format!("{:#}", e)),
+                Err(_) => Err(timed_out("Traefik drift check", timeout)),
+            }
+        }))
+    }
+
+    /// List the processes of the pod shown in the top popup
+    fn spawn_pod_top_refresh(&self) -> RefreshHandle {
+        let message_tx = self.message_tx.clone();
+        let clients = self.clients.clone();
+        let (pod, namespace) = self.pod_top_view.pod();
+        let (pod, namespace) = (pod.to_string(), namespace.to_string());
+        let timeout = self.refresh_config.pod_top_timeout;
+
+        tokio::spawn(async move {
+            let result = tokio::time::timeout(timeout, async {
+                let docker = clients.docker().await?;
+                docker.pod_processes(&pod, &namespace).await
+            })
+            .await;
+
+            let processes = match result {
+                Ok(Ok(processes)) => Ok(processes),
+                Ok(Err(e)) => Err(format!("{:#}", e)),
+                Err(_) => Err(timed_out("Listing processes", timeout)),
+            };
+            let outcome = processes.as_ref().map(|_| ()).map_err(Clone::clone);
+            let _ = message_tx
+                .send(AppMessage::PodTopLoaded {
+                    pod,
+                    namespace,
+                    processes,
+                })
+                .await;
+            outcome
+        })
+    }
+
+    pub(super) fn spawn_volume_stats_check(&self) -> Option<RefreshHandle> {
+        if !self.cluster_is_running() {
+            return None;
         }
 
         let message_tx = self.message_tx.clone();
+        let clients = self.clients.clone();
+        let k8s_client = self.k8s_client.clone();
         let kubeconfig = self.cluster_config.kubeconfig.clone();
         let context = self.cluster_config.context.clone();
         let timeout = self.refresh_config.volume_timeout;
-        let storage_path = crate::cluster::K3sManager::LOCAL_PV_STORAGE_PATH.to_string();
+        let storage_path =
+            crate::cluster::K3sManager::configured_pv_storage_path(&self.cluster_config);
         let container_name = self.cluster_config.container_name.clone();
 
-        tokio::spawn(async move {
+        Some(tokio::spawn(async move {
             let result = tokio::time::timeout(timeout, async {
-                let docker = DockerManager::from_default_socket()
-                    .map_err(|_| anyhow::anyhow!("Failed to create DockerManager"))?;
+                let docker = clients.docker().await?;
 
                 // 1. Get volume stats via docker exec + container mounts (PVC dirs, sizes, pod mapping)
                 let volume_stats = docker
@@ -271,11 +755,10 @@ impl App {
                     .await;
 
                 // 2. Get PVC metadata from K8s API (single call: capacity, phase, storage_class)
-                let pvc_metadata =
-                    match K8sClient::new(kubeconfig.as_deref(), context.as_deref()).await {
-                        Ok(k8s) => k8s.list_pvc_metadata().await.unwrap_or_default(),
-                        Err(_) => std::collections::HashMap::new(),
-                    };
+                let pvc_metadata = match shared_or_new(k8s_client, kubeconfig, context).await {
+                    Ok(k8s) => k8s.list_pvc_metadata().await.unwrap_or_default(),
+                    Err(_) => std::collections::HashMap::new(),
+                };
 
                 // 3. Merge: filesystem data + K8s metadata → Vec<PvcInfo>
                 let fs_stats = volume_stats.unwrap_or_default();
@@ -332,18 +815,51 @@ impl App {
             })
             .await;
 
-            let entries = match result {
-                Ok(Ok(e)) => e,
-                _ => vec![],
+            let (entries, outcome) = match result {
+                Ok(Ok(e)) => (e, Ok(())),
+                Ok(Err(e)) => (vec![], Err(format!("{:#}", e))),
+                Err(_) => (vec![], Err(timed_out("Reading volume stats", timeout))),
             };
 
             let _ = message_tx
                 .send(AppMessage::VolumeStatsUpdated(entries))
                 .await;
+            outcome
+        }))
+    }
+
+    /// Read which host port the API ended up on, which differs from
+    /// `api_port` when that was taken when the container was created
+    pub(super) fn spawn_api_port_check(&self) {
+        let message_tx = self.message_tx.clone();
+        let clients = self.clients.clone();
+        let container = self.cluster_config.container_name.clone();
+        tokio::spawn(async move {
+            let Ok(docker) = clients.docker().await else {
+                return;
+            };
+            if let Some(port) = docker
+                .published_port(&container, ClusterConfig::K3S_API_PORT)
+                .await
+            {
+                let _ = message_tx.send(AppMessage::ApiHostPortResolved(port)).await;
+            }
         });
     }
 
-    pub(super) fn spawn_port_forwards_check(&self) {
+    /// Ports published by the cluster container, shown in the menu
+    pub(super) fn set_forwarded_ports(&mut self, api_host_port: u16) {
+        let config = &self.cluster_config;
+        let mut ports = vec![
+            (config.http_port, config.http_port),
+            (config.https_port, config.https_port),
+            (api_host_port, ClusterConfig::K3S_API_PORT),
+        ];
+        ports.extend(config.additional_ports.clone());
+        self.menu.set_forwarded_ports(ports);
+    }
+
+    pub(super) fn spawn_port_forwards_check(&self) -> Option<RefreshHandle> {
         if !self.cluster_is_running() {
             let message_tx = self.message_tx.clone();
             tokio::spawn(async move {
@@ -351,21 +867,26 @@ impl App {
                     .send(AppMessage::ActivePortForwardsUpdated(vec![]))
                     .await;
             });
-            return;
+            return None;
         }
 
         let message_tx = self.message_tx.clone();
         let timeout = self.refresh_config.port_forward_timeout;
 
-        tokio::spawn(async move {
+        Some(tokio::spawn(async move {
             let mut detector = PortForwardDetector::new();
             let result = tokio::time::timeout(timeout, detector.detect()).await;
 
+            let outcome = result
+                .as_ref()
+                .map(|_| ())
+                .map_err(|_| timed_out("Detecting port forwards", timeout));
             let forwards = result.unwrap_or_default();
             let _ = message_tx
                 .send(AppMessage::ActivePortForwardsUpdated(forwards))
                 .await;
-        });
+            outcome
+        }))
     }
 
     /// Check image architectures for running pods (spawned when new pods appear)
@@ -375,12 +896,12 @@ impl App {
         }
 
         let message_tx = self.message_tx.clone();
+        let clients = self.clients.clone();
         let timeout = self.refresh_config.docker_stats_timeout;
 
         tokio::spawn(async move {
             let result = tokio::time::timeout(timeout, async {
-                let docker = DockerManager::from_default_socket()
-                    .map_err(|_| anyhow::anyhow!("Failed to create DockerManager"))?;
+                let docker = clients.docker().await?;
                 Ok::<_, anyhow::Error>(docker.get_pod_image_architectures().await)
             })
             .await;
@@ -397,6 +918,9 @@ impl App {
 
     /// Check each configured info block; spawn a refresh task if its interval has elapsed.
     pub(super) fn info_block_tick(&mut self) {
+        if self.safe_mode {
+            return;
+        }
         let now = std::time::Instant::now();
         let len = self.info_blocks.len();
         for i in 0..len {
@@ -466,14 +990,15 @@ impl App {
 
         let k8s_client = self.k8s_client.clone();
         let message_tx = self.message_tx.clone();
+        let clients = self.clients.clone();
 
         tokio::spawn(async move {
             // Build a DockerManager on-demand for checks that need it (mirrors
             // how info-block exec probes handle Docker access).
             let docker = match &check {
-                VisibleCheck::Container { .. } => DockerManager::from_default_socket().ok(),
+                VisibleCheck::Container { .. } => clients.docker().await.ok(),
                 VisibleCheck::Exec(cfg) if matches!(cfg.target, ExecutionTarget::Docker { .. }) => {
-                    DockerManager::from_default_socket().ok()
+                    clients.docker().await.ok()
                 }
                 _ => None,
             };
@@ -501,10 +1026,11 @@ impl App {
             .min(std::time::Duration::from_secs(60));
         let k8s_client = self.k8s_client.clone();
         let message_tx = self.message_tx.clone();
+        let clients = self.clients.clone();
 
         tokio::spawn(async move {
             let docker = match &exec.target {
-                ExecutionTarget::Docker { .. } => DockerManager::from_default_socket().ok(),
+                ExecutionTarget::Docker { .. } => clients.docker().await.ok(),
                 _ => None,
             };
             let result =
@@ -0,0 +1,279 @@
+//! Resource YAML editing
+//!
+//! Fetches a resource as YAML, opens it in the user's editor, shows a diff
+//! and applies the result with server-side apply.
+
+use ratatui::{backend::CrosstermBackend, Terminal};
+use std::collections::HashMap;
+use std::io::{Stdout, Write};
+
+use super::{editor, App, AppMessage, AppMode, PendingForm};
+use crate::config::{InputDefinition, InputSpec};
+use crate::k8s::diff::{self, DiffLine};
+use crate::k8s::resource_edit::{apply_resource_yaml, fetch_resource_yaml, owning_deployment};
+use crate::k8s::{ApplyOutcome, EditableKind, ResourceRef};
+use crate::ui::components::OutputLine;
+
+/// Unchanged lines shown around each change in the diff
+const DIFF_CONTEXT: usize = 3;
+
+/// An edited manifest waiting for the user to confirm the apply
+pub(super) struct StagedResourceEdit {
+    pub(super) resource: ResourceRef,
+    pub(super) yaml: String,
+    /// Set after a conflict; the next apply must be forced
+    pub(super) conflict: bool,
+}
+
+impl App {
+    /// Ask for the kind and `namespace/name` of the resource to edit
+    pub(super) fn open_resource_edit_form(&mut self) {
+        let kinds: Vec<String> = EditableKind::all()
+            .iter()
+            .map(|k| k.as_str().to_string())
+            .collect();
+        let order = vec!["kind".to_string(), "resource".to_string()];
+        let inputs: HashMap<String, InputDefinition> = HashMap::from([
+            (
+                "kind".to_string(),
+                InputDefinition::Detailed(InputSpec::Select {
+                    prompt: "Kind:".to_string(),
+                    options: kinds,
+                    default: None,
+                }),
+            ),
+            (
+                "resource".to_string(),
+                InputDefinition::Detailed(InputSpec::Text {
+                    prompt: "Resource (namespace/name):".to_string(),
+                    default: String::new(),
+                    required: true,
+                }),
+            ),
+        ]);
+
+        self.input_form.setup("Edit Resource", &inputs, &order);
+        self.pending_form = Some(PendingForm::ResourceEdit);
+        self.mode = AppMode::Input;
+    }
+
+    /// Handle the submitted resource form
+    pub(super) fn submit_resource_edit_form(&mut self, values: &HashMap<String, String>) {
+        let kind = values
+            .get("kind")
+            .and_then(|k| EditableKind::parse(k))
+            .unwrap_or(EditableKind::Deployment);
+        let target = values.get("resource").map(String::as_str).unwrap_or("");
+
+        match ResourceRef::parse(kind, target, &self.config.cluster.default_namespace) {
+            Ok(resource) => self.start_resource_edit(resource),
+            Err(e) => {
+                self.open_resource_edit_popup();
+                self.report(OutputLine::error(e.to_string()));
+            }
+        }
+    }
+
+    /// Edit the Deployment owning the pod selected in the pod list
+    pub(super) fn edit_selected_pod_owner(&mut self) {
+        let Some(pod) = self.pod_stats.selected_pod() else {
+            return;
+        };
+        let (pod_name, namespace) = (pod.name.clone(), pod.namespace.clone());
+        let Some(client) = self.k8s_client.as_ref().map(|c| c.client().clone()) else {
+            return;
+        };
+
+        self.open_resource_edit_popup();
+        self.report(OutputLine::info(format!(
+            "Looking up the Deployment owning {}/{}...",
+            namespace, pod_name
+        )));
+
+        let message_tx = self.message_tx.clone();
+        tokio::spawn(async move {
+            let msg = match owning_deployment(&client, &namespace, &pod_name).await {
+                Ok(resource) => match fetch_resource_yaml(&client, &resource).await {
+                    Ok(yaml) => AppMessage::ResourceYamlLoaded { resource, yaml },
+                    Err(e) => AppMessage::ResourceEditFailed(format!("{:#}", e)),
+                },
+                Err(e) => AppMessage::ResourceEditFailed(format!("{:#}", e)),
+            };
+            let _ = message_tx.send(msg).await;
+        });
+    }
+
+    /// Fetch a resource's YAML; the editor opens once it arrives
+    fn start_resource_edit(&mut self, resource: ResourceRef) {
+        self.open_resource_edit_popup();
+        let Some(client) = self.k8s_client.as_ref().map(|c| c.client().clone()) else {
+            self.report(OutputLine::error("Cluster is not running"));
+            return;
+        };
+        self.report(OutputLine::info(format!("Fetching {}...", resource)));
+
+        let message_tx = self.message_tx.clone();
+        tokio::spawn(async move {
+            let msg = match fetch_resource_yaml(&client, &resource).await {
+                Ok(yaml) => AppMessage::ResourceYamlLoaded { resource, yaml },
+                Err(e) => AppMessage::ResourceEditFailed(format!("{:#}", e)),
+            };
+            let _ = message_tx.send(msg).await;
+        });
+    }
+
+    fn open_resource_edit_popup(&mut self) {
+        self.staged_resource_edit = None;
+        self.clear_output_popup();
+        self.output_popup.set_title("Edit Resource".to_string());
+        self.output_popup.set_actions(None);
+        self.mode = AppMode::OutputPopup;
+    }
+
+    /// Open the fetched YAML in the editor and stage the result for apply.
+    pub(super) fn run_resource_editor(
+        &mut self,
+        terminal: &mut Terminal<CrosstermBackend<Stdout>>,
+        resource: ResourceRef,
+        original: String,
+    ) {
+        // Secrets end up in here: readable by the user only, under a name
+        // nobody can plant a symlink at, and removed on every path out
+        let file = tempfile::Builder::new()
+            .prefix(&format!(
+                "k3dev-{}-{}-{}-",
+                resource.kind.as_str(),
+                resource.namespace,
+                resource.name
+            ))
+            .suffix(".yaml")
+            .tempfile()
+            .and_then(|mut file| file.write_all(original.as_bytes()).map(|()| file));
+        let file = match file {
+            Ok(file) => file,
+            Err(e) => {
+                self.report(OutputLine::error(format!(
+                    "Failed to write a temporary file: {}",
+                    e
+                )));
+                return;
+            }
+        };
+
+        let status = editor::edit_file(terminal, file.path());
+        let edited = std::fs::read_to_string(file.path());
+        drop(file);
+
+        match status {
+            Ok(status) if !status.success() => {
+                self.report(OutputLine::warning(format!(
+                    "Editor exited with {}; discarding changes",
+                    status
+                )));
+                return;
+            }
+            Ok(_) => {}
+            Err(e) => {
+                self.report(OutputLine::error(format!(
+                    "Failed to launch editor '{}': {}",
+                    editor::editor_command().join(" "),
+                    e
+                )));
+                return;
+            }
+        }
+        let edited = match edited {
+            Ok(edited) => edited,
+            Err(e) => {
+                self.report(OutputLine::error(format!("Failed to read edits: {}", e)));
+                return;
+            }
+        };
+
+        let changes = diff::line_diff(&original, &edited);
+        if !diff::has_changes(&changes) {
+            self.report(OutputLine::info(format!("No changes to {}", resource)));
+            return;
+        }
+
+        self.report(OutputLine::info(format!("Changes to {}:", resource)));
+        for line in diff::with_context(&changes, DIFF_CONTEXT) {
+            let content = line.to_prefixed();
+            self.output_popup.add_line(match line {
+                DiffLine::Added(_) => OutputLine::success(content),
+                DiffLine::Removed(_) => OutputLine::error(content),
+                DiffLine::Same(_) => OutputLine::info(content),
+            });
+        }
+        self.output_popup.set_actions(Some("[a] Apply".to_string()));
+
+        self.staged_resource_edit = Some(StagedResourceEdit {
+            resource,
+            yaml: edited,
+            conflict: false,
+        });
+    }
+
+    /// Apply the staged edit. A conflicting apply is only retried with `force`.
+    pub(super) fn apply_staged_resource_edit(&mut self, force: bool) {
+        let Some(staged) = self.staged_resource_edit.as_ref() else {
+            return;
+        };
+        if staged.conflict && !force {
+            return;
+        }
+        let Some(client) = self.k8s_client.as_ref().map(|c| c.client().clone()) else {
+            self.report(OutputLine::error("Cluster is not running"));
+            return;
+        };
+
+        let resource = staged.resource.clone();
+        let yaml = staged.yaml.clone();
+        self.output_popup.set_actions(None);
+        self.report(OutputLine::info(if force {
+            format!("Force-applying {}...", resource)
+        } else {
+            format!("Applying {}...", resource)
+        }));
+
+        let message_tx = self.message_tx.clone();
+        tokio::spawn(async move {
+            let result = apply_resource_yaml(&client, &resource, &yaml, force)
+                .await
+                .map_err(|e| format!("{:#}", e));
+            let _ = message_tx
+                .send(AppMessage::ResourceApplied { resource, result })
+                .await;
+        });
+    }
+
+    /// Report the outcome of an apply; conflicts keep the edit staged for a forced retry.
+    pub(super) fn handle_resource_applied(
+        &mut self,
+        resource: ResourceRef,
+        result: Result<ApplyOutcome, String>,
+    ) {
+        match result {
+            Ok(ApplyOutcome::Applied) => {
+                self.staged_resource_edit = None;
+                self.report(OutputLine::success(format!("Applied {}", resource)));
+            }
+            Ok(ApplyOutcome::Conflict(message)) => {
+                self.report(OutputLine::warning(format!("Conflict: {}", message)));
+                self.report(OutputLine::warning(
+                    "The resource changed or another manager owns these fields. \
+                     Press [f] to force apply, or Esc to discard.",
+                ));
+                if let Some(staged) = self.staged_resource_edit.as_mut() {
+                    staged.conflict = true;
+                }
+                self.output_popup
+                    .set_actions(Some("[f] Force apply".to_string()));
+            }
+            Err(e) => {
+                self.staged_resource_edit = None;
+                self.report(OutputLine::error(e));
+            }
+        }
+    }
+}
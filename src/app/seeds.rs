@@ -0,0 +1,70 @@
+//! Load Seed actions
+//!
+//! Each bundle under `seeds:` is a palette action that runs as a job; the
+//! seed last applied to the cluster is shown in the action bar.
+
+use anyhow::Context;
+use tokio_util::sync::CancellationToken;
+
+use super::jobs::{JobId, JobKind};
+use super::{App, AppMessage};
+use crate::commands::CommandContext;
+use crate::config::SeedConfig;
+use crate::error::K3devError;
+use crate::k8s::seeds::{self, SeedRecord};
+
+impl App {
+    pub(super) fn load_seed(&mut self, name: &str) {
+        let Some(seed) = self.config.seeds.iter().find(|s| s.name == name).cloned() else {
+            self.output.add_error(format!("Seed not found: {}", name));
+            return;
+        };
+        self.submit_job(
+            format!("Seed {}", seed.name),
+            JobKind::Seed(Box::new(seed)),
+            false,
+        );
+    }
+
+    pub(super) fn spawn_seed(&mut self, job: JobId, cancel: CancellationToken, seed: SeedConfig) {
+        let timeout = self.refresh_config.cluster_operation_timeout;
+        let (ctx, tx) = CommandContext::new(self.message_tx.clone(), job, cancel, timeout);
+        let client = self.k8s_client.clone();
+        let default_namespace = self.config.cluster.default_namespace.clone();
+        let message_tx = self.message_tx.clone();
+
+        tokio::spawn(async move {
+            ctx.execute(move |_output_tx| async move {
+                let client = client.ok_or(K3devError::ClusterNotRunning)?;
+                let record = seeds::apply_seed(&client, &seed, &default_namespace, &tx)
+                    .await
+                    .context("Seed failed")?;
+                let _ = message_tx
+                    .send(AppMessage::SeedRecordLoaded(Some(record)))
+                    .await;
+                Ok(())
+            })
+            .await;
+        });
+    }
+
+    /// Read which seed the cluster was last seeded with
+    pub(super) fn spawn_seed_record_load(&self) {
+        let Some(client) = self.k8s_client.as_ref().map(|c| c.client().clone()) else {
+            return;
+        };
+        let message_tx = self.message_tx.clone();
+        tokio::spawn(async move {
+            match seeds::last_applied(&client).await {
+                Ok(record) => {
+                    let _ = message_tx.send(AppMessage::SeedRecordLoaded(record)).await;
+                }
+                Err(e) => tracing::debug!("Seed record not read: {:#}", e),
+            }
+        });
+    }
+
+    pub(super) fn set_seed_record(&mut self, record: Option<SeedRecord>) {
+        self.action_bar.set_seed(record.map(|r| r.name));
+    }
+}
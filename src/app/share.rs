@@ -0,0 +1,125 @@
+//! Share Ingress Port: SSH remote forwards of the ingress ports, listed in
+//! the Forwarded Ports section while they last
+
+use std::collections::HashMap;
+
+use tokio_util::sync::CancellationToken;
+
+use super::messages::AppMessage;
+use super::{App, AppMode, PendingForm};
+use crate::cluster::share::{self, share_port};
+use crate::config::{InputDefinition, InputSpec};
+use crate::ui::components::{OutputLine, SharedPort};
+
+/// A running forward and how to stop it
+pub(super) struct Share {
+    port: SharedPort,
+    stop: CancellationToken,
+}
+
+impl App {
+    /// Ask which ingress port to share
+    pub(super) fn open_share_form(&mut self) {
+        if self.config.share.host.is_empty() {
+            self.report(OutputLine::error(
+                "Set share.host (and share.user) in the config to share ports",
+            ));
+            return;
+        }
+        if !self.cluster_is_running() {
+            self.toasts
+                .push(OutputLine::warning("Cluster is not running"));
+            return;
+        }
+        let options = vec![
+            format!("{} (http)", self.cluster_config.http_port),
+            format!("{} (https)", self.cluster_config.https_port),
+        ];
+        let order = vec!["port".to_string()];
+        let inputs: HashMap<String, InputDefinition> = HashMap::from([(
+            "port".to_string(),
+            InputDefinition::Detailed(InputSpec::Select {
+                prompt: format!("Share on {}:", share::destination(&self.config.share)),
+                options,
+                default: None,
+            }),
+        )]);
+        self.input_form.setup("Share Ingress Port", &inputs, &order);
+        self.pending_form = Some(PendingForm::Share);
+        self.mode = AppMode::Input;
+    }
+
+    pub(super) fn submit_share_form(&mut self, values: &HashMap<String, String>) {
+        self.mode = AppMode::Normal;
+        let port = values
+            .get("port")
+            .and_then(|choice| choice.split_whitespace().next())
+            .and_then(|port| port.parse::<u16>().ok());
+        if let Some(port) = port {
+            self.start_share(port);
+        }
+    }
+
+    fn start_share(&mut self, local_port: u16) {
+        let config = self.config.share.clone();
+        let remote = format!("{}:{}", config.host, config.remote_port);
+        if self.shares.iter().any(|s| s.port.remote == remote) {
+            self.report(OutputLine::warning(format!(
+                "{} is already shared; Stop Sharing first",
+                remote
+            )));
+            return;
+        }
+
+        let stop = CancellationToken::new();
+        self.shares.push(Share {
+            port: SharedPort {
+                local_port,
+                remote: remote.clone(),
+            },
+            stop: stop.clone(),
+        });
+        self.sync_shared_ports();
+        self.report(OutputLine::info(format!(
+            "Sharing port {} on {}",
+            local_port, remote
+        )));
+
+        let message_tx = self.message_tx.clone();
+        tokio::spawn(async move {
+            let result = share_port(&config, local_port, stop)
+                .await
+                .map_err(|e| format!("{:#}", e));
+            let _ = message_tx
+                .send(AppMessage::ShareEnded { remote, result })
+                .await;
+        });
+    }
+
+    /// Stop every forward; each one reports when its ssh is gone
+    pub(super) fn stop_sharing(&mut self) {
+        if self.shares.is_empty() {
+            self.toasts.push(OutputLine::info("Nothing is shared"));
+            return;
+        }
+        for share in &self.shares {
+            share.stop.cancel();
+        }
+    }
+
+    pub(super) fn handle_share_ended(&mut self, remote: String, result: Result<(), String>) {
+        self.shares.retain(|s| s.port.remote != remote);
+        self.sync_shared_ports();
+        let line = match result {
+            Ok(()) => OutputLine::info(format!("Stopped sharing on {}", remote)),
+            Err(e) => OutputLine::error(format!("Sharing on {} ended: {}", remote, e)),
+        };
+        self.toasts.push(line.clone());
+        self.report(line);
+    }
+
+    fn sync_shared_ports(&mut self) {
+        let ports = self.shares.iter().map(|s| s.port.clone()).collect();
+        self.menu.set_shared_ports(ports);
+    }
+}
@@ -0,0 +1,168 @@
+//! Snapshots view actions
+//!
+//! Saving the running cluster as a named snapshot and recreating the cluster
+//! from a chosen one both run as jobs; the restart asks for confirmation
+//! first, as it replaces the cluster's current data.
+
+use std::collections::HashMap;
+use std::sync::Arc;
+
+use anyhow::Context;
+use tokio_util::sync::CancellationToken;
+
+use super::jobs::{JobId, JobKind};
+use super::{App, AppMessage, AppMode, PendingForm};
+use crate::cluster::ClusterManager;
+use crate::commands::CommandContext;
+use crate::config::{InputDefinition, InputSpec};
+use crate::ui::components::OutputLine;
+
+impl App {
+    pub(super) fn open_snapshots(&mut self) {
+        self.snapshots_view.reset();
+        self.mode = AppMode::Snapshots;
+        self.load_snapshots();
+    }
+
+    pub(super) fn load_snapshots(&mut self) {
+        let message_tx = self.message_tx.clone();
+        let cluster_config = Arc::clone(&self.cluster_config);
+        let clients = self.clients.clone();
+        let timeout = self.refresh_config.volume_timeout;
+        tokio::spawn(async move {
+            let result = tokio::time::timeout(timeout, async {
+                let manager = ClusterManager::for_action(cluster_config, &clients).await?;
+                manager.list_snapshots().await
+            })
+            .await;
+            let snapshots = match result {
+                Ok(Ok(snapshots)) => Ok(snapshots),
+                Ok(Err(e)) => Err(format!("{:#}", e)),
+                Err(_) => Err(format!("Listing snapshots timed out after {:?}", timeout)),
+            };
+            let _ = message_tx
+                .send(AppMessage::SnapshotsLoaded(snapshots))
+                .await;
+        });
+    }
+
+    /// Ask before replacing the cluster with the selected snapshot
+    pub(super) fn confirm_start_from_selected_snapshot(&mut self) {
+        let Some(snapshot) = self.snapshots_view.selected().cloned() else {
+            return;
+        };
+        if let Some(reason) = &snapshot.incompatibility {
+            self.toasts.push(OutputLine::warning(format!(
+                "Can't start from this snapshot: {}",
+                reason
+            )));
+            return;
+        }
+        let name = snapshot.name.as_deref().unwrap_or("the automatic snapshot");
+        self.confirm_popup.set_content(
+            "Start From Snapshot",
+            &format!(
+                "This will recreate the cluster from {}. Its current data, pods and volumes are replaced.",
+                name
+            ),
+        );
+        self.pending_snapshot_start = Some(snapshot.image);
+        self.mode = AppMode::ConfirmDestroy;
+    }
+
+    pub(super) fn start_from_snapshot(&mut self, image: String) {
+        self.submit_job(
+            format!("Start from {}", image),
+            JobKind::StartFromSnapshot(image),
+            false,
+        );
+    }
+
+    /// Ask for the name to save the running cluster under
+    pub(super) fn open_save_snapshot_form(&mut self) {
+        if !self.cluster_is_running() {
+            self.toasts
+                .push(OutputLine::warning("Cluster is not running"));
+            return;
+        }
+        let order = vec!["name".to_string()];
+        let inputs: HashMap<String, InputDefinition> = HashMap::from([(
+            "name".to_string(),
+            InputDefinition::Detailed(InputSpec::Text {
+                prompt: "Name:".to_string(),
+                default: String::new(),
+                required: true,
+            }),
+        )]);
+        self.input_form.setup("Save Snapshot", &inputs, &order);
+        self.pending_form = Some(PendingForm::SaveSnapshot);
+        self.mode = AppMode::Input;
+    }
+
+    pub(super) fn submit_save_snapshot_form(&mut self, values: &HashMap<String, String>) {
+        let name = values.get("name").map(|n| n.trim()).unwrap_or_default();
+        if name.is_empty() {
+            self.mode = AppMode::Snapshots;
+            return;
+        }
+        self.submit_job(
+            format!("Save snapshot {}", name),
+            JobKind::SaveSnapshot(name.to_string()),
+            false,
+        );
+    }
+
+    pub(super) fn spawn_save_snapshot(
+        &mut self,
+        job: JobId,
+        cancel: CancellationToken,
+        name: String,
+    ) {
+        let timeout = self.refresh_config.cluster_operation_timeout;
+        let (ctx, tx) = CommandContext::new(self.message_tx.clone(), job, cancel, timeout);
+        let cluster_config = Arc::clone(&self.cluster_config);
+        let clients = self.clients.clone();
+
+        tokio::spawn(async move {
+            ctx.execute(move |_output_tx| async move {
+                let manager = ClusterManager::for_action(cluster_config, &clients)
+                    .await
+                    .context("Manager error")?;
+                manager
+                    .save_snapshot(&name, tx)
+                    .await
+                    .context("Saving the snapshot failed")
+            })
+            .await;
+        });
+    }
+
+    pub(super) fn spawn_start_from_snapshot(
+        &mut self,
+        job: JobId,
+        cancel: CancellationToken,
+        image: String,
+    ) {
+        let on_demand = self.begin_start();
+        let timeout = self.refresh_config.cluster_operation_timeout;
+        let (ctx, tx) = CommandContext::new(self.message_tx.clone(), job, cancel, timeout);
+        let cluster_config = Arc::clone(&self.cluster_config);
+        let clients = self.clients.clone();
+
+        tokio::spawn(async move {
+            if let Some(listener) = on_demand {
+                let _ = listener.await;
+            }
+            ctx.execute(move |_output_tx| async move {
+                let mut manager = ClusterManager::for_action(cluster_config, &clients)
+                    .await
+                    .context("Manager error")?;
+                let result = manager.start_from_snapshot(&image, tx).await;
+                // The kubeconfig points at the new API server now
+                clients.reset_kube().await;
+                result.with_context(|| format!("Starting from {} failed", image))
+            })
+            .await;
+        });
+    }
+}
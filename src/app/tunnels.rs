@@ -0,0 +1,151 @@
+//! Open Public Tunnel: cloudflared or ngrok in front of an ingress host, with
+//! the public URL shown under the host in the Ingress section
+
+use std::collections::HashMap;
+
+use tokio_util::sync::CancellationToken;
+
+use super::messages::AppMessage;
+use super::{App, AppMode, PendingForm};
+use crate::cluster::tunnel::Tunnel;
+use crate::config::{InputDefinition, InputSpec};
+use crate::ui::components::OutputLine;
+
+/// A tunnel client for one ingress host; `url` is None until it's up
+pub(super) struct PublicTunnel {
+    url: Option<String>,
+    stop: CancellationToken,
+}
+
+impl App {
+    /// Ask which ingress host to tunnel to, starting from the selected one
+    pub(super) fn open_tunnel_form(&mut self) {
+        if !self.cluster_is_running() {
+            self.toasts
+                .push(OutputLine::warning("Cluster is not running"));
+            return;
+        }
+        let options: Vec<String> = self
+            .menu
+            .get_ingress_entries()
+            .iter()
+            .map(|e| e.host.clone())
+            .collect();
+        if options.is_empty() {
+            self.toasts
+                .push(OutputLine::info("No ingress hosts to tunnel to"));
+            return;
+        }
+        let order = vec!["host".to_string()];
+        let inputs: HashMap<String, InputDefinition> = HashMap::from([(
+            "host".to_string(),
+            InputDefinition::Detailed(InputSpec::Select {
+                prompt: "Ingress host:".to_string(),
+                options,
+                default: self.menu.selected_ingress_host().map(str::to_string),
+            }),
+        )]);
+        self.input_form.setup("Open Public Tunnel", &inputs, &order);
+        self.pending_form = Some(PendingForm::Tunnel);
+        self.mode = AppMode::Input;
+    }
+
+    pub(super) fn submit_tunnel_form(&mut self, values: &HashMap<String, String>) {
+        self.mode = AppMode::Normal;
+        if let Some(host) = values.get("host").filter(|h| !h.is_empty()) {
+            self.open_tunnel(host.clone());
+        }
+    }
+
+    fn open_tunnel(&mut self, host: String) {
+        if let Some(tunnel) = self.tunnels.get(&host) {
+            let state = match &tunnel.url {
+                Some(url) => format!("{} is already public at {}", host, url),
+                None => format!("A tunnel to {} is opening", host),
+            };
+            self.toasts.push(OutputLine::info(state));
+            return;
+        }
+
+        let provider = self.config.tunnel.provider;
+        let local_port = self.cluster_config.http_port;
+        let stop = CancellationToken::new();
+        self.tunnels.insert(
+            host.clone(),
+            PublicTunnel {
+                url: None,
+                stop: stop.clone(),
+            },
+        );
+        self.report(OutputLine::info(format!("Opening a tunnel to {}...", host)));
+
+        let message_tx = self.message_tx.clone();
+        tokio::spawn(async move {
+            let opened = tokio::select! {
+                opened = Tunnel::open(provider, &host, local_port) => opened,
+                _ = stop.cancelled() => {
+                    let _ = message_tx
+                        .send(AppMessage::TunnelClosed { host, result: Ok(()) })
+                        .await;
+                    return;
+                }
+            };
+            let result = match opened {
+                Ok(tunnel) => {
+                    let _ = message_tx
+                        .send(AppMessage::TunnelOpened {
+                            host: host.clone(),
+                            url: tunnel.url.clone(),
+                        })
+                        .await;
+                    tunnel.run(stop).await
+                }
+                Err(e) => Err(e),
+            };
+            let _ = message_tx
+                .send(AppMessage::TunnelClosed {
+                    host,
+                    result: result.map_err(|e| format!("{:#}", e)),
+                })
+                .await;
+        });
+    }
+
+    /// Close every tunnel; each one reports when its client is gone
+    pub(super) fn close_tunnels(&mut self) {
+        for tunnel in self.tunnels.values() {
+            tunnel.stop.cancel();
+        }
+    }
+
+    pub(super) fn handle_tunnel_opened(&mut self, host: String, url: String) {
+        let Some(tunnel) = self.tunnels.get_mut(&host) else {
+            return;
+        };
+        tunnel.url = Some(url.clone());
+        self.sync_tunnel_urls();
+        let line = OutputLine::success(format!("{} is public at {}", host, url));
+        self.toasts.push(line.clone());
+        self.report(line);
+    }
+
+    pub(super) fn handle_tunnel_closed(&mut self, host: String, result: Result<(), String>) {
+        self.tunnels.remove(&host);
+        self.sync_tunnel_urls();
+        let line = match result {
+            Ok(()) => OutputLine::info(format!("Closed the tunnel to {}", host)),
+            Err(e) => OutputLine::error(format!("Tunnel to {} failed: {}", host, e)),
+        };
+        self.toasts.push(line.clone());
+        self.report(line);
+    }
+
+    fn sync_tunnel_urls(&mut self) {
+        let urls = self
+            .tunnels
+            .iter()
+            .filter_map(|(host, t)| Some((host.clone(), t.url.clone()?)))
+            .collect();
+        self.menu.set_tunnel_urls(urls);
+    }
+}
@@ -0,0 +1,63 @@
+//! Cluster watchdog
+//!
+//! Feeds the watchdog from the lifecycle and the health checks, reports each
+//! failure it finds and runs the restarts it schedules.
+
+use std::time::Instant;
+
+use super::App;
+use crate::cluster::watchdog::Failure;
+use crate::cluster::ClusterStatus;
+use crate::ui::components::{ClusterAction, OutputLine};
+
+impl App {
+    /// Report a failure the watchdog just found
+    pub(super) fn watchdog_failed(&mut self, failure: Option<Failure>) {
+        let Some(failure) = failure else {
+            return;
+        };
+        tracing::warn!(failure = %failure, "Watchdog found the cluster dead");
+        let line = match self.watchdog.restart_in(Instant::now()) {
+            Some(delay) => OutputLine::error(format!(
+                "{}; restarting in {}s",
+                failure,
+                delay.as_secs().max(1)
+            )),
+            None => OutputLine::error(format!(
+                "{}; set watchdog.auto_restart to restart it automatically",
+                failure
+            )),
+        };
+        self.toasts.push(line.clone());
+        self.report(line);
+    }
+
+    /// Start the restart the watchdog scheduled, once it is due
+    pub(super) fn watchdog_tick(&mut self) {
+        // The instance in control restarts it
+        if self.control_owner.is_some() {
+            return;
+        }
+        let Some(attempt) = self.watchdog.due(Instant::now()) else {
+            return;
+        };
+        // A container that exited only needs starting
+        let action = if self.cluster_status == ClusterStatus::Running {
+            ClusterAction::Restart
+        } else {
+            ClusterAction::Start
+        };
+        tracing::warn!(
+            attempt,
+            action = action.as_str(),
+            "Watchdog restarting the cluster"
+        );
+        let line = OutputLine::warning(format!(
+            "Watchdog restarting the cluster (attempt {})",
+            attempt
+        ));
+        self.toasts.push(line.clone());
+        self.report(line);
+        self.do_execute_cluster_action(action);
+    }
+}
@@ -0,0 +1,301 @@
+//! Tabbed workspaces
+//!
+//! Each tab in the action bar keeps its own view of the main screen: the
+//! focused panel, the selected menu entry and pod, the open detail tab, the
+//! layout and the command output it follows. Pods, jobs and the menu are
+//! shared; switching tabs stores the current view and restores the other.
+//! `gt` / `gT` switch tabs, `gn` opens one and `gc` closes it.
+
+use crossterm::event::KeyCode;
+
+use crate::config::LayoutPreset;
+use crate::keybindings::KeyAction;
+use crate::ui::components::{DetailTab, OutputLine};
+
+use super::{App, AppMode, FocusArea, JobId};
+
+/// Most tabs open at once
+const MAX_WORKSPACES: usize = 9;
+
+/// View state of one tab
+#[derive(Debug, Clone)]
+pub(super) struct Workspace {
+    /// Shown in the action bar
+    label: String,
+    focus: FocusArea,
+    layout_preset: LayoutPreset,
+    menu_index: usize,
+    /// Selected pod as (namespace, name)
+    pod: Option<(String, String)>,
+    /// Detail panel tab, when the panel was open
+    detail_tab: Option<DetailTab>,
+    /// Job whose output the tab follows
+    job: Option<JobId>,
+    /// Whether that output was open in the popup
+    output_open: bool,
+}
+
+impl Workspace {
+    /// A tab showing the command menu
+    fn fresh(layout_preset: LayoutPreset) -> Self {
+        Self {
+            label: "commands".to_string(),
+            focus: FocusArea::Content,
+            layout_preset,
+            menu_index: 0,
+            pod: None,
+            detail_tab: None,
+            job: None,
+            output_open: false,
+        }
+    }
+}
+
+/// Open tabs in order. The active tab's view lives in the App itself; its
+/// entry here is only refreshed when another tab takes over.
+pub(super) struct Workspaces {
+    tabs: Vec<Workspace>,
+    active: usize,
+}
+
+impl Default for Workspaces {
+    fn default() -> Self {
+        Self {
+            tabs: vec![Workspace::fresh(LayoutPreset::default())],
+            active: 0,
+        }
+    }
+}
+
+impl Workspaces {
+    /// Store the active tab's view and return the view of the tab at `index`
+    fn switch(&mut self, index: usize, current: Workspace) -> Option<Workspace> {
+        if index == self.active || index >= self.tabs.len() {
+            return None;
+        }
+        self.tabs[self.active] = current;
+        self.active = index;
+        Some(self.tabs[index].clone())
+    }
+
+    /// Store the active tab's view and open `fresh` right after it
+    fn open(&mut self, current: Workspace, fresh: Workspace) -> bool {
+        if self.tabs.len() >= MAX_WORKSPACES {
+            return false;
+        }
+        self.tabs[self.active] = current;
+        self.active += 1;
+        self.tabs.insert(self.active, fresh);
+        true
+    }
+
+    /// Close the active tab and return the view of the one taking its place
+    fn close(&mut self) -> Option<Workspace> {
+        if self.tabs.len() < 2 {
+            return None;
+        }
+        self.tabs.remove(self.active);
+        self.active = self.active.min(self.tabs.len() - 1);
+        Some(self.tabs[self.active].clone())
+    }
+
+    /// Index of the next or previous tab, wrapping around
+    fn neighbour(&self, forward: bool) -> usize {
+        let len = self.tabs.len();
+        if forward {
+            (self.active + 1) % len
+        } else {
+            (self.active + len - 1) % len
+        }
+    }
+
+    /// Tab labels, with `current` for the active tab
+    fn labels(&self, current: String) -> Vec<String> {
+        let mut labels: Vec<String> = self.tabs.iter().map(|t| t.label.clone()).collect();
+        labels[self.active] = current;
+        labels
+    }
+}
+
+impl App {
+    /// Vim-style `g` prefix for the workspace keys; true if the key was used
+    pub(super) fn handle_workspace_key(&mut self, code: KeyCode, action: &KeyAction) -> bool {
+        if std::mem::take(&mut self.pending_workspace_key) {
+            match code {
+                KeyCode::Char('t') => self.switch_workspace(true),
+                KeyCode::Char('T') => self.switch_workspace(false),
+                KeyCode::Char('n') => self.open_workspace(),
+                KeyCode::Char('c') => self.close_workspace(),
+                _ => {}
+            }
+            return true;
+        }
+        if code == KeyCode::Char('g') && *action == KeyAction::None {
+            self.pending_workspace_key = true;
+            self.pending_count.clear();
+            return true;
+        }
+        false
+    }
+
+    /// Switch to the next or previous tab
+    fn switch_workspace(&mut self, forward: bool) {
+        let index = self.workspaces.neighbour(forward);
+        self.switch_workspace_to(index);
+    }
+
+    /// Switch to the tab at `index` (from a click on its label)
+    pub(super) fn switch_workspace_to(&mut self, index: usize) {
+        let current = self.workspace_snapshot();
+        if let Some(workspace) = self.workspaces.switch(index, current) {
+            self.restore_workspace(workspace);
+        }
+    }
+
+    fn open_workspace(&mut self) {
+        let current = self.workspace_snapshot();
+        let fresh = Workspace::fresh(self.config.ui.layout);
+        if self.workspaces.open(current, fresh.clone()) {
+            self.restore_workspace(fresh);
+        } else {
+            self.toasts.push(OutputLine::warning(format!(
+                "At most {} workspaces",
+                MAX_WORKSPACES
+            )));
+        }
+    }
+
+    fn close_workspace(&mut self) {
+        if let Some(workspace) = self.workspaces.close() {
+            self.restore_workspace(workspace);
+        }
+    }
+
+    /// Labels and active index for the action bar's tab strip
+    pub(super) fn workspace_tabs(&self) -> (Vec<String>, usize) {
+        (
+            self.workspaces.labels(self.workspace_label()),
+            self.workspaces.active,
+        )
+    }
+
+    /// The current view, to come back to later
+    fn workspace_snapshot(&self) -> Workspace {
+        let pod = self
+            .pod_stats
+            .selected_pod()
+            .map(|p| (p.namespace.clone(), p.name.clone()));
+        Workspace {
+            label: self.workspace_label(),
+            focus: self.focus,
+            layout_preset: self.layout_preset,
+            menu_index: self.menu.selected_index(),
+            pod,
+            detail_tab: self
+                .pod_detail_panel
+                .is_open()
+                .then(|| self.pod_detail_panel.active_tab()),
+            job: self.popup_job,
+            output_open: self.mode == AppMode::OutputPopup,
+        }
+    }
+
+    /// Name of the current view: the followed command, the pod or the panel
+    fn workspace_label(&self) -> String {
+        let output_shown = self.mode == AppMode::OutputPopup || self.layout_preset.docks_output();
+        if let (true, Some(job)) = (
+            output_shown,
+            self.popup_job.and_then(|id| self.jobs.get(id)),
+        ) {
+            return job.title().to_string();
+        }
+        match self.focus {
+            FocusArea::Content => "commands".to_string(),
+            FocusArea::ActionBar => "cluster".to_string(),
+            FocusArea::PodStats => match self.pod_stats.selected_pod() {
+                Some(pod) if self.pod_detail_panel.is_open() => {
+                    format!("{} {}", pod.name, self.pod_detail_panel.active_tab().name())
+                }
+                Some(pod) => pod.name.clone(),
+                None => "pods".to_string(),
+            },
+        }
+    }
+
+    fn restore_workspace(&mut self, workspace: Workspace) {
+        self.focus = workspace.focus;
+        self.layout_preset = workspace.layout_preset;
+        self.menu.select_index(workspace.menu_index);
+
+        // Pods may have come and gone; keep the selection if it did
+        if let Some((namespace, name)) = &workspace.pod {
+            let index = self
+                .pod_stats
+                .pods()
+                .iter()
+                .position(|p| &p.namespace == namespace && &p.name == name);
+            if let Some(index) = index {
+                self.pod_stats.select_index(index);
+            }
+        }
+        if let Some(tab) = workspace.detail_tab {
+            self.open_or_switch_detail_tab(tab);
+        }
+
+        // Finished jobs are dropped after a while
+        match workspace.job.filter(|id| self.jobs.get(*id).is_some()) {
+            Some(id) => {
+                self.show_job_output(id);
+                if !workspace.output_open {
+                    self.mode = AppMode::Normal;
+                }
+            }
+            None => {
+                self.clear_output_popup();
+                self.mode = AppMode::Normal;
+            }
+        }
+        self.update_pod_highlights();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tab(label: &str) -> Workspace {
+        let mut workspace = Workspace::fresh(LayoutPreset::Classic);
+        workspace.label = label.to_string();
+        workspace
+    }
+
+    #[test]
+    fn tabs_open_after_the_active_one_and_wrap_around() {
+        let mut workspaces = Workspaces::default();
+        assert!(workspaces.open(tab("one"), tab("two")));
+        assert!(workspaces.open(tab("two"), tab("three")));
+        assert_eq!(workspaces.active, 2);
+        assert_eq!(workspaces.neighbour(true), 0);
+
+        let first = workspaces.switch(0, tab("three")).unwrap();
+        assert_eq!(first.label, "one");
+        assert_eq!(workspaces.neighbour(false), 2);
+        assert!(workspaces.open(tab("one"), tab("new")));
+        assert_eq!(
+            workspaces.labels("new".to_string()),
+            ["one", "new", "two", "three"]
+        );
+    }
+
+    #[test]
+    fn closing_keeps_the_last_tab() {
+        let mut workspaces = Workspaces::default();
+        assert!(workspaces.close().is_none());
+
+        workspaces.open(tab("one"), tab("two"));
+        let next = workspaces.close().unwrap();
+        assert_eq!(next.label, "one");
+        assert_eq!(workspaces.active, 0);
+        assert!(workspaces.switch(0, tab("one")).is_none());
+    }
+}
@@ -0,0 +1,143 @@
+//! Opening URLs in a browser
+//!
+//! Without a `browser:` config URLs go to the system opener. A configured
+//! browser starts directly, with its own arguments and optionally in a
+//! private window, and single hosts can use a different one.
+
+use std::process::{Command, Stdio};
+
+use anyhow::{bail, Context, Result};
+
+use crate::config::{BrowserConfig, BrowserLaunch};
+
+/// Flag that opens a private window in a known browser
+fn private_flag(command: &str) -> Option<&'static str> {
+    let name = std::path::Path::new(command)
+        .file_name()?
+        .to_str()?
+        .to_lowercase();
+    if name.contains("firefox") || name.contains("librewolf") {
+        Some("--private-window")
+    } else if name.contains("edge") {
+        Some("--inprivate")
+    } else if name.contains("opera") {
+        Some("--private")
+    } else if ["chrom", "brave", "vivaldi"]
+        .iter()
+        .any(|browser| name.contains(browser))
+    {
+        Some("--incognito")
+    } else {
+        None
+    }
+}
+
+/// Host part of `url`, without scheme, credentials or port
+fn url_host(url: &str) -> &str {
+    let rest = url.split_once("://").map_or(url, |(_, rest)| rest);
+    let authority = rest.split(['/', '?', '#']).next().unwrap_or(rest);
+    let host = authority.rsplit('@').next().unwrap_or(authority);
+    host.split(':').next().unwrap_or(host)
+}
+
+/// The launch settings for `url`: its host's override, or the default
+fn launch_for<'a>(config: &'a BrowserConfig, url: &str) -> &'a BrowserLaunch {
+    config.hosts.get(url_host(url)).unwrap_or(&config.launch)
+}
+
+/// Program and arguments that open `url`; None means the system opener
+fn browser_command(launch: &BrowserLaunch, url: &str) -> Result<Option<(String, Vec<String>)>> {
+    if launch.command.is_empty() {
+        if launch.private {
+            bail!("browser.private needs browser.command");
+        }
+        return Ok(None);
+    }
+    let mut args = launch.args.clone();
+    if launch.private {
+        match private_flag(&launch.command) {
+            Some(flag) => args.push(flag.to_string()),
+            None => bail!(
+                "Unknown private window flag for {}; add it to browser.args",
+                launch.command
+            ),
+        }
+    }
+    args.push(url.to_string());
+    Ok(Some((launch.command.clone(), args)))
+}
+
+/// Open `url` the way the config asks
+pub fn open_url(config: &BrowserConfig, url: &str) -> Result<()> {
+    let Some((program, args)) = browser_command(launch_for(config, url), url)? else {
+        return open::that(url).context("Failed to open the system browser");
+    };
+    // The browser's own chatter would draw over the TUI
+    let mut child = Command::new(&program)
+        .args(&args)
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::null())
+        .spawn()
+        .with_context(|| format!("Failed to run {}", program))?;
+    std::thread::spawn(move || child.wait());
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn launch(command: &str, private: bool) -> BrowserLaunch {
+        BrowserLaunch {
+            command: command.to_string(),
+            args: vec!["-P".to_string(), "testing".to_string()],
+            private,
+        }
+    }
+
+    #[test]
+    fn host_overrides_the_default_browser() {
+        let mut config = BrowserConfig {
+            launch: launch("firefox", true),
+            ..Default::default()
+        };
+        config.hosts.insert(
+            "admin.local".to_string(),
+            launch("/usr/bin/chromium", false),
+        );
+
+        assert_eq!(url_host("https://user@admin.local:8443/x?y"), "admin.local");
+        assert_eq!(
+            launch_for(&config, "http://admin.local/login").command,
+            "/usr/bin/chromium"
+        );
+        assert_eq!(
+            browser_command(
+                launch_for(&config, "http://app.local/"),
+                "http://app.local/"
+            )
+            .unwrap(),
+            Some((
+                "firefox".to_string(),
+                vec![
+                    "-P".to_string(),
+                    "testing".to_string(),
+                    "--private-window".to_string(),
+                    "http://app.local/".to_string()
+                ]
+            ))
+        );
+    }
+
+    #[test]
+    fn private_needs_a_known_browser() {
+        assert_eq!(private_flag("google-chrome-stable"), Some("--incognito"));
+        assert!(browser_command(&launch("surf", true), "http://app.local/").is_err());
+        assert!(
+            browser_command(&BrowserLaunch::default(), "http://app.local/")
+                .unwrap()
+                .is_none()
+        );
+    }
+}
@@ -3,14 +3,31 @@
 //! Runs cluster actions and pod operations directly in the terminal,
 //! printing colored output instead of rendering the TUI.
 
+use std::collections::HashMap;
 use std::sync::Arc;
+use std::time::Duration;
 
-use anyhow::Result;
+use anyhow::{anyhow, Result};
 use tokio::sync::mpsc;
 
-use crate::cluster::{ClusterConfig, ClusterManager, IngressManager};
-use crate::config::{ConfigLoader, RefreshConfig};
-use crate::k8s::K8sClient;
+use crate::api::{ApiServer, DEFAULT_API_LISTEN};
+use crate::cluster::branches::{remove_branch_cluster, stale_branch_clusters};
+use crate::cluster::{hosts_file, hosts_helper, instance_lock, HostsUpdateResult};
+use crate::cluster::{ClusterConfig, ClusterManager, DockerManager, IngressManager};
+use crate::commands::headless::PreparedCommand;
+use crate::commands::{
+    command_catalog, prepare_command, run_exec, CommandOutcome, OutputClassifier, PrepareError,
+};
+use crate::config::branch::local_branches;
+use crate::config::{
+    expand_home, runnable_command_paths, ConfigLoader, ExecutionTarget, HookEvent, ReadinessGate,
+    RefreshConfig, StartConfig, StarterConfig, Template,
+};
+use crate::error::{self, K3devError};
+use crate::hooks::HookExecutor;
+use crate::ide_tasks::{jetbrains_run_configs, vscode_tasks, Ide, RUN_CONFIG_FILE_PREFIX};
+use crate::k8s::{scaffold, K8sClient};
+use crate::keybindings::KeybindingResolver;
 use crate::ui::components::{ClusterAction, OutputLine, OutputType};
 
 /// Load config and build a ClusterConfig arc
@@ -31,6 +48,8 @@ fn load_cluster_config(config_path: Option<&str>) -> (crate::config::Config, Arc
     let cluster_config = Arc::new(
         ClusterConfig::from(config.infrastructure.clone())
             .with_hooks(config.hooks.clone())
+            .with_smoke_tests(config.smoke_tests.clone())
+            .with_start(config.start.clone())
             .with_k8s_config(kubeconfig, context),
     );
 
@@ -38,6 +57,15 @@ fn load_cluster_config(config_path: Option<&str>) -> (crate::config::Config, Arc
 }
 
 /// Create a K8sClient from config
+/// Namespace for commands run without `--namespace`
+pub fn default_namespace(config_path: Option<&str>) -> String {
+    ConfigLoader::new(config_path)
+        .load()
+        .unwrap_or_default()
+        .cluster
+        .default_namespace
+}
+
 async fn create_k8s_client(config_path: Option<&str>) -> Result<K8sClient> {
     let loader = ConfigLoader::new(config_path);
     let config = loader.load().unwrap_or_default();
@@ -61,15 +89,38 @@ async fn create_k8s_client(config_path: Option<&str>) -> Result<K8sClient> {
 }
 
 /// Run a cluster action headlessly, printing output to stdout.
-/// Returns the process exit code (0 = success, 1 = failure).
-pub async fn run_cli_action(action: ClusterAction, config_path: Option<&str>) -> Result<i32> {
+/// Returns the process exit code (0 = success, otherwise the failure's
+/// code from [`crate::error`]). With `smoke_tests` a start also waits for
+/// the services and fails when they or a smoke test do. With
+/// `export_volumes` a destroy first saves the data volumes, to the given
+/// path or the default one.
+pub async fn run_cli_action(
+    action: ClusterAction,
+    config_path: Option<&str>,
+    smoke_tests: bool,
+    export_volumes: Option<Option<String>>,
+) -> Result<i32> {
     let (config, cluster_config) = load_cluster_config(config_path);
+    // Nothing tracks background readiness here, so scripts get a fully
+    // ready cluster when start returns
+    let cluster_config = Arc::new(
+        ClusterConfig::clone(&cluster_config).with_start(StartConfig {
+            wait_for: ReadinessGate::all(),
+            ..config.start.clone()
+        }),
+    );
 
     let _ = crate::logging::init_logging(&config.logging, &config.infrastructure.cluster_name);
 
     let refresh_config = RefreshConfig::default();
     let timeout = refresh_config.cluster_operation_timeout;
 
+    let export_path = export_volumes.map(|path| {
+        path.map(std::path::PathBuf::from).unwrap_or_else(|| {
+            crate::cluster::default_archive_path(&config.infrastructure.cluster_name)
+        })
+    });
+
     let (output_tx, mut output_rx) = mpsc::channel::<OutputLine>(100);
 
     // Spawn the cluster action
@@ -77,13 +128,34 @@ pub async fn run_cli_action(action: ClusterAction, config_path: Option<&str>) ->
         let mut manager = ClusterManager::new(cluster_config).await?;
 
         match action {
+            ClusterAction::Start if smoke_tests => {
+                manager.start(output_tx).await?;
+                match manager.services_deployed().await? {
+                    Some(report) if !report.all_passed() => Err(anyhow!(report.summary())),
+                    _ => Ok(()),
+                }
+            }
             ClusterAction::Start => manager.start(output_tx).await,
             ClusterAction::Stop => manager.stop(output_tx).await,
             ClusterAction::Restart => manager.restart(output_tx).await,
-            ClusterAction::Destroy => manager.delete(output_tx).await,
+            ClusterAction::Destroy => match &export_path {
+                Some(path) => {
+                    manager.export_and_delete(path, output_tx.clone()).await?;
+                    let _ = output_tx
+                        .send(OutputLine::success(format!(
+                            "Volumes saved to {}",
+                            path.display()
+                        )))
+                        .await;
+                    Ok(())
+                }
+                None => manager.delete(output_tx).await,
+            },
             ClusterAction::Info => manager.info(output_tx).await,
             ClusterAction::DeleteSnapshots => manager.delete_snapshots(output_tx).await,
-            ClusterAction::Diagnostics | ClusterAction::PreflightCheck => unreachable!(),
+            ClusterAction::Logs | ClusterAction::Diagnostics | ClusterAction::PreflightCheck => {
+                unreachable!()
+            }
         }
     });
 
@@ -105,19 +177,24 @@ pub async fn run_cli_action(action: ClusterAction, config_path: Option<&str>) ->
             print_output_line(&OutputLine::success("Done."));
             Ok(0)
         }
-        Ok(Ok(Err(e))) => {
-            print_output_line(&OutputLine::error(format!("Error: {}", e)));
-            Ok(1)
-        }
+        Ok(Ok(Err(e))) => Ok(report_error(&e)),
         Ok(Err(e)) => {
             print_output_line(&OutputLine::error(format!("Task panicked: {}", e)));
             Ok(1)
         }
-        Err(_) => {
-            print_output_line(&OutputLine::error("Operation timed out"));
-            Ok(1)
-        }
+        Err(_) => Ok(report_error(
+            &K3devError::Timeout(format!("k3dev {}", action.as_str())).into(),
+        )),
+    }
+}
+
+/// Print a headless command's error with its remediation, returning the
+/// exit code for it
+pub fn report_error(error: &anyhow::Error) -> i32 {
+    for line in error::report_lines(error) {
+        print_output_line(&line);
     }
+    error::exit_code(error)
 }
 
 /// Run cluster diagnostics headlessly
@@ -369,19 +446,91 @@ pub async fn run_cli_preflight(config_path: Option<&str>) -> Result<i32> {
     }
 }
 
+/// Write a support bundle for bug reports
+pub async fn run_cli_support_bundle(config_path: Option<&str>, out: Option<&str>) -> Result<i32> {
+    use crate::cluster::support_bundle::{collect, default_bundle_path, write_tar};
+
+    let (config, cluster_config) = load_cluster_config(config_path);
+    let config_file = ConfigLoader::new(config_path)
+        .load_with_path()
+        .ok()
+        .map(|(_, path)| path);
+
+    println!("\x1b[1mCollecting support bundle...\x1b[0m");
+    let sections = collect(&config, &cluster_config, config_file.as_deref()).await;
+    for section in &sections {
+        match &section.contents {
+            Ok(_) => println!("  \x1b[32m✓\x1b[0m {}", section.name),
+            Err(e) => println!("  \x1b[33m⚠\x1b[0m {} \x1b[90m({})\x1b[0m", section.name, e),
+        }
+    }
+
+    let path = out
+        .map(std::path::PathBuf::from)
+        .unwrap_or_else(|| default_bundle_path(&config.infrastructure.cluster_name));
+    if let Err(e) = write_tar(&sections, &path) {
+        print_output_line(&OutputLine::error(format!("{:#}", e)));
+        return Ok(1);
+    }
+    print_output_line(&OutputLine::success(format!(
+        "Wrote {}. Review it before attaching it to an issue.",
+        path.display()
+    )));
+    Ok(0)
+}
+
+/// Save or restore the cluster's data volumes (`k3dev volumes`), printing
+/// progress as it goes
+pub async fn run_cli_volumes(
+    config_path: Option<&str>,
+    save: bool,
+    path: Option<&str>,
+) -> Result<i32> {
+    let (config, cluster_config) = load_cluster_config(config_path);
+    let _ = crate::logging::init_logging(&config.logging, &config.infrastructure.cluster_name);
+
+    let path = path.map(std::path::PathBuf::from).unwrap_or_else(|| {
+        crate::cluster::default_archive_path(&config.infrastructure.cluster_name)
+    });
+    let (output_tx, mut output_rx) = mpsc::channel::<OutputLine>(100);
+    let handle = tokio::spawn(async move {
+        let manager = ClusterManager::new(cluster_config).await?;
+        if save {
+            manager.save_volumes(&path, output_tx).await
+        } else {
+            manager.restore_volumes(&path, output_tx).await
+        }
+    });
+    let printer = tokio::spawn(async move {
+        while let Some(line) = output_rx.recv().await {
+            print_output_line(&line);
+        }
+    });
+
+    let result = handle.await;
+    let _ = printer.await;
+    match result {
+        Ok(Ok(())) => Ok(0),
+        Ok(Err(e)) => Ok(report_error(&e)),
+        Err(e) => {
+            print_output_line(&OutputLine::error(format!("Task panicked: {}", e)));
+            Ok(1)
+        }
+    }
+}
+
 /// Update /etc/hosts with ingress entries
 pub async fn run_cli_update_hosts(config_path: Option<&str>) -> Result<i32> {
-    use crate::cluster::HostsUpdateResult;
-
     let (config, _cluster_config) = load_cluster_config(config_path);
     let _ = crate::logging::init_logging(&config.logging, &config.infrastructure.cluster_name);
 
     let domain = config.infrastructure.domain.clone();
+    let cluster = config.infrastructure.cluster_name.clone();
 
     let (output_tx, mut output_rx) = mpsc::channel::<OutputLine>(100);
 
     let update_handle = tokio::spawn(async move {
-        let mut ingress_manager = IngressManager::with_domain(domain);
+        let mut ingress_manager = IngressManager::with_domain(domain).for_cluster(cluster);
         ingress_manager.update_hosts(Some(output_tx)).await
     });
 
@@ -395,6 +544,75 @@ pub async fn run_cli_update_hosts(config_path: Option<&str>) -> Result<i32> {
     let result = update_handle.await??;
     let _ = printer.await;
 
+    report_hosts_result(result)
+}
+
+/// Restore /etc/hosts from the latest backup
+pub async fn run_cli_restore_hosts() -> Result<i32> {
+    let (output_tx, mut output_rx) = mpsc::channel::<OutputLine>(100);
+    let restore_handle =
+        tokio::spawn(async move { hosts_file::restore_latest_backup(Some(output_tx)).await });
+    let printer = tokio::spawn(async move {
+        while let Some(line) = output_rx.recv().await {
+            print_output_line(&line);
+        }
+    });
+
+    let result = restore_handle.await?;
+    let _ = printer.await;
+    match result {
+        Ok(HostsUpdateResult::NeedsSudo { .. }) => {
+            let backup = hosts_file::latest_backup().unwrap_or_default();
+            println!("\x1b[33m⚠ Need elevated privileges to write /etc/hosts\x1b[0m");
+            println!("Run: sudo cp {} /etc/hosts", backup.display());
+            Ok(1)
+        }
+        Ok(_) => Ok(0),
+        Err(e) => {
+            eprintln!("\x1b[31m✗ {:#}\x1b[0m", e);
+            Ok(1)
+        }
+    }
+}
+
+/// Diff the configured manifests against the cluster.
+/// Exits 0 when applying would change nothing, 1 otherwise.
+pub async fn run_cli_diff(config_path: Option<&str>) -> Result<i32> {
+    let config = ConfigLoader::new(config_path).load().unwrap_or_default();
+    let k8s_client = match create_k8s_client(config_path).await {
+        Ok(c) => c,
+        Err(e) => {
+            eprintln!("\x1b[31mFailed to connect to cluster: {}\x1b[0m", e);
+            return Ok(1);
+        }
+    };
+
+    let (output_tx, mut output_rx) = mpsc::channel::<OutputLine>(100);
+    let client = k8s_client.client().clone();
+    let dirs = config.manifest_dirs();
+    let namespace = config.cluster.default_namespace.clone();
+    let diff_handle = tokio::spawn(async move {
+        crate::k8s::manifest_diff::diff_manifests(&client, &dirs, &namespace, &output_tx).await
+    });
+    let printer = tokio::spawn(async move {
+        while let Some(line) = output_rx.recv().await {
+            print_output_line(&line);
+        }
+    });
+
+    let result = diff_handle.await?;
+    let _ = printer.await;
+    match result {
+        Ok(summary) if summary.is_clean() => Ok(0),
+        Ok(_) => Ok(1),
+        Err(e) => {
+            eprintln!("\x1b[31m✗ {:#}\x1b[0m", e);
+            Ok(1)
+        }
+    }
+}
+
+fn report_hosts_result(result: HostsUpdateResult) -> Result<i32> {
     match result {
         HostsUpdateResult::NoUpdateNeeded => {
             println!("\x1b[32m✓ /etc/hosts is already up to date\x1b[0m");
@@ -409,7 +627,8 @@ pub async fn run_cli_update_hosts(config_path: Option<&str>) -> Result<i32> {
                 "\x1b[33m⚠ Need elevated privileges to write {} entries to /etc/hosts\x1b[0m",
                 count
             );
-            println!("Run with sudo or manually add the following:");
+            println!("Run with sudo, install the helper with `k3dev hosts-helper install`,");
+            println!("or manually add the following:");
             println!();
             // Extract just the k3dev entries from content
             for line in content.lines() {
@@ -430,6 +649,61 @@ pub async fn run_cli_update_hosts(config_path: Option<&str>) -> Result<i32> {
     }
 }
 
+/// Install the sudo-less hosts helper for the config's cluster and domain
+pub fn run_cli_hosts_helper_install(config_path: Option<&str>) -> Result<i32> {
+    let config = ConfigLoader::new(config_path).load().unwrap_or_default();
+    let infra = &config.infrastructure;
+    // A branch cluster's names are covered by its base cluster's
+    let (cluster, domain) = match &infra.branch {
+        Some(branch) => (
+            branch.base_name.as_str(),
+            infra
+                .domain
+                .split_once('.')
+                .map_or(infra.domain.as_str(), |(_, base)| base),
+        ),
+        None => (infra.cluster_name.as_str(), infra.domain.as_str()),
+    };
+    println!(
+        "Installing the hosts helper for {} (*.{}); sudo will ask for your password...",
+        cluster, domain
+    );
+    if let Err(e) = hosts_helper::install(cluster, domain) {
+        eprintln!("\x1b[31m✗ {:#}\x1b[0m", e);
+        return Ok(1);
+    }
+    println!("\x1b[32m✓ Installed; /etc/hosts updates no longer prompt\x1b[0m");
+    Ok(0)
+}
+
+/// Remove the sudo-less hosts helper
+pub fn run_cli_hosts_helper_uninstall() -> Result<i32> {
+    if let Err(e) = hosts_helper::uninstall() {
+        eprintln!("\x1b[31m✗ {:#}\x1b[0m", e);
+        return Ok(1);
+    }
+    println!("\x1b[32m✓ Removed the hosts helper\x1b[0m");
+    Ok(0)
+}
+
+/// Report whether the sudo-less hosts helper is installed
+pub fn run_cli_hosts_helper_status() -> i32 {
+    if hosts_helper::is_installed() {
+        println!("\x1b[32m✓ Hosts helper installed\x1b[0m");
+        for path in hosts_helper::installed_paths() {
+            println!("  {}", path);
+        }
+        let (clusters, domains) = hosts_helper::installed_scope();
+        println!("  clusters: {}", clusters.join(", "));
+        println!("  domains:  {}", domains.join(", "));
+        0
+    } else {
+        println!("Hosts helper not installed; /etc/hosts updates use the sudo prompt");
+        println!("Install it with: k3dev hosts-helper install");
+        1
+    }
+}
+
 /// List pods with status
 pub async fn run_cli_pods(config_path: Option<&str>, namespace: Option<&str>) -> Result<i32> {
     let k8s_client = match create_k8s_client(config_path).await {
@@ -860,6 +1134,461 @@ pub async fn run_cli_capture(
     Ok(0)
 }
 
+/// Run a configured command entry outside the TUI.
+///
+/// Placeholder values come from `--set key=value`, falling back to the
+/// input's default. Output is printed as it is produced (host) or once the
+/// command finishes (docker, pod), classified by the entry's success criteria.
+/// Returns 0 on success, the command's exit code (or 1) on failure, or 2 when
+/// the command cannot be started (unknown path, missing values).
+pub async fn run_cli_command(
+    config_path: Option<&str>,
+    path: &str,
+    sets: &[String],
+) -> Result<i32> {
+    let config = match ConfigLoader::new(config_path).load() {
+        Ok(c) => c,
+        Err(e) => {
+            eprintln!("\x1b[31mFailed to load config: {:#}\x1b[0m", e);
+            return Ok(2);
+        }
+    };
+
+    let mut values = HashMap::new();
+    for set in sets {
+        let Some((key, value)) = set.split_once('=') else {
+            eprintln!("\x1b[31mInvalid --set '{}', expected key=value\x1b[0m", set);
+            return Ok(2);
+        };
+        values.insert(key.trim().to_string(), value.to_string());
+    }
+
+    let PreparedCommand {
+        entry,
+        exec,
+        ignored,
+    } = match prepare_command(&config.commands, path, values) {
+        Ok(prepared) => prepared,
+        Err(PrepareError::NotFound) => {
+            eprintln!("\x1b[31mCommand not found: {}\x1b[0m", path);
+            return Ok(2);
+        }
+        Err(PrepareError::Submenu(children)) => {
+            eprintln!(
+                "\x1b[31m'{}' is a submenu, not a command. Choose one of: {}\x1b[0m",
+                path,
+                children.join(", ")
+            );
+            return Ok(2);
+        }
+        Err(PrepareError::Missing(names)) => {
+            let missing: Vec<String> = names.iter().map(|n| format!("--set {}=...", n)).collect();
+            eprintln!(
+                "\x1b[31mMissing values for '{}': {}\x1b[0m",
+                path,
+                missing.join(" ")
+            );
+            return Ok(2);
+        }
+    };
+    for key in &ignored {
+        eprintln!(
+            "\x1b[33mWarning: '{}' does not use @{}; ignoring --set\x1b[0m",
+            entry.name, key
+        );
+    }
+
+    // Lines are judged by the entry's success criteria as they are printed
+    let mut classifier = OutputClassifier::new(&entry.success);
+    let (line_tx, mut line_rx) = mpsc::channel::<OutputLine>(100);
+    let printer = tokio::spawn(async move {
+        while let Some(line) = line_rx.recv().await {
+            let to_stderr = line.output_type == OutputType::Error;
+            print_run_line(&classifier.classify(line), to_stderr);
+        }
+        classifier
+    });
+
+    let k8s = match exec.target {
+        ExecutionTarget::Script | ExecutionTarget::Lua | ExecutionTarget::Kubernetes { .. } => {
+            create_k8s_client(config_path).await.ok()
+        }
+        _ => None,
+    };
+    let result = run_exec(&exec, k8s.as_ref(), line_tx).await;
+    let classifier = printer.await?;
+
+    let outcome = match result {
+        Ok(exit_code) => classifier.outcome(exit_code),
+        Err(reason) => CommandOutcome::Failed {
+            exit_code: None,
+            reason,
+        },
+    };
+    if !matches!(outcome, CommandOutcome::Success { warnings: 0, .. }) {
+        print_run_line(&outcome.summary_line(), true);
+    }
+
+    if outcome.is_failure() && !config.hooks.on_command_failed.is_empty() {
+        let (hook_tx, mut hook_rx) = mpsc::channel::<OutputLine>(100);
+        let hook_printer = tokio::spawn(async move {
+            while let Some(line) = hook_rx.recv().await {
+                print_output_line(&line);
+            }
+        });
+        let _ = HookExecutor::new(config.hooks.clone())
+            .execute_hooks_with_env(
+                HookEvent::OnCommandFailed,
+                &outcome.hook_env(&entry.name),
+                hook_tx,
+            )
+            .await;
+        let _ = hook_printer.await;
+    }
+
+    Ok(outcome.exit_code())
+}
+
+/// Print a line of `k3dev run` output without a timestamp, colored by its
+/// classification
+fn print_run_line(line: &OutputLine, to_stderr: bool) {
+    let text = match line.output_type {
+        OutputType::Error => format!("\x1b[31m{}\x1b[0m", line.content),
+        OutputType::Warning => format!("\x1b[33m{}\x1b[0m", line.content),
+        OutputType::Hint => format!("\x1b[36m{}\x1b[0m", line.content),
+        OutputType::Info | OutputType::Success => line.content.clone(),
+    };
+    if to_stderr {
+        eprintln!("{}", text);
+    } else {
+        println!("{}", text);
+    }
+}
+
+/// List runnable command paths for scripts and shell completion.
+/// Plain output is one path per line; `json` adds name, description, target and inputs.
+pub fn run_cli_list_commands(config_path: Option<&str>, json: bool) -> Result<i32> {
+    let config = match ConfigLoader::new(config_path).load() {
+        Ok(c) => c,
+        Err(e) => {
+            eprintln!("\x1b[31mFailed to load config: {:#}\x1b[0m", e);
+            return Ok(1);
+        }
+    };
+    let commands = runnable_command_paths(&config.commands);
+
+    if !json {
+        for (path, _) in &commands {
+            println!("{}", path);
+        }
+        return Ok(0);
+    }
+
+    let entries = command_catalog(&config.commands);
+    println!("{}", serde_json::to_string_pretty(&entries)?);
+    Ok(0)
+}
+
+/// List clusters whose git branch was deleted; remove the stopped ones with `yes`
+pub async fn run_cli_prune_branches(config_path: Option<&str>, yes: bool) -> Result<i32> {
+    let (_, cluster_config) = load_cluster_config(config_path);
+    let Some(branches) = local_branches() else {
+        eprintln!("\x1b[31mNot inside a git repository\x1b[0m");
+        return Ok(1);
+    };
+    let docker = match DockerManager::from_default_socket() {
+        Ok(docker) => docker,
+        Err(e) => {
+            eprintln!("\x1b[31mDocker is not reachable: {:#}\x1b[0m", e);
+            return Ok(1);
+        }
+    };
+    let stale =
+        stale_branch_clusters(&docker, &cluster_config.base_cluster_name(), &branches).await?;
+    if stale.is_empty() {
+        println!("No clusters of deleted branches");
+        return Ok(0);
+    }
+
+    for cluster in &stale {
+        println!(
+            "  {} (branch {}){}",
+            cluster.container,
+            cluster.branch,
+            if cluster.running { " — running" } else { "" }
+        );
+    }
+    if !yes {
+        println!("Run with --yes to remove the stopped ones");
+        return Ok(0);
+    }
+
+    let (tx, mut rx) = mpsc::channel::<OutputLine>(100);
+    let printer = tokio::spawn(async move {
+        while let Some(line) = rx.recv().await {
+            print_output_line(&line);
+        }
+    });
+    let mut failed = false;
+    for cluster in &stale {
+        if cluster.running {
+            let _ = tx
+                .send(OutputLine::warning(format!(
+                    "Skipping {}: it is running; stop it first",
+                    cluster.container
+                )))
+                .await;
+            continue;
+        }
+        if let Err(e) = remove_branch_cluster(&docker, &cluster_config, cluster, &tx).await {
+            failed = true;
+            let _ = tx
+                .send(OutputLine::error(format!(
+                    "Failed to remove {}: {:#}",
+                    cluster.container, e
+                )))
+                .await;
+        }
+    }
+    drop(tx);
+    let _ = printer.await;
+    Ok(if failed { 1 } else { 0 })
+}
+
+/// Write VS Code tasks or JetBrains run configurations for the command catalog
+pub fn run_cli_export_tasks(config_path: Option<&str>, ide: Ide, dir: &str) -> Result<i32> {
+    let config = match ConfigLoader::new(config_path).load() {
+        Ok(c) => c,
+        Err(e) => {
+            eprintln!("\x1b[31mFailed to load config: {:#}\x1b[0m", e);
+            return Ok(1);
+        }
+    };
+    // An explicit config is pinned by absolute path; otherwise the tasks
+    // use the normal lookup from the project directory
+    let config_file = config_path.map(|p| std::fs::canonicalize(p).unwrap_or_else(|_| p.into()));
+    let dir = std::path::Path::new(dir);
+
+    match ide {
+        Ide::Vscode => {
+            let path = dir.join(".vscode").join("tasks.json");
+            let existing = match std::fs::read_to_string(&path) {
+                Ok(content) => match serde_json::from_str(&content) {
+                    Ok(value) => Some(value),
+                    Err(e) => {
+                        eprintln!(
+                            "\x1b[31m{} is not plain JSON ({}); remove its comments or move it aside\x1b[0m",
+                            path.display(),
+                            e
+                        );
+                        return Ok(1);
+                    }
+                },
+                Err(_) => None,
+            };
+            let tasks = vscode_tasks(&config.commands, config_file.as_deref(), existing);
+            let count = runnable_command_paths(&config.commands).len();
+            std::fs::create_dir_all(dir.join(".vscode"))?;
+            std::fs::write(
+                &path,
+                format!("{}\n", serde_json::to_string_pretty(&tasks)?),
+            )?;
+            println!("Wrote {} task(s) to {}", count, path.display());
+        }
+        Ide::Jetbrains => {
+            let run_dir = dir.join(".run");
+            std::fs::create_dir_all(&run_dir)?;
+            // Drop configurations of commands that were renamed or removed
+            for entry in std::fs::read_dir(&run_dir)?.flatten() {
+                let name = entry.file_name().to_string_lossy().to_string();
+                if name.starts_with(RUN_CONFIG_FILE_PREFIX) && name.ends_with(".run.xml") {
+                    std::fs::remove_file(entry.path())?;
+                }
+            }
+            let configs = jetbrains_run_configs(&config.commands, config_file.as_deref());
+            for (file, xml) in &configs {
+                std::fs::write(run_dir.join(file), xml)?;
+            }
+            println!(
+                "Wrote {} run configuration(s) to {}",
+                configs.len(),
+                run_dir.display()
+            );
+        }
+    }
+    Ok(0)
+}
+
+/// Serve the HTTP API in the foreground until interrupted
+pub async fn run_cli_serve(config_path: Option<&str>, listen: Option<&str>) -> Result<i32> {
+    let config = ConfigLoader::new(config_path).load().unwrap_or_default();
+    let listen = listen
+        .map(str::to_string)
+        .or_else(|| Some(config.api.listen.clone()).filter(|l| !l.is_empty()))
+        .unwrap_or_else(|| DEFAULT_API_LISTEN.to_string());
+
+    let server = match ApiServer::bind(&listen, config_path, config.api.token.clone()).await {
+        Ok(server) => server,
+        Err(e) => {
+            eprintln!("\x1b[31m{:#}\x1b[0m", e);
+            return Ok(1);
+        }
+    };
+    let addr = server.local_addr().map(|a| a.to_string()).unwrap_or(listen);
+    println!("Serving the k3dev API on http://{}", addr);
+    if let Some(path) = server.token_file() {
+        println!("Bearer token: {}", path.display());
+    }
+
+    tokio::select! {
+        result = server.run() => {
+            if let Err(e) = result {
+                eprintln!("\x1b[31mAPI server stopped: {:#}\x1b[0m", e);
+                return Ok(1);
+            }
+        }
+        _ = tokio::signal::ctrl_c() => {}
+    }
+    Ok(0)
+}
+
+/// Where `k3dev init` writes: `config_path`, else ./k3dev.yml
+fn init_path(config_path: Option<&str>) -> Result<std::path::PathBuf> {
+    Ok(match config_path {
+        Some(p) => expand_home(std::path::Path::new(p))?,
+        None => std::path::PathBuf::from("k3dev.yml"),
+    })
+}
+
+/// Write a starter config from a template
+pub fn run_cli_init(config_path: Option<&str>, template: Template) -> Result<i32> {
+    let path = init_path(config_path)?;
+    let starter = StarterConfig {
+        template,
+        ..StarterConfig::default()
+    };
+    if let Err(e) = starter.write(&path) {
+        eprintln!("\x1b[31m{:#}\x1b[0m", e);
+        return Ok(1);
+    }
+    println!(
+        "Wrote {} ({} template: {})",
+        path.display(),
+        template.name(),
+        template.description()
+    );
+    Ok(0)
+}
+
+/// Write a starter config with a command group per Deployment of a running
+/// cluster, reached through the default kubeconfig
+pub async fn run_cli_init_from_cluster(
+    config_path: Option<&str>,
+    context: Option<&str>,
+) -> Result<i32> {
+    let path = init_path(config_path)?;
+    if path.exists() {
+        eprintln!("\x1b[31m{} already exists\x1b[0m", path.display());
+        return Ok(1);
+    }
+    let client = match K8sClient::new(None, context).await {
+        Ok(c) => c,
+        Err(e) => {
+            eprintln!("\x1b[31mFailed to connect to cluster: {}\x1b[0m", e);
+            return Ok(1);
+        }
+    };
+    let workloads = scaffold::discover_workloads(client.client()).await?;
+
+    let mut starter = StarterConfig::default();
+    if let Some(domain) = scaffold::common_domain(&workloads) {
+        starter.domain = domain;
+    }
+    let mut sections = String::new();
+    if let Some(context) = context {
+        sections.push_str(&format!("cluster:\n  context: \"{}\"\n\n", context));
+    }
+    sections.push_str(&scaffold::render_commands(&workloads));
+    let origin = format!("from context {}", context.unwrap_or("current-context"));
+    starter.write_with(&path, &origin, &sections)?;
+
+    for w in &workloads {
+        println!("  {}/{}  {}", w.namespace, w.name, w.hosts.join(", "));
+    }
+    println!(
+        "Wrote {} with {} command group(s), domain {}",
+        path.display(),
+        workloads.len(),
+        starter.domain
+    );
+    Ok(0)
+}
+
+/// Print the effective keymap as a markdown cheat sheet.
+/// Conflicts go to stderr so the output can be redirected into docs.
+pub fn run_cli_keys_export(config_path: Option<&str>) -> Result<i32> {
+    let config = ConfigLoader::new(config_path).load().unwrap_or_default();
+    let resolver = KeybindingResolver::from_config(config.keybindings.as_ref());
+
+    print!("{}", resolver.export_markdown());
+
+    for conflict in resolver.conflicts() {
+        eprintln!(
+            "\x1b[33mWarning: key '{}' bound to multiple actions: {}\x1b[0m",
+            conflict.key,
+            conflict.actions.join(", ")
+        );
+    }
+
+    Ok(0)
+}
+
+/// Mirror the output of the TUI controlling the cluster
+pub async fn run_cli_attach(config_path: Option<&str>) -> Result<i32> {
+    let (_config, cluster_config) = load_cluster_config(config_path);
+    let mut feed = match instance_lock::follow_output(&cluster_config.container_name).await {
+        Ok(feed) => feed,
+        Err(e) => {
+            eprintln!(
+                "\x1b[31m{:#} (cluster '{}')\x1b[0m",
+                e,
+                cluster_config.cluster_name()
+            );
+            return Ok(1);
+        }
+    };
+    println!(
+        "\x1b[90mAttached to k3dev (pid {}); Ctrl-C detaches\x1b[0m",
+        feed.pid
+    );
+
+    loop {
+        tokio::select! {
+            line = feed.next() => match line {
+                Some(line) => print_output_line(&line),
+                None => {
+                    // After a handover the new owner has the output
+                    tokio::time::sleep(Duration::from_secs(1)).await;
+                    match instance_lock::follow_output(&cluster_config.container_name).await {
+                        Ok(next) => {
+                            println!(
+                                "\x1b[90mk3dev (pid {}) took over from pid {}\x1b[0m",
+                                next.pid, feed.pid
+                            );
+                            feed = next;
+                        }
+                        Err(_) => {
+                            println!("\x1b[90mk3dev (pid {}) exited\x1b[0m", feed.pid);
+                            return Ok(0);
+                        }
+                    }
+                }
+            },
+            _ = tokio::signal::ctrl_c() => return Ok(0),
+        }
+    }
+}
+
 /// Print an OutputLine to stdout with ANSI colors
 fn print_output_line(line: &OutputLine) {
     let timestamp = line.timestamp.format("[%H:%M:%S]");
@@ -868,6 +1597,7 @@ fn print_output_line(line: &OutputLine) {
         OutputType::Success => ("\x1b[32m", "\x1b[0m"), // green
         OutputType::Error => ("\x1b[31m", "\x1b[0m"),   // red
         OutputType::Warning => ("\x1b[33m", "\x1b[0m"), // yellow
+        OutputType::Hint => ("\x1b[36m", "\x1b[0m"),    // cyan
     };
     println!(
         "\x1b[90m{}\x1b[0m {}{}{}",
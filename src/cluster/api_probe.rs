@@ -0,0 +1,157 @@
+//! HTTPS probe of the k3s API server
+//!
+//! `GET /healthz` with certificate checks off (k3s serves a self-signed
+//! certificate). Failures are classified so waits and diagnostics can say
+//! whether nothing is listening, something that isn't k3s answered, or the
+//! server is up but not healthy yet.
+
+use std::error::Error as StdError;
+use std::io::ErrorKind;
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+
+/// Outcome of one probe
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ApiHealth {
+    /// `/healthz` answered 200
+    Healthy,
+    /// 401/403: the server is up but refuses anonymous requests
+    Unauthorized(u16),
+    /// Any other status, e.g. 500 while the datastore is starting
+    Unhealthy(u16),
+    /// Nothing listens on the port
+    ConnectionRefused,
+    /// The TLS handshake failed, e.g. a plain HTTP server on the port
+    Tls(String),
+    TimedOut,
+    /// DNS, routing and other connection errors
+    Unreachable(String),
+}
+
+impl ApiHealth {
+    /// Whether the API server is answering requests
+    pub fn is_up(&self) -> bool {
+        matches!(self, Self::Healthy | Self::Unauthorized(_))
+    }
+
+    pub fn describe(&self) -> String {
+        match self {
+            Self::Healthy => "healthy".to_string(),
+            Self::Unauthorized(status) => format!("up, anonymous access denied ({})", status),
+            Self::Unhealthy(status) => format!("not healthy yet (HTTP {})", status),
+            Self::ConnectionRefused => "connection refused".to_string(),
+            Self::Tls(cause) => format!("TLS handshake failed: {}", cause),
+            Self::TimedOut => "timed out".to_string(),
+            Self::Unreachable(cause) => format!("unreachable: {}", cause),
+        }
+    }
+}
+
+/// Reusable client for probing one API endpoint
+pub struct ApiProbe {
+    client: reqwest::Client,
+    url: String,
+}
+
+impl ApiProbe {
+    pub fn new(host: &str, port: u16, timeout: Duration) -> Result<Self> {
+        let client = reqwest::Client::builder()
+            .danger_accept_invalid_certs(true)
+            .timeout(timeout)
+            .build()
+            .context("Failed to create HTTP client")?;
+        Ok(Self {
+            client,
+            url: format!("https://{}:{}/healthz", host, port),
+        })
+    }
+
+    pub fn url(&self) -> &str {
+        &self.url
+    }
+
+    pub async fn check(&self) -> ApiHealth {
+        match self.client.get(&self.url).send().await {
+            Ok(resp) => match resp.status().as_u16() {
+                200..=299 => ApiHealth::Healthy,
+                status @ (401 | 403) => ApiHealth::Unauthorized(status),
+                status => ApiHealth::Unhealthy(status),
+            },
+            Err(e) if e.is_timeout() => ApiHealth::TimedOut,
+            Err(e) => classify_error(&e),
+        }
+    }
+}
+
+/// Keywords of TLS failures in the messages of rustls and hyper
+const TLS_MARKERS: [&str; 4] = ["tls", "certificate", "handshake", "corrupt message"];
+
+/// Walk the error's causes for the most specific one
+fn classify_error(error: &(dyn StdError + 'static)) -> ApiHealth {
+    let mut root = error.to_string();
+    let mut source = Some(error);
+    while let Some(e) = source {
+        if let Some(io) = e.downcast_ref::<std::io::Error>() {
+            match io.kind() {
+                ErrorKind::ConnectionRefused => return ApiHealth::ConnectionRefused,
+                ErrorKind::TimedOut => return ApiHealth::TimedOut,
+                _ => {}
+            }
+        }
+        let message = e.to_string();
+        let lower = message.to_lowercase();
+        if TLS_MARKERS.iter().any(|marker| lower.contains(marker)) {
+            return ApiHealth::Tls(message);
+        }
+        root = message;
+        source = e.source();
+    }
+    ApiHealth::Unreachable(root)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// An error wrapping `source`, like reqwest's around hyper's
+    #[derive(Debug)]
+    struct Wrapped(std::io::Error);
+
+    impl std::fmt::Display for Wrapped {
+        fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+            write!(f, "error sending request")
+        }
+    }
+
+    impl StdError for Wrapped {
+        fn source(&self) -> Option<&(dyn StdError + 'static)> {
+            Some(&self.0)
+        }
+    }
+
+    #[test]
+    fn errors_are_classified_by_their_causes() {
+        let refused = Wrapped(std::io::Error::from(ErrorKind::ConnectionRefused));
+        assert_eq!(classify_error(&refused), ApiHealth::ConnectionRefused);
+
+        let tls = Wrapped(std::io::Error::other(
+            "received corrupt message of type InvalidContentType",
+        ));
+        assert!(matches!(classify_error(&tls), ApiHealth::Tls(_)));
+
+        let dns = Wrapped(std::io::Error::other("failed to lookup address"));
+        assert_eq!(
+            classify_error(&dns),
+            ApiHealth::Unreachable("failed to lookup address".to_string())
+        );
+    }
+
+    #[test]
+    fn unauthorized_counts_as_up() {
+        assert!(ApiHealth::Healthy.is_up());
+        assert!(ApiHealth::Unauthorized(401).is_up());
+        assert!(!ApiHealth::Unhealthy(500).is_up());
+        assert!(!ApiHealth::ConnectionRefused.is_up());
+    }
+}
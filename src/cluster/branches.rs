@@ -0,0 +1,96 @@
+//! Clusters of deleted git branches (`infrastructure.per_branch`)
+//!
+//! Branch clusters carry their branch and base cluster name as container
+//! labels. One whose branch no longer exists locally is stale; removing it
+//! drops its container, warm standby, network and volumes. Pods, snapshots
+//! and the kubeconfig are shared with the active cluster and left alone.
+
+use anyhow::Result;
+use tokio::sync::mpsc;
+
+use super::{ClusterConfig, DockerManager};
+use crate::config::branch::{BranchCluster, LABEL_BASE_CLUSTER, LABEL_BRANCH};
+use crate::ui::components::OutputLine;
+
+/// A branch cluster whose branch is gone
+#[derive(Debug, Clone, PartialEq)]
+pub struct StaleBranchCluster {
+    pub container: String,
+    pub branch: String,
+    pub base_name: String,
+    pub running: bool,
+}
+
+/// Branch clusters of `base_name` whose branch is not in `branches`
+pub async fn stale_branch_clusters(
+    docker: &DockerManager,
+    base_name: &str,
+    branches: &[String],
+) -> Result<Vec<StaleBranchCluster>> {
+    let mut stale = Vec::new();
+    for container in docker
+        .list_containers_by_prefix(&format!("{}-", base_name))
+        .await?
+    {
+        if !container.ends_with("-server") {
+            continue;
+        }
+        let labels = docker.get_container_labels(&container).await;
+        if labels.get(LABEL_BASE_CLUSTER).map(String::as_str) != Some(base_name) {
+            continue;
+        }
+        let Some(branch) = labels.get(LABEL_BRANCH) else {
+            continue;
+        };
+        if branches.contains(branch) {
+            continue;
+        }
+        stale.push(StaleBranchCluster {
+            running: docker.container_running(&container).await,
+            branch: branch.clone(),
+            base_name: base_name.to_string(),
+            container,
+        });
+    }
+    Ok(stale)
+}
+
+/// Remove a stopped branch cluster's container, standby, network and volumes
+pub async fn remove_branch_cluster(
+    docker: &DockerManager,
+    base: &ClusterConfig,
+    stale: &StaleBranchCluster,
+    output_tx: &mpsc::Sender<OutputLine>,
+) -> Result<()> {
+    let cluster_name = stale
+        .container
+        .strip_suffix("-server")
+        .unwrap_or(&stale.container);
+    let config = ClusterConfig {
+        container_name: stale.container.clone(),
+        network_name: format!("{}-net", cluster_name),
+        branch: Some(BranchCluster {
+            branch: stale.branch.clone(),
+            base_name: stale.base_name.clone(),
+        }),
+        ..base.clone()
+    };
+
+    let _ = output_tx
+        .send(OutputLine::info(format!(
+            "Removing {} (branch {})...",
+            stale.container, stale.branch
+        )))
+        .await;
+    docker
+        .remove_container(&config.container_name, true)
+        .await?;
+    let standby = format!("{}-standby", config.container_name);
+    if docker.container_exists(&standby).await {
+        docker.remove_container(&standby, true).await?;
+    }
+    docker.remove_network(&config.network_name).await?;
+    docker.remove_volume(&config.rancher_volume_name()).await?;
+    docker.remove_volume(&config.local_pv_volume_name()).await?;
+    Ok(())
+}
@@ -0,0 +1,79 @@
+//! Long-lived Docker and Kubernetes connections
+//!
+//! The TUI refreshes status, stats and views every few seconds. Connecting
+//! to Docker (and negotiating its API version) or inferring a kubeconfig for
+//! every one of those churns connections and loses what a connection keeps
+//! between calls, like the CPU samples stats are computed from.
+//! [`SharedClients`] connects once and hands out cheap clones; after a
+//! failure is reported with one of the `reset_*` methods, the next use
+//! connects again.
+
+use std::sync::Arc;
+
+use anyhow::Result;
+use kube::{Client, Config};
+use tokio::sync::Mutex;
+
+use super::docker::DockerManager;
+use super::kube_ops::KubeOps;
+use super::platform::PlatformInfo;
+
+/// Docker and Kubernetes handles shared by background tasks
+#[derive(Clone, Default)]
+pub struct SharedClients {
+    inner: Arc<Inner>,
+}
+
+#[derive(Default)]
+struct Inner {
+    docker: Mutex<Option<DockerManager>>,
+    kube: Mutex<Option<Client>>,
+}
+
+impl SharedClients {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The shared Docker connection, connecting first if there is none
+    pub async fn docker(&self) -> Result<DockerManager> {
+        // Held while connecting so concurrent callers don't connect twice
+        let mut docker = self.inner.docker.lock().await;
+        if let Some(docker) = docker.as_ref() {
+            return Ok(docker.clone());
+        }
+        let platform = PlatformInfo::detect()?;
+        let connected = DockerManager::connect_platform(&platform).await?;
+        *docker = Some(connected.clone());
+        Ok(connected)
+    }
+
+    /// Forget the Docker connection, e.g. after the daemon stopped answering
+    pub async fn reset_docker(&self) {
+        if self.inner.docker.lock().await.take().is_some() {
+            tracing::debug!("Dropped the shared Docker connection");
+        }
+    }
+
+    /// Kubernetes operations over the shared client. Without a kubeconfig
+    /// yet, the operations connect lazily on their own.
+    pub async fn kube_ops(&self) -> KubeOps {
+        let mut kube = self.inner.kube.lock().await;
+        if kube.is_none() {
+            *kube = match Config::infer().await {
+                Ok(config) => Client::try_from(config).ok(),
+                Err(_) => None,
+            };
+        }
+        match kube.as_ref() {
+            Some(client) => KubeOps::with_client(client.clone()),
+            None => KubeOps::new(),
+        }
+    }
+
+    /// Forget the Kubernetes client; the API server's address and
+    /// certificates change when the cluster is recreated
+    pub async fn reset_kube(&self) {
+        self.inner.kube.lock().await.take();
+    }
+}
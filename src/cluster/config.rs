@@ -1,6 +1,12 @@
+use std::collections::HashMap;
 use std::path::PathBuf;
 
-use crate::config::{HooksConfig, InfrastructureConfig, SpeedupConfig};
+use super::K3sManager;
+use crate::config::branch::{branch_slug, BranchCluster, LABEL_BASE_CLUSTER, LABEL_BRANCH};
+use crate::config::{
+    HooksConfig, InfrastructureConfig, PolicyEngine, ServiceMesh, SmokeTest, SpeedupConfig,
+    StartConfig, StorageConfig,
+};
 
 /// Unified cluster configuration settings
 ///
@@ -29,11 +35,33 @@ pub struct ClusterConfig {
     pub https_port: u16,
     pub additional_ports: Vec<(u16, u16)>,
 
+    // Enforce NetworkPolicies
+    pub network_policy: bool,
+
+    // Mesh for the mesh lab actions
+    pub service_mesh: ServiceMesh,
+
+    // Engine for the policy testing actions
+    pub policy_engine: PolicyEngine,
+
     // Speedup optimizations
     pub speedup: SpeedupConfig,
 
+    // Extra StorageClasses and the PV directory
+    pub storage: StorageConfig,
+
     // Hooks
     pub hooks: HooksConfig,
+
+    // Checks run after the on_services_deployed hooks
+    pub smoke_tests: Vec<SmokeTest>,
+
+    // Readiness gates
+    pub start: StartConfig,
+
+    // Per-branch clusters; `branch` is set when on a feature branch
+    pub per_branch: bool,
+    pub branch: Option<BranchCluster>,
 }
 
 /// Parse additional ports from string format "host:container" to tuple
@@ -70,8 +98,16 @@ impl From<InfrastructureConfig> for ClusterConfig {
             http_port: infra.http_port,
             https_port: infra.https_port,
             additional_ports,
+            network_policy: infra.network_policy,
+            service_mesh: infra.service_mesh,
+            policy_engine: infra.policy_engine,
             speedup: infra.speedup,
+            storage: infra.storage,
+            per_branch: infra.per_branch,
+            branch: infra.branch,
             hooks: HooksConfig::default(),
+            smoke_tests: Vec::new(),
+            start: StartConfig::default(),
         }
     }
 }
@@ -96,15 +132,83 @@ impl Default for ClusterConfig {
             http_port: infra.http_port,
             https_port: infra.https_port,
             additional_ports: vec![(2345, 2345), (8309, 8309)],
+            network_policy: infra.network_policy,
+            service_mesh: infra.service_mesh,
+            policy_engine: infra.policy_engine,
 
             speedup: SpeedupConfig::default(),
+            storage: StorageConfig::default(),
 
             hooks: HooksConfig::default(),
+            smoke_tests: Vec::new(),
+            start: StartConfig::default(),
+            per_branch: false,
+            branch: None,
         }
     }
 }
 
 impl ClusterConfig {
+    /// Port the API server listens on inside the cluster container; the
+    /// host side is `api_port`
+    pub const K3S_API_PORT: u16 = 6443;
+
+    /// Volume holding /var/lib/rancher; per branch cluster when names derive from a branch
+    pub fn rancher_volume_name(&self) -> String {
+        self.branch_volume_name(K3sManager::RANCHER_VOLUME_NAME)
+    }
+
+    /// Volume holding local-path PVs
+    pub fn local_pv_volume_name(&self) -> String {
+        self.branch_volume_name(K3sManager::LOCAL_PV_VOLUME_NAME)
+    }
+
+    /// `cluster_name`, including the branch suffix
+    pub fn cluster_name(&self) -> &str {
+        self.container_name
+            .strip_suffix("-server")
+            .unwrap_or(&self.container_name)
+    }
+
+    /// `cluster_name` without the branch suffix
+    pub fn base_cluster_name(&self) -> String {
+        match &self.branch {
+            Some(branch) => branch.base_name.clone(),
+            None => self
+                .container_name
+                .strip_suffix("-server")
+                .unwrap_or(&self.container_name)
+                .to_string(),
+        }
+    }
+
+    fn branch_volume_name(&self, base: &str) -> String {
+        match &self.branch {
+            Some(branch) => format!("{}-{}", base, branch_slug(&branch.branch)),
+            None => base.to_string(),
+        }
+    }
+
+    /// Labels for the cluster container; branch clusters are marked for `prune-branches`
+    pub fn container_labels(&self) -> HashMap<String, String> {
+        match &self.branch {
+            Some(branch) => HashMap::from([
+                (LABEL_BRANCH.to_string(), branch.branch.clone()),
+                (LABEL_BASE_CLUSTER.to_string(), branch.base_name.clone()),
+            ]),
+            None => HashMap::new(),
+        }
+    }
+
+    /// k3s server flag for the network-policy controller (empty when enabled)
+    pub fn network_policy_flag(&self) -> &'static str {
+        if self.network_policy {
+            ""
+        } else {
+            " --disable-network-policy"
+        }
+    }
+
     /// Get the k3s image name
     pub fn k3s_image(&self) -> String {
         format!("rancher/k3s:{}", self.k3s_version)
@@ -129,7 +233,7 @@ impl ClusterConfig {
     /// Get all port mappings as docker format strings
     pub fn port_mappings(&self) -> Vec<String> {
         let mut ports = vec![
-            format!("{}:{}", self.api_port, self.api_port),
+            format!("{}:{}", self.api_port, Self::K3S_API_PORT),
             format!("{}:{}", self.http_port, self.http_port),
             format!("{}:{}", self.https_port, self.https_port),
         ];
@@ -157,6 +261,18 @@ impl ClusterConfig {
         self
     }
 
+    /// Builder method to set the smoke tests
+    pub fn with_smoke_tests(mut self, smoke_tests: Vec<SmokeTest>) -> Self {
+        self.smoke_tests = smoke_tests;
+        self
+    }
+
+    /// Builder method to set start behaviour (readiness gates)
+    pub fn with_start(mut self, start: StartConfig) -> Self {
+        self.start = start;
+        self
+    }
+
     /// Builder method to set K8s client configuration
     pub fn with_k8s_config(mut self, kubeconfig: Option<String>, context: Option<String>) -> Self {
         self.kubeconfig = kubeconfig;
@@ -0,0 +1,140 @@
+//! Degraded-cluster detection
+//!
+//! A running container doesn't mean a usable cluster: without DNS or the
+//! ingress controller nothing inside works, and a NotReady node schedules
+//! nothing. The health check looks at the core deployments and the nodes
+//! and names every component that's down.
+
+use std::fmt;
+
+use anyhow::Result;
+
+use super::kube_ops::{DeploymentInfo, KubeOps, NodeInfo};
+
+/// Namespace of the core deployments
+pub const CORE_NAMESPACE: &str = "kube-system";
+
+/// Deployments the cluster is unusable without
+pub const CORE_DEPLOYMENTS: [&str; 2] = ["coredns", "traefik"];
+
+/// A component that is down
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FailingComponent {
+    Deployment { namespace: String, name: String },
+    Node(String),
+}
+
+/// A failing component and what's wrong with it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HealthProblem {
+    pub component: FailingComponent,
+    pub detail: String,
+}
+
+impl HealthProblem {
+    /// A core deployment that is down, e.g. a readiness gate that timed out
+    pub fn deployment(name: &str, detail: impl Into<String>) -> Self {
+        Self {
+            component: FailingComponent::Deployment {
+                namespace: CORE_NAMESPACE.to_string(),
+                name: name.to_string(),
+            },
+            detail: detail.into(),
+        }
+    }
+}
+
+impl fmt::Display for HealthProblem {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.component {
+            FailingComponent::Deployment { name, .. } => write!(f, "{} {}", name, self.detail),
+            FailingComponent::Node(name) => write!(f, "node {} {}", name, self.detail),
+        }
+    }
+}
+
+/// Core deployments without a ready replica and nodes that aren't Ready.
+/// A core deployment that doesn't exist (e.g. Traefik still being
+/// installed) isn't counted.
+pub fn find_problems(deployments: &[DeploymentInfo], nodes: &[NodeInfo]) -> Vec<HealthProblem> {
+    let mut problems: Vec<HealthProblem> = CORE_DEPLOYMENTS
+        .iter()
+        .filter_map(|core| deployments.iter().find(|d| d.name == *core))
+        .filter(|d| d.desired > 0 && d.ready == 0)
+        .map(|d| HealthProblem::deployment(&d.name, format!("0/{} ready", d.desired)))
+        .collect();
+    problems.extend(
+        nodes
+            .iter()
+            .filter(|n| n.status != "Ready")
+            .map(|n| HealthProblem {
+                component: FailingComponent::Node(n.name.clone()),
+                detail: n.status.clone(),
+            }),
+    );
+    problems
+}
+
+/// Check the core deployments and the nodes
+pub async fn check_health(kube_ops: &mut KubeOps) -> Result<Vec<HealthProblem>> {
+    let deployments = kube_ops.list_deployments(CORE_NAMESPACE).await?;
+    let nodes = kube_ops.list_nodes().await?;
+    Ok(find_problems(&deployments, &nodes))
+}
+
+/// One-line summary, e.g. "coredns 0/1 ready, node k3s NotReady"
+pub fn summarize(problems: &[HealthProblem]) -> String {
+    problems
+        .iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn deployment(name: &str, ready: i32, desired: i32) -> DeploymentInfo {
+        DeploymentInfo {
+            name: name.to_string(),
+            ready,
+            desired,
+        }
+    }
+
+    fn node(name: &str, status: &str) -> NodeInfo {
+        NodeInfo {
+            name: name.to_string(),
+            status: status.to_string(),
+            roles: String::new(),
+            internal_ip: None,
+            version: String::new(),
+        }
+    }
+
+    #[test]
+    fn core_deployments_without_ready_replicas_and_unready_nodes_are_problems() {
+        let deployments = [
+            deployment("coredns", 0, 1),
+            deployment("traefik", 1, 2),
+            deployment("metrics-server", 0, 1),
+        ];
+        let nodes = [node("k3s", "Ready"), node("agent", "NotReady")];
+        let problems = find_problems(&deployments, &nodes);
+        assert_eq!(
+            summarize(&problems),
+            "coredns 0/1 ready, node agent NotReady"
+        );
+        assert_eq!(
+            problems[0].component,
+            FailingComponent::Deployment {
+                namespace: "kube-system".to_string(),
+                name: "coredns".to_string(),
+            }
+        );
+
+        // Scaled to zero on purpose, or not installed at all
+        assert!(find_problems(&[deployment("coredns", 0, 0)], &[node("k3s", "Ready")]).is_empty());
+    }
+}
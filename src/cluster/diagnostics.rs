@@ -20,6 +20,8 @@ use kube::api::{Api, DeleteParams, ListParams, PostParams};
 use tokio::net::TcpStream;
 
 use crate::app::AppMessage;
+use crate::cluster::api_probe::ApiProbe;
+use crate::cluster::k3s_versions::ReleaseIndex;
 use crate::cluster::kube_ops::KubeOps;
 use crate::cluster::{
     ClusterConfig, DockerManager, IngressHealthChecker, IngressManager, PlatformInfo,
@@ -107,6 +109,28 @@ async fn init_k8s_client(config: &ClusterConfig) -> Result<K8sClient, String> {
         .map_err(|e| format!("client init failed: {}", e))
 }
 
+/// Host port the container publishes the API on, which differs from
+/// `api_port` when that was taken at creation
+async fn published_api_port(config: &ClusterConfig) -> u16 {
+    match docker_mgr() {
+        Ok(docker) => docker
+            .published_port(&config.container_name, ClusterConfig::K3S_API_PORT)
+            .await
+            .unwrap_or(config.api_port),
+        Err(_) => config.api_port,
+    }
+}
+
+/// Why the API server doesn't answer, from a direct probe of its port
+async fn probe_api(config: &ClusterConfig) -> String {
+    let port = published_api_port(config).await;
+    let host = PlatformInfo::docker_remote_host().unwrap_or("127.0.0.1");
+    match ApiProbe::new(host, port, Duration::from_secs(2)) {
+        Ok(probe) => format!("{}: {}", probe.url(), probe.check().await.describe()),
+        Err(e) => e.to_string(),
+    }
+}
+
 /// Construct a DockerManager using the auto-detected host socket.
 fn docker_mgr() -> Result<DockerManager, String> {
     DockerManager::from_default_socket().map_err(|e| e.to_string())
@@ -381,6 +405,11 @@ fn build_preflight_list(config: &ClusterConfig) -> Vec<DiagnosticResult> {
         ),
         ("pre_docker_disk", CAT_PREFLIGHT_DOCKER, "Docker disk space"),
         ("pre_k3s_image", CAT_PREFLIGHT_DOCKER, "K3s image available"),
+        (
+            "pre_k3s_version",
+            CAT_PREFLIGHT_DOCKER,
+            "K3s version supported",
+        ),
         (
             "pre_container_conflict",
             CAT_PREFLIGHT_DOCKER,
@@ -434,6 +463,12 @@ fn build_preflight_list(config: &ClusterConfig) -> Vec<DiagnosticResult> {
     tests
 }
 
+/// Whether a preflight port test is for the API port, which setup moves to
+/// a free port when it's taken (only for a local daemon)
+fn remaps_when_taken(test_name: &str) -> bool {
+    test_name.ends_with("(K8s API) available") && PlatformInfo::docker_remote_host().is_none()
+}
+
 /// Preflight skip logic: Docker tests require docker_accessible to pass
 fn should_skip_preflight(report: &DiagnosticsReport, test_idx: usize) -> Option<&'static str> {
     let test_cat = report.results[test_idx].category;
@@ -525,6 +560,25 @@ async fn execute_preflight_test(
                 Ok(Some("will be pulled on start".to_string()))
             }
         }
+        "pre_k3s_version" => {
+            let docker = docker_mgr()?;
+            let arch = docker.platform_architecture().await;
+            let index = ReleaseIndex::new().map_err(|e| e.to_string())?;
+            match index.check(&config.k3s_version, arch.as_deref()).await {
+                Ok(issues) if issues.is_empty() => Ok(Some(config.k3s_version.clone())),
+                Ok(issues) => Err(format!(
+                    "{}: {}",
+                    config.k3s_version,
+                    issues
+                        .iter()
+                        .map(|i| i.to_string())
+                        .collect::<Vec<_>>()
+                        .join("; ")
+                )),
+                // Offline or rate limited; the pull will tell
+                Err(e) => Ok(Some(format!("not checked ({:#})", e))),
+            }
+        }
         "pre_container_conflict" => {
             let docker = docker_mgr()?;
             if docker.container_exists(&config.container_name).await {
@@ -604,10 +658,15 @@ async fn execute_preflight_test(
                 }
             }
 
+            // Held by this k3dev waiting for a connection to start the cluster
+            if super::on_demand::holds(port) {
+                return Ok(Some("held by k3dev for on-demand start".to_string()));
+            }
+
             // Not used by our container — check if port is actually available
             // For privileged ports (< 1024), bind() fails without root even if free,
             // so use TCP connect instead: connection refused = port is free.
-            if port < 1024 {
+            let available = if port < 1024 {
                 match std::net::TcpStream::connect_timeout(
                     &std::net::SocketAddr::from(([127, 0, 0, 1], port)),
                     std::time::Duration::from_millis(500),
@@ -621,6 +680,13 @@ async fn execute_preflight_test(
                     Ok(_) => Ok(None),
                     Err(_) => Err(format!("port {} already in use by another service", port)),
                 }
+            };
+            // Setup publishes the API on the next free port instead
+            match available {
+                Err(_) if remaps_when_taken(test_name) => {
+                    Ok(Some("in use, will be remapped".to_string()))
+                }
+                other => other,
             }
         }
         "pre_kubeconfig_dir" => {
@@ -836,7 +902,10 @@ async fn execute_test(test_id: &str, config: &ClusterConfig) -> Result<Option<St
             if k8s.is_connected().await {
                 Ok(None)
             } else {
-                Err("API server not responding".to_string())
+                Err(format!(
+                    "API server not responding ({})",
+                    probe_api(config).await
+                ))
             }
         }
         "nodes_ready" => {
@@ -981,7 +1050,7 @@ async fn execute_test(test_id: &str, config: &ClusterConfig) -> Result<Option<St
             let ports: Vec<(u16, &str)> = vec![
                 (config.http_port, "HTTP"),
                 (config.https_port, "HTTPS"),
-                (config.api_port, "K8s API"),
+                (published_api_port(config).await, "K8s API"),
             ];
 
             let mut failed = Vec::new();
@@ -1039,10 +1108,11 @@ async fn execute_test(test_id: &str, config: &ClusterConfig) -> Result<Option<St
             if entries.is_empty() {
                 return Err("no ingress entries to check".to_string());
             }
-            let health = IngressHealthChecker::check_endpoints(&entries).await;
+            let concurrency = crate::config::RefreshConfig::default().ingress_health_concurrency;
+            let health = IngressHealthChecker::check_endpoints(&entries, concurrency).await;
             let unhealthy: Vec<_> = health
                 .iter()
-                .filter(|(_, s)| matches!(s, crate::cluster::IngressHealthStatus::Error))
+                .filter(|(_, c)| c.status == crate::cluster::IngressHealthStatus::Error)
                 .map(|(url, _)| url.as_str())
                 .collect();
             if unhealthy.is_empty() {
@@ -1734,6 +1804,14 @@ mod tests {
         assert!(tests.iter().all(|t| t.status == DiagnosticStatus::Pending));
     }
 
+    #[test]
+    fn test_only_the_api_port_is_remapped() {
+        let local = PlatformInfo::docker_remote_host().is_none();
+        assert_eq!(remaps_when_taken("Port 6443 (K8s API) available"), local);
+        assert!(!remaps_when_taken("Port 443 (HTTPS) available"));
+        assert!(!remaps_when_taken("Port 8080 (additional) available"));
+    }
+
     #[test]
     fn test_categories_in_order() {
         let tests = build_test_list();
@@ -0,0 +1,82 @@
+//! Container lifecycle events from the Docker events stream
+//!
+//! Lets the app react to a container starting or stopping as it happens
+//! instead of on the next poll.
+
+use std::collections::HashMap;
+
+use bollard::query_parameters::EventsOptions;
+use futures_util::{Stream, StreamExt};
+
+use super::DockerManager;
+
+/// Event actions that change whether a container is running
+const LIFECYCLE_ACTIONS: [&str; 5] = ["start", "stop", "die", "pause", "unpause"];
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ContainerEventKind {
+    Started,
+    Stopped,
+    Died,
+    Paused,
+    Unpaused,
+}
+
+impl ContainerEventKind {
+    fn from_action(action: &str) -> Option<Self> {
+        match action {
+            "start" => Some(Self::Started),
+            "stop" => Some(Self::Stopped),
+            "die" => Some(Self::Died),
+            "pause" => Some(Self::Paused),
+            "unpause" => Some(Self::Unpaused),
+            _ => None,
+        }
+    }
+}
+
+/// A container changed state
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ContainerEvent {
+    /// Container name without the leading `/`
+    pub name: String,
+    pub kind: ContainerEventKind,
+}
+
+impl ContainerEvent {
+    /// A pod container started by k3s (`k8s_...`) rather than a cluster container
+    pub fn is_pod_container(&self) -> bool {
+        self.name.starts_with("k8s_")
+    }
+}
+
+impl DockerManager {
+    /// Lifecycle events of all containers, from now on.
+    ///
+    /// The stream ends when the daemon connection drops; callers resubscribe.
+    pub fn container_events(&self) -> impl Stream<Item = ContainerEvent> + use<> {
+        let mut filters = HashMap::new();
+        filters.insert("type".to_string(), vec!["container".to_string()]);
+        filters.insert(
+            "event".to_string(),
+            LIFECYCLE_ACTIONS.iter().map(|a| a.to_string()).collect(),
+        );
+
+        self.client
+            .events(Some(EventsOptions {
+                filters: Some(filters),
+                ..Default::default()
+            }))
+            .take_while(|event| std::future::ready(event.is_ok()))
+            .filter_map(|event| {
+                std::future::ready(event.ok().and_then(|event| {
+                    let kind = ContainerEventKind::from_action(event.action.as_deref()?)?;
+                    let name = event.actor?.attributes?.remove("name")?;
+                    Some(ContainerEvent {
+                        name: name.trim_start_matches('/').to_string(),
+                        kind,
+                    })
+                }))
+            })
+    }
+}
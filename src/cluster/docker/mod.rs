@@ -8,12 +8,18 @@
 
 #![allow(deprecated)]
 
+mod events;
 pub(crate) mod pull_progress;
+mod pv_data;
 mod stats;
+mod top;
 mod volumes;
 
+pub use events::ContainerEvent;
 pub use pull_progress::{ContainerPullProgress, PullPhase};
-pub use stats::ContainerStats;
+pub use pv_data::PvDirectory;
+pub use stats::{ContainerStats, HostCapacity};
+pub use top::PodProcess;
 
 use anyhow::{anyhow, Context, Result};
 use bollard::exec::{CreateExecOptions, StartExecOptions, StartExecResults};
@@ -24,20 +30,46 @@ use bollard::models::{
 };
 use bollard::query_parameters::{
     CommitContainerOptions, CreateContainerOptions, CreateImageOptions, InspectContainerOptions,
-    InspectNetworkOptions, ListContainersOptions, ListImagesOptions, RemoveContainerOptions,
-    RemoveImageOptions, RemoveVolumeOptions, StartContainerOptions, StopContainerOptions,
+    InspectNetworkOptions, ListContainersOptions, ListImagesOptions, LogsOptionsBuilder,
+    RemoveContainerOptions, RemoveImageOptions, RemoveVolumeOptions, StartContainerOptions,
+    StopContainerOptions,
 };
 use bollard::ClientVersion;
 use bollard::Docker;
-use futures_util::StreamExt;
+use futures_util::{Stream, StreamExt};
 use std::collections::{HashMap, HashSet};
 use std::path::{Path, PathBuf};
+use std::sync::Arc;
 use std::time::Duration;
 
+use crate::error::K3devError;
+
+/// Final state of a stopped container
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ContainerExit {
+    pub exit_code: i64,
+    pub oom_killed: bool,
+    /// Error Docker recorded when the container failed to start
+    pub error: Option<String>,
+}
+
+/// Name, ID and state of a listed container
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ContainerSummary {
+    pub id: String,
+    pub name: String,
+    /// e.g. "running", "exited", "created"
+    pub state: String,
+}
+
 /// Docker container and network management
+///
+/// Cloning is cheap: clones share the connection and the stats samples.
+#[derive(Clone)]
 pub struct DockerManager {
     socket_path: PathBuf,
     pub(crate) client: Docker,
+    stats_cache: Arc<stats::StatsCache>,
 }
 
 impl DockerManager {
@@ -47,6 +79,7 @@ impl DockerManager {
         Ok(Self {
             socket_path,
             client,
+            stats_cache: Arc::default(),
         })
     }
 
@@ -55,6 +88,25 @@ impl DockerManager {
         Self::new(crate::cluster::PlatformInfo::find_docker_socket_sync())
     }
 
+    /// Connect to the platform's Docker socket with a negotiated API version,
+    /// for connections that are kept around
+    pub async fn connect_platform(platform: &crate::cluster::PlatformInfo) -> Result<Self> {
+        let socket_path = platform.docker_socket_path().await?;
+        let mut docker = Self::new(socket_path)?;
+
+        // Negotiate API version for compatibility with older Docker versions
+        if let Err(e) = docker.negotiate_api_version().await {
+            tracing::warn!(
+                "Docker API version negotiation failed (using default): {:#}",
+                e
+            );
+        }
+
+        // Warn if Docker daemon architecture differs from binary's compile-time target_arch
+        docker.check_architecture_mismatch().await;
+        Ok(docker)
+    }
+
     /// Connect to Docker using the resolved socket path.
     /// Falls back to DOCKER_HOST / default if the path doesn't exist (TCP/remote).
     fn connect(socket_path: &Path) -> Result<Docker> {
@@ -135,6 +187,20 @@ impl DockerManager {
         }
     }
 
+    /// The daemon's architecture as image platforms name it ("amd64", "arm64")
+    pub async fn platform_architecture(&self) -> Option<String> {
+        let arch = self.client.info().await.ok()?.architecture?;
+        Some(
+            match arch.as_str() {
+                "x86_64" => "amd64",
+                "aarch64" => "arm64",
+                "armv7l" => "arm",
+                other => other,
+            }
+            .to_string(),
+        )
+    }
+
     /// Get Docker's root directory (data-root)
     /// Returns the actual Docker data directory (e.g., "/var/lib/docker", "~/.local/share/docker/")
     /// Falls back to "/var/lib/docker" if detection fails
@@ -147,22 +213,55 @@ impl DockerManager {
         }
     }
 
+    /// Docker daemon version and platform, e.g. "28.1.1 (Docker Engine - Community)"
+    pub async fn server_version(&self) -> Result<String> {
+        let version = self
+            .client
+            .version()
+            .await
+            .context("Failed to get Docker version")?;
+        let number = version.version.unwrap_or_else(|| "unknown".to_string());
+        Ok(match version.platform {
+            Some(platform) => format!("{} ({})", number, platform.name),
+            None => number,
+        })
+    }
+
     /// Check if Docker is accessible.
     /// Retries briefly to handle systemd socket activation delays.
     pub async fn is_accessible(&self) -> bool {
-        if self.client.ping().await.is_ok() {
-            return true;
-        }
+        self.ensure_accessible().await.is_ok()
+    }
+
+    /// Like [`Self::is_accessible`], telling a stopped daemon from a socket
+    /// the current user may not open
+    pub async fn ensure_accessible(&self) -> Result<()> {
+        let mut result = self.client.ping().await;
 
         // Socket activation: daemon may be starting. Retry with short delays.
         for _ in 0..3 {
-            tokio::time::sleep(Duration::from_secs(1)).await;
-            if self.client.ping().await.is_ok() {
-                return true;
+            match &result {
+                Ok(_) => return Ok(()),
+                Err(e) if e.to_string().to_lowercase().contains("permission denied") => break,
+                Err(_) => {}
             }
+            tokio::time::sleep(Duration::from_secs(1)).await;
+            result = self.client.ping().await;
         }
 
-        false
+        match result {
+            Ok(_) => Ok(()),
+            Err(e) if e.to_string().to_lowercase().contains("permission denied") => {
+                Err(K3devError::PermissionDenied {
+                    socket: self.socket_path.display().to_string(),
+                }
+                .into())
+            }
+            Err(e) => {
+                tracing::debug!(error = %e, "Docker ping failed");
+                Err(K3devError::DockerUnavailable.into())
+            }
+        }
     }
 
     /// Check that Docker's cgroup driver is cgroupfs (required for k3s-in-docker).
@@ -228,6 +327,70 @@ impl DockerManager {
             .map(|s| s.to_string())
     }
 
+    /// How a container stopped; None while it runs or when it doesn't exist
+    pub async fn container_exit(&self, name: &str) -> Option<ContainerExit> {
+        let state = self
+            .client
+            .inspect_container(name, None::<InspectContainerOptions>)
+            .await
+            .ok()?
+            .state?;
+        if state.running.unwrap_or(false) {
+            return None;
+        }
+        Some(ContainerExit {
+            exit_code: state.exit_code.unwrap_or(0),
+            oom_killed: state.oom_killed.unwrap_or(false),
+            error: state.error.filter(|e| !e.is_empty()),
+        })
+    }
+
+    /// cgroup version of the Docker host ("1" or "2")
+    pub async fn cgroup_version(&self) -> Option<String> {
+        let info = self.client.info().await.ok()?;
+        info.cgroup_version
+            .map(|v| v.to_string())
+            .filter(|v| !v.is_empty())
+    }
+
+    /// Last `tail` lines of a container's stdout and stderr, with timestamps
+    pub async fn container_logs(&self, name: &str, tail: usize) -> Result<String> {
+        let options = LogsOptionsBuilder::default()
+            .stdout(true)
+            .stderr(true)
+            .timestamps(true)
+            .tail(&tail.to_string())
+            .build();
+        let mut stream = self.client.logs(name, Some(options));
+        let mut logs = String::new();
+        while let Some(chunk) = stream.next().await {
+            let chunk = chunk.with_context(|| format!("Failed to read logs of {}", name))?;
+            logs.push_str(&String::from_utf8_lossy(&chunk.into_bytes()));
+        }
+        Ok(logs)
+    }
+
+    /// A container's output from its last `tail` lines on, as it is written;
+    /// the stream ends when the container stops
+    pub fn follow_logs(
+        &self,
+        name: &str,
+        tail: usize,
+    ) -> impl Stream<Item = Result<String>> + use<> {
+        let options = LogsOptionsBuilder::default()
+            .stdout(true)
+            .stderr(true)
+            .follow(true)
+            .tail(&tail.to_string())
+            .build();
+        let name = name.to_string();
+        self.client.logs(&name, Some(options)).map(move |chunk| {
+            chunk
+                .map(|c| String::from_utf8_lossy(&c.into_bytes()).into_owned())
+                .with_context(|| format!("Failed to read logs of {}", name))
+        })
+    }
+
     /// Start a stopped container
     pub async fn start_container(&self, name: &str) -> Result<()> {
         self.client
@@ -264,6 +427,53 @@ impl DockerManager {
             .with_context(|| format!("Failed to remove container {}", name))
     }
 
+    /// Rename a container
+    pub async fn rename_container(&self, name: &str, new_name: &str) -> Result<()> {
+        use bollard::query_parameters::RenameContainerOptions;
+        self.client
+            .rename_container(
+                name,
+                RenameContainerOptions {
+                    name: new_name.to_string(),
+                },
+            )
+            .await
+            .with_context(|| format!("Failed to rename container {} to {}", name, new_name))
+    }
+
+    /// Change a running container's memory limit (`memory_swap` -1 is unlimited)
+    pub async fn update_memory_limit(
+        &self,
+        name: &str,
+        memory: i64,
+        memory_swap: i64,
+    ) -> Result<()> {
+        use bollard::models::ContainerUpdateBody;
+        self.client
+            .update_container(
+                name,
+                ContainerUpdateBody {
+                    memory: Some(memory),
+                    memory_swap: Some(memory_swap),
+                    ..Default::default()
+                },
+            )
+            .await
+            .with_context(|| format!("Failed to update the memory limit of {}", name))
+    }
+
+    /// Get container labels (empty if the container doesn't exist)
+    pub async fn get_container_labels(&self, name: &str) -> HashMap<String, String> {
+        match self
+            .client
+            .inspect_container(name, None::<InspectContainerOptions>)
+            .await
+        {
+            Ok(info) => info.config.and_then(|c| c.labels).unwrap_or_default(),
+            Err(_) => HashMap::new(),
+        }
+    }
+
     /// Send a signal to a running container.
     /// `signal` is a name like "SIGTERM" / "SIGKILL".
     pub async fn kill_container(&self, name: &str, signal: &str) -> Result<()> {
@@ -339,6 +549,25 @@ impl DockerManager {
 
     /// Execute a command in a running container
     pub async fn exec_in_container(&self, container: &str, command: &[&str]) -> Result<String> {
+        let (result, code) = self.exec_with_exit_code(container, command).await?;
+        if code != 0 {
+            anyhow::bail!(
+                "Command {:?} exited with code {}: {}",
+                command,
+                code,
+                result.trim()
+            );
+        }
+        Ok(result)
+    }
+
+    /// Execute a command and return its combined output and exit code.
+    /// A non-zero exit is not an error.
+    pub async fn exec_with_exit_code(
+        &self,
+        container: &str,
+        command: &[&str],
+    ) -> Result<(String, i64)> {
         let exec = self
             .client
             .create_exec(
@@ -370,20 +599,8 @@ impl DockerManager {
             }
         }
 
-        // Check exit code
         let inspect = self.client.inspect_exec(&exec_id).await?;
-        if let Some(code) = inspect.exit_code {
-            if code != 0 {
-                anyhow::bail!(
-                    "Command {:?} exited with code {}: {}",
-                    command,
-                    code,
-                    result.trim()
-                );
-            }
-        }
-
-        Ok(result)
+        Ok((result, inspect.exit_code.unwrap_or(0)))
     }
 
     /// Execute a command and return whether it exited 0. Errors propagate only
@@ -459,6 +676,86 @@ impl DockerManager {
         Ok(())
     }
 
+    /// Read a file or directory from a container as a tar archive
+    pub async fn download_from_container(&self, container: &str, path: &str) -> Result<Vec<u8>> {
+        let mut stream = self.client.download_from_container(
+            container,
+            Some(bollard::query_parameters::DownloadFromContainerOptions {
+                path: path.to_string(),
+            }),
+        );
+        let mut archive = Vec::new();
+        while let Some(chunk) = stream.next().await {
+            let chunk = chunk.with_context(|| format!("Failed to copy {}:{}", container, path))?;
+            archive.extend_from_slice(&chunk);
+        }
+        Ok(archive)
+    }
+
+    /// Stream a file or directory from a container into a tar file on the host
+    pub async fn download_to_file(&self, container: &str, path: &str, dest: &Path) -> Result<()> {
+        use tokio::io::AsyncWriteExt;
+
+        let mut file = tokio::fs::File::create(dest)
+            .await
+            .with_context(|| format!("Failed to create {}", dest.display()))?;
+        let mut stream = self.client.download_from_container(
+            container,
+            Some(bollard::query_parameters::DownloadFromContainerOptions {
+                path: path.to_string(),
+            }),
+        );
+        while let Some(chunk) = stream.next().await {
+            let chunk = chunk.with_context(|| format!("Failed to copy {}:{}", container, path))?;
+            file.write_all(&chunk).await?;
+        }
+        file.flush().await?;
+        Ok(())
+    }
+
+    /// Unpack a tar file from the host into a container under `path`,
+    /// streaming it instead of loading it into memory
+    pub async fn upload_archive_file(
+        &self,
+        container: &str,
+        path: &str,
+        archive: &Path,
+    ) -> Result<()> {
+        use tokio::io::AsyncReadExt;
+
+        let file = tokio::fs::File::open(archive)
+            .await
+            .with_context(|| format!("Failed to open {}", archive.display()))?;
+        let body = futures::stream::try_unfold(file, |mut file| async move {
+            let mut buf = vec![0u8; 256 * 1024];
+            let n = file.read(&mut buf).await?;
+            if n == 0 {
+                return Ok(None);
+            }
+            buf.truncate(n);
+            Ok(Some((buf.into(), file)))
+        });
+        self.client
+            .upload_to_container(
+                container,
+                Some(bollard::query_parameters::UploadToContainerOptions {
+                    path: path.to_string(),
+                    ..Default::default()
+                }),
+                bollard::body_try_stream(body),
+            )
+            .await
+            .with_context(|| {
+                format!(
+                    "Failed to copy {} to {}:{}",
+                    archive.display(),
+                    container,
+                    path
+                )
+            })?;
+        Ok(())
+    }
+
     /// Execute a command in a container without waiting for it to finish (detached).
     /// Achieves the same effect as `docker exec -d` by not attaching stdout/stderr.
     pub async fn exec_detached(&self, container: &str, command: &[&str]) -> Result<()> {
@@ -528,6 +825,14 @@ impl DockerManager {
         Ok(port_map)
     }
 
+    /// Host port a container publishes `container_port` on
+    pub async fn published_port(&self, container: &str, container_port: u16) -> Option<u16> {
+        self.get_container_ports(container)
+            .await
+            .ok()
+            .and_then(|ports| ports.get(&container_port).copied())
+    }
+
     /// List containers by name prefix
     pub async fn list_containers_by_prefix(&self, prefix: &str) -> Result<Vec<String>> {
         let mut filters = HashMap::new();
@@ -553,6 +858,68 @@ impl DockerManager {
         Ok(names)
     }
 
+    /// Names and full IDs of containers whose name matches any of `prefixes`,
+    /// from a single list call
+    pub async fn list_container_ids(&self, prefixes: &[&str]) -> Result<Vec<(String, String)>> {
+        let mut filters = HashMap::new();
+        filters.insert(
+            "name".to_string(),
+            prefixes.iter().map(|p| p.to_string()).collect(),
+        );
+
+        let containers = self
+            .client
+            .list_containers(Some(ListContainersOptions {
+                all: true,
+                filters: Some(filters),
+                ..Default::default()
+            }))
+            .await
+            .context("Failed to list containers")?;
+
+        Ok(containers
+            .into_iter()
+            .filter_map(|c| Some((c.names?, c.id?)))
+            .flat_map(|(names, id)| {
+                names
+                    .into_iter()
+                    .map(move |n| (n.trim_start_matches('/').to_string(), id.clone()))
+            })
+            .collect())
+    }
+
+    /// Every container whose name starts with `prefix`, stopped ones included
+    pub async fn list_container_summaries(&self, prefix: &str) -> Result<Vec<ContainerSummary>> {
+        let mut filters = HashMap::new();
+        filters.insert("name".to_string(), vec![prefix.to_string()]);
+
+        let containers = self
+            .client
+            .list_containers(Some(ListContainersOptions {
+                all: true,
+                filters: Some(filters),
+                ..Default::default()
+            }))
+            .await
+            .context("Failed to list containers")?;
+
+        Ok(containers
+            .into_iter()
+            .filter_map(|c| {
+                let name = c.names?.first()?.trim_start_matches('/').to_string();
+                // The name filter matches anywhere in the name
+                if !name.starts_with(prefix) {
+                    return None;
+                }
+                Some(ContainerSummary {
+                    id: c.id?,
+                    name,
+                    state: c.state.map(|s| s.to_string()).unwrap_or_default(),
+                })
+            })
+            .collect())
+    }
+
     /// List k8s containers with their volume mount sources.
     /// Parses pod name + namespace from container name format `k8s_{container}_{pod}_{namespace}_{uid}_{attempt}`.
     pub async fn list_containers_with_mounts(
@@ -670,6 +1037,32 @@ impl DockerManager {
         Ok(())
     }
 
+    /// Create a Docker volume with labels
+    pub async fn create_labeled_volume(
+        &self,
+        name: &str,
+        labels: HashMap<String, String>,
+    ) -> Result<()> {
+        self.client
+            .create_volume(VolumeCreateRequest {
+                name: Some(name.to_string()),
+                labels: Some(labels),
+                ..Default::default()
+            })
+            .await
+            .with_context(|| format!("Failed to create volume {}", name))?;
+
+        Ok(())
+    }
+
+    /// Labels of a Docker volume (empty if it doesn't exist)
+    pub async fn get_volume_labels(&self, name: &str) -> HashMap<String, String> {
+        match self.client.inspect_volume(name).await {
+            Ok(volume) => volume.labels,
+            Err(_) => HashMap::new(),
+        }
+    }
+
     /// Remove a Docker volume
     pub async fn remove_volume(&self, name: &str) -> Result<()> {
         // Ignore errors - volume might not exist
@@ -1025,49 +1418,17 @@ impl DockerManager {
                         || err_msg.contains("port is already allocated");
 
                     if is_port_error {
-                        let privileged_ports: Vec<String> = config
+                        let privileged = config
                             .ports
                             .iter()
                             .filter(|(host, _)| *host < 1024)
                             .map(|(host, container)| format!("{}:{}", host, container))
                             .collect();
-
-                        let mut context_msg = format!(
-                            "Failed to start container '{}': port binding error.\n\n\
-                             Error: {}\n\n",
-                            config.name, e
-                        );
-
-                        if !privileged_ports.is_empty() {
-                            context_msg.push_str(&format!(
-                                "Privileged ports configured: {}\n\n",
-                                privileged_ports.join(", ")
-                            ));
-                        }
-
-                        if cfg!(target_os = "macos") {
-                            context_msg.push_str(
-                                "On macOS, binding to ports below 1024 (like 80/443) requires \
-                                 Docker Desktop's privileged port mapping.\n\n\
-                                 To fix:\n\
-                                 - Open Docker Desktop → Settings → Advanced → \
-                                   Enable privileged port mapping\n\
-                                 - Or configure alternative ports in k3dev.yml:\n\
-                                   http_port: 8080\n\
-                                   https_port: 8443\n",
-                            );
-                        } else {
-                            context_msg.push_str(
-                                "Possible causes:\n\
-                                 - Another process is already using the port(s)\n\
-                                 - Insufficient permissions to bind to ports below 1024\n\n\
-                                 To fix, configure alternative ports in k3dev.yml:\n\
-                                   http_port: 8080\n\
-                                   https_port: 8443\n",
-                            );
-                        }
-
-                        anyhow!(context_msg)
+                        anyhow!(K3devError::PortConflict {
+                            container: config.name.clone(),
+                            privileged,
+                            detail: e.to_string(),
+                        })
                     } else {
                         anyhow!(e).context(format!("Failed to start container {}", config.name))
                     }
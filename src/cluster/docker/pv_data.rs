@@ -0,0 +1,252 @@
+//! PV data under the local-path storage directory
+//!
+//! Lists the PV directories in the k3s container with their size, file
+//! count and last change, copies one to the host, or empties one, so the
+//! data of stateful apps can be inspected or reset without `docker cp`.
+
+use std::path::{Component, Path, PathBuf};
+
+use anyhow::{Context, Result};
+
+use super::volumes::parse_pvc_dir_name;
+use super::DockerManager;
+
+/// A PV directory and what's in it
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PvDirectory {
+    /// Directory name, `pvc-<uuid>_<namespace>_<pvc-name>` for local-path PVs
+    pub dir_name: String,
+    /// Namespace and claim, when the name follows the local-path format
+    pub claim: Option<(String, String)>,
+    pub size_bytes: u64,
+    pub files: u64,
+    /// Last change of the directory itself, as a Unix timestamp
+    pub modified: Option<i64>,
+}
+
+impl PvDirectory {
+    /// `namespace/claim`, else the directory name
+    pub fn label(&self) -> String {
+        match &self.claim {
+            Some((namespace, pvc)) => format!("{}/{}", namespace, pvc),
+            None => self.dir_name.clone(),
+        }
+    }
+
+    /// Where a download goes by default: a timestamped directory in the
+    /// current directory
+    pub fn default_download_dir(&self) -> PathBuf {
+        let name = match &self.claim {
+            Some((namespace, pvc)) => format!("{}-{}", namespace, pvc),
+            None => self.dir_name.clone(),
+        };
+        PathBuf::from(format!(
+            "k3dev-pv-{}-{}",
+            name,
+            chrono::Local::now().format("%Y%m%d-%H%M%S")
+        ))
+    }
+}
+
+/// Parse the listing script's output: `name<TAB>bytes<TAB>files<TAB>mtime`
+fn parse_listing(output: &str) -> Vec<PvDirectory> {
+    let mut dirs: Vec<PvDirectory> = output
+        .lines()
+        .filter_map(|line| {
+            let mut fields = line.split('\t');
+            let dir_name = fields.next()?.trim().to_string();
+            if dir_name.is_empty() {
+                return None;
+            }
+            let mut number = || fields.next().and_then(|f| f.trim().parse::<i64>().ok());
+            let size_bytes = number().unwrap_or(0).max(0) as u64;
+            let files = number().unwrap_or(0).max(0) as u64;
+            let modified = number();
+            Some(PvDirectory {
+                claim: parse_pvc_dir_name(&dir_name),
+                dir_name,
+                size_bytes,
+                files,
+                modified,
+            })
+        })
+        .collect();
+    dirs.sort_by_key(|d| d.label());
+    dirs
+}
+
+/// A plain directory name, so it can't reach outside the storage path
+fn checked_dir_name(dir_name: &str) -> Result<&str> {
+    if dir_name.is_empty() || dir_name == "." || dir_name == ".." || dir_name.contains('/') {
+        anyhow::bail!("Invalid PV directory name: {}", dir_name);
+    }
+    Ok(dir_name)
+}
+
+impl DockerManager {
+    /// PV directories under `storage_path` in the cluster container
+    pub async fn list_pv_directories(
+        &self,
+        container_name: &str,
+        storage_path: &str,
+    ) -> Result<Vec<PvDirectory>> {
+        let script = r#"cd "$1" 2>/dev/null || exit 0
+for d in */; do
+  [ -d "$d" ] || continue
+  d="${d%/}"
+  printf '%s\t%s\t%s\t%s\n' "$d" "$(du -sb "$d" 2>/dev/null | cut -f1)" "$(find "$d" -type f 2>/dev/null | wc -l)" "$(stat -c %Y "$d" 2>/dev/null)"
+done"#;
+        let output = self
+            .exec_in_container(container_name, &["sh", "-c", script, "sh", storage_path])
+            .await
+            .context("Failed to list PV directories in container")?;
+        Ok(parse_listing(&output))
+    }
+
+    /// Copy a PV directory's contents to `dest` on the host; returns the
+    /// number of files written
+    pub async fn download_pv_directory(
+        &self,
+        container_name: &str,
+        storage_path: &str,
+        dir_name: &str,
+        dest: &Path,
+    ) -> Result<usize> {
+        let dir_name = checked_dir_name(dir_name)?;
+        let archive = self
+            .download_from_container(container_name, &format!("{}/{}", storage_path, dir_name))
+            .await?;
+        let dest = dest.to_path_buf();
+        tokio::task::spawn_blocking(move || unpack_stripped(&archive, &dest))
+            .await
+            .context("Copy task failed")?
+    }
+
+    /// Delete everything inside a PV directory, keeping the directory
+    pub async fn wipe_pv_directory(
+        &self,
+        container_name: &str,
+        storage_path: &str,
+        dir_name: &str,
+    ) -> Result<()> {
+        let dir_name = checked_dir_name(dir_name)?;
+        let path = format!("{}/{}", storage_path, dir_name);
+        self.exec_in_container(
+            container_name,
+            &[
+                "sh",
+                "-c",
+                r#"cd "$1" && rm -rf -- ./* ./.[!.]* ./..?*"#,
+                "sh",
+                &path,
+            ],
+        )
+        .await
+        .with_context(|| format!("Failed to wipe {}", dir_name))?;
+        Ok(())
+    }
+}
+
+/// Unpack a `docker cp`-style archive into `dest`, dropping its top-level
+/// directory (the PV directory itself). Symlinks and hard links are skipped:
+/// the pod chose their targets, and later entries would be written through
+/// them.
+fn unpack_stripped(archive: &[u8], dest: &Path) -> Result<usize> {
+    std::fs::create_dir_all(dest)
+        .with_context(|| format!("Failed to create {}", dest.display()))?;
+    let mut files = 0;
+    let mut archive = tar::Archive::new(archive);
+    for entry in archive.entries().context("Invalid archive")? {
+        let mut entry = entry.context("Invalid archive entry")?;
+        let path = entry.path()?.into_owned();
+        let rest: PathBuf = path.components().skip(1).collect();
+        if rest.as_os_str().is_empty() {
+            continue;
+        }
+        if rest
+            .components()
+            .any(|c| !matches!(c, Component::Normal(_)))
+        {
+            anyhow::bail!("Refusing to unpack {}", path.display());
+        }
+        let entry_type = entry.header().entry_type();
+        if entry_type.is_symlink() || entry_type.is_hard_link() {
+            tracing::warn!(path = %path.display(), "Skipping link in PV archive");
+            continue;
+        }
+        let target = dest.join(&rest);
+        if let Some(parent) = target.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        if entry_type.is_file() {
+            files += 1;
+        }
+        entry
+            .unpack(&target)
+            .with_context(|| format!("Failed to write {}", target.display()))?;
+    }
+    Ok(files)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn listing_is_parsed_and_sorted_by_claim() {
+        let output = "pvc-1b2c_web_uploads\t4096\t3\t1700000000\n\
+                      pvc-9f8e_db_data\t1048576\t42\t1700000500\n\
+                      scratch\t\t0\t\n";
+        let dirs = parse_listing(output);
+        let labels: Vec<String> = dirs.iter().map(PvDirectory::label).collect();
+        assert_eq!(labels, ["db/data", "scratch", "web/uploads"]);
+        assert_eq!(dirs[0].size_bytes, 1048576);
+        assert_eq!(dirs[0].files, 42);
+        assert_eq!(dirs[0].modified, Some(1700000500));
+        assert_eq!(dirs[1].claim, None);
+        assert_eq!(dirs[1].modified, None);
+
+        assert!(checked_dir_name("pvc-1b2c_web_uploads").is_ok());
+        assert!(checked_dir_name("..").is_err());
+        assert!(checked_dir_name("a/../../etc").is_err());
+    }
+
+    #[test]
+    fn links_in_the_archive_are_not_followed() {
+        let root = std::env::temp_dir().join(format!("k3dev-pv-unpack-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&root);
+        let outside = root.join("outside");
+        std::fs::create_dir_all(&outside).unwrap();
+
+        let mut builder = tar::Builder::new(Vec::new());
+        let mut add = |path: &str, entry_type: tar::EntryType, link: Option<&Path>, data: &[u8]| {
+            let mut header = tar::Header::new_gnu();
+            header.set_entry_type(entry_type);
+            header.set_size(data.len() as u64);
+            header.set_mode(0o644);
+            if let Some(link) = link {
+                header.set_link_name(link).unwrap();
+            }
+            builder.append_data(&mut header, path, data).unwrap();
+        };
+        add("pvc/", tar::EntryType::Directory, None, b"");
+        add("pvc/link", tar::EntryType::Symlink, Some(&outside), b"");
+        add("pvc/link/evil", tar::EntryType::Regular, None, b"pwned");
+        add(
+            "pvc/hard",
+            tar::EntryType::Link,
+            Some(Path::new("/etc/passwd")),
+            b"",
+        );
+        add("pvc/data.txt", tar::EntryType::Regular, None, b"ok");
+        let archive = builder.into_inner().unwrap();
+
+        let dest = root.join("dest");
+        assert_eq!(unpack_stripped(&archive, &dest).unwrap(), 2);
+        assert!(!dest.join("link").is_symlink());
+        assert!(!dest.join("hard").exists());
+        assert_eq!(std::fs::read(dest.join("data.txt")).unwrap(), b"ok");
+        assert!(!outside.join("evil").exists());
+        let _ = std::fs::remove_dir_all(&root);
+    }
+}
@@ -6,11 +6,12 @@
 //! - CPU delta calculation with spike detection
 
 use anyhow::{Context, Result};
-use bollard::query_parameters::InspectContainerOptions;
 use once_cell::sync::Lazy;
 use serde::Deserialize;
-use std::collections::{HashMap, HashSet};
-use std::sync::RwLock;
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::{Arc, RwLock};
+use tokio::task::JoinSet;
 
 use super::DockerManager;
 
@@ -44,9 +45,24 @@ struct CachedCpuStats {
     prev_cpu_percent: f64,
 }
 
-/// Global CPU cache that persists across DockerManager instances
-static CPU_CACHE: Lazy<RwLock<HashMap<String, CachedCpuStats>>> =
-    Lazy::new(|| RwLock::new(HashMap::new()));
+/// A container's cgroup directory, remembered between refreshes
+#[derive(Debug, Clone)]
+struct CachedCgroup {
+    /// Full container ID the path was found for; a recreated container gets a new one
+    id: String,
+    path: PathBuf,
+}
+
+/// Samples kept between refreshes; clones of a DockerManager share them,
+/// so CPU deltas stay correct however many tasks read stats through it
+#[derive(Debug, Default)]
+pub(crate) struct StatsCache {
+    /// Container ID → last CPU sample
+    cpu: RwLock<HashMap<String, CachedCpuStats>>,
+    /// Container name → ID and cgroup path, so the cgroup tree is only
+    /// walked for new containers
+    cgroups: RwLock<HashMap<String, CachedCgroup>>,
+}
 
 /// Stats for a single container
 #[derive(Debug, Clone, Default)]
@@ -59,7 +75,25 @@ pub struct ContainerStats {
     pub memory_limit_mb: f64,
 }
 
+/// CPUs and memory available to the Docker daemon (the VM on macOS)
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct HostCapacity {
+    pub cpus: usize,
+    pub memory_mb: f64,
+}
+
 impl DockerManager {
+    /// Capacity the cluster's pods share, from `docker info`
+    pub async fn host_capacity(&self) -> Option<HostCapacity> {
+        let info = self.client.info().await.ok()?;
+        let cpus = usize::try_from(info.ncpu?).ok().filter(|&n| n > 0)?;
+        let memory = info.mem_total.filter(|&m| m > 0)?;
+        Some(HostCapacity {
+            cpus,
+            memory_mb: memory as f64 / (1024.0 * 1024.0),
+        })
+    }
+
     /// Get per-pod stats using cgroups v2 (much faster than Docker API)
     /// Reads directly from /sys/fs/cgroup/kubepods for resource stats
     pub async fn get_pod_stats(&self, prefix: &str) -> Result<Vec<ContainerStats>> {
@@ -71,19 +105,18 @@ impl DockerManager {
             anyhow::bail!("Host-side cgroup stats unavailable with remote Docker");
         }
 
-        // Get container list from Docker (fast - just listing, no stats)
-        let mut containers = self.list_containers_by_prefix(prefix).await?;
-        let k8s_containers = self.list_containers_by_prefix("k8s_").await?;
-        containers.extend(k8s_containers);
-
-        // Deduplicate and filter
-        let containers: Vec<String> = containers
-            .into_iter()
-            .collect::<HashSet<_>>()
+        // One list call for names and IDs of the cluster and its pod containers
+        let containers: HashMap<String, String> = self
+            .list_container_ids(&[prefix, "k8s_"])
+            .await?
             .into_iter()
-            .filter(|c| !c.starts_with("k8s_POD_"))
+            .filter(|(name, _)| !name.starts_with("k8s_POD_"))
             .collect();
 
+        if let Ok(mut cache) = self.stats_cache.cgroups.write() {
+            cache.retain(|name, _| containers.contains_key(name));
+        }
+
         if containers.is_empty() {
             return Ok(Vec::new());
         }
@@ -97,40 +130,25 @@ impl DockerManager {
         // Get number of CPUs for percentage calculation
         let num_cpus = num_cpus::get() as f64;
 
-        // Get full container IDs and their cgroup paths
-        let mut pod_stats: HashMap<String, ContainerStats> = HashMap::new();
-
-        for container_name in &containers {
-            // Get full container ID from Docker
-            let full_id = match self.get_container_id(container_name).await {
-                Some(id) => id,
-                None => continue,
-            };
+        // Cgroup lookups and reads are blocking file I/O; run them side by side
+        let mut reads = JoinSet::new();
+        for (container_name, full_id) in containers {
+            let cache = Arc::clone(&self.stats_cache);
+            reads.spawn_blocking(move || {
+                let cgroup_path = cgroup_path_for(&cache, &container_name, &full_id)?;
+                let stats = read_cgroup_stats(&cache, &cgroup_path, &full_id, now_usec, num_cpus);
+                Some((container_name, stats))
+            });
+        }
 
-            // Find cgroup path for this container
-            let cgroup_path = find_container_cgroup(&full_id);
-            let cgroup_path = match cgroup_path {
-                Some(p) => p,
-                None => continue,
-            };
+        let mut pod_stats: HashMap<String, ContainerStats> = HashMap::new();
 
-            // Read stats from cgroups (very fast - just file reads)
-            let (cpu_percent, cpu_limit_millicores, memory_used_mb, memory_limit_mb) =
-                read_cgroup_stats(&cgroup_path, &full_id, now_usec, num_cpus);
-
-            // Extract pod name and namespace from container name
-            let (pod_name, namespace) = if container_name.starts_with("k8s_") {
-                let parts: Vec<&str> = container_name.split('_').collect();
-                if parts.len() >= 4 {
-                    (parts[2].to_string(), parts[3].to_string())
-                } else if parts.len() >= 3 {
-                    (parts[2].to_string(), "default".to_string())
-                } else {
-                    (container_name.clone(), "default".to_string())
-                }
-            } else {
-                (container_name.clone(), "system".to_string())
+        while let Some(read) = reads.join_next().await {
+            let Ok(Some((container_name, stats))) = read else {
+                continue;
             };
+            let (cpu_percent, cpu_limit_millicores, memory_used_mb, memory_limit_mb) = stats;
+            let (pod_name, namespace) = pod_identity(&container_name);
 
             // Aggregate stats for this pod
             let key = format!("{}/{}", namespace, pod_name);
@@ -177,15 +195,48 @@ impl DockerManager {
 
         Ok(stats_list)
     }
+}
+
+/// Cgroup directory of a container, from the cache while its ID is unchanged
+fn cgroup_path_for(cache: &StatsCache, name: &str, id: &str) -> Option<PathBuf> {
+    let cached = cache
+        .cgroups
+        .read()
+        .ok()
+        .and_then(|cache| cache.get(name).cloned())
+        .filter(|cached| cached.id == id && cached.path.exists());
+    if let Some(cached) = cached {
+        return Some(cached.path);
+    }
 
-    /// Get full container ID from container name
-    pub(super) async fn get_container_id(&self, name: &str) -> Option<String> {
-        let inspect = self
-            .client
-            .inspect_container(name, None::<InspectContainerOptions>)
-            .await
-            .ok()?;
-        inspect.id
+    let path = find_container_cgroup(id)?;
+    if let Ok(mut cgroups) = cache.cgroups.write() {
+        cgroups.insert(
+            name.to_string(),
+            CachedCgroup {
+                id: id.to_string(),
+                path: path.clone(),
+            },
+        );
+    }
+    Some(path)
+}
+
+/// Pod name and namespace of a container
+/// Pod containers are named `k8s_{container}_{pod}_{namespace}_{uid}_{attempt}`;
+/// anything else is a cluster container in the "system" namespace
+fn pod_identity(container_name: &str) -> (String, String) {
+    if container_name.starts_with("k8s_") {
+        let parts: Vec<&str> = container_name.split('_').collect();
+        if parts.len() >= 4 {
+            (parts[2].to_string(), parts[3].to_string())
+        } else if parts.len() >= 3 {
+            (parts[2].to_string(), "default".to_string())
+        } else {
+            (container_name.to_string(), "default".to_string())
+        }
+    } else {
+        (container_name.to_string(), "system".to_string())
     }
 }
 
@@ -214,19 +265,25 @@ static CGROUP_VERSION: Lazy<CgroupVersion> = Lazy::new(detect_cgroup_version);
 /// Supports both cgroup v1 and v2
 /// Returns (cpu_percent, cpu_limit_millicores, memory_used_mb, memory_limit_mb)
 fn read_cgroup_stats(
+    cache: &StatsCache,
     cgroup_path: &std::path::Path,
     container_id: &str,
     now_usec: u64,
     num_cpus: f64,
 ) -> (f64, f64, f64, f64) {
     match *CGROUP_VERSION {
-        CgroupVersion::V2 => read_cgroup_v2_stats(cgroup_path, container_id, now_usec, num_cpus),
-        CgroupVersion::V1 => read_cgroup_v1_stats(cgroup_path, container_id, now_usec, num_cpus),
+        CgroupVersion::V2 => {
+            read_cgroup_v2_stats(cache, cgroup_path, container_id, now_usec, num_cpus)
+        }
+        CgroupVersion::V1 => {
+            read_cgroup_v1_stats(cache, cgroup_path, container_id, now_usec, num_cpus)
+        }
     }
 }
 
 /// Read stats from cgroup v2 files
 fn read_cgroup_v2_stats(
+    cache: &StatsCache,
     cgroup_path: &std::path::Path,
     container_id: &str,
     now_usec: u64,
@@ -270,7 +327,7 @@ fn read_cgroup_v2_stats(
         })
         .unwrap_or(0.0);
 
-    let cpu_percent = calculate_cpu_percent(container_id, usage_usec, now_usec, num_cpus);
+    let cpu_percent = calculate_cpu_percent(cache, container_id, usage_usec, now_usec, num_cpus);
 
     // Read memory stats
     let memory_used_mb = fs::read_to_string(cgroup_path.join("memory.current"))
@@ -304,6 +361,7 @@ fn read_cgroup_v2_stats(
 /// v1 splits controllers: cpu/cpuacct and memory are in separate hierarchies
 /// The `cgroup_path` here is the cpu controller path; memory path is derived
 fn read_cgroup_v1_stats(
+    cache: &StatsCache,
     cgroup_path: &std::path::Path,
     container_id: &str,
     now_usec: u64,
@@ -335,7 +393,7 @@ fn read_cgroup_v1_stats(
         0.0
     };
 
-    let cpu_percent = calculate_cpu_percent(container_id, usage_usec, now_usec, num_cpus);
+    let cpu_percent = calculate_cpu_percent(cache, container_id, usage_usec, now_usec, num_cpus);
 
     // Memory: derive memory cgroup path from cpu path
     // /sys/fs/cgroup/cpu,cpuacct/kubepods/... → /sys/fs/cgroup/memory/kubepods/...
@@ -393,9 +451,16 @@ fn derive_v1_memory_path(cpu_path: &std::path::Path) -> Option<std::path::PathBu
     }
 }
 
-/// Calculate CPU percentage using global cached delta (shared between v1 and v2)
-fn calculate_cpu_percent(container_id: &str, usage_usec: u64, now_usec: u64, num_cpus: f64) -> f64 {
-    let prev = CPU_CACHE
+/// Calculate CPU percentage using the cached delta (shared between v1 and v2)
+fn calculate_cpu_percent(
+    cache: &StatsCache,
+    container_id: &str,
+    usage_usec: u64,
+    now_usec: u64,
+    num_cpus: f64,
+) -> f64 {
+    let prev = cache
+        .cpu
         .read()
         .ok()
         .and_then(|cache| cache.get(container_id).cloned());
@@ -426,9 +491,9 @@ fn calculate_cpu_percent(container_id: &str, usage_usec: u64, now_usec: u64, num
         (0.0, 0.0)
     };
 
-    // Update global cache
-    if let Ok(mut cache) = CPU_CACHE.write() {
-        cache.insert(
+    // Update the cache
+    if let Ok(mut cpu) = cache.cpu.write() {
+        cpu.insert(
             container_id.to_string(),
             CachedCpuStats {
                 usage_usec,
@@ -713,9 +778,11 @@ impl DockerManager {
                 0.0
             };
 
-            // CPU delta using same global cache
+            // CPU delta using the same cache
             let cpu_percent = {
-                let prev = CPU_CACHE
+                let prev = self
+                    .stats_cache
+                    .cpu
                     .read()
                     .ok()
                     .and_then(|cache| cache.get(&c.id).cloned());
@@ -745,7 +812,7 @@ impl DockerManager {
                     (0.0, 0.0)
                 };
 
-                if let Ok(mut cache) = CPU_CACHE.write() {
+                if let Ok(mut cache) = self.stats_cache.cpu.write() {
                     cache.insert(
                         c.id.clone(),
                         CachedCpuStats {
@@ -812,3 +879,24 @@ fn parse_agent_output(json: &str) -> Result<AgentOutput> {
     }
     serde_json::from_str(json).context("Failed to parse agent JSON output")
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pod_identity_comes_from_the_container_name() {
+        assert_eq!(
+            pod_identity("k8s_web_web-7d9f_shop_0b1c_0"),
+            ("web-7d9f".to_string(), "shop".to_string())
+        );
+        assert_eq!(
+            pod_identity("k8s_web_web-7d9f"),
+            ("web-7d9f".to_string(), "default".to_string())
+        );
+        assert_eq!(
+            pod_identity("k3dev-server"),
+            ("k3dev-server".to_string(), "system".to_string())
+        );
+    }
+}
@@ -0,0 +1,169 @@
+//! Processes running inside a pod's containers
+//!
+//! Uses the Docker top API on the pod's `k8s_` containers, so no `ps` is
+//! needed in the workload images.
+
+use anyhow::{anyhow, Result};
+use bollard::query_parameters::TopOptions;
+
+use super::DockerManager;
+
+/// `ps` arguments for the top API; `aux` reports %CPU and %MEM
+const PS_ARGS: &str = "aux";
+
+/// One process inside a pod container
+#[derive(Debug, Clone, PartialEq)]
+pub struct PodProcess {
+    /// Kubernetes container name
+    pub container: String,
+    pub pid: String,
+    pub user: String,
+    pub cpu_percent: f64,
+    pub memory_percent: f64,
+    pub rss_kb: u64,
+    pub command: String,
+}
+
+impl DockerManager {
+    /// Processes of all running containers of a pod, busiest first
+    pub async fn pod_processes(&self, pod: &str, namespace: &str) -> Result<Vec<PodProcess>> {
+        let containers: Vec<(String, String)> = self
+            .list_containers_by_prefix("k8s_")
+            .await?
+            .into_iter()
+            .filter_map(|name| {
+                let container = pod_container_name(&name, pod, namespace)?;
+                Some((name, container))
+            })
+            .collect();
+        if containers.is_empty() {
+            return Err(anyhow!("No containers found for pod {}/{}", namespace, pod));
+        }
+
+        let mut processes = Vec::new();
+        let mut last_error = None;
+        let mut any_ok = false;
+        for (docker_name, container) in &containers {
+            let options = TopOptions {
+                ps_args: PS_ARGS.to_string(),
+            };
+            // Exited containers of earlier attempts can't be inspected
+            match self.client.top_processes(docker_name, Some(options)).await {
+                Ok(top) => {
+                    any_ok = true;
+                    processes.extend(parse_top(
+                        container,
+                        &top.titles.unwrap_or_default(),
+                        top.processes.unwrap_or_default(),
+                    ));
+                }
+                Err(e) => last_error = Some(e),
+            }
+        }
+        if !any_ok {
+            if let Some(e) = last_error {
+                return Err(anyhow!(
+                    "Failed to list processes of {}/{}: {}",
+                    namespace,
+                    pod,
+                    e
+                ));
+            }
+        }
+
+        processes.sort_by(|a, b| {
+            b.cpu_percent
+                .total_cmp(&a.cpu_percent)
+                .then_with(|| a.container.cmp(&b.container))
+        });
+        Ok(processes)
+    }
+}
+
+/// Kubernetes container name of a Docker container belonging to the pod.
+/// Names follow `k8s_{container}_{pod}_{namespace}_{uid}_{attempt}`; the
+/// pause container (`k8s_POD_...`) is skipped.
+fn pod_container_name(docker_name: &str, pod: &str, namespace: &str) -> Option<String> {
+    let parts: Vec<&str> = docker_name.split('_').collect();
+    if parts.len() < 4 || parts[0] != "k8s" || parts[1] == "POD" {
+        return None;
+    }
+    (parts[2] == pod && parts[3] == namespace).then(|| parts[1].to_string())
+}
+
+/// Turn `ps` output columns into processes, looking columns up by title so
+/// differing `ps` implementations still parse
+fn parse_top(container: &str, titles: &[String], rows: Vec<Vec<String>>) -> Vec<PodProcess> {
+    let column = |names: &[&str]| titles.iter().position(|t| names.contains(&t.as_str()));
+    let pid = column(&["PID"]);
+    let user = column(&["USER", "UID"]);
+    let cpu = column(&["%CPU"]);
+    let memory = column(&["%MEM"]);
+    let rss = column(&["RSS"]);
+    let command = column(&["COMMAND", "CMD"]);
+
+    rows.into_iter()
+        .map(|row| {
+            let text = |i: Option<usize>| i.and_then(|i| row.get(i)).cloned().unwrap_or_default();
+            let number = |i: Option<usize>| text(i).parse::<f64>().unwrap_or(0.0);
+            PodProcess {
+                container: container.to_string(),
+                pid: text(pid),
+                user: text(user),
+                cpu_percent: number(cpu),
+                memory_percent: number(memory),
+                rss_kb: text(rss).parse().unwrap_or(0),
+                command: text(command),
+            }
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn pod_containers_are_matched_by_name() {
+        let name = |n: &str| pod_container_name(n, "web-7d4f9", "apps");
+        assert_eq!(
+            name("k8s_nginx_web-7d4f9_apps_1234_0").as_deref(),
+            Some("nginx")
+        );
+        assert_eq!(name("k8s_POD_web-7d4f9_apps_1234_0"), None);
+        assert_eq!(name("k8s_nginx_web-7d4f9_default_1234_0"), None);
+        assert_eq!(name("k8s_nginx_api-5c8b2_apps_1234_0"), None);
+    }
+
+    fn strings(csv: &str) -> Vec<String> {
+        csv.split(',').map(String::from).collect()
+    }
+
+    #[test]
+    fn ps_columns_are_found_by_title() {
+        let titles = strings("USER,PID,%CPU,%MEM,VSZ,RSS,TTY,STAT,START,TIME,COMMAND");
+        let row = strings("root,4242,87.5,1.2,1000,20480,?,R,10:00,0:42,node server.js");
+
+        let processes = parse_top("app", &titles, vec![row]);
+        assert_eq!(
+            processes,
+            [PodProcess {
+                container: "app".to_string(),
+                pid: "4242".to_string(),
+                user: "root".to_string(),
+                cpu_percent: 87.5,
+                memory_percent: 1.2,
+                rss_kb: 20480,
+                command: "node server.js".to_string(),
+            }]
+        );
+
+        // `ps -ef` style output has no usage columns
+        let titles = strings("UID,PID,PPID,C,STIME,TTY,TIME,CMD");
+        let row = strings("999,1,0,0,10:00,?,00:00:01,redis-server");
+        let processes = parse_top("redis", &titles, vec![row]);
+        assert_eq!(processes[0].user, "999");
+        assert_eq!(processes[0].cpu_percent, 0.0);
+        assert_eq!(processes[0].command, "redis-server");
+    }
+}
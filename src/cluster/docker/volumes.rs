@@ -130,7 +130,7 @@ impl DockerManager {
 /// The UUID part contains hyphens but no underscores, so the first `_` after "pvc-" separates
 /// the UUID from the namespace, and the second `_` separates namespace from PVC name.
 /// PVC names may contain underscores, so we rejoin remaining parts.
-fn parse_pvc_dir_name(dir_name: &str) -> Option<(String, String)> {
+pub(super) fn parse_pvc_dir_name(dir_name: &str) -> Option<(String, String)> {
     // Split on '_' — first part is "pvc-<uuid>", second is namespace, rest is PVC name
     let parts: Vec<&str> = dir_name.splitn(3, '_').collect();
     if parts.len() < 3 {
@@ -0,0 +1,113 @@
+//! Backoff for failing ingress endpoints
+//!
+//! An endpoint that keeps failing is checked less and less often, up to a
+//! cap, with some jitter so retries don't line up. One good answer puts it
+//! back on the regular refresh.
+
+use std::collections::hash_map::RandomState;
+use std::collections::HashMap;
+use std::hash::{BuildHasher, Hasher};
+use std::time::{Duration, Instant};
+
+use super::IngressHealthStatus;
+
+/// How retry delays grow
+#[derive(Debug, Clone, Copy)]
+pub struct BackoffPolicy {
+    /// Delay after the first failure; doubled after each further one
+    pub base: Duration,
+    pub max: Duration,
+    /// Fraction the delay is randomly moved by, e.g. 0.2 for ±20%
+    pub jitter: f64,
+}
+
+impl BackoffPolicy {
+    /// Delay after `failures` failures in a row; `random` is in [0, 1)
+    pub fn delay(&self, failures: u32, random: f64) -> Duration {
+        let doublings = failures.saturating_sub(1).min(16);
+        let delay = self.base.saturating_mul(1 << doublings).min(self.max);
+        let jitter = self.jitter.clamp(0.0, 1.0);
+        delay.mul_f64(1.0 + jitter * (2.0 * random - 1.0))
+    }
+}
+
+struct Failing {
+    failures: u32,
+    retry_at: Instant,
+}
+
+/// Failing endpoints and when to check them again, keyed "host|path"
+#[derive(Default)]
+pub struct HealthBackoff {
+    failing: HashMap<String, Failing>,
+}
+
+impl HealthBackoff {
+    /// Whether an endpoint should be checked in this round
+    pub fn is_due(&self, key: &str, now: Instant) -> bool {
+        self.failing.get(key).is_none_or(|f| now >= f.retry_at)
+    }
+
+    /// Note a check result; failures push the next check further out
+    pub fn record(
+        &mut self,
+        key: &str,
+        status: IngressHealthStatus,
+        now: Instant,
+        policy: &BackoffPolicy,
+    ) {
+        if status != IngressHealthStatus::Error {
+            self.failing.remove(key);
+            return;
+        }
+        let failing = self.failing.entry(key.to_string()).or_insert(Failing {
+            failures: 0,
+            retry_at: now,
+        });
+        failing.failures += 1;
+        failing.retry_at = now + policy.delay(failing.failures, random_unit());
+    }
+
+    pub fn clear(&mut self) {
+        self.failing.clear();
+    }
+}
+
+/// A random number in [0, 1), good enough for jitter
+pub(super) fn random_unit() -> f64 {
+    let bits = RandomState::new().build_hasher().finish() >> 11;
+    bits as f64 / (1u64 << 53) as f64
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const POLICY: BackoffPolicy = BackoffPolicy {
+        base: Duration::from_secs(15),
+        max: Duration::from_secs(120),
+        jitter: 0.2,
+    };
+
+    #[test]
+    fn delays_double_up_to_the_cap_within_the_jitter() {
+        assert_eq!(POLICY.delay(1, 0.5), Duration::from_secs(15));
+        assert_eq!(POLICY.delay(3, 0.5), Duration::from_secs(60));
+        assert_eq!(POLICY.delay(10, 0.5), Duration::from_secs(120));
+        assert_eq!(POLICY.delay(1, 0.0), Duration::from_secs(12));
+        assert!(POLICY.delay(1, 0.999) < Duration::from_secs(18));
+    }
+
+    #[test]
+    fn failing_endpoints_wait_until_a_good_answer() {
+        let mut backoff = HealthBackoff::default();
+        let now = Instant::now();
+        backoff.record("a|/", IngressHealthStatus::Error, now, &POLICY);
+        assert!(!backoff.is_due("a|/", now + Duration::from_secs(5)));
+        assert!(backoff.is_due("a|/", now + Duration::from_secs(20)));
+        assert!(backoff.is_due("b|/", now));
+
+        backoff.record("a|/", IngressHealthStatus::Warning, now, &POLICY);
+        assert!(backoff.is_due("a|/", now));
+    }
+}
@@ -0,0 +1,175 @@
+//! Recent ingress health checks per endpoint
+//!
+//! Keeps the last few results with their latency, so the sidebar can show a
+//! mini history and average latency, tell an endpoint that keeps flipping
+//! between up and down from one that is simply down, and spot one that keeps
+//! answering slower than its latency budget.
+
+use std::collections::{HashMap, VecDeque};
+use std::time::Duration;
+
+use super::{HealthCheck, IngressHealthStatus};
+
+/// Checks kept per endpoint
+pub const HISTORY_LEN: usize = 10;
+
+/// Up/down changes within the history that make an endpoint flapping
+const FLAPPING_CHANGES: usize = 3;
+
+/// Latest answered checks that must all miss the budget; one slow response
+/// (a cold cache, a GC pause) is not a regression
+const OVER_BUDGET_CHECKS: usize = 3;
+
+/// How an endpoint behaved over its recent checks
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum HealthTrend {
+    /// Not enough changes to call it either way
+    Steady,
+    /// Keeps switching between up and down
+    Flapping,
+    /// Every recent check failed
+    Down,
+}
+
+/// Recent checks keyed like the checker's results ("host|path")
+#[derive(Debug, Default)]
+pub struct HealthHistory {
+    checks: HashMap<String, VecDeque<HealthCheck>>,
+}
+
+impl HealthHistory {
+    /// Append a round of results; endpoints not checked in it keep theirs
+    pub fn record(&mut self, results: &HashMap<String, HealthCheck>) {
+        for (key, check) in results {
+            let history = self.checks.entry(key.clone()).or_default();
+            if history.len() == HISTORY_LEN {
+                history.pop_front();
+            }
+            history.push_back(*check);
+        }
+    }
+
+    /// Forget endpoints that no longer exist
+    pub fn retain(&mut self, mut keep: impl FnMut(&str) -> bool) {
+        self.checks.retain(|key, _| keep(key));
+    }
+
+    pub fn clear(&mut self) {
+        self.checks.clear();
+    }
+
+    /// Checks of an endpoint, oldest first
+    pub fn checks(&self, key: &str) -> impl Iterator<Item = &HealthCheck> {
+        self.checks.get(key).into_iter().flatten()
+    }
+
+    /// Average latency of the checks that got a response
+    pub fn average_latency(&self, key: &str) -> Option<Duration> {
+        let answered: Vec<Duration> = self
+            .checks(key)
+            .filter(|c| c.status != IngressHealthStatus::Error)
+            .map(|c| c.latency)
+            .collect();
+        let count = u32::try_from(answered.len()).ok().filter(|n| *n > 0)?;
+        Some(answered.iter().sum::<Duration>() / count)
+    }
+
+    /// Whether the latest answered checks all took longer than `budget`
+    pub fn over_budget(&self, key: &str, budget: Duration) -> bool {
+        let latest: Vec<Duration> = self
+            .checks(key)
+            .filter(|c| c.status != IngressHealthStatus::Error)
+            .map(|c| c.latency)
+            .collect();
+        latest.len() >= OVER_BUDGET_CHECKS
+            && latest[latest.len() - OVER_BUDGET_CHECKS..]
+                .iter()
+                .all(|latency| *latency > budget)
+    }
+
+    pub fn trend(&self, key: &str) -> HealthTrend {
+        let down: Vec<bool> = self
+            .checks(key)
+            .map(|c| c.status == IngressHealthStatus::Error)
+            .collect();
+        let changes = down.windows(2).filter(|w| w[0] != w[1]).count();
+        if changes >= FLAPPING_CHANGES {
+            HealthTrend::Flapping
+        } else if down.len() > 1 && down.iter().all(|d| *d) {
+            HealthTrend::Down
+        } else {
+            HealthTrend::Steady
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn round(status: IngressHealthStatus, millis: u64) -> HashMap<String, HealthCheck> {
+        HashMap::from([(
+            "app.test|/".to_string(),
+            HealthCheck {
+                status,
+                latency: Duration::from_millis(millis),
+            },
+        )])
+    }
+
+    #[test]
+    fn history_is_bounded_and_averages_answered_checks() {
+        let mut history = HealthHistory::default();
+        for _ in 0..HISTORY_LEN + 5 {
+            history.record(&round(IngressHealthStatus::Healthy, 10));
+        }
+        history.record(&round(IngressHealthStatus::Error, 5000));
+        assert_eq!(history.checks("app.test|/").count(), HISTORY_LEN);
+        assert_eq!(
+            history.average_latency("app.test|/"),
+            Some(Duration::from_millis(10))
+        );
+
+        history.record(&HashMap::new());
+        assert_eq!(history.checks("app.test|/").count(), HISTORY_LEN);
+        history.retain(|key| key != "app.test|/");
+        assert_eq!(history.checks("app.test|/").count(), 0);
+    }
+
+    #[test]
+    fn over_budget_needs_consecutive_slow_answers() {
+        let budget = Duration::from_millis(100);
+        let mut history = HealthHistory::default();
+        for millis in [500, 500, 50] {
+            history.record(&round(IngressHealthStatus::Healthy, millis));
+        }
+        assert!(!history.over_budget("app.test|/", budget));
+
+        for millis in [300, 300] {
+            history.record(&round(IngressHealthStatus::Healthy, millis));
+        }
+        assert!(!history.over_budget("app.test|/", budget));
+        // Failures don't count either way
+        history.record(&round(IngressHealthStatus::Error, 5000));
+        history.record(&round(IngressHealthStatus::Warning, 300));
+        assert!(history.over_budget("app.test|/", budget));
+    }
+
+    #[test]
+    fn flapping_is_told_apart_from_down() {
+        let mut history = HealthHistory::default();
+        for _ in 0..3 {
+            history.record(&round(IngressHealthStatus::Error, 0));
+        }
+        assert_eq!(history.trend("app.test|/"), HealthTrend::Down);
+
+        for status in [
+            IngressHealthStatus::Healthy,
+            IngressHealthStatus::Error,
+            IngressHealthStatus::Warning,
+        ] {
+            history.record(&round(status, 0));
+        }
+        assert_eq!(history.trend("app.test|/"), HealthTrend::Flapping);
+    }
+}
@@ -0,0 +1,238 @@
+//! k3dev's block in /etc/hosts and its backups
+//!
+//! Each cluster's entries live between `# BEGIN k3dev <cluster>` and
+//! `# END k3dev <cluster>`; an update only ever replaces that block. A copy
+//! of the file is saved before every edit, and the latest copy can be put
+//! back with `k3dev restore-hosts` or the palette.
+
+use std::path::PathBuf;
+
+use anyhow::{bail, Context, Result};
+use tokio::sync::mpsc;
+
+use super::HostsUpdateResult;
+use crate::ui::components::OutputLine;
+
+/// Marker on every k3dev-managed hosts line
+pub(crate) const HOSTS_MARKER: &str = "# k3dev-ingress";
+
+/// Backups kept; older ones are deleted
+const MAX_BACKUPS: usize = 10;
+
+/// Get the platform-appropriate hosts file path
+pub(crate) fn hosts_file_path() -> PathBuf {
+    #[cfg(windows)]
+    {
+        // Windows: C:\Windows\System32\drivers\etc\hosts
+        if let Ok(windir) = std::env::var("SystemRoot") {
+            PathBuf::from(windir)
+                .join("System32")
+                .join("drivers")
+                .join("etc")
+                .join("hosts")
+        } else {
+            PathBuf::from(r"C:\Windows\System32\drivers\etc\hosts")
+        }
+    }
+    #[cfg(not(windows))]
+    {
+        PathBuf::from("/etc/hosts")
+    }
+}
+
+fn begin_line(cluster: &str) -> String {
+    format!("# BEGIN k3dev {}", cluster)
+}
+
+fn end_line(cluster: &str) -> String {
+    format!("# END k3dev {}", cluster)
+}
+
+/// `current` with the cluster's block replaced by `entries` (dropped when
+/// empty). Loose marker lines from before blocks existed are dropped too;
+/// other clusters' blocks and every other line are kept as they are.
+/// Fails instead of guessing when the cluster's block has no END line.
+pub(crate) fn replace_block(current: &str, cluster: &str, entries: &[String]) -> Result<String> {
+    let begin = begin_line(cluster);
+    let end = end_line(cluster);
+
+    let mut kept = Vec::new();
+    let mut in_own = false;
+    let mut in_other = false;
+    for line in current.lines() {
+        let trimmed = line.trim();
+        if in_own {
+            in_own = trimmed != end;
+            continue;
+        }
+        if trimmed == begin {
+            in_own = true;
+            continue;
+        }
+        if trimmed.starts_with("# BEGIN k3dev ") {
+            in_other = true;
+        } else if trimmed.starts_with("# END k3dev ") {
+            in_other = false;
+        } else if !in_other && line.contains(HOSTS_MARKER) {
+            continue;
+        }
+        kept.push(line);
+    }
+    if in_own {
+        bail!(
+            "'{}' has no matching '{}'; not touching the file",
+            begin,
+            end
+        );
+    }
+
+    let mut content = kept.join("\n");
+    if !content.is_empty() && !content.ends_with('\n') {
+        content.push('\n');
+    }
+    if !entries.is_empty() {
+        content.push_str(&begin);
+        content.push('\n');
+        for entry in entries {
+            content.push_str(entry);
+            content.push('\n');
+        }
+        content.push_str(&end);
+        content.push('\n');
+    }
+    Ok(content)
+}
+
+fn backup_dir() -> PathBuf {
+    dirs::data_local_dir()
+        .map(|d| d.join("k3dev").join("hosts-backups"))
+        .unwrap_or_else(|| std::env::temp_dir().join("k3dev-hosts-backups"))
+}
+
+/// Save a copy of the hosts file before editing it; keeps the latest few
+pub(crate) fn backup(content: &str) -> Result<PathBuf> {
+    let dir = backup_dir();
+    std::fs::create_dir_all(&dir).with_context(|| format!("Failed to create {}", dir.display()))?;
+    let path = dir.join(format!(
+        "hosts-{}",
+        chrono::Local::now().format("%Y%m%d-%H%M%S%.3f")
+    ));
+    std::fs::write(&path, content)
+        .with_context(|| format!("Failed to write {}", path.display()))?;
+
+    let backups = list_backups();
+    for old in backups
+        .iter()
+        .take(backups.len().saturating_sub(MAX_BACKUPS))
+    {
+        let _ = std::fs::remove_file(old);
+    }
+    Ok(path)
+}
+
+/// Backups, oldest first (the timestamped names sort by age)
+fn list_backups() -> Vec<PathBuf> {
+    let mut backups: Vec<PathBuf> = std::fs::read_dir(backup_dir())
+        .map(|entries| {
+            entries
+                .filter_map(|e| e.ok())
+                .map(|e| e.path())
+                .filter(|p| {
+                    p.file_name()
+                        .and_then(|n| n.to_str())
+                        .is_some_and(|n| n.starts_with("hosts-"))
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+    backups.sort();
+    backups
+}
+
+/// The most recent backup
+pub fn latest_backup() -> Option<PathBuf> {
+    list_backups().pop()
+}
+
+/// Put the latest backup back. Writes directly when allowed; otherwise the
+/// caller writes the `NeedsSudo` content with its sudo prompt.
+pub async fn restore_latest_backup(
+    output_tx: Option<mpsc::Sender<OutputLine>>,
+) -> Result<HostsUpdateResult> {
+    let Some(path) = latest_backup() else {
+        bail!("No hosts backup to restore");
+    };
+    let content = tokio::fs::read_to_string(&path)
+        .await
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+    let count = content
+        .lines()
+        .filter(|line| line.contains(HOSTS_MARKER))
+        .count();
+    if let Some(tx) = &output_tx {
+        let _ = tx
+            .send(OutputLine::info(format!(
+                "Restoring /etc/hosts from {}",
+                path.display()
+            )))
+            .await;
+    }
+
+    if tokio::fs::write(hosts_file_path(), &content).await.is_ok() {
+        if let Some(tx) = &output_tx {
+            let _ = tx.send(OutputLine::success("Restored /etc/hosts")).await;
+        }
+        return Ok(HostsUpdateResult::WrittenDirectly { count });
+    }
+
+    if let Some(tx) = &output_tx {
+        let _ = tx
+            .send(OutputLine::info(
+                "Requesting elevated privileges to restore /etc/hosts...",
+            ))
+            .await;
+    }
+    Ok(HostsUpdateResult::NeedsSudo { content, count })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const HOSTS: &str = "127.0.0.1 localhost
+127.0.0.1 legacy.test # k3dev-ingress
+# BEGIN k3dev other
+127.0.0.1 other.test # k3dev-ingress
+# END k3dev other
+# BEGIN k3dev dev
+127.0.0.1 old.test # k3dev-ingress
+# END k3dev dev
+::1 localhost
+";
+
+    #[test]
+    fn only_the_clusters_block_is_replaced() {
+        let entries = vec!["127.0.0.1 new.test # k3dev-ingress".to_string()];
+        let updated = replace_block(HOSTS, "dev", &entries).unwrap();
+        assert_eq!(
+            updated,
+            "127.0.0.1 localhost
+# BEGIN k3dev other
+127.0.0.1 other.test # k3dev-ingress
+# END k3dev other
+::1 localhost
+# BEGIN k3dev dev
+127.0.0.1 new.test # k3dev-ingress
+# END k3dev dev
+"
+        );
+        // Replacing again is stable
+        assert_eq!(replace_block(&updated, "dev", &entries).unwrap(), updated);
+    }
+
+    #[test]
+    fn unterminated_blocks_are_refused() {
+        let broken = "127.0.0.1 localhost\n# BEGIN k3dev dev\n127.0.0.1 a.test\n::1 localhost\n";
+        assert!(replace_block(broken, "dev", &[]).is_err());
+    }
+}
@@ -0,0 +1,431 @@
+//! Privileged helper for sudo-less /etc/hosts updates
+//!
+//! `k3dev hosts-helper install` puts a small root-owned script in place that
+//! rewrites only a cluster's k3dev block in /etc/hosts, plus a rule that lets
+//! the installing user run it without a password: a polkit rule on Linux
+//! (`pkexec`; other users still need an admin password), a sudoers drop-in
+//! on macOS (`sudo -n`). Hosts updates use it when it is installed and fall
+//! back to the interactive sudo prompt otherwise.
+//!
+//! Anything the user runs can call the helper, so it only accepts what the
+//! installing config needs: the blocks of that config's clusters (and their
+//! per-branch clusters), and names under its domain. Installing from another
+//! project adds its cluster and domain to the ones already allowed.
+
+use std::path::Path;
+use std::process::Stdio;
+
+use anyhow::{bail, Context, Result};
+use tokio::io::AsyncWriteExt;
+use tokio::process::Command;
+
+/// Where the helper script is installed
+const HELPER_PATH: &str = "/usr/local/libexec/k3dev-hosts-helper";
+
+/// polkit action for running the helper; admin authentication by default
+#[cfg(not(target_os = "macos"))]
+const POLICY_PATH: &str = "/usr/share/polkit-1/actions/dev.k3dev.hosts-helper.policy";
+
+/// polkit rule allowing the installing user to run the helper
+#[cfg(not(target_os = "macos"))]
+const POLKIT_RULE_PATH: &str = "/etc/polkit-1/rules.d/50-k3dev-hosts-helper.rules";
+
+/// Files that let the installing user run the helper
+#[cfg(not(target_os = "macos"))]
+const RULE_PATHS: [&str; 2] = [POLICY_PATH, POLKIT_RULE_PATH];
+
+/// sudoers drop-in allowing the installing user to run the helper
+#[cfg(target_os = "macos")]
+const RULE_PATHS: [&str; 1] = ["/etc/sudoers.d/k3dev-hosts-helper"];
+
+/// polkit action id of the helper
+#[cfg(not(target_os = "macos"))]
+const ACTION_ID: &str = "dev.k3dev.hosts-helper";
+
+/// Replaces the block of the cluster named by its argument in /etc/hosts
+/// with the "IP HOST... [*.DOMAIN]" lines read from stdin, the same way
+/// `hosts_file::replace_block` does. Anything that is not an address,
+/// hostnames and an optional domain tag is refused, and so are clusters and
+/// names outside `CLUSTERS` and `DOMAINS`, which [`helper_script`] fills in.
+const HELPER_SCRIPT: &str = r##"#!/bin/sh
+# k3dev hosts helper: replaces the k3dev-managed lines of /etc/hosts.
+set -euf
+MARKER="# k3dev-ingress"
+HOSTS=/etc/hosts
+CLUSTERS="@CLUSTERS@"
+DOMAINS="@DOMAINS@"
+in_domains() {
+  for d in $DOMAINS; do
+    case "$1" in "$d"|*."$d") return 0 ;; esac
+  done
+  return 1
+}
+own_cluster() {
+  for c in $CLUSTERS; do
+    case "$1" in "$c"|"$c"-*) return 0 ;; esac
+  done
+  return 1
+}
+CLUSTER=${1:-}
+case "$CLUSTER" in ""|*[!A-Za-z0-9._-]*) echo "invalid cluster: $CLUSTER" >&2; exit 2 ;; esac
+own_cluster "$CLUSTER" || { echo "cluster not allowed: $CLUSTER" >&2; exit 2; }
+tmp=$(mktemp)
+block=$(mktemp)
+trap 'rm -f "$tmp" "$block"' EXIT
+awk -v begin="# BEGIN k3dev $CLUSTER" -v end="# END k3dev $CLUSTER" -v marker="$MARKER" '
+  own { if ($0 == end) own = 0; next }
+  $0 == begin { own = 1; next }
+  /^# BEGIN k3dev / { other = 1 }
+  /^# END k3dev / { other = 0; print; next }
+  !other && index($0, marker) { next }
+  { print }
+  END { if (own) exit 3 }
+' "$HOSTS" > "$tmp" || { echo "unterminated k3dev block for $CLUSTER in $HOSTS" >&2; exit 3; }
+while read -r ip rest; do
+  [ -z "$ip" ] && continue
+  case "$ip" in *[!0-9a-fA-F.:]*) echo "invalid address: $ip" >&2; exit 2 ;; esac
+  names=""
+  tag=""
+  for name in $rest; do
+    case "$name" in
+      \*.*[!A-Za-z0-9.-]*) echo "invalid domain: $name" >&2; exit 2 ;;
+      \*.?*)
+        in_domains "${name#\*.}" || { echo "domain not allowed: $name" >&2; exit 2; }
+        tag=" $name" ;;
+      *[!A-Za-z0-9.-]*) echo "invalid host: $name" >&2; exit 2 ;;
+      *)
+        in_domains "$name" || { echo "host not allowed: $name" >&2; exit 2; }
+        names="$names $name" ;;
+    esac
+  done
+  [ -n "$names" ] || { echo "no hosts for $ip" >&2; exit 2; }
+  printf '%s%s %s%s\n' "$ip" "$names" "$MARKER" "$tag" >> "$block"
+done
+if [ -s "$block" ]; then
+  { echo "# BEGIN k3dev $CLUSTER"; cat "$block"; echo "# END k3dev $CLUSTER"; } >> "$tmp"
+fi
+cat "$tmp" > "$HOSTS"
+"##;
+
+/// Whether the helper and its rule are installed
+pub fn is_installed() -> bool {
+    Path::new(HELPER_PATH).is_file() && RULE_PATHS.iter().all(|p| Path::new(p).is_file())
+}
+
+/// Replace a cluster's hosts block through the helper, without prompting.
+/// Each line is `IP HOST... [*.DOMAIN]`.
+pub async fn write_entries(cluster: &str, lines: &[String]) -> Result<()> {
+    let mut cmd = helper_command();
+    cmd.arg(cluster);
+    cmd.stdin(Stdio::piped())
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped());
+    let mut child = cmd.spawn().context("Failed to start the hosts helper")?;
+
+    let mut input = lines.join("\n");
+    input.push('\n');
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin.write_all(input.as_bytes()).await?;
+    }
+
+    let output = child.wait_with_output().await?;
+    if !output.status.success() {
+        bail!(
+            "hosts helper failed: {}",
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(())
+}
+
+#[cfg(not(target_os = "macos"))]
+fn helper_command() -> Command {
+    let mut cmd = Command::new("pkexec");
+    cmd.args(["--disable-internal-agent", HELPER_PATH]);
+    cmd
+}
+
+#[cfg(target_os = "macos")]
+fn helper_command() -> Command {
+    let mut cmd = Command::new("sudo");
+    cmd.args(["-n", HELPER_PATH]);
+    cmd
+}
+
+/// Content of each of [`RULE_PATHS`] for `user`
+#[cfg(not(target_os = "macos"))]
+fn rules(user: &str) -> Vec<String> {
+    let policy = format!(
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<!DOCTYPE policyconfig PUBLIC "-//freedesktop//DTD PolicyKit Policy Configuration 1.0//EN"
+ "http://www.freedesktop.org/standards/PolicyKit/1/policyconfig.dtd">
+<policyconfig>
+  <action id="{}">
+    <description>Update the k3dev entries in /etc/hosts</description>
+    <message>k3dev wants to update its entries in /etc/hosts</message>
+    <defaults>
+      <allow_any>auth_admin</allow_any>
+      <allow_inactive>auth_admin</allow_inactive>
+      <allow_active>auth_admin</allow_active>
+    </defaults>
+    <annotate key="org.freedesktop.policykit.exec.path">{}</annotate>
+  </action>
+</policyconfig>
+"#,
+        ACTION_ID, HELPER_PATH
+    );
+    let rule = format!(
+        r#"// Installed by `k3dev hosts-helper install`
+polkit.addRule(function(action, subject) {{
+    if (action.id == "{}" && subject.user == "{}") {{
+        return polkit.Result.YES;
+    }}
+}});
+"#,
+        ACTION_ID, user
+    );
+    vec![policy, rule]
+}
+
+#[cfg(target_os = "macos")]
+fn rules(user: &str) -> Vec<String> {
+    vec![format!("{} ALL=(root) NOPASSWD: {}\n", user, HELPER_PATH)]
+}
+
+/// Refuse user names that could break out of the rule they're written into
+fn check_user(user: &str) -> Result<()> {
+    let valid = !user.is_empty()
+        && !user.starts_with('-')
+        && user
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '_' | '-'));
+    if !valid {
+        bail!("Unsupported user name: {}", user);
+    }
+    Ok(())
+}
+
+/// Single-label domains that never resolve publicly
+const LOCAL_TLDS: [&str; 4] = ["local", "localhost", "test", "internal"];
+
+/// Refuse domains the helper shouldn't be able to take over, e.g. `com`
+fn check_domain(domain: &str) -> Result<()> {
+    let valid = !domain.is_empty()
+        && domain
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '.' || c == '-')
+        && !domain.starts_with(['.', '-'])
+        && !domain.ends_with('.');
+    if !valid {
+        bail!("Invalid domain: {}", domain);
+    }
+    if !domain.contains('.') && !LOCAL_TLDS.contains(&domain) {
+        bail!(
+            "Domain {} is a top-level domain; use a name below it, e.g. local.{}",
+            domain,
+            domain
+        );
+    }
+    Ok(())
+}
+
+/// The helper script allowing `clusters` and names under `domains`
+fn helper_script(clusters: &[String], domains: &[String]) -> String {
+    HELPER_SCRIPT
+        .replace("@CLUSTERS@", &clusters.join(" "))
+        .replace("@DOMAINS@", &domains.join(" "))
+}
+
+/// Clusters and domains an installed helper script allows
+fn allowed_scope(script: &str) -> (Vec<String>, Vec<String>) {
+    let list = |key: &str| {
+        script
+            .lines()
+            .find_map(|line| line.strip_prefix(key))
+            .map(|rest| rest.trim_end_matches('"'))
+            .map(|rest| rest.split_whitespace().map(str::to_string).collect())
+            .unwrap_or_default()
+    };
+    (list("CLUSTERS=\""), list("DOMAINS=\""))
+}
+
+/// Clusters and domains the installed helper allows; empty when it isn't installed
+pub fn installed_scope() -> (Vec<String>, Vec<String>) {
+    std::fs::read_to_string(HELPER_PATH)
+        .map(|script| allowed_scope(&script))
+        .unwrap_or_default()
+}
+
+/// Install the helper and its rule for `cluster` (the base name, without a
+/// branch suffix) and names under `domain`, asking for the sudo password once
+pub fn install(cluster: &str, domain: &str) -> Result<()> {
+    if cluster.is_empty()
+        || !cluster
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || matches!(c, '.' | '_' | '-'))
+    {
+        bail!("Invalid cluster name: {}", cluster);
+    }
+    check_domain(domain)?;
+    let user = std::env::var("SUDO_USER")
+        .or_else(|_| std::env::var("USER"))
+        .context("Cannot tell the current user (USER is not set)")?;
+    check_user(&user)?;
+
+    let (mut clusters, mut domains) = installed_scope();
+    if !clusters.iter().any(|c| c == cluster) {
+        clusters.push(cluster.to_string());
+    }
+    if !domains.iter().any(|d| d == domain) {
+        domains.push(domain.to_string());
+    }
+
+    // A private directory with a random name: nobody else can plant or swap
+    // the files root installs from it. Removed when dropped.
+    let staging = tempfile::Builder::new()
+        .prefix("k3dev-hosts-helper-")
+        .tempdir()
+        .context("Failed to create a staging directory")?;
+    let helper = staging.path().join("helper");
+    write_staged(&helper, &helper_script(&clusters, &domains))?;
+    let mut rule_files = Vec::new();
+    for (i, content) in rules(&user).iter().enumerate() {
+        let rule_file = staging.path().join(format!("rule-{}", i));
+        write_staged(&rule_file, content)?;
+        rule_files.push(rule_file);
+    }
+    install_staged(&helper, &rule_files)
+}
+
+/// Create `path`, failing if anything is there already
+fn write_staged(path: &Path, content: &str) -> Result<()> {
+    use std::io::Write;
+
+    let mut options = std::fs::OpenOptions::new();
+    options.write(true).create_new(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        options.mode(0o600);
+    }
+    options
+        .open(path)
+        .and_then(|mut file| file.write_all(content.as_bytes()))
+        .with_context(|| format!("Failed to write {}", path.display()))
+}
+
+/// The staged file is still the regular file this user wrote
+fn check_staged(path: &Path) -> Result<()> {
+    let metadata = std::fs::symlink_metadata(path)
+        .with_context(|| format!("Failed to read {}", path.display()))?;
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::MetadataExt;
+        if metadata.uid() != nix::unistd::getuid().as_raw() {
+            bail!("{} was replaced by another user", path.display());
+        }
+    }
+    if !metadata.is_file() {
+        bail!("{} is not a regular file", path.display());
+    }
+    Ok(())
+}
+
+fn install_staged(helper: &Path, rule_files: &[std::path::PathBuf]) -> Result<()> {
+    check_staged(helper)?;
+    for rule_file in rule_files {
+        check_staged(rule_file)?;
+    }
+    // sudoers files are only accepted with restrictive permissions
+    let rule_mode = if cfg!(target_os = "macos") {
+        "0440"
+    } else {
+        "0644"
+    };
+    let dirs: Vec<&Path> = installed_paths()
+        .into_iter()
+        .filter_map(|p| Path::new(p).parent())
+        .collect();
+    sudo(&["mkdir", "-p"], &dirs)?;
+    sudo(
+        &["install", "-o", "root", "-m", "0755"],
+        &[helper, Path::new(HELPER_PATH)],
+    )?;
+    for (rule_file, path) in rule_files.iter().zip(RULE_PATHS) {
+        #[cfg(target_os = "macos")]
+        sudo(&["visudo", "-cf"], &[rule_file])?;
+        sudo(
+            &["install", "-o", "root", "-m", rule_mode],
+            &[rule_file, Path::new(path)],
+        )?;
+    }
+    Ok(())
+}
+
+/// Remove the helper and its rule
+pub fn uninstall() -> Result<()> {
+    let paths: Vec<&Path> = installed_paths().into_iter().map(Path::new).collect();
+    sudo(&["rm", "-f"], &paths)
+}
+
+/// Run a command under sudo on the terminal, so it can ask for the password
+fn sudo(args: &[&str], paths: &[&Path]) -> Result<()> {
+    let status = std::process::Command::new("sudo")
+        .args(args)
+        .args(paths)
+        .status()
+        .context("Failed to run sudo")?;
+    if !status.success() {
+        bail!("sudo {} failed", args.join(" "));
+    }
+    Ok(())
+}
+
+/// Where the helper and its rule live, for status output
+pub fn installed_paths() -> Vec<&'static str> {
+    let mut paths = vec![HELPER_PATH];
+    paths.extend(RULE_PATHS);
+    paths
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn installed_scope_round_trips() {
+        let clusters = vec!["k3dev".to_string(), "shop".to_string()];
+        let domains = vec!["local.k8s.dev".to_string(), "shop.test".to_string()];
+        let script = helper_script(&clusters, &domains);
+        assert!(!script.contains('@'));
+        assert_eq!(allowed_scope(&script), (clusters, domains));
+        assert_eq!(allowed_scope("#!/bin/sh\n"), (Vec::new(), Vec::new()));
+    }
+
+    #[test]
+    fn public_domains_are_refused() {
+        assert!(check_domain("local.k8s.dev").is_ok());
+        assert!(check_domain("test").is_ok());
+        assert!(check_domain("com").is_err());
+        assert!(check_domain("dev; rm -rf /").is_err());
+        assert!(check_domain(".example.com").is_err());
+    }
+
+    #[test]
+    fn rules_allow_only_the_installing_user() {
+        let rules = rules("alice");
+        assert_eq!(rules.len(), RULE_PATHS.len());
+        #[cfg(not(target_os = "macos"))]
+        {
+            assert!(!rules[0].contains("<allow_active>yes"));
+            assert!(rules[1].contains(r#"subject.user == "alice""#));
+            assert!(rules[1].contains(ACTION_ID));
+        }
+        #[cfg(target_os = "macos")]
+        assert!(rules[0].starts_with("alice ALL=(root) NOPASSWD: "));
+
+        assert!(check_user("alice").is_ok());
+        assert!(check_user(r#"a" || true || ""#).is_err());
+        assert!(check_user("-x").is_err());
+    }
+}
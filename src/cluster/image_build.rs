@@ -0,0 +1,546 @@
+//! Image builds with BuildKit
+//!
+//! Builds from `builds:` run `docker buildx build` with raw JSON progress,
+//! so the BuildKit features of the CLI (inline cache, `--secret`, `--ssh`
+//! forwarding, `RUN --mount=type=cache`) work as they do in a terminal. The
+//! progress stream is folded into steps grouped by Dockerfile stage for the
+//! build panel, and into `#N` lines like `--progress=plain` for the job's
+//! output. k3s runs pods on the host's Docker daemon, so the image loaded
+//! there can be used right away.
+
+use std::collections::{HashMap, VecDeque};
+use std::path::Path;
+use std::process::Stdio;
+use std::time::{Duration, Instant};
+
+use anyhow::{anyhow, Context, Result};
+use base64::Engine;
+use chrono::{DateTime, Utc};
+use serde::Deserialize;
+use tokio::io::{AsyncBufReadExt, BufReader};
+use tokio::process::Command;
+use tokio::sync::mpsc;
+
+use crate::app::{AppMessage, JobId};
+use crate::config::{expand_home, BuildConfig};
+use crate::ui::components::OutputLine;
+
+/// Log lines kept for the build panel
+const RECENT_LOGS: usize = 8;
+
+/// Least time between two progress updates sent to the UI
+const PROGRESS_INTERVAL: Duration = Duration::from_millis(100);
+
+/// Steps without a `[stage n/m]` prefix, e.g. "exporting to image"
+const OTHER_STAGE: &str = "other";
+
+/// One solve status line of `--progress=rawjson`
+#[derive(Debug, Default, Deserialize)]
+struct SolveStatus {
+    #[serde(default)]
+    vertexes: Vec<Vertex>,
+    #[serde(default)]
+    logs: Vec<VertexLog>,
+}
+
+#[derive(Debug, Deserialize)]
+struct Vertex {
+    digest: String,
+    #[serde(default)]
+    name: String,
+    #[serde(default, deserialize_with = "timestamp")]
+    started: Option<DateTime<Utc>>,
+    #[serde(default, deserialize_with = "timestamp")]
+    completed: Option<DateTime<Utc>>,
+    #[serde(default)]
+    cached: bool,
+    #[serde(default)]
+    error: String,
+}
+
+#[derive(Debug, Deserialize)]
+struct VertexLog {
+    vertex: String,
+    /// Base64 encoded output
+    #[serde(default)]
+    msg: String,
+}
+
+/// BuildKit versions write timestamps as RFC 3339 strings or as protobuf
+/// `{seconds, nanos}` objects
+fn timestamp<'de, D>(deserializer: D) -> std::result::Result<Option<DateTime<Utc>>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Timestamp {
+        Text(String),
+        Proto {
+            #[serde(default)]
+            seconds: i64,
+            #[serde(default)]
+            nanos: u32,
+        },
+    }
+    Ok(match Option::<Timestamp>::deserialize(deserializer)? {
+        Some(Timestamp::Text(text)) => DateTime::parse_from_rfc3339(&text)
+            .ok()
+            .map(|t| t.with_timezone(&Utc)),
+        Some(Timestamp::Proto { seconds, nanos }) => DateTime::from_timestamp(seconds, nanos),
+        None => None,
+    })
+}
+
+/// A vertex of the build graph, numbered in the order it appeared
+#[derive(Debug, Clone, PartialEq)]
+pub struct BuildStep {
+    pub number: usize,
+    /// "[build 2/5] RUN cargo build"
+    pub name: String,
+    pub stage: String,
+    pub started: Option<DateTime<Utc>>,
+    pub completed: Option<DateTime<Utc>>,
+    pub cached: bool,
+    pub error: Option<String>,
+}
+
+/// Steps of one Dockerfile stage
+#[derive(Debug, Clone, PartialEq)]
+pub struct BuildStage {
+    pub name: String,
+    pub done: usize,
+    /// From the `n/m` prefix where there is one, else the steps seen so far
+    pub total: usize,
+    /// Every finished step came from the cache
+    pub cached: bool,
+    /// The step running now
+    pub running: Option<BuildStep>,
+    pub error: Option<String>,
+    /// First start to last completion
+    pub elapsed: Option<Duration>,
+}
+
+/// What the build panel shows
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct BuildProgress {
+    pub stages: Vec<BuildStage>,
+    /// Last lines any step printed
+    pub recent_logs: Vec<String>,
+}
+
+/// Steps seen so far in a build's progress stream
+#[derive(Debug, Default)]
+pub struct BuildTracker {
+    steps: Vec<BuildStep>,
+    by_digest: HashMap<String, usize>,
+    recent_logs: VecDeque<String>,
+}
+
+impl BuildTracker {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Fold one rawjson line in; returns the output lines it produces, or
+    /// None when the line is not progress (e.g. buildx's final "ERROR: ...")
+    pub fn apply_line(&mut self, line: &str) -> Option<Vec<OutputLine>> {
+        let status: SolveStatus = serde_json::from_str(line).ok()?;
+        let mut out = Vec::new();
+        for vertex in status.vertexes {
+            out.extend(self.apply_vertex(vertex));
+        }
+        for log in status.logs {
+            let Some(&index) = self.by_digest.get(&log.vertex) else {
+                continue;
+            };
+            let number = self.steps[index].number;
+            let data = base64::engine::general_purpose::STANDARD
+                .decode(log.msg.as_bytes())
+                .unwrap_or_default();
+            for text in String::from_utf8_lossy(&data).lines() {
+                let text = text.trim_end();
+                if text.is_empty() {
+                    continue;
+                }
+                if self.recent_logs.len() == RECENT_LOGS {
+                    self.recent_logs.pop_front();
+                }
+                self.recent_logs.push_back(text.to_string());
+                out.push(OutputLine::info(format!("#{} {}", number, text)));
+            }
+        }
+        Some(out)
+    }
+
+    fn apply_vertex(&mut self, vertex: Vertex) -> Vec<OutputLine> {
+        let index = match self.by_digest.get(&vertex.digest) {
+            Some(&index) => index,
+            None => {
+                let index = self.steps.len();
+                self.steps.push(BuildStep {
+                    number: index + 1,
+                    stage: stage_of(&vertex.name).0,
+                    name: vertex.name.clone(),
+                    started: None,
+                    completed: None,
+                    cached: false,
+                    error: None,
+                });
+                self.by_digest.insert(vertex.digest.clone(), index);
+                index
+            }
+        };
+        let step = &mut self.steps[index];
+        let mut out = Vec::new();
+        if step.started.is_none() && vertex.started.is_some() {
+            out.push(OutputLine::info(format!("#{} {}", step.number, step.name)));
+        }
+        step.started = step.started.or(vertex.started);
+        if !vertex.error.is_empty() && step.error.is_none() {
+            out.push(OutputLine::error(format!(
+                "#{} ERROR: {}",
+                step.number, vertex.error
+            )));
+            step.error = Some(vertex.error);
+        }
+        if step.completed.is_none() && vertex.completed.is_some() {
+            step.completed = vertex.completed;
+            step.cached = vertex.cached;
+            if step.error.is_none() {
+                let done = if vertex.cached {
+                    "CACHED".to_string()
+                } else {
+                    match step_duration(step) {
+                        Some(d) => format!("DONE {:.1}s", d.as_secs_f64()),
+                        None => "DONE".to_string(),
+                    }
+                };
+                out.push(OutputLine::info(format!("#{} {}", step.number, done)));
+            }
+        }
+        out
+    }
+
+    /// Steps grouped by stage, in the order the stages appeared
+    pub fn progress(&self) -> BuildProgress {
+        let mut stages: Vec<BuildStage> = Vec::new();
+        // First start and last completion of each stage
+        type Span = (Option<DateTime<Utc>>, Option<DateTime<Utc>>);
+        let mut spans: Vec<Span> = Vec::new();
+        for step in &self.steps {
+            let position = match stages.iter().position(|s| s.name == step.stage) {
+                Some(position) => position,
+                None => {
+                    stages.push(BuildStage {
+                        name: step.stage.clone(),
+                        done: 0,
+                        total: 0,
+                        cached: true,
+                        running: None,
+                        error: None,
+                        elapsed: None,
+                    });
+                    spans.push((None, None));
+                    stages.len() - 1
+                }
+            };
+            let stage = &mut stages[position];
+            let declared = stage_of(&step.name).1;
+            stage.total = stage.total.max(declared.unwrap_or(0));
+            if declared.is_none() {
+                stage.total += 1;
+            }
+            if step.completed.is_some() {
+                stage.done += 1;
+                stage.cached &= step.cached;
+            } else if step.started.is_some() && stage.running.is_none() {
+                stage.running = Some(step.clone());
+            }
+            if stage.error.is_none() {
+                stage.error = step.error.clone();
+            }
+            let span = &mut spans[position];
+            span.0 = match (span.0, step.started) {
+                (Some(a), Some(b)) => Some(a.min(b)),
+                (a, b) => a.or(b),
+            };
+            span.1 = span.1.max(step.completed);
+        }
+        for (stage, (start, end)) in stages.iter_mut().zip(spans) {
+            stage.total = stage.total.max(stage.done);
+            if stage.done == 0 {
+                stage.cached = false;
+            }
+            if stage.done == stage.total && stage.running.is_none() {
+                stage.elapsed = start.zip(end).and_then(|(s, e)| (e - s).to_std().ok());
+            }
+        }
+        BuildProgress {
+            stages,
+            recent_logs: self.recent_logs.iter().cloned().collect(),
+        }
+    }
+}
+
+fn step_duration(step: &BuildStep) -> Option<Duration> {
+    let (started, completed) = step.started.zip(step.completed)?;
+    (completed - started).to_std().ok()
+}
+
+/// Stage of a vertex name and the step count its prefix declares:
+/// "[build 2/5] RUN make" is ("build", Some(5)), "[internal] load .dockerignore"
+/// is ("internal", None)
+fn stage_of(name: &str) -> (String, Option<usize>) {
+    let Some(prefix) = name
+        .strip_prefix('[')
+        .and_then(|rest| rest.split_once(']'))
+        .map(|(prefix, _)| prefix.trim())
+    else {
+        return (OTHER_STAGE.to_string(), None);
+    };
+    let mut words = prefix.split_whitespace();
+    let stage = words.next().unwrap_or(OTHER_STAGE).to_string();
+    let total = words
+        .next()
+        .and_then(|count| count.split_once('/'))
+        .and_then(|(_, total)| total.parse().ok());
+    (stage, total)
+}
+
+/// Arguments to `docker` building `build`
+pub fn buildx_args(build: &BuildConfig) -> Vec<String> {
+    let context = expand(&build.context);
+    let mut args: Vec<String> = [
+        "buildx",
+        "build",
+        "--progress=rawjson",
+        "--load",
+        "--tag",
+        build.tag.as_str(),
+    ]
+    .iter()
+    .map(|s| s.to_string())
+    .collect();
+    if let Some(dockerfile) = &build.dockerfile {
+        args.push("--file".to_string());
+        args.push(Path::new(&context).join(dockerfile).display().to_string());
+    }
+    if let Some(target) = &build.target {
+        args.push("--target".to_string());
+        args.push(target.clone());
+    }
+    for (key, value) in &build.args {
+        args.push("--build-arg".to_string());
+        args.push(format!("{}={}", key, value));
+    }
+    for secret in &build.secrets {
+        args.push("--secret".to_string());
+        args.push(expand_secret(secret));
+    }
+    for ssh in &build.ssh {
+        args.push("--ssh".to_string());
+        args.push(ssh.clone());
+    }
+    for image in &build.cache_from {
+        args.push("--cache-from".to_string());
+        args.push(format!("type=registry,ref={}", image));
+    }
+    if build.inline_cache {
+        args.push("--cache-to".to_string());
+        args.push("type=inline".to_string());
+    }
+    args.push(context);
+    args
+}
+
+/// `~` expanded; the CLI gets no shell to do it
+fn expand(path: &str) -> String {
+    expand_home(Path::new(path))
+        .map(|p| p.display().to_string())
+        .unwrap_or_else(|_| path.to_string())
+}
+
+/// A `--secret` spec with `~` expanded in its `src=`/`source=` path
+fn expand_secret(spec: &str) -> String {
+    spec.split(',')
+        .map(|part| match part.split_once('=') {
+            Some((key @ ("src" | "source"), path)) => format!("{}={}", key, expand(path)),
+            _ => part.to_string(),
+        })
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+/// Run `build`, streaming output lines to `output_tx` and stage progress to
+/// the build panel as `AppMessage::BuildProgress` for `job`
+pub async fn run_build(
+    build: &BuildConfig,
+    job: JobId,
+    output_tx: &mpsc::Sender<OutputLine>,
+    message_tx: &mpsc::Sender<AppMessage>,
+) -> Result<()> {
+    let args = buildx_args(build);
+    let _ = output_tx
+        .send(OutputLine::info(format!("$ docker {}", args.join(" "))))
+        .await;
+    let mut child = Command::new("docker")
+        .args(&args)
+        .env("DOCKER_BUILDKIT", "1")
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        // A cancelled job drops this future; take the build down with it
+        .kill_on_drop(true)
+        .spawn()
+        .context("Failed to run docker; is the Docker CLI installed?")?;
+
+    let stdout = child.stdout.take();
+    let stdout_tx = output_tx.clone();
+    let stdout_handle = tokio::spawn(async move {
+        if let Some(out) = stdout {
+            let mut reader = BufReader::new(out).lines();
+            while let Ok(Some(line)) = reader.next_line().await {
+                let _ = stdout_tx.send(OutputLine::info(line)).await;
+            }
+        }
+    });
+
+    // rawjson progress goes to stderr, next to buildx's own messages
+    let mut tracker = BuildTracker::new();
+    let mut last_sent: Option<Instant> = None;
+    if let Some(err) = child.stderr.take() {
+        let mut reader = BufReader::new(err).lines();
+        while let Ok(Some(line)) = reader.next_line().await {
+            let lines = match tracker.apply_line(&line) {
+                Some(lines) => lines,
+                None if line.trim().is_empty() => continue,
+                None => vec![OutputLine::error(line)],
+            };
+            for line in lines {
+                let _ = output_tx.send(line).await;
+            }
+            if last_sent.is_none_or(|t| t.elapsed() >= PROGRESS_INTERVAL) {
+                last_sent = Some(Instant::now());
+                let _ = message_tx
+                    .send(AppMessage::BuildProgress {
+                        job,
+                        progress: tracker.progress(),
+                    })
+                    .await;
+            }
+        }
+    }
+    let _ = message_tx
+        .send(AppMessage::BuildProgress {
+            job,
+            progress: tracker.progress(),
+        })
+        .await;
+
+    let status = child.wait().await.context("Failed to wait for docker")?;
+    let _ = stdout_handle.await;
+    if !status.success() {
+        return Err(anyhow!(
+            "docker buildx build exited with {}",
+            status.code().unwrap_or(-1)
+        ));
+    }
+    let _ = output_tx
+        .send(OutputLine::success(format!(
+            "Built {}; pods can use it with imagePullPolicy: IfNotPresent",
+            build.tag
+        )))
+        .await;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn build() -> BuildConfig {
+        BuildConfig {
+            name: "api".to_string(),
+            context: "/src/api".to_string(),
+            dockerfile: Some("Dockerfile.dev".to_string()),
+            tag: "api:dev".to_string(),
+            target: Some("dev".to_string()),
+            args: [("A".to_string(), "1".to_string())].into_iter().collect(),
+            secrets: vec!["id=npmrc,src=/home/me/.npmrc".to_string()],
+            ssh: vec!["default".to_string()],
+            cache_from: vec!["registry.example.com/api:main".to_string()],
+            inline_cache: true,
+        }
+    }
+
+    #[test]
+    fn args_cover_buildkit_options() {
+        assert_eq!(
+            buildx_args(&build()).join(" "),
+            "buildx build --progress=rawjson --load --tag api:dev \
+             --file /src/api/Dockerfile.dev --target dev --build-arg A=1 \
+             --secret id=npmrc,src=/home/me/.npmrc --ssh default \
+             --cache-from type=registry,ref=registry.example.com/api:main \
+             --cache-to type=inline /src/api"
+        );
+    }
+
+    #[test]
+    fn stages_come_from_vertex_names() {
+        assert_eq!(
+            stage_of("[build 2/5] RUN make"),
+            ("build".to_string(), Some(5))
+        );
+        assert_eq!(
+            stage_of("[internal] load .dockerignore"),
+            ("internal".to_string(), None)
+        );
+        assert_eq!(
+            stage_of("exporting to image"),
+            (OTHER_STAGE.to_string(), None)
+        );
+    }
+
+    #[test]
+    fn progress_groups_steps_by_stage() {
+        let mut tracker = BuildTracker::new();
+        let lines = [
+            r#"{"vertexes":[{"digest":"a","name":"[internal] load build definition","started":"2026-10-16T10:00:00Z","completed":"2026-10-16T10:00:01Z"}]}"#,
+            r#"{"vertexes":[{"digest":"b","name":"[build 1/2] FROM rust","started":"2026-10-16T10:00:01Z","completed":"2026-10-16T10:00:01Z","cached":true}]}"#,
+            r#"{"vertexes":[{"digest":"c","name":"[build 2/2] RUN cargo build","started":{"seconds":1792144802,"nanos":0}}]}"#,
+            r#"{"logs":[{"vertex":"c","stream":1,"msg":"Q29tcGlsaW5nIGFwaQo="}]}"#,
+        ];
+        let mut output = Vec::new();
+        for line in lines {
+            output.extend(tracker.apply_line(line).unwrap());
+        }
+        assert!(tracker.apply_line("ERROR: failed to solve").is_none());
+
+        let contents: Vec<&str> = output.iter().map(|l| l.content.as_str()).collect();
+        assert_eq!(
+            contents,
+            [
+                "#1 [internal] load build definition",
+                "#1 DONE 1.0s",
+                "#2 [build 1/2] FROM rust",
+                "#2 CACHED",
+                "#3 [build 2/2] RUN cargo build",
+                "#3 Compiling api",
+            ]
+        );
+
+        let progress = tracker.progress();
+        assert_eq!(progress.recent_logs, ["Compiling api"]);
+        let internal = &progress.stages[0];
+        assert_eq!((internal.done, internal.total), (1, 1));
+        assert_eq!(internal.elapsed, Some(Duration::from_secs(1)));
+        let build = &progress.stages[1];
+        assert_eq!((build.done, build.total), (1, 2));
+        assert!(build.cached);
+        assert_eq!(
+            build.running.as_ref().map(|s| s.name.as_str()),
+            Some("[build 2/2] RUN cargo build")
+        );
+        assert_eq!(build.elapsed, None);
+    }
+}
@@ -0,0 +1,287 @@
+//! Cluster info snapshot shown by the Info view and `k3dev info`
+
+use std::collections::BTreeMap;
+
+use super::kube_ops::{DeploymentInfo, NodeInfo};
+use super::{ClusterConfig, ClusterStatus};
+
+/// Everything the Info view shows, gathered in one pass
+#[derive(Debug, Clone)]
+pub struct ClusterInfo {
+    pub status: ClusterStatus,
+    /// API server version (e.g. "v1.31")
+    pub version: Option<String>,
+    pub container: String,
+    pub image: String,
+    pub domain: String,
+    pub nodes: Vec<NodeInfo>,
+    pub namespaces: Vec<NamespaceSummary>,
+    /// kube-system Deployments (CoreDNS, Traefik, metrics-server, ...)
+    pub components: Vec<DeploymentInfo>,
+    pub ports: Vec<PortMapping>,
+}
+
+/// Pod counts of one namespace
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NamespaceSummary {
+    pub name: String,
+    pub pods: usize,
+    pub running: usize,
+    /// New pods get a mesh sidecar
+    pub injection: bool,
+    /// Pods running a mesh proxy
+    pub meshed: usize,
+}
+
+impl NamespaceSummary {
+    /// "on 2/3" style mesh column; empty when the namespace is not meshed
+    pub fn mesh_label(&self) -> String {
+        match (self.injection, self.meshed) {
+            (false, 0) => String::new(),
+            (true, meshed) => format!("on {}/{}", meshed, self.pods),
+            (false, meshed) => format!("off {}/{}", meshed, self.pods),
+        }
+    }
+}
+
+/// A host port published by the cluster container
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PortMapping {
+    pub host: u16,
+    pub container: u16,
+    pub purpose: &'static str,
+}
+
+impl ClusterInfo {
+    /// Config-derived fields only; the Kubernetes fields are filled in when
+    /// the cluster is running
+    pub fn new(config: &ClusterConfig, status: ClusterStatus) -> Self {
+        let mut ports = vec![
+            PortMapping {
+                host: config.api_port,
+                container: ClusterConfig::K3S_API_PORT,
+                purpose: "Kubernetes API",
+            },
+            PortMapping {
+                host: config.http_port,
+                container: config.http_port,
+                purpose: "HTTP ingress",
+            },
+            PortMapping {
+                host: config.https_port,
+                container: config.https_port,
+                purpose: "HTTPS ingress",
+            },
+        ];
+        ports.extend(
+            config
+                .additional_ports
+                .iter()
+                .map(|&(host, container)| PortMapping {
+                    host,
+                    container,
+                    purpose: "Additional",
+                }),
+        );
+
+        Self {
+            status,
+            version: None,
+            container: config.container_name.clone(),
+            image: config.k3s_image(),
+            domain: config.domain.clone(),
+            nodes: Vec::new(),
+            namespaces: Vec::new(),
+            components: Vec::new(),
+            ports,
+        }
+    }
+
+    /// Record the host port the running container publishes the API on,
+    /// which differs from `api_port` when that was taken at creation
+    pub fn set_api_host_port(&mut self, port: u16) {
+        if let Some(api) = self
+            .ports
+            .iter_mut()
+            .find(|p| p.purpose == "Kubernetes API")
+        {
+            if api.host != port {
+                api.host = port;
+                api.purpose = "Kubernetes API (remapped, port was in use)";
+            }
+        }
+    }
+
+    /// (ready, total) nodes
+    pub fn ready_nodes(&self) -> (usize, usize) {
+        let ready = self.nodes.iter().filter(|n| n.status == "Ready").count();
+        (ready, self.nodes.len())
+    }
+
+    /// (running, total) pods across all namespaces
+    pub fn running_pods(&self) -> (usize, usize) {
+        self.namespaces.iter().fold((0, 0), |(running, total), ns| {
+            (running + ns.running, total + ns.pods)
+        })
+    }
+
+    /// Whether any namespace is in a service mesh
+    pub fn has_mesh(&self) -> bool {
+        self.namespaces
+            .iter()
+            .any(|ns| ns.injection || ns.meshed > 0)
+    }
+
+    /// Plain-text rendering for the headless `info` command
+    pub fn to_lines(&self) -> Vec<String> {
+        let mut lines = vec![
+            "=== K3s Cluster Info ===".to_string(),
+            format!("Status: {:?}", self.status),
+            format!("Container: {} ({})", self.container, self.image),
+            format!("Domain: {}", self.domain),
+        ];
+        if self.status != ClusterStatus::Running {
+            return lines;
+        }
+        if let Some(version) = &self.version {
+            lines.push(format!("Server Version: {}", version));
+        }
+
+        lines.push("\n=== Nodes ===".to_string());
+        lines.push(format!(
+            "{:<20} {:<10} {:<15} {:<15} {}",
+            "NAME", "STATUS", "ROLES", "INTERNAL-IP", "VERSION"
+        ));
+        lines.extend(self.nodes.iter().map(NodeInfo::to_wide_string));
+
+        lines.push("\n=== Namespaces ===".to_string());
+        let mesh = self.has_mesh();
+        lines.push(format!(
+            "{:<30} {:<6} {:<8} {}",
+            "NAMESPACE",
+            "PODS",
+            "RUNNING",
+            if mesh { "MESH" } else { "" }
+        ));
+        lines.extend(self.namespaces.iter().map(|ns| {
+            format!(
+                "{:<30} {:<6} {:<8} {}",
+                ns.name,
+                ns.pods,
+                ns.running,
+                ns.mesh_label()
+            )
+            .trim_end()
+            .to_string()
+        }));
+
+        lines.push("\n=== Components ===".to_string());
+        lines.push(format!("{:<30} {}", "NAME", "READY"));
+        lines.extend(
+            self.components
+                .iter()
+                .map(|c| format!("{:<30} {}/{}", c.name, c.ready, c.desired)),
+        );
+
+        lines.push("\n=== Ports ===".to_string());
+        lines.push(format!("{:<6} {:<10} {}", "HOST", "CONTAINER", "PURPOSE"));
+        lines.extend(
+            self.ports
+                .iter()
+                .map(|p| format!("{:<6} {:<10} {}", p.host, p.container, p.purpose)),
+        );
+        lines
+    }
+}
+
+/// Count pods per namespace from (namespace, phase) pairs. Namespaces
+/// without pods are kept so the list matches the cluster's namespaces.
+pub fn summarize_namespaces(
+    namespaces: Vec<String>,
+    pod_phases: &[(String, String)],
+) -> Vec<NamespaceSummary> {
+    let mut counts: BTreeMap<String, (usize, usize)> =
+        namespaces.into_iter().map(|ns| (ns, (0, 0))).collect();
+    for (namespace, phase) in pod_phases {
+        let entry = counts.entry(namespace.clone()).or_default();
+        entry.0 += 1;
+        if phase == "Running" || phase == "Succeeded" {
+            entry.1 += 1;
+        }
+    }
+    counts
+        .into_iter()
+        .map(|(name, (pods, running))| NamespaceSummary {
+            name,
+            pods,
+            running,
+            injection: false,
+            meshed: 0,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn namespaces_are_counted_and_sorted() {
+        let phases = vec![
+            ("kube-system".to_string(), "Running".to_string()),
+            ("kube-system".to_string(), "Pending".to_string()),
+            ("apps".to_string(), "Running".to_string()),
+        ];
+        let summary = summarize_namespaces(
+            vec!["kube-system".to_string(), "default".to_string()],
+            &phases,
+        );
+
+        assert_eq!(
+            summary,
+            vec![
+                NamespaceSummary {
+                    name: "apps".to_string(),
+                    pods: 1,
+                    running: 1,
+                    injection: false,
+                    meshed: 0,
+                },
+                NamespaceSummary {
+                    name: "default".to_string(),
+                    pods: 0,
+                    running: 0,
+                    injection: false,
+                    meshed: 0,
+                },
+                NamespaceSummary {
+                    name: "kube-system".to_string(),
+                    pods: 2,
+                    running: 1,
+                    injection: false,
+                    meshed: 0,
+                },
+            ]
+        );
+    }
+
+    #[test]
+    fn ports_come_from_config() {
+        let config = ClusterConfig::default();
+        let info = ClusterInfo::new(&config, ClusterStatus::Stopped);
+
+        assert_eq!(info.ports.len(), 3 + config.additional_ports.len());
+        assert_eq!(info.ports[0].host, config.api_port);
+        assert_eq!(info.ports[0].purpose, "Kubernetes API");
+
+        let mut remapped = info.clone();
+        remapped.set_api_host_port(config.api_port);
+        assert_eq!(remapped.ports, info.ports);
+        remapped.set_api_host_port(6444);
+        assert_eq!(remapped.ports[0].host, 6444);
+        assert_eq!(remapped.ports[0].container, ClusterConfig::K3S_API_PORT);
+        assert!(remapped.ports[0].purpose.contains("remapped"));
+        // Stopped clusters only list the config-derived fields
+        assert_eq!(info.to_lines().len(), 4);
+    }
+}
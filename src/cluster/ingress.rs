@@ -1,34 +1,16 @@
 use anyhow::Result;
-use std::collections::{HashMap, HashSet};
-use std::path::PathBuf;
+use futures::StreamExt;
+use std::collections::{BTreeMap, HashMap, HashSet};
 use std::time::Duration;
 use tokio::fs;
 use tokio::sync::mpsc;
 
+use super::hosts_file::{self, hosts_file_path, HOSTS_MARKER};
+use super::hosts_helper;
 use super::kube_ops::KubeOps;
+use crate::k8s::{K8sClient, PodExecutor};
 use crate::ui::components::OutputLine;
 
-/// Get the platform-appropriate hosts file path
-fn hosts_file_path() -> PathBuf {
-    #[cfg(windows)]
-    {
-        // Windows: C:\Windows\System32\drivers\etc\hosts
-        if let Ok(windir) = std::env::var("SystemRoot") {
-            PathBuf::from(windir)
-                .join("System32")
-                .join("drivers")
-                .join("etc")
-                .join("hosts")
-        } else {
-            PathBuf::from(r"C:\Windows\System32\drivers\etc\hosts")
-        }
-    }
-    #[cfg(not(windows))]
-    {
-        PathBuf::from("/etc/hosts")
-    }
-}
-
 /// Health status for an ingress endpoint
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum IngressHealthStatus {
@@ -54,6 +36,14 @@ impl IngressHealthStatus {
     }
 }
 
+/// Result of one health check
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct HealthCheck {
+    pub status: IngressHealthStatus,
+    /// Time until the response (or the failure)
+    pub latency: Duration,
+}
+
 /// Ingress entry with host and all its paths
 #[derive(Debug, Clone)]
 pub struct IngressEntry {
@@ -65,8 +55,17 @@ pub struct IngressEntry {
 pub struct IngressHealthChecker;
 
 impl IngressHealthChecker {
-    /// Check health of a single endpoint (host + path)
-    pub async fn check_endpoint(host: &str, path: &str) -> IngressHealthStatus {
+    /// Check health of a single endpoint (host + path), timing the request
+    pub async fn check_endpoint(host: &str, path: &str) -> HealthCheck {
+        let started = std::time::Instant::now();
+        let status = Self::endpoint_status(host, path).await;
+        HealthCheck {
+            status,
+            latency: started.elapsed(),
+        }
+    }
+
+    async fn endpoint_status(host: &str, path: &str) -> IngressHealthStatus {
         let url = format!("http://{}{}", host, path);
 
         let client = match reqwest::Client::builder()
@@ -81,45 +80,200 @@ impl IngressHealthChecker {
         };
 
         match client.get(&url).send().await {
-            Ok(resp) => match resp.status().as_u16() {
-                200..=299 => IngressHealthStatus::Healthy,
-                300..=499 => IngressHealthStatus::Warning,
-                _ => IngressHealthStatus::Error,
-            },
+            Ok(resp) => status_from_code(resp.status().as_u16()),
             Err(_) => IngressHealthStatus::Error,
         }
     }
 
     /// Check health of multiple endpoints in parallel
     /// Key format: "host|path" (e.g., "example.com|/api")
-    pub async fn check_endpoints(entries: &[IngressEntry]) -> HashMap<String, IngressHealthStatus> {
-        let mut results = HashMap::new();
-
-        // Build list of all host+path combinations
-        let mut endpoints: Vec<(String, String)> = Vec::new();
-        for entry in entries {
-            for path in &entry.paths {
-                endpoints.push((entry.host.clone(), path.clone()));
+    /// Check every path of `entries`, at most `concurrency` at a time
+    pub async fn check_endpoints(
+        entries: &[IngressEntry],
+        concurrency: usize,
+    ) -> HashMap<String, HealthCheck> {
+        Self::check_each(entries, concurrency, |host, path| async move {
+            Self::check_endpoint(&host, &path).await
+        })
+        .await
+    }
+
+    /// Like `check_endpoints`, but from inside the Traefik pod: the requests
+    /// name the host in a header, so they don't depend on /etc/hosts or the
+    /// host's firewall. Latency includes the exec round trip.
+    pub async fn check_endpoints_in_cluster(
+        k8s: &K8sClient,
+        entries: &[IngressEntry],
+        concurrency: usize,
+    ) -> HashMap<String, HealthCheck> {
+        let executor = PodExecutor::new(k8s);
+        let traefik = executor
+            .find_pod(TRAEFIK_NAMESPACE, Some(TRAEFIK_SELECTOR), None)
+            .await
+            .ok();
+        let (executor, traefik) = (&executor, traefik.as_ref());
+
+        Self::check_each(entries, concurrency, |host, path| async move {
+            let started = std::time::Instant::now();
+            let status = match traefik {
+                Some(pod) => Self::probe_from_pod(executor, &pod.name, &host, &path).await,
+                None => IngressHealthStatus::Error,
+            };
+            HealthCheck {
+                status,
+                latency: started.elapsed(),
             }
+        })
+        .await
+    }
+
+    async fn probe_from_pod(
+        executor: &PodExecutor,
+        pod: &str,
+        host: &str,
+        path: &str,
+    ) -> IngressHealthStatus {
+        // busybox wget prints the response headers to stderr with -S, and
+        // exits non-zero on 4xx/5xx, so only the status line is trusted
+        let command = vec![
+            "wget".to_string(),
+            "-S".to_string(),
+            "-q".to_string(),
+            "-O".to_string(),
+            "/dev/null".to_string(),
+            "-T".to_string(),
+            "5".to_string(),
+            "--header".to_string(),
+            format!("Host: {}", host),
+            format!("http://127.0.0.1:{}{}", TRAEFIK_WEB_PORT, path),
+        ];
+        match executor.exec(TRAEFIK_NAMESPACE, pod, None, command).await {
+            Ok(result) => parse_status_code(&result.stderr)
+                .map_or(IngressHealthStatus::Error, status_from_code),
+            Err(_) => IngressHealthStatus::Error,
         }
+    }
 
-        // Check all endpoints in parallel
-        let futures: Vec<_> = endpoints
-            .into_iter()
-            .map(|(host, path)| async move {
-                let status = Self::check_endpoint(&host, &path).await;
-                let key = format!("{}|{}", host, path);
-                (key, status)
+    /// Run `check` on every host+path of `entries`, `concurrency` at a time
+    async fn check_each<F, Fut>(
+        entries: &[IngressEntry],
+        concurrency: usize,
+        check: F,
+    ) -> HashMap<String, HealthCheck>
+    where
+        F: Fn(String, String) -> Fut,
+        Fut: std::future::Future<Output = HealthCheck>,
+    {
+        let endpoints: Vec<(String, String)> = entries
+            .iter()
+            .flat_map(|entry| {
+                entry
+                    .paths
+                    .iter()
+                    .map(|path| (entry.host.clone(), path.clone()))
             })
             .collect();
 
-        let checked = futures::future::join_all(futures).await;
+        futures::stream::iter(endpoints)
+            .map(|(host, path)| {
+                let key = format!("{}|{}", host, path);
+                let check = check(host, path);
+                async move { (key, check.await) }
+            })
+            .buffer_unordered(concurrency.max(1))
+            .collect()
+            .await
+    }
+}
 
-        for (key, status) in checked {
-            results.insert(key, status);
+/// Namespace and labels of the Traefik pod k3s deploys
+const TRAEFIK_NAMESPACE: &str = "kube-system";
+const TRAEFIK_SELECTOR: &str = "app.kubernetes.io/name=traefik";
+/// Port of Traefik's `web` entrypoint inside its pod
+const TRAEFIK_WEB_PORT: u16 = 8000;
+
+fn status_from_code(code: u16) -> IngressHealthStatus {
+    match code {
+        200..=299 => IngressHealthStatus::Healthy,
+        300..=499 => IngressHealthStatus::Warning,
+        _ => IngressHealthStatus::Error,
+    }
+}
+
+/// First status code in wget's `-S` output ("  HTTP/1.1 404 Not Found")
+fn parse_status_code(headers: &str) -> Option<u16> {
+    headers.lines().find_map(|line| {
+        let mut parts = line.split_whitespace();
+        parts.next().filter(|proto| proto.starts_with("HTTP/"))?;
+        parts.next()?.parse().ok()
+    })
+}
+
+/// Most hostnames on one hosts line; some resolvers ignore the rest
+const MAX_HOSTS_PER_LINE: usize = 9;
+
+/// One k3dev-managed /etc/hosts line
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub(crate) struct HostsLine {
+    pub hosts: Vec<String>,
+    /// `*.<domain>` when the line holds hosts sharing that parent domain
+    pub group: Option<String>,
+}
+
+impl HostsLine {
+    /// `<ip> <hosts...> <marker> [*.<domain>]`
+    fn entry(&self, ip: &str, marker: &str) -> String {
+        let mut line = format!("{} {} {}", ip, self.hosts.join(" "), marker);
+        if let Some(group) = &self.group {
+            line.push(' ');
+            line.push_str(group);
         }
+        line
+    }
 
-        results
+    /// `<ip> <hosts...> [*.<domain>]`, as the hosts helper reads it
+    fn helper_input(&self, ip: &str) -> String {
+        let mut line = format!("{} {}", ip, self.hosts.join(" "));
+        if let Some(group) = &self.group {
+            line.push(' ');
+            line.push_str(group);
+        }
+        line
+    }
+}
+
+/// Lay out hosts on k3dev lines: hosts sharing a parent domain go on one
+/// line tagged `*.<domain>` instead of one line each. Wildcard ingress hosts
+/// can't be resolved from /etc/hosts; they only tag their domain's line.
+pub(crate) fn group_hosts(hosts: &[String]) -> Vec<HostsLine> {
+    let mut by_domain: BTreeMap<&str, Vec<String>> = BTreeMap::new();
+    for host in hosts.iter().filter(|h| !h.starts_with("*.")) {
+        let domain = host.split_once('.').map_or(host.as_str(), |(_, d)| d);
+        by_domain.entry(domain).or_default().push(host.clone());
+    }
+
+    let mut lines = Vec::new();
+    for (domain, mut members) in by_domain {
+        let wildcard = format!("*.{}", domain);
+        let tagged = members.len() > 1 || hosts.contains(&wildcard);
+        members.sort();
+        members.dedup();
+        for chunk in members.chunks(MAX_HOSTS_PER_LINE) {
+            lines.push(HostsLine {
+                hosts: chunk.to_vec(),
+                group: tagged.then(|| wildcard.clone()),
+            });
+        }
+    }
+    lines
+}
+
+/// Whether /etc/hosts resolves an ingress host; a wildcard host counts as
+/// present when any name under its domain is there
+fn hosts_cover(etc_hosts: &HashSet<String>, host: &str) -> bool {
+    match host.strip_prefix('*') {
+        Some(suffix) => etc_hosts.iter().any(|h| h.ends_with(suffix)),
+        None => etc_hosts.contains(host),
     }
 }
 
@@ -127,7 +281,7 @@ impl IngressHealthChecker {
 pub enum HostsUpdateResult {
     /// No update was needed (all hosts already present)
     NoUpdateNeeded,
-    /// Successfully written directly (had write permission)
+    /// Successfully written without prompting (write permission or the hosts helper)
     WrittenDirectly { count: usize },
     /// Needs elevated privileges — contains the full file content to write
     NeedsSudo { content: String, count: usize },
@@ -137,7 +291,8 @@ pub enum HostsUpdateResult {
 
 /// Ingress manager for /etc/hosts updates
 pub struct IngressManager {
-    hosts_marker: String,
+    /// Cluster whose block in /etc/hosts this manager writes
+    cluster: String,
     domain: Option<String>,
     /// IP address to use in /etc/hosts entries (127.0.0.1 for local, remote host IP for remote Docker)
     target_ip: String,
@@ -151,7 +306,7 @@ impl IngressManager {
             .unwrap_or("127.0.0.1")
             .to_string();
         Self {
-            hosts_marker: "# k3dev-ingress".to_string(),
+            cluster: "k3dev".to_string(),
             domain: None,
             target_ip,
             kube_ops: KubeOps::new(),
@@ -164,13 +319,19 @@ impl IngressManager {
             .unwrap_or("127.0.0.1")
             .to_string();
         Self {
-            hosts_marker: "# k3dev-ingress".to_string(),
+            cluster: "k3dev".to_string(),
             domain: Some(domain),
             target_ip,
             kube_ops: KubeOps::new(),
         }
     }
 
+    /// Write the hosts entries into this cluster's block
+    pub fn for_cluster(mut self, cluster: impl Into<String>) -> Self {
+        self.cluster = cluster.into();
+        self
+    }
+
     /// Get the Traefik dashboard domain based on configured domain
     pub fn traefik_dashboard_domain(&self) -> Option<String> {
         self.domain.as_ref().map(|d| format!("traefik.{}", d))
@@ -302,7 +463,10 @@ impl IngressManager {
         let etc_hosts = self.get_all_hosts_from_etc_hosts().await;
 
         // Return hosts that are in ingress but not in /etc/hosts
-        Ok(ingress_hosts.difference(&etc_hosts).cloned().collect())
+        Ok(ingress_hosts
+            .into_iter()
+            .filter(|host| !hosts_cover(&etc_hosts, host))
+            .collect())
     }
 
     /// Update /etc/hosts with ingress entries.
@@ -312,18 +476,31 @@ impl IngressManager {
         output_tx: Option<mpsc::Sender<OutputLine>>,
     ) -> Result<HostsUpdateResult> {
         let hosts = self.get_ingress_hosts().await?;
+        let (wildcards, concrete): (Vec<&String>, Vec<&String>) =
+            hosts.iter().partition(|h| h.starts_with("*."));
 
-        if hosts.is_empty() {
+        if let Some(tx) = &output_tx {
+            for wildcard in &wildcards {
+                let _ = tx
+                    .send(OutputLine::warning(format!(
+                        "{}: /etc/hosts has no wildcards; only named hosts under it are added",
+                        wildcard
+                    )))
+                    .await;
+            }
+        }
+
+        if concrete.is_empty() {
             if let Some(tx) = &output_tx {
                 let _ = tx.send(OutputLine::info("No ingress hosts found")).await;
             }
             return Ok(HostsUpdateResult::NoUpdateNeeded);
         }
+        let count = concrete.len();
 
         // Check if update is needed - check ALL hosts in /etc/hosts
-        let hosts_set: HashSet<String> = hosts.iter().cloned().collect();
         let etc_hosts = self.get_all_hosts_from_etc_hosts().await;
-        if hosts_set.is_subset(&etc_hosts) {
+        if concrete.iter().all(|h| etc_hosts.contains(h.as_str())) {
             if let Some(tx) = &output_tx {
                 let _ = tx
                     .send(OutputLine::info(
@@ -338,26 +515,14 @@ impl IngressManager {
         let hosts_path = hosts_file_path();
         let current_content = fs::read_to_string(&hosts_path).await.unwrap_or_default();
 
-        // Remove existing k3dev entries
-        let cleaned: Vec<&str> = current_content
-            .lines()
-            .filter(|line| !line.contains(&self.hosts_marker))
-            .collect();
-
-        // Add new entries
-        let mut new_entries: Vec<String> = hosts
+        // Replace this cluster's block with one line per domain
+        let lines = group_hosts(&hosts);
+        let new_entries: Vec<String> = lines
             .iter()
-            .map(|host| format!("{} {} {}", self.target_ip, host, self.hosts_marker))
+            .map(|line| line.entry(&self.target_ip, HOSTS_MARKER))
             .collect();
-        new_entries.sort();
-
-        // Combine
-        let mut final_content = cleaned.join("\n");
-        if !final_content.ends_with('\n') {
-            final_content.push('\n');
-        }
-        final_content.push_str(&new_entries.join("\n"));
-        final_content.push('\n');
+        let final_content =
+            hosts_file::replace_block(&current_content, &self.cluster, &new_entries)?;
 
         // Check if hosts file is on a read-only filesystem (NixOS, MicroOS, etc.)
         #[cfg(unix)]
@@ -390,17 +555,48 @@ impl IngressManager {
             }
         }
 
+        // Keep a copy to restore if the edit goes wrong
+        let backup = hosts_file::backup(&current_content)?;
+        tracing::info!(path = %backup.display(), "Backed up hosts file");
+
         // Try to write directly first (works if run as root or have write permissions)
         if fs::write(&hosts_path, &final_content).await.is_ok() {
             if let Some(tx) = &output_tx {
                 let _ = tx
                     .send(OutputLine::success(format!(
                         "Updated /etc/hosts with {} entries",
-                        hosts.len()
+                        count
                     )))
                     .await;
             }
-            return Ok(HostsUpdateResult::WrittenDirectly { count: hosts.len() });
+            return Ok(HostsUpdateResult::WrittenDirectly { count });
+        }
+
+        // The installed hosts helper writes without a password prompt
+        if hosts_helper::is_installed() {
+            let input: Vec<String> = lines
+                .iter()
+                .map(|line| line.helper_input(&self.target_ip))
+                .collect();
+            match hosts_helper::write_entries(&self.cluster, &input).await {
+                Ok(()) => {
+                    if let Some(tx) = &output_tx {
+                        let _ = tx
+                            .send(OutputLine::success(format!(
+                                "Updated /etc/hosts with {} entries via the hosts helper",
+                                count
+                            )))
+                            .await;
+                    }
+                    return Ok(HostsUpdateResult::WrittenDirectly { count });
+                }
+                Err(e) => {
+                    tracing::warn!(error = %e, "Hosts helper failed, falling back to sudo");
+                    if let Some(tx) = &output_tx {
+                        let _ = tx.send(OutputLine::warning(format!("{:#}", e))).await;
+                    }
+                }
+            }
         }
 
         // Needs elevated privileges — caller must handle this
@@ -414,7 +610,7 @@ impl IngressManager {
 
         Ok(HostsUpdateResult::NeedsSudo {
             content: final_content,
-            count: hosts.len(),
+            count,
         })
     }
 
@@ -447,3 +643,58 @@ impl Default for IngressManager {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn names(hosts: &[&str]) -> Vec<String> {
+        hosts.iter().map(|h| h.to_string()).collect()
+    }
+
+    #[test]
+    fn wget_status_line_is_parsed() {
+        let stderr = "  HTTP/1.1 302 Found\n  Location: /login\n  HTTP/1.1 200 OK\n";
+        assert_eq!(parse_status_code(stderr), Some(302));
+        assert_eq!(
+            parse_status_code("wget: can't connect to remote host: Connection refused"),
+            None
+        );
+    }
+
+    #[test]
+    fn hosts_sharing_a_domain_share_a_line() {
+        let lines = group_hosts(&names(&[
+            "b.myapp.test",
+            "a.myapp.test",
+            "*.myapp.test",
+            "api.other.test",
+            "*.lonely.test",
+        ]));
+        assert_eq!(
+            lines,
+            vec![
+                HostsLine {
+                    hosts: names(&["a.myapp.test", "b.myapp.test"]),
+                    group: Some("*.myapp.test".to_string()),
+                },
+                HostsLine {
+                    hosts: names(&["api.other.test"]),
+                    group: None,
+                },
+            ]
+        );
+        assert_eq!(
+            lines[0].entry("127.0.0.1", "# k3dev-ingress"),
+            "127.0.0.1 a.myapp.test b.myapp.test # k3dev-ingress *.myapp.test"
+        );
+    }
+
+    #[test]
+    fn wildcards_are_covered_by_any_host_under_their_domain() {
+        let etc_hosts: HashSet<String> = names(&["a.myapp.test"]).into_iter().collect();
+        assert!(hosts_cover(&etc_hosts, "*.myapp.test"));
+        assert!(!hosts_cover(&etc_hosts, "*.other.test"));
+        assert!(!hosts_cover(&etc_hosts, "b.myapp.test"));
+    }
+}
@@ -0,0 +1,505 @@
+//! One k3dev in control per cluster
+//!
+//! The first TUI to open a cluster locks a file in the runtime directory
+//! (`flock`), writes its pid to it and listens on a socket next to it. The
+//! lock decides ownership, so two instances starting together can't both
+//! win. Later instances find the owner on the socket and attach read-only,
+//! keeping a connection open so they notice when it exits. One of them can
+//! ask for control; the owner hands it over once nothing it runs would be
+//! cut short. `k3dev attach` reads the owner's output through the same
+//! socket.
+
+use std::fs::File;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
+
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, Local};
+use nix::errno::Errno;
+use nix::fcntl::{Flock, FlockArg};
+use tokio::io::{AsyncBufReadExt, AsyncReadExt, AsyncWriteExt, BufReader, Lines};
+use tokio::net::unix::OwnedWriteHalf;
+use tokio::net::{UnixListener, UnixStream};
+use tokio::sync::{broadcast, oneshot};
+use tokio::task::JoinSet;
+
+use crate::ui::components::{OutputLine, OutputMirror, OutputType};
+
+/// Longest wait for the other side of the socket to answer. Handing over
+/// waits for the owner's user-facing loop, so it gets longer.
+const ANSWER_TIMEOUT: Duration = Duration::from_secs(2);
+const HANDOVER_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Time between two looks for an owner that holds the lock but isn't
+/// listening yet (or is just leaving)
+const RETRY_INTERVAL: Duration = Duration::from_millis(50);
+
+/// Socket and lock file of one cluster
+#[derive(Debug, Clone)]
+struct LockPaths {
+    socket: PathBuf,
+    lock: PathBuf,
+}
+
+impl LockPaths {
+    fn for_cluster(container_name: &str) -> Self {
+        Self::in_dir(
+            &dirs::runtime_dir().unwrap_or_else(std::env::temp_dir),
+            container_name,
+        )
+    }
+
+    fn in_dir(dir: &Path, container_name: &str) -> Self {
+        Self {
+            socket: dir.join(format!("k3dev-{}.sock", container_name)),
+            lock: dir.join(format!("k3dev-{}.lock", container_name)),
+        }
+    }
+
+    fn remove(&self) {
+        let _ = std::fs::remove_file(&self.socket);
+        let _ = std::fs::remove_file(&self.lock);
+    }
+}
+
+/// Who controls the cluster, as found by [`acquire`]
+#[derive(Debug)]
+pub enum Control {
+    /// This instance does; serve the lock to let others find it
+    Owner(OwnerLock),
+    /// Another instance does. The stream closes when it exits or hands over.
+    Attached { pid: u32, watch: UnixStream },
+}
+
+/// Another instance asking for control; answer `true` to hand it over
+#[derive(Debug)]
+pub struct HandoverRequest {
+    pub pid: u32,
+    pub reply: oneshot::Sender<bool>,
+}
+
+/// The lock and bound socket of the controlling instance. Dropping it frees
+/// the cluster for the next instance.
+#[derive(Debug)]
+pub struct OwnerLock {
+    paths: LockPaths,
+    listener: UnixListener,
+    handed_over: bool,
+    /// Released after `Drop::drop` removed the paths
+    _lock: Flock<File>,
+}
+
+impl Drop for OwnerLock {
+    fn drop(&mut self) {
+        // After a handover the paths may already belong to the new owner
+        if !self.handed_over {
+            self.paths.remove();
+        }
+    }
+}
+
+/// Take control of the cluster, or find the instance that has it
+pub async fn acquire(container_name: &str) -> Result<Control> {
+    acquire_at(LockPaths::for_cluster(container_name)).await
+}
+
+async fn acquire_at(paths: LockPaths) -> Result<Control> {
+    let deadline = Instant::now() + ANSWER_TIMEOUT;
+    loop {
+        if let Some(mut lock) = try_lock(&paths.lock)? {
+            // Owners hold the lock for as long as they live, so a socket
+            // left there belongs to one that died
+            let _ = std::fs::remove_file(&paths.socket);
+            let listener = UnixListener::bind(&paths.socket)
+                .with_context(|| format!("Can't listen on {}", paths.socket.display()))?;
+            lock.set_len(0)
+                .and_then(|()| lock.write_all(format!("{}\n", std::process::id()).as_bytes()))
+                .with_context(|| format!("Can't write {}", paths.lock.display()))?;
+            return Ok(Control::Owner(OwnerLock {
+                paths,
+                listener,
+                handed_over: false,
+                _lock: lock,
+            }));
+        }
+
+        if let Ok(mut stream) = UnixStream::connect(&paths.socket).await {
+            let pid = ask(&mut stream, "owner", ANSWER_TIMEOUT).await?;
+            let pid = pid
+                .parse()
+                .with_context(|| format!("Unexpected answer from the owner: {}", pid))?;
+            return Ok(Control::Attached { pid, watch: stream });
+        }
+
+        // The owner locked but isn't listening yet, or is just leaving
+        if Instant::now() >= deadline {
+            bail!(
+                "Another k3dev holds {} but doesn't answer",
+                paths.lock.display()
+            );
+        }
+        tokio::time::sleep(RETRY_INTERVAL).await;
+    }
+}
+
+/// Lock `path` for this instance; None while another one holds it
+fn try_lock(path: &Path) -> Result<Option<Flock<File>>> {
+    use std::os::unix::fs::{MetadataExt, OpenOptionsExt};
+
+    let file = std::fs::OpenOptions::new()
+        .read(true)
+        .write(true)
+        .create(true)
+        .truncate(false)
+        .mode(0o600)
+        .open(path)
+        .with_context(|| format!("Can't open {}", path.display()))?;
+    let lock = match Flock::lock(file, FlockArg::LockExclusiveNonblock) {
+        Ok(lock) => lock,
+        Err((_, e)) if e == Errno::EWOULDBLOCK => return Ok(None),
+        Err((_, e)) => return Err(e).with_context(|| format!("Can't lock {}", path.display())),
+    };
+    // A leaving owner removes the file before it unlocks: the lock only
+    // counts if it's on the file that is at `path` now
+    let locked = lock.metadata()?;
+    let current = std::fs::metadata(path).ok();
+    if current.is_none_or(|m| (m.dev(), m.ino()) != (locked.dev(), locked.ino())) {
+        return Ok(None);
+    }
+    Ok(Some(lock))
+}
+
+/// Ask the owner to hand over control; `Ok(false)` when it is busy
+pub async fn request_control(container_name: &str) -> Result<bool> {
+    request_control_at(&LockPaths::for_cluster(container_name)).await
+}
+
+async fn request_control_at(paths: &LockPaths) -> Result<bool> {
+    let mut stream = UnixStream::connect(&paths.socket)
+        .await
+        .context("The controlling instance is gone")?;
+    let request = format!("takeover {}", std::process::id());
+    match ask(&mut stream, &request, HANDOVER_TIMEOUT).await?.as_str() {
+        "ok" => Ok(true),
+        "busy" => Ok(false),
+        other => bail!("Unexpected answer from the owner: {}", other),
+    }
+}
+
+/// The controlling instance's output, line by line
+pub struct OutputFeed {
+    /// Pid of the instance the lines come from
+    pub pid: u32,
+    lines: Lines<BufReader<UnixStream>>,
+}
+
+impl OutputFeed {
+    /// The next line; None once the instance exits or hands over
+    pub async fn next(&mut self) -> Option<OutputLine> {
+        loop {
+            let line = self.lines.next_line().await.ok()??;
+            if let Some(line) = decode_line(&line) {
+                return Some(line);
+            }
+        }
+    }
+}
+
+/// Follow the output of the instance controlling the cluster, starting with
+/// its latest lines
+pub async fn follow_output(container_name: &str) -> Result<OutputFeed> {
+    follow_output_at(&LockPaths::for_cluster(container_name)).await
+}
+
+async fn follow_output_at(paths: &LockPaths) -> Result<OutputFeed> {
+    let mut stream = UnixStream::connect(&paths.socket)
+        .await
+        .context("No k3dev is running for this cluster")?;
+    let pid = ask(&mut stream, "owner", ANSWER_TIMEOUT).await?;
+    let pid = pid
+        .parse()
+        .with_context(|| format!("Unexpected answer from the owner: {}", pid))?;
+    stream.write_all(b"follow\n").await?;
+    Ok(OutputFeed {
+        pid,
+        lines: BufReader::new(stream).lines(),
+    })
+}
+
+/// Wait until the owner behind `watch` exits or hands over
+pub async fn watch(mut watch: UnixStream) {
+    let mut buf = [0u8; 64];
+    while matches!(watch.read(&mut buf).await, Ok(n) if n > 0) {}
+}
+
+/// Send one line and read the one-line answer
+async fn ask(stream: &mut UnixStream, request: &str, timeout: Duration) -> Result<String> {
+    stream
+        .write_all(format!("{}\n", request).as_bytes())
+        .await?;
+    let mut answer = String::new();
+    let mut reader = BufReader::new(stream);
+    tokio::time::timeout(timeout, reader.read_line(&mut answer))
+        .await
+        .context("The controlling instance didn't answer")??;
+    Ok(answer.trim().to_string())
+}
+
+impl OwnerLock {
+    /// Answer other instances until one takes over. `on_request` passes
+    /// handover requests on to whoever decides them.
+    pub async fn serve(
+        mut self,
+        mirror: OutputMirror,
+        on_request: impl Fn(HandoverRequest) + Send + Sync + 'static,
+    ) {
+        let on_request = std::sync::Arc::new(on_request);
+        // Dropping the set closes every watcher's connection
+        let mut connections = JoinSet::new();
+        loop {
+            tokio::select! {
+                accepted = self.listener.accept() => {
+                    let Ok((stream, _)) = accepted else { continue };
+                    let paths = self.paths.clone();
+                    let mirror = mirror.clone();
+                    let on_request = on_request.clone();
+                    connections.spawn(async move {
+                        answer(stream, &paths, &mirror, |request| on_request(request)).await
+                    });
+                }
+                // A pattern that fails to match disables the branch until the
+                // next accept, so every finished connection is matched here
+                Some(handed_over) = connections.join_next() => {
+                    if matches!(handed_over, Ok(true)) {
+                        break;
+                    }
+                }
+            }
+        }
+        self.handed_over = true;
+    }
+}
+
+/// Answer one connection; true once control was handed over through it
+async fn answer(
+    stream: UnixStream,
+    paths: &LockPaths,
+    mirror: &OutputMirror,
+    on_request: impl Fn(HandoverRequest),
+) -> bool {
+    let (read, mut write) = stream.into_split();
+    let mut lines = BufReader::new(read).lines();
+    while let Ok(Some(line)) = lines.next_line().await {
+        let reply = match line.split_once(' ') {
+            None if line == "owner" => std::process::id().to_string(),
+            None if line == "follow" => {
+                send_output(write, mirror).await;
+                return false;
+            }
+            Some(("takeover", pid)) => {
+                let (reply, decision) = oneshot::channel();
+                on_request(HandoverRequest {
+                    pid: pid.parse().unwrap_or(0),
+                    reply,
+                });
+                if decision.await.unwrap_or(false) {
+                    // Free the paths before the new owner tries to bind them
+                    paths.remove();
+                    let _ = write.write_all(b"ok\n").await;
+                    return true;
+                }
+                "busy".to_string()
+            }
+            _ => "unknown request".to_string(),
+        };
+        if write
+            .write_all(format!("{}\n", reply).as_bytes())
+            .await
+            .is_err()
+        {
+            break;
+        }
+    }
+    false
+}
+
+/// Send the mirrored output until the client goes away
+async fn send_output(mut write: OwnedWriteHalf, mirror: &OutputMirror) {
+    let (recent, mut feed) = mirror.subscribe();
+    for line in recent {
+        if write
+            .write_all(encode_line(&line).as_bytes())
+            .await
+            .is_err()
+        {
+            return;
+        }
+    }
+    loop {
+        let line = match feed.recv().await {
+            Ok(line) => line,
+            Err(broadcast::error::RecvError::Lagged(skipped)) => {
+                OutputLine::warning(format!("({} lines skipped)", skipped))
+            }
+            Err(broadcast::error::RecvError::Closed) => return,
+        };
+        if write
+            .write_all(encode_line(&line).as_bytes())
+            .await
+            .is_err()
+        {
+            return;
+        }
+    }
+}
+
+/// One line on the socket: kind, RFC 3339 timestamp and content, tab separated
+fn encode_line(line: &OutputLine) -> String {
+    let kind = match line.output_type {
+        OutputType::Info => "info",
+        OutputType::Success => "success",
+        OutputType::Error => "error",
+        OutputType::Warning => "warning",
+        OutputType::Hint => "hint",
+    };
+    format!(
+        "{}\t{}\t{}\n",
+        kind,
+        line.timestamp.to_rfc3339(),
+        line.content.replace('\n', " ")
+    )
+}
+
+fn decode_line(text: &str) -> Option<OutputLine> {
+    let mut parts = text.splitn(3, '\t');
+    let output_type = match parts.next()? {
+        "info" => OutputType::Info,
+        "success" => OutputType::Success,
+        "error" => OutputType::Error,
+        "warning" => OutputType::Warning,
+        "hint" => OutputType::Hint,
+        _ => return None,
+    };
+    let timestamp = DateTime::parse_from_rfc3339(parts.next()?)
+        .ok()?
+        .with_timezone(&Local);
+    Some(OutputLine {
+        content: parts.next()?.to_string(),
+        output_type,
+        timestamp,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_paths(name: &str) -> (PathBuf, LockPaths) {
+        let dir = std::env::temp_dir().join(format!("k3dev-lock-{}-{}", name, std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let paths = LockPaths::in_dir(&dir, "test-server");
+        (dir, paths)
+    }
+
+    #[tokio::test]
+    async fn second_instance_attaches_and_can_take_over() {
+        let (dir, paths) = temp_paths("handover");
+        let Control::Owner(owner) = acquire_at(paths.clone()).await.unwrap() else {
+            panic!("first instance should own the cluster");
+        };
+        assert_eq!(
+            std::fs::read_to_string(&paths.lock).unwrap().trim(),
+            std::process::id().to_string()
+        );
+        // The owner refuses once, then agrees
+        let refused = std::sync::atomic::AtomicBool::new(false);
+        let server = tokio::spawn(owner.serve(OutputMirror::new(), move |request| {
+            let agree = refused.swap(true, std::sync::atomic::Ordering::SeqCst);
+            let _ = request.reply.send(agree);
+        }));
+
+        let Control::Attached { pid, watch: stream } = acquire_at(paths.clone()).await.unwrap()
+        else {
+            panic!("second instance should attach");
+        };
+        assert_eq!(pid, std::process::id());
+        assert!(!request_control_at(&paths).await.unwrap());
+        assert!(request_control_at(&paths).await.unwrap());
+
+        // The watcher sees the owner go, and the cluster is free again
+        tokio::time::timeout(Duration::from_secs(5), watch(stream))
+            .await
+            .unwrap();
+        server.await.unwrap();
+        assert!(matches!(
+            acquire_at(paths.clone()).await.unwrap(),
+            Control::Owner(_)
+        ));
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn attached_terminal_follows_the_output() {
+        let (dir, paths) = temp_paths("follow");
+        let Control::Owner(owner) = acquire_at(paths.clone()).await.unwrap() else {
+            panic!("first instance should own the cluster");
+        };
+        let mirror = OutputMirror::new();
+        mirror.send(OutputLine::info("before attaching"));
+        tokio::spawn(owner.serve(mirror.clone(), |_| {}));
+
+        let mut feed = follow_output_at(&paths).await.unwrap();
+        assert_eq!(feed.pid, std::process::id());
+        let first = feed.next().await.unwrap();
+        assert_eq!(first.content, "before attaching");
+
+        mirror.send(OutputLine::error("after\nattaching"));
+        let second = feed.next().await.unwrap();
+        assert_eq!(second.content, "after attaching");
+        assert_eq!(second.output_type, OutputType::Error);
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+    async fn instances_starting_together_have_one_owner() {
+        let (dir, paths) = temp_paths("race");
+        let instances: Vec<_> = (0..8)
+            .map(|_| {
+                let paths = paths.clone();
+                tokio::spawn(async move {
+                    match acquire_at(paths).await.unwrap() {
+                        Control::Owner(owner) => {
+                            tokio::spawn(owner.serve(OutputMirror::new(), |_| {}));
+                            true
+                        }
+                        Control::Attached { pid, .. } => {
+                            assert_eq!(pid, std::process::id());
+                            false
+                        }
+                    }
+                })
+            })
+            .collect();
+        let mut owners = 0;
+        for instance in instances {
+            if instance.await.unwrap() {
+                owners += 1;
+            }
+        }
+        assert_eq!(owners, 1);
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+
+    #[tokio::test]
+    async fn stale_socket_is_replaced() {
+        let (dir, paths) = temp_paths("stale");
+        // Bound then dropped: the file stays but nobody listens
+        drop(std::os::unix::net::UnixListener::bind(&paths.socket).unwrap());
+        assert!(matches!(
+            acquire_at(paths.clone()).await.unwrap(),
+            Control::Owner(_)
+        ));
+        std::fs::remove_dir_all(dir).unwrap();
+    }
+}
@@ -0,0 +1,263 @@
+//! Chaos tools for testing an app's resilience locally
+//!
+//! Cordoning the node, limiting the container's memory and delaying a
+//! service's traffic with netem all act on the cluster container, so they
+//! are undone by their counterpart or by recreating the container.
+
+use anyhow::{bail, Context, Result};
+use tokio::sync::mpsc;
+
+use super::K3sManager;
+use crate::ui::components::OutputLine;
+
+/// Selector matching every node
+const ALL_NODES: &str = "kubernetes.io/hostname";
+
+/// Smallest memory limit Docker accepts
+const MIN_MEMORY_BYTES: u64 = 6 * 1024 * 1024;
+
+/// Adds netem to the host side of the veth of each pod behind a service.
+/// The pod's eth0 names its peer's ifindex in `iflink`.
+const ADD_LATENCY_SCRIPT: &str = r#"
+command -v tc >/dev/null || { echo "tc is not installed in the cluster container" >&2; exit 1; }
+pods=$(k3s kubectl get endpoints -n "$1" "$2" -o jsonpath='{range .subsets[*].addresses[*]}{.targetRef.name}{"\n"}{end}') || exit 1
+[ -n "$pods" ] || { echo "Service $1/$2 has no ready endpoints" >&2; exit 1; }
+for pod in $pods; do
+  index=$(k3s kubectl exec -n "$1" "$pod" -- cat /sys/class/net/eth0/iflink) || { echo "Can't read the network interface of $pod" >&2; exit 1; }
+  dev=$(grep -lx "$index" /sys/class/net/*/ifindex | cut -d/ -f5)
+  [ -n "$dev" ] || { echo "No interface found for $pod" >&2; exit 1; }
+  tc qdisc replace dev "$dev" root netem delay "$3ms" || exit 1
+  echo "$pod: +$3ms on $dev"
+done
+"#;
+
+/// Removes every netem qdisc, whichever service it was added for
+const CLEAR_LATENCY_SCRIPT: &str = r#"
+command -v tc >/dev/null || { echo "tc is not installed in the cluster container" >&2; exit 1; }
+for dev in $(tc qdisc show | grep netem | sed -n 's/.* dev \([^ ]*\) .*/\1/p'); do
+  tc qdisc del dev "$dev" root && echo "Removed latency from $dev"
+done
+"#;
+
+/// A tool in the Chaos group of the command palette
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ChaosTool {
+    Cordon,
+    Uncordon,
+    MemoryPressure,
+    ClearMemoryPressure,
+    Latency,
+    ClearLatency,
+    PodKiller,
+}
+
+impl ChaosTool {
+    pub const ALL: [ChaosTool; 7] = [
+        ChaosTool::Cordon,
+        ChaosTool::Uncordon,
+        ChaosTool::MemoryPressure,
+        ChaosTool::ClearMemoryPressure,
+        ChaosTool::Latency,
+        ChaosTool::ClearLatency,
+        ChaosTool::PodKiller,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            ChaosTool::Cordon => "Cordon Node",
+            ChaosTool::Uncordon => "Uncordon Node",
+            ChaosTool::MemoryPressure => "Memory Pressure",
+            ChaosTool::ClearMemoryPressure => "Clear Memory Pressure",
+            ChaosTool::Latency => "Inject Latency",
+            ChaosTool::ClearLatency => "Clear Latency",
+            ChaosTool::PodKiller => "Toggle Pod Killer",
+        }
+    }
+
+    pub fn description(self) -> &'static str {
+        match self {
+            ChaosTool::Cordon => "Mark the node unschedulable; new pods stay Pending",
+            ChaosTool::Uncordon => "Let pods be scheduled on the node again",
+            ChaosTool::MemoryPressure => "Limit the cluster container's memory",
+            ChaosTool::ClearMemoryPressure => "Give the cluster container the host's memory again",
+            ChaosTool::Latency => "Delay traffic to a service's pods with tc/netem",
+            ChaosTool::ClearLatency => "Remove all injected latency",
+            ChaosTool::PodKiller => "Kill a random pod matching chaos.pod_killer on an interval",
+        }
+    }
+}
+
+/// What a chaos job does, with the values its form asked for
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ChaosAction {
+    Cordon,
+    Uncordon,
+    LimitMemory(u64),
+    ClearMemoryLimit,
+    AddLatency {
+        namespace: String,
+        service: String,
+        delay_ms: u32,
+    },
+    ClearLatency,
+}
+
+impl ChaosAction {
+    /// Title of the job running it
+    pub fn title(&self) -> String {
+        match self {
+            ChaosAction::Cordon => "Cordon node".to_string(),
+            ChaosAction::Uncordon => "Uncordon node".to_string(),
+            ChaosAction::LimitMemory(bytes) => format!("Limit memory to {}", format_mib(*bytes)),
+            ChaosAction::ClearMemoryLimit => "Clear memory limit".to_string(),
+            ChaosAction::AddLatency {
+                namespace,
+                service,
+                delay_ms,
+            } => format!("Add {}ms to {}/{}", delay_ms, namespace, service),
+            ChaosAction::ClearLatency => "Clear latency".to_string(),
+        }
+    }
+}
+
+/// A memory limit as typed in the form ("512Mi", "1G") in bytes
+pub fn parse_memory_limit(limit: &str) -> Result<u64> {
+    let bytes = crate::k8s::quotas::parse_quantity(limit)
+        .filter(|b| b.is_finite() && *b >= 0.0)
+        .with_context(|| format!("'{}' is not a memory size, e.g. 512Mi or 2Gi", limit))?
+        as u64;
+    if bytes < MIN_MEMORY_BYTES {
+        bail!("Docker needs a memory limit of at least 6Mi");
+    }
+    Ok(bytes)
+}
+
+fn format_mib(bytes: u64) -> String {
+    format!("{}Mi", bytes / (1024 * 1024))
+}
+
+impl K3sManager {
+    pub async fn run_chaos(
+        &self,
+        action: &ChaosAction,
+        output_tx: &mpsc::Sender<OutputLine>,
+    ) -> Result<()> {
+        self.ensure_running().await?;
+        match action {
+            ChaosAction::Cordon => self.set_schedulable(false, output_tx).await,
+            ChaosAction::Uncordon => self.set_schedulable(true, output_tx).await,
+            ChaosAction::LimitMemory(bytes) => self.limit_memory(Some(*bytes), output_tx).await,
+            ChaosAction::ClearMemoryLimit => self.limit_memory(None, output_tx).await,
+            ChaosAction::AddLatency {
+                namespace,
+                service,
+                delay_ms,
+            } => {
+                let delay = delay_ms.to_string();
+                self.run_chaos_script(ADD_LATENCY_SCRIPT, &[namespace, service, &delay], output_tx)
+                    .await
+                    .context("Latency not injected")
+            }
+            ChaosAction::ClearLatency => self
+                .run_chaos_script(CLEAR_LATENCY_SCRIPT, &[], output_tx)
+                .await
+                .context("Latency not cleared"),
+        }
+    }
+
+    async fn set_schedulable(
+        &self,
+        schedulable: bool,
+        output_tx: &mpsc::Sender<OutputLine>,
+    ) -> Result<()> {
+        let verb = if schedulable { "uncordon" } else { "cordon" };
+        let output = self
+            .docker
+            .exec_in_container(
+                &self.config.container_name,
+                &["k3s", "kubectl", verb, "-l", ALL_NODES],
+            )
+            .await
+            .with_context(|| format!("Failed to {} the node", verb))?;
+        send_lines(output_tx, &output).await;
+        if !schedulable {
+            let _ = output_tx
+                .send(OutputLine::warning(
+                    "New pods stay Pending until the node is uncordoned",
+                ))
+                .await;
+        }
+        Ok(())
+    }
+
+    /// Limit the container's memory, without swap so the limit bites;
+    /// `None` sets it to the host's memory
+    async fn limit_memory(
+        &self,
+        bytes: Option<u64>,
+        output_tx: &mpsc::Sender<OutputLine>,
+    ) -> Result<()> {
+        let (memory, swap) = match bytes {
+            Some(bytes) => (bytes as i64, bytes as i64),
+            None => {
+                // Docker has no way to remove a limit, so raise it to the host's memory
+                let capacity = self
+                    .docker
+                    .host_capacity()
+                    .await
+                    .context("Failed to read the host's memory")?;
+                ((capacity.memory_mb * 1024.0 * 1024.0) as i64, -1)
+            }
+        };
+        self.docker
+            .update_memory_limit(&self.config.container_name, memory, swap)
+            .await?;
+        let line = match bytes {
+            Some(bytes) => OutputLine::success(format!(
+                "Cluster container limited to {}; pods may be OOM-killed",
+                format_mib(bytes)
+            )),
+            None => OutputLine::success("Cluster container memory limit cleared"),
+        };
+        let _ = output_tx.send(line).await;
+        Ok(())
+    }
+
+    async fn run_chaos_script(
+        &self,
+        script: &str,
+        args: &[&str],
+        output_tx: &mpsc::Sender<OutputLine>,
+    ) -> Result<()> {
+        let mut command = vec!["sh", "-c", script, "sh"];
+        command.extend_from_slice(args);
+        let output = self
+            .docker
+            .exec_in_container(&self.config.container_name, &command)
+            .await?;
+        if output.trim().is_empty() {
+            let _ = output_tx.send(OutputLine::info("Nothing to change")).await;
+        }
+        send_lines(output_tx, &output).await;
+        Ok(())
+    }
+}
+
+async fn send_lines(output_tx: &mpsc::Sender<OutputLine>, output: &str) {
+    for line in output.lines().filter(|l| !l.trim().is_empty()) {
+        let _ = output_tx.send(OutputLine::info(line)).await;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn memory_limits_parse_as_quantities() {
+        assert_eq!(parse_memory_limit("512Mi").unwrap(), 512 * 1024 * 1024);
+        assert_eq!(parse_memory_limit("1G").unwrap(), 1_000_000_000);
+        assert!(parse_memory_limit("1Ki").is_err());
+        assert!(parse_memory_limit("lots").is_err());
+    }
+}
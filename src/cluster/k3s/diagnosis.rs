@@ -0,0 +1,215 @@
+//! Self-diagnosis when the API never becomes ready
+//!
+//! Instead of only reporting the timeout, the container's last log lines and
+//! exit state are shown together with the causes the facts point at.
+
+use tokio::sync::mpsc;
+
+use super::K3sManager;
+use crate::cluster::api_probe::ApiHealth;
+use crate::cluster::docker::ContainerExit;
+use crate::ui::components::OutputLine;
+
+/// Log lines shown after a failed start
+const LOG_TAIL: usize = 100;
+
+/// Below this, k3s and the packaged add-ons struggle to start
+const LOW_MEMORY_MB: f64 = 2048.0;
+
+/// Log messages with a known cause
+const LOG_MARKERS: [(&str, &str); 6] = [
+    (
+        "cgroup v1",
+        "The kubelet refuses to run on cgroup v1; switch the Docker host to cgroup v2",
+    ),
+    (
+        "address already in use",
+        "A port k3s listens on is taken inside the container's network",
+    ),
+    (
+        "no space left on device",
+        "Docker's disk is full; prune images or volumes (docker system prune)",
+    ),
+    (
+        "failed to find memory cgroup",
+        "The memory cgroup controller is disabled on the Docker host",
+    ),
+    (
+        "failed to find cpuset cgroup",
+        "The cpuset cgroup controller is disabled on the Docker host",
+    ),
+    (
+        "cannot connect to the docker daemon",
+        "k3s can't reach the Docker socket mounted into the container",
+    ),
+];
+
+/// What is known about a start whose API never became ready
+pub(super) struct StartupFacts<'a> {
+    pub health: &'a ApiHealth,
+    pub api_port: u16,
+    /// None while the container runs
+    pub exit: Option<&'a ContainerExit>,
+    pub cgroup_version: Option<&'a str>,
+    pub memory_mb: Option<f64>,
+    pub logs: &'a str,
+}
+
+/// Causes the facts point at, most specific first
+pub(super) fn likely_causes(facts: &StartupFacts) -> Vec<String> {
+    let mut causes = Vec::new();
+    if let Some(exit) = facts.exit {
+        if exit.oom_killed {
+            causes.push("The k3s container was killed for running out of memory".to_string());
+        }
+        if let Some(error) = &exit.error {
+            causes.push(format!("Docker could not start the container: {}", error));
+        }
+    }
+    let logs = facts.logs.to_lowercase();
+    for (marker, cause) in LOG_MARKERS {
+        if logs.contains(marker) {
+            causes.push(cause.to_string());
+        }
+    }
+    if facts.cgroup_version == Some("1") && !logs.contains("cgroup v1") {
+        causes.push(
+            "The Docker host uses cgroup v1; recent Kubernetes releases need cgroup v2".to_string(),
+        );
+    }
+    if matches!(facts.health, ApiHealth::Tls(_) | ApiHealth::Unhealthy(404)) {
+        causes.push(format!(
+            "Something other than k3s answers on port {}; set another infrastructure.api_port",
+            facts.api_port
+        ));
+    }
+    if let Some(memory) = facts.memory_mb.filter(|&m| m < LOW_MEMORY_MB) {
+        causes.push(format!(
+            "Docker has {:.0} MiB of memory; k3s needs about 2 GiB",
+            memory
+        ));
+    }
+    causes
+}
+
+impl K3sManager {
+    /// Show the container's state, its last logs and the likely causes
+    pub(super) async fn diagnose_api_timeout(
+        &self,
+        health: &ApiHealth,
+        api_port: u16,
+        output_tx: &mpsc::Sender<OutputLine>,
+    ) {
+        let container = &self.config.container_name;
+        let _ = output_tx
+            .send(OutputLine::warning(format!(
+                "The k3s API did not become ready ({}); collecting diagnostics...",
+                health.describe()
+            )))
+            .await;
+
+        let (exit, logs, cgroup_version, capacity) = tokio::join!(
+            self.docker.container_exit(container),
+            self.docker.container_logs(container, LOG_TAIL),
+            self.docker.cgroup_version(),
+            self.docker.host_capacity(),
+        );
+        let state = match &exit {
+            None => "running".to_string(),
+            Some(exit) if exit.oom_killed => {
+                format!("exited with code {} (OOM killed)", exit.exit_code)
+            }
+            Some(exit) => format!("exited with code {}", exit.exit_code),
+        };
+        let _ = output_tx
+            .send(OutputLine::info(format!(
+                "Container {}: {}",
+                container, state
+            )))
+            .await;
+
+        let logs = logs.unwrap_or_else(|e| {
+            tracing::warn!(error = %e, "Failed to read k3s container logs");
+            String::new()
+        });
+        if !logs.is_empty() {
+            let _ = output_tx
+                .send(OutputLine::info(format!(
+                    "--- Last {} log lines of {} ---",
+                    LOG_TAIL, container
+                )))
+                .await;
+            for line in logs.lines() {
+                let _ = output_tx.send(OutputLine::info(line)).await;
+            }
+            let _ = output_tx.send(OutputLine::info("---")).await;
+        }
+
+        let causes = likely_causes(&StartupFacts {
+            health,
+            api_port,
+            exit: exit.as_ref(),
+            cgroup_version: cgroup_version.as_deref(),
+            memory_mb: capacity.map(|c| c.memory_mb),
+            logs: &logs,
+        });
+        if causes.is_empty() {
+            let _ = output_tx
+                .send(OutputLine::info(
+                    "No known cause found; check the logs above or run Diagnostics",
+                ))
+                .await;
+            return;
+        }
+        let _ = output_tx.send(OutputLine::warning("Likely causes:")).await;
+        for cause in causes {
+            let _ = output_tx
+                .send(OutputLine::warning(format!("  • {}", cause)))
+                .await;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn facts<'a>(health: &'a ApiHealth, logs: &'a str) -> StartupFacts<'a> {
+        StartupFacts {
+            health,
+            api_port: 6443,
+            exit: None,
+            cgroup_version: Some("2"),
+            memory_mb: Some(8192.0),
+            logs,
+        }
+    }
+
+    #[test]
+    fn healthy_looking_hosts_have_no_causes() {
+        let health = ApiHealth::ConnectionRefused;
+        assert!(likely_causes(&facts(&health, "Starting k3s v1.35.2+k3s1")).is_empty());
+    }
+
+    #[test]
+    fn causes_come_from_logs_exit_state_and_host() {
+        let health = ApiHealth::Tls("corrupt message".to_string());
+        let exit = ContainerExit {
+            exit_code: 137,
+            oom_killed: true,
+            error: None,
+        };
+        let logs = "E0101 kubelet: failed to run Kubelet: kubelet is configured to not run on a host using cgroup v1";
+        let causes = likely_causes(&StartupFacts {
+            exit: Some(&exit),
+            cgroup_version: Some("1"),
+            memory_mb: Some(1024.0),
+            ..facts(&health, logs)
+        });
+        assert_eq!(causes.len(), 4);
+        assert!(causes[0].contains("out of memory"));
+        assert!(causes[1].contains("cgroup v1"));
+        assert!(causes[2].contains("port 6443"));
+        assert!(causes[3].contains("1024 MiB"));
+    }
+}
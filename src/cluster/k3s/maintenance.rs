@@ -0,0 +1,164 @@
+//! Re-running single setup steps on a running cluster
+//!
+//! Setup steps that failed or were undone (a deleted kubeconfig, a
+//! provisioner with broken config) can be repeated without destroying the
+//! cluster. Traefik is re-deployed by `TraefikManager`; the steps here work
+//! on the container itself.
+
+use anyhow::{Context, Result};
+use tokio::sync::mpsc;
+
+use super::{ClusterStatus, K3sManager};
+use crate::error::K3devError;
+use crate::ui::components::OutputLine;
+
+/// k3s's manifest for local-path-provisioner, applied again to reset it
+const LOCAL_STORAGE_MANIFEST: &str = "/var/lib/rancher/k3s/server/manifests/local-storage.yaml";
+
+/// Where socat is installed in the container
+const SOCAT_PATH: &str = "/usr/local/bin/socat";
+
+/// Seconds to wait for local-path-provisioner to come back
+const LOCAL_PATH_READY_TIMEOUT: u64 = 60;
+
+/// A setup step that can be repeated on a running cluster
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ClusterComponent {
+    Traefik,
+    LocalPath,
+    Socat,
+    Kubeconfig,
+}
+
+impl ClusterComponent {
+    pub const ALL: [ClusterComponent; 4] = [
+        ClusterComponent::Traefik,
+        ClusterComponent::LocalPath,
+        ClusterComponent::Socat,
+        ClusterComponent::Kubeconfig,
+    ];
+
+    /// What running it does, e.g. for a job title
+    pub fn action_label(self) -> &'static str {
+        match self {
+            ClusterComponent::Traefik => "Re-deploy Traefik",
+            ClusterComponent::LocalPath => "Reconfigure local-path-provisioner",
+            ClusterComponent::Socat => "Reinstall socat",
+            ClusterComponent::Kubeconfig => "Regenerate kubeconfig",
+        }
+    }
+
+    pub fn description(self) -> &'static str {
+        match self {
+            ClusterComponent::Traefik => "Renew certificates and apply the Traefik config again",
+            ClusterComponent::LocalPath => {
+                "Re-apply the local-path manifest and restart the provisioner"
+            }
+            ClusterComponent::Socat => "Copy the socat binary into the cluster container again",
+            ClusterComponent::Kubeconfig => "Write ~/.kube/config from the cluster again",
+        }
+    }
+}
+
+impl K3sManager {
+    /// Fail unless the cluster container is running
+    pub(crate) async fn ensure_running(&self) -> Result<()> {
+        match self.get_status().await {
+            ClusterStatus::Running => Ok(()),
+            _ => Err(K3devError::ClusterNotRunning.into()),
+        }
+    }
+
+    /// Re-apply k3s's local-path manifest (provisioner, its ConfigMap and
+    /// the StorageClass) and restart the provisioner
+    pub async fn reconfigure_local_path(
+        &mut self,
+        output_tx: &mpsc::Sender<OutputLine>,
+    ) -> Result<()> {
+        self.ensure_running().await?;
+        let container = &self.config.container_name;
+        let _ = output_tx
+            .send(OutputLine::info("Applying local-path manifest..."))
+            .await;
+        let applied = self
+            .docker
+            .exec_in_container(
+                container,
+                &["k3s", "kubectl", "apply", "-f", LOCAL_STORAGE_MANIFEST],
+            )
+            .await
+            .context("Failed to apply the local-path manifest")?;
+        for line in applied.lines().filter(|l| !l.trim().is_empty()) {
+            let _ = output_tx.send(OutputLine::info(line)).await;
+        }
+
+        // The provisioner reads its ConfigMap only at startup
+        self.docker
+            .exec_in_container(
+                container,
+                &[
+                    "k3s",
+                    "kubectl",
+                    "-n",
+                    "kube-system",
+                    "rollout",
+                    "restart",
+                    "deployment/local-path-provisioner",
+                ],
+            )
+            .await
+            .context("Failed to restart local-path-provisioner")?;
+        let _ = output_tx
+            .send(OutputLine::info("Waiting for local-path-provisioner..."))
+            .await;
+        let ready = self
+            .kube_ops
+            .wait_for_deployment_ready(
+                "local-path-provisioner",
+                "kube-system",
+                LOCAL_PATH_READY_TIMEOUT,
+            )
+            .await?;
+        if !ready {
+            anyhow::bail!(
+                "local-path-provisioner not ready after {}s",
+                LOCAL_PATH_READY_TIMEOUT
+            );
+        }
+        let _ = output_tx
+            .send(OutputLine::success("local-path-provisioner reconfigured"))
+            .await;
+        Ok(())
+    }
+
+    /// Replace the socat binary in the container
+    pub async fn reinstall_socat(&self, output_tx: &mpsc::Sender<OutputLine>) -> Result<()> {
+        self.ensure_running().await?;
+        let _ = output_tx
+            .send(OutputLine::info("Installing socat in container..."))
+            .await;
+        // install_socat skips the copy when a socat is already there
+        self.docker
+            .exec_in_container(&self.config.container_name, &["rm", "-f", SOCAT_PATH])
+            .await
+            .context("Failed to remove the old socat")?;
+        self.install_socat().await?;
+        let _ = output_tx
+            .send(OutputLine::success("socat reinstalled"))
+            .await;
+        Ok(())
+    }
+
+    /// Write ~/.kube/config again from the cluster's kubeconfig
+    pub async fn regenerate_kubeconfig(&self, output_tx: &mpsc::Sender<OutputLine>) -> Result<()> {
+        self.ensure_running().await?;
+        let _ = output_tx
+            .send(OutputLine::info("Setting up kubeconfig..."))
+            .await;
+        self.setup_kubeconfig().await?;
+        let _ = output_tx
+            .send(OutputLine::success("Kubeconfig regenerated"))
+            .await;
+        Ok(())
+    }
+}
@@ -3,19 +3,32 @@
 //! This module provides the K3sManager for managing K3s clusters:
 //! - Starting, stopping, and deleting clusters
 //! - Status checking
-//! - Cluster info display
+//! - Cluster info snapshots
 //!
 //! The implementation is split across multiple files:
 //! - `mod.rs` - Core struct and lifecycle methods
 //! - `setup.rs` - Setup utilities (API wait, socat, kubeconfig, etc.)
+//! - `diagnosis.rs` - Self-diagnosis when the API never becomes ready
+//! - `maintenance.rs` - Repeating setup steps on a running cluster
 //! - `snapshots.rs` - Snapshot-based startup optimization
+//! - `standby.rs` - Warm standby container
+//! - `volume_backup.rs` - Saving and restoring the data volumes as a tar file
 //! - `status.rs` - ClusterStatus enum
 
+mod chaos;
+mod diagnosis;
+mod maintenance;
 mod setup;
 mod snapshots;
+mod standby;
 mod status;
+mod volume_backup;
 
+pub use chaos::{parse_memory_limit, ChaosAction, ChaosTool};
+pub use maintenance::ClusterComponent;
+pub use snapshots::SnapshotInfo;
 pub use status::ClusterStatus;
+pub use volume_backup::default_archive_path;
 
 /// Outcome of a cluster start operation
 pub enum StartOutcome {
@@ -29,15 +42,24 @@ pub enum StartOutcome {
     FreshCreated,
 }
 
-use anyhow::{anyhow, Result};
+use anyhow::{Context, Result};
 use std::sync::Arc;
 use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
 
 use super::config::ClusterConfig;
 use super::docker::{ContainerRunConfig, DockerManager};
+use super::info::{summarize_namespaces, ClusterInfo};
+use super::k3s_versions::ReleaseIndex;
 use super::kube_ops::KubeOps;
+use super::mesh::apply_mesh_status;
 use super::platform::{docker_host_tcp_url, PlatformInfo};
+use super::runtime::{
+    cross_reference, parse_crictl, RuntimeEntry, CRICTL_SCRIPT, POD_CONTAINER_PREFIX,
+};
+use super::services::{parse_probe_output, probe_script, ServiceInfo};
 use crate::config::HookEvent;
+use crate::error::K3devError;
 use crate::hooks::HookExecutor;
 use crate::ui::components::OutputLine;
 
@@ -47,6 +69,8 @@ pub struct K3sManager {
     pub(crate) docker: DockerManager,
     pub(crate) platform: PlatformInfo,
     pub(crate) kube_ops: KubeOps,
+    /// Readiness gates still being waited for after start returned
+    pub(crate) pending_readiness: Option<JoinHandle<()>>,
 }
 
 impl K3sManager {
@@ -68,6 +92,57 @@ impl K3sManager {
         format!("{}/volumes/k3s-local-pv-data/_data", docker_root)
     }
 
+    /// PV storage path for snapshot copies and volume stats: the configured
+    /// host directory, else the default Docker volume path
+    pub(crate) fn configured_pv_storage_path(config: &ClusterConfig) -> String {
+        match config.storage.host_path() {
+            Some(path) => path.to_string_lossy().into_owned(),
+            None => Self::LOCAL_PV_STORAGE_PATH.to_string(),
+        }
+    }
+
+    /// PV storage path, the same in the container and on the Docker host
+    /// so pods can mount PV directories: the configured host directory,
+    /// else the PV volume's directory under Docker's data root
+    pub(crate) fn pv_storage_path(&self, docker_root: &str) -> String {
+        match self.config.storage.host_path() {
+            Some(path) => path.to_string_lossy().into_owned(),
+            None => Self::local_pv_storage_path(docker_root),
+        }
+    }
+
+    /// Container mount for PV storage (source, target, options)
+    pub(crate) fn pv_storage_mount(&self, pv_storage_path: &str) -> (String, String, String) {
+        match self.config.storage.host_path() {
+            // Plain bind mount of the host directory
+            Some(_) => (
+                pv_storage_path.to_string(),
+                pv_storage_path.to_string(),
+                String::new(),
+            ),
+            // Docker volume - accessible to pod containers via Docker's volume path
+            None => (
+                self.config.local_pv_volume_name(),
+                pv_storage_path.to_string(),
+                "volume".to_string(),
+            ),
+        }
+    }
+
+    /// Create the PV host directory or the PV Docker volume
+    pub(crate) async fn create_pv_storage(&self) -> Result<()> {
+        match self.config.storage.host_path() {
+            Some(path) => tokio::fs::create_dir_all(&path)
+                .await
+                .with_context(|| format!("Failed to create PV directory {}", path.display())),
+            None => {
+                self.docker
+                    .create_volume(&self.config.local_pv_volume_name())
+                    .await
+            }
+        }
+    }
+
     /// Get kubelet root dir based on Docker's actual data root directory
     pub(crate) fn kubelet_root_dir(docker_root: &str) -> String {
         format!("{}/kubelet", docker_root)
@@ -75,27 +150,28 @@ impl K3sManager {
 
     pub async fn new(config: Arc<ClusterConfig>) -> Result<Self> {
         let platform = PlatformInfo::detect()?;
-        let socket_path = platform.docker_socket_path().await?;
-        let mut docker = DockerManager::new(socket_path)?;
-
-        // Negotiate API version for compatibility with older Docker versions
-        if let Err(e) = docker.negotiate_api_version().await {
-            tracing::warn!(
-                "Docker API version negotiation failed (using default): {:#}",
-                e
-            );
-        }
-
-        // Warn if Docker daemon architecture differs from binary's compile-time target_arch
-        docker.check_architecture_mismatch().await;
-
-        let kube_ops = KubeOps::new();
-
+        let docker = DockerManager::connect_platform(&platform).await?;
         Ok(Self {
             config,
             docker,
             platform,
+            kube_ops: KubeOps::new(),
+            pending_readiness: None,
+        })
+    }
+
+    /// Build on connections that already exist (see `SharedClients`)
+    pub(crate) fn with_connections(
+        config: Arc<ClusterConfig>,
+        docker: DockerManager,
+        kube_ops: KubeOps,
+    ) -> Result<Self> {
+        Ok(Self {
+            config,
+            docker,
+            platform: PlatformInfo::detect()?,
             kube_ops,
+            pending_readiness: None,
         })
     }
 
@@ -134,11 +210,7 @@ impl K3sManager {
             .await;
 
         // Check Docker accessibility
-        if !self.docker.is_accessible().await {
-            return Err(anyhow!(
-                "Docker is not accessible. Please start Docker first."
-            ));
-        }
+        self.docker.ensure_accessible().await?;
 
         // Check Docker cgroup driver compatibility
         self.docker.check_cgroup_driver().await?;
@@ -180,19 +252,54 @@ impl K3sManager {
             return Ok(StartOutcome::StartedExisting);
         }
 
+        // Restored volumes must not be overwritten with the snapshot's data
+        let restored = self
+            .docker
+            .get_volume_labels(&self.config.rancher_volume_name())
+            .await
+            .contains_key(volume_backup::LABEL_RESTORED);
+        if restored {
+            let _ = output_tx
+                .send(OutputLine::info(
+                    "Creating cluster over restored volumes (snapshot not used)...",
+                ))
+                .await;
+        }
+
         // Create new cluster - check snapshot first
-        if self.config.speedup.use_snapshot {
+        if self.config.speedup.use_snapshot && !restored {
             let snapshot_image = self.get_snapshot_image_name();
 
-            // Fast path: use snapshot if it exists
+            // Fast path: use snapshot if it exists and matches the config
             if self.docker.image_exists(&snapshot_image).await {
-                let is_deep = K3sManager::is_deep_snapshot(&self.docker, &snapshot_image).await;
-                let _ = output_tx
-                    .send(OutputLine::info("Using snapshot for faster startup..."))
-                    .await;
-                self.start_from_snapshot(&snapshot_image, is_deep, &output_tx)
-                    .await?;
-                return Ok(StartOutcome::StartedFromSnapshot);
+                let labels = self.docker.get_image_labels(&snapshot_image).await;
+                let docker_root = self.docker.get_docker_root_dir().await;
+                match Self::snapshot_incompatibility(&labels, &self.config, &docker_root) {
+                    None => {
+                        let is_deep = Self::is_deep_snapshot(&labels);
+                        let _ = output_tx
+                            .send(OutputLine::info("Using snapshot for faster startup..."))
+                            .await;
+                        self.start_from_snapshot(&snapshot_image, is_deep, &output_tx)
+                            .await?;
+                        return Ok(StartOutcome::StartedFromSnapshot);
+                    }
+                    Some(reason) => {
+                        tracing::info!(snapshot = %snapshot_image, %reason, "Snapshot is stale");
+                        let _ = output_tx
+                            .send(OutputLine::warning(format!(
+                                "Snapshot {} is stale ({}), rebuilding...",
+                                snapshot_image, reason
+                            )))
+                            .await;
+                        if let Err(e) = self.docker.remove_image(&snapshot_image).await {
+                            tracing::warn!(error = %e, "Failed to remove stale snapshot");
+                        }
+                        if let Err(e) = self.remove_standby().await {
+                            tracing::warn!(error = %e, "Failed to remove warm standby");
+                        }
+                    }
+                }
             }
 
             // Slow path: create cluster and snapshot
@@ -226,6 +333,28 @@ impl K3sManager {
         }
     }
 
+    /// Warn about a k3s version that won't pull or no longer gets patches;
+    /// the pull goes ahead either way
+    async fn check_k3s_version(&self, output_tx: &mpsc::Sender<OutputLine>) {
+        let Ok(index) = ReleaseIndex::new() else {
+            return;
+        };
+        let arch = self.docker.platform_architecture().await;
+        match index.check(&self.config.k3s_version, arch.as_deref()).await {
+            Ok(issues) => {
+                for issue in issues {
+                    let _ = output_tx
+                        .send(OutputLine::warning(format!(
+                            "k3s {}: {}",
+                            self.config.k3s_version, issue
+                        )))
+                        .await;
+                }
+            }
+            Err(e) => tracing::debug!(error = %e, "Skipped the k3s version check"),
+        }
+    }
+
     /// Create a new k3s cluster
     async fn create_cluster(&mut self, output_tx: &mpsc::Sender<OutputLine>) -> Result<()> {
         let _ = output_tx
@@ -238,6 +367,9 @@ impl K3sManager {
             .await;
         let image = self.config.k3s_image();
         let image_exists = self.docker.image_exists(&image).await;
+        if !image_exists {
+            self.check_k3s_version(output_tx).await;
+        }
 
         // Create volume, PV directory, network, and pull image in parallel
         let pull_future = async {
@@ -258,13 +390,15 @@ impl K3sManager {
                         "Creating Docker volume for rancher data...",
                     ))
                     .await;
-                self.docker.create_volume(Self::RANCHER_VOLUME_NAME).await
+                self.docker
+                    .create_volume(&self.config.rancher_volume_name())
+                    .await
             },
             async {
                 let _ = output_tx
-                    .send(OutputLine::info("Creating Docker volume for PV storage..."))
+                    .send(OutputLine::info("Creating PV storage..."))
                     .await;
-                self.docker.create_volume(Self::LOCAL_PV_VOLUME_NAME).await
+                self.create_pv_storage().await
             },
             async {
                 let _ = output_tx
@@ -279,25 +413,13 @@ impl K3sManager {
         let socket_path = self.platform.docker_socket_path().await?;
         let cgroup_driver = "cgroupfs";
         let docker_root = self.docker.get_docker_root_dir().await;
-        let pv_storage_path = Self::local_pv_storage_path(&docker_root);
+        let pv_storage_path = self.pv_storage_path(&docker_root);
         let kubelet_root = Self::kubelet_root_dir(&docker_root);
         let iptables_mode = PlatformInfo::detect_iptables_mode();
 
         // Build port mappings
         #[allow(unused_mut)]
-        let mut ports: Vec<(u16, u16)> = self
-            .config
-            .port_mappings()
-            .iter()
-            .filter_map(|p| {
-                let parts: Vec<&str> = p.split(':').collect();
-                if parts.len() == 2 {
-                    Some((parts[0].parse().ok()?, parts[1].parse().ok()?))
-                } else {
-                    None
-                }
-            })
-            .collect();
+        let mut ports = self.published_ports();
 
         // On macOS, publish a port for the Docker API relay (socat) so
         // `k3dev docker` can access the raw Docker daemon from the host.
@@ -335,8 +457,7 @@ impl K3sManager {
                  --docker{docker_endpoint} \
                  --disable=metrics-server \
                  --disable=servicelb \
-                 --disable-cloud-controller \
-                 --disable-network-policy \
+                 --disable-cloud-controller{network_policy} \
                  --flannel-backend=host-gw \
                  --default-local-storage-path {pv} \
                  --service-node-port-range 80-32767 \
@@ -346,6 +467,7 @@ impl K3sManager {
                  --kube-apiserver-arg=enable-admission-plugins=NodeRestriction \
                  --kube-controller-manager-arg=concurrent-deployment-syncs=1",
                 docker_endpoint = docker_endpoint,
+                network_policy = self.config.network_policy_flag(),
                 pv = pv_storage_path,
                 kubelet = kubelet_root,
                 cgroup = cgroup_driver
@@ -368,16 +490,12 @@ impl K3sManager {
             ),
             // Docker volume for rancher data (server config, agent data) - no sudo required
             (
-                Self::RANCHER_VOLUME_NAME.to_string(),
+                self.config.rancher_volume_name(),
                 Self::RANCHER_DATA_PATH.to_string(),
                 "volume".to_string(),
             ),
-            // Docker volume for local PV storage - accessible to pod containers via Docker's volume path
-            (
-                Self::LOCAL_PV_VOLUME_NAME.to_string(),
-                pv_storage_path.clone(),
-                "volume".to_string(),
-            ),
+            // Local PV storage
+            self.pv_storage_mount(&pv_storage_path),
         ];
         let mut env = vec![
             // Tell K3s to use the same iptables backend as the host
@@ -416,7 +534,7 @@ impl K3sManager {
             entrypoint: Some(String::new()),
             command: Some(k3s_command),
             security_opt: vec!["apparmor=unconfined".to_string()],
-            labels: Default::default(),
+            labels: self.config.container_labels(),
             auto_remove: false,
         };
 
@@ -552,6 +670,11 @@ impl K3sManager {
                 .await;
         }
 
+        // The standby is attached to the network removed below
+        if let Err(e) = self.remove_standby().await {
+            tracing::warn!(error = %e, "Failed to remove warm standby");
+        }
+
         // Run all cleanup tasks in parallel:
         // - Pod containers (k8s_*) can be force-removed in parallel
         // - Network removal will fail if containers still attached, but we retry
@@ -560,6 +683,8 @@ impl K3sManager {
             .send(OutputLine::info("Cleaning up cluster resources..."))
             .await;
 
+        let rancher_volume = self.config.rancher_volume_name();
+        let pv_volume = self.config.local_pv_volume_name();
         let (
             pods_result,
             network_result,
@@ -569,8 +694,8 @@ impl K3sManager {
         ) = tokio::join!(
             self.docker.cleanup_containers_by_prefix("k8s_"),
             self.docker.remove_network(&self.config.network_name),
-            self.docker.remove_volume(Self::RANCHER_VOLUME_NAME),
-            self.docker.remove_volume(Self::LOCAL_PV_VOLUME_NAME),
+            self.docker.remove_volume(&rancher_volume),
+            self.docker.remove_volume(&pv_volume),
             self.cleanup_kubeconfig(),
         );
 
@@ -587,69 +712,84 @@ impl K3sManager {
         Ok(())
     }
 
-    /// Get cluster info
-    pub async fn info(&mut self, output_tx: mpsc::Sender<OutputLine>) -> Result<()> {
-        let _ = output_tx
-            .send(OutputLine::info("=== K3s Cluster Info ==="))
-            .await;
-
-        // Cluster status
+    /// Gather the cluster info snapshot
+    pub async fn info(&mut self) -> ClusterInfo {
         let status = self.get_status().await;
-        let _ = output_tx
-            .send(OutputLine::info(format!("Status: {:?}", status)))
-            .await;
-
+        let mut info = ClusterInfo::new(&self.config, status);
         if status != ClusterStatus::Running {
-            return Ok(());
+            return info;
         }
+        info.set_api_host_port(self.api_host_port().await);
 
-        // Kubernetes version
-        if let Ok(version) = self.kube_ops.get_version().await {
-            let _ = output_tx.send(OutputLine::info(version)).await;
-        }
+        info.version = self
+            .kube_ops
+            .get_version()
+            .await
+            .ok()
+            .map(|v| v.trim_start_matches("Server Version: ").to_string());
+        info.nodes = self.kube_ops.list_nodes().await.unwrap_or_default();
+
+        let namespaces = self.kube_ops.list_namespaces().await.unwrap_or_default();
+        let phases = self.kube_ops.list_pod_phases().await.unwrap_or_default();
+        info.namespaces = summarize_namespaces(namespaces, &phases);
+        let injected = self
+            .kube_ops
+            .list_injected_namespaces()
+            .await
+            .unwrap_or_default();
+        let meshed = self.kube_ops.list_meshed_pods().await.unwrap_or_default();
+        apply_mesh_status(&mut info.namespaces, &injected, &meshed);
 
-        // Nodes
-        let _ = output_tx.send(OutputLine::info("\n=== Nodes ===")).await;
-        let _ = output_tx
-            .send(OutputLine::info(format!(
-                "{:<20} {:<10} {:<15} {:<15} {}",
-                "NAME", "STATUS", "ROLES", "INTERNAL-IP", "VERSION"
-            )))
-            .await;
-        if let Ok(nodes) = self.kube_ops.list_nodes().await {
-            for node in nodes {
-                let _ = output_tx
-                    .send(OutputLine::info(node.to_wide_string()))
-                    .await;
-            }
-        }
+        info.components = self
+            .kube_ops
+            .list_deployments("kube-system")
+            .await
+            .unwrap_or_default();
+        info
+    }
 
-        // Namespaces
-        let _ = output_tx
-            .send(OutputLine::info("\n=== Namespaces ==="))
-            .await;
-        if let Ok(namespaces) = self.kube_ops.list_namespaces().await {
-            for ns in namespaces {
-                let _ = output_tx.send(OutputLine::info(format!("  {}", ns))).await;
-            }
+    /// List services and probe their ClusterIP ports from inside the cluster
+    /// container. Ports stay unprobed when the probe can't run.
+    pub async fn services(&mut self) -> Result<Vec<ServiceInfo>> {
+        if self.get_status().await != ClusterStatus::Running {
+            return Err(K3devError::ClusterNotRunning.into());
         }
+        let mut services = self.kube_ops.list_services().await?;
+        services.sort_by(|a, b| (&a.namespace, &a.name).cmp(&(&b.namespace, &b.name)));
 
-        // System pods
-        let _ = output_tx
-            .send(OutputLine::info("\n=== System Pods ==="))
-            .await;
-        let _ = output_tx
-            .send(OutputLine::info(format!(
-                "{:<50} {:<10} {}",
-                "NAME", "READY", "STATUS"
-            )))
-            .await;
-        if let Ok(pods) = self.kube_ops.list_pods("kube-system").await {
-            for pod in pods {
-                let _ = output_tx.send(OutputLine::info(pod.to_string_line())).await;
+        let targets: Vec<String> = services.iter().flat_map(|s| s.probe_targets()).collect();
+        if targets.is_empty() {
+            return Ok(services);
+        }
+        match self
+            .docker
+            .exec_in_container(
+                &self.config.container_name,
+                &["sh", "-c", &probe_script(&targets)],
+            )
+            .await
+        {
+            Ok(output) => {
+                let open = parse_probe_output(&output);
+                for service in &mut services {
+                    service.apply_probe(&open);
+                }
             }
+            Err(e) => tracing::warn!(error = %e, "Service port probe failed"),
         }
+        Ok(services)
+    }
 
-        Ok(())
+    /// What `k3s crictl` lists, joined with Docker's pod containers
+    pub async fn runtime(&mut self) -> Result<Vec<RuntimeEntry>> {
+        if self.get_status().await != ClusterStatus::Running {
+            return Err(K3devError::ClusterNotRunning.into());
+        }
+        let (crictl, docker) = tokio::join!(
+            self.docker
+                .exec_in_container(&self.config.container_name, &["sh", "-c", CRICTL_SCRIPT],),
+            self.docker.list_container_summaries(POD_CONTAINER_PREFIX),
+        );
+        Ok(cross_reference(parse_crictl(&crictl?)?, &docker?))
     }
 }
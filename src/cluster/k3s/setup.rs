@@ -13,11 +13,55 @@ use tokio::sync::mpsc;
 use tokio::time::sleep;
 
 use super::K3sManager;
+use crate::cluster::api_probe::{ApiHealth, ApiProbe};
+use crate::cluster::config::ClusterConfig;
 use crate::cluster::kube_ops::KubeOps;
 use crate::cluster::platform::PlatformInfo;
+use crate::cluster::readiness::{partition_gates, wait_for_gate};
+use crate::error::K3devError;
 use crate::ui::components::OutputLine;
 
 impl K3sManager {
+    /// Host:container ports for a new cluster container. When the API port
+    /// is taken on the host, the API is published on the next free port;
+    /// `api_host_port` reads back where it ended up.
+    pub(super) fn published_ports(&self) -> Vec<(u16, u16)> {
+        let mut ports: Vec<(u16, u16)> = self
+            .config
+            .port_mappings()
+            .iter()
+            .filter_map(|p| {
+                let parts: Vec<&str> = p.split(':').collect();
+                if parts.len() == 2 {
+                    Some((parts[0].parse().ok()?, parts[1].parse().ok()?))
+                } else {
+                    None
+                }
+            })
+            .collect();
+        // A remote daemon's ports can't be checked from here
+        if PlatformInfo::docker_remote_host().is_none() {
+            if let Some(port) = remap_api_port(&mut ports, |port| {
+                std::net::TcpListener::bind(("127.0.0.1", port)).is_ok()
+            }) {
+                tracing::info!(
+                    configured = self.config.api_port,
+                    port,
+                    "API port is in use, publishing the API on another port"
+                );
+            }
+        }
+        ports
+    }
+
+    /// Host port the running container publishes the API on
+    pub(crate) async fn api_host_port(&self) -> u16 {
+        self.docker
+            .published_port(&self.config.container_name, ClusterConfig::K3S_API_PORT)
+            .await
+            .unwrap_or(self.config.api_port)
+    }
+
     /// Wait for k3s API to become accessible
     /// Uses async HTTP client with exponential backoff for faster detection
     pub(super) async fn wait_for_api(&self, output_tx: &mpsc::Sender<OutputLine>) -> Result<()> {
@@ -25,13 +69,6 @@ impl K3sManager {
             .send(OutputLine::info("Waiting for k3s API..."))
             .await;
 
-        // Create HTTP client with short timeout and TLS disabled (self-signed cert)
-        let client = reqwest::Client::builder()
-            .danger_accept_invalid_certs(true)
-            .timeout(Duration::from_millis(500))
-            .build()
-            .context("Failed to create HTTP client")?;
-
         let start_time = std::time::Instant::now();
         let mut interval = Duration::from_millis(100); // Start fast
         let max_interval = Duration::from_secs(2);
@@ -42,39 +79,40 @@ impl K3sManager {
         let api_host = PlatformInfo::docker_remote_host()
             .unwrap_or("127.0.0.1")
             .to_string();
+        let api_port = self.api_host_port().await;
+        if api_port != self.config.api_port {
+            let _ = output_tx
+                .send(OutputLine::warning(format!(
+                    "Port {} is in use; the Kubernetes API is published on {}",
+                    self.config.api_port, api_port
+                )))
+                .await;
+        }
 
+        // Short timeout, certificate checks off (self-signed cert)
+        let probe = ApiProbe::new(&api_host, api_port, Duration::from_millis(500))?;
+        let mut health = ApiHealth::ConnectionRefused;
         for attempt in 0..max_attempts {
-            match client
-                .get(format!("https://{}:6443/healthz", api_host))
-                .send()
-                .await
-            {
-                Ok(resp) => {
-                    // 200 OK or 401 Unauthorized both mean API is up
-                    // 401 means auth is required but server is responding
-                    if resp.status().is_success() || resp.status() == 401 {
-                        let elapsed = start_time.elapsed();
-                        tracing::debug!(
-                            "API available after {} attempts ({}ms)",
-                            attempt + 1,
-                            elapsed.as_millis()
-                        );
-                        return Ok(());
-                    }
-                    tracing::debug!("API check: status {}", resp.status());
-                }
-                Err(e) => {
-                    tracing::debug!("API check failed: {}", e);
-                }
+            health = probe.check().await;
+            if health.is_up() {
+                let elapsed = start_time.elapsed();
+                tracing::debug!(
+                    "API available after {} attempts ({}ms)",
+                    attempt + 1,
+                    elapsed.as_millis()
+                );
+                return Ok(());
             }
+            tracing::debug!("API check: {}", health.describe());
 
             // Report progress every 5 seconds
             if last_progress_report.elapsed() >= Duration::from_secs(5) {
                 let elapsed = start_time.elapsed();
                 let _ = output_tx
                     .send(OutputLine::info(format!(
-                        "Still waiting for API... ({}s elapsed)",
-                        elapsed.as_secs()
+                        "Still waiting for API... ({}s elapsed, {})",
+                        elapsed.as_secs(),
+                        health.describe()
                     )))
                     .await;
                 last_progress_report = std::time::Instant::now();
@@ -85,7 +123,13 @@ impl K3sManager {
             interval = std::cmp::min(interval * 2, max_interval);
         }
 
-        Err(anyhow!("Timeout waiting for k3s API"))
+        self.diagnose_api_timeout(&health, api_port, output_tx)
+            .await;
+        Err(K3devError::ApiTimeout {
+            url: probe.url().to_string(),
+            health: health.describe(),
+        }
+        .into())
     }
 
     /// Install socat in the k3s container using embedded static binary.
@@ -196,6 +240,7 @@ impl K3sManager {
 
         let kubeconfig_path = kube_dir.join("config");
         let temp_config = kube_dir.join("k3s-config.tmp");
+        let api_port = self.api_host_port().await;
 
         // Wait for k3s to generate kubeconfig
         let max_retries = 30;
@@ -210,14 +255,10 @@ impl K3sManager {
 
             if let Ok(content) = result {
                 if !content.is_empty() && content.contains("clusters:") {
-                    // Replace 127.0.0.1 with the remote host's address when Docker is remote.
+                    // Use the remote host's address when Docker is remote.
                     // For local Docker, keep 127.0.0.1 (matches k3s default and SAN certs).
-                    let fixed_content =
-                        if let Some(remote_host) = PlatformInfo::docker_remote_host() {
-                            content.replace("127.0.0.1", remote_host)
-                        } else {
-                            content
-                        };
+                    let api_host = PlatformInfo::docker_remote_host().unwrap_or("127.0.0.1");
+                    let fixed_content = rewrite_api_server(&content, api_host, api_port);
 
                     fs::write(&temp_config, &fixed_content).await?;
                     fs::copy(&temp_config, &kubeconfig_path).await?;
@@ -252,82 +293,101 @@ impl K3sManager {
         Ok(())
     }
 
-    /// Wait for cluster to be fully ready
+    /// Wait for the `start.wait_for` gates; the other gates are tracked in
+    /// the background and left in `pending_readiness`
     pub(super) async fn wait_for_cluster_ready(
         &mut self,
         output_tx: &mpsc::Sender<OutputLine>,
     ) -> Result<()> {
-        let _ = output_tx
-            .send(OutputLine::info("Waiting for cluster components..."))
-            .await;
-
-        // Wait for core deployments in parallel for faster startup
-        // Note: metrics-server and servicelb are disabled
-        // We create separate KubeOps instances to avoid borrow checker issues
-        let tx1 = output_tx.clone();
-        let tx2 = output_tx.clone();
-
-        let coredns_task = tokio::spawn(async move {
-            let _ = tx1.send(OutputLine::info("Waiting for coredns...")).await;
-            let mut kube_ops = KubeOps::new();
-            match kube_ops
-                .wait_for_deployment_ready("coredns", "kube-system", 60)
-                .await
-            {
-                Ok(true) => Ok::<(), anyhow::Error>(()),
-                Ok(false) => {
-                    let _ = tx1
-                        .send(OutputLine::warning(
-                            "coredns not ready after 60s, continuing...",
-                        ))
-                        .await;
-                    Ok(())
-                }
-                Err(_) => {
-                    let _ = tx1
-                        .send(OutputLine::warning(
-                            "coredns not ready after 60s, continuing...",
-                        ))
-                        .await;
-                    Ok(())
-                }
-            }
-        });
+        let timeout = self.config.start.ready_timeout;
+        let (blocking, background) = partition_gates(&self.config.start.wait_for);
 
-        let provisioner_task = tokio::spawn(async move {
-            let _ = tx2
-                .send(OutputLine::info("Waiting for local-path-provisioner..."))
+        if !blocking.is_empty() {
+            let _ = output_tx
+                .send(OutputLine::info("Waiting for cluster components..."))
                 .await;
-            let mut kube_ops = KubeOps::new();
-            match kube_ops
-                .wait_for_deployment_ready("local-path-provisioner", "kube-system", 60)
-                .await
-            {
-                Ok(true) => Ok::<(), anyhow::Error>(()),
-                Ok(false) => {
-                    let _ = tx2
-                        .send(OutputLine::warning(
-                            "local-path-provisioner not ready after 60s, continuing...",
-                        ))
-                        .await;
-                    Ok(())
-                }
-                Err(_) => {
-                    let _ = tx2
-                        .send(OutputLine::warning(
-                            "local-path-provisioner not ready after 60s, continuing...",
-                        ))
-                        .await;
-                    Ok(())
+        }
+
+        // Gates are waited for in parallel; each uses its own KubeOps
+        let blocking: Vec<_> = blocking
+            .into_iter()
+            .map(|gate| tokio::spawn(wait_for_gate(gate, timeout, output_tx.clone())))
+            .collect();
+        let background: Vec<_> = background
+            .into_iter()
+            .map(|gate| tokio::spawn(wait_for_gate(gate, timeout, output_tx.clone())))
+            .collect();
+
+        for task in blocking {
+            task.await?;
+        }
+
+        self.pending_readiness = (!background.is_empty()).then(|| {
+            tokio::spawn(async move {
+                for task in background {
+                    let _ = task.await;
                 }
-            }
+            })
         });
 
-        // Wait for both tasks to complete
-        let (coredns_result, provisioner_result) = tokio::join!(coredns_task, provisioner_task);
-        coredns_result??;
-        provisioner_result??;
-
         Ok(())
     }
 }
+
+/// Move the API mapping to a free host port when its port is taken.
+/// Returns the new host port.
+fn remap_api_port(ports: &mut [(u16, u16)], is_free: impl Fn(u16) -> bool) -> Option<u16> {
+    let index = ports
+        .iter()
+        .position(|&(_, container)| container == ClusterConfig::K3S_API_PORT)?;
+    let configured = ports[index].0;
+    if is_free(configured) {
+        return None;
+    }
+    let taken: Vec<u16> = ports.iter().map(|&(host, _)| host).collect();
+    let port = (configured.saturating_add(1)..=u16::MAX)
+        .take(100)
+        .find(|port| !taken.contains(port) && is_free(*port))?;
+    ports[index].0 = port;
+    Some(port)
+}
+
+/// Point the server of k3s's kubeconfig (`https://127.0.0.1:6443`) at the
+/// host and port the API is published on
+fn rewrite_api_server(kubeconfig: &str, host: &str, port: u16) -> String {
+    kubeconfig.replace(
+        &format!("https://127.0.0.1:{}", ClusterConfig::K3S_API_PORT),
+        &format!("https://{}:{}", host, port),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn busy_api_port_moves_to_the_next_free_one() {
+        let mut ports = vec![(6443, 6443), (6444, 80), (443, 443)];
+        assert_eq!(remap_api_port(&mut ports, |_| true), None);
+        assert_eq!(ports[0], (6443, 6443));
+
+        // 6444 is published for HTTP already
+        let busy = [6443, 6445];
+        assert_eq!(
+            remap_api_port(&mut ports, |port| !busy.contains(&port)),
+            Some(6446)
+        );
+        assert_eq!(ports[0], (6446, 6443));
+    }
+
+    #[test]
+    fn kubeconfig_points_at_the_published_port() {
+        let kubeconfig = "clusters:\n- cluster:\n    server: https://127.0.0.1:6443\n";
+        assert_eq!(
+            rewrite_api_server(kubeconfig, "127.0.0.1", 6444),
+            "clusters:\n- cluster:\n    server: https://127.0.0.1:6444\n"
+        );
+        assert!(rewrite_api_server(kubeconfig, "10.0.0.5", 6443)
+            .contains("server: https://10.0.0.5:6443"));
+    }
+}
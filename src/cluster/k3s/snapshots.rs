@@ -4,14 +4,17 @@
 //! - Creating snapshots of initialized clusters
 //! - Starting clusters from snapshots
 //! - Deep snapshots (post-Traefik) for skipping wait_for_cluster_ready
+//! - Validating snapshot labels against the current config
+//! - Saving named snapshots and recreating the cluster from a chosen one
 //! - Cleaning up old snapshots
 
-use anyhow::Result;
+use anyhow::{bail, Result};
+use chrono::{DateTime, Utc};
 use sha2::{Digest, Sha256};
 use std::collections::HashMap;
 use tokio::sync::mpsc;
 
-use super::K3sManager;
+use super::{K3sManager, StartOutcome};
 use crate::cluster::config::ClusterConfig;
 use crate::cluster::docker::{ContainerRunConfig, DockerManager};
 use crate::cluster::platform::{docker_host_tcp_url, PlatformInfo};
@@ -19,6 +22,68 @@ use crate::config::HookEvent;
 use crate::hooks::HookExecutor;
 use crate::ui::components::OutputLine;
 
+/// Image labels written on every snapshot
+pub(super) const LABEL_CREATED: &str = "k3dev.snapshot.created";
+const LABEL_DEEP: &str = "k3dev.snapshot.deep";
+const LABEL_K3S_VERSION: &str = "k3dev.k3s_version";
+const LABEL_CONFIG_HASH: &str = "k3dev.config_hash";
+const LABEL_DOMAIN: &str = "k3dev.domain";
+const LABEL_DOCKER_ROOT: &str = "k3dev.docker_root";
+/// Name given to a snapshot saved from the Snapshots view
+const LABEL_NAME: &str = "k3dev.snapshot.name";
+
+/// Prefix of every snapshot image
+const SNAPSHOT_PREFIX: &str = "k3dev-snapshot-";
+
+/// A snapshot image, as listed in the Snapshots view
+#[derive(Debug, Clone, PartialEq)]
+pub struct SnapshotInfo {
+    /// Image reference, e.g. "k3dev-snapshot-v1-33-4-k3s1-a7b3c2d1-seeded:latest"
+    pub image: String,
+    /// Name it was saved under; None for the snapshot k3dev takes itself
+    pub name: Option<String>,
+    pub created: Option<DateTime<Utc>>,
+    pub k3s_version: Option<String>,
+    pub deep: bool,
+    /// Why the current config can't boot it, if it can't
+    pub incompatibility: Option<String>,
+}
+
+impl SnapshotInfo {
+    fn from_labels(
+        image: String,
+        labels: &HashMap<String, String>,
+        config: &ClusterConfig,
+        docker_root: &str,
+    ) -> Self {
+        Self {
+            name: labels.get(LABEL_NAME).cloned(),
+            created: labels
+                .get(LABEL_CREATED)
+                .and_then(|t| DateTime::parse_from_rfc3339(t).ok())
+                .map(|t| t.with_timezone(&Utc)),
+            k3s_version: labels.get(LABEL_K3S_VERSION).cloned(),
+            deep: K3sManager::is_deep_snapshot(labels),
+            incompatibility: K3sManager::snapshot_incompatibility(labels, config, docker_root),
+            image,
+        }
+    }
+}
+
+/// Image tag suffix for a snapshot name: lowercase letters, digits and
+/// dashes, since it becomes part of the image name
+fn sanitize_snapshot_name(name: &str) -> String {
+    let mut out = String::new();
+    for c in name.trim().to_lowercase().chars() {
+        if c.is_ascii_alphanumeric() {
+            out.push(c);
+        } else if !out.is_empty() && !out.ends_with('-') {
+            out.push('-');
+        }
+    }
+    out.trim_end_matches('-').to_string()
+}
+
 impl K3sManager {
     /// Sanitize k3s version string for use in snapshot image name
     /// Replaces dots and special chars with dashes
@@ -27,12 +92,6 @@ impl K3sManager {
         version.replace(['.', '/'], "-")
     }
 
-    /// Calculate config hash from fields that affect cluster state
-    /// Excludes: cluster_name, speedup settings, logging config
-    pub(super) fn calculate_config_hash(&self) -> String {
-        Self::calculate_config_hash_static(&self.config)
-    }
-
     /// Compute snapshot image name from config (static version)
     pub(crate) fn compute_snapshot_image_name(config: &ClusterConfig) -> String {
         let version = Self::sanitize_version(&config.k3s_version);
@@ -47,17 +106,71 @@ impl K3sManager {
         Self::compute_snapshot_image_name(&self.config)
     }
 
-    /// Check if a snapshot image is a deep snapshot (created after Traefik + hooks)
-    pub(crate) async fn is_deep_snapshot(docker: &DockerManager, image: &str) -> bool {
-        docker
-            .get_image_labels(image)
-            .await
-            .get("k3dev.snapshot.deep")
-            .map(|v| v == "true")
-            .unwrap_or(false)
+    /// Check if snapshot labels mark a deep snapshot (created after Traefik + hooks)
+    pub(super) fn is_deep_snapshot(labels: &HashMap<String, String>) -> bool {
+        labels.get(LABEL_DEEP).is_some_and(|v| v == "true")
+    }
+
+    /// Labels describing what a snapshot was built from
+    fn snapshot_labels(
+        config: &ClusterConfig,
+        docker_root: &str,
+        deep: bool,
+    ) -> HashMap<String, String> {
+        let mut labels = HashMap::from([
+            (LABEL_CREATED.to_string(), chrono::Utc::now().to_rfc3339()),
+            (LABEL_K3S_VERSION.to_string(), config.k3s_version.clone()),
+            (
+                LABEL_CONFIG_HASH.to_string(),
+                Self::calculate_config_hash_static(config),
+            ),
+            (LABEL_DOMAIN.to_string(), config.domain.clone()),
+            (LABEL_DOCKER_ROOT.to_string(), docker_root.to_string()),
+        ]);
+        if deep {
+            labels.insert(LABEL_DEEP.to_string(), "true".to_string());
+        }
+        labels
     }
 
-    /// Static version of calculate_config_hash
+    /// Why a snapshot can't be used with the current config, if it can't.
+    /// The image name already encodes version and hash, so a mismatch here
+    /// means the image was retagged or built from different settings.
+    pub(super) fn snapshot_incompatibility(
+        labels: &HashMap<String, String>,
+        config: &ClusterConfig,
+        docker_root: &str,
+    ) -> Option<String> {
+        let expected_hash = Self::calculate_config_hash_static(config);
+        let checks = [
+            (
+                LABEL_K3S_VERSION,
+                "k3s version",
+                config.k3s_version.as_str(),
+            ),
+            (LABEL_CONFIG_HASH, "infrastructure settings", &expected_hash),
+        ];
+        for (label, what, expected) in checks {
+            match labels.get(label) {
+                Some(actual) if actual == expected => {}
+                Some(actual) => {
+                    return Some(format!("{} changed: {} -> {}", what, actual, expected))
+                }
+                None => return Some(format!("missing {} label", label)),
+            }
+        }
+
+        // Older snapshots predate this label
+        if let Some(root) = labels.get(LABEL_DOCKER_ROOT) {
+            if root != docker_root {
+                return Some(format!("Docker root changed: {} -> {}", root, docker_root));
+            }
+        }
+        None
+    }
+
+    /// Calculate config hash from fields that affect cluster state
+    /// Excludes: cluster_name, speedup settings, logging config
     fn calculate_config_hash_static(config: &ClusterConfig) -> String {
         let mut hasher = Sha256::new();
         hasher.update(config.k3s_version.as_bytes());
@@ -70,13 +183,34 @@ impl K3sManager {
         }
         hasher.update(Self::RANCHER_DATA_PATH.as_bytes());
         hasher.update(Self::LOCAL_PV_STORAGE_PATH.as_bytes());
+        // Only hashed when set, so existing snapshots stay valid
+        if let Some(path) = config.storage.host_path() {
+            hasher.update(path.to_string_lossy().as_bytes());
+        }
         hasher.update(b"--docker");
         hasher.update(b"--disable=metrics-server");
         hasher.update(b"--disable=servicelb");
+        // Only hashed when on, so existing snapshots stay valid
+        if config.network_policy {
+            hasher.update(b"--network-policy");
+        }
         let result = hasher.finalize();
         format!("{:x}", result)[..8].to_string()
     }
 
+    /// Shell command copying the volume data into the container filesystem,
+    /// so a commit of the container includes it
+    fn snapshot_copy_command(config: &ClusterConfig) -> String {
+        format!(
+            "mkdir -p /snapshot-data && \
+             rm -rf /snapshot-data/rancher /snapshot-data/pv && \
+             cp -a {} /snapshot-data/rancher && \
+             cp -a {} /snapshot-data/pv",
+            Self::RANCHER_DATA_PATH,
+            Self::configured_pv_storage_path(config)
+        )
+    }
+
     /// Create a snapshot of the current running cluster
     pub(super) async fn create_snapshot(&self, output_tx: &mpsc::Sender<OutputLine>) -> Result<()> {
         let snapshot_image = self.get_snapshot_image_name();
@@ -93,14 +227,7 @@ impl K3sManager {
             .send(OutputLine::info("Saving cluster state into snapshot..."))
             .await;
 
-        let copy_cmd = format!(
-            "mkdir -p /snapshot-data && \
-             rm -rf /snapshot-data/rancher /snapshot-data/pv && \
-             cp -a {} /snapshot-data/rancher && \
-             cp -a {} /snapshot-data/pv",
-            Self::RANCHER_DATA_PATH,
-            Self::LOCAL_PV_STORAGE_PATH
-        );
+        let copy_cmd = Self::snapshot_copy_command(&self.config);
 
         match self
             .docker
@@ -124,20 +251,8 @@ impl K3sManager {
         }
 
         // Step 2: Prepare labels for the snapshot
-        let mut labels = HashMap::new();
-        labels.insert(
-            "k3dev.snapshot.created".to_string(),
-            chrono::Utc::now().to_rfc3339(),
-        );
-        labels.insert(
-            "k3dev.k3s_version".to_string(),
-            self.config.k3s_version.clone(),
-        );
-        labels.insert(
-            "k3dev.config_hash".to_string(),
-            self.calculate_config_hash(),
-        );
-        labels.insert("k3dev.domain".to_string(), self.config.domain.clone());
+        let docker_root = self.docker.get_docker_root_dir().await;
+        let labels = Self::snapshot_labels(&self.config, &docker_root, false);
 
         // Step 3: Commit the running container to an image (includes /snapshot-data/)
         match self
@@ -185,32 +300,15 @@ impl K3sManager {
             .await;
 
         // Copy volume data into container filesystem for snapshot
-        let copy_cmd = format!(
-            "mkdir -p /snapshot-data && \
-             rm -rf /snapshot-data/rancher /snapshot-data/pv && \
-             cp -a {} /snapshot-data/rancher && \
-             cp -a {} /snapshot-data/pv",
-            Self::RANCHER_DATA_PATH,
-            Self::LOCAL_PV_STORAGE_PATH
-        );
+        let copy_cmd = Self::snapshot_copy_command(config);
 
         docker
             .exec_in_container(container_name, &["sh", "-c", &copy_cmd])
             .await?;
 
-        // Prepare labels — same as regular snapshot but with deep flag
-        let mut labels = HashMap::new();
-        labels.insert(
-            "k3dev.snapshot.created".to_string(),
-            chrono::Utc::now().to_rfc3339(),
-        );
-        labels.insert("k3dev.k3s_version".to_string(), config.k3s_version.clone());
-        labels.insert(
-            "k3dev.config_hash".to_string(),
-            Self::calculate_config_hash_static(config),
-        );
-        labels.insert("k3dev.domain".to_string(), config.domain.clone());
-        labels.insert("k3dev.snapshot.deep".to_string(), "true".to_string());
+        // Same labels as a regular snapshot plus the deep flag
+        let docker_root = docker.get_docker_root_dir().await;
+        let labels = Self::snapshot_labels(config, &docker_root, true);
 
         docker
             .commit_container(container_name, &snapshot_image, labels)
@@ -226,6 +324,102 @@ impl K3sManager {
         Ok(())
     }
 
+    /// Every snapshot image, newest first, checked against the current config
+    pub async fn list_snapshots(&self) -> Result<Vec<SnapshotInfo>> {
+        let images = self.docker.list_images_by_pattern(SNAPSHOT_PREFIX).await?;
+        let docker_root = self.docker.get_docker_root_dir().await;
+        let mut snapshots = Vec::with_capacity(images.len());
+        for image in images {
+            let labels = self.docker.get_image_labels(&image).await;
+            snapshots.push(SnapshotInfo::from_labels(
+                image,
+                &labels,
+                &self.config,
+                &docker_root,
+            ));
+        }
+        snapshots.sort_by(|a, b| b.created.cmp(&a.created));
+        Ok(snapshots)
+    }
+
+    /// Save the running cluster as a named snapshot next to the automatic
+    /// one. It shares the config's image name prefix, so the automatic
+    /// cleanup keeps it until the config changes.
+    pub async fn save_named_snapshot(
+        &self,
+        name: &str,
+        output_tx: &mpsc::Sender<OutputLine>,
+    ) -> Result<String> {
+        let suffix = sanitize_snapshot_name(name);
+        if suffix.is_empty() {
+            bail!("Snapshot name '{}' has no letters or digits", name);
+        }
+        self.ensure_running().await?;
+        let image = format!("{}-{}", self.get_snapshot_image_name(), suffix);
+
+        let _ = output_tx
+            .send(OutputLine::info(format!(
+                "Saving cluster state as {}...",
+                image
+            )))
+            .await;
+        self.docker
+            .exec_in_container(
+                &self.config.container_name,
+                &["sh", "-c", &Self::snapshot_copy_command(&self.config)],
+            )
+            .await?;
+
+        // The cluster is fully up, so booting it can skip the deployment waits
+        let docker_root = self.docker.get_docker_root_dir().await;
+        let mut labels = Self::snapshot_labels(&self.config, &docker_root, true);
+        labels.insert(LABEL_NAME.to_string(), name.trim().to_string());
+        self.docker
+            .commit_container(&self.config.container_name, &image, labels)
+            .await?;
+
+        let _ = output_tx
+            .send(OutputLine::success(format!("Snapshot saved: {}", image)))
+            .await;
+        Ok(image)
+    }
+
+    /// Replace the cluster with one booted from `snapshot_image`. The image
+    /// must match the current config; the cluster's container, volumes and
+    /// pods are removed first, so only the snapshot's state remains.
+    pub(crate) async fn start_from_chosen_snapshot(
+        &mut self,
+        snapshot_image: &str,
+        output_tx: &mpsc::Sender<OutputLine>,
+    ) -> Result<StartOutcome> {
+        self.docker.ensure_accessible().await?;
+        if !self.docker.image_exists(snapshot_image).await {
+            bail!("Snapshot {} doesn't exist", snapshot_image);
+        }
+        let labels = self.docker.get_image_labels(snapshot_image).await;
+        let docker_root = self.docker.get_docker_root_dir().await;
+        if let Some(reason) = Self::snapshot_incompatibility(&labels, &self.config, &docker_root) {
+            bail!(
+                "Snapshot {} doesn't match the config ({})",
+                snapshot_image,
+                reason
+            );
+        }
+
+        self.delete(output_tx.clone()).await?;
+        if let Some(path) = self.config.storage.host_path() {
+            let _ = output_tx
+                .send(OutputLine::warning(format!(
+                    "PV data in {} is kept; the snapshot's PV data is copied over it",
+                    path.display()
+                )))
+                .await;
+        }
+        self.start_from_snapshot(snapshot_image, Self::is_deep_snapshot(&labels), output_tx)
+            .await?;
+        Ok(StartOutcome::StartedFromSnapshot)
+    }
+
     /// Start cluster from a snapshot image (fast path)
     /// If `is_deep` is true, skip wait_for_cluster_ready (coredns, local-path-provisioner, configmap)
     pub(super) async fn start_from_snapshot(
@@ -250,29 +444,92 @@ impl K3sManager {
                 .await;
         }
 
+        // Ensure prerequisites exist (volumes, network)
+        let _ = output_tx
+            .send(OutputLine::info("Ensuring prerequisites..."))
+            .await;
+        let rancher_volume = self.config.rancher_volume_name();
+        tokio::try_join!(
+            self.docker.create_volume(&rancher_volume),
+            self.create_pv_storage(),
+            self.docker.create_network(&self.config.network_name),
+        )?;
+
+        // Start container from snapshot, reusing the warm standby if it
+        // was created from this snapshot
+        if self.take_standby(snapshot_image).await {
+            let _ = output_tx
+                .send(OutputLine::info("Starting warm standby container..."))
+                .await;
+            self.docker
+                .start_container(&self.config.container_name)
+                .await?;
+        } else {
+            let _ = output_tx
+                .send(OutputLine::info("Starting container from snapshot..."))
+                .await;
+            let run_config = self
+                .snapshot_run_config(snapshot_image, &self.config.container_name)
+                .await?;
+            self.docker.run_container(&run_config).await?;
+        }
+
+        // Wait for API (should be fast since cluster is pre-initialized)
+        self.wait_for_api(output_tx).await?;
+
+        // Setup kubeconfig and install agent in parallel
+        let _ = output_tx
+            .send(OutputLine::info("Setting up kubeconfig..."))
+            .await;
+        let (kubeconfig_result, agent_result) =
+            tokio::join!(self.setup_kubeconfig(), self.install_agent(),);
+        kubeconfig_result?;
+        if let Err(e) = &agent_result {
+            let _ = output_tx
+                .send(OutputLine::warning(format!(
+                    "Agent install failed (stats will use fallback): {:#}",
+                    e
+                )))
+                .await;
+        }
+
+        if !is_deep {
+            // Legacy snapshot: wait for cluster to be fully ready (deployments, etc.)
+            self.wait_for_cluster_ready(output_tx).await?;
+        }
+
+        // Execute on_cluster_available hooks
+        if self.config.hooks.has_hooks() {
+            let hook_executor = HookExecutor::new(self.config.hooks.clone());
+            hook_executor
+                .execute_hooks(HookEvent::OnClusterAvailable, output_tx.clone())
+                .await?;
+        }
+
+        let _ = output_tx
+            .send(OutputLine::success("K3s cluster started from snapshot!"))
+            .await;
+
+        Ok(())
+    }
+
+    /// Container config for booting a snapshot image under `name`
+    pub(super) async fn snapshot_run_config(
+        &self,
+        snapshot_image: &str,
+        name: &str,
+    ) -> Result<ContainerRunConfig> {
         // Get docker socket path, docker root, and iptables mode
         let socket_path = self.platform.docker_socket_path().await?;
         let cgroup_driver = "cgroupfs";
         let docker_root = self.docker.get_docker_root_dir().await;
-        let pv_storage_path = Self::local_pv_storage_path(&docker_root);
+        let pv_storage_path = self.pv_storage_path(&docker_root);
         let kubelet_root = Self::kubelet_root_dir(&docker_root);
         let iptables_mode = PlatformInfo::detect_iptables_mode();
 
         // Build port mappings
         #[allow(unused_mut)]
-        let mut ports: Vec<(u16, u16)> = self
-            .config
-            .port_mappings()
-            .iter()
-            .filter_map(|p| {
-                let parts: Vec<&str> = p.split(':').collect();
-                if parts.len() == 2 {
-                    Some((parts[0].parse().ok()?, parts[1].parse().ok()?))
-                } else {
-                    None
-                }
-            })
-            .collect();
+        let mut ports = self.published_ports();
 
         #[cfg(target_os = "macos")]
         {
@@ -314,8 +571,7 @@ impl K3sManager {
                  --docker{docker_endpoint} \
                  --disable=metrics-server \
                  --disable=servicelb \
-                 --disable-cloud-controller \
-                 --disable-network-policy \
+                 --disable-cloud-controller{network_policy} \
                  --flannel-backend=host-gw \
                  --default-local-storage-path {pv} \
                  --service-node-port-range 80-32767 \
@@ -325,6 +581,7 @@ impl K3sManager {
                  --kube-apiserver-arg=enable-admission-plugins=NodeRestriction \
                  --kube-controller-manager-arg=concurrent-deployment-syncs=1",
                 docker_endpoint = docker_endpoint,
+                network_policy = self.config.network_policy_flag(),
                 rancher = Self::RANCHER_DATA_PATH,
                 pv = pv_storage_path,
                 kubelet = kubelet_root,
@@ -343,16 +600,12 @@ impl K3sManager {
             ),
             // Docker volume for rancher data
             (
-                Self::RANCHER_VOLUME_NAME.to_string(),
+                self.config.rancher_volume_name(),
                 Self::RANCHER_DATA_PATH.to_string(),
                 "volume".to_string(),
             ),
-            // Docker volume for local PV storage
-            (
-                Self::LOCAL_PV_VOLUME_NAME.to_string(),
-                pv_storage_path.clone(),
-                "volume".to_string(),
-            ),
+            // Local PV storage
+            self.pv_storage_mount(&pv_storage_path),
         ];
         let mut env = vec![("IPTABLES_MODE".to_string(), iptables_mode.to_string())];
 
@@ -370,9 +623,8 @@ impl K3sManager {
             );
         }
 
-        // Run container from snapshot image
-        let run_config = ContainerRunConfig {
-            name: self.config.container_name.clone(),
+        Ok(ContainerRunConfig {
+            name: name.to_string(),
             hostname: Some(self.config.container_name.clone()),
             image: snapshot_image.to_string(),
             detach: true,
@@ -386,63 +638,9 @@ impl K3sManager {
             entrypoint: Some(String::new()),
             command: Some(k3s_command),
             security_opt: vec!["apparmor=unconfined".to_string()],
-            labels: Default::default(),
+            labels: self.config.container_labels(),
             auto_remove: false,
-        };
-
-        // Ensure prerequisites exist (volumes, network)
-        let _ = output_tx
-            .send(OutputLine::info("Ensuring prerequisites..."))
-            .await;
-        tokio::try_join!(
-            self.docker.create_volume(Self::RANCHER_VOLUME_NAME),
-            self.docker.create_volume(Self::LOCAL_PV_VOLUME_NAME),
-            self.docker.create_network(&self.config.network_name),
-        )?;
-
-        // Start container from snapshot
-        let _ = output_tx
-            .send(OutputLine::info("Starting container from snapshot..."))
-            .await;
-        self.docker.run_container(&run_config).await?;
-
-        // Wait for API (should be fast since cluster is pre-initialized)
-        self.wait_for_api(output_tx).await?;
-
-        // Setup kubeconfig and install agent in parallel
-        let _ = output_tx
-            .send(OutputLine::info("Setting up kubeconfig..."))
-            .await;
-        let (kubeconfig_result, agent_result) =
-            tokio::join!(self.setup_kubeconfig(), self.install_agent(),);
-        kubeconfig_result?;
-        if let Err(e) = &agent_result {
-            let _ = output_tx
-                .send(OutputLine::warning(format!(
-                    "Agent install failed (stats will use fallback): {:#}",
-                    e
-                )))
-                .await;
-        }
-
-        if !is_deep {
-            // Legacy snapshot: wait for cluster to be fully ready (deployments, etc.)
-            self.wait_for_cluster_ready(output_tx).await?;
-        }
-
-        // Execute on_cluster_available hooks
-        if self.config.hooks.has_hooks() {
-            let hook_executor = HookExecutor::new(self.config.hooks.clone());
-            hook_executor
-                .execute_hooks(HookEvent::OnClusterAvailable, output_tx.clone())
-                .await?;
-        }
-
-        let _ = output_tx
-            .send(OutputLine::success("K3s cluster started from snapshot!"))
-            .await;
-
-        Ok(())
+        })
     }
 
     /// Cleanup old snapshots (static version for use from background tasks)
@@ -451,7 +649,7 @@ impl K3sManager {
         current_snapshot: &str,
         output_tx: &mpsc::Sender<OutputLine>,
     ) -> Result<()> {
-        let snapshots = docker.list_images_by_pattern("k3dev-snapshot-").await?;
+        let snapshots = docker.list_images_by_pattern(SNAPSHOT_PREFIX).await?;
 
         if snapshots.is_empty() {
             return Ok(());
@@ -496,10 +694,10 @@ impl K3sManager {
 
     /// Delete all snapshot images for this cluster
     pub async fn delete_snapshots(&self, output_tx: &mpsc::Sender<OutputLine>) -> Result<()> {
-        let snapshots = self
-            .docker
-            .list_images_by_pattern("k3dev-snapshot-")
-            .await?;
+        // The standby is a clone of the current snapshot
+        self.remove_standby().await?;
+
+        let snapshots = self.docker.list_images_by_pattern(SNAPSHOT_PREFIX).await?;
 
         if snapshots.is_empty() {
             return Ok(());
@@ -534,3 +732,65 @@ impl K3sManager {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn snapshot_names_become_image_suffixes() {
+        assert_eq!(sanitize_snapshot_name("Seeded DB"), "seeded-db");
+        assert_eq!(sanitize_snapshot_name("  v2 / demo!! "), "v2-demo");
+        assert_eq!(sanitize_snapshot_name("--"), "");
+    }
+
+    #[test]
+    fn snapshot_labels_match_their_config() {
+        let config = ClusterConfig::default();
+        let labels = K3sManager::snapshot_labels(&config, "/var/lib/docker", true);
+
+        assert!(K3sManager::is_deep_snapshot(&labels));
+        assert_eq!(
+            K3sManager::snapshot_incompatibility(&labels, &config, "/var/lib/docker"),
+            None
+        );
+    }
+
+    #[test]
+    fn changed_settings_make_snapshot_stale() {
+        let config = ClusterConfig::default();
+        let labels = K3sManager::snapshot_labels(&config, "/var/lib/docker", false);
+
+        let newer = ClusterConfig {
+            k3s_version: "v9.9.9-k3s1".to_string(),
+            ..config.clone()
+        };
+        let reason = K3sManager::snapshot_incompatibility(&labels, &newer, "/var/lib/docker");
+        assert!(reason.unwrap().starts_with("k3s version changed"));
+
+        let moved = ClusterConfig {
+            http_port: 8080,
+            ..config.clone()
+        };
+        let reason = K3sManager::snapshot_incompatibility(&labels, &moved, "/var/lib/docker");
+        assert!(reason
+            .unwrap()
+            .starts_with("infrastructure settings changed"));
+
+        let reason = K3sManager::snapshot_incompatibility(&labels, &config, "/data/docker");
+        assert!(reason.unwrap().starts_with("Docker root changed"));
+
+        // Snapshots from before the docker root label are still usable
+        let mut legacy = labels.clone();
+        legacy.remove(LABEL_DOCKER_ROOT);
+        assert_eq!(
+            K3sManager::snapshot_incompatibility(&legacy, &config, "/data/docker"),
+            None
+        );
+
+        legacy.remove(LABEL_CONFIG_HASH);
+        assert!(
+            K3sManager::snapshot_incompatibility(&legacy, &config, "/var/lib/docker").is_some()
+        );
+    }
+}
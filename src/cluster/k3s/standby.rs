@@ -0,0 +1,204 @@
+//! Warm standby container
+//!
+//! With `speedup.warm_standby`, a stopped clone of the cluster container is
+//! kept around, created from the current snapshot. Starting a cluster that
+//! would otherwise boot from the snapshot renames and starts the standby
+//! instead of creating a container. The standby is refreshed in the
+//! background after each stop or destroy.
+
+use std::collections::HashMap;
+
+use anyhow::Result;
+use tokio::sync::mpsc;
+
+use super::snapshots::LABEL_CREATED;
+use super::K3sManager;
+use crate::config::SpeedupConfig;
+use crate::ui::components::OutputLine;
+
+/// Label holding the snapshot a standby was created from (`image@created`).
+/// Snapshots are re-committed under the same name (e.g. the deep snapshot),
+/// so the creation time tells them apart.
+const LABEL_STANDBY_SNAPSHOT: &str = "k3dev.standby.snapshot";
+
+/// A standby clones the snapshot, so it needs snapshots on
+fn standby_enabled(speedup: &SpeedupConfig) -> bool {
+    speedup.use_snapshot && speedup.warm_standby
+}
+
+/// Identity of a snapshot image from its labels
+fn snapshot_identity(snapshot_image: &str, labels: &HashMap<String, String>) -> String {
+    let created = labels.get(LABEL_CREATED).map_or("", String::as_str);
+    format!("{}@{}", snapshot_image, created)
+}
+
+/// What refreshing the standby has to do
+#[derive(Debug, PartialEq, Eq)]
+enum StandbyRefresh {
+    /// It was created from the current snapshot
+    Keep,
+    /// There is no snapshot to create one from
+    Remove,
+    /// It is missing or was created from an older snapshot
+    Recreate,
+}
+
+/// Compare the identity stored on the standby, if any, with the snapshot's
+fn plan_refresh(snapshot_exists: bool, standby: Option<&str>, identity: &str) -> StandbyRefresh {
+    if !snapshot_exists {
+        StandbyRefresh::Remove
+    } else if standby == Some(identity) {
+        StandbyRefresh::Keep
+    } else {
+        StandbyRefresh::Recreate
+    }
+}
+
+impl K3sManager {
+    /// Name of the standby container
+    pub(super) fn standby_name(&self) -> String {
+        format!("{}-standby", self.config.container_name)
+    }
+
+    /// Identity of a snapshot image as stored on its standby
+    async fn snapshot_identity(&self, snapshot_image: &str) -> String {
+        let labels = self.docker.get_image_labels(snapshot_image).await;
+        snapshot_identity(snapshot_image, &labels)
+    }
+
+    /// Snapshot the standby was created from, if it exists
+    async fn standby_snapshot(&self) -> Option<String> {
+        self.docker
+            .get_container_labels(&self.standby_name())
+            .await
+            .remove(LABEL_STANDBY_SNAPSHOT)
+    }
+
+    /// Turn the standby into the cluster container if it was created from
+    /// `snapshot_image`. Returns false when there is no usable standby.
+    pub(super) async fn take_standby(&self, snapshot_image: &str) -> bool {
+        if !standby_enabled(&self.config.speedup) {
+            return false;
+        }
+        let identity = self.snapshot_identity(snapshot_image).await;
+        let standby = self.standby_snapshot().await;
+        if plan_refresh(true, standby.as_deref(), &identity) != StandbyRefresh::Keep {
+            return false;
+        }
+        match self
+            .docker
+            .rename_container(&self.standby_name(), &self.config.container_name)
+            .await
+        {
+            Ok(()) => true,
+            Err(e) => {
+                tracing::warn!(error = %e, "Failed to take over warm standby");
+                false
+            }
+        }
+    }
+
+    /// Make sure a standby created from the current snapshot exists.
+    /// Stale standbys are replaced; without a snapshot none is kept.
+    pub async fn refresh_standby(&self, output_tx: &mpsc::Sender<OutputLine>) -> Result<()> {
+        if !standby_enabled(&self.config.speedup) {
+            return Ok(());
+        }
+
+        let standby = self.standby_name();
+        let snapshot_image = self.get_snapshot_image_name();
+        let exists = self.docker.image_exists(&snapshot_image).await;
+        let identity = self.snapshot_identity(&snapshot_image).await;
+        let plan = plan_refresh(exists, self.standby_snapshot().await.as_deref(), &identity);
+        if plan == StandbyRefresh::Keep {
+            return Ok(());
+        }
+        if self.docker.container_exists(&standby).await {
+            self.docker.remove_container(&standby, true).await?;
+        }
+        if plan == StandbyRefresh::Remove {
+            return Ok(());
+        }
+
+        // The standby pins the network and volumes, so they must exist
+        let rancher_volume = self.config.rancher_volume_name();
+        tokio::try_join!(
+            self.docker.create_volume(&rancher_volume),
+            self.create_pv_storage(),
+            self.docker.create_network(&self.config.network_name),
+        )?;
+
+        let mut run_config = self.snapshot_run_config(&snapshot_image, &standby).await?;
+        run_config.detach = false;
+        run_config
+            .labels
+            .insert(LABEL_STANDBY_SNAPSHOT.to_string(), identity);
+        self.docker.run_container(&run_config).await?;
+
+        let _ = output_tx
+            .send(OutputLine::info("Warm standby container refreshed"))
+            .await;
+        Ok(())
+    }
+
+    /// Remove the standby container, if any
+    pub(super) async fn remove_standby(&self) -> Result<()> {
+        let standby = self.standby_name();
+        if self.docker.container_exists(&standby).await {
+            self.docker.remove_container(&standby, true).await?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn standby_needs_snapshots() {
+        let speedup = |use_snapshot, warm_standby| SpeedupConfig {
+            use_snapshot,
+            warm_standby,
+            ..SpeedupConfig::default()
+        };
+        assert!(standby_enabled(&speedup(true, true)));
+        assert!(!standby_enabled(&speedup(false, true)));
+        assert!(!standby_enabled(&speedup(true, false)));
+    }
+
+    #[test]
+    fn recommitted_snapshot_has_a_new_identity() {
+        let labels =
+            |created: &str| HashMap::from([(LABEL_CREATED.to_string(), created.to_string())]);
+        let old = snapshot_identity("k3dev-snapshot:v1", &labels("2026-01-01T00:00:00Z"));
+        let new = snapshot_identity("k3dev-snapshot:v1", &labels("2026-02-01T00:00:00Z"));
+        assert_eq!(old, "k3dev-snapshot:v1@2026-01-01T00:00:00Z");
+        assert_ne!(old, new);
+        assert_eq!(
+            snapshot_identity("k3dev-snapshot:v1", &HashMap::new()),
+            "k3dev-snapshot:v1@"
+        );
+    }
+
+    #[test]
+    fn stale_or_missing_standby_is_recreated() {
+        let current = "k3dev-snapshot:v1@2026-02-01T00:00:00Z";
+        let stale = "k3dev-snapshot:v1@2026-01-01T00:00:00Z";
+        assert_eq!(
+            plan_refresh(true, Some(current), current),
+            StandbyRefresh::Keep
+        );
+        assert_eq!(
+            plan_refresh(true, Some(stale), current),
+            StandbyRefresh::Recreate
+        );
+        assert_eq!(plan_refresh(true, None, current), StandbyRefresh::Recreate);
+        // Without the snapshot even a matching standby goes
+        assert_eq!(
+            plan_refresh(false, Some(current), current),
+            StandbyRefresh::Remove
+        );
+        assert_eq!(plan_refresh(false, None, current), StandbyRefresh::Remove);
+    }
+}
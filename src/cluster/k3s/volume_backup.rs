@@ -0,0 +1,441 @@
+//! Volume archives: the cluster's data volumes as a tar file on the host
+//!
+//! `k3dev volumes save` writes the rancher data (datastore, certificates,
+//! manifests) and the PV storage of a stopped cluster to one tar file;
+//! `k3dev volumes restore` writes such a file into the volumes of a cluster
+//! that doesn't exist yet. Unlike snapshot images, the file doesn't depend on
+//! the config hash or the Docker host, so seeded database state can be kept
+//! next to the project and shared.
+//!
+//! Archive layout: `k3dev-volumes.json` (manifest), `rancher/...` and `pv/...`.
+
+use std::io::{Read, Write};
+use std::path::{Component, Path, PathBuf};
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+use tokio::sync::mpsc;
+
+use super::K3sManager;
+use crate::cluster::docker::ContainerRunConfig;
+use crate::ui::components::OutputLine;
+
+/// Manifest entry, written first in the archive
+const MANIFEST_NAME: &str = "k3dev-volumes.json";
+const RANCHER_PREFIX: &str = "rancher";
+const PV_PREFIX: &str = "pv";
+
+/// Label on a rancher volume filled by a restore: the next start creates
+/// the cluster over it instead of booting a snapshot, whose data would
+/// overwrite the restored state
+pub(super) const LABEL_RESTORED: &str = "k3dev.volumes.restored";
+
+/// What an archive was saved from
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+struct VolumeManifest {
+    cluster: String,
+    k3s_version: String,
+    created: String,
+}
+
+/// Where `k3dev volumes save` writes without `--out`
+pub fn default_archive_path(cluster_name: &str) -> PathBuf {
+    PathBuf::from(format!(
+        "k3dev-volumes-{}-{}.tar",
+        cluster_name,
+        chrono::Local::now().format("%Y%m%d-%H%M%S")
+    ))
+}
+
+/// Copy the entries of `archive` into `builder`, renaming each with `map`
+/// (`None` drops the entry). Hard link targets are renamed the same way.
+/// Returns the number of regular files copied.
+fn copy_entries<R: Read, W: Write>(
+    archive: R,
+    builder: &mut tar::Builder<W>,
+    map: impl Fn(&Path) -> Option<PathBuf>,
+) -> Result<u64> {
+    let mut files = 0;
+    let mut archive = tar::Archive::new(archive);
+    for entry in archive.entries().context("Invalid archive")? {
+        let mut entry = entry.context("Invalid archive entry")?;
+        let path = entry.path()?.into_owned();
+        if path
+            .components()
+            .any(|c| !matches!(c, Component::Normal(_) | Component::CurDir))
+        {
+            anyhow::bail!("Refusing to copy {}", path.display());
+        }
+        let Some(target) = map(&path) else {
+            continue;
+        };
+        let mut header = entry.header().clone();
+        let entry_type = header.entry_type();
+        if entry_type.is_symlink() || entry_type.is_hard_link() {
+            let link = entry
+                .link_name()?
+                .map(|l| l.into_owned())
+                .unwrap_or_default();
+            let link = if entry_type.is_hard_link() {
+                map(&link).with_context(|| format!("Dangling hard link {}", path.display()))?
+            } else {
+                link
+            };
+            builder.append_link(&mut header, &target, &link)?;
+        } else {
+            if entry_type.is_file() {
+                files += 1;
+            }
+            builder.append_data(&mut header, &target, &mut entry)?;
+        }
+    }
+    Ok(files)
+}
+
+/// Replace the first component of `path` (the directory `docker cp` puts
+/// everything under) with `prefix`; the directory itself is dropped
+fn reroot(path: &Path, prefix: &str) -> Option<PathBuf> {
+    let rest: PathBuf = path.components().skip(1).collect();
+    (!rest.as_os_str().is_empty()).then(|| Path::new(prefix).join(rest))
+}
+
+/// Move `path` from under `from` to under `to`
+fn move_prefix(path: &Path, from: &str, to: &str) -> Option<PathBuf> {
+    let rest = path.strip_prefix(from).ok()?;
+    (!rest.as_os_str().is_empty()).then(|| Path::new(to).join(rest))
+}
+
+/// Write the manifest and both downloaded volumes into `dest`
+fn write_archive(manifest: &VolumeManifest, rancher: &Path, pv: &Path, dest: &Path) -> Result<u64> {
+    let file = std::fs::File::create(dest)
+        .with_context(|| format!("Failed to create {}", dest.display()))?;
+    let mut builder = tar::Builder::new(std::io::BufWriter::new(file));
+
+    let json = serde_json::to_vec_pretty(manifest)?;
+    let mut header = tar::Header::new_gnu();
+    header.set_size(json.len() as u64);
+    header.set_mode(0o644);
+    header.set_mtime(chrono::Utc::now().timestamp().max(0) as u64);
+    builder.append_data(&mut header, MANIFEST_NAME, json.as_slice())?;
+
+    // data/ holds the k3s binaries, which k3s extracts again when missing
+    let mut files = copy_entries(std::fs::File::open(rancher)?, &mut builder, |path| {
+        let path = reroot(path, RANCHER_PREFIX)?;
+        (!path.starts_with(Path::new(RANCHER_PREFIX).join("data"))).then_some(path)
+    })?;
+    files += copy_entries(std::fs::File::open(pv)?, &mut builder, |path| {
+        reroot(path, PV_PREFIX)
+    })?;
+    builder.into_inner()?.flush()?;
+    Ok(files)
+}
+
+/// Read the manifest of a saved archive and write its volumes to `dest`,
+/// laid out for an upload to `/` of a container with the volumes mounted
+fn prepare_upload(
+    src: &Path,
+    pv_storage_path: &str,
+    dest: &Path,
+) -> Result<(Option<VolumeManifest>, u64)> {
+    let mut manifest = None;
+    let mut archive = tar::Archive::new(
+        std::fs::File::open(src).with_context(|| format!("Failed to open {}", src.display()))?,
+    );
+    // The manifest is the first entry
+    if let Some(entry) = archive.entries().context("Invalid archive")?.next() {
+        let mut entry = entry.context("Invalid archive entry")?;
+        if entry.path()?.as_ref() == Path::new(MANIFEST_NAME) {
+            let mut json = String::new();
+            entry.read_to_string(&mut json)?;
+            manifest = serde_json::from_str(&json).ok();
+        }
+    }
+
+    let rancher_dir = K3sManager::RANCHER_DATA_PATH.trim_start_matches('/');
+    let pv_dir = pv_storage_path.trim_start_matches('/');
+    let file = std::fs::File::create(dest)
+        .with_context(|| format!("Failed to create {}", dest.display()))?;
+    let mut builder = tar::Builder::new(std::io::BufWriter::new(file));
+    let files = copy_entries(std::fs::File::open(src)?, &mut builder, |path| {
+        move_prefix(path, RANCHER_PREFIX, rancher_dir)
+            .or_else(|| move_prefix(path, PV_PREFIX, pv_dir))
+    })?;
+    builder.into_inner()?.flush()?;
+    Ok((manifest, files))
+}
+
+/// `<dest>.<what>.part`, next to the final file
+fn part_path(dest: &Path, what: &str) -> PathBuf {
+    let mut name = dest.file_name().unwrap_or_default().to_os_string();
+    name.push(format!(".{}.part", what));
+    dest.with_file_name(name)
+}
+
+impl K3sManager {
+    /// Save the rancher data and PV storage of the stopped cluster to `dest`
+    pub async fn save_volumes(
+        &self,
+        dest: &Path,
+        output_tx: &mpsc::Sender<OutputLine>,
+    ) -> Result<()> {
+        let container = &self.config.container_name;
+        if !self.docker.container_exists(container).await {
+            anyhow::bail!("Cluster container {} doesn't exist", container);
+        }
+        // A running datastore would be copied mid-write
+        if self.docker.container_running(container).await {
+            anyhow::bail!(
+                "Stop the cluster first, so its datastore is saved in a consistent state"
+            );
+        }
+
+        let rancher_part = part_path(dest, "rancher");
+        let pv_part = part_path(dest, "pv");
+        let result = async {
+            let _ = output_tx
+                .send(OutputLine::info("Copying rancher data..."))
+                .await;
+            self.docker
+                .download_to_file(container, Self::RANCHER_DATA_PATH, &rancher_part)
+                .await?;
+            let _ = output_tx.send(OutputLine::info("Copying PV data...")).await;
+            let docker_root = self.docker.get_docker_root_dir().await;
+            self.docker
+                .download_to_file(container, &self.pv_storage_path(&docker_root), &pv_part)
+                .await?;
+
+            let _ = output_tx
+                .send(OutputLine::info(format!("Writing {}...", dest.display())))
+                .await;
+            let manifest = VolumeManifest {
+                cluster: self.config.container_name.clone(),
+                k3s_version: self.config.k3s_version.clone(),
+                created: chrono::Utc::now().to_rfc3339(),
+            };
+            let (rancher, pv, out) = (rancher_part.clone(), pv_part.clone(), dest.to_path_buf());
+            tokio::task::spawn_blocking(move || write_archive(&manifest, &rancher, &pv, &out))
+                .await
+                .context("Archive task failed")?
+        }
+        .await;
+        let _ = tokio::fs::remove_file(&rancher_part).await;
+        let _ = tokio::fs::remove_file(&pv_part).await;
+
+        let files = result?;
+        let size = tokio::fs::metadata(dest)
+            .await
+            .map(|m| m.len())
+            .unwrap_or(0);
+        let _ = output_tx
+            .send(OutputLine::success(format!(
+                "Saved {} files ({:.1} MiB) to {}",
+                files,
+                size as f64 / (1024.0 * 1024.0),
+                dest.display()
+            )))
+            .await;
+        Ok(())
+    }
+
+    /// Write a saved archive into fresh volumes; the next start uses them
+    pub async fn restore_volumes(
+        &self,
+        src: &Path,
+        output_tx: &mpsc::Sender<OutputLine>,
+    ) -> Result<()> {
+        let container = &self.config.container_name;
+        if self.docker.container_exists(container).await {
+            anyhow::bail!(
+                "Cluster container {} exists; destroy the cluster first, volumes are restored into a fresh one",
+                container
+            );
+        }
+
+        // The standby mounts the volumes being replaced
+        self.remove_standby().await?;
+        let rancher_volume = self.config.rancher_volume_name();
+        self.docker.remove_volume(&rancher_volume).await?;
+        if self.config.storage.host_path().is_none() {
+            self.docker
+                .remove_volume(&self.config.local_pv_volume_name())
+                .await?;
+        }
+        self.docker
+            .create_labeled_volume(
+                &rancher_volume,
+                HashMap::from([(
+                    LABEL_RESTORED.to_string(),
+                    src.file_name()
+                        .unwrap_or_default()
+                        .to_string_lossy()
+                        .into_owned(),
+                )]),
+            )
+            .await?;
+        self.create_pv_storage().await?;
+
+        let image = self.config.k3s_image();
+        if !self.docker.image_exists(&image).await {
+            let _ = output_tx
+                .send(OutputLine::info(format!("Pulling k3s image: {}...", image)))
+                .await;
+            self.docker.pull_image(&image).await?;
+        }
+
+        let docker_root = self.docker.get_docker_root_dir().await;
+        let pv_storage_path = self.pv_storage_path(&docker_root);
+        let upload_part =
+            std::env::temp_dir().join(format!("k3dev-restore-{}.tar", std::process::id()));
+        let helper = format!("{}-restore", container);
+        let result = async {
+            let _ = output_tx
+                .send(OutputLine::info(format!("Reading {}...", src.display())))
+                .await;
+            let (source, path, out) = (src.to_path_buf(), pv_storage_path.clone(), upload_part.clone());
+            let (manifest, files) =
+                tokio::task::spawn_blocking(move || prepare_upload(&source, &path, &out))
+                    .await
+                    .context("Archive task failed")??;
+            match manifest {
+                Some(m) if m.k3s_version != self.config.k3s_version => {
+                    let _ = output_tx
+                        .send(OutputLine::warning(format!(
+                            "Saved from k3s {}, the config uses {}; k3s can't downgrade its datastore",
+                            m.k3s_version, self.config.k3s_version
+                        )))
+                        .await;
+                }
+                Some(_) => {}
+                None => anyhow::bail!("{} is not a k3dev volume archive", src.display()),
+            }
+
+            // Created but never started: Docker unpacks into its mounts
+            let _ = output_tx
+                .send(OutputLine::info(format!("Restoring {} files...", files)))
+                .await;
+            if self.docker.container_exists(&helper).await {
+                self.docker.remove_container(&helper, true).await?;
+            }
+            self.docker
+                .run_container(&ContainerRunConfig {
+                    name: helper.clone(),
+                    image: image.clone(),
+                    detach: false,
+                    volumes: vec![
+                        (
+                            rancher_volume.clone(),
+                            Self::RANCHER_DATA_PATH.to_string(),
+                            "volume".to_string(),
+                        ),
+                        self.pv_storage_mount(&pv_storage_path),
+                    ],
+                    labels: self.config.container_labels(),
+                    ..Default::default()
+                })
+                .await?;
+            self.docker
+                .upload_archive_file(&helper, "/", &upload_part)
+                .await
+        }
+        .await;
+        let _ = tokio::fs::remove_file(&upload_part).await;
+        let _ = self.docker.remove_container(&helper, true).await;
+        if let Err(e) = result {
+            // Don't leave half-restored volumes for the next start
+            let _ = self.docker.remove_volume(&rancher_volume).await;
+            return Err(e);
+        }
+
+        let _ = output_tx
+            .send(OutputLine::success(format!(
+                "Volumes restored from {}; start the cluster to use them",
+                src.display()
+            )))
+            .await;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn docker_cp_archive(top: &str, files: &[(&str, &str)]) -> Vec<u8> {
+        let mut builder = tar::Builder::new(Vec::new());
+        let mut header = tar::Header::new_gnu();
+        header.set_entry_type(tar::EntryType::Directory);
+        header.set_size(0);
+        header.set_mode(0o755);
+        builder
+            .append_data(&mut header, format!("{}/", top), std::io::empty())
+            .unwrap();
+        for (name, data) in files {
+            let mut header = tar::Header::new_gnu();
+            header.set_size(data.len() as u64);
+            header.set_mode(0o644);
+            builder
+                .append_data(&mut header, format!("{}/{}", top, name), data.as_bytes())
+                .unwrap();
+        }
+        builder.into_inner().unwrap()
+    }
+
+    fn paths(archive: &Path) -> Vec<String> {
+        tar::Archive::new(std::fs::File::open(archive).unwrap())
+            .entries()
+            .unwrap()
+            .map(|e| e.unwrap().path().unwrap().to_string_lossy().into_owned())
+            .collect()
+    }
+
+    #[test]
+    fn saved_volumes_round_trip_to_container_paths() {
+        let dir = std::env::temp_dir().join(format!("k3dev-volume-backup-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let (rancher, pv) = (dir.join("rancher.tar"), dir.join("pv.tar"));
+        std::fs::write(
+            &rancher,
+            docker_cp_archive(
+                "k3s",
+                &[("server/db/state.db", "db"), ("data/abc/bin/k3s", "binary")],
+            ),
+        )
+        .unwrap();
+        std::fs::write(
+            &pv,
+            docker_cp_archive("_data", &[("pvc-1_db_data/pgdata/PG_VERSION", "16")]),
+        )
+        .unwrap();
+
+        let manifest = VolumeManifest {
+            cluster: "k3s-default".to_string(),
+            k3s_version: "v1.33.4-k3s1".to_string(),
+            created: "2026-01-01T00:00:00Z".to_string(),
+        };
+        let saved = dir.join("volumes.tar");
+        assert_eq!(write_archive(&manifest, &rancher, &pv, &saved).unwrap(), 2);
+        assert_eq!(
+            paths(&saved),
+            [
+                MANIFEST_NAME,
+                "rancher/server/db/state.db",
+                "pv/pvc-1_db_data/pgdata/PG_VERSION"
+            ]
+        );
+
+        let upload = dir.join("upload.tar");
+        let (read, files) =
+            prepare_upload(&saved, "/var/lib/docker/volumes/pv/_data", &upload).unwrap();
+        assert_eq!(read, Some(manifest));
+        assert_eq!(files, 2);
+        assert_eq!(
+            paths(&upload),
+            [
+                "var/lib/rancher/k3s/server/db/state.db",
+                "var/lib/docker/volumes/pv/_data/pvc-1_db_data/pgdata/PG_VERSION"
+            ]
+        );
+        std::fs::remove_dir_all(&dir).unwrap();
+    }
+}
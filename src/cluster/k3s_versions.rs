@@ -0,0 +1,338 @@
+//! k3s releases published on Docker Hub
+//!
+//! The configured `k3s_version` is only an image tag; a typo, a release that
+//! was never built for the Docker host's architecture or a Kubernetes minor
+//! that stopped getting patches only shows up when the image is pulled or
+//! the cluster misbehaves. The tag list on Docker Hub answers all three
+//! before anything is pulled, and gives the recent stable releases to pick
+//! from.
+
+use std::cmp::Reverse;
+use std::fmt;
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
+
+/// Docker Hub's tag API for rancher/k3s
+const TAGS_URL: &str = "https://hub.docker.com/v2/repositories/rancher/k3s/tags";
+
+/// Tag pages read for recent releases, newest first
+const TAG_PAGES: usize = 2;
+
+/// Kubernetes patches the newest three minor releases
+const SUPPORTED_MINORS: u32 = 3;
+
+/// A stable k3s release, e.g. "v1.35.2-k3s1"
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct K3sVersion {
+    pub major: u32,
+    pub minor: u32,
+    pub patch: u32,
+    /// k3s build of the Kubernetes release
+    pub build: u32,
+}
+
+impl K3sVersion {
+    /// "v1.35.2-k3s1" or "v1.35.2+k3s1"; None for release candidates and
+    /// per-architecture tags
+    pub fn parse(tag: &str) -> Option<Self> {
+        let rest = tag.strip_prefix('v')?;
+        let (kube, build) = rest
+            .split_once("-k3s")
+            .or_else(|| rest.split_once("+k3s"))?;
+        let mut parts = kube.split('.').map(|p| p.parse::<u32>().ok());
+        let version = Self {
+            major: parts.next()??,
+            minor: parts.next()??,
+            patch: parts.next()??,
+            build: build.parse().ok()?,
+        };
+        parts.next().is_none().then_some(version)
+    }
+}
+
+impl fmt::Display for K3sVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "v{}.{}.{}-k3s{}",
+            self.major, self.minor, self.patch, self.build
+        )
+    }
+}
+
+/// An image tag and the architectures it was built for
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize)]
+pub struct ImageTag {
+    pub name: String,
+    #[serde(default, rename = "images", deserialize_with = "architectures")]
+    pub architectures: Vec<String>,
+}
+
+fn architectures<'de, D>(deserializer: D) -> std::result::Result<Vec<String>, D::Error>
+where
+    D: serde::Deserializer<'de>,
+{
+    #[derive(Deserialize)]
+    struct Image {
+        #[serde(default)]
+        architecture: String,
+    }
+    let images = Vec::<Image>::deserialize(deserializer)?;
+    let mut archs: Vec<String> = images
+        .into_iter()
+        .map(|i| i.architecture)
+        .filter(|a| !a.is_empty())
+        .collect();
+    archs.sort();
+    archs.dedup();
+    Ok(archs)
+}
+
+#[derive(Deserialize)]
+struct TagPage {
+    #[serde(default)]
+    results: Vec<ImageTag>,
+    next: Option<String>,
+}
+
+/// What is wrong with a configured version
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum VersionIssue {
+    /// Docker Hub has no such tag
+    NotFound,
+    /// The tag exists, but not for the Docker host
+    MissingArchitecture {
+        arch: String,
+        available: Vec<String>,
+    },
+    /// The Kubernetes minor release no longer gets patches
+    EndOfLife {
+        version: K3sVersion,
+        newest: K3sVersion,
+    },
+}
+
+impl fmt::Display for VersionIssue {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::NotFound => write!(f, "no such rancher/k3s tag"),
+            Self::MissingArchitecture { arch, available } => {
+                write!(f, "not built for {} (only {})", arch, available.join(", "))
+            }
+            Self::EndOfLife { version, newest } => write!(
+                f,
+                "Kubernetes {}.{} is end of life; the newest release is {}",
+                version.major, version.minor, newest
+            ),
+        }
+    }
+}
+
+/// A release offered by the version picker
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VersionChoice {
+    pub version: K3sVersion,
+    pub architectures: Vec<String>,
+}
+
+/// Docker Hub's tag list, read once per check
+pub struct ReleaseIndex {
+    client: reqwest::Client,
+}
+
+impl ReleaseIndex {
+    pub fn new() -> Result<Self> {
+        let client = reqwest::Client::builder()
+            .timeout(Duration::from_secs(4))
+            .build()
+            .context("Failed to create HTTP client")?;
+        Ok(Self { client })
+    }
+
+    /// The tag, or None if it doesn't exist
+    pub async fn tag(&self, name: &str) -> Result<Option<ImageTag>> {
+        let resp = self
+            .client
+            .get(format!("{}/{}", TAGS_URL, name))
+            .send()
+            .await
+            .context("Failed to reach Docker Hub")?;
+        if resp.status() == reqwest::StatusCode::NOT_FOUND {
+            return Ok(None);
+        }
+        let resp = resp
+            .error_for_status()
+            .context("Docker Hub refused the request")?;
+        Ok(Some(
+            resp.json()
+                .await
+                .context("Unexpected tag from Docker Hub")?,
+        ))
+    }
+
+    /// The most recently pushed tags, release candidates included
+    pub async fn recent_tags(&self) -> Result<Vec<ImageTag>> {
+        let mut tags = Vec::new();
+        let mut url = Some(format!("{}?page_size=100&ordering=last_updated", TAGS_URL));
+        for _ in 0..TAG_PAGES {
+            let Some(page_url) = url.take() else {
+                break;
+            };
+            let page: TagPage = self
+                .client
+                .get(&page_url)
+                .send()
+                .await
+                .context("Failed to reach Docker Hub")?
+                .error_for_status()
+                .context("Docker Hub refused the request")?
+                .json()
+                .await
+                .context("Unexpected tag list from Docker Hub")?;
+            tags.extend(page.results);
+            url = page.next;
+        }
+        Ok(tags)
+    }
+
+    /// Newest patch of each recent minor release, newest first
+    pub async fn choices(&self) -> Result<Vec<VersionChoice>> {
+        Ok(recent_stable(&self.recent_tags().await?))
+    }
+
+    /// What is wrong with `version` on a Docker host of `arch` ("amd64")
+    pub async fn check(&self, version: &str, arch: Option<&str>) -> Result<Vec<VersionIssue>> {
+        let (tag, recent) = tokio::join!(self.tag(version), self.recent_tags());
+        Ok(assess(
+            version,
+            tag?.as_ref(),
+            &recent_stable(&recent?),
+            arch,
+        ))
+    }
+}
+
+/// Newest patch of each minor release among `tags`, newest first
+pub fn recent_stable(tags: &[ImageTag]) -> Vec<VersionChoice> {
+    let mut choices: Vec<VersionChoice> = tags
+        .iter()
+        .filter_map(|t| {
+            K3sVersion::parse(&t.name).map(|version| VersionChoice {
+                version,
+                architectures: t.architectures.clone(),
+            })
+        })
+        .collect();
+    choices.sort_by_key(|c| Reverse(c.version));
+    choices.dedup_by_key(|c| (c.version.major, c.version.minor));
+    choices
+}
+
+/// Issues of `version`, given its tag (None when missing) and the recent
+/// releases
+pub fn assess(
+    version: &str,
+    tag: Option<&ImageTag>,
+    recent: &[VersionChoice],
+    arch: Option<&str>,
+) -> Vec<VersionIssue> {
+    let Some(tag) = tag else {
+        return vec![VersionIssue::NotFound];
+    };
+    let mut issues = Vec::new();
+    if let Some(arch) = arch {
+        // Tags without image details are assumed to be fine
+        if !tag.architectures.is_empty() && !tag.architectures.iter().any(|a| a == arch) {
+            issues.push(VersionIssue::MissingArchitecture {
+                arch: arch.to_string(),
+                available: tag.architectures.clone(),
+            });
+        }
+    }
+    if let Some(version) = K3sVersion::parse(version) {
+        if is_end_of_life(&version, recent) {
+            issues.push(VersionIssue::EndOfLife {
+                version,
+                newest: recent[0].version,
+            });
+        }
+    }
+    issues
+}
+
+/// Whether `version` is older than the minor releases still patched, going
+/// by the newest of `recent`
+pub fn is_end_of_life(version: &K3sVersion, recent: &[VersionChoice]) -> bool {
+    recent.first().is_some_and(|newest| {
+        let newest = newest.version;
+        version.major < newest.major
+            || (version.major == newest.major
+                && version.minor < newest.minor.saturating_sub(SUPPORTED_MINORS - 1))
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn tag(name: &str, archs: &[&str]) -> ImageTag {
+        ImageTag {
+            name: name.to_string(),
+            architectures: archs.iter().map(|a| a.to_string()).collect(),
+        }
+    }
+
+    fn recent() -> Vec<VersionChoice> {
+        recent_stable(&[
+            tag("v1.35.2-k3s1", &["amd64", "arm64"]),
+            tag("v1.35.2-k3s1-amd64", &["amd64"]),
+            tag("v1.36.0-rc1-k3s1", &["amd64"]),
+            tag("v1.35.1-k3s2", &["amd64", "arm64"]),
+            tag("v1.34.5-k3s1", &["amd64", "arm64"]),
+            tag("v1.33.9-k3s1", &["amd64", "arm64"]),
+        ])
+    }
+
+    #[test]
+    fn versions_parse_and_order() {
+        let version = K3sVersion::parse("v1.35.2-k3s1").unwrap();
+        assert_eq!(version.to_string(), "v1.35.2-k3s1");
+        assert_eq!(K3sVersion::parse("v1.35.2+k3s1"), Some(version));
+        assert!(K3sVersion::parse("v1.36.0-rc1-k3s1").is_none());
+        assert!(K3sVersion::parse("v1.35.2-k3s1-amd64").is_none());
+        assert!(K3sVersion::parse("latest").is_none());
+        assert!(K3sVersion::parse("v1.35.10-k3s1").unwrap() > version);
+    }
+
+    #[test]
+    fn recent_stable_keeps_newest_patch_per_minor() {
+        let names: Vec<String> = recent().iter().map(|c| c.version.to_string()).collect();
+        assert_eq!(names, ["v1.35.2-k3s1", "v1.34.5-k3s1", "v1.33.9-k3s1"]);
+    }
+
+    #[test]
+    fn issues_cover_missing_tags_architectures_and_eol() {
+        let recent = recent();
+        assert_eq!(
+            assess("v1.35.9-k3s1", None, &recent, Some("amd64")),
+            [VersionIssue::NotFound]
+        );
+
+        let amd64_only = tag("v1.32.1-k3s1", &["amd64"]);
+        let issues = assess("v1.32.1-k3s1", Some(&amd64_only), &recent, Some("arm64"));
+        assert_eq!(issues.len(), 2);
+        assert!(matches!(
+            &issues[0],
+            VersionIssue::MissingArchitecture { arch, .. } if arch == "arm64"
+        ));
+        assert_eq!(
+            issues[1].to_string(),
+            "Kubernetes 1.32 is end of life; the newest release is v1.35.2-k3s1"
+        );
+
+        let supported = tag("v1.33.9-k3s1", &["amd64", "arm64"]);
+        assert!(assess("v1.33.9-k3s1", Some(&supported), &recent, Some("arm64")).is_empty());
+    }
+}
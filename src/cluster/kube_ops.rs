@@ -3,6 +3,8 @@
 use anyhow::{anyhow, Result};
 use k8s_openapi::api::apps::v1::Deployment;
 use k8s_openapi::api::core::v1::{Namespace, Node, Pod, Secret, Service};
+use k8s_openapi::api::discovery::v1::EndpointSlice;
+use k8s_openapi::api::storage::v1::StorageClass;
 use k8s_openapi::apimachinery::pkg::apis::meta::v1::ObjectMeta;
 use k8s_openapi::ByteString;
 use kube::api::{Api, DynamicObject, ListParams, Patch, PatchParams, PostParams};
@@ -15,6 +17,9 @@ use std::collections::BTreeMap;
 use std::time::Duration;
 use tokio::time::sleep;
 
+use super::mesh;
+use super::services::{count_ready_endpoints, ServiceInfo};
+
 /// Lazy-compiled regex for extracting Host from Traefik IngressRoute match rules
 static HOST_REGEX: Lazy<Regex> =
     Lazy::new(|| Regex::new(r"Host\(`([^`]+)`\)").expect("Invalid HOST_REGEX pattern"));
@@ -35,6 +40,13 @@ impl KubeOps {
         Self { client: None }
     }
 
+    /// Operations over a client that is already connected
+    pub fn with_client(client: Client) -> Self {
+        Self {
+            client: Some(client),
+        }
+    }
+
     /// Get or create the kube client
     async fn client(&mut self) -> Result<&Client> {
         if self.client.is_none() {
@@ -70,6 +82,27 @@ impl KubeOps {
         Ok(deploy.status.and_then(|s| s.ready_replicas).unwrap_or(0))
     }
 
+    /// List deployments in a namespace with their replica counts
+    pub async fn list_deployments(&mut self, namespace: &str) -> Result<Vec<DeploymentInfo>> {
+        let client = self.client().await?;
+        let deployments: Api<Deployment> = Api::namespaced(client.clone(), namespace);
+        let list = deployments.list(&ListParams::default()).await?;
+
+        Ok(list
+            .items
+            .into_iter()
+            .map(|deploy| DeploymentInfo {
+                name: deploy.metadata.name.unwrap_or_default(),
+                ready: deploy
+                    .status
+                    .as_ref()
+                    .and_then(|s| s.ready_replicas)
+                    .unwrap_or(0),
+                desired: deploy.spec.and_then(|s| s.replicas).unwrap_or(1),
+            })
+            .collect())
+    }
+
     /// Wait for deployment to have at least one ready replica
     pub async fn wait_for_deployment_ready(
         &mut self,
@@ -135,6 +168,29 @@ impl KubeOps {
         }
     }
 
+    /// List all services with their ready endpoint counts
+    pub async fn list_services(&mut self) -> Result<Vec<ServiceInfo>> {
+        let client = self.client().await?;
+        let services: Api<Service> = Api::all(client.clone());
+        let slices: Api<EndpointSlice> = Api::all(client.clone());
+        let params = ListParams::default();
+        let (services, slices) = tokio::try_join!(services.list(&params), slices.list(&params))?;
+
+        let endpoints = count_ready_endpoints(&slices.items);
+        Ok(services
+            .items
+            .into_iter()
+            .map(|service| {
+                let mut info = ServiceInfo::from_service(service);
+                info.endpoints = endpoints
+                    .get(&format!("{}/{}", info.namespace, info.name))
+                    .copied()
+                    .unwrap_or(0);
+                info
+            })
+            .collect())
+    }
+
     // ==================== Namespace Operations ====================
 
     /// List all namespaces
@@ -149,6 +205,19 @@ impl KubeOps {
             .collect())
     }
 
+    /// Namespaces whose new pods get a mesh sidecar
+    pub async fn list_injected_namespaces(&mut self) -> Result<Vec<String>> {
+        let client = self.client().await?;
+        let namespaces: Api<Namespace> = Api::all(client.clone());
+        let list = namespaces.list(&ListParams::default()).await?;
+        Ok(list
+            .items
+            .into_iter()
+            .filter(|ns| mesh::injection_enabled(&ns.metadata))
+            .filter_map(|ns| ns.metadata.name)
+            .collect())
+    }
+
     // ==================== Node Operations ====================
 
     /// List all nodes with details
@@ -222,39 +291,39 @@ impl KubeOps {
 
     // ==================== Pod Operations ====================
 
-    /// List pods in a namespace
-    pub async fn list_pods(&mut self, namespace: &str) -> Result<Vec<PodInfo>> {
+    /// Namespace and phase of every pod, for per-namespace counts
+    pub async fn list_pod_phases(&mut self) -> Result<Vec<(String, String)>> {
         let client = self.client().await?;
-        let pods: Api<Pod> = Api::namespaced(client.clone(), namespace);
+        let pods: Api<Pod> = Api::all(client.clone());
         let list = pods.list(&ListParams::default()).await?;
 
         Ok(list
             .items
             .into_iter()
             .map(|pod| {
-                let name = pod.metadata.name.unwrap_or_default();
-                let status = pod
+                let namespace = pod.metadata.namespace.unwrap_or_default();
+                let phase = pod
                     .status
-                    .as_ref()
-                    .and_then(|s| s.phase.clone())
+                    .and_then(|s| s.phase)
                     .unwrap_or_else(|| "Unknown".to_string());
-                let ready_count = pod
-                    .status
-                    .as_ref()
-                    .and_then(|s| s.container_statuses.as_ref())
-                    .map(|cs| cs.iter().filter(|c| c.ready).count())
-                    .unwrap_or(0);
-                let total_count = pod.spec.as_ref().map(|s| s.containers.len()).unwrap_or(0);
-
-                PodInfo {
-                    name,
-                    status,
-                    ready: format!("{}/{}", ready_count, total_count),
-                }
+                (namespace, phase)
             })
             .collect())
     }
 
+    /// Namespace of every pod running a mesh proxy
+    pub async fn list_meshed_pods(&mut self) -> Result<Vec<String>> {
+        let client = self.client().await?;
+        let pods: Api<Pod> = Api::all(client.clone());
+        let list = pods.list(&ListParams::default()).await?;
+        Ok(list
+            .items
+            .into_iter()
+            .filter(mesh::has_proxy)
+            .filter_map(|pod| pod.metadata.namespace)
+            .collect())
+    }
+
     /// List all pods across all namespaces (for tunnel detection)
     pub async fn list_all_pods(&mut self) -> Result<Vec<PodFullInfo>> {
         let client = self.client().await?;
@@ -467,6 +536,84 @@ impl KubeOps {
         }
     }
 
+    /// `spec.valuesContent` of a k3s HelmChartConfig, or None if there is
+    /// no such HelmChartConfig
+    pub async fn get_helm_chart_values(
+        &mut self,
+        name: &str,
+        namespace: &str,
+    ) -> Result<Option<String>> {
+        let client = self.client().await?;
+        let ar = ApiResource {
+            group: "helm.cattle.io".to_string(),
+            version: "v1".to_string(),
+            kind: "HelmChartConfig".to_string(),
+            api_version: "helm.cattle.io/v1".to_string(),
+            plural: "helmchartconfigs".to_string(),
+        };
+        let api: Api<DynamicObject> = Api::namespaced_with(client.clone(), namespace, &ar);
+        let Some(obj) = api.get_opt(name).await? else {
+            return Ok(None);
+        };
+        Ok(Some(
+            obj.data
+                .get("spec")
+                .and_then(|spec| spec.get("valuesContent"))
+                .and_then(|v| v.as_str())
+                .unwrap_or_default()
+                .to_string(),
+        ))
+    }
+
+    // ==================== Storage Classes ====================
+
+    /// Create or update a StorageClass
+    pub async fn apply_storage_class(&mut self, class: &StorageClass) -> Result<()> {
+        let client = self.client().await?;
+        let api: Api<StorageClass> = Api::all(client.clone());
+        let name = class
+            .metadata
+            .name
+            .as_deref()
+            .ok_or_else(|| anyhow!("Missing metadata.name"))?;
+        api.patch(
+            name,
+            &PatchParams::apply("k3dev").force(),
+            &Patch::Apply(class),
+        )
+        .await?;
+        Ok(())
+    }
+
+    /// Names of all StorageClasses
+    pub async fn list_storage_class_names(&mut self) -> Result<Vec<String>> {
+        let client = self.client().await?;
+        let api: Api<StorageClass> = Api::all(client.clone());
+        let list = api.list(&ListParams::default()).await?;
+        Ok(list
+            .items
+            .into_iter()
+            .filter_map(|class| class.metadata.name)
+            .collect())
+    }
+
+    /// Set a StorageClass's annotation, leaving its other fields alone
+    pub async fn annotate_storage_class(
+        &mut self,
+        name: &str,
+        annotation: &str,
+        value: &str,
+    ) -> Result<()> {
+        let client = self.client().await?;
+        let api: Api<StorageClass> = Api::all(client.clone());
+        let patch = serde_json::json!({
+            "metadata": { "annotations": { annotation: value } }
+        });
+        api.patch(name, &PatchParams::default(), &Patch::Merge(&patch))
+            .await?;
+        Ok(())
+    }
+
     // ==================== Cluster Info ====================
 
     /// Get Kubernetes version
@@ -555,16 +702,16 @@ impl NodeInfo {
     }
 }
 
-#[derive(Debug, Clone)]
-pub struct PodInfo {
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct DeploymentInfo {
     pub name: String,
-    pub status: String,
-    pub ready: String,
+    pub ready: i32,
+    pub desired: i32,
 }
 
-impl PodInfo {
-    pub fn to_string_line(&self) -> String {
-        format!("{:<50} {:<10} {}", self.name, self.ready, self.status)
+impl DeploymentInfo {
+    pub fn is_ready(&self) -> bool {
+        self.ready >= self.desired
     }
 }
 
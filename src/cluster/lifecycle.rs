@@ -0,0 +1,250 @@
+//! Cluster lifecycle state machine
+//!
+//! Status polls only say what the container looks like right now. The
+//! machine combines them with the actions k3dev runs and with component
+//! health into one state, and reports each change as a
+//! [`ClusterTransition`], so the UI, toasts and hooks can react to edges
+//! such as Starting → Running or Running → Degraded.
+
+use super::ClusterStatus;
+
+/// Where the cluster is in its lifecycle
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ClusterState {
+    NotCreated,
+    Stopped,
+    Starting,
+    Running,
+    /// Running, but a core component is unhealthy
+    Degraded,
+    Stopping,
+    Paused,
+    /// Docker isn't reachable
+    RuntimeDown,
+    #[default]
+    Unknown,
+}
+
+impl ClusterState {
+    pub fn label(self) -> &'static str {
+        match self {
+            ClusterState::NotCreated => "not created",
+            ClusterState::Stopped => "stopped",
+            ClusterState::Starting => "starting",
+            ClusterState::Running => "running",
+            ClusterState::Degraded => "degraded",
+            ClusterState::Stopping => "stopping",
+            ClusterState::Paused => "paused",
+            ClusterState::RuntimeDown => "docker down",
+            ClusterState::Unknown => "unknown",
+        }
+    }
+
+    /// The API server is up
+    pub fn is_up(self) -> bool {
+        matches!(self, ClusterState::Running | ClusterState::Degraded)
+    }
+
+    fn observed(status: ClusterStatus) -> Self {
+        match status {
+            ClusterStatus::Running => ClusterState::Running,
+            ClusterStatus::Stopped => ClusterState::Stopped,
+            ClusterStatus::Starting => ClusterState::Starting,
+            ClusterStatus::Paused => ClusterState::Paused,
+            ClusterStatus::NotCreated => ClusterState::NotCreated,
+            ClusterStatus::RuntimeNotRunning => ClusterState::RuntimeDown,
+            ClusterStatus::Unknown => ClusterState::Unknown,
+        }
+    }
+}
+
+/// Something that may move the cluster to another state
+#[derive(Debug, Clone)]
+pub enum ClusterInput {
+    /// What a status poll saw
+    Observed(ClusterStatus),
+    /// k3dev began a start or restart
+    StartBegun,
+    /// k3dev began a stop or destroy
+    StopBegun,
+    /// The start or stop ended, however it went; the next poll decides
+    ActionEnded,
+    /// A core component is unhealthy, with why
+    Unhealthy(String),
+    /// Core components are healthy again
+    Healthy,
+}
+
+/// The cluster moved from one state to another
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ClusterTransition {
+    pub from: ClusterState,
+    pub to: ClusterState,
+    /// Why the cluster is degraded, for transitions into Degraded
+    pub reason: Option<String>,
+}
+
+/// Current state plus what's needed to pick the next one
+#[derive(Debug, Default)]
+pub struct ClusterLifecycle {
+    state: ClusterState,
+    /// Start or stop k3dev is running; polls showing the old state are
+    /// expected meanwhile and ignored
+    action: Option<ClusterState>,
+    /// Why the cluster is unhealthy, while it is
+    unhealthy: Option<String>,
+}
+
+impl ClusterLifecycle {
+    pub fn state(&self) -> ClusterState {
+        self.state
+    }
+
+    /// Why the cluster is degraded, if it is
+    pub fn degraded_reason(&self) -> Option<&str> {
+        self.unhealthy
+            .as_deref()
+            .filter(|_| self.state == ClusterState::Degraded)
+    }
+
+    /// Feed an input; returns the transition it caused, if any
+    pub fn apply(&mut self, input: ClusterInput) -> Option<ClusterTransition> {
+        let next = match input {
+            ClusterInput::Observed(status) => {
+                let observed = ClusterState::observed(status);
+                let settled = match self.action {
+                    Some(ClusterState::Starting) => observed.is_up(),
+                    Some(ClusterState::Stopping) => {
+                        matches!(observed, ClusterState::Stopped | ClusterState::NotCreated)
+                    }
+                    _ => true,
+                };
+                if !settled {
+                    return None;
+                }
+                match observed {
+                    ClusterState::Running if self.unhealthy.is_some() => ClusterState::Degraded,
+                    state => state,
+                }
+            }
+            ClusterInput::StartBegun => {
+                self.action = Some(ClusterState::Starting);
+                ClusterState::Starting
+            }
+            ClusterInput::StopBegun => {
+                self.action = Some(ClusterState::Stopping);
+                ClusterState::Stopping
+            }
+            ClusterInput::ActionEnded => {
+                self.action = None;
+                return None;
+            }
+            ClusterInput::Unhealthy(reason) => {
+                if !self.state.is_up() {
+                    return None;
+                }
+                self.unhealthy = Some(reason);
+                ClusterState::Degraded
+            }
+            ClusterInput::Healthy => {
+                self.unhealthy = None;
+                match self.state {
+                    ClusterState::Degraded => ClusterState::Running,
+                    state => state,
+                }
+            }
+        };
+        if !next.is_up() {
+            self.unhealthy = None;
+        }
+        if next == self.state {
+            return None;
+        }
+        let from = std::mem::replace(&mut self.state, next);
+        Some(ClusterTransition {
+            from,
+            to: next,
+            reason: self
+                .unhealthy
+                .clone()
+                .filter(|_| next == ClusterState::Degraded),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn to(transition: Option<ClusterTransition>) -> Option<ClusterState> {
+        transition.map(|t| t.to)
+    }
+
+    #[test]
+    fn polls_during_a_start_wait_for_the_cluster_to_come_up() {
+        let mut lifecycle = ClusterLifecycle::default();
+        assert_eq!(
+            to(lifecycle.apply(ClusterInput::Observed(ClusterStatus::Stopped))),
+            Some(ClusterState::Stopped)
+        );
+        assert_eq!(
+            to(lifecycle.apply(ClusterInput::StartBegun)),
+            Some(ClusterState::Starting)
+        );
+        assert_eq!(
+            lifecycle.apply(ClusterInput::Observed(ClusterStatus::Stopped)),
+            None
+        );
+        let up = lifecycle
+            .apply(ClusterInput::Observed(ClusterStatus::Running))
+            .unwrap();
+        assert_eq!(
+            (up.from, up.to),
+            (ClusterState::Starting, ClusterState::Running)
+        );
+
+        // A failed stop falls back to whatever the next poll sees
+        lifecycle.apply(ClusterInput::StopBegun);
+        lifecycle.apply(ClusterInput::ActionEnded);
+        assert_eq!(
+            to(lifecycle.apply(ClusterInput::Observed(ClusterStatus::Running))),
+            Some(ClusterState::Running)
+        );
+    }
+
+    #[test]
+    fn unhealthy_components_degrade_a_running_cluster_until_healthy() {
+        let mut lifecycle = ClusterLifecycle::default();
+        assert_eq!(
+            lifecycle.apply(ClusterInput::Unhealthy("coredns".to_string())),
+            None
+        );
+        lifecycle.apply(ClusterInput::Observed(ClusterStatus::Running));
+
+        let degraded = lifecycle
+            .apply(ClusterInput::Unhealthy("coredns".to_string()))
+            .unwrap();
+        assert_eq!(degraded.to, ClusterState::Degraded);
+        assert_eq!(degraded.reason.as_deref(), Some("coredns"));
+        // Polls keep seeing a running container
+        assert_eq!(
+            lifecycle.apply(ClusterInput::Observed(ClusterStatus::Running)),
+            None
+        );
+        assert_eq!(lifecycle.degraded_reason(), Some("coredns"));
+
+        assert_eq!(
+            to(lifecycle.apply(ClusterInput::Healthy)),
+            Some(ClusterState::Running)
+        );
+        lifecycle.apply(ClusterInput::Unhealthy("coredns".to_string()));
+        assert_eq!(
+            to(lifecycle.apply(ClusterInput::Observed(ClusterStatus::Stopped))),
+            Some(ClusterState::Stopped)
+        );
+        assert_eq!(
+            to(lifecycle.apply(ClusterInput::Observed(ClusterStatus::Running))),
+            Some(ClusterState::Running)
+        );
+    }
+}
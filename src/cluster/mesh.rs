@@ -0,0 +1,164 @@
+//! Service mesh lab mode
+//!
+//! Installs Linkerd or Istio's minimal profile into the cluster with the
+//! mesh's own CLI, and tells which namespaces have sidecar injection enabled
+//! and which pods run a proxy. Install and uninstall are host commands, so
+//! they run as regular jobs with streamed output and cancellation.
+
+use std::collections::BTreeMap;
+
+use k8s_openapi::api::core::v1::Pod;
+use k8s_openapi::apimachinery::pkg::apis::meta::v1::ObjectMeta;
+
+use super::info::NamespaceSummary;
+use crate::config::{CommandEntry, ServiceMesh};
+
+/// Sidecar container names of the supported meshes
+const PROXY_CONTAINERS: [&str; 2] = ["linkerd-proxy", "istio-proxy"];
+
+const LINKERD_INSTALL: &str = r#"set -e
+command -v linkerd >/dev/null || { echo "linkerd CLI not found: https://linkerd.io/2/getting-started/" >&2; exit 127; }
+linkerd check --pre
+linkerd install --crds | kubectl apply -f -
+linkerd install | kubectl apply -f -
+linkerd check --wait 5m
+echo "Enable injection with: kubectl annotate namespace <ns> linkerd.io/inject=enabled"
+"#;
+
+const LINKERD_UNINSTALL: &str = r#"set -e
+command -v linkerd >/dev/null || { echo "linkerd CLI not found" >&2; exit 127; }
+linkerd uninstall | kubectl delete --ignore-not-found -f -
+"#;
+
+const ISTIO_INSTALL: &str = r#"set -e
+command -v istioctl >/dev/null || { echo "istioctl not found: https://istio.io/latest/docs/setup/getting-started/" >&2; exit 127; }
+istioctl install --set profile=minimal -y
+echo "Enable injection with: kubectl label namespace <ns> istio-injection=enabled"
+"#;
+
+const ISTIO_UNINSTALL: &str = r#"set -e
+command -v istioctl >/dev/null || { echo "istioctl not found" >&2; exit 127; }
+istioctl uninstall --purge -y
+kubectl delete namespace istio-system --ignore-not-found
+"#;
+
+/// The host command installing `mesh`
+pub fn install_command(mesh: ServiceMesh) -> CommandEntry {
+    let script = match mesh {
+        ServiceMesh::Linkerd => LINKERD_INSTALL,
+        ServiceMesh::Istio => ISTIO_INSTALL,
+    };
+    CommandEntry::host(format!("Install {}", mesh.label()), script)
+}
+
+/// The host command removing `mesh`
+pub fn uninstall_command(mesh: ServiceMesh) -> CommandEntry {
+    let script = match mesh {
+        ServiceMesh::Linkerd => LINKERD_UNINSTALL,
+        ServiceMesh::Istio => ISTIO_UNINSTALL,
+    };
+    CommandEntry::host(format!("Uninstall {}", mesh.label()), script)
+}
+
+/// Whether new pods of a namespace get a mesh sidecar
+pub(crate) fn injection_enabled(meta: &ObjectMeta) -> bool {
+    let label = |key: &str| meta.labels.as_ref().and_then(|l| l.get(key));
+    let annotation = |key: &str| meta.annotations.as_ref().and_then(|a| a.get(key));
+    label("istio-injection").is_some_and(|v| v == "enabled")
+        || label("istio.io/rev").is_some()
+        || annotation("linkerd.io/inject").is_some_and(|v| v == "enabled")
+}
+
+/// Whether a pod runs a mesh proxy, as a regular or native sidecar
+pub(crate) fn has_proxy(pod: &Pod) -> bool {
+    pod.spec.as_ref().is_some_and(|spec| {
+        spec.containers
+            .iter()
+            .chain(spec.init_containers.iter().flatten())
+            .any(|c| PROXY_CONTAINERS.contains(&c.name.as_str()))
+    })
+}
+
+/// Fill in the mesh fields of the namespace summaries
+pub(crate) fn apply_mesh_status(
+    namespaces: &mut [NamespaceSummary],
+    injected: &[String],
+    meshed_pods: &[String],
+) {
+    let mut meshed: BTreeMap<&str, usize> = BTreeMap::new();
+    for namespace in meshed_pods {
+        *meshed.entry(namespace).or_default() += 1;
+    }
+    for ns in namespaces {
+        ns.injection = injected.contains(&ns.name);
+        ns.meshed = meshed.get(ns.name.as_str()).copied().unwrap_or(0);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn meta(labels: &[(&str, &str)], annotations: &[(&str, &str)]) -> ObjectMeta {
+        let map = |pairs: &[(&str, &str)]| {
+            Some(
+                pairs
+                    .iter()
+                    .map(|(k, v)| (k.to_string(), v.to_string()))
+                    .collect(),
+            )
+        };
+        ObjectMeta {
+            labels: map(labels),
+            annotations: map(annotations),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn injection_is_read_from_either_mesh() {
+        assert!(injection_enabled(&meta(
+            &[("istio-injection", "enabled")],
+            &[]
+        )));
+        assert!(injection_enabled(&meta(
+            &[("istio.io/rev", "default")],
+            &[]
+        )));
+        assert!(injection_enabled(&meta(
+            &[],
+            &[("linkerd.io/inject", "enabled")]
+        )));
+        assert!(!injection_enabled(&meta(
+            &[("istio-injection", "disabled")],
+            &[]
+        )));
+        assert!(!injection_enabled(&meta(&[], &[])));
+    }
+
+    #[test]
+    fn meshed_pods_are_counted_per_namespace() {
+        let mut namespaces = vec![
+            NamespaceSummary {
+                name: "apps".to_string(),
+                pods: 3,
+                running: 3,
+                injection: false,
+                meshed: 0,
+            },
+            NamespaceSummary {
+                name: "default".to_string(),
+                pods: 1,
+                running: 1,
+                injection: false,
+                meshed: 0,
+            },
+        ];
+        let meshed = vec!["apps".to_string(), "apps".to_string()];
+        apply_mesh_status(&mut namespaces, &["apps".to_string()], &meshed);
+        assert!(namespaces[0].injection);
+        assert_eq!(namespaces[0].meshed, 2);
+        assert!(!namespaces[1].injection);
+        assert_eq!(namespaces[1].meshed, 0);
+    }
+}
@@ -1,32 +1,78 @@
+pub mod api_probe;
+pub mod branches;
+mod clients;
 mod config;
+pub mod degraded;
 pub mod diagnostics;
 pub(crate) mod docker;
+pub mod health_backoff;
+pub mod health_history;
+pub mod hosts_file;
+pub mod hosts_helper;
+pub mod image_build;
+mod info;
 mod ingress;
+pub mod instance_lock;
 mod k3s;
+pub mod k3s_versions;
 pub(crate) mod kube_ops;
+mod lifecycle;
+pub mod mesh;
+pub mod on_demand;
 mod platform;
+pub mod policy_engine;
 mod port_forward;
+mod readiness;
+pub mod runtime;
+pub mod server_logs;
+mod services;
+pub mod share;
+mod storage;
+pub mod support_bundle;
 mod traefik;
+pub mod traefik_drift;
+pub mod tunnel;
+pub mod watchdog;
 
+pub use clients::SharedClients;
 pub use config::ClusterConfig;
 #[allow(unused_imports)]
 pub use docker::ContainerRunConfig;
-pub use docker::{ContainerPullProgress, ContainerStats, DockerManager, PullPhase};
+pub use docker::{
+    ContainerEvent, ContainerPullProgress, ContainerStats, DockerManager, HostCapacity, PodProcess,
+    PullPhase, PvDirectory,
+};
+pub use info::ClusterInfo;
 pub use ingress::{
-    HostsUpdateResult, IngressEntry, IngressHealthChecker, IngressHealthStatus, IngressManager,
+    HealthCheck, HostsUpdateResult, IngressEntry, IngressHealthChecker, IngressHealthStatus,
+    IngressManager,
+};
+pub use k3s::{
+    default_archive_path, parse_memory_limit, ChaosAction, ChaosTool, ClusterComponent,
+    ClusterStatus, K3sManager, SnapshotInfo,
 };
-pub use k3s::{ClusterStatus, K3sManager};
+pub use lifecycle::{ClusterInput, ClusterLifecycle, ClusterState, ClusterTransition};
 pub use platform::{find_available_port, PlatformInfo};
 pub use port_forward::PortForwardDetector;
+pub use readiness::{subscribe_readiness, ReadinessState, ReadinessUpdate};
+pub use services::ServiceInfo;
 pub use traefik::TraefikManager;
 
-use anyhow::Result;
+use anyhow::{anyhow, Context, Result};
+use std::path::Path;
 use std::sync::Arc;
 use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
 
+use crate::config::branch::local_branches;
 use crate::config::HookEvent;
+use crate::error::K3devError;
 use crate::hooks::HookExecutor;
+use crate::k8s::K8sClient;
+use crate::smoke::{run_smoke_tests, SmokeReport};
 use crate::ui::components::OutputLine;
+use branches::{remove_branch_cluster, stale_branch_clusters};
+use kube_ops::KubeOps;
 
 /// Unified cluster manager that orchestrates all cluster operations
 pub struct ClusterManager {
@@ -34,6 +80,9 @@ pub struct ClusterManager {
     k3s: Option<K3sManager>,
     ingress: IngressManager,
     platform: PlatformInfo,
+    /// Traefik, on_services_deployed hooks and smoke tests after a start;
+    /// fails when Traefik or a hook did
+    services: Option<JoinHandle<Result<Option<SmokeReport>>>>,
 }
 
 impl ClusterManager {
@@ -52,6 +101,39 @@ impl ClusterManager {
             k3s,
             ingress,
             platform,
+            services: None,
+        })
+    }
+
+    /// A manager on the shared Docker and Kubernetes connections, for reads
+    pub async fn with_clients(config: Arc<ClusterConfig>, clients: &SharedClients) -> Result<Self> {
+        let kube_ops = clients.kube_ops().await;
+        Self::with_shared_docker(config, clients, kube_ops).await
+    }
+
+    /// A manager on the shared Docker connection with its own Kubernetes
+    /// client, for actions that recreate the cluster and its kubeconfig
+    pub async fn for_action(config: Arc<ClusterConfig>, clients: &SharedClients) -> Result<Self> {
+        Self::with_shared_docker(config, clients, KubeOps::new()).await
+    }
+
+    async fn with_shared_docker(
+        config: Arc<ClusterConfig>,
+        clients: &SharedClients,
+        kube_ops: KubeOps,
+    ) -> Result<Self> {
+        let platform = PlatformInfo::detect()?;
+        // Like `new`, a missing Docker only leaves the k3s side out
+        let k3s = match clients.docker().await {
+            Ok(docker) => K3sManager::with_connections(Arc::clone(&config), docker, kube_ops).ok(),
+            Err(_) => None,
+        };
+        Ok(Self {
+            config,
+            k3s,
+            ingress: IngressManager::new(),
+            platform,
+            services: None,
         })
     }
 
@@ -66,6 +148,24 @@ impl ClusterManager {
 
     /// Start the cluster and all services
     pub async fn start(&mut self, output_tx: mpsc::Sender<OutputLine>) -> Result<()> {
+        self.start_with(None, output_tx).await
+    }
+
+    /// Replace the cluster with one booted from a saved snapshot, then
+    /// deploy services as a normal start does
+    pub async fn start_from_snapshot(
+        &mut self,
+        snapshot_image: &str,
+        output_tx: mpsc::Sender<OutputLine>,
+    ) -> Result<()> {
+        self.start_with(Some(snapshot_image), output_tx).await
+    }
+
+    async fn start_with(
+        &mut self,
+        snapshot_image: Option<&str>,
+        output_tx: mpsc::Sender<OutputLine>,
+    ) -> Result<()> {
         // Ensure K3sManager is available
         if self.k3s.is_none() {
             match K3sManager::new(Arc::clone(&self.config)).await {
@@ -84,7 +184,10 @@ impl ClusterManager {
 
         // Start k3s cluster (core components only)
         let outcome = if let Some(k3s) = &mut self.k3s {
-            k3s.start(output_tx.clone()).await?
+            match snapshot_image {
+                Some(image) => k3s.start_from_chosen_snapshot(image, &output_tx).await?,
+                None => k3s.start(output_tx.clone()).await?,
+            }
         } else {
             let _ = output_tx
                 .send(OutputLine::error("Failed to initialize cluster manager"))
@@ -92,6 +195,27 @@ impl ClusterManager {
             return Ok(());
         };
 
+        // Components start did not wait for; the deep snapshot must include them
+        let pending_readiness = self
+            .k3s
+            .as_mut()
+            .and_then(|k3s| k3s.pending_readiness.take());
+
+        // Extra StorageClasses; a failure leaves the cluster usable
+        if let Some(k3s) = &mut self.k3s {
+            if let Err(e) =
+                storage::apply_storage_classes(&self.config.storage, &mut k3s.kube_ops, &output_tx)
+                    .await
+            {
+                let _ = output_tx
+                    .send(OutputLine::warning(format!(
+                        "StorageClasses not applied: {:#}",
+                        e
+                    )))
+                    .await;
+            }
+        }
+
         // Determine if we need to create a deep snapshot after Traefik + hooks
         let needs_deep_snapshot =
             matches!(outcome, k3s::StartOutcome::FreshCreated) && self.config.speedup.use_snapshot;
@@ -109,7 +233,7 @@ impl ClusterManager {
         let tx = output_tx.clone();
 
         // Spawn background task for Traefik deployment and post-deployment tasks
-        tokio::spawn(async move {
+        self.services = Some(tokio::spawn(async move {
             // Deploy Traefik
             if let Err(e) = traefik_manager.deploy(tx.clone()).await {
                 let _ = tx
@@ -118,7 +242,7 @@ impl ClusterManager {
                         e
                     )))
                     .await;
-                return;
+                return Err(e.context("Traefik deployment failed"));
             }
 
             // Execute on_services_deployed hooks
@@ -131,7 +255,7 @@ impl ClusterManager {
                     let _ = tx
                         .send(OutputLine::error(format!("Hook execution failed: {}", e)))
                         .await;
-                    return;
+                    return Err(e.context("Hook execution failed"));
                 }
             }
 
@@ -139,8 +263,21 @@ impl ClusterManager {
                 .send(OutputLine::success("All services deployed successfully!"))
                 .await;
 
+            let smoke = if config.smoke_tests.is_empty() {
+                None
+            } else {
+                let k8s = K8sClient::new(config.kubeconfig.as_deref(), config.context.as_deref())
+                    .await
+                    .ok();
+                let docker = DockerManager::new(socket_path.clone()).ok();
+                Some(run_smoke_tests(&config.smoke_tests, k8s.as_ref(), docker.as_ref(), &tx).await)
+            };
+
             // Create deep snapshot after all services are deployed
             if needs_deep_snapshot {
+                if let Some(pending) = pending_readiness {
+                    let _ = pending.await;
+                }
                 match DockerManager::new(socket_path) {
                     Ok(docker) => {
                         let snapshot_image = K3sManager::compute_snapshot_image_name(&config);
@@ -177,7 +314,12 @@ impl ClusterManager {
                     }
                 }
             }
-        });
+            Ok(smoke)
+        }));
+
+        if self.config.per_branch {
+            self.prune_stale_branches(&output_tx).await;
+        }
 
         let _ = output_tx
             .send(OutputLine::success("Cluster started successfully!"))
@@ -185,11 +327,51 @@ impl ClusterManager {
         Ok(())
     }
 
+    /// Wait for what the last start deploys in the background. The report
+    /// is None when no smoke tests are configured.
+    pub async fn services_deployed(&mut self) -> Result<Option<SmokeReport>> {
+        let services = self
+            .services
+            .take()
+            .ok_or_else(|| anyhow!("No services were deployed"))?;
+        services.await?
+    }
+
+    /// Remove stopped clusters whose git branch was deleted
+    async fn prune_stale_branches(&self, output_tx: &mpsc::Sender<OutputLine>) {
+        let Some(branches) = local_branches() else {
+            return;
+        };
+        let Ok(docker) = DockerManager::from_default_socket() else {
+            return;
+        };
+        let stale =
+            match stale_branch_clusters(&docker, &self.config.base_cluster_name(), &branches).await
+            {
+                Ok(stale) => stale,
+                Err(e) => {
+                    tracing::warn!(error = %e, "Failed to list branch clusters");
+                    return;
+                }
+            };
+        for cluster in stale.iter().filter(|c| !c.running) {
+            if let Err(e) = remove_branch_cluster(&docker, &self.config, cluster, output_tx).await {
+                let _ = output_tx
+                    .send(OutputLine::warning(format!(
+                        "Failed to remove {}: {:#}",
+                        cluster.container, e
+                    )))
+                    .await;
+            }
+        }
+    }
+
     /// Stop the cluster
     pub async fn stop(&self, output_tx: mpsc::Sender<OutputLine>) -> Result<()> {
         if let Some(k3s) = &self.k3s {
-            k3s.stop(output_tx).await?;
+            k3s.stop(output_tx.clone()).await?;
         }
+        self.spawn_standby_refresh(output_tx);
         Ok(())
     }
 
@@ -200,8 +382,39 @@ impl ClusterManager {
         self.start(output_tx).await
     }
 
-    /// Delete the cluster and cleanup
+    /// Run the `on_before_destroy` hooks, then delete the cluster and cleanup
     pub async fn delete(&mut self, output_tx: mpsc::Sender<OutputLine>) -> Result<()> {
+        self.run_before_destroy_hooks(&output_tx).await?;
+        self.remove(output_tx).await
+    }
+
+    /// Like [`ClusterManager::delete`], but save the data volumes to `dest`
+    /// between the hooks and the removal. A failed export keeps the cluster.
+    pub async fn export_and_delete(
+        &mut self,
+        dest: &Path,
+        output_tx: mpsc::Sender<OutputLine>,
+    ) -> Result<()> {
+        self.run_before_destroy_hooks(&output_tx).await?;
+        let k3s = self.k3s.as_ref().ok_or(K3devError::DockerUnavailable)?;
+        // Not self.stop(): the standby refresh would race the export
+        k3s.stop(output_tx.clone()).await?;
+        k3s.save_volumes(dest, &output_tx)
+            .await
+            .context("Volume export failed; the cluster was stopped but not destroyed")?;
+        self.remove(output_tx).await
+    }
+
+    /// Hooks that get the last look at the running cluster, e.g. to dump
+    /// its databases. A failing one keeps the cluster.
+    async fn run_before_destroy_hooks(&self, output_tx: &mpsc::Sender<OutputLine>) -> Result<()> {
+        HookExecutor::new(self.config.hooks.clone())
+            .execute_hooks(HookEvent::OnBeforeDestroy, output_tx.clone())
+            .await
+            .context("Destroy cancelled")
+    }
+
+    async fn remove(&mut self, output_tx: mpsc::Sender<OutputLine>) -> Result<()> {
         // Skip Traefik uninstall - resources live inside k3s container which is being deleted
         // This saves ~2-3 seconds since we don't need to wait for K8s API calls
 
@@ -209,12 +422,109 @@ impl ClusterManager {
         if let Some(k3s) = &self.k3s {
             k3s.delete(output_tx.clone()).await?;
         }
+        self.spawn_standby_refresh(output_tx);
 
         // Note: /etc/hosts entries are kept on purpose - user can manually update with 'H' key
 
         Ok(())
     }
 
+    /// Recreate the warm standby in the background so the next start can
+    /// use it
+    fn spawn_standby_refresh(&self, output_tx: mpsc::Sender<OutputLine>) {
+        if !(self.config.speedup.use_snapshot && self.config.speedup.warm_standby) {
+            return;
+        }
+        let config = Arc::clone(&self.config);
+        tokio::spawn(async move {
+            let result = match K3sManager::new(config).await {
+                Ok(k3s) => k3s.refresh_standby(&output_tx).await,
+                Err(e) => Err(e),
+            };
+            if let Err(e) = result {
+                tracing::warn!(error = %e, "Warm standby refresh failed");
+                let _ = output_tx
+                    .send(OutputLine::warning(format!(
+                        "Warm standby refresh failed: {:#}",
+                        e
+                    )))
+                    .await;
+            }
+        });
+    }
+
+    /// Repeat one setup step on the running cluster
+    pub async fn redeploy(
+        &mut self,
+        component: ClusterComponent,
+        output_tx: mpsc::Sender<OutputLine>,
+    ) -> Result<()> {
+        let k3s = self.k3s.as_mut().ok_or(K3devError::DockerUnavailable)?;
+        match component {
+            ClusterComponent::Traefik => {
+                k3s.ensure_running().await?;
+                TraefikManager::new(Arc::clone(&self.config))
+                    .deploy(output_tx)
+                    .await
+            }
+            ClusterComponent::LocalPath => {
+                k3s.reconfigure_local_path(&output_tx).await?;
+                // The manifest marks local-path as the default again
+                storage::apply_storage_classes(&self.config.storage, &mut k3s.kube_ops, &output_tx)
+                    .await
+            }
+            ClusterComponent::Socat => k3s.reinstall_socat(&output_tx).await,
+            ClusterComponent::Kubeconfig => k3s.regenerate_kubeconfig(&output_tx).await,
+        }
+    }
+
+    /// Run a tool from the Chaos group on the running cluster
+    pub async fn chaos(
+        &self,
+        action: &ChaosAction,
+        output_tx: mpsc::Sender<OutputLine>,
+    ) -> Result<()> {
+        let k3s = self.k3s.as_ref().ok_or(K3devError::DockerUnavailable)?;
+        k3s.run_chaos(action, &output_tx).await
+    }
+
+    /// Save the data volumes of the stopped cluster to a tar file
+    pub async fn save_volumes(
+        &self,
+        dest: &Path,
+        output_tx: mpsc::Sender<OutputLine>,
+    ) -> Result<()> {
+        let k3s = self.k3s.as_ref().ok_or(K3devError::DockerUnavailable)?;
+        k3s.save_volumes(dest, &output_tx).await
+    }
+
+    /// Restore the data volumes from a tar file before the cluster is created
+    pub async fn restore_volumes(
+        &self,
+        src: &Path,
+        output_tx: mpsc::Sender<OutputLine>,
+    ) -> Result<()> {
+        let k3s = self.k3s.as_ref().ok_or(K3devError::DockerUnavailable)?;
+        k3s.restore_volumes(src, &output_tx).await
+    }
+
+    /// Snapshot images, newest first, for the Snapshots view
+    pub async fn list_snapshots(&self) -> Result<Vec<SnapshotInfo>> {
+        let k3s = self.k3s.as_ref().ok_or(K3devError::DockerUnavailable)?;
+        k3s.list_snapshots().await
+    }
+
+    /// Save the running cluster as a named snapshot
+    pub async fn save_snapshot(
+        &self,
+        name: &str,
+        output_tx: mpsc::Sender<OutputLine>,
+    ) -> Result<()> {
+        let k3s = self.k3s.as_ref().ok_or(K3devError::DockerUnavailable)?;
+        k3s.save_named_snapshot(name, &output_tx).await?;
+        Ok(())
+    }
+
     /// Delete all snapshot images
     pub async fn delete_snapshots(&self, output_tx: mpsc::Sender<OutputLine>) -> Result<()> {
         if let Some(k3s) = &self.k3s {
@@ -223,7 +533,31 @@ impl ClusterManager {
         Ok(())
     }
 
-    /// Get cluster info
+    /// Gather the snapshot shown by the Info view
+    pub async fn cluster_info(&mut self) -> ClusterInfo {
+        match &mut self.k3s {
+            Some(k3s) => k3s.info().await,
+            None => ClusterInfo::new(&self.config, ClusterStatus::RuntimeNotRunning),
+        }
+    }
+
+    /// Services with endpoints and listening probes, for the Services view
+    pub async fn services(&mut self) -> Result<Vec<ServiceInfo>> {
+        match &mut self.k3s {
+            Some(k3s) => k3s.services().await,
+            None => Err(K3devError::DockerUnavailable.into()),
+        }
+    }
+
+    /// crictl's pods and containers next to Docker's, for the Runtime view
+    pub async fn runtime(&mut self) -> Result<Vec<runtime::RuntimeEntry>> {
+        match &mut self.k3s {
+            Some(k3s) => k3s.runtime().await,
+            None => Err(K3devError::DockerUnavailable.into()),
+        }
+    }
+
+    /// Print cluster info (headless `info` command)
     pub async fn info(&mut self, output_tx: mpsc::Sender<OutputLine>) -> Result<()> {
         // Platform info
         let _ = output_tx.send(OutputLine::info("=== Platform ===")).await;
@@ -244,8 +578,8 @@ impl ClusterManager {
         }
 
         // K3s info
-        if let Some(k3s) = &mut self.k3s {
-            k3s.info(output_tx.clone()).await?;
+        for line in self.cluster_info().await.to_lines() {
+            let _ = output_tx.send(OutputLine::info(line)).await;
         }
 
         // Show ingress hosts
@@ -0,0 +1,148 @@
+//! On-demand cluster start
+//!
+//! While the cluster is down, k3dev can hold the ingress ports itself. The
+//! first connection to one of them is the signal to start the cluster: a
+//! plain HTTP request gets a page that reloads itself, then the ports are
+//! released so the cluster container can publish them.
+
+use std::sync::Mutex;
+use std::time::Duration;
+
+use anyhow::{Context, Result};
+use futures::future::select_ok;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::{TcpListener, TcpStream};
+
+/// Seconds the starting page waits before reloading
+const RELOAD_AFTER_SECS: u32 = 15;
+
+/// Longest wait for the request before answering anyway
+const READ_TIMEOUT: Duration = Duration::from_secs(1);
+
+/// Ports this process currently listens on, so the preflight check doesn't
+/// report them as taken by another service
+static HELD_PORTS: Mutex<Vec<u16>> = Mutex::new(Vec::new());
+
+/// Whether k3dev itself holds the port for an on-demand start
+pub fn holds(port: u16) -> bool {
+    HELD_PORTS.lock().is_ok_and(|held| held.contains(&port))
+}
+
+/// A bound port, listed in `HELD_PORTS` until dropped
+struct HeldPort {
+    port: u16,
+    listener: TcpListener,
+}
+
+impl HeldPort {
+    async fn bind(port: u16) -> Result<Self> {
+        let listener = TcpListener::bind(("0.0.0.0", port))
+            .await
+            .with_context(|| format!("Can't listen on port {}", port))?;
+        if let Ok(mut held) = HELD_PORTS.lock() {
+            held.push(port);
+        }
+        Ok(Self { port, listener })
+    }
+}
+
+impl Drop for HeldPort {
+    fn drop(&mut self) {
+        if let Ok(mut held) = HELD_PORTS.lock() {
+            if let Some(index) = held.iter().position(|&p| p == self.port) {
+                held.remove(index);
+            }
+        }
+    }
+}
+
+/// Listen on the HTTP and HTTPS ports until something connects; returns the
+/// port it connected to. The ports are free again once this returns.
+pub async fn wait_for_connection(http_port: u16, https_port: u16) -> Result<u16> {
+    let mut listeners = Vec::new();
+    for port in [http_port, https_port] {
+        listeners.push(HeldPort::bind(port).await?);
+    }
+
+    let accepts = listeners.iter().map(|held| {
+        Box::pin(async move {
+            held.listener
+                .accept()
+                .await
+                .map(|(stream, _)| (held.port, stream))
+        })
+    });
+    let ((port, mut stream), _) = select_ok(accepts)
+        .await
+        .context("Failed to accept a connection")?;
+    drop(listeners);
+
+    // TLS can't be answered without the cluster's certificate; the client
+    // just sees the connection close
+    if port == http_port {
+        answer_starting(&mut stream).await;
+    }
+    Ok(port)
+}
+
+/// Read what the client sent, so closing doesn't reset the connection
+/// before the page arrives, then send the page
+async fn answer_starting(stream: &mut TcpStream) {
+    let mut request = [0u8; 4096];
+    let _ = tokio::time::timeout(READ_TIMEOUT, stream.read(&mut request)).await;
+    let _ = stream.write_all(starting_response().as_bytes()).await;
+    let _ = stream.shutdown().await;
+}
+
+fn starting_response() -> String {
+    let body = format!(
+        "<!doctype html><html><head><meta http-equiv=\"refresh\" content=\"{secs}\">\
+         <title>Starting cluster</title></head><body>\
+         <p>k3dev is starting the cluster. This page reloads in {secs} seconds.</p>\
+         </body></html>",
+        secs = RELOAD_AFTER_SECS
+    );
+    format!(
+        "HTTP/1.1 503 Service Unavailable\r\nContent-Type: text/html; charset=utf-8\r\n\
+         Retry-After: {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+        RELOAD_AFTER_SECS,
+        body.len(),
+        body
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[tokio::test]
+    async fn first_connection_gets_the_starting_page() {
+        // Free ports picked by the OS, released again for the listener
+        let free = || std::net::TcpListener::bind("127.0.0.1:0").unwrap();
+        let (a, b) = (free(), free());
+        let (http, https) = (
+            a.local_addr().unwrap().port(),
+            b.local_addr().unwrap().port(),
+        );
+        drop((a, b));
+
+        let waiter = tokio::spawn(wait_for_connection(http, https));
+        let mut client = loop {
+            match TcpStream::connect(("127.0.0.1", http)).await {
+                Ok(stream) => break stream,
+                Err(_) => tokio::time::sleep(Duration::from_millis(10)).await,
+            }
+        };
+        assert!(holds(http) && holds(https));
+        client.write_all(b"GET / HTTP/1.1\r\n\r\n").await.unwrap();
+        let mut response = String::new();
+        client.read_to_string(&mut response).await.unwrap();
+
+        assert_eq!(waiter.await.unwrap().unwrap(), http);
+        assert!(response.starts_with("HTTP/1.1 503"));
+        // Both ports are released
+        assert!(!holds(http) && !holds(https));
+        assert!(std::net::TcpListener::bind(("0.0.0.0", http)).is_ok());
+        assert!(std::net::TcpListener::bind(("0.0.0.0", https)).is_ok());
+    }
+}
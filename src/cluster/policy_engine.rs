@@ -0,0 +1,53 @@
+//! Policy testing mode
+//!
+//! Installs Kyverno or OPA Gatekeeper from its release manifest, so a team's
+//! cluster policies can be applied locally and workloads checked against
+//! them before they are pushed. The **Policy Violations** view reads what
+//! the engine reports (see `k8s::policy_violations`).
+
+use crate::config::{CommandEntry, PolicyEngine};
+
+/// Release manifests; pinned so a lab cluster matches what was tested
+const KYVERNO_MANIFEST: &str =
+    "https://github.com/kyverno/kyverno/releases/download/v1.13.4/install.yaml";
+const GATEKEEPER_MANIFEST: &str =
+    "https://raw.githubusercontent.com/open-policy-agent/gatekeeper/v3.18.2/deploy/gatekeeper.yaml";
+
+/// Namespace the engine's controllers run in
+fn namespace(engine: PolicyEngine) -> &'static str {
+    match engine {
+        PolicyEngine::Kyverno => "kyverno",
+        PolicyEngine::Gatekeeper => "gatekeeper-system",
+    }
+}
+
+fn manifest(engine: PolicyEngine) -> &'static str {
+    match engine {
+        PolicyEngine::Kyverno => KYVERNO_MANIFEST,
+        PolicyEngine::Gatekeeper => GATEKEEPER_MANIFEST,
+    }
+}
+
+/// The host command installing `engine` and waiting for its controllers
+pub fn install_command(engine: PolicyEngine) -> CommandEntry {
+    // Kyverno's CRDs are too large for client-side apply
+    let script = format!(
+        "set -e\n\
+         kubectl apply --server-side -f {manifest}\n\
+         kubectl -n {ns} wait --for=condition=Available deployment --all --timeout=300s\n\
+         echo \"{label} is ready; apply your policies, then open Policy Violations\"\n",
+        manifest = manifest(engine),
+        ns = namespace(engine),
+        label = engine.label(),
+    );
+    CommandEntry::host(format!("Install {}", engine.label()), script)
+}
+
+/// The host command removing `engine` and everything its manifest created
+pub fn uninstall_command(engine: PolicyEngine) -> CommandEntry {
+    let script = format!(
+        "set -e\nkubectl delete --ignore-not-found -f {}\n",
+        manifest(engine)
+    );
+    CommandEntry::host(format!("Uninstall {}", engine.label()), script)
+}
@@ -0,0 +1,107 @@
+//! Readiness of the kube-system components a fresh cluster brings up
+//!
+//! Start returns once the API answers and the gates listed in
+//! `start.wait_for` are ready; the remaining components are waited for in the
+//! background. Every state change is broadcast so the UI can track it.
+
+use once_cell::sync::Lazy;
+use std::time::Duration;
+use tokio::sync::{broadcast, mpsc};
+
+use super::kube_ops::KubeOps;
+use crate::config::ReadinessGate;
+use crate::ui::components::OutputLine;
+
+/// State of one readiness gate
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReadinessState {
+    Waiting,
+    Ready,
+    TimedOut,
+}
+
+/// A gate changed state
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ReadinessUpdate {
+    pub gate: ReadinessGate,
+    pub state: ReadinessState,
+}
+
+/// Background gates outlive the start operation, so their progress is
+/// broadcast process-wide like hook completions.
+static READINESS: Lazy<broadcast::Sender<ReadinessUpdate>> = Lazy::new(|| broadcast::channel(16).0);
+
+/// Subscribe to readiness changes
+pub fn subscribe_readiness() -> broadcast::Receiver<ReadinessUpdate> {
+    READINESS.subscribe()
+}
+
+fn publish(gate: ReadinessGate, state: ReadinessState) {
+    // Err only means nobody is subscribed
+    let _ = READINESS.send(ReadinessUpdate { gate, state });
+}
+
+/// Split all gates into those start blocks on and those tracked in the
+/// background, keeping the canonical order
+pub fn partition_gates(wait_for: &[ReadinessGate]) -> (Vec<ReadinessGate>, Vec<ReadinessGate>) {
+    ReadinessGate::all()
+        .into_iter()
+        .partition(|gate| wait_for.contains(gate))
+}
+
+/// Wait for a gate's Deployment to have a ready replica
+pub async fn wait_for_gate(
+    gate: ReadinessGate,
+    timeout: Duration,
+    output_tx: mpsc::Sender<OutputLine>,
+) -> ReadinessState {
+    publish(gate, ReadinessState::Waiting);
+    let _ = output_tx
+        .send(OutputLine::info(format!("Waiting for {}...", gate.name())))
+        .await;
+
+    let mut kube_ops = KubeOps::new();
+    let state = match kube_ops
+        .wait_for_deployment_ready(gate.name(), "kube-system", timeout.as_secs())
+        .await
+    {
+        Ok(true) => ReadinessState::Ready,
+        Ok(false) | Err(_) => ReadinessState::TimedOut,
+    };
+
+    if state == ReadinessState::TimedOut {
+        let _ = output_tx
+            .send(OutputLine::warning(format!(
+                "{} not ready after {}s, continuing...",
+                gate.name(),
+                timeout.as_secs()
+            )))
+            .await;
+    }
+    publish(gate, state);
+    state
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn gates_split_into_blocking_and_background() {
+        let (blocking, background) = partition_gates(&[ReadinessGate::Coredns]);
+        assert_eq!(blocking, [ReadinessGate::Coredns]);
+        assert_eq!(background, [ReadinessGate::LocalPathProvisioner]);
+
+        let (blocking, background) = partition_gates(&[]);
+        assert!(blocking.is_empty());
+        assert_eq!(background, ReadinessGate::all());
+    }
+
+    #[test]
+    fn gate_names_parse_from_config() {
+        let gates: Vec<ReadinessGate> =
+            serde_yml::from_str("[coredns, local-path-provisioner]").unwrap();
+        assert_eq!(gates, ReadinessGate::all());
+        assert!(serde_yml::from_str::<Vec<ReadinessGate>>("[traefik]").is_err());
+    }
+}
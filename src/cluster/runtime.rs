@@ -0,0 +1,284 @@
+//! What the kubelet's runtime sees, next to what Docker sees
+//!
+//! k3s runs with `--docker`, so pods are Docker containers created through
+//! cri-dockerd and share their IDs with it. `k3s crictl` lists the pod
+//! sandboxes and containers the kubelet knows about; joining them with the
+//! `k8s_` containers in Docker by ID shows where the two disagree, e.g. a
+//! container Kubernetes thinks is running that Docker has stopped, or a
+//! leftover Docker container no pod owns.
+
+use std::collections::HashMap;
+
+use anyhow::{Context, Result};
+use serde::Deserialize;
+
+use super::docker::ContainerSummary;
+
+/// Prefix cri-dockerd gives the Docker containers it creates
+pub const POD_CONTAINER_PREFIX: &str = "k8s_";
+
+/// Shell command listing sandboxes and containers as two JSON documents
+pub const CRICTL_SCRIPT: &str = "k3s crictl pods -o json && echo '---' && k3s crictl ps -a -o json";
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RuntimeKind {
+    /// A pod sandbox (the pause container)
+    Pod,
+    Container,
+}
+
+/// One sandbox or container, from crictl, Docker or both
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RuntimeEntry {
+    pub kind: RuntimeKind,
+    pub id: String,
+    pub namespace: String,
+    pub pod: String,
+    /// Container name; empty for sandboxes
+    pub name: String,
+    pub attempt: u32,
+    /// crictl state, lowercased without its prefix ("running", "ready");
+    /// None when the runtime doesn't know the container
+    pub cri_state: Option<String>,
+    /// None when Docker has no such container
+    pub docker_state: Option<String>,
+}
+
+impl RuntimeEntry {
+    /// ID as crictl prints it
+    pub fn short_id(&self) -> &str {
+        &self.id[..self.id.len().min(13)]
+    }
+
+    /// How the two views disagree, if they do
+    pub fn mismatch(&self) -> Option<&'static str> {
+        match (&self.cri_state, &self.docker_state) {
+            (Some(_), None) => Some("missing in Docker"),
+            (None, Some(_)) => Some("unknown to Kubernetes"),
+            (Some(cri), Some(docker)) => {
+                let cri_running = matches!(cri.as_str(), "running" | "ready");
+                let docker_running = docker == "running";
+                match (cri_running, docker_running) {
+                    (true, false) => Some("stopped in Docker"),
+                    (false, true) => Some("still running in Docker"),
+                    _ => None,
+                }
+            }
+            (None, None) => None,
+        }
+    }
+}
+
+#[derive(Deserialize)]
+struct PodList {
+    #[serde(default)]
+    items: Vec<CriPod>,
+}
+
+#[derive(Deserialize)]
+struct CriPod {
+    id: String,
+    metadata: CriMetadata,
+    #[serde(default)]
+    state: String,
+}
+
+#[derive(Deserialize)]
+struct ContainerList {
+    #[serde(default)]
+    containers: Vec<CriContainer>,
+}
+
+#[derive(Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct CriContainer {
+    id: String,
+    #[serde(default)]
+    pod_sandbox_id: String,
+    metadata: CriMetadata,
+    #[serde(default)]
+    state: String,
+    #[serde(default)]
+    labels: HashMap<String, String>,
+}
+
+#[derive(Deserialize, Default)]
+struct CriMetadata {
+    #[serde(default)]
+    name: String,
+    #[serde(default)]
+    namespace: String,
+    #[serde(default)]
+    attempt: u32,
+}
+
+/// "CONTAINER_RUNNING" → "running", "SANDBOX_NOTREADY" → "notready"
+fn cri_state(state: &str) -> String {
+    state
+        .trim_start_matches("CONTAINER_")
+        .trim_start_matches("SANDBOX_")
+        .to_lowercase()
+}
+
+/// Entries from the output of [`CRICTL_SCRIPT`]
+pub fn parse_crictl(output: &str) -> Result<Vec<RuntimeEntry>> {
+    let (pods, containers) = output
+        .split_once("\n---\n")
+        .context("Unexpected crictl output")?;
+    let pods: PodList = serde_json::from_str(pods).context("Failed to parse crictl pods")?;
+    let containers: ContainerList =
+        serde_json::from_str(containers).context("Failed to parse crictl ps")?;
+
+    let sandboxes: HashMap<&str, &CriPod> = pods.items.iter().map(|p| (p.id.as_str(), p)).collect();
+    let mut entries: Vec<RuntimeEntry> = pods
+        .items
+        .iter()
+        .map(|pod| RuntimeEntry {
+            kind: RuntimeKind::Pod,
+            id: pod.id.clone(),
+            namespace: pod.metadata.namespace.clone(),
+            pod: pod.metadata.name.clone(),
+            name: String::new(),
+            attempt: pod.metadata.attempt,
+            cri_state: Some(cri_state(&pod.state)),
+            docker_state: None,
+        })
+        .collect();
+    for container in &containers.containers {
+        // Older crictl versions leave the pod labels off, the sandbox has them
+        let sandbox = sandboxes.get(container.pod_sandbox_id.as_str());
+        let namespace = container
+            .labels
+            .get("io.kubernetes.pod.namespace")
+            .cloned()
+            .or_else(|| sandbox.map(|s| s.metadata.namespace.clone()))
+            .unwrap_or_default();
+        let pod = container
+            .labels
+            .get("io.kubernetes.pod.name")
+            .cloned()
+            .or_else(|| sandbox.map(|s| s.metadata.name.clone()))
+            .unwrap_or_default();
+        entries.push(RuntimeEntry {
+            kind: RuntimeKind::Container,
+            id: container.id.clone(),
+            namespace,
+            pod,
+            name: container.metadata.name.clone(),
+            attempt: container.metadata.attempt,
+            cri_state: Some(cri_state(&container.state)),
+            docker_state: None,
+        });
+    }
+    Ok(entries)
+}
+
+/// Fill in Docker's side and add the `k8s_` containers crictl doesn't list.
+/// Entries end up sorted by namespace, pod, sandbox first.
+pub fn cross_reference(
+    mut entries: Vec<RuntimeEntry>,
+    docker: &[ContainerSummary],
+) -> Vec<RuntimeEntry> {
+    let by_id: HashMap<&str, &ContainerSummary> =
+        docker.iter().map(|c| (c.id.as_str(), c)).collect();
+    for entry in &mut entries {
+        entry.docker_state = by_id.get(entry.id.as_str()).map(|c| c.state.clone());
+    }
+    let known: Vec<String> = entries.iter().map(|e| e.id.clone()).collect();
+    for container in docker {
+        if known.contains(&container.id) {
+            continue;
+        }
+        entries.push(docker_only_entry(container));
+    }
+    entries.sort_by(|a, b| {
+        (
+            &a.namespace,
+            &a.pod,
+            a.kind == RuntimeKind::Container,
+            &a.name,
+        )
+            .cmp(&(
+                &b.namespace,
+                &b.pod,
+                b.kind == RuntimeKind::Container,
+                &b.name,
+            ))
+    });
+    entries
+}
+
+/// Entry for a Docker container named
+/// `k8s_{container}_{pod}_{namespace}_{uid}_{attempt}`
+fn docker_only_entry(container: &ContainerSummary) -> RuntimeEntry {
+    let parts: Vec<&str> = container.name.split('_').collect();
+    let part = |i: usize| parts.get(i).copied().unwrap_or_default().to_string();
+    let kind = if part(1) == "POD" {
+        RuntimeKind::Pod
+    } else {
+        RuntimeKind::Container
+    };
+    RuntimeEntry {
+        kind,
+        id: container.id.clone(),
+        namespace: part(3),
+        pod: part(2),
+        name: if kind == RuntimeKind::Pod {
+            String::new()
+        } else {
+            part(1)
+        },
+        attempt: parts.get(5).and_then(|a| a.parse().ok()).unwrap_or(0),
+        cri_state: None,
+        docker_state: Some(container.state.clone()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const OUTPUT: &str = r#"{"items":[{"id":"aaa111","metadata":{"name":"web-1","uid":"u1","namespace":"apps","attempt":0},"state":"SANDBOX_READY"}]}
+---
+{"containers":[{"id":"bbb222","podSandboxId":"aaa111","metadata":{"name":"web","attempt":2},"state":"CONTAINER_RUNNING","labels":{"io.kubernetes.pod.name":"web-1","io.kubernetes.pod.namespace":"apps"}}]}
+"#;
+
+    fn docker(id: &str, name: &str, state: &str) -> ContainerSummary {
+        ContainerSummary {
+            id: id.to_string(),
+            name: name.to_string(),
+            state: state.to_string(),
+        }
+    }
+
+    #[test]
+    fn crictl_output_is_parsed() {
+        let entries = parse_crictl(OUTPUT).unwrap();
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[0].kind, RuntimeKind::Pod);
+        assert_eq!(entries[0].cri_state.as_deref(), Some("ready"));
+        assert_eq!(entries[1].pod, "web-1");
+        assert_eq!(entries[1].name, "web");
+        assert_eq!(entries[1].attempt, 2);
+        assert_eq!(entries[1].cri_state.as_deref(), Some("running"));
+    }
+
+    #[test]
+    fn docker_state_is_joined_and_mismatches_flagged() {
+        let entries = cross_reference(
+            parse_crictl(OUTPUT).unwrap(),
+            &[
+                docker("aaa111", "k8s_POD_web-1_apps_u1_0", "running"),
+                docker("bbb222", "k8s_web_web-1_apps_u1_2", "exited"),
+                docker("ccc333", "k8s_old_web-0_apps_u0_0", "running"),
+            ],
+        );
+        assert_eq!(entries.len(), 3);
+        assert_eq!(entries[0].pod, "web-0");
+        assert_eq!(entries[0].name, "old");
+        assert_eq!(entries[0].mismatch(), Some("unknown to Kubernetes"));
+        assert_eq!(entries[1].kind, RuntimeKind::Pod);
+        assert_eq!(entries[1].mismatch(), None);
+        assert_eq!(entries[2].mismatch(), Some("stopped in Docker"));
+    }
+}
@@ -0,0 +1,144 @@
+//! Following the k3s server container's logs
+//!
+//! Most cluster-level problems (etcd/kine, the kubelet, cri-dockerd,
+//! networking) only show up in the server's own output. Lines are colored
+//! by their level, which k3s writes as `level=...` for its own messages and
+//! as a klog prefix (`E1016 ...`) for the embedded Kubernetes components.
+
+use anyhow::Result;
+use futures_util::StreamExt;
+use tokio::sync::mpsc;
+
+use super::DockerManager;
+use crate::ui::components::OutputLine;
+
+/// Earlier lines shown before following
+const INITIAL_TAIL: usize = 200;
+
+/// Stream the container's logs into `output_tx` until it stops
+pub async fn follow_server_logs(
+    container: &str,
+    output_tx: &mpsc::Sender<OutputLine>,
+) -> Result<()> {
+    let docker = DockerManager::from_default_socket()?;
+    if !docker.container_exists(container).await {
+        anyhow::bail!(
+            "Container {} does not exist; start the cluster first",
+            container
+        );
+    }
+    let mut logs = Box::pin(docker.follow_logs(container, INITIAL_TAIL));
+    let mut pending = String::new();
+    while let Some(chunk) = logs.next().await {
+        pending.push_str(&chunk?);
+        for line in take_lines(&mut pending) {
+            if output_tx.send(log_line(&line)).await.is_err() {
+                return Ok(());
+            }
+        }
+    }
+    if !pending.is_empty() {
+        let _ = output_tx.send(log_line(&pending)).await;
+    }
+    let _ = output_tx
+        .send(OutputLine::warning(format!("{} stopped", container)))
+        .await;
+    Ok(())
+}
+
+/// Complete lines of `buffer`; a trailing partial line stays in it
+fn take_lines(buffer: &mut String) -> Vec<String> {
+    let Some(end) = buffer.rfind('\n') else {
+        return Vec::new();
+    };
+    let rest = buffer.split_off(end + 1);
+    let lines = buffer
+        .lines()
+        .filter(|l| !l.trim().is_empty())
+        .map(str::to_string)
+        .collect();
+    *buffer = rest;
+    lines
+}
+
+#[derive(Debug, PartialEq, Eq)]
+enum Level {
+    Error,
+    Warning,
+    Info,
+}
+
+fn level(line: &str) -> Level {
+    if let Some(rest) = line.split_once("level=").map(|(_, rest)| rest) {
+        let value = rest
+            .split_whitespace()
+            .next()
+            .unwrap_or("")
+            .trim_matches('"');
+        return match value {
+            "error" | "fatal" | "panic" => Level::Error,
+            "warning" | "warn" => Level::Warning,
+            _ => Level::Info,
+        };
+    }
+    // klog: severity letter, then MMDD
+    let mut chars = line.chars();
+    let severity = chars.next();
+    let dated = chars.take(4).all(|c| c.is_ascii_digit());
+    match severity {
+        Some('E' | 'F') if dated => Level::Error,
+        Some('W') if dated => Level::Warning,
+        _ if line.starts_with("panic:") => Level::Error,
+        _ => Level::Info,
+    }
+}
+
+fn log_line(line: &str) -> OutputLine {
+    match level(line) {
+        Level::Error => OutputLine::error(line),
+        Level::Warning => OutputLine::warning(line),
+        Level::Info => OutputLine::info(line),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn levels_come_from_logrus_and_klog_lines() {
+        assert_eq!(
+            level(r#"time="2026-10-16T10:00:00Z" level=error msg="Failed to connect""#),
+            Level::Error
+        );
+        assert_eq!(
+            level(r#"time="2026-10-16T10:00:00Z" level=warning msg="Unable to read""#),
+            Level::Warning
+        );
+        assert_eq!(
+            level("E1016 10:00:00.123456      42 pod_workers.go:1301] Error syncing pod"),
+            Level::Error
+        );
+        assert_eq!(
+            level("W1016 10:00:00.123456      42 reflector.go:561] failed to list"),
+            Level::Warning
+        );
+        assert_eq!(
+            level("I1016 10:00:00.123456      42 server.go:123] Started"),
+            Level::Info
+        );
+        assert_eq!(level("Error-free startup"), Level::Info);
+        assert_eq!(level("panic: runtime error"), Level::Error);
+    }
+
+    #[test]
+    fn partial_lines_wait_for_their_end() {
+        let mut buffer = "first\nsecond\nthi".to_string();
+        assert_eq!(take_lines(&mut buffer), vec!["first", "second"]);
+        assert_eq!(buffer, "thi");
+        assert!(take_lines(&mut buffer).is_empty());
+        buffer.push_str("rd\n");
+        assert_eq!(take_lines(&mut buffer), vec!["third"]);
+        assert!(buffer.is_empty());
+    }
+}
@@ -0,0 +1,227 @@
+//! Services with their endpoints and listening ports, shown by the Services view
+
+use std::collections::{HashMap, HashSet};
+
+use k8s_openapi::api::core::v1::Service;
+use k8s_openapi::api::discovery::v1::EndpointSlice;
+
+/// Label EndpointSlices carry with the name of their Service
+const SERVICE_NAME_LABEL: &str = "kubernetes.io/service-name";
+
+/// One Service and what backs it
+#[derive(Debug, Clone, PartialEq)]
+pub struct ServiceInfo {
+    pub namespace: String,
+    pub name: String,
+    /// ClusterIP, NodePort, LoadBalancer or ExternalName
+    pub type_: String,
+    /// None for headless and ExternalName services
+    pub cluster_ip: Option<String>,
+    pub ports: Vec<ServicePortInfo>,
+    /// Whether endpoints come from a pod selector (vs. managed by hand)
+    pub has_selector: bool,
+    /// Ready endpoint addresses
+    pub endpoints: usize,
+}
+
+/// One port of a Service, with the result of the listening probe
+#[derive(Debug, Clone, PartialEq)]
+pub struct ServicePortInfo {
+    pub port: u16,
+    pub node_port: Option<u16>,
+    pub protocol: String,
+    /// None when the port was not probed (UDP, headless, probe failed)
+    pub listening: Option<bool>,
+}
+
+impl ServiceInfo {
+    /// Build from the API object; endpoints and probes are filled in later
+    pub fn from_service(service: Service) -> Self {
+        let spec = service.spec.unwrap_or_default();
+        let cluster_ip = spec.cluster_ip.filter(|ip| !ip.is_empty() && ip != "None");
+        let ports = spec
+            .ports
+            .unwrap_or_default()
+            .into_iter()
+            .map(|p| ServicePortInfo {
+                port: u16::try_from(p.port).unwrap_or(0),
+                node_port: p.node_port.and_then(|n| u16::try_from(n).ok()),
+                protocol: p.protocol.unwrap_or_else(|| "TCP".to_string()),
+                listening: None,
+            })
+            .collect();
+
+        Self {
+            namespace: service.metadata.namespace.unwrap_or_default(),
+            name: service.metadata.name.unwrap_or_default(),
+            type_: spec.type_.unwrap_or_else(|| "ClusterIP".to_string()),
+            cluster_ip,
+            ports,
+            has_selector: spec.selector.is_some_and(|s| !s.is_empty()),
+            endpoints: 0,
+        }
+    }
+
+    /// A selector that matches no ready pod usually means a label typo
+    pub fn missing_endpoints(&self) -> bool {
+        self.has_selector && self.endpoints == 0
+    }
+
+    /// `ip:port` targets the listening probe connects to
+    pub fn probe_targets(&self) -> Vec<String> {
+        let Some(ip) = &self.cluster_ip else {
+            return Vec::new();
+        };
+        self.ports
+            .iter()
+            .filter(|p| p.protocol == "TCP")
+            .map(|p| format!("{}:{}", ip, p.port))
+            .collect()
+    }
+
+    /// Record which of this service's probe targets accepted a connection
+    pub fn apply_probe(&mut self, open: &HashSet<String>) {
+        let Some(ip) = self.cluster_ip.clone() else {
+            return;
+        };
+        for port in self.ports.iter_mut().filter(|p| p.protocol == "TCP") {
+            port.listening = Some(open.contains(&format!("{}:{}", ip, port.port)));
+        }
+    }
+}
+
+/// Ready endpoint addresses per `namespace/service`
+pub fn count_ready_endpoints(slices: &[EndpointSlice]) -> HashMap<String, usize> {
+    let mut counts = HashMap::new();
+    for slice in slices {
+        let Some(service) = slice
+            .metadata
+            .labels
+            .as_ref()
+            .and_then(|l| l.get(SERVICE_NAME_LABEL))
+        else {
+            continue;
+        };
+        let namespace = slice.metadata.namespace.as_deref().unwrap_or_default();
+        // Endpoints without conditions are ready per the API docs
+        let ready: usize = slice
+            .endpoints
+            .iter()
+            .filter(|e| e.conditions.as_ref().and_then(|c| c.ready).unwrap_or(true))
+            .map(|e| e.addresses.len())
+            .sum();
+        *counts
+            .entry(format!("{}/{}", namespace, service))
+            .or_default() += ready;
+    }
+    counts
+}
+
+/// Shell script run in the k3s container that connects to every target in
+/// parallel and prints `<target> open` for those that accept. socat is always
+/// installed there by the cluster setup.
+pub fn probe_script(targets: &[String]) -> String {
+    let checks: Vec<String> = targets
+        .iter()
+        .map(|t| {
+            format!(
+                "(socat -T1 /dev/null TCP:{},connect-timeout=1 >/dev/null 2>&1 && echo '{} open') &",
+                t, t
+            )
+        })
+        .collect();
+    format!("{} wait", checks.join(" "))
+}
+
+/// Targets the probe script reported as open
+pub fn parse_probe_output(output: &str) -> HashSet<String> {
+    output
+        .lines()
+        .filter_map(|line| line.trim().strip_suffix(" open"))
+        .map(str::to_string)
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use k8s_openapi::api::core::v1::{ServicePort, ServiceSpec};
+    use k8s_openapi::api::discovery::v1::{Endpoint, EndpointConditions};
+    use k8s_openapi::apimachinery::pkg::apis::meta::v1::ObjectMeta;
+    use std::collections::BTreeMap;
+
+    fn slice(service: &str, ready: &[bool]) -> EndpointSlice {
+        EndpointSlice {
+            metadata: ObjectMeta {
+                namespace: Some("apps".to_string()),
+                labels: Some(BTreeMap::from([(
+                    SERVICE_NAME_LABEL.to_string(),
+                    service.to_string(),
+                )])),
+                ..Default::default()
+            },
+            endpoints: ready
+                .iter()
+                .map(|&ready| Endpoint {
+                    addresses: vec!["10.42.0.5".to_string()],
+                    conditions: Some(EndpointConditions {
+                        ready: Some(ready),
+                        ..Default::default()
+                    }),
+                    ..Default::default()
+                })
+                .collect(),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn counts_only_ready_endpoints() {
+        let counts = count_ready_endpoints(&[
+            slice("web", &[true, false]),
+            slice("web", &[true]),
+            slice("api", &[false]),
+        ]);
+        assert_eq!(counts["apps/web"], 2);
+        assert_eq!(counts["apps/api"], 0);
+    }
+
+    #[test]
+    fn selector_without_endpoints_is_flagged() {
+        let service = Service {
+            metadata: ObjectMeta {
+                name: Some("web".to_string()),
+                namespace: Some("apps".to_string()),
+                ..Default::default()
+            },
+            spec: Some(ServiceSpec {
+                cluster_ip: Some("10.43.0.10".to_string()),
+                selector: Some(BTreeMap::from([("app".to_string(), "wbe".to_string())])),
+                ports: Some(vec![
+                    ServicePort {
+                        port: 80,
+                        ..Default::default()
+                    },
+                    ServicePort {
+                        port: 53,
+                        protocol: Some("UDP".to_string()),
+                        ..Default::default()
+                    },
+                ]),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        let mut info = ServiceInfo::from_service(service);
+        assert!(info.missing_endpoints());
+        assert_eq!(info.probe_targets(), ["10.43.0.10:80"]);
+
+        let open = parse_probe_output("10.43.0.10:80 open\n");
+        info.apply_probe(&open);
+        assert_eq!(info.ports[0].listening, Some(true));
+        assert_eq!(info.ports[1].listening, None);
+
+        info.has_selector = false;
+        assert!(!info.missing_endpoints());
+    }
+}
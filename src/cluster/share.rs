@@ -0,0 +1,102 @@
+//! Sharing an ingress port over SSH
+//!
+//! `ssh -R` publishes a local port on another host, so a teammate or a phone
+//! can reach the cluster's ingress without access to this machine. The
+//! forward lasts until it is stopped or ssh exits.
+
+use std::process::Stdio;
+
+use anyhow::{anyhow, bail, Context, Result};
+use tokio::io::AsyncReadExt;
+use tokio::process::Command;
+use tokio_util::sync::CancellationToken;
+
+use crate::config::ShareConfig;
+
+/// `user@host`, or only the host when no user is set
+pub fn destination(config: &ShareConfig) -> String {
+    if config.user.is_empty() {
+        config.host.clone()
+    } else {
+        format!("{}@{}", config.user, config.host)
+    }
+}
+
+/// Arguments for an ssh that only forwards `remote_port` on the host to
+/// `local_port` here. It never asks for a password, which would land in the
+/// TUI, and exits when the forward can't be set up or the link dies.
+fn ssh_args(config: &ShareConfig, local_port: u16) -> Vec<String> {
+    vec![
+        "-N".to_string(),
+        "-o".to_string(),
+        "BatchMode=yes".to_string(),
+        "-o".to_string(),
+        "ExitOnForwardFailure=yes".to_string(),
+        "-o".to_string(),
+        "ServerAliveInterval=30".to_string(),
+        "-R".to_string(),
+        format!("{}:localhost:{}", config.remote_port, local_port),
+        destination(config),
+    ]
+}
+
+/// Forward `local_port` to the share host until `stop` is cancelled. An
+/// error carries what ssh said when it gave up.
+pub async fn share_port(
+    config: &ShareConfig,
+    local_port: u16,
+    stop: CancellationToken,
+) -> Result<()> {
+    if config.host.is_empty() {
+        bail!("Set share.host in the config to share ports");
+    }
+    let mut child = Command::new("ssh")
+        .args(ssh_args(config, local_port))
+        .stdin(Stdio::null())
+        .stdout(Stdio::null())
+        .stderr(Stdio::piped())
+        .kill_on_drop(true)
+        .spawn()
+        .context("Failed to run ssh")?;
+
+    let status = tokio::select! {
+        status = child.wait() => status.context("Failed to wait for ssh")?,
+        _ = stop.cancelled() => {
+            let _ = child.kill().await;
+            return Ok(());
+        }
+    };
+
+    let mut stderr = String::new();
+    if let Some(mut pipe) = child.stderr.take() {
+        let _ = pipe.read_to_string(&mut stderr).await;
+    }
+    let reason = stderr
+        .lines()
+        .rev()
+        .find(|l| !l.trim().is_empty())
+        .map(|l| l.trim().to_string())
+        .unwrap_or_else(|| format!("ssh exited with {}", status));
+    Err(anyhow!(reason))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn forwards_the_remote_port_to_the_local_one() {
+        let mut config = ShareConfig {
+            host: "demo.example.com".to_string(),
+            ..Default::default()
+        };
+        let args = ssh_args(&config, 443);
+        assert_eq!(
+            args[args.len() - 2..],
+            ["8080:localhost:443", "demo.example.com"]
+        );
+
+        config.user = "deploy".to_string();
+        assert_eq!(destination(&config), "deploy@demo.example.com");
+    }
+}
@@ -0,0 +1,145 @@
+//! Extra StorageClasses and the default class (`infrastructure.storage`)
+//!
+//! k3s ships one StorageClass, `local-path`, marked as the default. Classes
+//! from the config are created or updated at every start; when
+//! `default_class` names another class, the default annotation is moved to
+//! it, so claims without a `storageClassName` use it.
+
+use std::collections::BTreeMap;
+
+use anyhow::Result;
+use k8s_openapi::api::storage::v1::StorageClass;
+use k8s_openapi::apimachinery::pkg::apis::meta::v1::ObjectMeta;
+use tokio::sync::mpsc;
+
+use super::kube_ops::KubeOps;
+use crate::config::{StorageClassConfig, StorageConfig};
+use crate::ui::components::OutputLine;
+
+/// Annotation marking the cluster's default StorageClass
+pub const DEFAULT_CLASS_ANNOTATION: &str = "storageclass.kubernetes.io/is-default-class";
+
+/// The StorageClass k3s creates
+pub const K3S_STORAGE_CLASS: &str = "local-path";
+
+/// The StorageClass object for a configured class
+pub fn storage_class(config: &StorageClassConfig, is_default: bool) -> StorageClass {
+    StorageClass {
+        metadata: ObjectMeta {
+            name: Some(config.name.clone()),
+            annotations: Some(BTreeMap::from([(
+                DEFAULT_CLASS_ANNOTATION.to_string(),
+                is_default.to_string(),
+            )])),
+            ..Default::default()
+        },
+        provisioner: config.provisioner.clone(),
+        reclaim_policy: Some(
+            config
+                .reclaim_policy
+                .clone()
+                .unwrap_or_else(|| "Delete".to_string()),
+        ),
+        volume_binding_mode: Some(
+            config
+                .volume_binding_mode
+                .clone()
+                .unwrap_or_else(|| "WaitForFirstConsumer".to_string()),
+        ),
+        allow_volume_expansion: Some(config.allow_volume_expansion),
+        parameters: (!config.parameters.is_empty()).then(|| config.parameters.clone()),
+        ..Default::default()
+    }
+}
+
+/// The class that should be the default: `default_class`, else k3s's
+pub fn default_class_name(config: &StorageConfig) -> &str {
+    config.default_class.as_deref().unwrap_or(K3S_STORAGE_CLASS)
+}
+
+/// Create or update the configured classes and move the default
+/// annotation to `default_class`
+pub async fn apply_storage_classes(
+    config: &StorageConfig,
+    kube_ops: &mut KubeOps,
+    output_tx: &mpsc::Sender<OutputLine>,
+) -> Result<()> {
+    if config.classes.is_empty() && config.default_class.is_none() {
+        return Ok(());
+    }
+    let default = default_class_name(config);
+
+    for class in &config.classes {
+        let _ = output_tx
+            .send(OutputLine::info(format!(
+                "Applying StorageClass {}...",
+                class.name
+            )))
+            .await;
+        // Provisioner, parameters and policies are immutable once created
+        if let Err(e) = kube_ops
+            .apply_storage_class(&storage_class(class, class.name == default))
+            .await
+        {
+            let _ = output_tx
+                .send(OutputLine::warning(format!(
+                    "StorageClass {} not applied (delete it to change immutable fields): {:#}",
+                    class.name, e
+                )))
+                .await;
+        }
+    }
+
+    let names = kube_ops.list_storage_class_names().await?;
+    if !names.iter().any(|name| name == default) {
+        let _ = output_tx
+            .send(OutputLine::warning(format!(
+                "Default StorageClass {} not found; the default is unchanged",
+                default
+            )))
+            .await;
+        return Ok(());
+    }
+    for name in &names {
+        let is_default = name == default;
+        kube_ops
+            .annotate_storage_class(name, DEFAULT_CLASS_ANNOTATION, &is_default.to_string())
+            .await?;
+    }
+    let _ = output_tx
+        .send(OutputLine::info(format!(
+            "Default StorageClass: {}",
+            default
+        )))
+        .await;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn configured_class_fills_in_defaults_and_marks_the_default() {
+        let config: StorageConfig = serde_yml::from_str(
+            "default_class: fast\nclasses:\n  - name: fast\n    reclaim_policy: Retain\n    parameters: {nodePath: /data}\n",
+        )
+        .unwrap();
+        assert_eq!(default_class_name(&config), "fast");
+
+        let class = storage_class(&config.classes[0], true);
+        assert_eq!(class.provisioner, "rancher.io/local-path");
+        assert_eq!(class.reclaim_policy.as_deref(), Some("Retain"));
+        assert_eq!(
+            class.volume_binding_mode.as_deref(),
+            Some("WaitForFirstConsumer")
+        );
+        assert_eq!(
+            class.metadata.annotations.unwrap()[DEFAULT_CLASS_ANNOTATION],
+            "true"
+        );
+        assert_eq!(class.parameters.unwrap()["nodePath"], "/data");
+
+        assert_eq!(default_class_name(&StorageConfig::default()), "local-path");
+    }
+}
@@ -0,0 +1,385 @@
+//! Support bundle for bug reports
+//!
+//! Collects the k3dev log, the config with secrets redacted, the k3s
+//! container log, node and pod listings, events and version info into a
+//! tarball. A section that can't be collected (e.g. the cluster is down) is
+//! written as a note instead, since a bundle of a broken cluster is the one
+//! that matters most.
+
+use std::io::{Read, Seek, SeekFrom};
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use k8s_openapi::api::core::v1::{Event, Node, Pod};
+use kube::api::{Api, ListParams};
+use kube::Client;
+use serde_yml::Value;
+
+use crate::cluster::{ClusterConfig, DockerManager};
+use crate::config::Config;
+use crate::k8s::K8sClient;
+
+/// Only the end of the k3dev log is kept
+const MAX_LOG_BYTES: u64 = 2 * 1024 * 1024;
+
+/// Lines of the k3s container log to keep
+const K3S_LOG_LINES: usize = 5000;
+
+/// Mapping keys whose values are replaced in the bundled config
+const SECRET_KEY_PARTS: &[&str] = &[
+    "password",
+    "passwd",
+    "secret",
+    "token",
+    "apikey",
+    "api_key",
+    "credential",
+    "private_key",
+];
+
+const REDACTED: &str = "<redacted>";
+
+/// A file of the bundle, or why it couldn't be collected
+pub struct BundleSection {
+    pub name: &'static str,
+    pub contents: Result<String, String>,
+}
+
+/// Collect every section; never fails as a whole
+pub async fn collect(
+    config: &Config,
+    cluster_config: &ClusterConfig,
+    config_file: Option<&Path>,
+) -> Vec<BundleSection> {
+    let docker = DockerManager::from_default_socket();
+    let client = K8sClient::new(
+        cluster_config.kubeconfig.as_deref(),
+        cluster_config.context.as_deref(),
+    )
+    .await
+    .map(|c| c.client().clone());
+
+    let mut sections = vec![BundleSection {
+        name: "version.txt",
+        contents: Ok(
+            version_info(cluster_config, docker.as_ref().ok(), client.as_ref().ok()).await,
+        ),
+    }];
+    sections.push(BundleSection {
+        name: "config.yaml",
+        contents: match config_file {
+            Some(path) => std::fs::read_to_string(path)
+                .map_err(|e| format!("Failed to read {}: {}", path.display(), e))
+                .and_then(|raw| redact_secrets(&raw).map_err(|e| format!("{:#}", e))),
+            None => Err("No config file found; built-in defaults are in use".to_string()),
+        },
+    });
+    let log_file = config
+        .logging
+        .file
+        .replace("{cluster_name}", &config.infrastructure.cluster_name);
+    sections.push(BundleSection {
+        name: "k3dev.log",
+        contents: if config.logging.enabled {
+            read_tail(Path::new(&log_file), MAX_LOG_BYTES).map_err(|e| format!("{:#}", e))
+        } else {
+            Err("File logging is disabled (logging.enabled: false)".to_string())
+        },
+    });
+    sections.push(BundleSection {
+        name: "k3s.log",
+        contents: match &docker {
+            Ok(docker) => docker
+                .container_logs(&cluster_config.container_name, K3S_LOG_LINES)
+                .await
+                .map_err(|e| format!("{:#}", e)),
+            Err(e) => Err(format!("{:#}", e)),
+        },
+    });
+
+    let listings = match &client {
+        Ok(client) => [
+            list_nodes(client).await,
+            list_pods(client).await,
+            list_events(client).await,
+        ]
+        .map(|listing| listing.map_err(|e| format!("{:#}", e))),
+        Err(e) => [(); 3].map(|_| Err(format!("Cluster is not reachable: {}", e))),
+    };
+    for (name, contents) in ["nodes.txt", "pods.txt", "events.txt"]
+        .into_iter()
+        .zip(listings)
+    {
+        sections.push(BundleSection { name, contents });
+    }
+    sections
+}
+
+/// Write the sections into a tar archive under a single top-level directory
+pub fn write_tar(sections: &[BundleSection], path: &Path) -> Result<()> {
+    let file = std::fs::File::create(path)
+        .with_context(|| format!("Failed to create {}", path.display()))?;
+    let dir = path
+        .file_stem()
+        .and_then(|s| s.to_str())
+        .unwrap_or("k3dev-support");
+    let mtime = chrono::Utc::now().timestamp().max(0) as u64;
+
+    let mut builder = tar::Builder::new(file);
+    for section in sections {
+        let (name, data) = match &section.contents {
+            Ok(contents) => (section.name.to_string(), contents.clone()),
+            Err(e) => (format!("{}.unavailable", section.name), format!("{}\n", e)),
+        };
+        let mut header = tar::Header::new_gnu();
+        header.set_size(data.len() as u64);
+        header.set_mode(0o644);
+        header.set_mtime(mtime);
+        header.set_cksum();
+        builder
+            .append_data(&mut header, format!("{}/{}", dir, name), data.as_bytes())
+            .with_context(|| format!("Failed to add {} to the bundle", name))?;
+    }
+    builder
+        .into_inner()
+        .with_context(|| format!("Failed to write {}", path.display()))?;
+    Ok(())
+}
+
+/// Default bundle path in the current directory
+pub fn default_bundle_path(cluster_name: &str) -> PathBuf {
+    PathBuf::from(format!(
+        "k3dev-support-{}-{}.tar",
+        cluster_name,
+        chrono::Local::now().format("%Y%m%d-%H%M%S")
+    ))
+}
+
+async fn version_info(
+    cluster_config: &ClusterConfig,
+    docker: Option<&DockerManager>,
+    client: Option<&Client>,
+) -> String {
+    let mut lines = vec![
+        format!("k3dev: {}", env!("CARGO_PKG_VERSION")),
+        format!("host: {} {}", std::env::consts::OS, std::env::consts::ARCH),
+        format!("configured k3s: {}", cluster_config.k3s_version),
+        format!("k3s container: {}", cluster_config.container_name),
+    ];
+    match docker {
+        Some(docker) => {
+            lines.push(match docker.server_version().await {
+                Ok(version) => format!("docker: {}", version),
+                Err(e) => format!("docker: unavailable ({:#})", e),
+            });
+            lines.push(format!(
+                "k3s container status: {}",
+                docker
+                    .container_status(&cluster_config.container_name)
+                    .await
+                    .unwrap_or_else(|| "not found".to_string())
+            ));
+        }
+        None => lines.push("docker: unavailable".to_string()),
+    }
+    lines.push(match client {
+        Some(client) => match client.apiserver_version().await {
+            Ok(info) => format!("api server: {}", info.git_version),
+            Err(e) => format!("api server: unreachable ({})", e),
+        },
+        None => "api server: unreachable".to_string(),
+    });
+    lines.join("\n") + "\n"
+}
+
+/// Replace the values of secret-looking keys anywhere in a YAML document
+pub fn redact_secrets(raw: &str) -> Result<String> {
+    let mut value: Value = serde_yml::from_str(raw).context("Failed to parse the config")?;
+    redact_value(&mut value);
+    serde_yml::to_string(&value).context("Failed to serialize the config")
+}
+
+fn redact_value(value: &mut Value) {
+    match value {
+        Value::Mapping(map) => {
+            for (key, value) in map.iter_mut() {
+                let is_secret = key.as_str().is_some_and(|k| {
+                    let k = k.to_ascii_lowercase();
+                    SECRET_KEY_PARTS.iter().any(|part| k.contains(part))
+                });
+                if is_secret && !value.is_mapping() && !value.is_sequence() {
+                    *value = Value::String(REDACTED.to_string());
+                } else {
+                    redact_value(value);
+                }
+            }
+        }
+        Value::Sequence(items) => items.iter_mut().for_each(redact_value),
+        Value::Tagged(tagged) => redact_value(&mut tagged.value),
+        _ => {}
+    }
+}
+
+/// Up to the last `max` bytes of a file
+fn read_tail(path: &Path, max: u64) -> Result<String> {
+    let mut file =
+        std::fs::File::open(path).with_context(|| format!("Failed to open {}", path.display()))?;
+    let len = file.metadata()?.len();
+    if len > max {
+        file.seek(SeekFrom::Start(len - max))?;
+    }
+    let mut data = Vec::new();
+    file.read_to_end(&mut data)?;
+    Ok(String::from_utf8_lossy(&data).into_owned())
+}
+
+async fn list_nodes(client: &Client) -> Result<String> {
+    let nodes = Api::<Node>::all(client.clone())
+        .list(&ListParams::default())
+        .await
+        .context("Failed to list nodes")?;
+    let mut out = format!(
+        "{:<24} {:<10} {:<16} {}\n",
+        "NAME", "READY", "VERSION", "CONDITIONS"
+    );
+    for node in nodes {
+        let status = node.status.unwrap_or_default();
+        let conditions = status.conditions.unwrap_or_default();
+        let ready = conditions
+            .iter()
+            .find(|c| c.type_ == "Ready")
+            .map(|c| c.status.clone())
+            .unwrap_or_else(|| "Unknown".to_string());
+        let problems: Vec<String> = conditions
+            .iter()
+            .filter(|c| c.type_ != "Ready" && c.status == "True")
+            .map(|c| c.type_.clone())
+            .collect();
+        out.push_str(&format!(
+            "{:<24} {:<10} {:<16} {}\n",
+            node.metadata.name.unwrap_or_default(),
+            ready,
+            status
+                .node_info
+                .map(|i| i.kubelet_version)
+                .unwrap_or_default(),
+            if problems.is_empty() {
+                "-".to_string()
+            } else {
+                problems.join(",")
+            }
+        ));
+    }
+    Ok(out)
+}
+
+async fn list_pods(client: &Client) -> Result<String> {
+    let pods = Api::<Pod>::all(client.clone())
+        .list(&ListParams::default())
+        .await
+        .context("Failed to list pods")?;
+    let mut out = format!(
+        "{:<20} {:<48} {:<10} {:<6} {:<9} {}\n",
+        "NAMESPACE", "NAME", "PHASE", "READY", "RESTARTS", "WAITING"
+    );
+    for pod in pods {
+        let status = pod.status.unwrap_or_default();
+        let containers = status.container_statuses.unwrap_or_default();
+        let ready = containers.iter().filter(|c| c.ready).count();
+        let restarts: i32 = containers.iter().map(|c| c.restart_count).sum();
+        let waiting: Vec<String> = containers
+            .iter()
+            .filter_map(|c| {
+                let reason = c.state.as_ref()?.waiting.as_ref()?.reason.clone()?;
+                Some(format!("{}={}", c.name, reason))
+            })
+            .collect();
+        out.push_str(&format!(
+            "{:<20} {:<48} {:<10} {:<6} {:<9} {}\n",
+            pod.metadata.namespace.unwrap_or_default(),
+            pod.metadata.name.unwrap_or_default(),
+            status.phase.unwrap_or_default(),
+            format!("{}/{}", ready, containers.len()),
+            restarts,
+            if waiting.is_empty() {
+                "-".to_string()
+            } else {
+                waiting.join(",")
+            }
+        ));
+    }
+    Ok(out)
+}
+
+async fn list_events(client: &Client) -> Result<String> {
+    let events = Api::<Event>::all(client.clone())
+        .list(&ListParams::default())
+        .await
+        .context("Failed to list events")?;
+    let mut rows: Vec<(String, String)> = events
+        .into_iter()
+        .map(|e| {
+            let time = e
+                .last_timestamp
+                .map(|t| t.0.to_string())
+                .or_else(|| e.event_time.map(|t| t.0.to_string()))
+                .unwrap_or_default();
+            let object = format!(
+                "{}/{}",
+                e.involved_object.kind.unwrap_or_default().to_lowercase(),
+                e.involved_object.name.unwrap_or_default()
+            );
+            let line = format!(
+                "{:<28} {:<20} {:<8} {:<24} {:<40} {}",
+                time,
+                e.metadata.namespace.unwrap_or_default(),
+                e.type_.unwrap_or_default(),
+                e.reason.unwrap_or_default(),
+                object,
+                e.message.unwrap_or_default().trim()
+            );
+            (time, line)
+        })
+        .collect();
+    rows.sort_by(|a, b| a.0.cmp(&b.0));
+
+    let mut out = format!(
+        "{:<28} {:<20} {:<8} {:<24} {:<40} {}\n",
+        "LAST SEEN", "NAMESPACE", "TYPE", "REASON", "OBJECT", "MESSAGE"
+    );
+    for (_, line) in rows {
+        out.push_str(&line);
+        out.push('\n');
+    }
+    Ok(out)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn redacts_secret_values() {
+        let raw = "\
+infrastructure:
+  cluster_name: dev
+commands:
+  - name: Deploy
+    env:
+      API_TOKEN: abc123
+      REGION: eu
+    registry:
+      password: hunter2
+      secrets:
+        - name: a
+";
+        let redacted = redact_secrets(raw).unwrap();
+        assert!(!redacted.contains("abc123"));
+        assert!(!redacted.contains("hunter2"));
+        assert!(redacted.contains("API_TOKEN: <redacted>"));
+        assert!(redacted.contains("REGION: eu"));
+        assert!(redacted.contains("cluster_name: dev"));
+        // Nested structures under a secret-looking key are walked, not replaced
+        assert!(redacted.contains("name: a"));
+    }
+}
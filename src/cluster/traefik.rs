@@ -480,47 +480,8 @@ impl TraefikManager {
             .send(OutputLine::info("Applying Traefik configuration..."))
             .await;
 
-        let dashboard_domain = self.config.traefik_dashboard_domain();
-        let match_rule = format!(
-            "Host(`{}`) && (PathPrefix(`/dashboard`) || PathPrefix(`/api`))",
-            dashboard_domain
-        );
-
         // HelmChartConfig to customize K3s built-in Traefik
-        let helm_chart_config = format!(
-            r#"apiVersion: helm.cattle.io/v1
-kind: HelmChartConfig
-metadata:
-  name: traefik
-  namespace: kube-system
-spec:
-  valuesContent: |-
-    ports:
-      web:
-        nodePort: {http_port}
-      websecure:
-        nodePort: {https_port}
-    service:
-      type: NodePort
-    tlsStore:
-      default:
-        defaultCertificate:
-          secretName: traefik-tls
-    providers:
-      kubernetesCRD:
-        allowExternalNameServices: true
-        allowEmptyServices: true
-    ingressRoute:
-      dashboard:
-        enabled: true
-        matchRule: "{match_rule}"
-        entryPoints:
-          - websecure
-"#,
-            http_port = self.config.http_port,
-            https_port = self.config.https_port,
-            match_rule = match_rule,
-        );
+        let helm_chart_config = render_helm_chart_config(&self.config);
 
         // Apply via kube API
         self.kube_ops.apply_yaml(&helm_chart_config).await?;
@@ -563,3 +524,54 @@ spec:
         Err(anyhow!("Timeout waiting for Traefik"))
     }
 }
+
+/// Name and namespace of the HelmChartConfig k3dev applies
+pub const HELM_CHART_CONFIG_NAME: &str = "traefik";
+pub const HELM_CHART_CONFIG_NAMESPACE: &str = "kube-system";
+
+/// Helm values k3dev sets on K3s built-in Traefik
+pub fn render_traefik_values(config: &ClusterConfig) -> String {
+    let match_rule = format!(
+        "Host(`{}`) && (PathPrefix(`/dashboard`) || PathPrefix(`/api`))",
+        config.traefik_dashboard_domain()
+    );
+    format!(
+        r#"ports:
+  web:
+    nodePort: {http_port}
+  websecure:
+    nodePort: {https_port}
+service:
+  type: NodePort
+tlsStore:
+  default:
+    defaultCertificate:
+      secretName: traefik-tls
+providers:
+  kubernetesCRD:
+    allowExternalNameServices: true
+    allowEmptyServices: true
+ingressRoute:
+  dashboard:
+    enabled: true
+    matchRule: "{match_rule}"
+    entryPoints:
+      - websecure
+"#,
+        http_port = config.http_port,
+        https_port = config.https_port,
+        match_rule = match_rule,
+    )
+}
+
+/// The HelmChartConfig manifest carrying `render_traefik_values`
+pub fn render_helm_chart_config(config: &ClusterConfig) -> String {
+    let values: String = render_traefik_values(config)
+        .lines()
+        .map(|line| format!("    {}\n", line))
+        .collect();
+    format!(
+        "apiVersion: helm.cattle.io/v1\nkind: HelmChartConfig\nmetadata:\n  name: {}\n  namespace: {}\nspec:\n  valuesContent: |-\n{}",
+        HELM_CHART_CONFIG_NAME, HELM_CHART_CONFIG_NAMESPACE, values
+    )
+}
@@ -0,0 +1,201 @@
+//! Traefik config drift detection
+//!
+//! k3dev configures K3s built-in Traefik through a HelmChartConfig. Edits
+//! made with kubectl or Helm (or a k3s upgrade resetting it) go unnoticed
+//! until ingresses stop answering on the configured ports, so the live
+//! values are compared with what `render_traefik_values` would apply.
+
+use std::collections::BTreeMap;
+use std::fmt;
+
+use anyhow::Result;
+
+use super::kube_ops::KubeOps;
+use super::traefik::{render_traefik_values, HELM_CHART_CONFIG_NAME, HELM_CHART_CONFIG_NAMESPACE};
+use super::ClusterConfig;
+
+/// A value that differs from what k3dev applies; `None` where it's absent
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ValueDrift {
+    /// Dotted path, e.g. "ports.web.nodePort"
+    pub path: String,
+    pub expected: Option<String>,
+    pub live: Option<String>,
+}
+
+impl fmt::Display for ValueDrift {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (&self.expected, &self.live) {
+            (Some(expected), Some(live)) => {
+                write!(f, "{}: {} (expected {})", self.path, live, expected)
+            }
+            (Some(expected), None) => write!(f, "{}: missing (expected {})", self.path, expected),
+            (None, Some(live)) => write!(f, "{}: {} (not set by k3dev)", self.path, live),
+            (None, None) => write!(f, "{}", self.path),
+        }
+    }
+}
+
+/// How the live Traefik config differs from k3dev's
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum TraefikDrift {
+    /// The HelmChartConfig was deleted
+    Missing,
+    /// `valuesContent` isn't valid YAML
+    Unparseable(String),
+    /// Values that were changed, added or removed
+    Changed(Vec<ValueDrift>),
+}
+
+impl TraefikDrift {
+    /// One line per difference, for the output panel
+    pub fn details(&self) -> Vec<String> {
+        match self {
+            TraefikDrift::Missing => vec![format!(
+                "HelmChartConfig {}/{} not found",
+                HELM_CHART_CONFIG_NAMESPACE, HELM_CHART_CONFIG_NAME
+            )],
+            TraefikDrift::Unparseable(e) => vec![format!("valuesContent is not valid YAML: {}", e)],
+            TraefikDrift::Changed(values) => values.iter().map(ToString::to_string).collect(),
+        }
+    }
+}
+
+impl fmt::Display for TraefikDrift {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            TraefikDrift::Missing => write!(f, "HelmChartConfig missing"),
+            TraefikDrift::Unparseable(_) => write!(f, "values unparseable"),
+            TraefikDrift::Changed(values) if values.len() == 1 => {
+                write!(f, "{} changed", values[0].path)
+            }
+            TraefikDrift::Changed(values) => write!(f, "{} values changed", values.len()),
+        }
+    }
+}
+
+/// Flatten a YAML value into dotted leaf paths and their rendered values
+fn flatten(value: &serde_yml::Value, path: &str, out: &mut BTreeMap<String, String>) {
+    let join = |key: &str| {
+        if path.is_empty() {
+            key.to_string()
+        } else {
+            format!("{}.{}", path, key)
+        }
+    };
+    match value {
+        serde_yml::Value::Mapping(map) => {
+            for (key, value) in map {
+                let key = match key {
+                    serde_yml::Value::String(s) => s.clone(),
+                    other => scalar(other),
+                };
+                flatten(value, &join(&key), out);
+            }
+        }
+        serde_yml::Value::Sequence(items) => {
+            for (i, item) in items.iter().enumerate() {
+                flatten(item, &format!("{}[{}]", path, i), out);
+            }
+        }
+        serde_yml::Value::Tagged(tagged) => flatten(&tagged.value, path, out),
+        other => {
+            out.insert(path.to_string(), scalar(other));
+        }
+    }
+}
+
+fn scalar(value: &serde_yml::Value) -> String {
+    match value {
+        serde_yml::Value::String(s) => s.clone(),
+        other => serde_yml::to_string(other)
+            .map(|s| s.trim_end().to_string())
+            .unwrap_or_default(),
+    }
+}
+
+/// Compare two Helm values documents leaf by leaf. Key order and
+/// formatting don't matter; `None` means no drift.
+pub fn diff_values(expected: &str, live: &str) -> Option<TraefikDrift> {
+    let expected: serde_yml::Value = serde_yml::from_str(expected).unwrap_or_default();
+    let live: serde_yml::Value = match serde_yml::from_str(live) {
+        Ok(value) => value,
+        Err(e) => return Some(TraefikDrift::Unparseable(e.to_string())),
+    };
+    let (mut want, mut have) = (BTreeMap::new(), BTreeMap::new());
+    flatten(&expected, "", &mut want);
+    flatten(&live, "", &mut have);
+
+    let mut changed: Vec<ValueDrift> = want
+        .iter()
+        .filter(|(path, value)| have.get(*path) != Some(value))
+        .map(|(path, value)| ValueDrift {
+            path: path.clone(),
+            expected: Some(value.clone()),
+            live: have.get(path).cloned(),
+        })
+        .collect();
+    changed.extend(
+        have.iter()
+            .filter(|(path, _)| !want.contains_key(*path))
+            .map(|(path, value)| ValueDrift {
+                path: path.clone(),
+                expected: None,
+                live: Some(value.clone()),
+            }),
+    );
+    (!changed.is_empty()).then_some(TraefikDrift::Changed(changed))
+}
+
+/// Compare the live HelmChartConfig with the one k3dev would apply
+pub async fn check_drift(
+    config: &ClusterConfig,
+    kube_ops: &mut KubeOps,
+) -> Result<Option<TraefikDrift>> {
+    let live = kube_ops
+        .get_helm_chart_values(HELM_CHART_CONFIG_NAME, HELM_CHART_CONFIG_NAMESPACE)
+        .await?;
+    Ok(match live {
+        Some(live) => diff_values(&render_traefik_values(config), &live),
+        None => Some(TraefikDrift::Missing),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const EXPECTED: &str = "ports:\n  web:\n    nodePort: 80\nservice:\n  type: NodePort\nentryPoints:\n  - websecure\n";
+
+    #[test]
+    fn changed_added_and_removed_values_are_drift() {
+        // Same values, different layout
+        assert_eq!(
+            diff_values(
+                EXPECTED,
+                "service: {type: NodePort}\nentryPoints: [websecure]\nports:\n  web:\n    nodePort: 80\n"
+            ),
+            None
+        );
+
+        let drift = diff_values(
+            EXPECTED,
+            "ports:\n  web:\n    nodePort: 8080\nentryPoints:\n  - websecure\nlogs:\n  access: true\n",
+        )
+        .unwrap();
+        assert_eq!(
+            drift.details(),
+            [
+                "ports.web.nodePort: 8080 (expected 80)",
+                "service.type: missing (expected NodePort)",
+                "logs.access: true (not set by k3dev)",
+            ]
+        );
+        assert_eq!(drift.to_string(), "3 values changed");
+
+        assert!(matches!(
+            diff_values(EXPECTED, "ports: [unclosed"),
+            Some(TraefikDrift::Unparseable(_))
+        ));
+    }
+}
@@ -0,0 +1,182 @@
+//! Public tunnels to an ingress host
+//!
+//! cloudflared or ngrok forwards a public URL to the ingress HTTP port and
+//! rewrites the Host header, so the ingress rule for that host still matches
+//! on requests from anywhere. The client stays up until the tunnel is closed
+//! or the cluster stops.
+
+use std::process::Stdio;
+use std::time::Duration;
+
+use anyhow::{anyhow, bail, Context, Result};
+use tokio::io::{AsyncBufReadExt, AsyncRead, BufReader, Lines};
+use tokio::process::{Child, Command};
+use tokio_util::sync::CancellationToken;
+
+use crate::config::TunnelProvider;
+
+/// How long the client gets to print its public URL
+const OPEN_TIMEOUT: Duration = Duration::from_secs(30);
+
+type LogLines = Lines<BufReader<Box<dyn AsyncRead + Send + Unpin>>>;
+
+/// A running tunnel client and its public URL
+pub struct Tunnel {
+    child: Child,
+    log: LogLines,
+    pub url: String,
+}
+
+fn binary(provider: TunnelProvider) -> &'static str {
+    match provider {
+        TunnelProvider::Cloudflared => "cloudflared",
+        TunnelProvider::Ngrok => "ngrok",
+    }
+}
+
+/// Arguments that tunnel to `local_port` and present requests as `host`
+fn client_args(provider: TunnelProvider, host: &str, local_port: u16) -> Vec<String> {
+    let target = format!("http://localhost:{}", local_port);
+    match provider {
+        TunnelProvider::Cloudflared => vec![
+            "tunnel".to_string(),
+            "--no-autoupdate".to_string(),
+            "--url".to_string(),
+            target,
+            "--http-host-header".to_string(),
+            host.to_string(),
+        ],
+        TunnelProvider::Ngrok => vec![
+            "http".to_string(),
+            target,
+            format!("--host-header={}", host),
+            "--log=stdout".to_string(),
+            "--log-format=logfmt".to_string(),
+        ],
+    }
+}
+
+/// The public URL in a line of the client's log, if it's the one that has it
+fn public_url(provider: TunnelProvider, line: &str) -> Option<String> {
+    let url = match provider {
+        // The banner also links to cloudflare.com; only the quick tunnel
+        // name counts
+        TunnelProvider::Cloudflared => line
+            .split(|c: char| c.is_whitespace() || c == '|')
+            .find(|word| word.starts_with("https://") && word.ends_with(".trycloudflare.com"))?,
+        TunnelProvider::Ngrok => line
+            .split_whitespace()
+            .find_map(|field| field.strip_prefix("url="))
+            .filter(|url| url.starts_with("https://"))?,
+    };
+    Some(url.to_string())
+}
+
+impl Tunnel {
+    /// Start the client and wait until it reports its public URL. An error
+    /// carries the client's last log line when it gives up before that.
+    pub async fn open(provider: TunnelProvider, host: &str, local_port: u16) -> Result<Self> {
+        let mut command = Command::new(binary(provider));
+        command
+            .args(client_args(provider, host, local_port))
+            .stdin(Stdio::null())
+            .kill_on_drop(true);
+        // cloudflared logs to stderr, ngrok as told to stdout
+        match provider {
+            TunnelProvider::Cloudflared => command.stdout(Stdio::null()).stderr(Stdio::piped()),
+            TunnelProvider::Ngrok => command.stdout(Stdio::piped()).stderr(Stdio::null()),
+        };
+        let mut child = command
+            .spawn()
+            .with_context(|| format!("Failed to run {}", binary(provider)))?;
+        let pipe: Box<dyn AsyncRead + Send + Unpin> = match provider {
+            TunnelProvider::Cloudflared => Box::new(child.stderr.take().context("No log pipe")?),
+            TunnelProvider::Ngrok => Box::new(child.stdout.take().context("No log pipe")?),
+        };
+        let mut log = BufReader::new(pipe).lines();
+
+        let mut last = String::new();
+        let found = tokio::time::timeout(OPEN_TIMEOUT, async {
+            while let Some(line) = log.next_line().await? {
+                if let Some(url) = public_url(provider, &line) {
+                    return Ok(Some(url));
+                }
+                if !line.trim().is_empty() {
+                    last = line.trim().to_string();
+                }
+            }
+            Ok::<_, std::io::Error>(None)
+        })
+        .await;
+
+        match found {
+            Ok(Ok(Some(url))) => Ok(Self { child, log, url }),
+            Ok(Ok(None)) | Ok(Err(_)) if !last.is_empty() => Err(anyhow!(last)),
+            Ok(Ok(None)) | Ok(Err(_)) => bail!("{} exited without a public URL", binary(provider)),
+            Err(_) => bail!(
+                "{} gave no public URL within {}s",
+                binary(provider),
+                OPEN_TIMEOUT.as_secs()
+            ),
+        }
+    }
+
+    /// Keep the tunnel up until `stop` is cancelled. An error means the
+    /// client exited on its own.
+    pub async fn run(mut self, stop: CancellationToken) -> Result<()> {
+        loop {
+            tokio::select! {
+                _ = stop.cancelled() => {
+                    let _ = self.child.kill().await;
+                    return Ok(());
+                }
+                // Drain the log so the client never blocks on a full pipe
+                line = self.log.next_line() => {
+                    if !matches!(line, Ok(Some(_))) {
+                        break;
+                    }
+                }
+            }
+        }
+        let status = self
+            .child
+            .wait()
+            .await
+            .context("Failed to wait for the tunnel")?;
+        bail!("Tunnel client exited with {}", status)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_the_public_url() {
+        let cloudflared = [
+            "2024-05-01T10:00:00Z INF Thank you for trying Cloudflare Tunnel. See https://www.cloudflare.com/website-terms/",
+            "2024-05-01T10:00:02Z INF |  https://quiet-lake-1234.trycloudflare.com                                 |",
+        ];
+        assert_eq!(
+            public_url(TunnelProvider::Cloudflared, cloudflared[0]),
+            None
+        );
+        assert_eq!(
+            public_url(TunnelProvider::Cloudflared, cloudflared[1]).as_deref(),
+            Some("https://quiet-lake-1234.trycloudflare.com")
+        );
+
+        let ngrok = r#"t=2024-05-01T10:00:02+0000 lvl=info msg="started tunnel" obj=tunnels name=command_line addr=http://localhost:80 url=https://ab12.ngrok-free.app"#;
+        assert_eq!(
+            public_url(TunnelProvider::Ngrok, ngrok).as_deref(),
+            Some("https://ab12.ngrok-free.app")
+        );
+        assert_eq!(
+            public_url(
+                TunnelProvider::Ngrok,
+                "t=... lvl=info msg=\"open config file\""
+            ),
+            None
+        );
+    }
+}
@@ -0,0 +1,203 @@
+//! Watchdog for the k3s container
+//!
+//! Notices the container exiting without k3dev stopping it, and the API
+//! server hanging while the container keeps running. With
+//! `watchdog.auto_restart` it asks for a restart after a delay that doubles
+//! with each restart that didn't bring the cluster back.
+
+use std::fmt;
+use std::time::{Duration, Instant};
+
+use super::health_backoff::{random_unit, BackoffPolicy};
+use crate::config::WatchdogConfig;
+
+/// Wait before the first restart
+const FIRST_RESTART_DELAY: Duration = Duration::from_secs(10);
+
+/// Why the watchdog thinks the cluster is dead
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Failure {
+    /// The container stopped on its own
+    Exited,
+    /// The container runs but its API keeps failing, with the last error
+    ApiHung(String),
+}
+
+impl fmt::Display for Failure {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Failure::Exited => write!(f, "k3s container exited unexpectedly"),
+            Failure::ApiHung(error) => write!(f, "k3s API is not answering ({})", error),
+        }
+    }
+}
+
+/// Failure tracking and restart scheduling; the app feeds it observations
+/// and runs the restarts it asks for
+#[derive(Debug)]
+pub struct Watchdog {
+    auto_restart: bool,
+    api_failure_limit: u32,
+    policy: BackoffPolicy,
+    api_failures: u32,
+    failure: Option<Failure>,
+    /// Restarts since the cluster was last healthy for a while
+    restarts: u32,
+    restart_at: Option<Instant>,
+    restarting: bool,
+    last_restart: Option<Instant>,
+}
+
+impl Watchdog {
+    pub fn new(config: &WatchdogConfig) -> Self {
+        Self {
+            auto_restart: config.auto_restart,
+            api_failure_limit: config.api_failures.max(1),
+            policy: BackoffPolicy {
+                base: FIRST_RESTART_DELAY,
+                max: config.max_backoff.max(FIRST_RESTART_DELAY),
+                jitter: 0.1,
+            },
+            api_failures: 0,
+            failure: None,
+            restarts: 0,
+            restart_at: None,
+            restarting: false,
+            last_restart: None,
+        }
+    }
+
+    /// The container stopped without k3dev stopping it; returns the failure
+    /// when it is news
+    pub fn exited(&mut self, now: Instant) -> Option<Failure> {
+        self.fail(Failure::Exited, now)
+    }
+
+    /// An API check failed; returns the failure once enough failed in a row
+    pub fn api_failed(&mut self, error: &str, now: Instant) -> Option<Failure> {
+        self.api_failures += 1;
+        if self.api_failures < self.api_failure_limit {
+            return None;
+        }
+        self.fail(Failure::ApiHung(error.to_string()), now)
+    }
+
+    /// An API check passed: whatever was wrong is over. Restarts only stop
+    /// counting once the cluster outlived the longest backoff.
+    pub fn api_ok(&mut self, now: Instant) {
+        self.api_failures = 0;
+        self.failure = None;
+        self.restart_at = None;
+        let stable = self
+            .last_restart
+            .is_none_or(|at| now.duration_since(at) >= self.policy.max);
+        if stable {
+            self.restarts = 0;
+        }
+    }
+
+    /// k3dev stopped or removed the cluster on purpose
+    pub fn reset(&mut self) {
+        self.api_failures = 0;
+        self.failure = None;
+        self.restart_at = None;
+        self.restarting = false;
+    }
+
+    /// Time until the next restart, while one is scheduled
+    pub fn restart_in(&self, now: Instant) -> Option<Duration> {
+        self.restart_at.map(|at| at.saturating_duration_since(now))
+    }
+
+    /// The restart to run now, as its attempt number
+    pub fn due(&mut self, now: Instant) -> Option<u32> {
+        if self.restart_at.is_none_or(|at| now < at) {
+            return None;
+        }
+        self.restart_at = None;
+        self.restarting = true;
+        self.restarts += 1;
+        self.last_restart = Some(now);
+        Some(self.restarts)
+    }
+
+    /// A restart the watchdog asked for ended; if the cluster isn't healthy
+    /// yet, the next one is scheduled and the API gets a fresh count
+    pub fn restart_finished(&mut self, now: Instant) {
+        if !std::mem::take(&mut self.restarting) {
+            return;
+        }
+        self.api_failures = 0;
+        if self.failure.is_some() {
+            self.schedule(now);
+        }
+    }
+
+    fn fail(&mut self, failure: Failure, now: Instant) -> Option<Failure> {
+        if self.failure.is_some() || self.restarting {
+            return None;
+        }
+        self.failure = Some(failure.clone());
+        self.schedule(now);
+        Some(failure)
+    }
+
+    fn schedule(&mut self, now: Instant) {
+        if self.auto_restart {
+            self.restart_at = Some(now + self.policy.delay(self.restarts + 1, random_unit()));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn watchdog(auto_restart: bool) -> Watchdog {
+        Watchdog::new(&WatchdogConfig {
+            auto_restart,
+            api_failures: 3,
+            max_backoff: Duration::from_secs(300),
+        })
+    }
+
+    #[test]
+    fn hung_api_needs_consecutive_failures() {
+        let now = Instant::now();
+        let mut dog = watchdog(false);
+        assert_eq!(dog.api_failed("timed out", now), None);
+        assert_eq!(dog.api_failed("timed out", now), None);
+        dog.api_ok(now);
+        assert_eq!(dog.api_failed("timed out", now), None);
+        assert_eq!(dog.api_failed("timed out", now), None);
+        assert_eq!(
+            dog.api_failed("timed out", now),
+            Some(Failure::ApiHung("timed out".to_string()))
+        );
+        // Reported once, and nothing restarts without auto_restart
+        assert_eq!(dog.api_failed("timed out", now), None);
+        assert_eq!(dog.restart_in(now), None);
+    }
+
+    #[test]
+    fn restarts_back_off_until_the_cluster_is_healthy() {
+        let now = Instant::now();
+        let mut dog = watchdog(true);
+        assert_eq!(dog.exited(now), Some(Failure::Exited));
+        let first = dog.restart_in(now).unwrap();
+        assert!(first >= Duration::from_secs(9) && first <= Duration::from_secs(11));
+        assert_eq!(dog.due(now), None);
+
+        let later = now + first;
+        assert_eq!(dog.due(later), Some(1));
+        // Still down after the restart: the next one waits twice as long
+        dog.restart_finished(later);
+        let second = dog.restart_in(later).unwrap();
+        assert!(second >= Duration::from_secs(18));
+
+        assert_eq!(dog.due(later + second), Some(2));
+        dog.api_ok(later + second);
+        dog.restart_finished(later + second);
+        assert_eq!(dog.restart_in(later + second), None);
+    }
+}
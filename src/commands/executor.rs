@@ -6,8 +6,12 @@
 use std::future::Future;
 use std::time::Duration;
 use tokio::sync::mpsc;
+use tokio_util::sync::CancellationToken;
 
-use crate::app::AppMessage;
+use super::outcome::{CommandOutcome, OutputClassifier};
+use crate::app::{AppMessage, JobId};
+use crate::cluster::DockerManager;
+use crate::error::{self, K3devError};
 use crate::ui::components::OutputLine;
 
 /// Context for command execution
@@ -16,6 +20,10 @@ pub struct CommandContext {
     pub output_tx: mpsc::Sender<OutputLine>,
     /// Sender for app messages
     pub message_tx: mpsc::Sender<AppMessage>,
+    /// Job the output and completion are reported for
+    pub job: JobId,
+    /// Stops the operation when cancelled
+    pub cancel: CancellationToken,
     /// Timeout duration
     pub timeout: Duration,
 }
@@ -24,6 +32,8 @@ impl CommandContext {
     /// Create a new command context
     pub fn new(
         message_tx: mpsc::Sender<AppMessage>,
+        job: JobId,
+        cancel: CancellationToken,
         timeout: Duration,
     ) -> (Self, mpsc::Sender<OutputLine>) {
         let (output_tx, mut output_rx) = mpsc::channel::<OutputLine>(100);
@@ -32,7 +42,7 @@ impl CommandContext {
         let msg_tx = message_tx.clone();
         tokio::spawn(async move {
             while let Some(line) = output_rx.recv().await {
-                let _ = msg_tx.send(AppMessage::OutputLine(line)).await;
+                let _ = msg_tx.send(AppMessage::RunOutput { job, line }).await;
             }
         });
 
@@ -40,43 +50,191 @@ impl CommandContext {
             Self {
                 output_tx: output_tx.clone(),
                 message_tx,
+                job,
+                cancel,
                 timeout,
             },
             output_tx,
         )
     }
 
-    /// Execute an async operation with timeout and proper completion handling
+    /// Execute an async operation with timeout, cancellation and proper completion handling
     ///
     /// The operation receives an output sender and should return Ok(()) on success
-    /// or Err(error_message) on failure.
+    /// or the error on failure, which is reported with its remediation when the
+    /// cause is known. A cancelled operation is dropped and reported with exit
+    /// code 130.
     pub async fn execute<F, Fut>(self, operation: F)
     where
         F: FnOnce(mpsc::Sender<OutputLine>) -> Fut + Send + 'static,
-        Fut: Future<Output = Result<(), String>> + Send,
+        Fut: Future<Output = anyhow::Result<()>> + Send,
     {
         let output_tx = self.output_tx;
         let message_tx = self.message_tx;
+        let job = self.job;
         let timeout = self.timeout;
 
-        let result = tokio::time::timeout(timeout, operation(output_tx)).await;
+        let result = tokio::select! {
+            _ = self.cancel.cancelled() => None,
+            result = tokio::time::timeout(timeout, operation(output_tx)) => Some(result),
+        };
 
-        match result {
-            Ok(Ok(_)) => {
-                let _ = message_tx.send(AppMessage::CommandComplete(0)).await;
+        let (error, exit_code) = match result {
+            None => (None, 130),
+            Some(Ok(Ok(_))) => (None, 0),
+            Some(Ok(Err(e))) => (Some(e), 1),
+            Some(Err(_)) => (Some(K3devError::Timeout("Operation".to_string()).into()), 1),
+        };
+        if let Some(error) = error {
+            for line in error::report_lines(&error) {
+                let _ = message_tx.send(AppMessage::RunOutput { job, line }).await;
             }
-            Ok(Err(e)) => {
-                let _ = message_tx.send(AppMessage::Error(e)).await;
-                let _ = message_tx.send(AppMessage::CommandComplete(1)).await;
+        }
+        let _ = message_tx
+            .send(AppMessage::CommandComplete { job, exit_code })
+            .await;
+    }
+
+    /// Execute a custom command whose exit code and output are judged by its
+    /// success criteria. The operation returns the process exit code; output
+    /// lines are recolored by the classifier before reaching the popup.
+    pub async fn execute_classified<F, Fut>(
+        self,
+        command: String,
+        mut classifier: OutputClassifier,
+        operation: F,
+    ) where
+        F: FnOnce(mpsc::Sender<OutputLine>) -> Fut + Send + 'static,
+        Fut: Future<Output = Result<i32, String>> + Send,
+    {
+        let (raw_tx, mut raw_rx) = mpsc::channel::<OutputLine>(100);
+        let run = tokio::time::timeout(self.timeout, operation(raw_tx));
+        tokio::pin!(run);
+
+        let result = loop {
+            tokio::select! {
+                Some(line) = raw_rx.recv() => {
+                    let _ = self.output_tx.send(classifier.classify(line)).await;
+                }
+                result = &mut run => break Some(result),
+                _ = self.cancel.cancelled() => break None,
             }
-            Err(_) => {
-                let _ = message_tx
-                    .send(AppMessage::Error("Operation timed out".to_string()))
-                    .await;
-                let _ = message_tx.send(AppMessage::CommandComplete(1)).await;
+        };
+        // Lines still buffered when the operation finished
+        while let Ok(line) = raw_rx.try_recv() {
+            let _ = self.output_tx.send(classifier.classify(line)).await;
+        }
+
+        let outcome = match result {
+            None => CommandOutcome::Failed {
+                exit_code: None,
+                reason: "Cancelled".to_string(),
+            },
+            Some(Ok(Ok(exit_code))) => classifier.outcome(exit_code),
+            Some(Ok(Err(reason))) => CommandOutcome::Failed {
+                exit_code: None,
+                reason,
+            },
+            Some(Err(_)) => CommandOutcome::Failed {
+                exit_code: None,
+                reason: "Operation timed out".to_string(),
+            },
+        };
+        let _ = self
+            .message_tx
+            .send(AppMessage::CommandFinished {
+                job: self.job,
+                command,
+                outcome,
+            })
+            .await;
+    }
+}
+
+/// Run a host-side shell command, streaming stdout as info and stderr as
+/// error lines. Returns the exit code.
+pub async fn run_host_command(
+    command: &str,
+    workdir: &str,
+    output_tx: mpsc::Sender<OutputLine>,
+) -> Result<i32, String> {
+    use std::process::Stdio;
+    use tokio::io::{AsyncBufReadExt, BufReader};
+    use tokio::process::Command;
+
+    let mut cmd = Command::new("sh");
+    cmd.arg("-c").arg(command);
+    if !workdir.is_empty() {
+        cmd.current_dir(workdir);
+    }
+    cmd.stdout(Stdio::piped());
+    cmd.stderr(Stdio::piped());
+    // A cancelled job drops this future; take the process down with it
+    cmd.kill_on_drop(true);
+
+    let mut child = cmd
+        .spawn()
+        .map_err(|e| format!("Failed to spawn host command: {}", e))?;
+
+    let stdout = child.stdout.take();
+    let stderr = child.stderr.take();
+
+    let stdout_tx = output_tx.clone();
+    let stdout_handle = tokio::spawn(async move {
+        if let Some(out) = stdout {
+            let mut reader = BufReader::new(out).lines();
+            while let Ok(Some(line)) = reader.next_line().await {
+                let _ = stdout_tx.send(OutputLine::info(line)).await;
+            }
+        }
+    });
+
+    let stderr_tx = output_tx.clone();
+    let stderr_handle = tokio::spawn(async move {
+        if let Some(err) = stderr {
+            let mut reader = BufReader::new(err).lines();
+            while let Ok(Some(line)) = reader.next_line().await {
+                let _ = stderr_tx.send(OutputLine::error(line)).await;
             }
         }
+    });
+
+    let status = child
+        .wait()
+        .await
+        .map_err(|e| format!("Failed to wait on host command: {}", e))?;
+
+    let _ = stdout_handle.await;
+    let _ = stderr_handle.await;
+
+    Ok(status.code().unwrap_or(-1))
+}
+
+/// Run a one-shot command inside a docker container. Returns the exit code.
+pub async fn run_docker_command(
+    container: &str,
+    command: &str,
+    workdir: &str,
+    output_tx: mpsc::Sender<OutputLine>,
+) -> Result<i32, String> {
+    let docker = DockerManager::from_default_socket()
+        .map_err(|e| format!("Failed to connect to docker: {}", e))?;
+
+    // Compose `cd <workdir> && <cmd>` so the user's workdir is honored.
+    let full_cmd = if workdir.is_empty() {
+        command.to_string()
+    } else {
+        format!("cd {} && {}", workdir, command)
+    };
+
+    let (out, exit_code) = docker
+        .exec_with_exit_code(container, &["sh", "-c", &full_cmd])
+        .await
+        .map_err(|e| format!("docker exec failed: {}", e))?;
+    for line in out.lines() {
+        let _ = output_tx.send(OutputLine::info(line.to_string())).await;
     }
+    Ok(exit_code as i32)
 }
 
 #[cfg(test)]
@@ -86,7 +244,12 @@ mod tests {
     #[tokio::test]
     async fn test_command_context_creation() {
         let (tx, _rx) = mpsc::channel(10);
-        let (ctx, _output_tx) = CommandContext::new(tx, Duration::from_secs(1));
+        let (ctx, _output_tx) = CommandContext::new(
+            tx,
+            JobId::default(),
+            CancellationToken::new(),
+            Duration::from_secs(1),
+        );
         assert_eq!(ctx.timeout, Duration::from_secs(1));
     }
 }
@@ -0,0 +1,346 @@
+//! Running configured commands without the TUI
+//!
+//! Shared by `k3dev run` and the HTTP API: resolve a command path and its
+//! placeholder values, then run the command on whatever target it names.
+
+use std::collections::HashMap;
+
+use serde_json::{json, Value};
+use tokio::sync::mpsc;
+
+use super::lua::lua_quote;
+use super::{run_docker_command, run_host_command, run_lua_command, run_script_command};
+use crate::config::{
+    find_command_by_path, get_exec_placeholders, runnable_command_paths,
+    substitute_exec_placeholders, substitute_input_placeholders, CommandEntry, CommandGroup,
+    ExecConfig, ExecutionTarget,
+};
+use crate::k8s::{K8sClient, PodExecutor};
+use crate::ui::components::OutputLine;
+
+/// A command with every placeholder filled in, ready to run
+#[derive(Debug, Clone)]
+pub struct PreparedCommand {
+    pub entry: CommandEntry,
+    pub exec: ExecConfig,
+    /// Given values the command has no placeholder for
+    pub ignored: Vec<String>,
+}
+
+/// Why a command path can't be run
+#[derive(Debug, Clone, PartialEq)]
+pub enum PrepareError {
+    NotFound,
+    /// The path names a submenu; these are its children
+    Submenu(Vec<String>),
+    /// Placeholders with neither a value nor a default
+    Missing(Vec<String>),
+}
+
+/// Resolve a command path and fill its placeholders from `values`, falling
+/// back to each input's default
+pub fn prepare_command(
+    groups: &[CommandGroup],
+    path: &str,
+    values: HashMap<String, String>,
+) -> Result<PreparedCommand, PrepareError> {
+    prepare(groups, path, values, false)
+}
+
+/// [`prepare_command`] for values sent by an API client. `cmd` runs through a
+/// shell (or Lua), and so does `workdir` in a container or pod, so the values
+/// are quoted there; a host `workdir` and the target's fields are used as
+/// they are.
+pub fn prepare_client_command(
+    groups: &[CommandGroup],
+    path: &str,
+    values: HashMap<String, String>,
+) -> Result<PreparedCommand, PrepareError> {
+    prepare(groups, path, values, true)
+}
+
+/// `s` as a single word for `sh`
+pub fn shell_quote(s: &str) -> String {
+    format!("'{}'", s.replace('\'', r"'\''"))
+}
+
+fn prepare(
+    groups: &[CommandGroup],
+    path: &str,
+    mut values: HashMap<String, String>,
+    quote: bool,
+) -> Result<PreparedCommand, PrepareError> {
+    let entry = find_command_by_path(groups, path).ok_or(PrepareError::NotFound)?;
+    let Some(exec) = &entry.exec else {
+        return Err(PrepareError::Submenu(
+            entry.commands.iter().map(|c| c.name.clone()).collect(),
+        ));
+    };
+
+    let placeholders = get_exec_placeholders(exec);
+    let mut ignored: Vec<String> = values
+        .keys()
+        .filter(|key| !placeholders.contains(key))
+        .cloned()
+        .collect();
+    ignored.sort();
+    values.retain(|key, _| placeholders.contains(key));
+    let mut missing = Vec::new();
+    for name in &placeholders {
+        if values.contains_key(name) {
+            continue;
+        }
+        match exec.input.get(name).and_then(|d| d.default_value()) {
+            Some(default) => {
+                values.insert(name.clone(), default);
+            }
+            None => missing.push(name.clone()),
+        }
+    }
+    if !missing.is_empty() {
+        return Err(PrepareError::Missing(missing));
+    }
+
+    // Every field is substituted once, from the config's text
+    let mut exec = exec.clone();
+    let shell_values = if quote {
+        let quote_value = match exec.target {
+            ExecutionTarget::Lua => lua_quote,
+            _ => shell_quote,
+        };
+        values
+            .iter()
+            .map(|(key, value)| (key.clone(), quote_value(value)))
+            .collect()
+    } else {
+        values.clone()
+    };
+    let cmd = substitute_input_placeholders(&exec.cmd, &shell_values);
+    // Containers and pods run `cd <workdir>`; the host sets it directly
+    let workdir = match exec.target {
+        ExecutionTarget::Docker { .. } | ExecutionTarget::Kubernetes { .. } => {
+            substitute_input_placeholders(&exec.workdir, &shell_values)
+        }
+        _ => substitute_input_placeholders(&exec.workdir, &values),
+    };
+    substitute_exec_placeholders(&mut exec, &values);
+    exec.cmd = cmd;
+    exec.workdir = workdir;
+    Ok(PreparedCommand {
+        entry: entry.clone(),
+        exec,
+        ignored,
+    })
+}
+
+/// Run a prepared exec on its target, streaming output. Returns the exit code.
+///
+/// Script and Kubernetes targets use `k8s`; the others ignore it.
+pub async fn run_exec(
+    exec: &ExecConfig,
+    k8s: Option<&K8sClient>,
+    output_tx: mpsc::Sender<OutputLine>,
+) -> Result<i32, String> {
+    match &exec.target {
+        ExecutionTarget::Host => run_host_command(&exec.cmd, &exec.workdir, output_tx).await,
+        ExecutionTarget::Script => {
+            run_script_command(&exec.cmd, &exec.workdir, k8s, output_tx).await
+        }
+        ExecutionTarget::Lua => run_lua_command(&exec.cmd, &exec.workdir, k8s, output_tx).await,
+        ExecutionTarget::Docker { container } => {
+            run_docker_command(container, &exec.cmd, &exec.workdir, output_tx).await
+        }
+        ExecutionTarget::Kubernetes { .. } => match k8s {
+            Some(client) => run_pod_command(client, exec, output_tx).await,
+            None => Err("Failed to connect to cluster".to_string()),
+        },
+    }
+}
+
+/// Run a command in the pod selected by a Kubernetes target. Returns the exit code.
+async fn run_pod_command(
+    k8s_client: &K8sClient,
+    exec: &ExecConfig,
+    output_tx: mpsc::Sender<OutputLine>,
+) -> Result<i32, String> {
+    let Some(target) = exec.target.as_kubernetes() else {
+        return Err("Not a Kubernetes target".to_string());
+    };
+    let executor = PodExecutor::new(k8s_client);
+    let pod = executor
+        .find_pod(
+            target.namespace,
+            Some(target.selector).filter(|s| !s.is_empty()),
+            Some(target.pod_name).filter(|s| !s.is_empty()),
+        )
+        .await
+        .map_err(|e| format!("Pod not found: {}", e))?;
+
+    let result = executor
+        .exec_simple(
+            &pod.namespace,
+            &pod.name,
+            Some(target.container).filter(|c| !c.is_empty()),
+            &in_workdir(exec),
+        )
+        .await
+        .map_err(|e| format!("Exec in {} failed: {}", pod.name, e))?;
+    for line in result.stdout.lines() {
+        let _ = output_tx.send(OutputLine::info(line)).await;
+    }
+    for line in result.stderr.lines() {
+        let _ = output_tx.send(OutputLine::error(line)).await;
+    }
+    Ok(result.exit_code)
+}
+
+/// Prefix a command with `cd <workdir>` when a workdir is set
+fn in_workdir(exec: &ExecConfig) -> String {
+    if exec.workdir.is_empty() {
+        exec.cmd.clone()
+    } else {
+        format!("cd {} && {}", exec.workdir, exec.cmd)
+    }
+}
+
+/// Short name of an execution target, as shown in command listings
+pub fn target_label(target: &ExecutionTarget) -> &'static str {
+    match target {
+        ExecutionTarget::Host => "host",
+        ExecutionTarget::Script => "script",
+        ExecutionTarget::Lua => "lua",
+        ExecutionTarget::Docker { .. } => "docker",
+        ExecutionTarget::Kubernetes { .. } => "kubernetes",
+    }
+}
+
+/// Runnable commands with their path, name, description, target and inputs
+pub fn command_catalog(groups: &[CommandGroup]) -> Vec<Value> {
+    runnable_command_paths(groups)
+        .into_iter()
+        .filter_map(|(path, entry)| {
+            let exec = entry.exec.as_ref()?;
+            Some(json!({
+                "path": path,
+                "name": entry.name,
+                "description": entry.description,
+                "target": target_label(&exec.target),
+                "inputs": get_exec_placeholders(exec),
+            }))
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn groups() -> Vec<CommandGroup> {
+        serde_yml::from_str(
+            r#"
+- name: App
+  commands:
+    - name: Deploy
+      exec:
+        target: { type: host }
+        cmd: deploy --version @version --env @env
+        input:
+          env:
+            type: select
+            prompt: "Env:"
+            options: [dev, prod]
+            default: dev
+    - name: Db
+      commands:
+        - name: Migrate
+          exec:
+            target: { type: host }
+            cmd: migrate
+    - name: Build
+      exec:
+        target: { type: host }
+        workdir: ~/src/@app
+        cmd: make
+    - name: Logs
+      exec:
+        target: { type: docker, container: web }
+        workdir: /srv/@app
+        cmd: tail log
+    - name: Greet
+      exec:
+        target: { type: lua }
+        cmd: print(@msg)
+"#,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn fills_defaults_and_reports_missing_values() {
+        let groups = groups();
+        assert_eq!(
+            prepare_command(&groups, "App/Deploy", HashMap::new()).unwrap_err(),
+            PrepareError::Missing(vec!["version".to_string()])
+        );
+
+        let values = HashMap::from([
+            ("version".to_string(), "42".to_string()),
+            ("extra".to_string(), "x".to_string()),
+        ]);
+        let prepared = prepare_command(&groups, "App/Deploy", values).unwrap();
+        assert_eq!(prepared.exec.cmd, "deploy --version 42 --env dev");
+        assert_eq!(prepared.ignored, vec!["extra".to_string()]);
+
+        assert_eq!(
+            prepare_command(&groups, "App/Db", HashMap::new()).unwrap_err(),
+            PrepareError::Submenu(vec!["Migrate".to_string()])
+        );
+        assert_eq!(
+            prepare_command(&groups, "App/Nope", HashMap::new()).unwrap_err(),
+            PrepareError::NotFound
+        );
+    }
+
+    #[test]
+    fn client_values_are_quoted_for_the_shell() {
+        let values = HashMap::from([("version".to_string(), "1; rm -rf ~ #'".to_string())]);
+        let prepared = prepare_client_command(&groups(), "App/Deploy", values).unwrap();
+        assert_eq!(
+            prepared.exec.cmd,
+            r"deploy --version '1; rm -rf ~ #'\''' --env 'dev'"
+        );
+
+        // Only a workdir that goes through `cd` is quoted
+        let values = || HashMap::from([("app".to_string(), "shop".to_string())]);
+        let prepared = prepare_client_command(&groups(), "App/Build", values()).unwrap();
+        assert_eq!(prepared.exec.workdir, "~/src/shop");
+        let prepared = prepare_client_command(&groups(), "App/Logs", values()).unwrap();
+        assert_eq!(prepared.exec.workdir, "/srv/'shop'");
+
+        let values = HashMap::from([("msg".to_string(), r#"hi") os.exit("#.to_string())]);
+        let prepared = prepare_client_command(&groups(), "App/Greet", values).unwrap();
+        assert_eq!(prepared.exec.cmd, r#"print("hi\") os.exit(")"#);
+    }
+
+    #[test]
+    fn client_values_are_not_substituted_into_each_other() {
+        let values = HashMap::from([
+            ("version".to_string(), "@env'".to_string()),
+            ("env".to_string(), "'; touch /tmp/pwned; '".to_string()),
+        ]);
+        let prepared = prepare_client_command(&groups(), "App/Deploy", values).unwrap();
+        assert_eq!(
+            prepared.exec.cmd,
+            r"deploy --version '@env'\''' --env ''\''; touch /tmp/pwned; '\'''"
+        );
+
+        // Values for placeholders the command doesn't have are dropped
+        let values = HashMap::from([
+            ("version".to_string(), "@zz".to_string()),
+            ("zz".to_string(), "'; touch /tmp/pwned; '".to_string()),
+        ]);
+        let prepared = prepare_client_command(&groups(), "App/Deploy", values).unwrap();
+        assert_eq!(prepared.exec.cmd, "deploy --version '@zz' --env 'dev'");
+        assert_eq!(prepared.ignored, vec!["zz".to_string()]);
+    }
+}
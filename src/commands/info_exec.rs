@@ -9,6 +9,8 @@ use once_cell::sync::Lazy;
 use regex::Regex;
 use std::time::Duration;
 
+use super::lua::capture_lua;
+use super::script::capture_script;
 use crate::cluster::DockerManager;
 use crate::config::{ExecConfig, ExecutionTarget};
 use crate::k8s::{K8sClient, PodExecutor};
@@ -36,6 +38,12 @@ async fn run(
 ) -> Result<String> {
     match &exec.target {
         ExecutionTarget::Host => run_host(&exec.workdir, &exec.cmd).await,
+        ExecutionTarget::Script => capture_script(&exec.cmd, &exec.workdir, k8s)
+            .await
+            .map(|(_, output)| output),
+        ExecutionTarget::Lua => capture_lua(&exec.cmd, &exec.workdir, k8s)
+            .await
+            .map(|(_, output)| output),
         ExecutionTarget::Docker { container } => {
             let docker = docker.ok_or_else(|| anyhow!("docker client unavailable"))?;
             run_docker(docker, container, &exec.workdir, &exec.cmd).await
@@ -0,0 +1,335 @@
+//! Lua target: sandboxed scripts run inside k3dev
+//!
+//! The script runs in an embedded Lua 5.4 with only the `string`, `table`,
+//! `math`, `utf8` and `coroutine` libraries, so it can't touch files or start
+//! processes. It reads cluster and Docker state through a `k3dev` table that
+//! makes the same read-only calls as the script API:
+//!
+//! ```lua
+//! for _, w in ipairs(k3dev.list{apiVersion = "apps/v1", kind = "Deployment"}) do
+//!   k3dev.emit(w.metadata.name, (w.status.readyReplicas or 0) > 0 and "success" or "warning")
+//! end
+//! ```
+//!
+//! `print` writes info lines. The chunk's return value is the exit code: an
+//! integer as it is, `false` as 1, anything else as 0. An error is printed
+//! and exits with 1.
+
+use std::path::Path;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+
+use anyhow::anyhow;
+use mlua::{
+    HookTriggers, Lua, LuaOptions, LuaSerdeExt, SerializeOptions, StdLib, Value as LuaValue,
+};
+use serde_json::{json, Value};
+use tokio::runtime::Handle;
+use tokio::sync::mpsc;
+
+use super::script::{ScriptApi, ScriptCall};
+use crate::k8s::K8sClient;
+use crate::ui::components::OutputLine;
+
+/// Most memory a script may allocate
+const MEMORY_LIMIT: usize = 64 * 1024 * 1024;
+
+/// Instructions between checks for a cancelled run
+const CANCEL_CHECK_INSTRUCTIONS: u32 = 10_000;
+
+/// Sets the flag when the run is dropped (cancelled or timed out), which
+/// stops the interpreter at its next check
+struct CancelOnDrop(Arc<AtomicBool>);
+
+impl Drop for CancelOnDrop {
+    fn drop(&mut self) {
+        self.0.store(true, Ordering::Relaxed);
+    }
+}
+
+/// `s` as a Lua string literal
+pub fn lua_quote(s: &str) -> String {
+    let mut quoted = String::with_capacity(s.len() + 2);
+    quoted.push('"');
+    for c in s.chars() {
+        match c {
+            '"' => quoted.push_str("\\\""),
+            '\\' => quoted.push_str("\\\\"),
+            '\n' => quoted.push_str("\\n"),
+            '\r' => quoted.push_str("\\r"),
+            c if c.is_control() => quoted.push_str(&format!("\\u{{{:x}}}", c as u32)),
+            c => quoted.push(c),
+        }
+    }
+    quoted.push('"');
+    quoted
+}
+
+/// The script's source: `cmd` itself, or the file it names when it is a
+/// single `.lua` path (relative to `workdir`)
+fn load_source(cmd: &str, workdir: &str) -> Result<(String, String), String> {
+    let trimmed = cmd.trim();
+    if trimmed.ends_with(".lua") && !trimmed.contains(char::is_whitespace) {
+        let path = Path::new(workdir).join(trimmed);
+        let source = std::fs::read_to_string(&path)
+            .map_err(|e| format!("Failed to read {}: {}", path.display(), e))?;
+        return Ok((format!("@{}", trimmed), source));
+    }
+    Ok(("=cmd".to_string(), cmd.to_string()))
+}
+
+/// Run a Lua script, streaming its output. Returns the exit code.
+pub async fn run_lua_command(
+    command: &str,
+    workdir: &str,
+    k8s: Option<&K8sClient>,
+    output_tx: mpsc::Sender<OutputLine>,
+) -> Result<i32, String> {
+    let (name, source) = load_source(command, workdir)?;
+    let api = ScriptApi::new(k8s);
+    let cancelled = Arc::new(AtomicBool::new(false));
+    let _cancel = CancelOnDrop(Arc::clone(&cancelled));
+    let handle = Handle::current();
+
+    tokio::task::spawn_blocking(move || {
+        let lua = sandbox(api, handle, cancelled, output_tx.clone())
+            .map_err(|e| format!("Failed to set up Lua: {}", e))?;
+        let result = lua
+            .load(&source)
+            .set_name(name)
+            .eval::<LuaValue>()
+            .map(|value| match value {
+                LuaValue::Integer(code) => code as i32,
+                LuaValue::Boolean(false) => 1,
+                _ => 0,
+            });
+        match result {
+            Ok(code) => Ok(code),
+            Err(e) => {
+                let _ = output_tx.blocking_send(OutputLine::error(e.to_string()));
+                Ok(1)
+            }
+        }
+    })
+    .await
+    .map_err(|e| format!("Lua script panicked: {}", e))?
+}
+
+/// Run a Lua script to completion and return its exit code and output
+pub async fn capture_lua(
+    command: &str,
+    workdir: &str,
+    k8s: Option<&K8sClient>,
+) -> anyhow::Result<(i32, String)> {
+    let (tx, mut rx) = mpsc::channel::<OutputLine>(100);
+    let collector = tokio::spawn(async move {
+        let mut lines = Vec::new();
+        while let Some(line) = rx.recv().await {
+            lines.push(line.content);
+        }
+        lines.join("\n")
+    });
+    let exit_code = run_lua_command(command, workdir, k8s, tx)
+        .await
+        .map_err(|e| anyhow!(e))?;
+    let output = collector.await.unwrap_or_default();
+    Ok((exit_code, output))
+}
+
+/// Answers the `k3dev` table's calls from the blocking thread the script
+/// runs on
+struct Caller {
+    api: ScriptApi,
+    handle: Handle,
+}
+
+impl Caller {
+    fn call<'lua>(&self, lua: &'lua Lua, request: Value) -> mlua::Result<LuaValue<'lua>> {
+        let call: ScriptCall = serde_json::from_value(request)
+            .map_err(|e| mlua::Error::runtime(format!("Bad call: {}", e)))?;
+        let result = self
+            .handle
+            .block_on(self.api.call(&call))
+            .map_err(|e| mlua::Error::runtime(format!("{:#}", e)))?;
+        // null (e.g. a missing object) becomes nil
+        lua.to_value_with(
+            &result,
+            SerializeOptions::new()
+                .serialize_none_to_null(false)
+                .serialize_unit_to_null(false),
+        )
+    }
+}
+
+/// A Lua state with the safe libraries, the `k3dev` table and `print`
+fn sandbox(
+    api: ScriptApi,
+    handle: Handle,
+    cancelled: Arc<AtomicBool>,
+    output_tx: mpsc::Sender<OutputLine>,
+) -> mlua::Result<Lua> {
+    let lua = Lua::new_with(
+        StdLib::STRING | StdLib::TABLE | StdLib::MATH | StdLib::UTF8 | StdLib::COROUTINE,
+        LuaOptions::default(),
+    )?;
+    lua.set_memory_limit(MEMORY_LIMIT)?;
+    let stop_tx = output_tx.clone();
+    lua.set_hook(
+        HookTriggers::new().every_nth_instruction(CANCEL_CHECK_INSTRUCTIONS),
+        move |_, _| {
+            if cancelled.load(Ordering::Relaxed) || stop_tx.is_closed() {
+                return Err(mlua::Error::runtime("cancelled"));
+            }
+            Ok(())
+        },
+    );
+
+    let globals = lua.globals();
+    // The base library can still read files
+    globals.set("dofile", LuaValue::Nil)?;
+    globals.set("loadfile", LuaValue::Nil)?;
+
+    let print_tx = output_tx.clone();
+    globals.set(
+        "print",
+        lua.create_function(move |lua, args: mlua::Variadic<LuaValue>| {
+            let tostring: mlua::Function = lua.globals().get("tostring")?;
+            let parts = args
+                .into_iter()
+                .map(|arg| tostring.call::<_, String>(arg))
+                .collect::<mlua::Result<Vec<_>>>()?;
+            let _ = print_tx.blocking_send(OutputLine::info(parts.join("\t")));
+            Ok(())
+        })?,
+    )?;
+
+    let k3dev = lua.create_table()?;
+    let emit_tx = output_tx;
+    k3dev.set(
+        "emit",
+        lua.create_function(move |_, (text, level): (String, Option<String>)| {
+            let line = match level.as_deref() {
+                Some("success") => OutputLine::success(text),
+                Some("warning") => OutputLine::warning(text),
+                Some("error") => OutputLine::error(text),
+                _ => OutputLine::info(text),
+            };
+            let _ = emit_tx.blocking_send(line);
+            Ok(())
+        })?,
+    )?;
+
+    let caller = Arc::new(Caller { api, handle });
+
+    for name in ["list", "get"] {
+        let caller = Arc::clone(&caller);
+        k3dev.set(
+            name,
+            lua.create_function(move |lua, args: mlua::Table| {
+                let mut request: Value = lua.from_value(LuaValue::Table(args))?;
+                let Some(object) = request.as_object_mut() else {
+                    return Err(mlua::Error::runtime("expected a table"));
+                };
+                object.insert("call".to_string(), json!(name));
+                caller.call(lua, request)
+            })?,
+        )?;
+    }
+    for name in ["containers", "images"] {
+        let caller = Arc::clone(&caller);
+        k3dev.set(
+            name,
+            lua.create_function(move |lua, prefix: Option<String>| {
+                caller.call(
+                    lua,
+                    json!({ "call": name, "prefix": prefix.unwrap_or_default() }),
+                )
+            })?,
+        )?;
+    }
+    globals.set("k3dev", k3dev)?;
+    drop(globals);
+    Ok(lua)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ui::components::OutputType;
+    use std::time::Duration;
+
+    async fn run(source: &str) -> (i32, Vec<OutputLine>) {
+        let (tx, mut rx) = mpsc::channel(100);
+        let code = run_lua_command(source, "", None, tx).await.unwrap();
+        let mut lines = Vec::new();
+        while let Some(line) = rx.recv().await {
+            lines.push(line);
+        }
+        (code, lines)
+    }
+
+    #[tokio::test]
+    async fn prints_emits_and_returns_an_exit_code() {
+        let (code, lines) = run(r#"
+            print("queue", 12)
+            k3dev.emit("backlog", "warning")
+            return 3
+        "#)
+        .await;
+        assert_eq!(code, 3);
+        assert_eq!(lines[0].content, "queue\t12");
+        assert_eq!(lines[1].content, "backlog");
+        assert_eq!(lines[1].output_type, OutputType::Warning);
+
+        assert_eq!(run("return false").await.0, 1);
+        assert_eq!(run("local x = 1").await.0, 0);
+    }
+
+    #[tokio::test]
+    async fn scripts_cannot_reach_the_host() {
+        let (code, _) = run(r#"
+            assert(io == nil and os == nil and require == nil and package == nil)
+            assert(dofile == nil and loadfile == nil and debug == nil)
+        "#)
+        .await;
+        assert_eq!(code, 0);
+
+        let (code, lines) = run("error('boom')").await;
+        assert_eq!(code, 1);
+        assert!(lines[0].content.contains("boom"));
+        assert_eq!(lines[0].output_type, OutputType::Error);
+    }
+
+    #[tokio::test]
+    async fn api_errors_can_be_caught() {
+        let (code, lines) = run(r#"
+            local ok, err = pcall(k3dev.list, { kind = "Pod" })
+            print(ok, err)
+            return ok and 0 or 2
+        "#)
+        .await;
+        assert_eq!(code, 2);
+        assert!(lines[0].content.contains("Cluster is not running"));
+    }
+
+    #[tokio::test]
+    async fn dropping_the_run_stops_the_script() {
+        let (tx, mut rx) = mpsc::channel(100);
+        let run = run_lua_command("while true do end", "", None, tx);
+        assert!(tokio::time::timeout(Duration::from_millis(100), run)
+            .await
+            .is_err());
+        // The interpreter drops its sender once it stops
+        let stopped = tokio::time::timeout(Duration::from_secs(5), async {
+            while rx.recv().await.is_some() {}
+        });
+        assert!(stopped.await.is_ok());
+    }
+
+    #[test]
+    fn quoted_values_stay_one_lua_string() {
+        assert_eq!(lua_quote("a\"b\\c\n"), r#""a\"b\\c\n""#);
+        assert_eq!(lua_quote("x\u{1}"), r#""x\u{1}""#);
+        assert_eq!(lua_quote("]] os.exit() --"), r#""]] os.exit() --""#);
+    }
+}
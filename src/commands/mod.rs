@@ -3,11 +3,21 @@
 //! This module provides typed command identifiers and command execution utilities.
 
 mod executor;
+pub mod headless;
 mod info_exec;
+pub mod lua;
+mod outcome;
+pub mod script;
 mod types;
 mod visibility;
 
-pub use executor::CommandContext;
+pub use executor::{run_docker_command, run_host_command, CommandContext};
+pub use headless::{
+    command_catalog, prepare_client_command, prepare_command, run_exec, shell_quote, PrepareError,
+};
 pub use info_exec::{capture_exec, strip_ansi, trim_output};
+pub use lua::run_lua_command;
+pub use outcome::{CommandOutcome, OutputClassifier};
+pub use script::run_script_command;
 pub use types::PaletteCommandId;
 pub use visibility::check_visible;
@@ -0,0 +1,194 @@
+//! Classify command output and exit codes against a command's success criteria
+//!
+//! Some tools exit 0 while printing errors, others use non-zero codes for
+//! expected results. The classifier recolors matching output lines and
+//! decides the final outcome from the exit code plus what was seen.
+
+use regex::Regex;
+use std::collections::HashMap;
+
+use crate::config::SuccessCriteria;
+use crate::ui::components::{OutputLine, OutputType};
+
+/// Final result of a custom command
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum CommandOutcome {
+    Success {
+        exit_code: i32,
+        warnings: usize,
+    },
+    Failed {
+        /// None when the command never produced an exit code (spawn error, timeout)
+        exit_code: Option<i32>,
+        reason: String,
+    },
+}
+
+impl CommandOutcome {
+    pub fn is_failure(&self) -> bool {
+        matches!(self, CommandOutcome::Failed { .. })
+    }
+
+    /// Exit code to report for the command as a whole: 0 on success, the
+    /// command's own code on failure (1 if it exited 0 or never ran)
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            CommandOutcome::Success { .. } => 0,
+            CommandOutcome::Failed { exit_code, .. } => match exit_code {
+                Some(code) if *code != 0 => *code,
+                _ => 1,
+            },
+        }
+    }
+
+    /// Environment passed to `on_command_failed` hooks
+    pub fn hook_env(&self, command: &str) -> HashMap<String, String> {
+        let mut env = HashMap::from([("K3DEV_COMMAND".to_string(), command.to_string())]);
+        if let CommandOutcome::Failed { exit_code, reason } = self {
+            env.insert(
+                "K3DEV_EXIT_CODE".to_string(),
+                exit_code.map(|c| c.to_string()).unwrap_or_default(),
+            );
+            env.insert("K3DEV_FAILURE".to_string(), reason.clone());
+        }
+        env
+    }
+
+    /// Closing status line for the output
+    pub fn summary_line(&self) -> OutputLine {
+        match self {
+            CommandOutcome::Success { warnings: 0, .. } => {
+                OutputLine::success("Command completed successfully")
+            }
+            CommandOutcome::Success { warnings, .. } => OutputLine::warning(format!(
+                "Command completed with {} warning{}",
+                warnings,
+                if *warnings == 1 { "" } else { "s" }
+            )),
+            CommandOutcome::Failed { reason, .. } => OutputLine::error(reason.clone()),
+        }
+    }
+}
+
+/// Applies success criteria to output lines as they stream in
+pub struct OutputClassifier {
+    exit_codes: Vec<i32>,
+    error_patterns: Vec<Regex>,
+    warning_patterns: Vec<Regex>,
+    first_error: Option<String>,
+    errors: usize,
+    warnings: usize,
+}
+
+impl OutputClassifier {
+    /// Build a classifier. Invalid patterns are skipped (the config validator
+    /// reports them at load time).
+    pub fn new(criteria: &SuccessCriteria) -> Self {
+        let compile = |patterns: &[String]| -> Vec<Regex> {
+            patterns.iter().filter_map(|p| Regex::new(p).ok()).collect()
+        };
+        Self {
+            exit_codes: criteria.exit_codes.clone(),
+            error_patterns: compile(&criteria.error_patterns),
+            warning_patterns: compile(&criteria.warning_patterns),
+            first_error: None,
+            errors: 0,
+            warnings: 0,
+        }
+    }
+
+    /// Recolor a line that matches an error or warning pattern and count it
+    pub fn classify(&mut self, mut line: OutputLine) -> OutputLine {
+        if self
+            .error_patterns
+            .iter()
+            .any(|r| r.is_match(&line.content))
+        {
+            self.errors += 1;
+            if self.first_error.is_none() {
+                self.first_error = Some(line.content.trim().to_string());
+            }
+            line.output_type = OutputType::Error;
+        } else if self
+            .warning_patterns
+            .iter()
+            .any(|r| r.is_match(&line.content))
+        {
+            self.warnings += 1;
+            line.output_type = OutputType::Warning;
+        }
+        line
+    }
+
+    /// Decide the outcome once the command has exited
+    pub fn outcome(&self, exit_code: i32) -> CommandOutcome {
+        if !self.exit_codes.contains(&exit_code) {
+            return CommandOutcome::Failed {
+                exit_code: Some(exit_code),
+                reason: format!("Command exited with code {}", exit_code),
+            };
+        }
+        if let Some(first) = &self.first_error {
+            return CommandOutcome::Failed {
+                exit_code: Some(exit_code),
+                reason: format!(
+                    "Command reported {} error line{} (first: {})",
+                    self.errors,
+                    if self.errors == 1 { "" } else { "s" },
+                    first
+                ),
+            };
+        }
+        CommandOutcome::Success {
+            exit_code,
+            warnings: self.warnings,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn criteria(exit_codes: Vec<i32>, errors: &[&str], warnings: &[&str]) -> SuccessCriteria {
+        SuccessCriteria {
+            exit_codes,
+            error_patterns: errors.iter().map(|s| s.to_string()).collect(),
+            warning_patterns: warnings.iter().map(|s| s.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn default_criteria_only_accept_zero() {
+        let classifier = OutputClassifier::new(&SuccessCriteria::default());
+        assert!(!classifier.outcome(0).is_failure());
+        assert_eq!(classifier.outcome(2).exit_code(), 2);
+    }
+
+    #[test]
+    fn error_lines_fail_a_zero_exit() {
+        let mut classifier = OutputClassifier::new(&criteria(vec![0], &["^ERROR"], &["WARN"]));
+        let line = classifier.classify(OutputLine::info("ERROR: migration failed"));
+        assert_eq!(line.output_type, OutputType::Error);
+        let line = classifier.classify(OutputLine::info("WARN: slow query"));
+        assert_eq!(line.output_type, OutputType::Warning);
+
+        let outcome = classifier.outcome(0);
+        assert!(outcome.is_failure());
+        assert_eq!(outcome.exit_code(), 1);
+    }
+
+    #[test]
+    fn allowed_exit_codes_and_warnings() {
+        let mut classifier = OutputClassifier::new(&criteria(vec![0, 3], &[], &["WARN"]));
+        classifier.classify(OutputLine::info("WARN: deprecated flag"));
+        assert_eq!(
+            classifier.outcome(3),
+            CommandOutcome::Success {
+                exit_code: 3,
+                warnings: 1
+            }
+        );
+        assert_eq!(classifier.outcome(3).exit_code(), 0);
+    }
+}
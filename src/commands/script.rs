@@ -0,0 +1,362 @@
+//! Script target: a host process that can call back into k3dev
+//!
+//! The script runs like a host command, but every stdout line that is a JSON
+//! object with a `call` field is a request; the reply is written to its stdin
+//! as one JSON line. Objects with an `emit` field become output lines with a
+//! level. Everything else is plain output, so any language with a JSON
+//! library (or a WASM runtime CLI) can be used for custom panels and commands.
+//! The script is an ordinary host process; sandboxed scripts use the Lua
+//! target (`commands::lua`) instead.
+//!
+//! ```text
+//! → {"id": 1, "call": "list", "apiVersion": "v1", "kind": "Pod", "namespace": "default"}
+//! ← {"id": 1, "result": [ ...pod objects... ]}
+//! → {"emit": "queue depth 12", "level": "warning"}
+//! ```
+
+use std::process::Stdio;
+
+use anyhow::{anyhow, Context, Result};
+use kube::api::{Api, ApiResource, DynamicObject, ListParams};
+use kube::core::GroupVersionKind;
+use kube::discovery::{pinned_kind, Scope};
+use kube::Client;
+use serde::Deserialize;
+use serde_json::{json, Value};
+use tokio::io::{AsyncBufReadExt, AsyncWriteExt, BufReader};
+use tokio::process::Command;
+use tokio::sync::mpsc;
+
+use crate::cluster::DockerManager;
+use crate::k8s::K8sClient;
+use crate::ui::components::OutputLine;
+
+/// Version of the request/reply protocol, exported as `K3DEV_SCRIPT_API`
+pub const SCRIPT_API_VERSION: &str = "1";
+
+/// A read a script can ask for
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(tag = "call", rename_all = "snake_case")]
+pub enum ScriptCall {
+    /// List objects of a kind, in one namespace or all of them
+    #[serde(rename_all = "camelCase")]
+    List {
+        #[serde(default = "default_api_version")]
+        api_version: String,
+        kind: String,
+        #[serde(default)]
+        namespace: Option<String>,
+        #[serde(default)]
+        label_selector: Option<String>,
+    },
+    /// Get one object; the result is null when it doesn't exist
+    #[serde(rename_all = "camelCase")]
+    Get {
+        #[serde(default = "default_api_version")]
+        api_version: String,
+        kind: String,
+        #[serde(default)]
+        namespace: Option<String>,
+        name: String,
+    },
+    /// Docker containers whose name starts with a prefix
+    Containers {
+        #[serde(default)]
+        prefix: String,
+    },
+    /// Local image tags starting with a prefix
+    Images {
+        #[serde(default)]
+        prefix: String,
+    },
+}
+
+fn default_api_version() -> String {
+    "v1".to_string()
+}
+
+/// One line of script output
+#[derive(Debug)]
+pub enum ScriptLine {
+    Call {
+        id: Value,
+        call: Result<ScriptCall, String>,
+    },
+    Emit(OutputLine),
+    Text(String),
+}
+
+impl ScriptLine {
+    pub fn parse(line: &str) -> Self {
+        let trimmed = line.trim_start();
+        if !trimmed.starts_with('{') {
+            return ScriptLine::Text(line.to_string());
+        }
+        let Ok(Value::Object(object)) = serde_json::from_str::<Value>(trimmed) else {
+            return ScriptLine::Text(line.to_string());
+        };
+        if let Some(text) = object.get("emit") {
+            let text = text
+                .as_str()
+                .map(str::to_string)
+                .unwrap_or_else(|| text.to_string());
+            return ScriptLine::Emit(match object.get("level").and_then(Value::as_str) {
+                Some("success") => OutputLine::success(text),
+                Some("warning") => OutputLine::warning(text),
+                Some("error") => OutputLine::error(text),
+                _ => OutputLine::info(text),
+            });
+        }
+        if object.contains_key("call") {
+            let id = object.get("id").cloned().unwrap_or(Value::Null);
+            let call = serde_json::from_value(Value::Object(object)).map_err(|e| e.to_string());
+            return ScriptLine::Call { id, call };
+        }
+        ScriptLine::Text(line.to_string())
+    }
+}
+
+/// Answers script calls from the cluster and the Docker daemon
+pub struct ScriptApi {
+    k8s: Option<Client>,
+    docker: Option<DockerManager>,
+}
+
+impl ScriptApi {
+    pub fn new(k8s: Option<&K8sClient>) -> Self {
+        Self {
+            k8s: k8s.map(|c| c.client().clone()),
+            docker: DockerManager::from_default_socket().ok(),
+        }
+    }
+
+    pub async fn call(&self, call: &ScriptCall) -> Result<Value> {
+        match call {
+            ScriptCall::List {
+                api_version,
+                kind,
+                namespace,
+                label_selector,
+            } => {
+                let api = self
+                    .dynamic_api(api_version, kind, namespace.as_deref(), true)
+                    .await?;
+                let mut params = ListParams::default();
+                if let Some(selector) = label_selector {
+                    params = params.labels(selector);
+                }
+                let list = api
+                    .list(&params)
+                    .await
+                    .with_context(|| format!("Failed to list {}", kind))?;
+                Ok(serde_json::to_value(list.items)?)
+            }
+            ScriptCall::Get {
+                api_version,
+                kind,
+                namespace,
+                name,
+            } => {
+                let api = self
+                    .dynamic_api(api_version, kind, namespace.as_deref(), false)
+                    .await?;
+                let object = api
+                    .get_opt(name)
+                    .await
+                    .with_context(|| format!("Failed to get {} {}", kind, name))?;
+                Ok(serde_json::to_value(object)?)
+            }
+            ScriptCall::Containers { prefix } => {
+                let docker = self.docker()?;
+                let mut containers = Vec::new();
+                for name in docker.list_containers_by_prefix(prefix).await? {
+                    let status = docker.container_status(&name).await;
+                    containers.push(json!({ "name": name, "status": status }));
+                }
+                Ok(Value::Array(containers))
+            }
+            ScriptCall::Images { prefix } => {
+                let mut images = self.docker()?.list_images_by_pattern(prefix).await?;
+                images.sort();
+                Ok(json!(images))
+            }
+        }
+    }
+
+    async fn dynamic_api(
+        &self,
+        api_version: &str,
+        kind: &str,
+        namespace: Option<&str>,
+        allow_all_namespaces: bool,
+    ) -> Result<Api<DynamicObject>> {
+        let client = self
+            .k8s
+            .clone()
+            .ok_or_else(|| anyhow!("Cluster is not running"))?;
+        let (group, version) = api_version.rsplit_once('/').unwrap_or(("", api_version));
+        let gvk = GroupVersionKind::gvk(group, version, kind);
+        let (resource, caps): (ApiResource, _) = pinned_kind(&client, &gvk)
+            .await
+            .with_context(|| format!("Unknown kind {} in {}", kind, api_version))?;
+        Ok(match (caps.scope, namespace) {
+            (Scope::Cluster, _) => Api::all_with(client, &resource),
+            (Scope::Namespaced, Some(ns)) => Api::namespaced_with(client, ns, &resource),
+            (Scope::Namespaced, None) if allow_all_namespaces => Api::all_with(client, &resource),
+            (Scope::Namespaced, None) => {
+                return Err(anyhow!("{} is namespaced; pass \"namespace\"", kind))
+            }
+        })
+    }
+
+    fn docker(&self) -> Result<&DockerManager> {
+        self.docker
+            .as_ref()
+            .ok_or_else(|| anyhow!("Docker is not reachable"))
+    }
+}
+
+/// Run a script, answering its calls and streaming its output. Returns the exit code.
+pub async fn run_script_command(
+    command: &str,
+    workdir: &str,
+    k8s: Option<&K8sClient>,
+    output_tx: mpsc::Sender<OutputLine>,
+) -> Result<i32, String> {
+    let mut cmd = Command::new("sh");
+    cmd.arg("-c").arg(command);
+    if !workdir.is_empty() {
+        cmd.current_dir(workdir);
+    }
+    cmd.env("K3DEV_SCRIPT_API", SCRIPT_API_VERSION);
+    cmd.stdin(Stdio::piped());
+    cmd.stdout(Stdio::piped());
+    cmd.stderr(Stdio::piped());
+    cmd.kill_on_drop(true);
+
+    let mut child = cmd
+        .spawn()
+        .map_err(|e| format!("Failed to spawn script: {}", e))?;
+    let mut stdin = child.stdin.take();
+    let stdout = child.stdout.take();
+    let stderr = child.stderr.take();
+
+    let stderr_tx = output_tx.clone();
+    let stderr_handle = tokio::spawn(async move {
+        if let Some(err) = stderr {
+            let mut reader = BufReader::new(err).lines();
+            while let Ok(Some(line)) = reader.next_line().await {
+                let _ = stderr_tx.send(OutputLine::error(line)).await;
+            }
+        }
+    });
+
+    let api = ScriptApi::new(k8s);
+    if let Some(out) = stdout {
+        let mut reader = BufReader::new(out).lines();
+        while let Ok(Some(line)) = reader.next_line().await {
+            match ScriptLine::parse(&line) {
+                ScriptLine::Text(text) => {
+                    let _ = output_tx.send(OutputLine::info(text)).await;
+                }
+                ScriptLine::Emit(line) => {
+                    let _ = output_tx.send(line).await;
+                }
+                ScriptLine::Call { id, call } => {
+                    let reply = match call {
+                        Ok(call) => match api.call(&call).await {
+                            Ok(result) => json!({ "id": id, "result": result }),
+                            Err(e) => json!({ "id": id, "error": format!("{:#}", e) }),
+                        },
+                        Err(e) => json!({ "id": id, "error": format!("Bad call: {}", e) }),
+                    };
+                    // A script that closed stdin doesn't want replies
+                    if let Some(input) = stdin.as_mut() {
+                        let reply = format!("{}\n", reply);
+                        if input.write_all(reply.as_bytes()).await.is_err() {
+                            stdin = None;
+                        }
+                    }
+                }
+            }
+        }
+    }
+    drop(stdin);
+
+    let status = child
+        .wait()
+        .await
+        .map_err(|e| format!("Failed to wait on script: {}", e))?;
+    let _ = stderr_handle.await;
+
+    Ok(status.code().unwrap_or(-1))
+}
+
+/// Run a script to completion and return its exit code and output
+pub async fn capture_script(
+    command: &str,
+    workdir: &str,
+    k8s: Option<&K8sClient>,
+) -> Result<(i32, String)> {
+    let (tx, mut rx) = mpsc::channel::<OutputLine>(100);
+    let collector = tokio::spawn(async move {
+        let mut lines = Vec::new();
+        while let Some(line) = rx.recv().await {
+            lines.push(line.content);
+        }
+        lines.join("\n")
+    });
+    let exit_code = run_script_command(command, workdir, k8s, tx)
+        .await
+        .map_err(|e| anyhow!(e))?;
+    let output = collector.await.unwrap_or_default();
+    Ok((exit_code, output))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ui::components::OutputType;
+
+    #[test]
+    fn parses_calls_emits_and_text() {
+        match ScriptLine::parse(
+            r#"{"id": 7, "call": "list", "kind": "Pod", "namespace": "web", "labelSelector": "app=api"}"#,
+        ) {
+            ScriptLine::Call { id, call } => {
+                assert_eq!(id, json!(7));
+                assert_eq!(
+                    call,
+                    Ok(ScriptCall::List {
+                        api_version: "v1".to_string(),
+                        kind: "Pod".to_string(),
+                        namespace: Some("web".to_string()),
+                        label_selector: Some("app=api".to_string()),
+                    })
+                );
+            }
+            other => panic!("expected a call, got {:?}", other),
+        }
+
+        match ScriptLine::parse(r#"{"emit": "queue depth 12", "level": "warning"}"#) {
+            ScriptLine::Emit(line) => {
+                assert_eq!(line.content, "queue depth 12");
+                assert_eq!(line.output_type, OutputType::Warning);
+            }
+            other => panic!("expected an emit, got {:?}", other),
+        }
+
+        assert!(matches!(
+            ScriptLine::parse(r#"{"id": 1, "call": "nope"}"#),
+            ScriptLine::Call { call: Err(_), .. }
+        ));
+        assert!(matches!(
+            ScriptLine::parse("{not json"),
+            ScriptLine::Text(_)
+        ));
+        assert!(matches!(
+            ScriptLine::parse(r#"{"level": "info"}"#),
+            ScriptLine::Text(_)
+        ));
+    }
+}
@@ -3,6 +3,7 @@
 //! This module defines typed enums for command identifiers, replacing magic strings
 //! with type-safe variants.
 
+use crate::cluster::{ChaosTool, ClusterComponent};
 use crate::ui::components::ClusterAction;
 
 /// Command palette command identifiers
@@ -20,16 +21,55 @@ pub enum PaletteCommandId {
     ClusterDeleteSnapshots,
     ClusterDiagnostics,
     ClusterPreflightCheck,
+    ClusterTakeControl,
+    ClusterSharePort,
+    ClusterStopSharing,
+    ClusterOpenTunnel,
+    ClusterCloseTunnels,
+    ClusterLogs,
+    ClusterEditResource,
+    ClusterServices,
+    ClusterNetworkPolicies,
+    ClusterDependencyMap,
+    ClusterRbac,
+    ClusterCreateNamespace,
+    ClusterDeleteNamespace,
+    ClusterInstallMesh,
+    ClusterUninstallMesh,
+    ClusterInstallPolicyEngine,
+    ClusterUninstallPolicyEngine,
+    ClusterPolicyViolations,
+    ClusterQuotas,
+    ClusterRuntime,
+    ClusterPvBrowser,
+    ClusterSnapshots,
+    ClusterDiffManifests,
+    ClusterK3sVersion,
+    ClusterBuildImage,
+    ClusterPullSecret,
+    /// Repeat one setup step without recreating the cluster
+    ClusterRedeploy(ClusterComponent),
+    /// Apply a bundle from `seeds:` by name
+    LoadSeed(String),
+    /// A tool from the Chaos group
+    Chaos(ChaosTool),
 
     // Application commands
     AppRefresh,
     AppUpdateHosts,
+    AppRestoreHosts,
+    AppEditConfig,
     AppHelp,
+    AppNotifications,
+    AppRefreshTasks,
+    AppHookRuns,
     AppQuit,
 
     // Navigation commands
     NavFocusMenu,
     NavFocusActions,
+    NavPodsByCpu,
+    NavPodsByMemory,
 
     // Custom commands from config (path like "Group Name/Command Name")
     Custom(String),
@@ -47,12 +87,61 @@ impl PaletteCommandId {
             Self::ClusterDeleteSnapshots => "cluster:delete-snapshots",
             Self::ClusterDiagnostics => "cluster:diagnostics",
             Self::ClusterPreflightCheck => "cluster:preflight-check",
+            Self::ClusterTakeControl => "cluster:take-control",
+            Self::ClusterSharePort => "cluster:share-port",
+            Self::ClusterStopSharing => "cluster:stop-sharing",
+            Self::ClusterOpenTunnel => "cluster:open-tunnel",
+            Self::ClusterCloseTunnels => "cluster:close-tunnels",
+            Self::ClusterLogs => "cluster:logs",
+            Self::ClusterEditResource => "cluster:edit-resource",
+            Self::ClusterServices => "cluster:services",
+            Self::ClusterNetworkPolicies => "cluster:network-policies",
+            Self::ClusterDependencyMap => "cluster:dependency-map",
+            Self::ClusterRbac => "cluster:rbac",
+            Self::ClusterCreateNamespace => "cluster:create-namespace",
+            Self::ClusterDeleteNamespace => "cluster:delete-namespace",
+            Self::ClusterInstallMesh => "cluster:install-mesh",
+            Self::ClusterUninstallMesh => "cluster:uninstall-mesh",
+            Self::ClusterInstallPolicyEngine => "cluster:install-policy-engine",
+            Self::ClusterUninstallPolicyEngine => "cluster:uninstall-policy-engine",
+            Self::ClusterPolicyViolations => "cluster:policy-violations",
+            Self::ClusterQuotas => "cluster:quotas",
+            Self::ClusterRuntime => "cluster:runtime",
+            Self::ClusterPvBrowser => "cluster:pv-data",
+            Self::ClusterSnapshots => "cluster:snapshots",
+            Self::ClusterDiffManifests => "cluster:diff-manifests",
+            Self::ClusterK3sVersion => "cluster:k3s-version",
+            Self::ClusterBuildImage => "cluster:build-image",
+            Self::ClusterPullSecret => "cluster:pull-secret",
+            Self::ClusterRedeploy(component) => match component {
+                ClusterComponent::Traefik => "cluster:redeploy-traefik",
+                ClusterComponent::LocalPath => "cluster:reconfigure-local-path",
+                ClusterComponent::Socat => "cluster:reinstall-socat",
+                ClusterComponent::Kubeconfig => "cluster:regenerate-kubeconfig",
+            },
+            Self::LoadSeed(_) => "seed:load",
+            Self::Chaos(tool) => match tool {
+                ChaosTool::Cordon => "chaos:cordon",
+                ChaosTool::Uncordon => "chaos:uncordon",
+                ChaosTool::MemoryPressure => "chaos:memory-pressure",
+                ChaosTool::ClearMemoryPressure => "chaos:clear-memory-pressure",
+                ChaosTool::Latency => "chaos:latency",
+                ChaosTool::ClearLatency => "chaos:clear-latency",
+                ChaosTool::PodKiller => "chaos:pod-killer",
+            },
             Self::AppRefresh => "app:refresh",
             Self::AppUpdateHosts => "app:update-hosts",
+            Self::AppRestoreHosts => "app:restore-hosts",
+            Self::AppEditConfig => "app:edit-config",
             Self::AppHelp => "app:help",
+            Self::AppNotifications => "app:notifications",
+            Self::AppRefreshTasks => "app:refresh-tasks",
+            Self::AppHookRuns => "app:hooks",
             Self::AppQuit => "app:quit",
             Self::NavFocusMenu => "nav:focus-menu",
             Self::NavFocusActions => "nav:focus-actions",
+            Self::NavPodsByCpu => "nav:pods-by-cpu",
+            Self::NavPodsByMemory => "nav:pods-by-memory",
             Self::Custom(path) => path.as_str(),
         }
     }
@@ -76,6 +165,7 @@ impl PaletteCommandId {
             Self::ClusterDeleteSnapshots => Some(ClusterAction::DeleteSnapshots),
             Self::ClusterDiagnostics => Some(ClusterAction::Diagnostics),
             Self::ClusterPreflightCheck => Some(ClusterAction::PreflightCheck),
+            Self::ClusterLogs => Some(ClusterAction::Logs),
             _ => None,
         }
     }
@@ -7,6 +7,8 @@
 use anyhow::Result;
 use std::time::Duration;
 
+use super::lua::capture_lua;
+use super::script::capture_script;
 use crate::cluster::DockerManager;
 use crate::config::{ExecConfig, ExecutionTarget, VisibleCheck};
 use crate::k8s::{K8sClient, PodExecutor};
@@ -64,6 +66,12 @@ async fn run_exec(
 ) -> Result<bool> {
     match &exec.target {
         ExecutionTarget::Host => run_host(&exec.workdir, &exec.cmd).await,
+        ExecutionTarget::Script => capture_script(&exec.cmd, &exec.workdir, k8s)
+            .await
+            .map(|(exit_code, _)| exit_code == 0),
+        ExecutionTarget::Lua => capture_lua(&exec.cmd, &exec.workdir, k8s)
+            .await
+            .map(|(exit_code, _)| exit_code == 0),
         ExecutionTarget::Docker { container } => {
             let Some(docker) = docker else {
                 return Ok(false);
@@ -0,0 +1,220 @@
+//! Shell completion scripts
+//!
+//! Subcommand names are baked into the script when it is generated. Command
+//! paths for `k3dev run` are fetched at completion time via
+//! `k3dev list-commands`, so they follow config edits without regenerating.
+
+use clap::ValueEnum;
+
+/// Shells with a completion script
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Shell {
+    Bash,
+    Zsh,
+    Fish,
+}
+
+/// A subcommand name with its one-line description
+pub struct Subcommand {
+    pub name: String,
+    pub about: String,
+}
+
+/// Render the completion script for a shell
+pub fn generate(shell: Shell, subcommands: &[Subcommand]) -> String {
+    match shell {
+        Shell::Bash => bash(subcommands),
+        Shell::Zsh => zsh(subcommands),
+        Shell::Fish => fish(subcommands),
+    }
+}
+
+fn shell_names() -> String {
+    Shell::value_variants()
+        .iter()
+        .filter_map(|s| s.to_possible_value())
+        .map(|v| v.get_name().to_string())
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn bash(subcommands: &[Subcommand]) -> String {
+    let names: Vec<&str> = subcommands.iter().map(|s| s.name.as_str()).collect();
+    format!(
+        r#"# bash completion for k3dev
+# Install: k3dev completions bash > ~/.local/share/bash-completion/completions/k3dev
+
+_k3dev() {{
+    local cur prev sub i
+    cur="${{COMP_WORDS[COMP_CWORD]}}"
+    prev="${{COMP_WORDS[COMP_CWORD-1]}}"
+    local -a cfg=()
+    sub=""
+    for ((i = 1; i < COMP_CWORD; i++)); do
+        case "${{COMP_WORDS[i]}}" in
+            -c|--config) cfg=(--config "${{COMP_WORDS[i+1]}}"); ((i++)) ;;
+            -*) ;;
+            *) [[ -z "$sub" ]] && sub="${{COMP_WORDS[i]}}" ;;
+        esac
+    done
+
+    case "$prev" in
+        -c|--config) COMPREPLY=($(compgen -f -- "$cur")); return ;;
+    esac
+
+    if [[ -z "$sub" ]]; then
+        COMPREPLY=($(compgen -W "{names} -c --config -h --help -V --version" -- "$cur"))
+        return
+    fi
+
+    case "$sub" in
+        run)
+            if [[ "$cur" == -* ]]; then
+                COMPREPLY=($(compgen -W "--set" -- "$cur"))
+                return
+            fi
+            # Command paths contain spaces: match unescaped, reply escaped
+            local want="${{cur//\\ / }}" path
+            want="${{want#[\"\']}}"
+            COMPREPLY=()
+            while IFS= read -r path; do
+                [[ "$path" == "$want"* ]] && COMPREPLY+=("$(printf '%q' "$path")")
+            done < <(k3dev "${{cfg[@]}}" list-commands 2>/dev/null)
+            ;;
+        completions) COMPREPLY=($(compgen -W "{shells}" -- "$cur")) ;;
+        keys) COMPREPLY=($(compgen -W "export" -- "$cur")) ;;
+        list-commands) COMPREPLY=($(compgen -W "--json" -- "$cur")) ;;
+    esac
+}}
+
+complete -F _k3dev k3dev
+"#,
+        names = names.join(" "),
+        shells = shell_names(),
+    )
+}
+
+fn zsh(subcommands: &[Subcommand]) -> String {
+    let entries: Vec<String> = subcommands
+        .iter()
+        .map(|s| {
+            let about = s.about.replace('\'', "'\\''").replace(':', "\\:");
+            format!("        '{}:{}'", s.name, about)
+        })
+        .collect();
+    format!(
+        r#"#compdef k3dev
+# Install: k3dev completions zsh > "${{fpath[1]}}/_k3dev"
+
+_k3dev_run_paths() {{
+    local -a paths
+    paths=("${{(@f)$(k3dev $_k3dev_cfg list-commands 2>/dev/null)}}")
+    compadd -a paths
+}}
+
+_k3dev() {{
+    local -a subcommands _k3dev_cfg
+    local i=${{words[(I)(-c|--config)]}}
+    (( i > 0 )) && _k3dev_cfg=(--config "${{words[i+1]}}")
+    subcommands=(
+{entries}
+    )
+
+    local state
+    _arguments -C \
+        '(-c --config)'{{-c,--config}}'[Path to configuration file]:config file:_files' \
+        '1: :->subcommand' \
+        '*:: :->args'
+
+    case $state in
+        subcommand) _describe 'command' subcommands ;;
+        args)
+            case $words[1] in
+                run) _arguments '1:command path:_k3dev_run_paths' '*--set[Placeholder value]:KEY=VALUE:' ;;
+                completions) _values 'shell' {shells} ;;
+                keys) _values 'subcommand' export ;;
+                list-commands) _arguments '--json[Print JSON]' ;;
+            esac
+            ;;
+    esac
+}}
+
+_k3dev "$@"
+"#,
+        entries = entries.join("\n"),
+        shells = shell_names(),
+    )
+}
+
+fn fish(subcommands: &[Subcommand]) -> String {
+    let mut out = String::from(
+        r#"# fish completion for k3dev
+# Install: k3dev completions fish > ~/.config/fish/completions/k3dev.fish
+
+function __k3dev_config
+    set -l tokens (commandline -opc)
+    set -l i (contains -i -- -c $tokens; or contains -i -- --config $tokens)
+    if test -n "$i"; and test $i -lt (count $tokens)
+        echo --config
+        echo $tokens[(math $i + 1)]
+    end
+end
+
+complete -c k3dev -f
+complete -c k3dev -s c -l config -r -F -d 'Path to configuration file'
+"#,
+    );
+    for s in subcommands {
+        out.push_str(&format!(
+            "complete -c k3dev -n __fish_use_subcommand -a {} -d '{}'\n",
+            s.name,
+            s.about.replace('\\', "\\\\").replace('\'', "\\'")
+        ));
+    }
+    out.push_str(&format!(
+        r#"complete -c k3dev -n '__fish_seen_subcommand_from run' -a '(k3dev (__k3dev_config) list-commands 2>/dev/null)'
+complete -c k3dev -n '__fish_seen_subcommand_from run' -l set -r -d 'Placeholder value (KEY=VALUE)'
+complete -c k3dev -n '__fish_seen_subcommand_from completions' -a '{}'
+complete -c k3dev -n '__fish_seen_subcommand_from keys' -a export
+complete -c k3dev -n '__fish_seen_subcommand_from list-commands' -l json -d 'Print JSON'
+"#,
+        shell_names()
+    ));
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn subcommands() -> Vec<Subcommand> {
+        vec![
+            Subcommand {
+                name: "run".to_string(),
+                about: "Run a configured command".to_string(),
+            },
+            Subcommand {
+                name: "docker".to_string(),
+                about: "Run docker against the cluster's daemon: passthrough".to_string(),
+            },
+        ]
+    }
+
+    #[test]
+    fn scripts_list_subcommands_and_fetch_paths() {
+        for shell in Shell::value_variants() {
+            let script = generate(*shell, &subcommands());
+            assert!(script.contains("docker"), "{:?}", shell);
+            assert!(script.contains("list-commands"), "{:?}", shell);
+        }
+    }
+
+    #[test]
+    fn descriptions_are_escaped() {
+        let zsh = generate(Shell::Zsh, &subcommands());
+        assert!(zsh.contains("'docker:Run docker against the cluster'\\''s daemon\\: passthrough'"));
+
+        let fish = generate(Shell::Fish, &subcommands());
+        assert!(fish.contains("-d 'Run docker against the cluster\\'s daemon: passthrough'"));
+    }
+}
@@ -0,0 +1,124 @@
+//! Per-branch clusters (`infrastructure.per_branch`)
+//!
+//! On a feature branch the cluster name gets a `-<branch>` suffix and the
+//! domain a `<branch>.` prefix, so each branch has its own container,
+//! network, volumes, snapshot and hostnames. `main` and `master` keep the
+//! plain names. The branch is read from the git checkout in the working
+//! directory.
+
+use std::process::Command;
+
+use super::InfrastructureConfig;
+
+/// Container label with the branch a cluster was created for
+pub const LABEL_BRANCH: &str = "k3dev.branch";
+
+/// Container label with the cluster name before the branch suffix
+pub const LABEL_BASE_CLUSTER: &str = "k3dev.base-cluster";
+
+/// Longest branch suffix; keeps container names and DNS labels short
+const MAX_SLUG_LEN: usize = 24;
+
+/// Branches that use the plain cluster name
+const MAIN_BRANCHES: [&str; 2] = ["main", "master"];
+
+/// The branch a cluster's names were derived from
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BranchCluster {
+    pub branch: String,
+    /// `cluster_name` as written in the config
+    pub base_name: String,
+}
+
+/// Lowercase, alphanumerics and single dashes only, e.g. "feature/JIRA-12_login" → "feature-jira-12-login"
+pub fn branch_slug(branch: &str) -> String {
+    let mut slug = String::new();
+    for c in branch.chars() {
+        if c.is_ascii_alphanumeric() {
+            slug.push(c.to_ascii_lowercase());
+        } else if !slug.is_empty() && !slug.ends_with('-') {
+            slug.push('-');
+        }
+    }
+    slug.truncate(MAX_SLUG_LEN);
+    slug.trim_end_matches('-').to_string()
+}
+
+/// Branch checked out in the working directory; None outside a repository
+/// or on a detached HEAD
+pub fn current_branch() -> Option<String> {
+    git(&["rev-parse", "--abbrev-ref", "HEAD"])
+        .map(|out| out.trim().to_string())
+        .filter(|branch| !branch.is_empty() && branch != "HEAD")
+}
+
+/// Local branch names of the repository in the working directory
+pub fn local_branches() -> Option<Vec<String>> {
+    git(&["for-each-ref", "--format=%(refname:short)", "refs/heads"])
+        .map(|out| out.lines().map(str::to_string).collect())
+}
+
+fn git(args: &[&str]) -> Option<String> {
+    let output = Command::new("git").args(args).output().ok()?;
+    output
+        .status
+        .success()
+        .then(|| String::from_utf8_lossy(&output.stdout).to_string())
+}
+
+/// Derive names from the current branch when `per_branch` is on
+pub(super) fn apply_current_branch(infra: &mut InfrastructureConfig) {
+    if !infra.per_branch {
+        return;
+    }
+    if let Some(branch) = current_branch() {
+        apply_branch(infra, &branch);
+    }
+}
+
+/// Suffix the cluster name and prefix the domain with the branch slug
+pub fn apply_branch(infra: &mut InfrastructureConfig, branch: &str) {
+    let slug = branch_slug(branch);
+    if slug.is_empty() || MAIN_BRANCHES.contains(&branch) {
+        return;
+    }
+    infra.branch = Some(BranchCluster {
+        branch: branch.to_string(),
+        base_name: infra.cluster_name.clone(),
+    });
+    infra.cluster_name = format!("{}-{}", infra.cluster_name, slug);
+    infra.domain = format!("{}.{}", slug, infra.domain);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn slugs_are_short_dns_safe_labels() {
+        assert_eq!(
+            branch_slug("feature/JIRA-12_login"),
+            "feature-jira-12-login"
+        );
+        assert_eq!(branch_slug("--x--"), "x");
+        assert_eq!(
+            branch_slug("a-very-long-branch-name-that-goes-on"),
+            "a-very-long-branch-name"
+        );
+    }
+
+    #[test]
+    fn feature_branches_get_their_own_names() {
+        let mut infra = InfrastructureConfig::default();
+        apply_branch(&mut infra, "feat/search");
+        assert_eq!(infra.cluster_name, "k3dev-feat-search");
+        assert_eq!(infra.container_name(), "k3dev-feat-search-server");
+        assert_eq!(infra.domain, "feat-search.local.k8s.dev");
+        assert_eq!(infra.branch.unwrap().base_name, "k3dev");
+
+        let mut infra = InfrastructureConfig::default();
+        apply_branch(&mut infra, "main");
+        assert_eq!(infra.cluster_name, "k3dev");
+        assert!(infra.branch.is_none());
+    }
+}
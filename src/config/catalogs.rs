@@ -0,0 +1,261 @@
+//! Team-shared command catalogs (`commands_source:`)
+//!
+//! A catalog is a YAML file with a `commands:` list in a git repository.
+//! Each source is cloned once into the cache directory and fetched again
+//! in the background on every start; the loader only reads the checkout,
+//! so startup never waits for the network and an offline start keeps the
+//! last catalog. Catalog groups are appended after the config's own and
+//! remember which source they came from.
+
+use std::path::{Path, PathBuf};
+
+use anyhow::{anyhow, bail, Context, Result};
+use serde::Deserialize;
+use tokio::process::Command;
+
+use super::types::{CommandGroup, CommandSource, Config};
+
+/// Catalog file read when a source names none
+const DEFAULT_CATALOG_FILE: &str = "k3dev-commands.yml";
+
+/// A `commands_source` entry with its defaults filled in
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Catalog {
+    /// Clone URL without the `git+` prefix
+    pub url: String,
+    /// Branch or tag; the remote's default branch when unset
+    pub git_ref: Option<String>,
+    /// Catalog file inside the repository
+    pub path: String,
+}
+
+impl Catalog {
+    pub fn from_source(source: &CommandSource) -> Result<Self> {
+        let (spec, git_ref, path) = match source {
+            CommandSource::Url(url) => {
+                let (url, git_ref) = match url.split_once('#') {
+                    Some((url, r)) if !r.is_empty() => (url, Some(r.to_string())),
+                    _ => (url.as_str(), None),
+                };
+                (url.to_string(), git_ref, None)
+            }
+            CommandSource::Detailed { url, git_ref, path } => {
+                (url.clone(), git_ref.clone(), path.clone())
+            }
+        };
+        let url = spec
+            .strip_prefix("git+")
+            .ok_or_else(|| anyhow!("{}: only git+ sources are supported", spec))?
+            .to_string();
+        if url.is_empty() {
+            bail!("{}: missing repository URL", spec);
+        }
+        Ok(Self {
+            url,
+            git_ref,
+            path: path.unwrap_or_else(|| DEFAULT_CATALOG_FILE.to_string()),
+        })
+    }
+
+    /// Short name shown next to the catalog's groups, e.g.
+    /// "github.com/acme/platform-commands"
+    pub fn label(&self) -> String {
+        let without_scheme = self
+            .url
+            .split_once("://")
+            .map_or(self.url.as_str(), |(_, rest)| rest);
+        let without_user = without_scheme
+            .split_once('@')
+            .map_or(without_scheme, |(_, rest)| rest);
+        without_user
+            .trim_end_matches('/')
+            .trim_end_matches(".git")
+            .replace(':', "/")
+    }
+
+    /// Checkout directory under the cache directory
+    pub fn checkout_dir(&self) -> Option<PathBuf> {
+        let mut name: String = self
+            .label()
+            .chars()
+            .map(|c| if c.is_ascii_alphanumeric() { c } else { '-' })
+            .collect();
+        if let Some(r) = &self.git_ref {
+            name.push('@');
+            name.extend(
+                r.chars()
+                    .map(|c| if c.is_ascii_alphanumeric() { c } else { '-' }),
+            );
+        }
+        dirs::cache_dir().map(|d| d.join("k3dev").join("catalogs").join(name))
+    }
+}
+
+#[derive(Deserialize)]
+struct CatalogFile {
+    #[serde(default)]
+    commands: Vec<CommandGroup>,
+}
+
+/// Groups of a catalog file, tagged with where they came from
+fn read_catalog(file: &Path, label: &str) -> Result<Vec<CommandGroup>> {
+    let content = std::fs::read_to_string(file)
+        .with_context(|| format!("Failed to read {}", file.display()))?;
+    let catalog: CatalogFile = serde_yml::from_str(&content)
+        .with_context(|| format!("Failed to parse {}", file.display()))?;
+    Ok(catalog
+        .commands
+        .into_iter()
+        .map(|mut group| {
+            group.source = Some(label.to_string());
+            group
+        })
+        .collect())
+}
+
+/// Append the checked-out catalogs to `config.commands`. Problems are kept
+/// in `config.catalog_errors` instead of failing the load; a catalog that
+/// was never fetched is skipped quietly until the first update.
+pub fn merge_catalogs(config: &mut Config) {
+    for source in config.commands_source.clone() {
+        let catalog = match Catalog::from_source(&source) {
+            Ok(c) => c,
+            Err(e) => {
+                config.catalog_errors.push(format!("{:#}", e));
+                continue;
+            }
+        };
+        let Some(dir) = catalog.checkout_dir() else {
+            continue;
+        };
+        if !dir.exists() {
+            continue;
+        }
+        match read_catalog(&dir.join(&catalog.path), &catalog.label()) {
+            Ok(groups) => config.commands.extend(groups),
+            Err(e) => config
+                .catalog_errors
+                .push(format!("{}: {:#}", catalog.label(), e)),
+        }
+    }
+}
+
+async fn git(args: &[&str], dir: Option<&Path>) -> Result<String> {
+    let mut cmd = Command::new("git");
+    if let Some(dir) = dir {
+        cmd.arg("-C").arg(dir);
+    }
+    let output = cmd
+        .args(args)
+        .env("GIT_TERMINAL_PROMPT", "0")
+        .output()
+        .await
+        .context("Failed to run git")?;
+    if !output.status.success() {
+        bail!(
+            "git {} failed: {}",
+            args.first().copied().unwrap_or_default(),
+            String::from_utf8_lossy(&output.stderr).trim()
+        );
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// Clone or fast-forward a catalog's checkout. Returns whether its commit
+/// changed.
+pub async fn update_catalog(catalog: &Catalog) -> Result<bool> {
+    let dir = catalog
+        .checkout_dir()
+        .ok_or_else(|| anyhow!("No cache directory"))?;
+    if !dir.join(".git").exists() {
+        if let Some(parent) = dir.parent() {
+            std::fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create {}", parent.display()))?;
+        }
+        let dir_str = dir.to_string_lossy();
+        let mut args = vec!["clone", "--depth", "1"];
+        if let Some(r) = &catalog.git_ref {
+            args.extend(["--branch", r.as_str()]);
+        }
+        args.extend([catalog.url.as_str(), dir_str.as_ref()]);
+        git(&args, None).await?;
+        return Ok(true);
+    }
+
+    let before = git(&["rev-parse", "HEAD"], Some(&dir)).await?;
+    let git_ref = catalog.git_ref.as_deref().unwrap_or("HEAD");
+    git(&["fetch", "--depth", "1", "origin", git_ref], Some(&dir)).await?;
+    // The checkout is k3dev's copy; local edits are discarded
+    git(&["reset", "--hard", "FETCH_HEAD"], Some(&dir)).await?;
+    let after = git(&["rev-parse", "HEAD"], Some(&dir)).await?;
+    Ok(before != after)
+}
+
+/// Update every catalog of the config: (label, changed or error) per source
+pub async fn update_catalogs(sources: &[CommandSource]) -> Vec<(String, Result<bool, String>)> {
+    let mut results = Vec::new();
+    for source in sources {
+        let result = match Catalog::from_source(source) {
+            Ok(catalog) => (
+                catalog.label(),
+                update_catalog(&catalog)
+                    .await
+                    .map_err(|e| format!("{:#}", e)),
+            ),
+            Err(e) => (source.to_string(), Err(format!("{:#}", e))),
+        };
+        results.push(result);
+    }
+    results
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn shorthand_and_detailed_sources() {
+        let short =
+            CommandSource::Url("git+https://github.com/acme/platform-commands.git#v2".to_string());
+        let catalog = Catalog::from_source(&short).unwrap();
+        assert_eq!(catalog.url, "https://github.com/acme/platform-commands.git");
+        assert_eq!(catalog.git_ref.as_deref(), Some("v2"));
+        assert_eq!(catalog.path, DEFAULT_CATALOG_FILE);
+        assert_eq!(catalog.label(), "github.com/acme/platform-commands");
+
+        let ssh = CommandSource::Detailed {
+            url: "git+git@gitlab.example.com:team/catalog.git".to_string(),
+            git_ref: None,
+            path: Some("k3dev/db.yml".to_string()),
+        };
+        let catalog = Catalog::from_source(&ssh).unwrap();
+        assert_eq!(catalog.label(), "gitlab.example.com/team/catalog");
+        assert_eq!(catalog.path, "k3dev/db.yml");
+
+        let plain = CommandSource::Url("https://example.com/c.git".to_string());
+        assert!(Catalog::from_source(&plain).is_err());
+    }
+
+    #[test]
+    fn catalog_groups_carry_their_source() {
+        let dir = std::env::temp_dir().join(format!("k3dev-catalog-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let file = dir.join("k3dev-commands.yml");
+        std::fs::write(
+            &file,
+            r#"
+commands:
+  - name: "Database"
+    commands:
+      - name: "Migrate"
+        exec: { target: { type: host }, cmd: "make migrate" }
+"#,
+        )
+        .unwrap();
+        let groups = read_catalog(&file, "github.com/acme/cmds").unwrap();
+        assert_eq!(groups.len(), 1);
+        assert_eq!(groups[0].source.as_deref(), Some("github.com/acme/cmds"));
+        assert!(read_catalog(&dir.join("missing.yml"), "x").is_err());
+        let _ = std::fs::remove_dir_all(&dir);
+    }
+}
@@ -0,0 +1,90 @@
+//! In-place edits of the config file
+//!
+//! Values are replaced line by line so the user's comments and layout
+//! survive; a round trip through serde would drop both.
+
+use std::fs;
+use std::path::Path;
+
+use anyhow::{anyhow, Context, Result};
+
+const SECTION: &str = "infrastructure:";
+
+/// `content` with `infrastructure.<key>` set to `value`, which must already
+/// be YAML (quoted if needed)
+pub fn set_infrastructure_value(content: &str, key: &str, value: &str) -> Result<String> {
+    let mut lines: Vec<String> = content.lines().map(str::to_string).collect();
+    let Some(section) = lines.iter().position(|l| l.starts_with(SECTION)) else {
+        if !lines.last().is_none_or(|l| l.is_empty()) {
+            lines.push(String::new());
+        }
+        lines.push(SECTION.to_string());
+        lines.push(format!("  {}: {}", key, value));
+        return Ok(lines.join("\n") + "\n");
+    };
+    let rest = lines[section][SECTION.len()..].trim();
+    if !rest.is_empty() && !rest.starts_with('#') {
+        return Err(anyhow!(
+            "infrastructure is written inline; set {} by hand",
+            key
+        ));
+    }
+
+    // The section's lines are indented; the first key sets the indentation
+    let block_end = lines[section + 1..]
+        .iter()
+        .position(|l| !l.is_empty() && !l.starts_with([' ', '\t']) && !l.starts_with('#'))
+        .map_or(lines.len(), |i| section + 1 + i);
+    let indent = lines[section + 1..block_end]
+        .iter()
+        .find(|l| !l.trim().is_empty() && !l.trim_start().starts_with('#'))
+        .map(|l| l[..l.len() - l.trim_start().len()].to_string())
+        .unwrap_or_else(|| "  ".to_string());
+    let prefix = format!("{}{}:", indent, key);
+    let new_line = format!("{}{}: {}", indent, key, value);
+    match lines[section + 1..block_end]
+        .iter()
+        .position(|l| l.starts_with(&prefix))
+    {
+        Some(i) => lines[section + 1 + i] = new_line,
+        None => lines.insert(section + 1, new_line),
+    }
+    Ok(lines.join("\n") + "\n")
+}
+
+/// Set `infrastructure.<key>` in the config file at `path`
+pub fn write_infrastructure_value(path: &Path, key: &str, value: &str) -> Result<()> {
+    let content =
+        fs::read_to_string(path).with_context(|| format!("Failed to read {}", path.display()))?;
+    let updated = set_infrastructure_value(&content, key, value)?;
+    fs::write(path, updated).with_context(|| format!("Failed to write {}", path.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn existing_value_is_replaced_and_comments_kept() {
+        let content = "# mine\ninfrastructure:\n  # pinned\n  k3s_version: \"v1.30.1-k3s1\"\n  domain: x.test\ncommands: []\n";
+        let updated = set_infrastructure_value(content, "k3s_version", "\"v1.35.2-k3s1\"").unwrap();
+        assert_eq!(
+            updated,
+            "# mine\ninfrastructure:\n  # pinned\n  k3s_version: \"v1.35.2-k3s1\"\n  domain: x.test\ncommands: []\n"
+        );
+    }
+
+    #[test]
+    fn missing_key_and_section_are_added() {
+        let content = "infrastructure:\n    domain: x.test\n";
+        assert_eq!(
+            set_infrastructure_value(content, "k3s_version", "v1").unwrap(),
+            "infrastructure:\n    k3s_version: v1\n    domain: x.test\n"
+        );
+        assert_eq!(
+            set_infrastructure_value("commands: []\n", "k3s_version", "v1").unwrap(),
+            "commands: []\n\ninfrastructure:\n  k3s_version: v1\n"
+        );
+        assert!(set_infrastructure_value("infrastructure: {}\n", "k3s_version", "v1").is_err());
+    }
+}
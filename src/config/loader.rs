@@ -45,12 +45,33 @@ impl ConfigLoader {
         let mut config: Config = serde_yml::from_str(&content)
             .with_context(|| format!("Failed to parse config file: {}", path.display()))?;
 
+        super::catalogs::merge_catalogs(&mut config);
+        super::tags::apply_enabled_tags(&mut config);
+        super::safe::apply_safe_mode(&mut config);
         self.resolve_placeholders(&mut config);
+        apply_default_namespace(&mut config);
+        super::branch::apply_current_branch(&mut config.infrastructure);
         self.validate(&config)?;
 
         Ok((config, path))
     }
 
+    /// Whether a config file exists at the explicit path or any standard location
+    pub fn config_file_exists(&self) -> bool {
+        self.find_config_file().is_ok()
+    }
+
+    /// Where a newly created config should be written: the explicit path if
+    /// one was given, otherwise the user config directory.
+    pub fn setup_target_path(&self) -> PathBuf {
+        if let Some(path) = &self.config_path {
+            return expand_home(path).unwrap_or_else(|_| path.clone());
+        }
+        dirs::config_dir()
+            .map(|d| d.join("k3dev").join("config.yml"))
+            .unwrap_or_else(|| PathBuf::from("./k3dev.yml"))
+    }
+
     /// Search for configuration file in standard locations
     fn find_config_file(&self) -> Result<PathBuf> {
         // If explicit path provided, use it
@@ -107,6 +128,15 @@ impl ConfigLoader {
                 self.resolve_visible(v, &placeholders);
             }
         }
+
+        for test in &mut config.smoke_tests {
+            test.url = self.replace_placeholders(&test.url, &placeholders);
+            if let Some(exec) = &mut test.exec {
+                self.resolve_target(&mut exec.target, &placeholders);
+                exec.workdir = self.replace_placeholders(&exec.workdir, &placeholders);
+                exec.cmd = self.replace_placeholders(&exec.cmd, &placeholders);
+            }
+        }
     }
 
     fn resolve_visible(&self, v: &mut Visible, placeholders: &HashMap<String, String>) {
@@ -166,7 +196,7 @@ impl ConfigLoader {
 
     fn resolve_target(&self, target: &mut ExecutionTarget, placeholders: &HashMap<String, String>) {
         match target {
-            ExecutionTarget::Host => {}
+            ExecutionTarget::Host | ExecutionTarget::Script | ExecutionTarget::Lua => {}
             ExecutionTarget::Docker { container } => {
                 *container = self.replace_placeholders(container, placeholders);
             }
@@ -230,7 +260,7 @@ impl ConfigLoader {
             }
 
             match &exec.target {
-                ExecutionTarget::Host => {}
+                ExecutionTarget::Host | ExecutionTarget::Script | ExecutionTarget::Lua => {}
                 ExecutionTarget::Docker { container } => {
                     if container.is_empty() && !has_input_placeholders(container) {
                         return Err(anyhow!(
@@ -313,7 +343,7 @@ pub fn get_exec_placeholders(exec: &ExecConfig) -> Vec<String> {
 
     let mut fields: Vec<&str> = vec![&exec.workdir, &exec.cmd];
     match &exec.target {
-        ExecutionTarget::Host => {}
+        ExecutionTarget::Host | ExecutionTarget::Script | ExecutionTarget::Lua => {}
         ExecutionTarget::Docker { container } => {
             fields.push(container);
         }
@@ -341,3 +371,235 @@ pub fn get_exec_placeholders(exec: &ExecConfig) -> Vec<String> {
 
     all_placeholders
 }
+
+/// Replace `@placeholder` occurrences in one pass, so `@` in a value is never
+/// read as another placeholder. Names without a value are left as they are.
+pub fn substitute_input_placeholders(s: &str, values: &HashMap<String, String>) -> String {
+    PLACEHOLDER_EXTRACT_REGEX
+        .replace_all(s, |caps: &regex::Captures| match values.get(&caps[1]) {
+            Some(value) => value.clone(),
+            None => caps[0].to_string(),
+        })
+        .into_owned()
+}
+
+/// Replace `@placeholder` occurrences in every field of an ExecConfig
+pub fn substitute_exec_placeholders(exec: &mut ExecConfig, values: &HashMap<String, String>) {
+    let subst = |s: &mut String| *s = substitute_input_placeholders(s, values);
+    match &mut exec.target {
+        ExecutionTarget::Host | ExecutionTarget::Script | ExecutionTarget::Lua => {}
+        ExecutionTarget::Docker { container } => subst(container),
+        ExecutionTarget::Kubernetes {
+            namespace,
+            selector,
+            pod_name,
+            container,
+        } => {
+            subst(namespace);
+            subst(selector);
+            subst(pod_name);
+            subst(container);
+        }
+    }
+    subst(&mut exec.workdir);
+    subst(&mut exec.cmd);
+}
+
+/// Point Kubernetes targets and pod visibility checks that don't name a
+/// namespace at `cluster.default_namespace`
+fn apply_default_namespace(config: &mut Config) {
+    fn fill(namespace: &mut String, default: &str) {
+        if namespace.is_empty() {
+            *namespace = default.to_string();
+        }
+    }
+    fn fill_exec(exec: &mut ExecConfig, default: &str) {
+        if let ExecutionTarget::Kubernetes { namespace, .. } = &mut exec.target {
+            fill(namespace, default);
+        }
+    }
+    fn fill_visible(visible: &mut Option<Visible>, default: &str) {
+        match visible.as_mut().map(|v| &mut v.check) {
+            Some(VisibleCheck::Pod { namespace, .. }) => fill(namespace, default),
+            Some(VisibleCheck::Exec(exec)) => fill_exec(exec, default),
+            _ => {}
+        }
+    }
+    fn fill_entry(entry: &mut CommandEntry, default: &str) {
+        if let Some(exec) = &mut entry.exec {
+            fill_exec(exec, default);
+        }
+        fill_visible(&mut entry.visible, default);
+        for nested in &mut entry.commands {
+            fill_entry(nested, default);
+        }
+    }
+
+    let default = config.cluster.default_namespace.clone();
+    if default.is_empty() {
+        return;
+    }
+    for entry in config.commands.iter_mut().flat_map(|g| &mut g.commands) {
+        fill_entry(entry, &default);
+    }
+    for block in &mut config.info_blocks {
+        fill_exec(&mut block.exec, &default);
+        fill_visible(&mut block.visible, &default);
+    }
+    for test in &mut config.smoke_tests {
+        if let Some(exec) = &mut test.exec {
+            fill_exec(exec, &default);
+        }
+    }
+}
+
+/// Find a command by its path (e.g., "Group Name/Command Name" or "Group/Subgroup/Command").
+/// Names are matched case-insensitively.
+pub fn find_command_by_path<'a>(
+    groups: &'a [CommandGroup],
+    path: &str,
+) -> Option<&'a CommandEntry> {
+    let mut parts = path.split('/').map(str::trim);
+
+    let group_name = parts.next()?;
+    let group = groups
+        .iter()
+        .find(|g| g.name.eq_ignore_ascii_case(group_name))?;
+
+    let mut commands = &group.commands;
+    let mut found = None;
+    for part in parts {
+        let entry = commands
+            .iter()
+            .find(|c| c.name.eq_ignore_ascii_case(part))?;
+        commands = &entry.commands;
+        found = Some(entry);
+    }
+    found
+}
+
+/// Every runnable command with its path ("Group/Command", "Group/Sub/Command"),
+/// in menu order. Submenus without `exec` are skipped.
+pub fn runnable_command_paths(groups: &[CommandGroup]) -> Vec<(String, &CommandEntry)> {
+    fn walk<'a>(
+        prefix: &str,
+        entries: &'a [CommandEntry],
+        out: &mut Vec<(String, &'a CommandEntry)>,
+    ) {
+        for entry in entries {
+            let path = format!("{}/{}", prefix, entry.name);
+            if entry.exec.is_some() {
+                out.push((path.clone(), entry));
+            }
+            walk(&path, &entry.commands, out);
+        }
+    }
+
+    let mut out = Vec::new();
+    for group in groups {
+        walk(&group.name, &group.commands, &mut out);
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn groups() -> Vec<CommandGroup> {
+        serde_yml::from_str(
+            r#"
+- name: App
+  commands:
+    - name: Tools
+      commands:
+        - name: Migrate
+          exec:
+            target: { namespace: "@ns", selector: "app=web" }
+            cmd: "migrate --to @version"
+"#,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn finds_nested_command_case_insensitively() {
+        let groups = groups();
+        let cmd = find_command_by_path(&groups, "app/tools/MIGRATE").unwrap();
+        assert_eq!(cmd.name, "Migrate");
+        assert!(find_command_by_path(&groups, "App").is_none());
+        assert!(find_command_by_path(&groups, "App/Missing").is_none());
+    }
+
+    #[test]
+    fn lists_only_runnable_paths() {
+        let groups = groups();
+        let paths: Vec<String> = runnable_command_paths(&groups)
+            .into_iter()
+            .map(|(path, _)| path)
+            .collect();
+        assert_eq!(paths, vec!["App/Tools/Migrate"]);
+    }
+
+    #[test]
+    fn substitutes_placeholders_in_target_and_cmd() {
+        let groups = groups();
+        let mut exec = find_command_by_path(&groups, "App/Tools/Migrate")
+            .and_then(|c| c.exec.clone())
+            .unwrap();
+        let values = HashMap::from([
+            ("ns".to_string(), "staging".to_string()),
+            ("version".to_string(), "42".to_string()),
+        ]);
+        substitute_exec_placeholders(&mut exec, &values);
+
+        assert_eq!(exec.cmd, "migrate --to 42");
+        assert_eq!(exec.target.as_kubernetes().unwrap().namespace, "staging");
+        assert!(get_exec_placeholders(&exec).is_empty());
+    }
+
+    #[test]
+    fn substitutes_in_a_single_pass() {
+        let values = HashMap::from([
+            ("a".to_string(), "@b".to_string()),
+            ("b".to_string(), "x".to_string()),
+            ("ns".to_string(), "staging".to_string()),
+        ]);
+        assert_eq!(
+            substitute_input_placeholders("@a @b @ns_name @missing", &values),
+            "@b x @ns_name @missing"
+        );
+    }
+
+    #[test]
+    fn fills_missing_namespaces_with_default() {
+        let mut config: Config = serde_yml::from_str(
+            r#"
+cluster:
+  default_namespace: apps
+commands:
+  - name: App
+    commands:
+      - name: Shell
+        exec:
+          target: { selector: "app=web" }
+          cmd: "sh"
+      - name: Logs
+        exec:
+          target: { namespace: monitoring, selector: "app=grafana" }
+          cmd: "cat /var/log/grafana.log"
+"#,
+        )
+        .unwrap();
+        apply_default_namespace(&mut config);
+
+        let namespace = |path| {
+            find_command_by_path(&config.commands, path)
+                .and_then(|c| c.exec.as_ref())
+                .and_then(|e| e.target.as_kubernetes())
+                .map(|k| k.namespace.to_string())
+        };
+        assert_eq!(namespace("App/Shell").as_deref(), Some("apps"));
+        assert_eq!(namespace("App/Logs").as_deref(), Some("monitoring"));
+    }
+}
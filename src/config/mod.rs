@@ -1,13 +1,34 @@
+pub mod branch;
+mod catalogs;
+mod edit;
 mod loader;
+mod safe;
+mod starter;
+mod tags;
+mod templates;
 mod timeouts;
 mod types;
 mod validator;
 
-pub use loader::{expand_home, get_exec_placeholders, ConfigLoader};
-pub use timeouts::{RefreshConfig, RefreshScheduler, RefreshTask};
+pub use catalogs::update_catalogs;
+pub use edit::write_infrastructure_value;
+pub use loader::{
+    expand_home, find_command_by_path, get_exec_placeholders, runnable_command_paths,
+    substitute_exec_placeholders, substitute_input_placeholders, ConfigLoader,
+};
+pub use safe::{safe_mode, SAFE_ENV};
+pub use starter::StarterConfig;
+pub use tags::TAGS_ENV;
+pub use templates::Template;
+pub use timeouts::{RefreshConfig, RefreshScheduler, RefreshTask, TaskStats};
 pub use types::{
-    CommandEntry, CommandGroup, Config, ExecConfig, ExecutionTarget, HookCommand, HookEvent,
-    HooksConfig, InfoBlock, InfrastructureConfig, InputDefinition, InputSpec, KeybindingsConfig,
-    LoggingConfig, SpeedupConfig, UiConfig, VisibleCheck,
+    BrowserConfig, BrowserLaunch, BuildConfig, CommandColor, CommandEntry, CommandGroup,
+    Concurrency, Config, ExecConfig, ExecutionTarget, FileChangeHook, HealthProbe, HookCommand,
+    HookEvent, HookSchedule, HooksConfig, InfoBlock, InfrastructureConfig, IngressHealthConfig,
+    InputDefinition, InputSpec, KeybindingsConfig, LayoutPreset, LoggingConfig, NotificationEvent,
+    OutputOverride, PaneTarget, PodKillerConfig, PolicyEngine, PullSecretConfig, ReadinessGate,
+    SeedConfig, ServiceMesh, ShareConfig, SmokeTest, SpeedupConfig, StartConfig,
+    StorageClassConfig, StorageConfig, SuccessCriteria, ThresholdLevel, Thresholds,
+    TranscriptConfig, TranscriptFormat, TunnelProvider, UiConfig, VisibleCheck, WatchdogConfig,
 };
 pub use validator::ConfigValidator;
@@ -0,0 +1,23 @@
+//! Safe mode (`k3dev --safe`)
+//!
+//! Starts k3dev with nothing firing on its own, so a broken hook or a
+//! misbehaving check can be looked at without it running straight away.
+//! Every config load drops the `hooks:`; the TUI also stops its background
+//! refreshes and its automatic `/etc/hosts` checks.
+
+use super::types::{Config, HooksConfig};
+
+/// Environment variable that turns safe mode on, set by `--safe`
+pub const SAFE_ENV: &str = "K3DEV_SAFE";
+
+/// Whether `K3DEV_SAFE` is set to something other than `0`
+pub fn safe_mode() -> bool {
+    std::env::var(SAFE_ENV).is_ok_and(|v| !v.is_empty() && v != "0")
+}
+
+/// Drop the hooks in safe mode
+pub(super) fn apply_safe_mode(config: &mut Config) {
+    if safe_mode() {
+        config.hooks = HooksConfig::default();
+    }
+}
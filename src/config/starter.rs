@@ -0,0 +1,213 @@
+//! Starter config generation for first-run setup
+
+use anyhow::{anyhow, Context, Result};
+use std::fs;
+use std::path::Path;
+
+use super::Template;
+
+/// Answers collected by the setup wizard
+#[derive(Debug, Clone, PartialEq)]
+pub struct StarterConfig {
+    pub cluster_name: String,
+    pub domain: String,
+    pub k3s_version: String,
+    pub api_port: u16,
+    pub http_port: u16,
+    pub https_port: u16,
+    pub use_snapshot: bool,
+    /// Services and commands past the basics
+    pub template: Template,
+}
+
+impl Default for StarterConfig {
+    fn default() -> Self {
+        let infra = super::InfrastructureConfig::default();
+        Self {
+            cluster_name: infra.cluster_name,
+            domain: infra.domain,
+            k3s_version: infra.k3s_version,
+            api_port: infra.api_port,
+            http_port: infra.http_port,
+            https_port: infra.https_port,
+            use_snapshot: infra.speedup.use_snapshot,
+            template: Template::default(),
+        }
+    }
+}
+
+impl StarterConfig {
+    /// Check the answers before anything is written
+    pub fn validate(&self) -> Result<()> {
+        validate_cluster_name(&self.cluster_name)?;
+        if self.domain.trim().is_empty() {
+            return Err(anyhow!("Domain cannot be empty"));
+        }
+        if self.domain.contains(char::is_whitespace) {
+            return Err(anyhow!("Domain cannot contain spaces"));
+        }
+        if self.k3s_version.trim().is_empty() {
+            return Err(anyhow!("k3s version cannot be empty"));
+        }
+        if self.k3s_version.contains(char::is_whitespace) {
+            return Err(anyhow!("k3s version cannot contain spaces"));
+        }
+        let ports = [
+            ("API", self.api_port),
+            ("HTTP", self.http_port),
+            ("HTTPS", self.https_port),
+        ];
+        for (i, (name, port)) in ports.iter().enumerate() {
+            if *port == 0 {
+                return Err(anyhow!("{} port cannot be 0", name));
+            }
+            if let Some((other, _)) = ports[..i].iter().find(|(_, p)| p == port) {
+                return Err(anyhow!("{} and {} ports are both {}", other, name, port));
+            }
+        }
+        Ok(())
+    }
+
+    /// Render a commented k3dev.yml with the template's sections
+    pub fn render(&self) -> String {
+        self.render_with(
+            &format!("template: {}", self.template.name()),
+            &self.template.sections(&self.domain),
+        )
+    }
+
+    /// Render a commented k3dev.yml with the given `hooks:`/`commands:`
+    /// sections; `origin` says where they came from
+    pub fn render_with(&self, origin: &str, sections: &str) -> String {
+        format!(
+            r#"# k3dev configuration (generated by k3dev, {origin})
+# Full reference: https://github.com/daylioti/k3dev/blob/main/docs/CONFIGURATION.md
+
+# =============================================================================
+# Infrastructure Settings
+# =============================================================================
+infrastructure:
+  # Cluster name - used to derive container and network names
+  cluster_name: "{cluster_name}"
+
+  # Domain suffix for ingress hosts (e.g. myapp.{domain})
+  domain: "{domain}"
+
+  # rancher/k3s image tag (see https://hub.docker.com/r/rancher/k3s/tags)
+  k3s_version: "{k3s_version}"
+
+  # Kubernetes API port
+  api_port: {api_port}
+
+  # HTTP/HTTPS ports for ingress traffic
+  http_port: {http_port}
+  https_port: {https_port}
+
+  speedup:
+    # Snapshot-based startup: first start creates a snapshot, later starts take seconds
+    use_snapshot: {use_snapshot}
+
+{sections}"#,
+            origin = origin,
+            cluster_name = self.cluster_name,
+            domain = self.domain,
+            k3s_version = self.k3s_version,
+            api_port = self.api_port,
+            http_port = self.http_port,
+            https_port = self.https_port,
+            use_snapshot = self.use_snapshot,
+            sections = sections,
+        )
+    }
+
+    /// Write the rendered config, creating parent directories. Refuses to
+    /// overwrite an existing file.
+    pub fn write(&self, path: &Path) -> Result<()> {
+        write_new(path, &self.render())
+    }
+
+    /// Like [`write`](Self::write), with [`render_with`](Self::render_with)
+    pub fn write_with(&self, path: &Path, origin: &str, sections: &str) -> Result<()> {
+        write_new(path, &self.render_with(origin, sections))
+    }
+}
+
+/// Write a new file, creating parent directories. Refuses to overwrite.
+fn write_new(path: &Path, content: &str) -> Result<()> {
+    if path.exists() {
+        return Err(anyhow!("{} already exists", path.display()));
+    }
+    if let Some(parent) = path.parent() {
+        if !parent.as_os_str().is_empty() {
+            fs::create_dir_all(parent)
+                .with_context(|| format!("Failed to create {}", parent.display()))?;
+        }
+    }
+    fs::write(path, content).with_context(|| format!("Failed to write {}", path.display()))
+}
+
+/// Cluster names become Docker container/network names
+fn validate_cluster_name(name: &str) -> Result<()> {
+    if name.is_empty() {
+        return Err(anyhow!("Cluster name cannot be empty"));
+    }
+    let valid = name
+        .chars()
+        .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-')
+        && !name.starts_with('-')
+        && !name.ends_with('-');
+    if !valid {
+        return Err(anyhow!(
+            "Cluster name may only contain lowercase letters, digits and '-'"
+        ));
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Config;
+
+    #[test]
+    fn rendered_config_round_trips() {
+        let starter = StarterConfig {
+            cluster_name: "demo".to_string(),
+            domain: "demo.test".to_string(),
+            k3s_version: "v1.35.2-k3s1".to_string(),
+            api_port: 7443,
+            http_port: 8080,
+            https_port: 8443,
+            use_snapshot: false,
+            template: Template::Minimal,
+        };
+        let config: Config = serde_yml::from_str(&starter.render()).unwrap();
+        assert_eq!(config.infrastructure.cluster_name, "demo");
+        assert_eq!(config.infrastructure.domain, "demo.test");
+        assert_eq!(config.infrastructure.k3s_version, "v1.35.2-k3s1");
+        assert_eq!(config.infrastructure.api_port, 7443);
+        assert_eq!(config.infrastructure.http_port, 8080);
+        assert_eq!(config.infrastructure.https_port, 8443);
+        assert!(!config.infrastructure.speedup.use_snapshot);
+        assert!(config.commands.is_empty());
+    }
+
+    #[test]
+    fn defaults_are_valid() {
+        assert!(StarterConfig::default().validate().is_ok());
+    }
+
+    #[test]
+    fn rejects_bad_cluster_name_and_duplicate_ports() {
+        let mut starter = StarterConfig {
+            cluster_name: "My Cluster".to_string(),
+            ..StarterConfig::default()
+        };
+        assert!(starter.validate().is_err());
+
+        starter.cluster_name = "ok".to_string();
+        starter.https_port = starter.http_port;
+        let err = starter.validate().unwrap_err().to_string();
+        assert!(err.contains("HTTP and HTTPS"), "unexpected error: {err}");
+    }
+}
@@ -0,0 +1,120 @@
+//! Tag-based command visibility
+//!
+//! Commands and groups can carry `tags:` such as `[dba, danger]`. A tagged
+//! command is only loaded when every one of its tags (including those of
+//! its group and parent submenus) is enabled, so the menu, the palette, the
+//! HTTP API and `k3dev run` all agree on what exists. Untagged commands are
+//! always loaded.
+
+use std::collections::HashSet;
+
+use super::types::{CommandEntry, CommandGroup, Config};
+
+/// Environment variable that replaces the config's `tags:`, set by `--tags`
+pub const TAGS_ENV: &str = "K3DEV_TAGS";
+
+/// Tags in effect: `K3DEV_TAGS` (comma-separated) when set, else `tags:`
+fn enabled_tags(config: &Config) -> HashSet<String> {
+    match std::env::var(TAGS_ENV) {
+        Ok(value) => value
+            .split(',')
+            .map(str::trim)
+            .filter(|t| !t.is_empty())
+            .map(str::to_string)
+            .collect(),
+        Err(_) => config.tags.iter().cloned().collect(),
+    }
+}
+
+fn allowed(tags: &[String], enabled: &HashSet<String>) -> bool {
+    tags.iter().all(|t| enabled.contains(t))
+}
+
+/// Drop entries whose tags aren't enabled, and submenus left without any
+fn retain_entries(entries: &mut Vec<CommandEntry>, enabled: &HashSet<String>) {
+    entries.retain_mut(|entry| {
+        if !allowed(&entry.tags, enabled) {
+            return false;
+        }
+        if entry.commands.is_empty() {
+            return true;
+        }
+        retain_entries(&mut entry.commands, enabled);
+        entry.exec.is_some() || !entry.commands.is_empty()
+    });
+}
+
+/// Remove the commands the enabled tags don't cover from `config.commands`
+pub(super) fn apply_enabled_tags(config: &mut Config) {
+    let enabled = enabled_tags(config);
+    retain_groups(&mut config.commands, &enabled);
+}
+
+fn retain_groups(groups: &mut Vec<CommandGroup>, enabled: &HashSet<String>) {
+    groups.retain_mut(|group| {
+        if !allowed(&group.tags, enabled) {
+            return false;
+        }
+        // An empty group stays for the validator to point out
+        if group.commands.is_empty() {
+            return true;
+        }
+        retain_entries(&mut group.commands, enabled);
+        !group.commands.is_empty()
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn filtered(enabled: &[&str]) -> Vec<String> {
+        let mut config: Config = serde_yml::from_str(
+            r#"
+commands:
+  - name: "App"
+    commands:
+      - name: "Logs"
+        exec: { target: { type: host }, cmd: "true" }
+      - name: "Drop Tables"
+        tags: [dba, danger]
+        exec: { target: { type: host }, cmd: "true" }
+      - name: "Danger Zone"
+        commands:
+          - name: "Wipe"
+            tags: [danger]
+            exec: { target: { type: host }, cmd: "true" }
+  - name: "Database"
+    tags: [dba]
+    commands:
+      - name: "psql"
+        exec: { target: { type: host }, cmd: "true" }
+"#,
+        )
+        .unwrap();
+        let enabled = enabled.iter().map(|t| t.to_string()).collect();
+        retain_groups(&mut config.commands, &enabled);
+        let mut names = Vec::new();
+        for group in &config.commands {
+            for entry in &group.commands {
+                names.push(format!("{}/{}", group.name, entry.name));
+            }
+        }
+        names
+    }
+
+    #[test]
+    fn tagged_commands_need_every_tag() {
+        assert_eq!(filtered(&[]), ["App/Logs"]);
+        assert_eq!(filtered(&["dba"]), ["App/Logs", "Database/psql"]);
+        assert_eq!(
+            filtered(&["dba", "danger"]),
+            [
+                "App/Logs",
+                "App/Drop Tables",
+                "App/Danger Zone",
+                "Database/psql"
+            ]
+        );
+    }
+}
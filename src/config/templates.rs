@@ -0,0 +1,358 @@
+//! Cluster templates for new configs
+//!
+//! A template fills the parts of a starter config past the infrastructure
+//! basics: `on_services_deployed` hooks that install its services into the
+//! `services` namespace, and a command group to use them. The manifests are
+//! plain Deployments with upstream images, so only kubectl is needed.
+
+use clap::ValueEnum;
+
+/// Starter bundle picked in the setup wizard or with `k3dev init --template`
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum)]
+pub enum Template {
+    /// Just the cluster
+    #[default]
+    Minimal,
+    /// Traefik demo route, Mailpit and Postgres
+    WebDev,
+    /// Kafka and MinIO
+    Data,
+}
+
+impl Template {
+    pub const ALL: [Template; 3] = [Template::Minimal, Template::WebDev, Template::Data];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Template::Minimal => "minimal",
+            Template::WebDev => "web-dev",
+            Template::Data => "data",
+        }
+    }
+
+    pub fn description(self) -> &'static str {
+        match self {
+            Template::Minimal => "just the cluster",
+            Template::WebDev => "Traefik demo route, Mailpit and Postgres",
+            Template::Data => "Kafka and MinIO",
+        }
+    }
+
+    /// The template after this one, wrapping around
+    pub fn next(self) -> Template {
+        let i = Self::ALL.iter().position(|t| *t == self).unwrap_or(0);
+        Self::ALL[(i + 1) % Self::ALL.len()]
+    }
+
+    /// `hooks:` and `commands:` sections of the rendered config
+    pub fn sections(self, domain: &str) -> String {
+        let sections = match self {
+            Template::Minimal => MINIMAL,
+            Template::WebDev => WEB_DEV,
+            Template::Data => DATA,
+        };
+        sections.replace("{domain}", domain)
+    }
+}
+
+const MINIMAL: &str = r#"# =============================================================================
+# Custom Commands
+# Add groups of commands shown in the menu. Example:
+# =============================================================================
+commands: []
+#  - name: "App"
+#    commands:
+#      - name: "Logs"
+#        exec:
+#          target: { type: kubernetes, namespace: default, selector: "app=myapp" }
+#          cmd: "tail -f /var/log/app.log"
+"#;
+
+const WEB_DEV: &str = r#"# =============================================================================
+# Services (web-dev template)
+# Installed into the "services" namespace once Traefik is up. Edit or remove
+# these hooks freely; they re-apply on every start.
+# =============================================================================
+hooks:
+  on_services_deployed:
+    - name: "Install Postgres"
+      command: |
+        kubectl create namespace services --dry-run=client -o yaml | kubectl apply -f -
+        kubectl apply -n services -f - <<'EOF'
+        apiVersion: v1
+        kind: PersistentVolumeClaim
+        metadata: { name: postgres-data }
+        spec:
+          accessModes: [ReadWriteOnce]
+          resources: { requests: { storage: 1Gi } }
+        ---
+        apiVersion: apps/v1
+        kind: Deployment
+        metadata: { name: postgres }
+        spec:
+          strategy: { type: Recreate }
+          selector: { matchLabels: { app: postgres } }
+          template:
+            metadata: { labels: { app: postgres } }
+            spec:
+              containers:
+                - name: postgres
+                  image: postgres:17-alpine
+                  env:
+                    - { name: POSTGRES_PASSWORD, value: postgres }
+                    - { name: PGDATA, value: /var/lib/postgresql/data/pgdata }
+                  ports: [{ containerPort: 5432 }]
+                  volumeMounts: [{ name: data, mountPath: /var/lib/postgresql/data }]
+              volumes: [{ name: data, persistentVolumeClaim: { claimName: postgres-data } }]
+        ---
+        apiVersion: v1
+        kind: Service
+        metadata: { name: postgres }
+        spec:
+          selector: { app: postgres }
+          ports: [{ port: 5432 }]
+        EOF
+
+    - name: "Install Mailpit"
+      command: |
+        kubectl create namespace services --dry-run=client -o yaml | kubectl apply -f -
+        kubectl apply -n services -f - <<'EOF'
+        apiVersion: apps/v1
+        kind: Deployment
+        metadata: { name: mailpit }
+        spec:
+          selector: { matchLabels: { app: mailpit } }
+          template:
+            metadata: { labels: { app: mailpit } }
+            spec:
+              containers:
+                - name: mailpit
+                  image: axllent/mailpit:latest
+                  ports: [{ containerPort: 1025 }, { containerPort: 8025 }]
+        ---
+        apiVersion: v1
+        kind: Service
+        metadata: { name: mailpit }
+        spec:
+          selector: { app: mailpit }
+          ports:
+            - { name: smtp, port: 1025 }
+            - { name: http, port: 8025 }
+        ---
+        apiVersion: networking.k8s.io/v1
+        kind: Ingress
+        metadata: { name: mailpit }
+        spec:
+          rules:
+            - host: mail.{domain}
+              http:
+                paths:
+                  - path: /
+                    pathType: Prefix
+                    backend: { service: { name: mailpit, port: { number: 8025 } } }
+        EOF
+
+    - name: "Install Traefik demo route"
+      command: |
+        kubectl create namespace services --dry-run=client -o yaml | kubectl apply -f -
+        kubectl apply -n services -f - <<'EOF'
+        apiVersion: apps/v1
+        kind: Deployment
+        metadata: { name: whoami }
+        spec:
+          selector: { matchLabels: { app: whoami } }
+          template:
+            metadata: { labels: { app: whoami } }
+            spec:
+              containers:
+                - name: whoami
+                  image: traefik/whoami:latest
+                  ports: [{ containerPort: 80 }]
+        ---
+        apiVersion: v1
+        kind: Service
+        metadata: { name: whoami }
+        spec:
+          selector: { app: whoami }
+          ports: [{ port: 80 }]
+        ---
+        apiVersion: networking.k8s.io/v1
+        kind: Ingress
+        metadata: { name: whoami }
+        spec:
+          rules:
+            - host: whoami.{domain}
+              http:
+                paths:
+                  - path: /
+                    pathType: Prefix
+                    backend: { service: { name: whoami, port: { number: 80 } } }
+        EOF
+
+# =============================================================================
+# Custom Commands
+# =============================================================================
+commands:
+  - name: "Services"
+    commands:
+      - name: "psql"
+        description: "Postgres shell (postgres.services:5432, password postgres)"
+        exec:
+          target: { type: kubernetes, namespace: services, selector: "app=postgres" }
+          cmd: "psql -U postgres"
+      - name: "Send Test Mail"
+        description: "Mail sent to mailpit.services:1025 shows up at http://mail.{domain}"
+        exec:
+          target: { type: kubernetes, namespace: services, selector: "app=mailpit" }
+          cmd: "printf 'Subject: k3dev test\\n\\nHello from k3dev\\n' | /mailpit sendmail -S localhost:1025 dev@example.com"
+"#;
+
+const DATA: &str = r#"# =============================================================================
+# Services (data template)
+# Installed into the "services" namespace once Traefik is up. Edit or remove
+# these hooks freely; they re-apply on every start.
+# =============================================================================
+hooks:
+  on_services_deployed:
+    - name: "Install Kafka"
+      command: |
+        kubectl create namespace services --dry-run=client -o yaml | kubectl apply -f -
+        kubectl apply -n services -f - <<'EOF'
+        apiVersion: apps/v1
+        kind: Deployment
+        metadata: { name: kafka }
+        spec:
+          selector: { matchLabels: { app: kafka } }
+          template:
+            metadata: { labels: { app: kafka } }
+            spec:
+              enableServiceLinks: false
+              containers:
+                - name: kafka
+                  image: apache/kafka:3.9.0
+                  env:
+                    - { name: KAFKA_NODE_ID, value: "1" }
+                    - { name: KAFKA_PROCESS_ROLES, value: "broker,controller" }
+                    - { name: KAFKA_LISTENERS, value: "PLAINTEXT://:9092,CONTROLLER://:9093" }
+                    - { name: KAFKA_ADVERTISED_LISTENERS, value: "PLAINTEXT://kafka.services:9092" }
+                    - { name: KAFKA_CONTROLLER_LISTENER_NAMES, value: CONTROLLER }
+                    - { name: KAFKA_CONTROLLER_QUORUM_VOTERS, value: "1@localhost:9093" }
+                    - { name: KAFKA_OFFSETS_TOPIC_REPLICATION_FACTOR, value: "1" }
+                  ports: [{ containerPort: 9092 }]
+        ---
+        apiVersion: v1
+        kind: Service
+        metadata: { name: kafka }
+        spec:
+          selector: { app: kafka }
+          ports: [{ port: 9092 }]
+        EOF
+
+    - name: "Install MinIO"
+      command: |
+        kubectl create namespace services --dry-run=client -o yaml | kubectl apply -f -
+        kubectl apply -n services -f - <<'EOF'
+        apiVersion: v1
+        kind: PersistentVolumeClaim
+        metadata: { name: minio-data }
+        spec:
+          accessModes: [ReadWriteOnce]
+          resources: { requests: { storage: 5Gi } }
+        ---
+        apiVersion: apps/v1
+        kind: Deployment
+        metadata: { name: minio }
+        spec:
+          strategy: { type: Recreate }
+          selector: { matchLabels: { app: minio } }
+          template:
+            metadata: { labels: { app: minio } }
+            spec:
+              containers:
+                - name: minio
+                  image: minio/minio:latest
+                  args: [server, /data, --console-address, ":9001"]
+                  env:
+                    - { name: MINIO_ROOT_USER, value: minio }
+                    - { name: MINIO_ROOT_PASSWORD, value: minio123 }
+                  ports: [{ containerPort: 9000 }, { containerPort: 9001 }]
+                  volumeMounts: [{ name: data, mountPath: /data }]
+              volumes: [{ name: data, persistentVolumeClaim: { claimName: minio-data } }]
+        ---
+        apiVersion: v1
+        kind: Service
+        metadata: { name: minio }
+        spec:
+          selector: { app: minio }
+          ports:
+            - { name: api, port: 9000 }
+            - { name: console, port: 9001 }
+        ---
+        apiVersion: networking.k8s.io/v1
+        kind: Ingress
+        metadata: { name: minio }
+        spec:
+          rules:
+            - host: minio.{domain}
+              http:
+                paths:
+                  - path: /
+                    pathType: Prefix
+                    backend: { service: { name: minio, port: { number: 9001 } } }
+        EOF
+
+# =============================================================================
+# Custom Commands
+# =============================================================================
+commands:
+  - name: "Services"
+    commands:
+      - name: "Kafka Topics"
+        description: "List topics on kafka.services:9092"
+        exec:
+          target: { type: kubernetes, namespace: services, selector: "app=kafka" }
+          cmd: "/opt/kafka/bin/kafka-topics.sh --bootstrap-server localhost:9092 --list"
+      - name: "Create Topic"
+        exec:
+          target: { type: kubernetes, namespace: services, selector: "app=kafka" }
+          cmd: "/opt/kafka/bin/kafka-topics.sh --bootstrap-server localhost:9092 --create --topic @topic"
+          input:
+            topic: "Topic name:"
+      - name: "MinIO Shell"
+        description: "Console at http://minio.{domain} (minio / minio123)"
+        exec:
+          target: { type: kubernetes, namespace: services, selector: "app=minio" }
+          cmd: "/bin/sh"
+"#;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::StarterConfig;
+
+    #[test]
+    fn every_template_renders_a_valid_config() {
+        for template in Template::ALL {
+            let starter = StarterConfig {
+                template,
+                ..StarterConfig::default()
+            };
+            let config: crate::config::Config = serde_yml::from_str(&starter.render())
+                .unwrap_or_else(|e| panic!("{} does not parse: {e}", template.name()));
+            let expected_hooks = match template {
+                Template::Minimal => 0,
+                Template::WebDev => 3,
+                Template::Data => 2,
+            };
+            assert_eq!(config.hooks.on_services_deployed.len(), expected_hooks);
+            assert_eq!(config.commands.is_empty(), template == Template::Minimal);
+        }
+    }
+
+    #[test]
+    fn ingress_hosts_use_the_domain() {
+        let sections = Template::WebDev.sections("demo.test");
+        assert!(sections.contains("host: mail.demo.test"));
+        assert!(!sections.contains("{domain}"));
+    }
+}
@@ -6,6 +6,9 @@
 use std::collections::HashMap;
 use std::time::{Duration, Instant};
 
+use super::{HealthProbe, HookSchedule, IngressHealthConfig};
+use crate::cluster::health_backoff::BackoffPolicy;
+
 /// Configuration for refresh intervals and operation timeouts
 #[derive(Debug, Clone)]
 pub struct RefreshConfig {
@@ -33,6 +36,18 @@ pub struct RefreshConfig {
     /// Timeout for ingress health check operations
     pub ingress_health_timeout: Duration,
 
+    /// Most ingress endpoints checked at the same time
+    pub ingress_health_concurrency: usize,
+
+    /// Longest wait before re-checking an endpoint that keeps failing
+    pub ingress_health_max_backoff: Duration,
+
+    /// Random spread applied to backoff delays (0.2 = ±20%)
+    pub ingress_health_jitter: f64,
+
+    /// Where ingress endpoints are checked from
+    pub ingress_health_probe: HealthProbe,
+
     /// Timeout for docker stats operations
     pub docker_stats_timeout: Duration,
 
@@ -47,6 +62,40 @@ pub struct RefreshConfig {
 
     /// Timeout for volume stats operations
     pub volume_timeout: Duration,
+
+    /// Interval for refreshing the cluster info view while it is open
+    pub cluster_info_refresh: Duration,
+
+    /// Timeout for gathering cluster info
+    pub cluster_info_timeout: Duration,
+
+    /// Interval for refreshing the pod top popup while it is open
+    pub pod_top_refresh: Duration,
+
+    /// Timeout for listing a pod's processes
+    pub pod_top_timeout: Duration,
+
+    /// Interval for refreshing the services view while it is open
+    pub services_refresh: Duration,
+
+    /// Timeout for listing and probing services
+    pub services_timeout: Duration,
+
+    /// Interval for checking core deployments and nodes while running
+    pub health_check: Duration,
+
+    /// Timeout for the cluster health check
+    pub health_check_timeout: Duration,
+
+    /// Interval for comparing the live Traefik config with k3dev's
+    pub traefik_drift: Duration,
+
+    /// Timeout for reading the live Traefik config
+    pub traefik_drift_timeout: Duration,
+
+    /// Quiet period before an on-demand refresh runs; requests within it
+    /// are folded into one run
+    pub request_debounce: Duration,
 }
 
 impl Default for RefreshConfig {
@@ -63,11 +112,46 @@ impl Default for RefreshConfig {
             cluster_operation_timeout: Duration::from_secs(300), // 5 minutes
             ingress_timeout: Duration::from_secs(10),
             ingress_health_timeout: Duration::from_secs(30),
+            ingress_health_concurrency: IngressHealthConfig::default().concurrency,
+            ingress_health_max_backoff: IngressHealthConfig::default().max_backoff,
+            ingress_health_jitter: IngressHealthConfig::default().jitter,
+            ingress_health_probe: HealthProbe::default(),
             docker_stats_timeout: Duration::from_secs(5),
             port_forward_timeout: Duration::from_secs(10),
             manual_hosts_timeout: Duration::from_secs(60),
             volume_refresh: Duration::from_secs(10),
             volume_timeout: Duration::from_secs(10),
+            cluster_info_refresh: Duration::from_secs(5),
+            cluster_info_timeout: Duration::from_secs(10),
+            pod_top_refresh: Duration::from_secs(3),
+            pod_top_timeout: Duration::from_secs(5),
+            services_refresh: Duration::from_secs(10),
+            services_timeout: Duration::from_secs(15),
+            health_check: Duration::from_secs(10),
+            health_check_timeout: Duration::from_secs(10),
+            traefik_drift: Duration::from_secs(30),
+            traefik_drift_timeout: Duration::from_secs(10),
+            request_debounce: Duration::from_millis(250),
+        }
+    }
+}
+
+impl RefreshConfig {
+    /// Apply the `ingress_health:` settings from the config file
+    pub fn with_ingress_health(mut self, config: &IngressHealthConfig) -> Self {
+        self.ingress_health_concurrency = config.concurrency.max(1);
+        self.ingress_health_max_backoff = config.max_backoff;
+        self.ingress_health_jitter = config.jitter.clamp(0.0, 1.0);
+        self.ingress_health_probe = config.probe;
+        self
+    }
+
+    /// Backoff for failing ingress endpoints, starting at the refresh interval
+    pub fn ingress_backoff(&self) -> BackoffPolicy {
+        BackoffPolicy {
+            base: self.ingress_refresh,
+            max: self.ingress_health_max_backoff.max(self.ingress_refresh),
+            jitter: self.ingress_health_jitter,
         }
     }
 }
@@ -85,99 +169,306 @@ pub enum RefreshTask {
     StatsRefresh,
     /// Refresh volume/PVC stats
     VolumeRefresh,
+    /// Refresh the cluster info view
+    ClusterInfoRefresh,
+    /// Refresh the pod top popup
+    PodTopRefresh,
+    /// Refresh the services view
+    ServicesRefresh,
+    /// Check core deployments and nodes for a degraded cluster
+    HealthCheck,
+    /// Compare the live Traefik config with the one k3dev applies
+    TraefikDrift,
+    /// Run the recurring hook at this index of `hooks.recurring`
+    RecurringHook(usize),
+}
+
+impl RefreshTask {
+    /// Every task, in the order the diagnostics popup lists them
+    pub const ALL: [RefreshTask; 10] = [
+        RefreshTask::StatsRefresh,
+        RefreshTask::HealthCheck,
+        RefreshTask::TraefikDrift,
+        RefreshTask::IngressRefresh,
+        RefreshTask::HostsCheck,
+        RefreshTask::VolumeRefresh,
+        RefreshTask::ClusterInfoRefresh,
+        RefreshTask::PodTopRefresh,
+        RefreshTask::ServicesRefresh,
+        RefreshTask::BlinkToggle,
+    ];
+
+    pub fn label(self) -> &'static str {
+        match self {
+            RefreshTask::IngressRefresh => "Ingress",
+            RefreshTask::HostsCheck => "Hosts check",
+            RefreshTask::BlinkToggle => "Blink",
+            RefreshTask::StatsRefresh => "Pod stats",
+            RefreshTask::VolumeRefresh => "Volumes",
+            RefreshTask::ClusterInfoRefresh => "Cluster info",
+            RefreshTask::PodTopRefresh => "Pod top",
+            RefreshTask::ServicesRefresh => "Services",
+            RefreshTask::HealthCheck => "Cluster health",
+            RefreshTask::TraefikDrift => "Traefik drift",
+            RefreshTask::RecurringHook(_) => "Recurring hook",
+        }
+    }
 }
 
 /// Internal state for a scheduled task
 struct TaskState {
     interval: Duration,
     last_run: Instant,
+    /// Skip runs while the previous one is still in flight
+    coalesce: bool,
+    /// When the current run started
+    in_flight: Option<Instant>,
+    /// When an on-demand request becomes due
+    requested: Option<Instant>,
+    /// Only run when requested, not on the interval
+    on_request_only: bool,
+    /// Run on this schedule instead of the interval
+    cron: Option<croner::Cron>,
+    cron_due: Option<Instant>,
+    last_duration: Option<Duration>,
+    last_error: Option<String>,
+    runs: u64,
+    skipped: u64,
+}
+
+impl TaskState {
+    fn new(interval: Duration, coalesce: bool, now: Instant) -> Self {
+        Self {
+            interval,
+            last_run: now,
+            coalesce,
+            in_flight: None,
+            requested: None,
+            on_request_only: false,
+            cron: None,
+            cron_due: None,
+            last_duration: None,
+            last_error: None,
+            runs: 0,
+            skipped: 0,
+        }
+    }
+
+    /// Whether a new run has to wait for the current one
+    fn busy(&self) -> bool {
+        self.coalesce && self.in_flight.is_some()
+    }
+
+    /// When the interval or cron schedule next makes the task due
+    fn scheduled_at(&self) -> Option<Instant> {
+        if self.on_request_only {
+            None
+        } else if self.cron.is_some() {
+            self.cron_due
+        } else {
+            Some(self.last_run + self.interval)
+        }
+    }
+
+    /// Start the interval, or wait for the next cron time, from `now`
+    fn restart_schedule(&mut self, now: Instant) {
+        self.last_run = now;
+        if let Some(cron) = &self.cron {
+            self.cron_due = next_cron_run(cron);
+        }
+    }
+}
+
+/// The next time `cron` fires, None if it never does again
+fn next_cron_run(cron: &croner::Cron) -> Option<Instant> {
+    let now = chrono::Local::now();
+    let next = cron.find_next_occurrence(&now, false).ok()?;
+    Some(Instant::now() + (next - now).to_std().ok()?)
+}
+
+/// What the scheduler knows about one task, for display
+#[derive(Debug, Clone)]
+pub struct TaskStats {
+    pub task: RefreshTask,
+    pub interval: Duration,
+    /// How long the current run has been going
+    pub running_for: Option<Duration>,
+    pub last_duration: Option<Duration>,
+    pub last_error: Option<String>,
+    pub runs: u64,
+    /// Scheduled runs dropped because the previous one was still in flight
+    pub skipped: u64,
 }
 
 /// Unified scheduler for managing periodic refresh tasks
 ///
 /// Replaces multiple individual timers with a single scheduler that
-/// efficiently tracks and triggers multiple periodic tasks.
+/// efficiently tracks and triggers multiple periodic tasks. Besides their
+/// interval, tasks run when requested (debounced, so a burst of requests
+/// becomes one run). Work the app spawns for a task is reported with
+/// [`start`](Self::start) and [`finish`](Self::finish); a coalesced task is
+/// not handed out again until its run has finished.
 pub struct RefreshScheduler {
     tasks: HashMap<RefreshTask, TaskState>,
+    debounce: Duration,
 }
 
 impl RefreshScheduler {
     /// Create a new scheduler with intervals from config
     pub fn new(config: &RefreshConfig) -> Self {
         let now = Instant::now();
-        let mut tasks = HashMap::new();
-
-        tasks.insert(
-            RefreshTask::IngressRefresh,
-            TaskState {
-                interval: config.ingress_refresh,
-                last_run: now,
-            },
-        );
-
-        tasks.insert(
-            RefreshTask::HostsCheck,
-            TaskState {
-                interval: config.hosts_check,
-                last_run: now,
-            },
-        );
+        let tasks = [
+            (RefreshTask::IngressRefresh, config.ingress_refresh),
+            (RefreshTask::HostsCheck, config.hosts_check),
+            (RefreshTask::BlinkToggle, config.blink_toggle),
+            (RefreshTask::StatsRefresh, config.stats_refresh),
+            (RefreshTask::VolumeRefresh, config.volume_refresh),
+            (RefreshTask::ClusterInfoRefresh, config.cluster_info_refresh),
+            (RefreshTask::PodTopRefresh, config.pod_top_refresh),
+            (RefreshTask::ServicesRefresh, config.services_refresh),
+            (RefreshTask::HealthCheck, config.health_check),
+            (RefreshTask::TraefikDrift, config.traefik_drift),
+        ]
+        .into_iter()
+        .map(|(task, interval)| {
+            // Blinking does no background work, everything else may be slow
+            let coalesce = task != RefreshTask::BlinkToggle;
+            (task, TaskState::new(interval, coalesce, now))
+        })
+        .collect();
 
-        tasks.insert(
-            RefreshTask::BlinkToggle,
-            TaskState {
-                interval: config.blink_toggle,
-                last_run: now,
-            },
-        );
-
-        tasks.insert(
-            RefreshTask::StatsRefresh,
-            TaskState {
-                interval: config.stats_refresh,
-                last_run: now,
-            },
-        );
-
-        tasks.insert(
-            RefreshTask::VolumeRefresh,
-            TaskState {
-                interval: config.volume_refresh,
-                last_run: now,
-            },
-        );
-
-        Self { tasks }
+        Self {
+            tasks,
+            debounce: config.request_debounce,
+        }
     }
 
     /// Check which tasks are due to run and return them
     ///
-    /// This method checks all registered tasks and returns a list of
-    /// tasks whose interval has elapsed. It automatically updates the
-    /// last_run time for returned tasks.
+    /// A task is due when its interval has elapsed or a request for it has
+    /// settled. Coalesced tasks still in flight are skipped (their interval
+    /// starts over) and keep pending requests until they finish. The
+    /// last_run time of returned tasks is updated.
     pub fn tick(&mut self) -> Vec<RefreshTask> {
         let now = Instant::now();
         let mut due_tasks = Vec::new();
 
         for (task, state) in self.tasks.iter_mut() {
-            if now.duration_since(state.last_run) >= state.interval {
-                due_tasks.push(*task);
-                state.last_run = now;
+            let interval_due = state.scheduled_at().is_some_and(|at| at <= now);
+            let requested_due = state.requested.is_some_and(|at| at <= now);
+            if !interval_due && !requested_due {
+                continue;
             }
+            if state.busy() {
+                if interval_due {
+                    state.skipped += 1;
+                    state.restart_schedule(now);
+                }
+                continue;
+            }
+            due_tasks.push(*task);
+            state.restart_schedule(now);
+            state.requested = None;
         }
 
         due_tasks
     }
 
-    /// Mark multiple tasks as having just run
-    pub fn mark_run_multiple(&mut self, tasks: &[RefreshTask]) {
+    /// When the next task is due
+    pub fn next_due(&self) -> Option<Instant> {
+        self.tasks
+            .values()
+            .filter_map(|state| {
+                let interval_due = state.scheduled_at();
+                match (state.requested, interval_due) {
+                    (Some(at), Some(due)) if !state.busy() => Some(at.min(due)),
+                    (Some(at), None) if !state.busy() => Some(at),
+                    (_, due) => due,
+                }
+            })
+            .min()
+    }
+
+    /// Run recurring hook `index` of `hooks.recurring` on its schedule. Like
+    /// refreshes, a run doesn't start while the previous one is going.
+    pub fn add_recurring_hook(&mut self, index: usize, schedule: &HookSchedule) {
         let now = Instant::now();
+        let state = match schedule {
+            HookSchedule::Every(every) => TaskState::new(*every, true, now),
+            HookSchedule::Cron(cron) => TaskState {
+                cron: Some((**cron).clone()),
+                cron_due: next_cron_run(cron),
+                ..TaskState::new(Duration::ZERO, true, now)
+            },
+        };
+        self.tasks.insert(RefreshTask::RecurringHook(index), state);
+    }
+
+    /// Forget every recurring hook, before scheduling those of a new config
+    pub fn clear_recurring_hooks(&mut self) {
+        self.tasks
+            .retain(|task, _| !matches!(task, RefreshTask::RecurringHook(_)));
+    }
+
+    /// Stop running tasks on their interval (safe mode); they still run
+    /// when requested. Blinking goes on.
+    pub fn pause_intervals(&mut self) {
+        for (task, state) in self.tasks.iter_mut() {
+            state.on_request_only = *task != RefreshTask::BlinkToggle;
+        }
+    }
+
+    /// Ask for a run of `task` soon; repeated requests push it back until
+    /// they stop for the debounce period
+    pub fn request(&mut self, task: RefreshTask) {
+        if let Some(state) = self.tasks.get_mut(&task) {
+            state.requested = Some(Instant::now() + self.debounce);
+        }
+    }
+
+    /// Ask for a run of each of `tasks`
+    pub fn request_multiple(&mut self, tasks: &[RefreshTask]) {
         for task in tasks {
-            if let Some(state) = self.tasks.get_mut(task) {
-                state.last_run = now;
+            self.request(*task);
+        }
+    }
+
+    /// Record that work for `task` was spawned
+    pub fn start(&mut self, task: RefreshTask) {
+        if let Some(state) = self.tasks.get_mut(&task) {
+            state.in_flight = Some(Instant::now());
+            state.runs += 1;
+        }
+    }
+
+    /// Record that the work for `task` is done, with its error if it failed
+    pub fn finish(&mut self, task: RefreshTask, error: Option<String>) {
+        if let Some(state) = self.tasks.get_mut(&task) {
+            if let Some(started) = state.in_flight.take() {
+                state.last_duration = Some(started.elapsed());
             }
+            state.last_error = error;
         }
     }
+
+    /// Per-task statistics in [`RefreshTask::ALL`] order
+    pub fn stats(&self) -> Vec<TaskStats> {
+        RefreshTask::ALL
+            .iter()
+            .filter_map(|task| {
+                let state = self.tasks.get(task)?;
+                Some(TaskStats {
+                    task: *task,
+                    interval: state.interval,
+                    running_for: state.in_flight.map(|started| started.elapsed()),
+                    last_duration: state.last_duration,
+                    last_error: state.last_error.clone(),
+                    runs: state.runs,
+                    skipped: state.skipped,
+                })
+            })
+            .collect()
+    }
 }
 
 #[cfg(test)]
@@ -208,4 +499,106 @@ mod tests {
         let due = scheduler.tick();
         assert!(due.is_empty());
     }
+
+    #[test]
+    fn next_due_is_the_earliest_task() {
+        let config = RefreshConfig {
+            blink_toggle: Duration::from_millis(10),
+            ..RefreshConfig::default()
+        };
+        let before = Instant::now();
+        let scheduler = RefreshScheduler::new(&config);
+        let next = scheduler.next_due().unwrap();
+        assert!(next >= before + Duration::from_millis(10));
+        assert!(next <= Instant::now() + Duration::from_millis(10));
+    }
+
+    #[test]
+    fn in_flight_tasks_are_skipped_until_finished() {
+        let config = RefreshConfig {
+            stats_refresh: Duration::from_millis(10),
+            ..RefreshConfig::default()
+        };
+        let mut scheduler = RefreshScheduler::new(&config);
+        sleep(Duration::from_millis(15));
+        assert!(scheduler.tick().contains(&RefreshTask::StatsRefresh));
+        scheduler.start(RefreshTask::StatsRefresh);
+
+        sleep(Duration::from_millis(15));
+        assert!(!scheduler.tick().contains(&RefreshTask::StatsRefresh));
+        scheduler.finish(RefreshTask::StatsRefresh, Some("timed out".to_string()));
+
+        sleep(Duration::from_millis(15));
+        assert!(scheduler.tick().contains(&RefreshTask::StatsRefresh));
+        let stats = &scheduler.stats()[0];
+        assert_eq!(stats.task, RefreshTask::StatsRefresh);
+        assert_eq!((stats.runs, stats.skipped), (1, 1));
+        assert_eq!(stats.last_error.as_deref(), Some("timed out"));
+        assert!(stats.last_duration.is_some());
+    }
+
+    #[test]
+    fn requests_are_debounced_and_wait_for_the_running_task() {
+        let config = RefreshConfig {
+            request_debounce: Duration::from_millis(10),
+            ..RefreshConfig::default()
+        };
+        let mut scheduler = RefreshScheduler::new(&config);
+        scheduler.request(RefreshTask::VolumeRefresh);
+        scheduler.request(RefreshTask::VolumeRefresh);
+        assert!(scheduler.tick().is_empty());
+        assert!(scheduler.next_due().unwrap() <= Instant::now() + Duration::from_millis(10));
+
+        sleep(Duration::from_millis(15));
+        assert_eq!(scheduler.tick(), vec![RefreshTask::VolumeRefresh]);
+        assert!(scheduler.tick().is_empty());
+
+        // A request while the task runs is held until it finishes
+        scheduler.start(RefreshTask::VolumeRefresh);
+        scheduler.request(RefreshTask::VolumeRefresh);
+        sleep(Duration::from_millis(15));
+        assert!(scheduler.tick().is_empty());
+        scheduler.finish(RefreshTask::VolumeRefresh, None);
+        assert_eq!(scheduler.tick(), vec![RefreshTask::VolumeRefresh]);
+    }
+
+    #[test]
+    fn paused_intervals_only_run_requested_tasks() {
+        let config = RefreshConfig {
+            stats_refresh: Duration::from_millis(10),
+            request_debounce: Duration::from_millis(10),
+            ..RefreshConfig::default()
+        };
+        let mut scheduler = RefreshScheduler::new(&config);
+        scheduler.pause_intervals();
+        sleep(Duration::from_millis(15));
+        assert!(scheduler.tick().is_empty());
+
+        scheduler.request(RefreshTask::StatsRefresh);
+        sleep(Duration::from_millis(15));
+        assert_eq!(scheduler.tick(), vec![RefreshTask::StatsRefresh]);
+    }
+
+    #[test]
+    fn recurring_hooks_follow_their_schedule() {
+        let mut scheduler = RefreshScheduler::new(&RefreshConfig::default());
+        scheduler.add_recurring_hook(0, &HookSchedule::Every(Duration::from_millis(10)));
+        // Once a year, so never during the test
+        let yearly = croner::Cron::new("0 0 1 1 *").parse().unwrap();
+        scheduler.add_recurring_hook(1, &HookSchedule::Cron(Box::new(yearly)));
+
+        assert!(scheduler.tick().is_empty());
+        sleep(Duration::from_millis(15));
+        assert_eq!(scheduler.tick(), vec![RefreshTask::RecurringHook(0)]);
+
+        // Recurring hooks stay out of the refresh task listing
+        assert!(scheduler
+            .stats()
+            .iter()
+            .all(|s| !matches!(s.task, RefreshTask::RecurringHook(_))));
+
+        scheduler.clear_recurring_hooks();
+        sleep(Duration::from_millis(15));
+        assert!(scheduler.tick().is_empty());
+    }
 }
@@ -1,7 +1,11 @@
 use serde::Deserialize;
-use std::collections::HashMap;
+use std::collections::{BTreeMap, HashMap};
+use std::path::{Path, PathBuf};
 use std::time::Duration;
 
+use super::branch::{branch_slug, BranchCluster};
+use super::loader::expand_home;
+use crate::ui::components::{OutputLimit, DEFAULT_MAX_LINES};
 use crate::ui::Theme;
 
 /// Root configuration structure
@@ -28,12 +32,29 @@ pub struct Config {
     #[serde(default)]
     pub commands: Vec<CommandGroup>,
 
+    /// Git repositories whose command catalogs are merged into the menu
+    #[serde(default)]
+    pub commands_source: Vec<CommandSource>,
+
+    /// Catalogs that could not be read, reported as config warnings
+    #[serde(skip)]
+    pub catalog_errors: Vec<String>,
+
+    /// Command tags to show; tagged commands need all their tags enabled.
+    /// `--tags` / `K3DEV_TAGS` replace this list
+    #[serde(default)]
+    pub tags: Vec<String>,
+
     #[serde(default)]
     pub info_blocks: Vec<InfoBlock>,
 
     #[serde(default)]
     pub hooks: HooksConfig,
 
+    /// Checks run after the on_services_deployed hooks
+    #[serde(default)]
+    pub smoke_tests: Vec<SmokeTest>,
+
     /// Custom keybindings
     #[serde(default)]
     pub keybindings: Option<KeybindingsConfig>,
@@ -45,6 +66,85 @@ pub struct Config {
     /// Packet-capture (tcpdump → pcap) settings
     #[serde(default)]
     pub capture: CaptureConfig,
+
+    /// Toast notifications for cluster events
+    #[serde(default)]
+    pub notifications: NotificationsConfig,
+
+    /// Cluster start behaviour
+    #[serde(default)]
+    pub start: StartConfig,
+
+    /// Local HTTP API for editors and dashboards
+    #[serde(default)]
+    pub api: ApiConfig,
+
+    /// How ingress endpoints are health-checked
+    #[serde(default)]
+    pub ingress_health: IngressHealthConfig,
+
+    /// Directories of Kubernetes manifests for Diff Manifests
+    #[serde(default)]
+    pub manifests: Vec<String>,
+
+    /// Host directories the Mount Host Path action may mount into pods
+    #[serde(default)]
+    pub host_mounts: HostMountsConfig,
+
+    /// Images the Build Image action builds with BuildKit
+    #[serde(default)]
+    pub builds: Vec<BuildConfig>,
+
+    /// Registry credentials the Provision Pull Secret action copies into namespaces
+    #[serde(default)]
+    pub pull_secret: PullSecretConfig,
+
+    /// Named seed data bundles the Load Seed palette actions apply to pods
+    #[serde(default)]
+    pub seeds: Vec<SeedConfig>,
+
+    /// Settings of the Chaos palette tools
+    #[serde(default)]
+    pub chaos: ChaosConfig,
+
+    /// Detection of a dead or hung cluster, and optional restarts
+    #[serde(default)]
+    pub watchdog: WatchdogConfig,
+
+    /// SSH host the Share Ingress Port action forwards through
+    #[serde(default)]
+    pub share: ShareConfig,
+
+    /// Which tunnel client makes an ingress host public
+    #[serde(default)]
+    pub tunnel: TunnelConfig,
+
+    /// Browser ingress URLs open in, instead of the system default
+    #[serde(default)]
+    pub browser: BrowserConfig,
+
+    /// Where pod shells and log follows open in tmux or zellij
+    #[serde(default)]
+    pub multiplexer: MultiplexerConfig,
+
+    /// How much output a command keeps in memory
+    #[serde(default)]
+    pub output: OutputConfig,
+
+    /// Recording of each session's output and actions to a file
+    #[serde(default)]
+    pub transcript: TranscriptConfig,
+}
+
+impl Config {
+    /// `manifests` with `~` expanded; relative paths stay relative to the
+    /// directory k3dev runs in
+    pub fn manifest_dirs(&self) -> Vec<PathBuf> {
+        self.manifests
+            .iter()
+            .map(|dir| expand_home(Path::new(dir)).unwrap_or_else(|_| PathBuf::from(dir)))
+            .collect()
+    }
 }
 
 /// Menu width configuration
@@ -133,11 +233,69 @@ impl MenuWidth {
 }
 
 /// UI configuration options
-#[derive(Debug, Clone, Deserialize, Default)]
+#[derive(Debug, Clone, Deserialize)]
 pub struct UiConfig {
     /// Menu panel width: "auto", "30%", or a fixed number
     #[serde(default)]
     pub menu_width: MenuWidth,
+
+    /// Panel arrangement: "classic", "docked" or "columns"
+    #[serde(default)]
+    pub layout: LayoutPreset,
+
+    /// Give commands without an `icon:` one for their target kind
+    #[serde(default = "default_true")]
+    pub command_icons: bool,
+}
+
+impl Default for UiConfig {
+    fn default() -> Self {
+        Self {
+            menu_width: MenuWidth::default(),
+            layout: LayoutPreset::default(),
+            command_icons: true,
+        }
+    }
+}
+
+/// How the main screen's panels are arranged
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum LayoutPreset {
+    /// Menu and pods; command output in a popup
+    #[default]
+    Classic,
+    /// Menu and pods above, command output docked across the bottom
+    Docked,
+    /// Menu, pods and command output side by side
+    Columns,
+}
+
+impl LayoutPreset {
+    pub const ALL: [LayoutPreset; 3] = [
+        LayoutPreset::Classic,
+        LayoutPreset::Docked,
+        LayoutPreset::Columns,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            LayoutPreset::Classic => "classic",
+            LayoutPreset::Docked => "docked",
+            LayoutPreset::Columns => "columns",
+        }
+    }
+
+    /// The preset after this one, wrapping around
+    pub fn next(self) -> LayoutPreset {
+        let i = Self::ALL.iter().position(|p| *p == self).unwrap_or(0);
+        Self::ALL[(i + 1) % Self::ALL.len()]
+    }
+
+    /// Whether command output has a panel of its own instead of the popup
+    pub fn docks_output(self) -> bool {
+        self != LayoutPreset::Classic
+    }
 }
 
 /// Keybinding configuration for customizing keyboard shortcuts
@@ -170,19 +328,82 @@ pub struct KeybindingsConfig {
     pub toggle_focus: Option<String>,
     #[serde(default)]
     pub execute: Option<String>,
+    #[serde(default)]
+    pub page_up: Option<String>,
+    #[serde(default)]
+    pub page_down: Option<String>,
+
+    // Layout and focus
+    #[serde(default)]
+    pub search: Option<String>,
+    #[serde(default)]
+    pub grow_menu: Option<String>,
+    #[serde(default)]
+    pub shrink_menu: Option<String>,
+    #[serde(default)]
+    pub focus_menu: Option<String>,
+    #[serde(default)]
+    pub focus_pods: Option<String>,
+    #[serde(default)]
+    pub focus_actions: Option<String>,
 
     /// Custom command bindings: key -> "Group Name/Command Name"
     #[serde(default)]
     pub custom: HashMap<String, String>,
+
+    /// Per-mode remaps: context (normal, pod_stats, output_popup, input,
+    /// palette) -> action -> key
+    #[serde(default)]
+    pub contexts: HashMap<String, HashMap<String, String>>,
+}
+
+impl KeybindingsConfig {
+    /// Built-in action remaps as (config key, configured binding) pairs
+    pub fn builtin(&self) -> [(&'static str, Option<&String>); 20] {
+        [
+            ("quit", self.quit.as_ref()),
+            ("help", self.help.as_ref()),
+            ("refresh", self.refresh.as_ref()),
+            ("command_palette", self.command_palette.as_ref()),
+            ("update_hosts", self.update_hosts.as_ref()),
+            ("cancel", self.cancel.as_ref()),
+            ("move_up", self.move_up.as_ref()),
+            ("move_down", self.move_down.as_ref()),
+            ("move_left", self.move_left.as_ref()),
+            ("move_right", self.move_right.as_ref()),
+            ("toggle_focus", self.toggle_focus.as_ref()),
+            ("execute", self.execute.as_ref()),
+            ("page_up", self.page_up.as_ref()),
+            ("page_down", self.page_down.as_ref()),
+            ("search", self.search.as_ref()),
+            ("grow_menu", self.grow_menu.as_ref()),
+            ("shrink_menu", self.shrink_menu.as_ref()),
+            ("focus_menu", self.focus_menu.as_ref()),
+            ("focus_pods", self.focus_pods.as_ref()),
+            ("focus_actions", self.focus_actions.as_ref()),
+        ]
+    }
 }
 
 /// Hook event types for cluster lifecycle
+// Variant names mirror the `on_*` config keys
+#[allow(clippy::enum_variant_names)]
 #[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum HookEvent {
     /// After k3s API is responding, before traefik is deployed
     OnClusterAvailable,
     /// After traefik and core services are deployed
     OnServicesDeployed,
+    /// After a custom command fails its success criteria
+    OnCommandFailed,
+    /// After a running cluster turns degraded
+    OnClusterDegraded,
+    /// On a recurring hook's schedule
+    Recurring,
+    /// After watched files changed
+    OnFileChange,
+    /// Before a cluster is destroyed, while it still runs
+    OnBeforeDestroy,
 }
 
 impl HookEvent {
@@ -190,6 +411,11 @@ impl HookEvent {
         match self {
             HookEvent::OnClusterAvailable => "on_cluster_available",
             HookEvent::OnServicesDeployed => "on_services_deployed",
+            HookEvent::OnCommandFailed => "on_command_failed",
+            HookEvent::OnClusterDegraded => "on_cluster_degraded",
+            HookEvent::Recurring => "recurring",
+            HookEvent::OnFileChange => "on_file_change",
+            HookEvent::OnBeforeDestroy => "on_before_destroy",
         }
     }
 }
@@ -238,12 +464,42 @@ pub struct HooksConfig {
     /// Hooks to run after services are deployed
     #[serde(default)]
     pub on_services_deployed: Vec<HookCommand>,
+
+    /// Hooks to run when a custom command fails. The command is described by
+    /// `K3DEV_COMMAND`, `K3DEV_EXIT_CODE` and `K3DEV_FAILURE` in the hook env.
+    #[serde(default)]
+    pub on_command_failed: Vec<HookCommand>,
+
+    /// Hooks to run when a running cluster turns degraded. The cause is in
+    /// `K3DEV_DEGRADED_REASON` in the hook env.
+    #[serde(default)]
+    pub on_cluster_degraded: Vec<HookCommand>,
+
+    /// Hooks the TUI runs on an interval or cron schedule
+    #[serde(default)]
+    pub recurring: Vec<RecurringHook>,
+
+    /// Hooks the TUI runs when watched files change. The changed files are
+    /// in `K3DEV_CHANGED_FILES`, one per line.
+    #[serde(default)]
+    pub on_file_change: Vec<FileChangeHook>,
+
+    /// Hooks to run before the cluster is destroyed, while it still runs,
+    /// e.g. to dump databases. A failing hook cancels the destroy.
+    #[serde(default)]
+    pub on_before_destroy: Vec<HookCommand>,
 }
 
 impl HooksConfig {
     /// Check if any hooks are configured
     pub fn has_hooks(&self) -> bool {
-        !self.on_cluster_available.is_empty() || !self.on_services_deployed.is_empty()
+        !self.on_cluster_available.is_empty()
+            || !self.on_services_deployed.is_empty()
+            || !self.on_command_failed.is_empty()
+            || !self.on_cluster_degraded.is_empty()
+            || !self.recurring.is_empty()
+            || !self.on_file_change.is_empty()
+            || !self.on_before_destroy.is_empty()
     }
 
     /// Get hooks for a specific event
@@ -251,20 +507,230 @@ impl HooksConfig {
         match event {
             HookEvent::OnClusterAvailable => &self.on_cluster_available,
             HookEvent::OnServicesDeployed => &self.on_services_deployed,
+            HookEvent::OnCommandFailed => &self.on_command_failed,
+            HookEvent::OnClusterDegraded => &self.on_cluster_degraded,
+            HookEvent::OnBeforeDestroy => &self.on_before_destroy,
+            // Each of these runs on its own schedule or files
+            HookEvent::Recurring | HookEvent::OnFileChange => &[],
+        }
+    }
+}
+
+/// A hook run when files under `paths` change, once they've been quiet for
+/// `debounce`. Relative paths are under the hook's `workdir`. `include` and
+/// `exclude` globs are matched against a file's path below the watched
+/// directory; `*` crosses directories.
+///
+/// ```yaml
+/// hooks:
+///   on_file_change:
+///     - name: Re-apply manifests
+///       command: kubectl apply -f k8s/
+///       workdir: ~/projects/myapp
+///       paths: [k8s]
+///       include: ["*.yaml", "*.yml"]   # default: every file
+///       exclude: ["*.swp"]
+///       debounce: 2s                   # default 1s
+/// ```
+#[derive(Debug, Clone, Deserialize)]
+#[serde(try_from = "RawFileChangeHook")]
+pub struct FileChangeHook {
+    pub hook: HookCommand,
+    pub paths: Vec<String>,
+    pub include: globset::GlobSet,
+    pub exclude: globset::GlobSet,
+    pub debounce: Duration,
+}
+
+impl FileChangeHook {
+    /// Whether a change to `relative` (below a watched directory) counts
+    pub fn matches(&self, relative: &Path) -> bool {
+        (self.include.is_empty() || self.include.is_match(relative))
+            && !self.exclude.is_match(relative)
+    }
+}
+
+#[derive(Deserialize)]
+struct RawFileChangeHook {
+    #[serde(flatten)]
+    hook: HookCommand,
+    paths: Vec<String>,
+    #[serde(default)]
+    include: Vec<String>,
+    #[serde(default)]
+    exclude: Vec<String>,
+    #[serde(
+        default = "default_file_change_debounce",
+        deserialize_with = "deser_duration"
+    )]
+    debounce: Duration,
+}
+
+fn default_file_change_debounce() -> Duration {
+    Duration::from_secs(1)
+}
+
+fn glob_set(hook: &str, globs: &[String]) -> Result<globset::GlobSet, String> {
+    let mut set = globset::GlobSetBuilder::new();
+    for glob in globs {
+        set.add(
+            globset::Glob::new(glob)
+                .map_err(|e| format!("hook '{}': invalid glob \"{}\": {}", hook, glob, e))?,
+        );
+    }
+    set.build().map_err(|e| format!("hook '{}': {}", hook, e))
+}
+
+impl TryFrom<RawFileChangeHook> for FileChangeHook {
+    type Error = String;
+
+    fn try_from(raw: RawFileChangeHook) -> Result<Self, Self::Error> {
+        if raw.paths.is_empty() {
+            return Err(format!("hook '{}' has no paths to watch", raw.hook.name));
         }
+        Ok(Self {
+            include: glob_set(&raw.hook.name, &raw.include)?,
+            exclude: glob_set(&raw.hook.name, &raw.exclude)?,
+            hook: raw.hook,
+            paths: raw.paths,
+            debounce: raw.debounce,
+        })
+    }
+}
+
+/// A hook the TUI runs while it's open, every `every` or on a `cron`
+/// schedule (minute hour day-of-month month day-of-week, in local time)
+///
+/// ```yaml
+/// hooks:
+///   recurring:
+///     - name: Refresh OAuth token
+///       command: ./scripts/refresh-token.sh
+///       every: 1h
+///     - name: Re-sync fixtures
+///       command: make fixtures
+///       cron: "0 3 * * *"
+/// ```
+#[derive(Debug, Clone, Deserialize)]
+#[serde(try_from = "RawRecurringHook")]
+pub struct RecurringHook {
+    pub hook: HookCommand,
+    pub schedule: HookSchedule,
+}
+
+/// When a recurring hook runs
+#[derive(Debug, Clone)]
+pub enum HookSchedule {
+    Every(Duration),
+    Cron(Box<croner::Cron>),
+}
+
+#[derive(Deserialize)]
+struct RawRecurringHook {
+    #[serde(flatten)]
+    hook: HookCommand,
+    #[serde(default)]
+    every: Option<String>,
+    #[serde(default)]
+    cron: Option<String>,
+}
+
+impl TryFrom<RawRecurringHook> for RecurringHook {
+    type Error = String;
+
+    fn try_from(raw: RawRecurringHook) -> Result<Self, Self::Error> {
+        let name = &raw.hook.name;
+        let schedule = match (raw.every, raw.cron) {
+            (Some(every), None) => HookSchedule::Every(
+                parse_duration_str(&every).map_err(|e| format!("hook '{}': {}", name, e))?,
+            ),
+            (None, Some(cron)) => {
+                HookSchedule::Cron(Box::new(croner::Cron::new(&cron).parse().map_err(|e| {
+                    format!("hook '{}': invalid cron \"{}\": {}", name, cron, e)
+                })?))
+            }
+            _ => {
+                return Err(format!(
+                    "recurring hook '{}' needs either every or cron",
+                    name
+                ))
+            }
+        };
+        Ok(Self {
+            hook: raw.hook,
+            schedule,
+        })
     }
 }
 
+/// A check that the deployed stack works, run after the
+/// `on_services_deployed` hooks. It passes once the URL answers or the
+/// command exits 0, and is retried until `timeout`.
+///
+/// ```yaml
+/// smoke_tests:
+///   - name: web answers
+///     url: http://app.local/health
+///     status: 200                # default: any 2xx
+///   - name: database accepts queries
+///     exec:
+///       target: { selector: app=postgres }
+///       cmd: pg_isready
+///     timeout: 2m                # default 60s
+/// ```
+#[derive(Debug, Clone, Deserialize)]
+pub struct SmokeTest {
+    pub name: String,
+
+    /// URL requested without following redirects
+    #[serde(default)]
+    pub url: String,
+
+    /// Status the URL must answer with; any 2xx when unset
+    #[serde(default)]
+    pub status: Option<u16>,
+
+    /// Command that must exit 0, in any target a command can use
+    #[serde(default)]
+    pub exec: Option<ExecConfig>,
+
+    #[serde(default = "default_smoke_timeout", deserialize_with = "deser_duration")]
+    pub timeout: Duration,
+}
+
+fn default_smoke_timeout() -> Duration {
+    Duration::from_secs(60)
+}
+
 /// Kubernetes client configuration (kubeconfig path and context)
 /// Note: This is separate from cluster::ClusterConfig which contains infrastructure settings.
 /// These values get merged into cluster::ClusterConfig at runtime.
-#[derive(Debug, Clone, Deserialize, Default)]
+#[derive(Debug, Clone, Deserialize)]
 pub struct K8sClientConfig {
     #[serde(default)]
     pub kubeconfig: String,
 
     #[serde(default)]
     pub context: String,
+
+    /// Namespace used by command targets, CLI commands and views that
+    /// don't name one
+    #[serde(default = "default_namespace")]
+    pub default_namespace: String,
+}
+
+fn default_namespace() -> String {
+    "default".to_string()
+}
+
+impl Default for K8sClientConfig {
+    fn default() -> Self {
+        Self {
+            kubeconfig: String::new(),
+            context: String::new(),
+            default_namespace: default_namespace(),
+        }
+    }
 }
 
 /// Infrastructure configuration
@@ -298,9 +764,73 @@ pub struct InfrastructureConfig {
     #[serde(default)]
     pub additional_ports: Vec<String>,
 
+    /// Run the k3s network-policy controller so NetworkPolicies are enforced.
+    /// Default: false - applies when the cluster container is created
+    #[serde(default)]
+    pub network_policy: bool,
+
+    /// Mesh installed by the "Install Service Mesh" action. Default: linkerd
+    #[serde(default)]
+    pub service_mesh: ServiceMesh,
+
+    /// Engine installed by the "Install Policy Engine" action. Default: kyverno
+    #[serde(default)]
+    pub policy_engine: PolicyEngine,
+
     /// Speedup optimizations configuration
     #[serde(default)]
     pub speedup: SpeedupConfig,
+
+    /// Extra StorageClasses and where local-path PVs are stored
+    #[serde(default)]
+    pub storage: StorageConfig,
+
+    /// Give each git branch its own cluster: the branch is appended to
+    /// `cluster_name` and prepended to `domain`. Default: false
+    #[serde(default)]
+    pub per_branch: bool,
+
+    /// Branch the names above were derived from (set by the loader)
+    #[serde(skip)]
+    pub branch: Option<BranchCluster>,
+}
+
+/// Service mesh for the mesh lab actions
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ServiceMesh {
+    #[default]
+    Linkerd,
+    /// Istio's minimal profile (istiod only, no gateways)
+    Istio,
+}
+
+impl ServiceMesh {
+    pub fn label(self) -> &'static str {
+        match self {
+            ServiceMesh::Linkerd => "Linkerd",
+            ServiceMesh::Istio => "Istio",
+        }
+    }
+}
+
+/// Admission policy engine for the policy testing actions
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PolicyEngine {
+    #[default]
+    Kyverno,
+    /// OPA Gatekeeper
+    Gatekeeper,
+}
+
+impl PolicyEngine {
+    pub fn label(self) -> &'static str {
+        match self {
+            PolicyEngine::Kyverno => "Kyverno",
+            PolicyEngine::Gatekeeper => "Gatekeeper",
+        }
+    }
 }
 
 /// Speedup optimization configuration
@@ -315,6 +845,12 @@ pub struct SpeedupConfig {
     /// Default: true - only keeps the current snapshot
     #[serde(default = "default_true")]
     pub snapshot_auto_cleanup: bool,
+
+    /// Keep a stopped clone of the cluster container created from the
+    /// snapshot, so a start after destroy skips container creation.
+    /// Default: false
+    #[serde(default)]
+    pub warm_standby: bool,
 }
 
 impl Default for SpeedupConfig {
@@ -322,6 +858,7 @@ impl Default for SpeedupConfig {
         Self {
             use_snapshot: true,
             snapshot_auto_cleanup: true,
+            warm_standby: false,
         }
     }
 }
@@ -330,6 +867,61 @@ fn default_true() -> bool {
     true
 }
 
+/// Storage configuration (`infrastructure.storage:`)
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct StorageConfig {
+    /// Host directory holding local-path PVs instead of a Docker volume.
+    /// Applies when the cluster container is created.
+    #[serde(default)]
+    pub host_path: Option<PathBuf>,
+
+    /// StorageClass marked as the cluster default. Default: k3s's `local-path`
+    #[serde(default)]
+    pub default_class: Option<String>,
+
+    /// StorageClasses created or updated at every start
+    #[serde(default)]
+    pub classes: Vec<StorageClassConfig>,
+}
+
+impl StorageConfig {
+    /// `host_path` with `~` expanded
+    pub fn host_path(&self) -> Option<PathBuf> {
+        self.host_path
+            .as_ref()
+            .map(|path| expand_home(path).unwrap_or_else(|_| path.clone()))
+    }
+}
+
+/// An extra StorageClass
+#[derive(Debug, Clone, Deserialize)]
+pub struct StorageClassConfig {
+    pub name: String,
+
+    /// Default: rancher.io/local-path (k3s's provisioner)
+    #[serde(default = "default_storage_provisioner")]
+    pub provisioner: String,
+
+    /// Delete | Retain. Default: Delete
+    #[serde(default)]
+    pub reclaim_policy: Option<String>,
+
+    /// WaitForFirstConsumer | Immediate. Default: WaitForFirstConsumer
+    #[serde(default)]
+    pub volume_binding_mode: Option<String>,
+
+    #[serde(default)]
+    pub allow_volume_expansion: bool,
+
+    /// Provisioner parameters, passed as-is
+    #[serde(default)]
+    pub parameters: BTreeMap<String, String>,
+}
+
+fn default_storage_provisioner() -> String {
+    "rancher.io/local-path".to_string()
+}
+
 impl InfrastructureConfig {
     /// Get container name derived from cluster name
     pub fn container_name(&self) -> String {
@@ -377,7 +969,13 @@ impl Default for InfrastructureConfig {
             http_port: default_http_port(),
             https_port: default_https_port(),
             additional_ports: vec!["2345:2345".to_string(), "8309:8309".to_string()],
+            network_policy: false,
+            service_mesh: ServiceMesh::default(),
+            policy_engine: PolicyEngine::default(),
             speedup: SpeedupConfig::default(),
+            storage: StorageConfig::default(),
+            per_branch: false,
+            branch: None,
         }
     }
 }
@@ -390,8 +988,48 @@ pub struct CommandGroup {
     #[serde(default)]
     pub icon: String,
 
+    /// Tags every command of the group inherits
+    #[serde(default)]
+    pub tags: Vec<String>,
+
     #[serde(default)]
     pub commands: Vec<CommandEntry>,
+
+    /// Catalog the group was merged from; `None` for the config's own groups
+    #[serde(skip)]
+    pub source: Option<String>,
+}
+
+/// A shared command catalog in a git repository.
+///
+/// ```yaml
+/// commands_source:
+///   - git+https://github.com/acme/platform-commands.git#main
+///   - url: git+ssh://git@gitlab.example.com/team/catalog.git
+///     ref: v2
+///     path: k3dev/commands.yml
+/// ```
+#[derive(Debug, Clone, Deserialize, PartialEq, Eq)]
+#[serde(untagged)]
+pub enum CommandSource {
+    /// `git+<url>[#ref]`, reading `k3dev-commands.yml`
+    Url(String),
+    Detailed {
+        url: String,
+        #[serde(default, rename = "ref")]
+        git_ref: Option<String>,
+        /// Catalog file inside the repository
+        #[serde(default)]
+        path: Option<String>,
+    },
+}
+
+impl std::fmt::Display for CommandSource {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            CommandSource::Url(url) | CommandSource::Detailed { url, .. } => f.write_str(url),
+        }
+    }
 }
 
 /// A single executable command or submenu
@@ -403,32 +1041,168 @@ pub struct CommandEntry {
     #[serde(default)]
     pub description: Option<String>,
 
+    /// Shown before the name in the menu and the palette
+    #[serde(default)]
+    pub icon: Option<String>,
+
+    /// Theme color the name is drawn in
+    #[serde(default)]
+    pub color: Option<CommandColor>,
+
     #[serde(default)]
     pub exec: Option<ExecConfig>,
 
     #[serde(default)]
     pub commands: Vec<CommandEntry>,
 
+    /// Roles or flags the command needs enabled, e.g. `[dba, danger]`
+    #[serde(default)]
+    pub tags: Vec<String>,
+
     /// Optional visibility gate — entry is hidden until the check passes.
     #[serde(default)]
     pub visible: Option<Visible>,
-}
-
-/// How to execute a command
-#[derive(Debug, Clone, Deserialize)]
-pub struct ExecConfig {
-    pub target: ExecutionTarget,
 
+    /// How the exit code and output decide whether the command succeeded
     #[serde(default)]
-    pub workdir: String,
+    pub success: SuccessCriteria,
 
-    pub cmd: String,
+    /// Whether the command waits for other commands or runs next to them
+    #[serde(default)]
+    pub concurrency: Concurrency,
 
+    /// Overrides of the `output:` limits for this command
     #[serde(default)]
-    pub input: HashMap<String, InputDefinition>,
+    pub output: OutputOverride,
 }
 
-/// Definition for one runtime input prompt.
+impl CommandEntry {
+    /// A host shell command built by k3dev itself (addon installs and the like)
+    pub fn host(name: impl Into<String>, cmd: impl Into<String>) -> Self {
+        Self {
+            name: name.into(),
+            description: None,
+            icon: None,
+            color: None,
+            exec: Some(ExecConfig {
+                target: ExecutionTarget::Host,
+                workdir: String::new(),
+                cmd: cmd.into(),
+                input: HashMap::new(),
+            }),
+            commands: Vec::new(),
+            tags: Vec::new(),
+            visible: None,
+            success: SuccessCriteria::default(),
+            concurrency: Concurrency::default(),
+            output: OutputOverride::default(),
+        }
+    }
+
+    /// Tagged `danger` or `destructive`
+    pub fn is_destructive(&self) -> bool {
+        self.tags
+            .iter()
+            .any(|t| t == "danger" || t == "destructive")
+    }
+
+    /// The configured icon, else (with `auto`) one for what the command
+    /// runs in. Submenus only get a configured one.
+    pub fn display_icon(&self, auto: bool) -> Option<String> {
+        if let Some(icon) = &self.icon {
+            return Some(icon.clone());
+        }
+        let exec = self.exec.as_ref().filter(|_| auto)?;
+        let icon = if self.is_destructive() {
+            "⚠"
+        } else {
+            match exec.target {
+                ExecutionTarget::Host | ExecutionTarget::Script | ExecutionTarget::Lua => "❯",
+                ExecutionTarget::Docker { .. } => "◆",
+                ExecutionTarget::Kubernetes { .. } => "☸",
+            }
+        };
+        Some(icon.to_string())
+    }
+
+    /// The configured color, else `danger` for destructive commands
+    pub fn display_color(&self) -> Option<CommandColor> {
+        self.color
+            .or_else(|| self.is_destructive().then_some(CommandColor::Danger))
+    }
+}
+
+/// Color hint for a command, drawn with the theme's matching color
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum CommandColor {
+    Primary,
+    Success,
+    Warning,
+    Danger,
+    Muted,
+}
+
+/// How a command started while others run is scheduled
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Concurrency {
+    /// Wait until the commands started before it are done
+    #[default]
+    Queue,
+    /// Start right away, next to whatever is running
+    Parallel,
+}
+
+/// Success criteria for a command.
+///
+/// ```yaml
+/// success:
+///   exit_codes: [0, 3]          # default [0]
+///   error_patterns: ["^ERROR"]  # any matching line fails the command
+///   warning_patterns: ["WARN"]  # matching lines are highlighted and counted
+/// ```
+#[derive(Debug, Clone, Deserialize)]
+pub struct SuccessCriteria {
+    #[serde(default = "default_success_exit_codes")]
+    pub exit_codes: Vec<i32>,
+
+    #[serde(default)]
+    pub error_patterns: Vec<String>,
+
+    #[serde(default)]
+    pub warning_patterns: Vec<String>,
+}
+
+fn default_success_exit_codes() -> Vec<i32> {
+    vec![0]
+}
+
+impl Default for SuccessCriteria {
+    fn default() -> Self {
+        Self {
+            exit_codes: default_success_exit_codes(),
+            error_patterns: Vec::new(),
+            warning_patterns: Vec::new(),
+        }
+    }
+}
+
+/// How to execute a command
+#[derive(Debug, Clone, Deserialize)]
+pub struct ExecConfig {
+    pub target: ExecutionTarget,
+
+    #[serde(default)]
+    pub workdir: String,
+
+    pub cmd: String,
+
+    #[serde(default)]
+    pub input: HashMap<String, InputDefinition>,
+}
+
+/// Definition for one runtime input prompt.
 ///
 /// Two YAML shapes are accepted:
 /// - bare string — shorthand for a plain text prompt
@@ -466,12 +1240,44 @@ pub enum InputSpec {
     },
 }
 
+impl InputDefinition {
+    /// Text the input form asks with
+    pub fn prompt(&self) -> &str {
+        match self {
+            InputDefinition::Prompt(prompt)
+            | InputDefinition::Detailed(InputSpec::Text { prompt, .. })
+            | InputDefinition::Detailed(InputSpec::Select { prompt, .. })
+            | InputDefinition::Detailed(InputSpec::MultiSelect { prompt, .. }) => prompt,
+        }
+    }
+
+    /// Default value used when the input is not prompted for (e.g. headless runs).
+    /// Selects fall back to their first option and multi-select defaults are
+    /// joined with spaces, matching the input form.
+    pub fn default_value(&self) -> Option<String> {
+        match self {
+            InputDefinition::Prompt(_) => None,
+            InputDefinition::Detailed(InputSpec::Text { default, .. }) => {
+                Some(default.clone()).filter(|d| !d.is_empty())
+            }
+            InputDefinition::Detailed(InputSpec::Select {
+                default, options, ..
+            }) => default.clone().or_else(|| options.first().cloned()),
+            InputDefinition::Detailed(InputSpec::MultiSelect { default, .. }) => {
+                Some(default.join(" ")).filter(|d| !d.is_empty())
+            }
+        }
+    }
+}
+
 /// Where a command runs.
 ///
 /// Tagged YAML representation; if `type:` is omitted, defaults to `kubernetes`:
 /// ```yaml
 /// target: { type: host }
 /// target: { type: docker, container: "k3dev-server" }
+/// target: { type: script }
+/// target: { type: lua }
 /// target: { type: kubernetes, namespace: "default", selector: "app=foo" }
 /// target: { namespace: "default", selector: "app=foo" }   # implicit kubernetes
 /// ```
@@ -479,6 +1285,12 @@ pub enum InputSpec {
 pub enum ExecutionTarget {
     /// Run on the user's host shell.
     Host,
+    /// Run on the host with the script API: JSON calls on stdout are
+    /// answered on stdin (see `commands::script`). Not sandboxed.
+    Script,
+    /// Run `cmd` as Lua inside k3dev, sandboxed, with the same API as a
+    /// `k3dev` table (see `commands::lua`).
+    Lua,
     /// `docker exec` into a named container.
     Docker { container: String },
     /// `kubectl exec` style — into a pod located by selector or name.
@@ -497,6 +1309,8 @@ pub enum ExecutionTarget {
 #[serde(tag = "type", rename_all = "lowercase")]
 enum ExecutionTargetTagged {
     Host,
+    Script,
+    Lua,
     Docker {
         #[serde(default)]
         container: String,
@@ -517,6 +1331,8 @@ impl From<ExecutionTargetTagged> for ExecutionTarget {
     fn from(t: ExecutionTargetTagged) -> Self {
         match t {
             ExecutionTargetTagged::Host => ExecutionTarget::Host,
+            ExecutionTargetTagged::Script => ExecutionTarget::Script,
+            ExecutionTargetTagged::Lua => ExecutionTarget::Lua,
             ExecutionTargetTagged::Docker { container } => ExecutionTarget::Docker { container },
             ExecutionTargetTagged::Kubernetes {
                 namespace,
@@ -616,12 +1432,75 @@ pub struct InfoBlock {
     /// Optional visibility gate — block is hidden until the check passes.
     #[serde(default)]
     pub visible: Option<Visible>,
+
+    /// Colour the output by the first number in it (queue depth, workers...)
+    #[serde(default)]
+    pub thresholds: Option<Thresholds>,
 }
 
 fn default_info_block_interval() -> Duration {
     Duration::from_secs(30)
 }
 
+/// Levels an info block's numeric output is judged against.
+///
+/// ```yaml
+/// thresholds: { warn: 100, critical: 1000 }        # higher is worse
+/// thresholds: { warn: 2, critical: 0, below: true } # lower is worse
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Deserialize)]
+pub struct Thresholds {
+    #[serde(default)]
+    pub warn: Option<f64>,
+
+    #[serde(default)]
+    pub critical: Option<f64>,
+
+    /// Values at or below the levels are bad, instead of at or above
+    #[serde(default)]
+    pub below: bool,
+}
+
+/// How a value compares to its thresholds
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ThresholdLevel {
+    Ok,
+    Warn,
+    Critical,
+}
+
+impl Thresholds {
+    pub fn level(&self, value: f64) -> ThresholdLevel {
+        let reached = |level: Option<f64>| {
+            level.is_some_and(|l| if self.below { value <= l } else { value >= l })
+        };
+        if reached(self.critical) {
+            ThresholdLevel::Critical
+        } else if reached(self.warn) {
+            ThresholdLevel::Warn
+        } else {
+            ThresholdLevel::Ok
+        }
+    }
+
+    /// Level of the first number in `output`; None when it has none
+    pub fn judge(&self, output: &str) -> Option<ThresholdLevel> {
+        output
+            .split(|c: char| !(c.is_ascii_digit() || c == '.' || c == '-'))
+            .find_map(|word| word.parse::<f64>().ok())
+            .map(|value| self.level(value))
+    }
+
+    /// Whether `warn` comes before `critical` in the direction values worsen
+    pub fn ordered(&self) -> bool {
+        match (self.warn, self.critical) {
+            (Some(warn), Some(critical)) if self.below => warn >= critical,
+            (Some(warn), Some(critical)) => warn <= critical,
+            _ => true,
+        }
+    }
+}
+
 fn default_visible_interval() -> Duration {
     Duration::from_secs(5)
 }
@@ -872,6 +1751,753 @@ fn default_capture_iface() -> String {
     "any".to_string()
 }
 
+/// kube-system components a cluster start can wait for
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum ReadinessGate {
+    /// Cluster DNS; hooks that resolve service names need it
+    Coredns,
+    /// Dynamic PersistentVolume provisioning
+    LocalPathProvisioner,
+}
+
+impl ReadinessGate {
+    pub fn all() -> Vec<ReadinessGate> {
+        vec![ReadinessGate::Coredns, ReadinessGate::LocalPathProvisioner]
+    }
+
+    /// Deployment name in kube-system (also the config name)
+    pub fn name(&self) -> &'static str {
+        match self {
+            ReadinessGate::Coredns => "coredns",
+            ReadinessGate::LocalPathProvisioner => "local-path-provisioner",
+        }
+    }
+}
+
+/// Cluster start settings
+#[derive(Debug, Clone, Deserialize)]
+pub struct StartConfig {
+    /// Components start waits for before the cluster counts as available.
+    /// The rest are tracked in the background. Default: none, so control
+    /// returns as soon as the API answers.
+    #[serde(default)]
+    pub wait_for: Vec<ReadinessGate>,
+
+    /// How long to wait for each component (duration string, e.g. "60s")
+    #[serde(default = "default_ready_timeout", deserialize_with = "deser_duration")]
+    pub ready_timeout: Duration,
+
+    /// Hold the ingress ports while the cluster is down and start it on the
+    /// first connection (TUI only)
+    #[serde(default)]
+    pub on_demand: bool,
+}
+
+impl Default for StartConfig {
+    fn default() -> Self {
+        Self {
+            wait_for: Vec::new(),
+            ready_timeout: default_ready_timeout(),
+            on_demand: false,
+        }
+    }
+}
+
+fn default_ready_timeout() -> Duration {
+    Duration::from_secs(60)
+}
+
+/// Local HTTP API settings
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct ApiConfig {
+    /// Address to serve on, e.g. "127.0.0.1:7373". Empty: no API server.
+    #[serde(default)]
+    pub listen: String,
+
+    /// Bearer token every request must send. Default: none
+    #[serde(default)]
+    pub token: Option<String>,
+}
+
+/// Host directory mounts
+#[derive(Debug, Clone, Deserialize)]
+pub struct HostMountsConfig {
+    /// Only directories inside these may be mounted. Default: the home directory
+    #[serde(default = "default_allowed_roots")]
+    pub allowed_roots: Vec<String>,
+}
+
+impl HostMountsConfig {
+    /// `allowed_roots` with `~` expanded
+    pub fn roots(&self) -> Vec<PathBuf> {
+        self.allowed_roots
+            .iter()
+            .map(|root| expand_home(Path::new(root)).unwrap_or_else(|_| PathBuf::from(root)))
+            .collect()
+    }
+}
+
+impl Default for HostMountsConfig {
+    fn default() -> Self {
+        Self {
+            allowed_roots: default_allowed_roots(),
+        }
+    }
+}
+
+fn default_allowed_roots() -> Vec<String> {
+    vec!["~".to_string()]
+}
+
+/// An image built with `docker buildx build`.
+///
+/// ```yaml
+/// builds:
+///   - name: api
+///     context: ./services/api
+///     dockerfile: Dockerfile.dev     # relative to context
+///     tag: api:dev
+///     target: dev
+///     args: { RUST_VERSION: "1.85" }
+///     secrets: ["id=npmrc,src=~/.npmrc"]
+///     ssh: ["default"]
+///     cache_from: ["registry.example.com/api:main"]
+/// ```
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct BuildConfig {
+    pub name: String,
+
+    /// Build context directory. Default: the directory k3dev runs in
+    #[serde(default = "default_build_context")]
+    pub context: String,
+
+    /// Dockerfile relative to the context. Default: `Dockerfile`
+    #[serde(default)]
+    pub dockerfile: Option<String>,
+
+    /// Tag of the built image, loaded into the Docker daemon k3s runs on
+    pub tag: String,
+
+    /// Stage to build
+    #[serde(default)]
+    pub target: Option<String>,
+
+    /// `--build-arg` values
+    #[serde(default)]
+    pub args: BTreeMap<String, String>,
+
+    /// `--secret` specs, e.g. "id=npmrc,src=~/.npmrc"
+    #[serde(default)]
+    pub secrets: Vec<String>,
+
+    /// `--ssh` specs, e.g. "default" to forward the SSH agent
+    #[serde(default)]
+    pub ssh: Vec<String>,
+
+    /// Images whose inline build cache is reused
+    #[serde(default)]
+    pub cache_from: Vec<String>,
+
+    /// Embed cache metadata in the image so it can be a `cache_from` source
+    #[serde(default = "default_true")]
+    pub inline_cache: bool,
+}
+
+fn default_build_context() -> String {
+    ".".to_string()
+}
+
+/// ImagePullSecret provisioning.
+///
+/// ```yaml
+/// pull_secret:
+///   name: k3dev-registry
+///   registries: ["ghcr.io"]      # default: every registry docker login knows
+///   docker_config: ~/.docker/config.json
+/// ```
+#[derive(Debug, Clone, Deserialize)]
+pub struct PullSecretConfig {
+    /// Name of the Secret created in each namespace
+    #[serde(default = "default_pull_secret_name")]
+    pub name: String,
+
+    /// Registries whose credentials go into the Secret. Default: all of them
+    #[serde(default)]
+    pub registries: Vec<String>,
+
+    /// Docker CLI config to read. Default: `$DOCKER_CONFIG/config.json`, else
+    /// `~/.docker/config.json`
+    #[serde(default)]
+    pub docker_config: Option<String>,
+}
+
+impl Default for PullSecretConfig {
+    fn default() -> Self {
+        Self {
+            name: default_pull_secret_name(),
+            registries: Vec::new(),
+            docker_config: None,
+        }
+    }
+}
+
+fn default_pull_secret_name() -> String {
+    "k3dev-registry".to_string()
+}
+
+/// Seed data bundle, loaded into a pod with "Load Seed: <name>".
+///
+/// ```yaml
+/// seeds:
+///   - name: demo
+///     namespace: default               # default: cluster.default_namespace
+///     selector: app=postgres           # or pod_name
+///     container: postgres
+///     sql: [seeds/schema.sql, seeds/demo.sql]
+///     sql_cmd: psql -U postgres -d app -v ON_ERROR_STOP=1
+///     commands: ["/app/manage.py loaddata demo.json"]
+/// ```
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct SeedConfig {
+    pub name: String,
+
+    #[serde(default)]
+    pub description: Option<String>,
+
+    #[serde(default)]
+    pub namespace: String,
+
+    #[serde(default)]
+    pub selector: String,
+
+    #[serde(default)]
+    pub pod_name: String,
+
+    #[serde(default)]
+    pub container: String,
+
+    /// Host files piped, in order, into `sql_cmd` in the pod. Relative paths
+    /// are resolved from the directory k3dev runs in
+    #[serde(default)]
+    pub sql: Vec<String>,
+
+    /// Command in the pod that reads SQL on stdin
+    #[serde(default = "default_seed_sql_cmd")]
+    pub sql_cmd: String,
+
+    /// Fixture commands run in the pod after the SQL files
+    #[serde(default)]
+    pub commands: Vec<String>,
+}
+
+fn default_seed_sql_cmd() -> String {
+    "psql -v ON_ERROR_STOP=1".to_string()
+}
+
+/// Chaos tool settings.
+///
+/// ```yaml
+/// chaos:
+///   pod_killer:
+///     namespace: shop
+///     selector: app=api
+///     interval: 30s
+/// ```
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct ChaosConfig {
+    /// Pods the Toggle Pod Killer action kills; unset disables it
+    #[serde(default)]
+    pub pod_killer: Option<PodKillerConfig>,
+}
+
+/// Which pods the pod killer picks from, and how often
+#[derive(Debug, Clone, Deserialize)]
+pub struct PodKillerConfig {
+    /// Namespace of the pods; empty uses `cluster.default_namespace`
+    #[serde(default)]
+    pub namespace: String,
+
+    /// Label selector the pods must match
+    #[serde(default)]
+    pub selector: String,
+
+    /// Time between kills (duration string, e.g. "30s")
+    #[serde(
+        default = "default_pod_killer_interval",
+        deserialize_with = "deser_duration"
+    )]
+    pub interval: Duration,
+}
+
+fn default_pod_killer_interval() -> Duration {
+    Duration::from_secs(60)
+}
+
+/// Cluster watchdog settings.
+///
+/// ```yaml
+/// watchdog:
+///   auto_restart: true
+///   api_failures: 3
+///   max_backoff: 5m
+/// ```
+#[derive(Debug, Clone, Deserialize)]
+pub struct WatchdogConfig {
+    /// Restart the cluster when it dies; otherwise only report it
+    #[serde(default)]
+    pub auto_restart: bool,
+
+    /// Failed API health checks in a row that make the cluster hung
+    #[serde(default = "default_watchdog_api_failures")]
+    pub api_failures: u32,
+
+    /// Longest wait between restarts (duration string)
+    #[serde(
+        default = "default_watchdog_max_backoff",
+        deserialize_with = "deser_duration"
+    )]
+    pub max_backoff: Duration,
+}
+
+impl Default for WatchdogConfig {
+    fn default() -> Self {
+        Self {
+            auto_restart: false,
+            api_failures: default_watchdog_api_failures(),
+            max_backoff: default_watchdog_max_backoff(),
+        }
+    }
+}
+
+fn default_watchdog_api_failures() -> u32 {
+    3
+}
+
+fn default_watchdog_max_backoff() -> Duration {
+    Duration::from_secs(300)
+}
+
+/// SSH remote forwards for sharing an ingress port.
+///
+/// ```yaml
+/// share:
+///   host: demo.example.com
+///   user: deploy
+///   remote_port: 8080
+/// ```
+#[derive(Debug, Clone, Deserialize)]
+pub struct ShareConfig {
+    /// SSH host the port is published on; sharing is off while empty
+    #[serde(default)]
+    pub host: String,
+
+    /// Login on that host; empty uses ssh's own default
+    #[serde(default)]
+    pub user: String,
+
+    /// Port the host listens on for the teammate
+    #[serde(default = "default_share_remote_port")]
+    pub remote_port: u16,
+}
+
+impl Default for ShareConfig {
+    fn default() -> Self {
+        Self {
+            host: String::new(),
+            user: String::new(),
+            remote_port: default_share_remote_port(),
+        }
+    }
+}
+
+fn default_share_remote_port() -> u16 {
+    8080
+}
+
+/// Public tunnels to an ingress host.
+///
+/// ```yaml
+/// tunnel:
+///   provider: ngrok   # cloudflared (default) or ngrok
+/// ```
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct TunnelConfig {
+    #[serde(default)]
+    pub provider: TunnelProvider,
+}
+
+/// Tunnel client run for Open Public Tunnel; it must be on PATH
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TunnelProvider {
+    /// A Cloudflare quick tunnel on a random trycloudflare.com name
+    #[default]
+    Cloudflared,
+    /// ngrok with the authtoken from its own config
+    Ngrok,
+}
+
+/// How ingress URLs are opened.
+///
+/// ```yaml
+/// browser:
+///   command: firefox
+///   args: ["-P", "testing"]
+///   private: true
+///   hosts:
+///     admin.local:
+///       command: chromium
+/// ```
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct BrowserConfig {
+    /// Browser for every host without an override
+    #[serde(flatten)]
+    pub launch: BrowserLaunch,
+
+    /// Browsers for single hosts, used in place of the one above
+    #[serde(default)]
+    pub hosts: HashMap<String, BrowserLaunch>,
+}
+
+/// One way to start a browser on a URL
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct BrowserLaunch {
+    /// Browser binary; empty uses the system opener (xdg-open, open)
+    #[serde(default)]
+    pub command: String,
+
+    /// Arguments before the URL, e.g. a profile
+    #[serde(default)]
+    pub args: Vec<String>,
+
+    /// Open a private/incognito window
+    #[serde(default)]
+    pub private: bool,
+}
+
+/// Terminal multiplexer integration.
+///
+/// ```yaml
+/// multiplexer:
+///   open_in: window   # pane (default) or window
+/// ```
+#[derive(Debug, Clone, Default, Deserialize)]
+pub struct MultiplexerConfig {
+    #[serde(default)]
+    pub open_in: PaneTarget,
+}
+
+/// What a multiplexer opens for a pod shell or log follow
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PaneTarget {
+    /// A split next to the k3dev pane
+    #[default]
+    Pane,
+    /// A tmux window; zellij runs it in a floating pane
+    Window,
+}
+
+/// Output buffer limits.
+///
+/// ```yaml
+/// output:
+///   max_lines: 10000   # lines kept per command, older ones are dropped
+///   spill: true        # append dropped lines to a file in spill_dir
+/// ```
+#[derive(Debug, Clone, Deserialize)]
+pub struct OutputConfig {
+    #[serde(default = "default_output_max_lines")]
+    pub max_lines: usize,
+
+    #[serde(default)]
+    pub spill: bool,
+
+    /// Default: `<XDG_DATA_HOME>/k3dev/output`
+    #[serde(default = "default_output_spill_dir")]
+    pub spill_dir: PathBuf,
+}
+
+impl OutputConfig {
+    /// Limit of a command's output; a spilling command gets a file named
+    /// after it and the time it started
+    pub fn limit_for(&self, name: &str, overrides: &OutputOverride) -> OutputLimit {
+        let spill = overrides.spill.unwrap_or(self.spill).then(|| {
+            let stamp = chrono::Local::now().format("%Y%m%d-%H%M%S");
+            expand_home(&self.spill_dir)
+                .unwrap_or_else(|_| self.spill_dir.clone())
+                .join(format!("{}-{}.log", branch_slug(name), stamp))
+        });
+        OutputLimit {
+            max_lines: overrides.max_lines.unwrap_or(self.max_lines),
+            spill,
+        }
+    }
+
+    /// Limit of output that isn't a command's, which is never spilled
+    pub fn default_limit(&self) -> OutputLimit {
+        OutputLimit {
+            max_lines: self.max_lines,
+            spill: None,
+        }
+    }
+}
+
+impl Default for OutputConfig {
+    fn default() -> Self {
+        Self {
+            max_lines: default_output_max_lines(),
+            spill: false,
+            spill_dir: default_output_spill_dir(),
+        }
+    }
+}
+
+fn default_output_max_lines() -> usize {
+    DEFAULT_MAX_LINES
+}
+
+fn default_output_spill_dir() -> PathBuf {
+    dirs::data_local_dir()
+        .map(|d| d.join("k3dev").join("output"))
+        .unwrap_or_else(|| std::env::temp_dir().join("k3dev-output"))
+}
+
+/// Session transcripts.
+///
+/// ```yaml
+/// transcript:
+///   enabled: true
+///   format: asciicast   # plain (default) or asciicast
+///   dir: ~/k3dev-sessions
+/// ```
+#[derive(Debug, Clone, Deserialize)]
+pub struct TranscriptConfig {
+    #[serde(default)]
+    pub enabled: bool,
+
+    #[serde(default)]
+    pub format: TranscriptFormat,
+
+    /// Default: `<XDG_DATA_HOME>/k3dev/transcripts`
+    #[serde(default = "default_transcript_dir")]
+    pub dir: PathBuf,
+}
+
+impl Default for TranscriptConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            format: TranscriptFormat::default(),
+            dir: default_transcript_dir(),
+        }
+    }
+}
+
+fn default_transcript_dir() -> PathBuf {
+    dirs::data_local_dir()
+        .map(|d| d.join("k3dev").join("transcripts"))
+        .unwrap_or_else(|| std::env::temp_dir().join("k3dev-transcripts"))
+}
+
+/// File format of a session transcript
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum TranscriptFormat {
+    /// Timestamped lines, as in the output panel
+    #[default]
+    Plain,
+    /// asciinema's asciicast v2, replayable with `asciinema play`
+    Asciicast,
+}
+
+/// A command's `output:` settings; unset fields fall back to the top level
+#[derive(Debug, Clone, Copy, Default, Deserialize)]
+pub struct OutputOverride {
+    #[serde(default)]
+    pub max_lines: Option<usize>,
+
+    #[serde(default)]
+    pub spill: Option<bool>,
+}
+
+/// Ingress health check settings
+#[derive(Debug, Clone, Deserialize)]
+pub struct IngressHealthConfig {
+    /// Endpoints checked at the same time
+    #[serde(default = "default_health_concurrency")]
+    pub concurrency: usize,
+
+    /// Longest wait before re-checking a failing endpoint (duration string)
+    #[serde(
+        default = "default_health_max_backoff",
+        deserialize_with = "deser_duration"
+    )]
+    pub max_backoff: Duration,
+
+    /// Random spread of backoff delays, 0.0-1.0
+    #[serde(default = "default_health_jitter")]
+    pub jitter: f64,
+
+    /// Response times ingress paths are expected to stay under
+    #[serde(default)]
+    pub latency_budgets: Vec<LatencyBudget>,
+
+    /// Where the checks are made from
+    #[serde(default)]
+    pub probe: HealthProbe,
+}
+
+impl IngressHealthConfig {
+    /// Budget of an ingress path: the first entry for its path and host
+    pub fn latency_budget(&self, host: &str, path: &str) -> Option<Duration> {
+        self.latency_budgets
+            .iter()
+            .find(|b| b.path == path && (b.host.is_empty() || b.host == host))
+            .map(|b| b.budget)
+    }
+}
+
+impl Default for IngressHealthConfig {
+    fn default() -> Self {
+        Self {
+            concurrency: default_health_concurrency(),
+            max_backoff: default_health_max_backoff(),
+            jitter: default_health_jitter(),
+            latency_budgets: Vec::new(),
+            probe: HealthProbe::default(),
+        }
+    }
+}
+
+/// Where ingress health checks are made from
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum HealthProbe {
+    /// Requests from this machine, through /etc/hosts
+    #[default]
+    Host,
+    /// Requests from inside the Traefik pod, sending the Host header
+    Cluster,
+}
+
+/// Expected response time of an ingress path.
+///
+/// ```yaml
+/// ingress_health:
+///   latency_budgets:
+///     - path: /api/orders
+///       host: shop.local.k8s.dev   # optional, any host when unset
+///       budget: 150ms
+/// ```
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct LatencyBudget {
+    pub path: String,
+
+    #[serde(default)]
+    pub host: String,
+
+    #[serde(deserialize_with = "deser_duration")]
+    pub budget: Duration,
+}
+
+fn default_health_concurrency() -> usize {
+    4
+}
+
+fn default_health_max_backoff() -> Duration {
+    Duration::from_secs(300)
+}
+
+fn default_health_jitter() -> f64 {
+    0.2
+}
+
+/// Cluster events that can raise a toast notification
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum NotificationEvent {
+    /// A pod container entered CrashLoopBackOff
+    PodCrashLoop,
+    /// A PersistentVolumeClaim became Bound
+    PvcBound,
+    /// An ingress host became healthy
+    IngressHealthy,
+    /// A lifecycle hook event finished running
+    HookFinished,
+    /// The cluster changed lifecycle state (started, stopped, degraded)
+    ClusterState,
+    /// The live Traefik config stopped matching k3dev's
+    TraefikDrift,
+}
+
+impl NotificationEvent {
+    pub fn all() -> Vec<NotificationEvent> {
+        vec![
+            NotificationEvent::PodCrashLoop,
+            NotificationEvent::PvcBound,
+            NotificationEvent::IngressHealthy,
+            NotificationEvent::HookFinished,
+            NotificationEvent::ClusterState,
+            NotificationEvent::TraefikDrift,
+        ]
+    }
+}
+
+/// Toast notification settings
+#[derive(Debug, Clone, Deserialize)]
+pub struct NotificationsConfig {
+    /// Show toasts at all
+    #[serde(default = "default_notifications_enabled")]
+    pub enabled: bool,
+
+    /// Events that raise a toast. Default: all of them.
+    #[serde(default = "NotificationEvent::all")]
+    pub events: Vec<NotificationEvent>,
+
+    /// How long a toast stays on screen (duration string, e.g. "5s")
+    #[serde(
+        default = "default_toast_duration",
+        deserialize_with = "deser_duration"
+    )]
+    pub duration: Duration,
+
+    /// Number of notifications kept for the palette history
+    #[serde(default = "default_notification_history_size")]
+    pub history_size: usize,
+}
+
+impl NotificationsConfig {
+    /// Whether a toast should be shown for the given event
+    pub fn allows(&self, event: NotificationEvent) -> bool {
+        self.enabled && self.events.contains(&event)
+    }
+}
+
+impl Default for NotificationsConfig {
+    fn default() -> Self {
+        Self {
+            enabled: default_notifications_enabled(),
+            events: NotificationEvent::all(),
+            duration: default_toast_duration(),
+            history_size: default_notification_history_size(),
+        }
+    }
+}
+
+fn default_notifications_enabled() -> bool {
+    true
+}
+
+fn default_toast_duration() -> Duration {
+    Duration::from_secs(5)
+}
+
+fn default_notification_history_size() -> usize {
+    100
+}
+
 fn default_log_file() -> String {
     let tmp = std::env::temp_dir();
     format!("{}/k3dev-{{cluster_name}}.log", tmp.display())
@@ -885,6 +2511,75 @@ fn default_log_level() -> String {
 mod tests {
     use super::*;
 
+    #[test]
+    fn thresholds_judge_the_first_number() {
+        let depth = Thresholds {
+            warn: Some(100.0),
+            critical: Some(1000.0),
+            below: false,
+        };
+        assert_eq!(depth.judge("12 messages"), Some(ThresholdLevel::Ok));
+        assert_eq!(depth.judge("lag: 100"), Some(ThresholdLevel::Warn));
+        assert_eq!(depth.judge("1500.5\n3"), Some(ThresholdLevel::Critical));
+        assert_eq!(depth.judge("pending"), None);
+
+        let workers = Thresholds {
+            warn: Some(2.0),
+            critical: Some(0.0),
+            below: true,
+        };
+        assert_eq!(workers.judge("4"), Some(ThresholdLevel::Ok));
+        assert_eq!(workers.judge("1 running"), Some(ThresholdLevel::Warn));
+        assert_eq!(workers.judge("0"), Some(ThresholdLevel::Critical));
+        assert!(workers.ordered() && depth.ordered());
+    }
+
+    #[test]
+    fn recurring_hooks_need_one_schedule() {
+        let hooks: HooksConfig = serde_yml::from_str(
+            "recurring:\n  - { name: token, command: ./refresh.sh, every: 1h }\n  - { name: fixtures, command: make fixtures, cron: '0 3 * * *' }\n",
+        )
+        .unwrap();
+        assert!(matches!(
+            hooks.recurring[0].schedule,
+            HookSchedule::Every(d) if d == Duration::from_secs(3600)
+        ));
+        assert!(matches!(hooks.recurring[1].schedule, HookSchedule::Cron(_)));
+        assert_eq!(hooks.recurring[1].hook.timeout, 300);
+
+        for invalid in [
+            "{ name: a, command: x }",
+            "{ name: a, command: x, every: 1h, cron: '@daily' }",
+            "{ name: a, command: x, cron: '61 * * * *' }",
+        ] {
+            let yaml = format!("recurring: [{}]", invalid);
+            assert!(
+                serde_yml::from_str::<HooksConfig>(&yaml).is_err(),
+                "{}",
+                yaml
+            );
+        }
+    }
+
+    #[test]
+    fn file_change_globs_filter_paths() {
+        let hooks: HooksConfig = serde_yml::from_str(
+            "on_file_change:\n  - { name: apply, command: make apply, paths: [k8s], include: ['*.yaml'], exclude: ['secrets/*'] }\n",
+        )
+        .unwrap();
+        let hook = &hooks.on_file_change[0];
+        assert_eq!(hook.debounce, Duration::from_secs(1));
+        assert!(hook.matches(Path::new("deploy.yaml")));
+        assert!(hook.matches(Path::new("overlays/dev/patch.yaml")));
+        assert!(!hook.matches(Path::new("README.md")));
+        assert!(!hook.matches(Path::new("secrets/db.yaml")));
+
+        assert!(serde_yml::from_str::<HooksConfig>(
+            "on_file_change: [{ name: a, command: x, paths: [k8s], include: ['[*.yaml'] }]"
+        )
+        .is_err());
+    }
+
     #[test]
     fn duration_parses_seconds() {
         assert_eq!(parse_duration_str("1s").unwrap(), Duration::from_secs(1));
@@ -980,10 +2675,58 @@ cmd: "uname -s"
         }
     }
 
+    #[test]
+    fn notifications_default_allows_all_events() {
+        let cfg = NotificationsConfig::default();
+        for event in NotificationEvent::all() {
+            assert!(cfg.allows(event));
+        }
+    }
+
+    #[test]
+    fn notifications_filter_parses() {
+        let yaml = r#"
+events: [pod_crash_loop, hook_finished]
+duration: "8s"
+"#;
+        let cfg: NotificationsConfig = serde_yml::from_str(yaml).unwrap();
+        assert_eq!(cfg.duration, Duration::from_secs(8));
+        assert!(cfg.allows(NotificationEvent::PodCrashLoop));
+        assert!(!cfg.allows(NotificationEvent::PvcBound));
+
+        let disabled: NotificationsConfig = serde_yml::from_str("enabled: false").unwrap();
+        assert!(!disabled.allows(NotificationEvent::HookFinished));
+    }
+
     #[test]
     fn visible_rejects_unknown_type() {
         let err = serde_yml::from_str::<Visible>(r#"{ type: bogus }"#).unwrap_err();
         let msg = err.to_string();
         assert!(msg.contains("unknown"), "unexpected error: {msg}");
     }
+
+    #[test]
+    fn command_icons_come_from_config_or_target() {
+        let yaml = r#"
+- name: Shell
+  exec: { target: { type: kubernetes, selector: "app=web" }, cmd: sh }
+- name: Reset
+  tags: [danger]
+  exec: { target: { type: host }, cmd: make reset }
+- name: Logs
+  icon: "L"
+  color: success
+  exec: { target: { type: docker, container: web }, cmd: tail log }
+- name: Submenu
+  commands: []
+"#;
+        let entries: Vec<CommandEntry> = serde_yml::from_str(yaml).unwrap();
+        assert_eq!(entries[0].display_icon(true).as_deref(), Some("☸"));
+        assert_eq!(entries[0].display_icon(false), None);
+        assert_eq!(entries[1].display_icon(true).as_deref(), Some("⚠"));
+        assert_eq!(entries[1].display_color(), Some(CommandColor::Danger));
+        assert_eq!(entries[2].display_icon(false).as_deref(), Some("L"));
+        assert_eq!(entries[2].display_color(), Some(CommandColor::Success));
+        assert_eq!(entries[3].display_icon(true), None);
+    }
 }
@@ -8,6 +8,7 @@ use std::collections::{HashMap, HashSet};
 
 use super::{ConfigValidator, ValidationWarning};
 use crate::config::types::{CommandEntry, ExecutionTarget, InputDefinition, InputSpec};
+use crate::keybindings::{KeyAction, KeyContext, KeybindingResolver};
 
 /// Lazy-compiled regex for extracting @placeholder names
 static PLACEHOLDER_REGEX: Lazy<Regex> =
@@ -114,7 +115,7 @@ impl<'a> ConfigValidator<'a> {
             // Check exec config fields
             if let Some(exec) = &entry.exec {
                 match &exec.target {
-                    ExecutionTarget::Host => {}
+                    ExecutionTarget::Host | ExecutionTarget::Script | ExecutionTarget::Lua => {}
                     ExecutionTarget::Docker { container } => {
                         self.extract_placeholders(container, used);
                     }
@@ -170,7 +171,7 @@ impl<'a> ConfigValidator<'a> {
                 let mut fields: Vec<(&str, &str)> =
                     vec![("workdir", &exec.workdir), ("cmd", &exec.cmd)];
                 match &exec.target {
-                    ExecutionTarget::Host => {}
+                    ExecutionTarget::Host | ExecutionTarget::Script | ExecutionTarget::Lua => {}
                     ExecutionTarget::Docker { container } => {
                         fields.push(("target.container", container));
                     }
@@ -287,48 +288,49 @@ impl<'a> ConfigValidator<'a> {
     /// Check for keybinding conflicts (if keybindings are configured)
     pub(super) fn check_keybinding_conflicts(&mut self) {
         if let Some(keybindings) = &self.config.keybindings {
-            let mut bindings: HashMap<String, Vec<String>> = HashMap::new();
-
-            // Collect all keybindings
-            let builtin_bindings = [
-                (&keybindings.quit, "quit"),
-                (&keybindings.help, "help"),
-                (&keybindings.refresh, "refresh"),
-                (&keybindings.command_palette, "command_palette"),
-                (&keybindings.update_hosts, "update_hosts"),
-                (&keybindings.cancel, "cancel"),
-                (&keybindings.move_up, "move_up"),
-                (&keybindings.move_down, "move_down"),
-                (&keybindings.move_left, "move_left"),
-                (&keybindings.move_right, "move_right"),
-                (&keybindings.toggle_focus, "toggle_focus"),
-                (&keybindings.execute, "execute"),
-            ];
-
-            for (opt_key, action) in builtin_bindings {
-                if let Some(key) = opt_key {
-                    let normalized = key.to_lowercase();
-                    bindings
-                        .entry(normalized)
-                        .or_default()
-                        .push(action.to_string());
-                }
+            // Conflicts against the defaults as well as between remaps
+            let resolver = KeybindingResolver::from_config(Some(keybindings));
+            for conflict in resolver.conflicts() {
+                let key = if conflict.context == KeyContext::Normal {
+                    conflict.key.clone()
+                } else {
+                    format!("{} ({})", conflict.key, conflict.context.config_key())
+                };
+                self.result
+                    .add_warning(ValidationWarning::DuplicateKeybinding {
+                        key,
+                        actions: conflict.actions.clone(),
+                    });
             }
 
-            // Add custom bindings
-            for (key, command_path) in &keybindings.custom {
-                let normalized = key.to_lowercase();
-                bindings
-                    .entry(normalized)
-                    .or_default()
-                    .push(format!("custom:{}", command_path));
+            // Keys that the pod list handles before the global bindings
+            for (key, action, panel_action) in resolver.panel_shadowed(keybindings) {
+                self.result
+                    .add_warning(ValidationWarning::ShadowedKeybinding {
+                        key,
+                        action,
+                        panel: KeyContext::PodStats.title().to_lowercase(),
+                        panel_action,
+                    });
             }
 
-            // Report conflicts
-            for (key, actions) in bindings {
-                if actions.len() > 1 {
+            // Context names and actions must be known
+            for (context, actions) in &keybindings.contexts {
+                if KeyContext::from_config_key(context).is_none() {
                     self.result
-                        .add_warning(ValidationWarning::DuplicateKeybinding { key, actions });
+                        .add_warning(ValidationWarning::UnknownKeybindingContext {
+                            context: context.clone(),
+                        });
+                    continue;
+                }
+                for action in actions.keys() {
+                    if KeyAction::from_config_key(action).is_none() {
+                        self.result
+                            .add_warning(ValidationWarning::UnknownKeybindingAction {
+                                context: context.clone(),
+                                action: action.clone(),
+                            });
+                    }
                 }
             }
 
@@ -341,22 +343,7 @@ impl<'a> ConfigValidator<'a> {
         &mut self,
         keybindings: &crate::config::types::KeybindingsConfig,
     ) {
-        let all_keys: Vec<(&str, Option<&String>)> = vec![
-            ("quit", keybindings.quit.as_ref()),
-            ("help", keybindings.help.as_ref()),
-            ("refresh", keybindings.refresh.as_ref()),
-            ("command_palette", keybindings.command_palette.as_ref()),
-            ("update_hosts", keybindings.update_hosts.as_ref()),
-            ("cancel", keybindings.cancel.as_ref()),
-            ("move_up", keybindings.move_up.as_ref()),
-            ("move_down", keybindings.move_down.as_ref()),
-            ("move_left", keybindings.move_left.as_ref()),
-            ("move_right", keybindings.move_right.as_ref()),
-            ("toggle_focus", keybindings.toggle_focus.as_ref()),
-            ("execute", keybindings.execute.as_ref()),
-        ];
-
-        for (action, opt_key) in all_keys {
+        for (action, opt_key) in keybindings.builtin() {
             if let Some(key) = opt_key {
                 if let Err(reason) = super::validate_key_syntax(key) {
                     self.result
@@ -368,6 +355,18 @@ impl<'a> ConfigValidator<'a> {
             }
         }
 
+        for (context, actions) in &keybindings.contexts {
+            for (action, key) in actions {
+                if let Err(reason) = super::validate_key_syntax(key) {
+                    self.result
+                        .add_warning(ValidationWarning::InvalidKeybindingSyntax {
+                            key: format!("{}.{} = {}", context, action, key),
+                            reason,
+                        });
+                }
+            }
+        }
+
         for key in keybindings.custom.keys() {
             if let Err(reason) = super::validate_key_syntax(key) {
                 self.result
@@ -446,4 +445,104 @@ impl<'a> ConfigValidator<'a> {
             self.check_input_options_in_entries(&entry.commands, &entry_path);
         }
     }
+
+    /// Check for builds sharing a name
+    pub(super) fn check_duplicate_build_names(&mut self) {
+        let mut seen: HashSet<&str> = HashSet::new();
+        for build in &self.config.builds {
+            if !seen.insert(&build.name) {
+                self.result
+                    .add_warning(ValidationWarning::DuplicateBuildName {
+                        name: build.name.clone(),
+                    });
+            }
+        }
+    }
+
+    /// Check for seeds sharing a name
+    pub(super) fn check_duplicate_seed_names(&mut self) {
+        let mut seen: HashSet<&str> = HashSet::new();
+        for seed in &self.config.seeds {
+            if !seen.insert(&seed.name) {
+                self.result
+                    .add_warning(ValidationWarning::DuplicateSeedName {
+                        name: seed.name.clone(),
+                    });
+            }
+        }
+    }
+
+    /// The pod killer refuses to pick from every pod in a namespace
+    pub(super) fn check_pod_killer(&mut self) {
+        let Some(killer) = &self.config.chaos.pod_killer else {
+            return;
+        };
+        if killer.selector.trim().is_empty() {
+            self.result
+                .add_warning(ValidationWarning::PodKillerWithoutSelector);
+        }
+    }
+
+    /// A smoke test without anything to check can't pass
+    pub(super) fn check_smoke_tests(&mut self) {
+        for test in &self.config.smoke_tests {
+            if test.url.is_empty() && test.exec.is_none() {
+                self.result
+                    .add_warning(ValidationWarning::SmokeTestWithoutCheck {
+                        name: test.name.clone(),
+                    });
+            }
+        }
+    }
+
+    /// A critical level milder than the warn level never shows as a warning
+    pub(super) fn check_info_block_thresholds(&mut self) {
+        for block in &self.config.info_blocks {
+            if block.thresholds.is_some_and(|t| !t.ordered()) {
+                self.result
+                    .add_warning(ValidationWarning::ThresholdsOutOfOrder {
+                        block: block.name.clone(),
+                    });
+            }
+        }
+    }
+
+    /// Report `commands_source` catalogs the loader could not merge
+    pub(super) fn check_catalogs(&mut self) {
+        for error in &self.config.catalog_errors {
+            self.result
+                .add_warning(ValidationWarning::UnreadableCatalog {
+                    error: error.clone(),
+                });
+        }
+    }
+
+    /// Warn about `success` error/warning patterns that don't compile
+    pub(super) fn check_success_patterns(&mut self) {
+        for group in &self.config.commands {
+            self.check_success_patterns_in_entries(&group.commands, &group.name);
+        }
+    }
+
+    fn check_success_patterns_in_entries(&mut self, entries: &[CommandEntry], path: &str) {
+        for entry in entries {
+            let entry_path = format!("{}/{}", path, entry.name);
+            let patterns = entry
+                .success
+                .error_patterns
+                .iter()
+                .chain(&entry.success.warning_patterns);
+            for pattern in patterns {
+                if let Err(e) = Regex::new(pattern) {
+                    self.result
+                        .add_warning(ValidationWarning::InvalidSuccessPattern {
+                            path: entry_path.clone(),
+                            pattern: pattern.clone(),
+                            error: e.to_string(),
+                        });
+                }
+            }
+            self.check_success_patterns_in_entries(&entry.commands, &entry_path);
+        }
+    }
 }
@@ -48,6 +48,19 @@ pub enum ValidationWarning {
         key: String,
         reason: String,
     },
+    UnknownKeybindingContext {
+        context: String,
+    },
+    UnknownKeybindingAction {
+        context: String,
+        action: String,
+    },
+    ShadowedKeybinding {
+        key: String,
+        action: String,
+        panel: String,
+        panel_action: String,
+    },
     PortConflict {
         ports: Vec<u16>,
         description: String,
@@ -61,6 +74,27 @@ pub enum ValidationWarning {
         input: String,
         default: String,
     },
+    InvalidSuccessPattern {
+        path: String,
+        pattern: String,
+        error: String,
+    },
+    DuplicateBuildName {
+        name: String,
+    },
+    DuplicateSeedName {
+        name: String,
+    },
+    PodKillerWithoutSelector,
+    SmokeTestWithoutCheck {
+        name: String,
+    },
+    ThresholdsOutOfOrder {
+        block: String,
+    },
+    UnreadableCatalog {
+        error: String,
+    },
 }
 
 impl std::fmt::Display for ValidationWarning {
@@ -92,6 +126,32 @@ impl std::fmt::Display for ValidationWarning {
             ValidationWarning::InvalidKeybindingSyntax { key, reason } => {
                 write!(f, "Invalid keybinding '{}': {}", key, reason)
             }
+            ValidationWarning::UnknownKeybindingContext { context } => {
+                write!(
+                    f,
+                    "Unknown keybinding context '{}'; expected normal, pod_stats, output_popup, input or palette",
+                    context
+                )
+            }
+            ValidationWarning::UnknownKeybindingAction { context, action } => {
+                write!(
+                    f,
+                    "Unknown action '{}' in keybinding context '{}'",
+                    action, context
+                )
+            }
+            ValidationWarning::ShadowedKeybinding {
+                key,
+                action,
+                panel,
+                panel_action,
+            } => {
+                write!(
+                    f,
+                    "Key '{}' ({}) is handled as '{}' while the {} is focused",
+                    key, action, panel_action, panel
+                )
+            }
             ValidationWarning::PortConflict { ports, description } => {
                 write!(f, "Port conflict {:?}: {}", ports, description)
             }
@@ -109,6 +169,54 @@ impl std::fmt::Display for ValidationWarning {
                     path, input, default
                 )
             }
+            ValidationWarning::InvalidSuccessPattern {
+                path,
+                pattern,
+                error,
+            } => {
+                write!(
+                    f,
+                    "{}: success pattern '{}' is not a valid regex and is ignored: {}",
+                    path, pattern, error
+                )
+            }
+            ValidationWarning::DuplicateBuildName { name } => {
+                write!(
+                    f,
+                    "Build '{}' is defined more than once; only the first is offered",
+                    name
+                )
+            }
+            ValidationWarning::DuplicateSeedName { name } => {
+                write!(
+                    f,
+                    "Seed '{}' is defined more than once; only the first is loaded",
+                    name
+                )
+            }
+            ValidationWarning::PodKillerWithoutSelector => {
+                write!(
+                    f,
+                    "chaos.pod_killer has no selector; the pod killer won't start"
+                )
+            }
+            ValidationWarning::SmokeTestWithoutCheck { name } => {
+                write!(
+                    f,
+                    "Smoke test '{}' has neither url nor exec; it always fails",
+                    name
+                )
+            }
+            ValidationWarning::ThresholdsOutOfOrder { block } => {
+                write!(
+                    f,
+                    "Info block '{}': critical threshold comes before warn; swap them or set below: true",
+                    block
+                )
+            }
+            ValidationWarning::UnreadableCatalog { error } => {
+                write!(f, "Command catalog skipped: {}", error)
+            }
         }
     }
 }
@@ -137,6 +245,13 @@ impl<'a> ConfigValidator<'a> {
         self.check_suspicious_ports();
         self.check_keybinding_conflicts();
         self.check_input_options();
+        self.check_success_patterns();
+        self.check_duplicate_build_names();
+        self.check_duplicate_seed_names();
+        self.check_pod_killer();
+        self.check_smoke_tests();
+        self.check_info_block_thresholds();
+        self.check_catalogs();
         self.result
     }
 }
@@ -179,6 +294,7 @@ pub(super) fn validate_key_syntax(key: &str) -> Result<(), String> {
         "f11",
         "f12",
         "space",
+        "plus",
     ];
 
     for (i, part) in parts.iter().enumerate() {
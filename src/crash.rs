@@ -0,0 +1,168 @@
+//! Panic handling for the TUI
+//!
+//! Background work runs in tokio tasks; a panic there only ends that task,
+//! but the default hook prints it over the TUI and nobody notices what
+//! stopped working. The hook installed here logs every panic with its
+//! backtrace and hands the ones outside the UI thread to a listener, which
+//! shows them in a popup that can write a crash report. A panic on the UI
+//! thread still ends k3dev, after the terminal has been restored.
+
+use std::backtrace::Backtrace;
+use std::panic::{self, PanicHookInfo};
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+use std::thread;
+
+use anyhow::{Context, Result};
+use chrono::{DateTime, Local};
+
+/// Where crash reports are filed
+pub const ISSUES_URL: &str = "https://github.com/daylioti/k3dev/issues/new";
+
+type Listener = Box<dyn Fn(PanicReport) + Send + Sync>;
+
+/// Receives panics outside the UI thread once the app is running
+static LISTENER: Mutex<Option<Listener>> = Mutex::new(None);
+
+/// A caught panic
+#[derive(Debug, Clone)]
+pub struct PanicReport {
+    pub message: String,
+    /// "src/app/refresh.rs:120:5"
+    pub location: Option<String>,
+    pub thread: String,
+    pub backtrace: String,
+    pub time: DateTime<Local>,
+}
+
+impl PanicReport {
+    fn capture(info: &PanicHookInfo) -> Self {
+        let payload = info.payload();
+        let message = payload
+            .downcast_ref::<&str>()
+            .map(|s| s.to_string())
+            .or_else(|| payload.downcast_ref::<String>().cloned())
+            .unwrap_or_else(|| "Box<dyn Any>".to_string());
+        Self {
+            message,
+            location: info.location().map(|l| l.to_string()),
+            thread: thread::current().name().unwrap_or("unnamed").to_string(),
+            backtrace: Backtrace::force_capture().to_string(),
+            time: Local::now(),
+        }
+    }
+
+    /// "panicked at src/main.rs:1:1: message"
+    pub fn summary(&self) -> String {
+        match &self.location {
+            Some(location) => format!("panicked at {}: {}", location, self.message),
+            None => format!("panicked: {}", self.message),
+        }
+    }
+
+    /// The report as a GitHub issue body
+    pub fn issue_markdown(&self) -> String {
+        format!(
+            "## What happened\n\n\
+             <!-- What were you doing when this happened? -->\n\n\
+             ## Steps to reproduce\n\n1. \n\n\
+             ## Environment\n\n\
+             - k3dev: {}\n\
+             - OS: {} {}\n\
+             - Time: {}\n\n\
+             ## Panic\n\n\
+             Thread `{}` {}\n\n\
+             <details>\n<summary>Backtrace</summary>\n\n```\n{}\n```\n\n</details>\n",
+            env!("CARGO_PKG_VERSION"),
+            std::env::consts::OS,
+            std::env::consts::ARCH,
+            self.time.format("%Y-%m-%d %H:%M:%S"),
+            self.thread,
+            self.summary(),
+            self.backtrace.trim_end(),
+        )
+    }
+
+    /// Write the issue body to `path`
+    pub fn write(&self, path: &Path) -> Result<()> {
+        std::fs::write(path, self.issue_markdown())
+            .with_context(|| format!("Failed to write crash report {}", path.display()))
+    }
+}
+
+/// `k3dev-crash-<timestamp>.md` in the working directory
+pub fn default_report_path(report: &PanicReport) -> PathBuf {
+    PathBuf::from(format!(
+        "k3dev-crash-{}.md",
+        report.time.format("%Y%m%d-%H%M%S")
+    ))
+}
+
+/// Install the panic hook; call from the UI thread. `restore_terminal` runs
+/// before a panic on that thread is printed.
+pub fn install_hook(restore_terminal: fn()) {
+    let ui_thread = thread::current().id();
+    let original_hook = panic::take_hook();
+    panic::set_hook(Box::new(move |info| {
+        let report = PanicReport::capture(info);
+        tracing::error!(
+            thread = %report.thread,
+            backtrace = %report.backtrace,
+            "{}",
+            report.summary()
+        );
+
+        if thread::current().id() != ui_thread {
+            if let Ok(listener) = LISTENER.lock() {
+                if let Some(listener) = listener.as_ref() {
+                    listener(report);
+                    return;
+                }
+            }
+        }
+        restore_terminal();
+        original_hook(info);
+    }));
+}
+
+/// Send later panics outside the UI thread to `listener` instead of stderr
+pub fn set_listener(listener: impl Fn(PanicReport) + Send + Sync + 'static) {
+    if let Ok(mut current) = LISTENER.lock() {
+        *current = Some(Box::new(listener));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn report() -> PanicReport {
+        PanicReport {
+            message: "index out of bounds".to_string(),
+            location: Some("src/app/refresh.rs:120:5".to_string()),
+            thread: "tokio-runtime-worker".to_string(),
+            backtrace: "   0: k3dev::app::refresh\n".to_string(),
+            time: Local::now(),
+        }
+    }
+
+    #[test]
+    fn issue_body_has_panic_and_backtrace() {
+        let body = report().issue_markdown();
+        assert!(body.contains(
+            "Thread `tokio-runtime-worker` panicked at src/app/refresh.rs:120:5: index out of bounds"
+        ));
+        assert!(body.contains("```\n   0: k3dev::app::refresh\n```"));
+        assert!(body.contains(&format!("- k3dev: {}", env!("CARGO_PKG_VERSION"))));
+    }
+
+    #[test]
+    fn report_path_is_timestamped() {
+        let report = report();
+        let path = default_report_path(&report);
+        assert_eq!(
+            path.to_string_lossy(),
+            format!("k3dev-crash-{}.md", report.time.format("%Y%m%d-%H%M%S"))
+        );
+    }
+}
@@ -0,0 +1,186 @@
+//! Failures k3dev knows how to explain
+//!
+//! Most errors are plain `anyhow` chains and are shown as they are. The ones
+//! below have a known cause and fix: they travel inside the `anyhow` chain
+//! like any other error, and where errors are reported they are found again
+//! to show what to do about them and, without the TUI, to pick an exit code
+//! scripts can tell apart.
+
+use thiserror::Error;
+
+use crate::ui::components::OutputLine;
+
+/// Exit code of failures without a more specific one
+pub const EXIT_FAILURE: i32 = 1;
+
+#[derive(Debug, Error)]
+pub enum K3devError {
+    /// The Docker daemon doesn't answer
+    #[error("Docker is not accessible")]
+    DockerUnavailable,
+
+    /// The Docker socket refuses the current user
+    #[error("Permission denied on the Docker socket {socket}")]
+    PermissionDenied { socket: String },
+
+    /// A host port the cluster publishes can't be bound
+    #[error("Failed to start container '{container}': port binding error: {detail}")]
+    PortConflict {
+        container: String,
+        /// Published ports below 1024, as "host:container"
+        privileged: Vec<String>,
+        detail: String,
+    },
+
+    /// The k3s API never became ready after the container started
+    #[error("Timeout waiting for k3s API at {url}: {health}")]
+    ApiTimeout { url: String, health: String },
+
+    /// The operation needs a running cluster
+    #[error("Cluster is not running")]
+    ClusterNotRunning,
+
+    /// The operation as a whole ran out of time
+    #[error("{0} timed out")]
+    Timeout(String),
+}
+
+impl K3devError {
+    /// The K3devError in `error`, however much context was added around it
+    pub fn find(error: &anyhow::Error) -> Option<&K3devError> {
+        error
+            .downcast_ref::<K3devError>()
+            .or_else(|| error.chain().find_map(|e| e.downcast_ref::<K3devError>()))
+    }
+
+    /// Exit code of headless commands failing with this error
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            Self::DockerUnavailable => 10,
+            Self::PermissionDenied { .. } => 11,
+            Self::PortConflict { .. } => 12,
+            Self::ApiTimeout { .. } => 13,
+            Self::ClusterNotRunning => 14,
+            Self::Timeout(_) => 15,
+        }
+    }
+
+    /// What the user can do about it, one step per line
+    pub fn remediation(&self) -> String {
+        match self {
+            Self::DockerUnavailable if cfg!(target_os = "macos") => {
+                "Start Docker Desktop (or Colima/OrbStack) and wait until `docker info` answers"
+                    .to_string()
+            }
+            Self::DockerUnavailable => {
+                "Start the Docker daemon (sudo systemctl start docker) and check that `docker info` answers"
+                    .to_string()
+            }
+            Self::PermissionDenied { .. } => {
+                "Add your user to the docker group (sudo usermod -aG docker $USER), then log out and back in"
+                    .to_string()
+            }
+            Self::PortConflict { privileged, .. }
+                if cfg!(target_os = "macos") && !privileged.is_empty() =>
+            {
+                format!(
+                    "Ports below 1024 ({}) need Docker Desktop's privileged port mapping: \
+                     Settings → Advanced → Enable privileged port mapping\n\
+                     Or publish other ports in k3dev.yml (http_port: 8080, https_port: 8443)",
+                    privileged.join(", ")
+                )
+            }
+            Self::PortConflict { .. } => {
+                "Stop the process using the port (see `lsof -i :PORT`)\n\
+                 Or publish other ports in k3dev.yml (http_port: 8080, https_port: 8443)"
+                    .to_string()
+            }
+            Self::ApiTimeout { .. } => {
+                "Check the likely causes above, or run `k3dev diagnostics` and `k3dev logs`"
+                    .to_string()
+            }
+            Self::ClusterNotRunning => "Start it with `k3dev start` or the Start action".to_string(),
+            Self::Timeout(_) => {
+                "Try again; if it keeps timing out, run `k3dev diagnostics`".to_string()
+            }
+        }
+    }
+}
+
+/// Exit code for a headless command that failed with `error`
+pub fn exit_code(error: &anyhow::Error) -> i32 {
+    K3devError::find(error).map_or(EXIT_FAILURE, K3devError::exit_code)
+}
+
+/// The error line, followed by the remediation when the cause is known
+pub fn report_lines(error: &anyhow::Error) -> Vec<OutputLine> {
+    let mut lines = vec![OutputLine::error(format!("Error: {:#}", error))];
+    if let Some(known) = K3devError::find(error) {
+        lines.extend(
+            known
+                .remediation()
+                .lines()
+                .map(|step| OutputLine::hint(format!("→ {}", step))),
+        );
+    }
+    lines
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ui::components::OutputType;
+    use anyhow::Context;
+
+    #[test]
+    fn known_errors_are_found_behind_context() {
+        let error = Err::<(), _>(K3devError::ClusterNotRunning)
+            .context("Failed to list pods")
+            .unwrap_err();
+        assert!(matches!(
+            K3devError::find(&error),
+            Some(K3devError::ClusterNotRunning)
+        ));
+        assert_eq!(exit_code(&error), 14);
+
+        let lines = report_lines(&error);
+        assert_eq!(
+            lines[0].content,
+            "Error: Failed to list pods: Cluster is not running"
+        );
+        assert_eq!(lines[1].output_type, OutputType::Hint);
+    }
+
+    #[test]
+    fn other_errors_have_no_hint() {
+        let error = anyhow::anyhow!("something broke");
+        assert_eq!(exit_code(&error), EXIT_FAILURE);
+        assert_eq!(report_lines(&error).len(), 1);
+    }
+
+    #[test]
+    fn exit_codes_are_distinct() {
+        let errors = [
+            K3devError::DockerUnavailable,
+            K3devError::PermissionDenied {
+                socket: String::new(),
+            },
+            K3devError::PortConflict {
+                container: String::new(),
+                privileged: Vec::new(),
+                detail: String::new(),
+            },
+            K3devError::ApiTimeout {
+                url: String::new(),
+                health: String::new(),
+            },
+            K3devError::ClusterNotRunning,
+            K3devError::Timeout(String::new()),
+        ];
+        let mut codes: Vec<i32> = errors.iter().map(K3devError::exit_code).collect();
+        codes.sort();
+        codes.dedup();
+        assert_eq!(codes.len(), errors.len());
+        assert!(!codes.contains(&EXIT_FAILURE));
+    }
+}
@@ -1,23 +1,92 @@
 use anyhow::{anyhow, Result};
+use chrono::{DateTime, Local};
+use once_cell::sync::Lazy;
 use std::collections::HashMap;
 use std::path::PathBuf;
 use std::process::Stdio;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Instant;
 use tokio::io::{AsyncBufReadExt, BufReader};
 use tokio::process::Command;
-use tokio::sync::mpsc;
+use tokio::sync::{broadcast, mpsc};
 use tokio::time::{timeout, Duration};
 
 use crate::config::{HookCommand, HookEvent, HooksConfig};
 use crate::ui::components::OutputLine;
 
+/// Outcome of running all hooks for one event
+#[derive(Debug, Clone)]
+pub struct HookCompletion {
+    pub event: HookEvent,
+    pub hook_count: usize,
+    pub success: bool,
+}
+
+/// Hooks run deep inside cluster operations that only carry an output channel,
+/// so completions are broadcast process-wide for anyone interested (e.g. toasts).
+static HOOK_COMPLETIONS: Lazy<broadcast::Sender<HookCompletion>> =
+    Lazy::new(|| broadcast::channel(16).0);
+
+/// Subscribe to hook completion notices
+pub fn subscribe_completions() -> broadcast::Receiver<HookCompletion> {
+    HOOK_COMPLETIONS.subscribe()
+}
+
+/// Lines kept of a single hook's output
+const MAX_RUN_LINES: usize = 5000;
+
+/// One hook command that ran, with its own output
+#[derive(Debug, Clone)]
+pub struct HookRun {
+    pub event: HookEvent,
+    pub name: String,
+    pub started: DateTime<Local>,
+    pub duration: Duration,
+    pub success: bool,
+    /// The command's stdout (info) and stderr (warning) lines, then the
+    /// failure if there was one
+    pub output: Vec<OutputLine>,
+}
+
+static HOOK_RUNS: Lazy<broadcast::Sender<HookRun>> = Lazy::new(|| broadcast::channel(64).0);
+
+/// Subscribe to single hook runs
+pub fn subscribe_runs() -> broadcast::Receiver<HookRun> {
+    HOOK_RUNS.subscribe()
+}
+
+static CAPTURE_OUTPUT: AtomicBool = AtomicBool::new(false);
+
+/// Keep hook commands' output out of the output stream, where parallel
+/// operations would interleave with it. Only the runs carry it from then on;
+/// the TUI lists them in its Hooks view.
+pub fn capture_output() {
+    CAPTURE_OUTPUT.store(true, Ordering::Relaxed);
+}
+
 /// Executor for running hook commands
 pub struct HookExecutor {
     config: HooksConfig,
+    stream_output: bool,
 }
 
 impl HookExecutor {
     pub fn new(config: HooksConfig) -> Self {
-        Self { config }
+        Self {
+            config,
+            stream_output: false,
+        }
+    }
+
+    /// Stream hook output even when it's captured, for callers that hand the
+    /// output stream to someone who can't open the Hooks view
+    pub fn with_streamed_output(mut self) -> Self {
+        self.stream_output = true;
+        self
+    }
+
+    fn captures_output(&self) -> bool {
+        !self.stream_output && CAPTURE_OUTPUT.load(Ordering::Relaxed)
     }
 
     /// Execute all hooks for a given event
@@ -25,6 +94,18 @@ impl HookExecutor {
         &self,
         event: HookEvent,
         output_tx: mpsc::Sender<OutputLine>,
+    ) -> Result<()> {
+        self.execute_hooks_with_env(event, &HashMap::new(), output_tx)
+            .await
+    }
+
+    /// Execute all hooks for an event with extra environment variables
+    /// describing it. Per-hook `env` still takes precedence.
+    pub async fn execute_hooks_with_env(
+        &self,
+        event: HookEvent,
+        event_env: &HashMap<String, String>,
+        output_tx: mpsc::Sender<OutputLine>,
     ) -> Result<()> {
         let hooks = self.config.get_hooks(event);
 
@@ -32,6 +113,25 @@ impl HookExecutor {
             return Ok(());
         }
 
+        let result = self.run_hooks(event, hooks, event_env, &output_tx).await;
+
+        // Err only means nobody is subscribed
+        let _ = HOOK_COMPLETIONS.send(HookCompletion {
+            event,
+            hook_count: hooks.len(),
+            success: result.is_ok(),
+        });
+
+        result
+    }
+
+    async fn run_hooks(
+        &self,
+        event: HookEvent,
+        hooks: &[HookCommand],
+        event_env: &HashMap<String, String>,
+        output_tx: &mpsc::Sender<OutputLine>,
+    ) -> Result<()> {
         let _ = output_tx
             .send(OutputLine::info(format!(
                 "Running {} hooks ({} total)...",
@@ -50,20 +150,9 @@ impl HookExecutor {
                 )))
                 .await;
 
-            match self.execute_hook(hook, output_tx.clone()).await {
-                Ok(_) => {
-                    let _ = output_tx
-                        .send(OutputLine::success(format!("  {} completed", hook.name)))
-                        .await;
-                }
-                Err(e) => {
-                    let _ = output_tx
-                        .send(OutputLine::error(format!("  {} failed: {}", hook.name, e)))
-                        .await;
-
-                    if !hook.continue_on_error {
-                        return Err(anyhow!("Hook '{}' failed: {}", hook.name, e));
-                    }
+            if let Err(e) = self.run_hook(event, hook, event_env, output_tx).await {
+                if !hook.continue_on_error {
+                    return Err(anyhow!("Hook '{}' failed: {}", hook.name, e));
                 }
             }
         }
@@ -78,10 +167,106 @@ impl HookExecutor {
         Ok(())
     }
 
+    /// Run a recurring hook once. Its output only goes to `output_tx` when
+    /// it isn't captured, like any hook's.
+    pub async fn execute_recurring(
+        &self,
+        hook: &HookCommand,
+        output_tx: mpsc::Sender<OutputLine>,
+    ) -> Result<()> {
+        self.run_hook(HookEvent::Recurring, hook, &HashMap::new(), &output_tx)
+            .await
+    }
+
+    /// Run an `on_file_change` hook once for `changed` files
+    pub async fn execute_file_change(
+        &self,
+        hook: &HookCommand,
+        changed: &[PathBuf],
+        output_tx: mpsc::Sender<OutputLine>,
+    ) -> Result<()> {
+        let files: Vec<String> = changed
+            .iter()
+            .map(|path| path.to_string_lossy().into_owned())
+            .collect();
+        let env = HashMap::from([("K3DEV_CHANGED_FILES".to_string(), files.join("\n"))]);
+        self.run_hook(HookEvent::OnFileChange, hook, &env, &output_tx)
+            .await
+    }
+
+    /// Run one hook, publish it as a [`HookRun`] and report how it ended
+    async fn run_hook(
+        &self,
+        event: HookEvent,
+        hook: &HookCommand,
+        event_env: &HashMap<String, String>,
+        output_tx: &mpsc::Sender<OutputLine>,
+    ) -> Result<()> {
+        let started = Local::now();
+        let timer = Instant::now();
+        let (result, mut output) = self.capture_hook(hook, event_env, output_tx).await;
+        let duration = timer.elapsed();
+        if let Err(e) = &result {
+            output.push(OutputLine::error(format!("{} failed: {}", hook.name, e)));
+        }
+        // Err only means nobody is subscribed
+        let _ = HOOK_RUNS.send(HookRun {
+            event,
+            name: hook.name.clone(),
+            started,
+            duration,
+            success: result.is_ok(),
+            output,
+        });
+
+        let line = match &result {
+            Ok(()) => OutputLine::success(format!(
+                "  {} completed in {:.1}s",
+                hook.name,
+                duration.as_secs_f64()
+            )),
+            Err(e) if self.captures_output() => OutputLine::error(format!(
+                "  {} failed: {} (output in the Hooks view)",
+                hook.name, e
+            )),
+            Err(e) => OutputLine::error(format!("  {} failed: {}", hook.name, e)),
+        };
+        let _ = output_tx.send(line).await;
+        result
+    }
+
+    /// Run a hook, keeping its output lines and also streaming them to
+    /// `output_tx` unless output is captured
+    async fn capture_hook(
+        &self,
+        hook: &HookCommand,
+        event_env: &HashMap<String, String>,
+        output_tx: &mpsc::Sender<OutputLine>,
+    ) -> (Result<()>, Vec<OutputLine>) {
+        let (line_tx, mut line_rx) = mpsc::channel::<OutputLine>(100);
+        let forward = (!self.captures_output()).then(|| output_tx.clone());
+        let collector = tokio::spawn(async move {
+            let mut output = Vec::new();
+            while let Some(line) = line_rx.recv().await {
+                if let Some(forward) = &forward {
+                    let _ = forward.send(line.clone()).await;
+                }
+                if output.len() < MAX_RUN_LINES {
+                    output.push(line);
+                }
+            }
+            output
+        });
+
+        let result = self.execute_hook(hook, event_env, line_tx).await;
+        (result, collector.await.unwrap_or_default())
+    }
+
     /// Execute a single hook command
     async fn execute_hook(
         &self,
         hook: &HookCommand,
+        event_env: &HashMap<String, String>,
         output_tx: mpsc::Sender<OutputLine>,
     ) -> Result<()> {
         // Expand workdir if specified
@@ -96,8 +281,9 @@ impl HookExecutor {
             None
         };
 
-        // Merge global env with hook-specific env (hook-specific takes precedence)
+        // Merge global env, event env and hook-specific env (hook-specific takes precedence)
         let mut env: HashMap<String, String> = self.config.env.clone();
+        env.extend(event_env.iter().map(|(k, v)| (k.clone(), v.clone())));
         for (key, value) in &hook.env {
             // Expand ~ in environment variable values
             env.insert(key.clone(), expand_home(value));
@@ -196,7 +382,7 @@ impl HookExecutor {
 }
 
 /// Expand ~ to home directory in a path string
-fn expand_home(path: &str) -> String {
+pub(super) fn expand_home(path: &str) -> String {
     if path.starts_with('~') {
         if let Some(home) = dirs::home_dir() {
             return path.replacen('~', &home.to_string_lossy(), 1);
@@ -219,4 +405,28 @@ mod tests {
         assert_eq!(expand_home("/absolute/path"), "/absolute/path");
         assert_eq!(expand_home("relative/path"), "relative/path");
     }
+
+    #[cfg(unix)]
+    #[tokio::test]
+    async fn keeps_each_hooks_output() {
+        let executor = HookExecutor::new(HooksConfig::default());
+        let hook = HookCommand {
+            name: "migrate".to_string(),
+            command: "echo applied; echo 'no such table' >&2; exit 3".to_string(),
+            workdir: None,
+            env: HashMap::new(),
+            continue_on_error: false,
+            timeout: 10,
+        };
+        let (output_tx, _output_rx) = mpsc::channel(16);
+
+        let (result, output) = executor
+            .capture_hook(&hook, &HashMap::new(), &output_tx)
+            .await;
+
+        assert!(result.is_err());
+        let mut texts: Vec<&str> = output.iter().map(|l| l.content.as_str()).collect();
+        texts.sort();
+        assert_eq!(texts, ["  applied", "  no such table"]);
+    }
 }
@@ -1,3 +1,7 @@
 mod executor;
+mod watcher;
 
-pub use executor::HookExecutor;
+pub use executor::{
+    capture_output, subscribe_completions, subscribe_runs, HookCompletion, HookExecutor, HookRun,
+};
+pub use watcher::FileWatchers;
@@ -0,0 +1,183 @@
+//! `on_file_change` hooks
+//!
+//! Each hook gets its own watcher over its paths. Changes that pass the
+//! hook's globs are collected until the files have been quiet for its
+//! debounce time, then the hook runs once for all of them. Changes made
+//! while it runs start another run after it.
+
+use std::collections::BTreeSet;
+use std::path::{Path, PathBuf};
+
+use notify::{EventKind, RecommendedWatcher, RecursiveMode, Watcher};
+use tokio::sync::mpsc;
+
+use super::executor::expand_home;
+use super::HookExecutor;
+use crate::config::{FileChangeHook, HooksConfig};
+use crate::ui::components::OutputLine;
+
+/// The running watchers; dropping this stops them and their hooks
+pub struct FileWatchers {
+    _watchers: Vec<RecommendedWatcher>,
+}
+
+impl FileWatchers {
+    /// Watch the paths of every `on_file_change` hook. Hook results go to
+    /// `output_tx`; the returned warnings name paths that can't be watched.
+    pub fn start(config: &HooksConfig, output_tx: mpsc::Sender<OutputLine>) -> (Self, Vec<String>) {
+        let mut watchers = Vec::new();
+        let mut warnings = Vec::new();
+        for hook in &config.on_file_change {
+            match watch_hook(config, hook, output_tx.clone()) {
+                Ok((watcher, missing)) => {
+                    watchers.push(watcher);
+                    warnings.extend(missing);
+                }
+                Err(e) => warnings.push(format!(
+                    "Can't watch files for hook '{}': {}",
+                    hook.hook.name, e
+                )),
+            }
+        }
+        (
+            Self {
+                _watchers: watchers,
+            },
+            warnings,
+        )
+    }
+}
+
+/// Where `path` of `hook` is on disk
+fn resolve(hook: &FileChangeHook, path: &str) -> PathBuf {
+    let path = PathBuf::from(expand_home(path));
+    if path.is_absolute() {
+        return path;
+    }
+    match &hook.hook.workdir {
+        Some(workdir) => PathBuf::from(expand_home(workdir)).join(path),
+        None => path,
+    }
+}
+
+/// `path` below the watched root it's in; a watched file is its own name
+fn relative<'a>(roots: &[PathBuf], path: &'a Path) -> &'a Path {
+    roots
+        .iter()
+        .filter_map(|root| path.strip_prefix(root).ok())
+        .find(|rest| !rest.as_os_str().is_empty())
+        .or_else(|| path.file_name().map(Path::new))
+        .unwrap_or(path)
+}
+
+fn watch_hook(
+    config: &HooksConfig,
+    hook: &FileChangeHook,
+    output_tx: mpsc::Sender<OutputLine>,
+) -> notify::Result<(RecommendedWatcher, Vec<String>)> {
+    let (event_tx, events) = mpsc::unbounded_channel();
+    let mut watcher = notify::recommended_watcher(move |event: notify::Result<notify::Event>| {
+        let Ok(event) = event else {
+            return;
+        };
+        if matches!(event.kind, EventKind::Access(_)) {
+            return;
+        }
+        for path in event.paths {
+            let _ = event_tx.send(path);
+        }
+    })?;
+
+    let mut roots = Vec::new();
+    let mut missing = Vec::new();
+    for path in &hook.paths {
+        // Events carry the path as watched, so watch the canonical one
+        // for the globs to see it below its root
+        let root = resolve(hook, path);
+        let root = root.canonicalize().unwrap_or(root);
+        match watcher.watch(&root, RecursiveMode::Recursive) {
+            Ok(()) => roots.push(root),
+            Err(e) => missing.push(format!(
+                "Hook '{}' can't watch {}: {}",
+                hook.hook.name,
+                root.display(),
+                e
+            )),
+        }
+    }
+
+    let executor = HookExecutor::new(config.clone());
+    tokio::spawn(run_on_changes(
+        executor,
+        hook.clone(),
+        roots,
+        events,
+        output_tx,
+    ));
+    Ok((watcher, missing))
+}
+
+async fn run_on_changes(
+    executor: HookExecutor,
+    hook: FileChangeHook,
+    roots: Vec<PathBuf>,
+    mut events: mpsc::UnboundedReceiver<PathBuf>,
+    output_tx: mpsc::Sender<OutputLine>,
+) {
+    let mut changed = BTreeSet::new();
+    loop {
+        // Nothing to run for yet: wait as long as it takes
+        let next = if changed.is_empty() {
+            events.recv().await
+        } else {
+            match tokio::time::timeout(hook.debounce, events.recv()).await {
+                Ok(next) => next,
+                Err(_) => {
+                    let files: Vec<PathBuf> = std::mem::take(&mut changed).into_iter().collect();
+                    let what = match files.as_slice() {
+                        [file] => relative(&roots, file).display().to_string(),
+                        files => format!("{} files", files.len()),
+                    };
+                    let _ = output_tx
+                        .send(OutputLine::info(format!(
+                            "{} changed, running {}",
+                            what, hook.hook.name
+                        )))
+                        .await;
+                    let _ = executor
+                        .execute_file_change(&hook.hook, &files, output_tx.clone())
+                        .await;
+                    continue;
+                }
+            }
+        };
+        // The watcher was dropped
+        let Some(path) = next else {
+            return;
+        };
+        if hook.matches(relative(&roots, &path)) {
+            changed.insert(path);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn paths_are_matched_below_their_root() {
+        let roots = vec![
+            PathBuf::from("/srv/app/k8s"),
+            PathBuf::from("/srv/app/.env"),
+        ];
+        assert_eq!(
+            relative(&roots, Path::new("/srv/app/k8s/base/deploy.yaml")),
+            Path::new("base/deploy.yaml")
+        );
+        assert_eq!(
+            relative(&roots, Path::new("/srv/app/.env")),
+            Path::new(".env")
+        );
+    }
+}
@@ -0,0 +1,323 @@
+//! Editor task files for the command catalog
+//!
+//! Each runnable command becomes a VS Code task or a JetBrains shell run
+//! configuration that calls `k3dev run`. Placeholders are asked for when the
+//! task starts, with the input's default prefilled.
+
+use std::path::Path;
+
+use clap::ValueEnum;
+use serde_json::{json, Value};
+
+use crate::commands::shell_quote;
+use crate::config::{
+    get_exec_placeholders, runnable_command_paths, CommandGroup, InputDefinition, InputSpec,
+};
+
+/// Prefix of generated task labels; tasks with it are replaced on re-export
+pub const TASK_LABEL_PREFIX: &str = "k3dev: ";
+
+/// Prefix of generated VS Code input ids
+const INPUT_ID_PREFIX: &str = "k3dev-";
+
+/// Prefix of generated JetBrains run configuration files
+pub const RUN_CONFIG_FILE_PREFIX: &str = "k3dev_";
+
+/// Editors with a task format
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum Ide {
+    Vscode,
+    Jetbrains,
+}
+
+/// One runnable command and how to prompt for its placeholders
+struct TaskSpec {
+    path: String,
+    description: Option<String>,
+    inputs: Vec<TaskInput>,
+}
+
+struct TaskInput {
+    name: String,
+    prompt: String,
+    default: Option<String>,
+    options: Vec<String>,
+}
+
+fn task_specs(groups: &[CommandGroup]) -> Vec<TaskSpec> {
+    runnable_command_paths(groups)
+        .into_iter()
+        .filter_map(|(path, entry)| {
+            let exec = entry.exec.as_ref()?;
+            let inputs = get_exec_placeholders(exec)
+                .into_iter()
+                .map(|name| {
+                    let definition = exec.input.get(&name);
+                    let (prompt, options) = match definition {
+                        Some(InputDefinition::Prompt(prompt)) => (prompt.clone(), Vec::new()),
+                        Some(InputDefinition::Detailed(InputSpec::Select {
+                            prompt,
+                            options,
+                            ..
+                        })) => (prompt.clone(), options.clone()),
+                        Some(InputDefinition::Detailed(
+                            InputSpec::Text { prompt, .. } | InputSpec::MultiSelect { prompt, .. },
+                        )) => (prompt.clone(), Vec::new()),
+                        None => (format!("{}:", name), Vec::new()),
+                    };
+                    TaskInput {
+                        default: definition.and_then(|d| d.default_value()),
+                        name,
+                        prompt,
+                        options,
+                    }
+                })
+                .collect();
+            Some(TaskSpec {
+                path,
+                description: entry.description.clone(),
+                inputs,
+            })
+        })
+        .collect()
+}
+
+/// Arguments before `run`: the config file, when one was given
+fn config_args(config_file: Option<&Path>) -> Vec<String> {
+    match config_file {
+        Some(path) => vec!["--config".to_string(), path.display().to_string()],
+        None => Vec::new(),
+    }
+}
+
+/// `.vscode/tasks.json` with a task per command.
+///
+/// Tasks and inputs from an earlier export are replaced; everything else in
+/// `existing` is kept.
+pub fn vscode_tasks(
+    groups: &[CommandGroup],
+    config_file: Option<&Path>,
+    existing: Option<Value>,
+) -> Value {
+    let mut document = match existing {
+        Some(Value::Object(object)) => Value::Object(object),
+        _ => json!({ "version": "2.0.0" }),
+    };
+    let keep = |key: &str, field: &str, prefix: &str| -> Vec<Value> {
+        document
+            .get(key)
+            .and_then(Value::as_array)
+            .map(|items| {
+                items
+                    .iter()
+                    .filter(|item| {
+                        !item
+                            .get(field)
+                            .and_then(Value::as_str)
+                            .is_some_and(|v| v.starts_with(prefix))
+                    })
+                    .cloned()
+                    .collect()
+            })
+            .unwrap_or_default()
+    };
+    let mut tasks = keep("tasks", "label", TASK_LABEL_PREFIX);
+    let mut inputs = keep("inputs", "id", INPUT_ID_PREFIX);
+
+    for spec in task_specs(groups) {
+        let mut args = config_args(config_file);
+        args.extend(["run".to_string(), spec.path.clone()]);
+        for input in &spec.inputs {
+            let id = format!("{}{}-{}", INPUT_ID_PREFIX, slug(&spec.path), input.name);
+            args.push("--set".to_string());
+            args.push(format!("{}=${{input:{}}}", input.name, id));
+
+            let mut definition = if input.options.is_empty() {
+                json!({ "id": id, "type": "promptString", "description": input.prompt })
+            } else {
+                json!({
+                    "id": id,
+                    "type": "pickString",
+                    "description": input.prompt,
+                    "options": input.options,
+                })
+            };
+            if let Some(default) = &input.default {
+                definition["default"] = json!(default);
+            }
+            inputs.push(definition);
+        }
+
+        let mut task = json!({
+            "label": format!("{}{}", TASK_LABEL_PREFIX, spec.path),
+            "type": "process",
+            "command": "k3dev",
+            "args": args,
+            "problemMatcher": [],
+        });
+        if let Some(description) = &spec.description {
+            task["detail"] = json!(description);
+        }
+        tasks.push(task);
+    }
+
+    document["tasks"] = Value::Array(tasks);
+    if inputs.is_empty() {
+        if let Some(object) = document.as_object_mut() {
+            object.remove("inputs");
+        }
+    } else {
+        document["inputs"] = Value::Array(inputs);
+    }
+    document
+}
+
+/// JetBrains shell run configurations for `.run/`, as (file name, XML)
+pub fn jetbrains_run_configs(
+    groups: &[CommandGroup],
+    config_file: Option<&Path>,
+) -> Vec<(String, String)> {
+    task_specs(groups)
+        .into_iter()
+        .map(|spec| {
+            let mut script = Vec::new();
+            let mut command: Vec<String> = vec!["k3dev".to_string()];
+            command.extend(config_args(config_file).iter().map(|a| shell_quote(a)));
+            command.push("run".to_string());
+            command.push(shell_quote(&spec.path));
+            for input in &spec.inputs {
+                let var = format!("k3dev_{}", input.name);
+                let prompt = match &input.default {
+                    Some(default) => format!("{} [{}] ", input.prompt, default),
+                    None => format!("{} ", input.prompt),
+                };
+                script.push(format!("read -rp {} {}", shell_quote(&prompt), var));
+                if let Some(default) = &input.default {
+                    script.push(format!("{}=${{{}:-{}}}", var, var, shell_quote(default)));
+                }
+                command.push(format!("--set \"{}=${}\"", input.name, var));
+            }
+            script.push(command.join(" "));
+
+            let name = format!("{}{}", TASK_LABEL_PREFIX, spec.path);
+            let xml = format!(
+                r#"<component name="ProjectRunConfigurationManager">
+  <configuration default="false" name="{name}" type="ShConfigurationType">
+    <option name="SCRIPT_TEXT" value="{script}" />
+    <option name="INDEPENDENT_SCRIPT_PATH" value="true" />
+    <option name="SCRIPT_PATH" value="" />
+    <option name="SCRIPT_OPTIONS" value="" />
+    <option name="INDEPENDENT_SCRIPT_WORKING_DIRECTORY" value="true" />
+    <option name="SCRIPT_WORKING_DIRECTORY" value="$PROJECT_DIR$" />
+    <option name="INDEPENDENT_INTERPRETER_PATH" value="true" />
+    <option name="INTERPRETER_PATH" value="/bin/bash" />
+    <option name="INTERPRETER_OPTIONS" value="" />
+    <option name="EXECUTE_IN_TERMINAL" value="true" />
+    <option name="EXECUTE_SCRIPT_FILE" value="false" />
+    <envs />
+    <method v="2" />
+  </configuration>
+</component>
+"#,
+                name = xml_escape(&name),
+                script = xml_escape(&script.join("; ")),
+            );
+            let file = format!("{}{}.run.xml", RUN_CONFIG_FILE_PREFIX, slug(&spec.path));
+            (file, xml)
+        })
+        .collect()
+}
+
+/// Lowercase, with every run of other characters turned into one `-`
+fn slug(path: &str) -> String {
+    let mut slug = String::new();
+    for c in path.chars() {
+        if c.is_ascii_alphanumeric() {
+            slug.push(c.to_ascii_lowercase());
+        } else if !slug.ends_with('-') {
+            slug.push('-');
+        }
+    }
+    slug.trim_matches('-').to_string()
+}
+
+fn xml_escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+        .replace('\n', "&#10;")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn groups() -> Vec<CommandGroup> {
+        serde_yml::from_str(
+            r#"
+- name: Database
+  commands:
+    - name: Migrate
+      description: Run migrations
+      exec:
+        target: { type: host }
+        cmd: migrate --to @version --env @env
+        input:
+          env:
+            type: select
+            prompt: "Env:"
+            options: [dev, prod]
+"#,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn vscode_tasks_replace_earlier_exports_only() {
+        let existing = json!({
+            "version": "2.0.0",
+            "tasks": [
+                { "label": "build", "type": "shell", "command": "make" },
+                { "label": "k3dev: Old/Gone", "type": "process", "command": "k3dev" }
+            ],
+            "inputs": [{ "id": "k3dev-old-gone-x", "type": "promptString" }]
+        });
+        let tasks = vscode_tasks(&groups(), None, Some(existing));
+
+        let labels: Vec<&str> = tasks["tasks"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .map(|t| t["label"].as_str().unwrap())
+            .collect();
+        assert_eq!(labels, ["build", "k3dev: Database/Migrate"]);
+        assert_eq!(
+            tasks["tasks"][1]["args"],
+            json!([
+                "run",
+                "Database/Migrate",
+                "--set",
+                "version=${input:k3dev-database-migrate-version}",
+                "--set",
+                "env=${input:k3dev-database-migrate-env}"
+            ])
+        );
+        let inputs = tasks["inputs"].as_array().unwrap();
+        assert_eq!(inputs.len(), 2);
+        assert_eq!(inputs[1]["type"], "pickString");
+        assert_eq!(inputs[1]["default"], "dev");
+    }
+
+    #[test]
+    fn jetbrains_configs_prompt_in_the_terminal() {
+        let configs = jetbrains_run_configs(&groups(), Some(Path::new("/p/k3dev.yml")));
+        assert_eq!(configs.len(), 1);
+        let (file, xml) = &configs[0];
+        assert_eq!(file, "k3dev_database-migrate.run.xml");
+        assert!(xml.contains(r#"name="k3dev: Database/Migrate""#));
+        assert!(xml.contains("k3dev '--config' '/p/k3dev.yml' run 'Database/Migrate'"));
+        assert!(xml.contains("k3dev_env=${k3dev_env:-'dev'}"));
+        assert!(xml.contains("--set &quot;env=$k3dev_env&quot;"));
+    }
+}
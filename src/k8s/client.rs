@@ -7,6 +7,7 @@ use kube::{
 };
 use std::collections::HashMap;
 use std::path::Path;
+use std::sync::Arc;
 
 use super::jiff_to_chrono;
 use crate::config::expand_home;
@@ -64,6 +65,8 @@ pub struct PvcMetadata {
 #[derive(Clone)]
 pub struct K8sClient {
     client: Client,
+    /// Kept to build impersonating clients
+    config: Arc<Config>,
 }
 
 impl K8sClient {
@@ -81,9 +84,20 @@ impl K8sClient {
             Config::infer().await?
         };
 
-        let client = Client::try_from(config)?;
+        let client = Client::try_from(config.clone())?;
 
-        Ok(Self { client })
+        Ok(Self {
+            client,
+            config: Arc::new(config),
+        })
+    }
+
+    /// A client acting as another user, e.g. `system:serviceaccount:ns:name`
+    pub fn impersonating(&self, user: &str, groups: Vec<String>) -> Result<Client> {
+        let mut config = (*self.config).clone();
+        config.auth_info.impersonate = Some(user.to_string());
+        config.auth_info.impersonate_groups = Some(groups);
+        Client::try_from(config).context("Failed to create impersonating client")
     }
 
     /// Check if connected to cluster
@@ -0,0 +1,135 @@
+//! Minimal line diff (LCS-based) for showing edits before they are applied
+
+/// One line of a diff
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DiffLine {
+    Same(String),
+    Added(String),
+    Removed(String),
+}
+
+impl DiffLine {
+    /// Render with a unified-diff style prefix
+    pub fn to_prefixed(&self) -> String {
+        match self {
+            DiffLine::Same(l) => format!("  {}", l),
+            DiffLine::Added(l) => format!("+ {}", l),
+            DiffLine::Removed(l) => format!("- {}", l),
+        }
+    }
+}
+
+/// Diff two texts line by line
+pub fn line_diff(old: &str, new: &str) -> Vec<DiffLine> {
+    let a: Vec<&str> = old.lines().collect();
+    let b: Vec<&str> = new.lines().collect();
+
+    // lcs[i][j] = length of the LCS of a[i..] and b[j..]
+    let mut lcs = vec![vec![0usize; b.len() + 1]; a.len() + 1];
+    for i in (0..a.len()).rev() {
+        for j in (0..b.len()).rev() {
+            lcs[i][j] = if a[i] == b[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let mut out = Vec::with_capacity(a.len().max(b.len()));
+    let (mut i, mut j) = (0, 0);
+    while i < a.len() && j < b.len() {
+        if a[i] == b[j] {
+            out.push(DiffLine::Same(a[i].to_string()));
+            i += 1;
+            j += 1;
+        } else if lcs[i + 1][j] >= lcs[i][j + 1] {
+            out.push(DiffLine::Removed(a[i].to_string()));
+            i += 1;
+        } else {
+            out.push(DiffLine::Added(b[j].to_string()));
+            j += 1;
+        }
+    }
+    out.extend(a[i..].iter().map(|l| DiffLine::Removed(l.to_string())));
+    out.extend(b[j..].iter().map(|l| DiffLine::Added(l.to_string())));
+    out
+}
+
+/// Keep only changed lines plus `context` unchanged lines around each change.
+/// Skipped runs are replaced by a single `Same("...")` marker.
+pub fn with_context(diff: &[DiffLine], context: usize) -> Vec<DiffLine> {
+    let changed: Vec<usize> = diff
+        .iter()
+        .enumerate()
+        .filter(|(_, l)| !matches!(l, DiffLine::Same(_)))
+        .map(|(i, _)| i)
+        .collect();
+
+    let keep = |i: usize| {
+        changed
+            .iter()
+            .any(|&c| i + context >= c && i <= c + context)
+    };
+
+    let mut out = Vec::new();
+    let mut skipped = false;
+    for (i, line) in diff.iter().enumerate() {
+        if keep(i) {
+            if skipped {
+                out.push(DiffLine::Same("...".to_string()));
+                skipped = false;
+            }
+            out.push(line.clone());
+        } else {
+            skipped = true;
+        }
+    }
+    if skipped && !out.is_empty() {
+        out.push(DiffLine::Same("...".to_string()));
+    }
+    out
+}
+
+/// True when the diff contains at least one added or removed line
+pub fn has_changes(diff: &[DiffLine]) -> bool {
+    diff.iter().any(|l| !matches!(l, DiffLine::Same(_)))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn identical_texts_have_no_changes() {
+        let diff = line_diff("a\nb\n", "a\nb\n");
+        assert!(!has_changes(&diff));
+        assert_eq!(diff.len(), 2);
+    }
+
+    #[test]
+    fn detects_replaced_line() {
+        let diff = line_diff("a\nb\nc", "a\nx\nc");
+        assert_eq!(
+            diff,
+            vec![
+                DiffLine::Same("a".into()),
+                DiffLine::Removed("b".into()),
+                DiffLine::Added("x".into()),
+                DiffLine::Same("c".into()),
+            ]
+        );
+    }
+
+    #[test]
+    fn context_collapses_unchanged_runs() {
+        let old = "1\n2\n3\n4\n5\n6\n7";
+        let new = "1\n2\n3\nfour\n5\n6\n7";
+        let trimmed = with_context(&line_diff(old, new), 1);
+        let rendered: Vec<String> = trimmed.iter().map(|l| l.to_prefixed()).collect();
+        assert_eq!(
+            rendered,
+            vec!["  ...", "  3", "- 4", "+ four", "  5", "  ..."]
+        );
+    }
+}
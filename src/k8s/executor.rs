@@ -1,10 +1,11 @@
 use anyhow::{anyhow, Result};
 use k8s_openapi::api::core::v1::Pod;
+use k8s_openapi::apimachinery::pkg::apis::meta::v1::Status;
 use kube::{
     api::{Api, AttachParams},
     Client,
 };
-use tokio::io::AsyncReadExt;
+use tokio::io::{AsyncRead, AsyncReadExt, AsyncWriteExt};
 
 use super::client::{K8sClient, PodInfo};
 
@@ -107,10 +108,10 @@ impl PodExecutor {
 
         let status = attached.take_status();
         let exit_code = if let Some(status_future) = status {
-            match status_future.await {
-                Some(status) if status.status.as_deref() == Some("Success") => 0,
-                _ => 1,
-            }
+            status_future
+                .await
+                .as_ref()
+                .map_or(1, exit_code_from_status)
         } else {
             0
         };
@@ -122,6 +123,56 @@ impl PodExecutor {
         })
     }
 
+    /// Run `sh -c command` with `input` on its stdin, closed once written
+    pub async fn exec_with_stdin(
+        &self,
+        namespace: &str,
+        pod_name: &str,
+        container: Option<&str>,
+        command: &str,
+        input: Vec<u8>,
+    ) -> Result<ExecResult> {
+        let pods: Api<Pod> = Api::namespaced(self.client.clone(), namespace);
+
+        let attach_params = AttachParams {
+            container: container.map(String::from),
+            stdin: true,
+            stdout: true,
+            stderr: true,
+            tty: false,
+            ..Default::default()
+        };
+        let cmd_parts = vec!["sh".to_string(), "-c".to_string(), command.to_string()];
+        let mut attached = pods.exec(pod_name, cmd_parts, &attach_params).await?;
+
+        let stdin = attached.stdin();
+        let stdout = attached.stdout();
+        let stderr = attached.stderr();
+        // Written while the output is read, so a chatty command can't stall
+        // on a full pipe
+        let write = async move {
+            if let Some(mut stdin) = stdin {
+                stdin.write_all(&input).await?;
+                stdin.shutdown().await?;
+            }
+            Ok::<_, std::io::Error>(())
+        };
+        let (written, stdout, stderr) =
+            tokio::join!(write, read_stream(stdout), read_stream(stderr));
+        written?;
+
+        let exit_code = match attached.take_status() {
+            Some(status) => status.await.as_ref().map_or(1, exit_code_from_status),
+            None => 0,
+        };
+
+        Ok(ExecResult {
+            stdout: stdout?,
+            stderr: stderr?,
+            exit_code,
+        })
+    }
+
     /// Execute a simple command string
     pub async fn exec_simple(
         &self,
@@ -150,6 +201,35 @@ impl PodExecutor {
     }
 }
 
+/// Read an exec output stream to the end (empty if it wasn't attached)
+async fn read_stream(stream: Option<impl AsyncRead + Unpin>) -> std::io::Result<String> {
+    let mut buf = Vec::new();
+    if let Some(mut stream) = stream {
+        stream.read_to_end(&mut buf).await?;
+    }
+    Ok(String::from_utf8_lossy(&buf).to_string())
+}
+
+/// Extract the process exit code from an exec status.
+/// Non-zero exits are reported as a failure with an `ExitCode` cause.
+fn exit_code_from_status(status: &Status) -> i32 {
+    if status.status.as_deref() == Some("Success") {
+        return 0;
+    }
+    status
+        .details
+        .as_ref()
+        .and_then(|d| d.causes.as_ref())
+        .and_then(|causes| {
+            causes
+                .iter()
+                .find(|c| c.reason.as_deref() == Some("ExitCode"))
+        })
+        .and_then(|c| c.message.as_deref())
+        .and_then(|m| m.trim().parse().ok())
+        .unwrap_or(1)
+}
+
 fn pod_to_info(pod: &Pod) -> PodInfo {
     PodInfo {
         name: pod.metadata.name.clone().unwrap_or_default(),
@@ -169,3 +249,36 @@ fn pod_to_info(pod: &Pod) -> PodInfo {
         ip: pod.status.as_ref().and_then(|s| s.pod_ip.clone()),
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use k8s_openapi::apimachinery::pkg::apis::meta::v1::{StatusCause, StatusDetails};
+
+    fn failure(cause: Option<(&str, &str)>) -> Status {
+        Status {
+            status: Some("Failure".to_string()),
+            details: cause.map(|(reason, message)| StatusDetails {
+                causes: Some(vec![StatusCause {
+                    reason: Some(reason.to_string()),
+                    message: Some(message.to_string()),
+                    ..Default::default()
+                }]),
+                ..Default::default()
+            }),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn exit_code_is_read_from_status_cause() {
+        let success = Status {
+            status: Some("Success".to_string()),
+            ..Default::default()
+        };
+        assert_eq!(exit_code_from_status(&success), 0);
+        assert_eq!(exit_code_from_status(&failure(Some(("ExitCode", "3")))), 3);
+        assert_eq!(exit_code_from_status(&failure(None)), 1);
+        assert_eq!(exit_code_from_status(&failure(Some(("Other", "x")))), 1);
+    }
+}
@@ -0,0 +1,275 @@
+//! Mounting a host directory into a Deployment
+//!
+//! k3s runs pods on the host's Docker daemon, so a `hostPath` volume binds
+//! the host's own directory into the container, which makes it the quickest
+//! way to run local code in a cluster workload. Only directories inside one
+//! of the configured roots are accepted, after symlinks and `..` are
+//! resolved. Added volumes carry the `k3dev-host-` prefix, so unmounting
+//! removes exactly what was added and leaves the rest of the spec as it was.
+
+use std::path::{Path, PathBuf};
+
+use anyhow::{anyhow, bail, Context, Result};
+use k8s_openapi::api::apps::v1::Deployment;
+use k8s_openapi::api::core::v1::{HostPathVolumeSource, PodSpec, Volume, VolumeMount};
+use kube::{
+    api::{Api, PostParams},
+    Client,
+};
+
+use super::ResourceRef;
+
+/// Name prefix of the volumes added by a mount
+const VOLUME_PREFIX: &str = "k3dev-host-";
+
+/// Volume names are DNS labels
+const MAX_VOLUME_NAME: usize = 63;
+
+/// Resolve `path` and check it is a directory inside one of `roots`
+pub fn validate_host_path(path: &str, roots: &[PathBuf]) -> Result<PathBuf> {
+    let expanded = crate::config::expand_home(Path::new(path.trim()))?;
+    if !expanded.is_absolute() {
+        bail!("{} is not an absolute path", path);
+    }
+    let resolved = expanded
+        .canonicalize()
+        .with_context(|| format!("{} does not exist", expanded.display()))?;
+    if !resolved.is_dir() {
+        bail!("{} is not a directory", resolved.display());
+    }
+    let inside_root = roots.iter().any(|root| {
+        root.canonicalize()
+            .is_ok_and(|root| resolved != root && resolved.starts_with(&root))
+    });
+    if !inside_root {
+        bail!(
+            "{} is not inside an allowed root ({}); add one to host_mounts.allowed_roots",
+            resolved.display(),
+            roots
+                .iter()
+                .map(|r| r.display().to_string())
+                .collect::<Vec<_>>()
+                .join(", ")
+        );
+    }
+    Ok(resolved)
+}
+
+/// Check a container mount path: absolute, not `/`, without `..`
+pub fn validate_mount_path(path: &str) -> Result<String> {
+    let path = path.trim();
+    if !path.starts_with('/') {
+        bail!("Mount path must be absolute, e.g. /app");
+    }
+    let path = path.trim_end_matches('/');
+    if path.is_empty() {
+        bail!("Mount path must not be /");
+    }
+    if path.split('/').any(|part| part == "..") {
+        bail!("Mount path must not contain '..'");
+    }
+    Ok(path.to_string())
+}
+
+/// Volume name for a mount path (`/app/src` → `k3dev-host-app-src`)
+fn volume_name(mount_path: &str) -> String {
+    let slug: String = mount_path
+        .trim_matches('/')
+        .chars()
+        .map(|c| {
+            if c.is_ascii_alphanumeric() {
+                c.to_ascii_lowercase()
+            } else {
+                '-'
+            }
+        })
+        .collect();
+    let mut name = format!("{}{}", VOLUME_PREFIX, slug);
+    name.truncate(MAX_VOLUME_NAME);
+    name.trim_end_matches('-').to_string()
+}
+
+/// Add a hostPath volume and mount it into `container`. A previous mount at
+/// the same path is replaced; any other volume there is refused.
+fn add_host_mount(
+    spec: &mut PodSpec,
+    container: &str,
+    host_path: &Path,
+    mount_path: &str,
+) -> Result<()> {
+    let name = volume_name(mount_path);
+    let target = spec
+        .containers
+        .iter_mut()
+        .find(|c| c.name == container)
+        .ok_or_else(|| anyhow!("No container named {}", container))?;
+    let mounts = target.volume_mounts.get_or_insert_with(Vec::new);
+    if let Some(existing) = mounts
+        .iter()
+        .find(|m| m.mount_path.trim_end_matches('/') == mount_path)
+    {
+        if !existing.name.starts_with(VOLUME_PREFIX) {
+            bail!(
+                "{} is already mounted from volume {}",
+                mount_path,
+                existing.name
+            );
+        }
+    }
+    mounts.retain(|m| m.name != name);
+    mounts.push(VolumeMount {
+        name: name.clone(),
+        mount_path: mount_path.to_string(),
+        ..Default::default()
+    });
+
+    let volumes = spec.volumes.get_or_insert_with(Vec::new);
+    volumes.retain(|v| v.name != name);
+    volumes.push(Volume {
+        name,
+        host_path: Some(HostPathVolumeSource {
+            path: host_path.display().to_string(),
+            // No type: the kubelet runs in the k3s container and can't see
+            // the host directory, Docker binds it
+            type_: None,
+        }),
+        ..Default::default()
+    });
+    Ok(())
+}
+
+/// Remove every mount added by `add_host_mount`; returns how many volumes
+fn remove_host_mounts(spec: &mut PodSpec) -> usize {
+    let is_ours = |name: &str| name.starts_with(VOLUME_PREFIX);
+    for container in spec
+        .containers
+        .iter_mut()
+        .chain(spec.init_containers.iter_mut().flatten())
+    {
+        if let Some(mounts) = &mut container.volume_mounts {
+            mounts.retain(|m| !is_ours(&m.name));
+        }
+    }
+    let Some(volumes) = &mut spec.volumes else {
+        return 0;
+    };
+    let before = volumes.len();
+    volumes.retain(|v| !is_ours(&v.name));
+    before - volumes.len()
+}
+
+/// Mount `host_path` at `mount_path` in one container of a Deployment
+pub async fn mount_host_path(
+    client: &Client,
+    deployment: &ResourceRef,
+    container: &str,
+    host_path: &Path,
+    mount_path: &str,
+) -> Result<()> {
+    update_pod_spec(client, deployment, |spec| {
+        add_host_mount(spec, container, host_path, mount_path)
+    })
+    .await
+}
+
+/// Remove the host mounts of a Deployment; returns how many there were
+pub async fn unmount_host_paths(client: &Client, deployment: &ResourceRef) -> Result<usize> {
+    let mut removed = 0;
+    update_pod_spec(client, deployment, |spec| {
+        removed = remove_host_mounts(spec);
+        Ok(())
+    })
+    .await?;
+    Ok(removed)
+}
+
+/// Change a Deployment's pod template and replace it; the resourceVersion
+/// of the read makes the write fail if someone changed it in between
+async fn update_pod_spec(
+    client: &Client,
+    deployment: &ResourceRef,
+    change: impl FnOnce(&mut PodSpec) -> Result<()>,
+) -> Result<()> {
+    let api: Api<Deployment> = Api::namespaced(client.clone(), &deployment.namespace);
+    let mut found = api
+        .get(&deployment.name)
+        .await
+        .with_context(|| format!("Failed to get {}", deployment))?;
+    let spec = found
+        .spec
+        .as_mut()
+        .and_then(|s| s.template.spec.as_mut())
+        .ok_or_else(|| anyhow!("{} has no pod template", deployment))?;
+    change(spec)?;
+    api.replace(&deployment.name, &PostParams::default(), &found)
+        .await
+        .with_context(|| format!("Failed to update {}", deployment))?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use k8s_openapi::api::core::v1::Container;
+
+    fn spec() -> PodSpec {
+        PodSpec {
+            containers: vec![Container {
+                name: "web".to_string(),
+                volume_mounts: Some(vec![VolumeMount {
+                    name: "config".to_string(),
+                    mount_path: "/etc/web".to_string(),
+                    ..Default::default()
+                }]),
+                ..Default::default()
+            }],
+            volumes: Some(vec![Volume {
+                name: "config".to_string(),
+                ..Default::default()
+            }]),
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn unmount_restores_the_original_spec() {
+        let original = spec();
+        let mut mounted = original.clone();
+        add_host_mount(&mut mounted, "web", Path::new("/home/me/app"), "/app/src").unwrap();
+        // Mounting the same path again replaces the previous mount
+        add_host_mount(&mut mounted, "web", Path::new("/home/me/app2"), "/app/src").unwrap();
+        let volumes = mounted.volumes.as_ref().unwrap();
+        assert_eq!(volumes.len(), 2);
+        assert_eq!(volumes[1].name, "k3dev-host-app-src");
+        assert_eq!(volumes[1].host_path.as_ref().unwrap().path, "/home/me/app2");
+
+        assert_eq!(remove_host_mounts(&mut mounted), 1);
+        assert_eq!(mounted, original);
+    }
+
+    #[test]
+    fn mounts_over_other_volumes_are_refused() {
+        let mut spec = spec();
+        assert!(add_host_mount(&mut spec, "web", Path::new("/x"), "/etc/web").is_err());
+        assert!(add_host_mount(&mut spec, "db", Path::new("/x"), "/data").is_err());
+    }
+
+    #[test]
+    fn host_paths_must_be_inside_a_root() {
+        let root = std::env::temp_dir().join(format!("k3dev-mount-{}", std::process::id()));
+        std::fs::create_dir_all(root.join("project")).unwrap();
+        let roots = vec![root.clone()];
+        let inside = root.join("project");
+        let escaping = format!("{}/project/../..", root.display());
+
+        assert!(validate_host_path(&inside.display().to_string(), &roots).is_ok());
+        assert!(validate_host_path(&root.display().to_string(), &roots).is_err());
+        assert!(validate_host_path(&escaping, &roots).is_err());
+        assert!(validate_host_path("relative/dir", &roots).is_err());
+        let _ = std::fs::remove_dir_all(&root);
+
+        assert_eq!(validate_mount_path("/app/").unwrap(), "/app");
+        assert!(validate_mount_path("app").is_err());
+        assert!(validate_mount_path("/app/../etc").is_err());
+    }
+}
@@ -0,0 +1,220 @@
+//! Swapping a Deployment's container image and following the rollout
+//!
+//! k3s runs pods on the host's Docker daemon, so any image built locally can
+//! be used directly; the override also sets `imagePullPolicy: IfNotPresent`
+//! so the kubelet doesn't try to pull a tag that only exists locally.
+
+use anyhow::{anyhow, Context, Result};
+use k8s_openapi::api::apps::v1::Deployment;
+use kube::{
+    api::{Api, Patch, PatchParams},
+    Client,
+};
+
+use super::ResourceRef;
+
+/// A container of a Deployment's pod template
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ContainerImage {
+    pub container: String,
+    pub image: String,
+}
+
+/// Rollout progress of a Deployment
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RolloutStatus {
+    pub desired: i32,
+    pub updated: i32,
+    pub available: i32,
+    pub unavailable: i32,
+    /// The controller has seen the latest spec
+    pub observed: bool,
+}
+
+impl RolloutStatus {
+    pub fn from_deployment(deployment: &Deployment) -> Self {
+        let desired = deployment
+            .spec
+            .as_ref()
+            .and_then(|s| s.replicas)
+            .unwrap_or(1);
+        let status = deployment.status.clone().unwrap_or_default();
+        let observed = match (deployment.metadata.generation, status.observed_generation) {
+            (Some(generation), Some(observed)) => observed >= generation,
+            _ => false,
+        };
+        Self {
+            desired,
+            updated: status.updated_replicas.unwrap_or(0),
+            available: status.available_replicas.unwrap_or(0),
+            unavailable: status.unavailable_replicas.unwrap_or(0),
+            observed,
+        }
+    }
+
+    /// All replicas run the new template and are available
+    pub fn is_complete(&self) -> bool {
+        self.observed
+            && self.updated >= self.desired
+            && self.available >= self.desired
+            && self.unavailable == 0
+    }
+
+    /// `kubectl rollout status` style progress line
+    pub fn describe(&self) -> String {
+        format!(
+            "{} of {} updated replicas available ({} unavailable)",
+            self.available.min(self.updated),
+            self.desired,
+            self.unavailable
+        )
+    }
+}
+
+/// Repository part of an image reference, normalized the way Docker names
+/// local images (`docker.io/library/nginx:1.27` → `nginx`)
+pub fn image_repository(image: &str) -> &str {
+    let image = image.split('@').next().unwrap_or(image);
+    let name_start = image.rfind('/').map(|i| i + 1).unwrap_or(0);
+    let repo = match image[name_start..].rfind(':') {
+        Some(i) => &image[..name_start + i],
+        None => image,
+    };
+    repo.strip_prefix("docker.io/library/")
+        .or_else(|| repo.strip_prefix("docker.io/"))
+        .unwrap_or(repo)
+}
+
+/// The name Docker lists a local image under, with the implicit `latest` tag
+/// spelled out (`docker.io/library/nginx` → `nginx:latest`)
+pub fn local_image_name(image: &str) -> String {
+    let repo = image_repository(image);
+    let without_digest = image.split('@').next().unwrap_or(image);
+    let name_start = without_digest.rfind('/').map(|i| i + 1).unwrap_or(0);
+    let tag = without_digest[name_start..]
+        .rsplit_once(':')
+        .map(|(_, tag)| tag)
+        .unwrap_or("latest");
+    format!("{}:{}", repo, tag)
+}
+
+/// Containers of a Deployment with their current images
+pub async fn deployment_images(
+    client: &Client,
+    deployment: &ResourceRef,
+) -> Result<Vec<ContainerImage>> {
+    let api: Api<Deployment> = Api::namespaced(client.clone(), &deployment.namespace);
+    let found = api
+        .get(&deployment.name)
+        .await
+        .with_context(|| format!("Failed to get {}", deployment))?;
+    let containers = found
+        .spec
+        .and_then(|s| s.template.spec)
+        .map(|s| s.containers)
+        .unwrap_or_default();
+    if containers.is_empty() {
+        return Err(anyhow!("{} has no containers", deployment));
+    }
+    Ok(containers
+        .into_iter()
+        .map(|c| ContainerImage {
+            container: c.name,
+            image: c.image.unwrap_or_default(),
+        })
+        .collect())
+}
+
+/// Point one container of a Deployment at a new image
+pub async fn set_container_image(
+    client: &Client,
+    deployment: &ResourceRef,
+    container: &str,
+    image: &str,
+) -> Result<()> {
+    let api: Api<Deployment> = Api::namespaced(client.clone(), &deployment.namespace);
+    let patch = serde_json::json!({
+        "spec": { "template": { "spec": { "containers": [{
+            "name": container,
+            "image": image,
+            "imagePullPolicy": "IfNotPresent",
+        }]}}}
+    });
+    api.patch(
+        &deployment.name,
+        &PatchParams::default(),
+        &Patch::Strategic(&patch),
+    )
+    .await
+    .with_context(|| format!("Failed to set the image of {}", deployment))?;
+    Ok(())
+}
+
+pub async fn rollout_status(client: &Client, deployment: &ResourceRef) -> Result<RolloutStatus> {
+    let api: Api<Deployment> = Api::namespaced(client.clone(), &deployment.namespace);
+    let found = api
+        .get(&deployment.name)
+        .await
+        .with_context(|| format!("Failed to get {}", deployment))?;
+    Ok(RolloutStatus::from_deployment(&found))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use k8s_openapi::api::apps::v1::{DeploymentSpec, DeploymentStatus};
+
+    #[test]
+    fn normalizes_image_repositories() {
+        assert_eq!(image_repository("web:dev"), "web");
+        assert_eq!(image_repository("docker.io/library/nginx:1.27"), "nginx");
+        assert_eq!(image_repository("docker.io/acme/api:1.0"), "acme/api");
+        assert_eq!(
+            image_repository("localhost:5000/api:1.0"),
+            "localhost:5000/api"
+        );
+        assert_eq!(image_repository("localhost:5000/api"), "localhost:5000/api");
+        assert_eq!(image_repository("api@sha256:abc"), "api");
+        assert_eq!(local_image_name("docker.io/library/nginx"), "nginx:latest");
+        assert_eq!(
+            local_image_name("localhost:5000/api:1.0"),
+            "localhost:5000/api:1.0"
+        );
+    }
+
+    #[test]
+    fn rollout_completes_when_all_replicas_are_updated() {
+        let mut deployment = Deployment {
+            spec: Some(DeploymentSpec {
+                replicas: Some(2),
+                ..Default::default()
+            }),
+            status: Some(DeploymentStatus {
+                observed_generation: Some(3),
+                updated_replicas: Some(1),
+                available_replicas: Some(2),
+                unavailable_replicas: Some(1),
+                ..Default::default()
+            }),
+            ..Default::default()
+        };
+        deployment.metadata.generation = Some(3);
+        let status = RolloutStatus::from_deployment(&deployment);
+        assert!(!status.is_complete());
+        assert_eq!(
+            status.describe(),
+            "1 of 2 updated replicas available (1 unavailable)"
+        );
+
+        deployment.status = Some(DeploymentStatus {
+            observed_generation: Some(3),
+            updated_replicas: Some(2),
+            available_replicas: Some(2),
+            ..Default::default()
+        });
+        assert!(RolloutStatus::from_deployment(&deployment).is_complete());
+
+        deployment.metadata.generation = Some(4);
+        assert!(!RolloutStatus::from_deployment(&deployment).is_complete());
+    }
+}
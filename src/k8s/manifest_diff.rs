@@ -0,0 +1,325 @@
+//! Diff of the configured manifests against the cluster
+//!
+//! Every object in the manifest directories goes through a server-side apply
+//! dry run, and the object the server would store is diffed against the live
+//! one, so defaulting, admission webhooks and field ownership are accounted
+//! for the way `kubectl diff --server-side` does. A directory with a
+//! kustomization is rendered with `kubectl kustomize` first.
+
+use std::path::{Path, PathBuf};
+
+use anyhow::{anyhow, bail, Context, Result};
+use kube::{
+    api::{Api, DynamicObject, Patch, PatchParams},
+    core::GroupVersionKind,
+    discovery::{Discovery, Scope},
+    Client,
+};
+use serde::Deserialize;
+use tokio::sync::mpsc;
+
+use super::diff::{self, DiffLine};
+use super::resource_edit::strip_server_fields;
+use crate::ui::components::OutputLine;
+
+const FIELD_MANAGER: &str = "k3dev";
+
+/// Unchanged lines shown around each change
+const DIFF_CONTEXT: usize = 3;
+
+const KUSTOMIZATION_FILES: [&str; 3] = ["kustomization.yaml", "kustomization.yml", "Kustomization"];
+
+/// How the objects of a diff run compare with the cluster
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq)]
+pub struct DiffSummary {
+    pub changed: usize,
+    pub created: usize,
+    pub unchanged: usize,
+    /// Objects the server refused, e.g. by an admission policy
+    pub failed: usize,
+}
+
+impl DiffSummary {
+    pub fn is_clean(&self) -> bool {
+        self.changed == 0 && self.created == 0 && self.failed == 0
+    }
+}
+
+/// Diff every object of `dirs`, streaming a colored diff per changed object
+pub async fn diff_manifests(
+    client: &Client,
+    dirs: &[PathBuf],
+    default_namespace: &str,
+    output_tx: &mpsc::Sender<OutputLine>,
+) -> Result<DiffSummary> {
+    if dirs.is_empty() {
+        bail!("No manifest directories configured (set `manifests:` in the config)");
+    }
+    let mut objects = Vec::new();
+    for dir in dirs {
+        let rendered = render_dir(dir).await?;
+        objects.extend(
+            parse_documents(&rendered)
+                .with_context(|| format!("Invalid YAML in {}", dir.display()))?,
+        );
+    }
+    let _ = output_tx
+        .send(OutputLine::info(format!(
+            "Comparing {} objects with the cluster...",
+            objects.len()
+        )))
+        .await;
+
+    let discovery = Discovery::new(client.clone())
+        .run()
+        .await
+        .context("Failed to discover the cluster's API resources")?;
+
+    let mut summary = DiffSummary::default();
+    for obj in objects {
+        let label = object_label(&obj, default_namespace);
+        match diff_object(client, &discovery, obj, default_namespace).await {
+            Ok(ObjectDiff::Unchanged) => summary.unchanged += 1,
+            Ok(ObjectDiff::Created(lines)) => {
+                summary.created += 1;
+                let _ = output_tx
+                    .send(OutputLine::success(format!("+++ {} (new)", label)))
+                    .await;
+                send_diff(output_tx, &lines).await;
+            }
+            Ok(ObjectDiff::Changed(lines)) => {
+                summary.changed += 1;
+                let _ = output_tx
+                    .send(OutputLine::warning(format!("~~~ {}", label)))
+                    .await;
+                send_diff(output_tx, &lines).await;
+            }
+            Err(e) => {
+                summary.failed += 1;
+                let _ = output_tx
+                    .send(OutputLine::error(format!("✗ {}: {:#}", label, e)))
+                    .await;
+            }
+        }
+    }
+
+    let _ = output_tx
+        .send(OutputLine::info(format!(
+            "{} changed, {} new, {} unchanged, {} rejected",
+            summary.changed, summary.created, summary.unchanged, summary.failed
+        )))
+        .await;
+    Ok(summary)
+}
+
+enum ObjectDiff {
+    Unchanged,
+    Created(Vec<DiffLine>),
+    Changed(Vec<DiffLine>),
+}
+
+async fn diff_object(
+    client: &Client,
+    discovery: &Discovery,
+    mut obj: DynamicObject,
+    default_namespace: &str,
+) -> Result<ObjectDiff> {
+    let types = obj
+        .types
+        .as_ref()
+        .ok_or_else(|| anyhow!("missing apiVersion/kind"))?;
+    let gvk = GroupVersionKind::try_from(types)?;
+    let (resource, caps) = discovery
+        .resolve_gvk(&gvk)
+        .ok_or_else(|| anyhow!("the cluster has no {} resource", gvk.kind))?;
+    let name = obj
+        .metadata
+        .name
+        .clone()
+        .ok_or_else(|| anyhow!("missing metadata.name"))?;
+
+    let api: Api<DynamicObject> = if caps.scope == Scope::Namespaced {
+        let namespace = obj
+            .metadata
+            .namespace
+            .get_or_insert_with(|| default_namespace.to_string())
+            .clone();
+        Api::namespaced_with(client.clone(), &namespace, &resource)
+    } else {
+        Api::all_with(client.clone(), &resource)
+    };
+
+    let live = api.get_opt(&name).await?;
+    let params = PatchParams::apply(FIELD_MANAGER).dry_run().force();
+    let dry_run = api.patch(&name, &params, &Patch::Apply(&obj)).await?;
+
+    let after = comparable_yaml(&dry_run)?;
+    let Some(live) = live else {
+        return Ok(ObjectDiff::Created(diff::line_diff("", &after)));
+    };
+    let changes = diff::line_diff(&comparable_yaml(&live)?, &after);
+    if diff::has_changes(&changes) {
+        Ok(ObjectDiff::Changed(diff::with_context(
+            &changes,
+            DIFF_CONTEXT,
+        )))
+    } else {
+        Ok(ObjectDiff::Unchanged)
+    }
+}
+
+/// YAML of an object without the fields every write changes
+fn comparable_yaml(obj: &DynamicObject) -> Result<String> {
+    let mut value = serde_json::to_value(obj)?;
+    strip_server_fields(&mut value);
+    if let Some(meta) = value.get_mut("metadata").and_then(|m| m.as_object_mut()) {
+        meta.remove("resourceVersion");
+    }
+    Ok(serde_yml::to_string(&value)?)
+}
+
+async fn send_diff(output_tx: &mpsc::Sender<OutputLine>, lines: &[DiffLine]) {
+    for line in lines {
+        let content = line.to_prefixed();
+        let _ = output_tx
+            .send(match line {
+                DiffLine::Added(_) => OutputLine::success(content),
+                DiffLine::Removed(_) => OutputLine::error(content),
+                DiffLine::Same(_) => OutputLine::info(content),
+            })
+            .await;
+    }
+}
+
+fn object_label(obj: &DynamicObject, default_namespace: &str) -> String {
+    let kind = obj.types.as_ref().map(|t| t.kind.as_str()).unwrap_or("?");
+    let name = obj.metadata.name.as_deref().unwrap_or("?");
+    match &obj.metadata.namespace {
+        Some(ns) => format!("{} {}/{}", kind, ns, name),
+        None if kind == "Namespace" || kind.starts_with("Cluster") => format!("{} {}", kind, name),
+        None => format!("{} {}/{}", kind, default_namespace, name),
+    }
+}
+
+/// All manifests of a directory as one multi-document YAML
+async fn render_dir(dir: &Path) -> Result<String> {
+    if KUSTOMIZATION_FILES.iter().any(|f| dir.join(f).is_file()) {
+        let output = tokio::process::Command::new("kubectl")
+            .arg("kustomize")
+            .arg(dir)
+            .output()
+            .await
+            .context("Failed to run kubectl kustomize")?;
+        if !output.status.success() {
+            bail!(
+                "kubectl kustomize {} failed: {}",
+                dir.display(),
+                String::from_utf8_lossy(&output.stderr).trim()
+            );
+        }
+        return Ok(String::from_utf8_lossy(&output.stdout).into_owned());
+    }
+
+    let mut documents = Vec::new();
+    for file in manifest_files(dir)? {
+        documents.push(
+            std::fs::read_to_string(&file)
+                .with_context(|| format!("Failed to read {}", file.display()))?,
+        );
+    }
+    Ok(documents.join("\n---\n"))
+}
+
+/// YAML and JSON files under `dir`, recursively, in path order
+fn manifest_files(dir: &Path) -> Result<Vec<PathBuf>> {
+    let mut files = Vec::new();
+    let entries =
+        std::fs::read_dir(dir).with_context(|| format!("Failed to read {}", dir.display()))?;
+    for entry in entries {
+        let path = entry?.path();
+        if path.is_dir() {
+            files.extend(manifest_files(&path)?);
+        } else if path
+            .extension()
+            .and_then(|e| e.to_str())
+            .is_some_and(|e| matches!(e, "yaml" | "yml" | "json"))
+        {
+            files.push(path);
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+/// Objects of a multi-document YAML; empty documents are skipped and `List`
+/// documents are expanded
+fn parse_documents(yaml: &str) -> Result<Vec<DynamicObject>> {
+    let mut objects = Vec::new();
+    for document in serde_yml::Deserializer::from_str(yaml) {
+        let value = serde_json::Value::deserialize(document)?;
+        if value.is_null() {
+            continue;
+        }
+        if value.get("kind").and_then(|k| k.as_str()) == Some("List") {
+            for item in value["items"].as_array().into_iter().flatten() {
+                objects.push(serde_json::from_value(item.clone())?);
+            }
+            continue;
+        }
+        objects.push(serde_json::from_value(value)?);
+    }
+    Ok(objects)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn documents_are_split_and_lists_expanded() {
+        let yaml = "apiVersion: v1
+kind: ConfigMap
+metadata:
+  name: settings
+---
+---
+apiVersion: v1
+kind: List
+items:
+  - apiVersion: v1
+    kind: Service
+    metadata:
+      name: web
+  - apiVersion: v1
+    kind: Namespace
+    metadata:
+      name: apps
+";
+        let objects = parse_documents(yaml).unwrap();
+        let labels: Vec<String> = objects.iter().map(|o| object_label(o, "default")).collect();
+        assert_eq!(
+            labels,
+            vec![
+                "ConfigMap default/settings",
+                "Service default/web",
+                "Namespace apps"
+            ]
+        );
+    }
+
+    #[test]
+    fn manifest_files_are_found_recursively_in_order() {
+        let dir = std::env::temp_dir().join(format!("k3dev-manifests-{}", std::process::id()));
+        std::fs::create_dir_all(dir.join("base")).unwrap();
+        for file in ["b.yaml", "a.yml", "notes.md", "base/c.json"] {
+            std::fs::write(dir.join(file), "").unwrap();
+        }
+        let files: Vec<String> = manifest_files(&dir)
+            .unwrap()
+            .iter()
+            .map(|f| f.strip_prefix(&dir).unwrap().display().to_string())
+            .collect();
+        let _ = std::fs::remove_dir_all(&dir);
+        assert_eq!(files, vec!["a.yml", "b.yaml", "base/c.json"]);
+    }
+}
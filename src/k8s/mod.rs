@@ -1,10 +1,33 @@
 mod client;
+pub mod dependency_map;
+pub mod diff;
 mod executor;
+pub mod host_mount;
+pub mod image_override;
+pub mod manifest_diff;
+pub mod namespaces;
+pub mod network_policy;
+pub mod pod_diagnosis;
+pub mod pod_killer;
+pub mod policy_violations;
+pub mod pull_secret;
+pub mod quotas;
+pub mod rbac;
+pub mod resource_edit;
+pub mod scaffold;
+pub mod seeds;
 pub mod shell_session;
 pub mod timeline;
 
 pub use client::{K8sClient, PendingPodInfo, PvcInfo};
+pub use dependency_map::{get_dependency_map, NamespaceDependencies};
 pub use executor::PodExecutor;
+pub use namespaces::NamespaceDeletion;
+pub use network_policy::{get_network_policies, NamespacePolicies};
+pub use policy_violations::{get_policy_violations, PolicyViolation};
+pub use quotas::{get_quotas, NamespaceQuotas};
+pub use rbac::{can_i, get_rbac, CanIAnswer, CanIQuery, NamespaceRbac};
+pub use resource_edit::{ApplyOutcome, EditableKind, ResourceRef};
 pub use shell_session::ShellSessionHandle;
 pub use timeline::{get_pod_timeline, PodTimeline};
 
@@ -0,0 +1,209 @@
+//! Namespace creation and deletion
+//!
+//! Deleting a namespace only starts its termination; the API server removes
+//! it once every resource inside is gone and its finalizers have run. A
+//! controller that is no longer installed can leave it `Terminating` forever,
+//! so a stuck deletion can be forced by clearing the finalizers.
+
+use std::time::Duration;
+
+use anyhow::{anyhow, Context, Result};
+use k8s_openapi::api::core::v1::{Namespace, NamespaceSpec};
+use kube::{
+    api::{Api, DeleteParams, Patch, PatchParams, PostParams},
+    Client,
+};
+
+/// Namespaces the cluster needs; they are never offered for deletion
+pub const PROTECTED_NAMESPACES: &[&str] =
+    &["default", "kube-system", "kube-public", "kube-node-lease"];
+
+/// How long to wait for a deleted namespace to disappear
+const DELETE_WAIT: Duration = Duration::from_secs(30);
+
+/// Condition types set on a namespace whose termination is blocked
+const BLOCKING_CONDITIONS: &[&str] = &[
+    "NamespaceDeletionDiscoveryFailure",
+    "NamespaceDeletionContentFailure",
+    "NamespaceContentRemaining",
+    "NamespaceFinalizersRemaining",
+];
+
+/// Result of deleting a namespace
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum NamespaceDeletion {
+    Deleted,
+    /// Still terminating after the wait, with what the API server says blocks it
+    Stuck(Vec<String>),
+}
+
+/// Check a namespace name is a valid DNS-1123 label
+pub fn validate_namespace_name(name: &str) -> Result<(), String> {
+    if name.is_empty() || name.len() > 63 {
+        return Err("Namespace names must be 1-63 characters".to_string());
+    }
+    let valid_chars = name
+        .chars()
+        .all(|c| c.is_ascii_lowercase() || c.is_ascii_digit() || c == '-');
+    if !valid_chars || name.starts_with('-') || name.ends_with('-') {
+        return Err(format!(
+            "'{}' is not a valid namespace name: use lowercase letters, digits and '-', \
+             starting and ending with a letter or digit",
+            name
+        ));
+    }
+    Ok(())
+}
+
+pub async fn create_namespace(client: &Client, name: &str) -> Result<()> {
+    let namespaces: Api<Namespace> = Api::all(client.clone());
+    let namespace = Namespace {
+        metadata: kube::api::ObjectMeta {
+            name: Some(name.to_string()),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    namespaces
+        .create(&PostParams::default(), &namespace)
+        .await
+        .with_context(|| format!("Failed to create namespace {}", name))?;
+    Ok(())
+}
+
+/// Delete a namespace and wait for it to go away
+pub async fn delete_namespace(client: &Client, name: &str) -> Result<NamespaceDeletion> {
+    if PROTECTED_NAMESPACES.contains(&name) {
+        return Err(anyhow!("Namespace {} is required by the cluster", name));
+    }
+    let namespaces: Api<Namespace> = Api::all(client.clone());
+    namespaces
+        .delete(name, &DeleteParams::default())
+        .await
+        .with_context(|| format!("Failed to delete namespace {}", name))?;
+    wait_for_deletion(&namespaces, name).await
+}
+
+/// Clear the finalizers of a namespace stuck terminating, then wait again.
+/// Resources the finalizers were protecting may be left behind.
+pub async fn force_finalize_namespace(client: &Client, name: &str) -> Result<NamespaceDeletion> {
+    let namespaces: Api<Namespace> = Api::all(client.clone());
+    let mut namespace = namespaces
+        .get(name)
+        .await
+        .with_context(|| format!("Failed to get namespace {}", name))?;
+
+    if namespace.metadata.finalizers.is_some() {
+        let patch = serde_json::json!({ "metadata": { "finalizers": null } });
+        namespaces
+            .patch(name, &PatchParams::default(), &Patch::Merge(&patch))
+            .await
+            .with_context(|| format!("Failed to clear finalizers of {}", name))?;
+    }
+
+    // spec.finalizers can only be changed through the finalize subresource
+    namespace.metadata.managed_fields = None;
+    namespace.spec = Some(NamespaceSpec {
+        finalizers: Some(Vec::new()),
+    });
+    namespaces
+        .replace_subresource("finalize", name, &PostParams::default(), &namespace)
+        .await
+        .with_context(|| format!("Failed to finalize namespace {}", name))?;
+
+    wait_for_deletion(&namespaces, name).await
+}
+
+async fn wait_for_deletion(namespaces: &Api<Namespace>, name: &str) -> Result<NamespaceDeletion> {
+    let deadline = tokio::time::Instant::now() + DELETE_WAIT;
+    loop {
+        let Some(namespace) = namespaces.get_opt(name).await? else {
+            return Ok(NamespaceDeletion::Deleted);
+        };
+        if tokio::time::Instant::now() >= deadline {
+            return Ok(NamespaceDeletion::Stuck(deletion_blockers(&namespace)));
+        }
+        tokio::time::sleep(Duration::from_secs(1)).await;
+    }
+}
+
+/// What keeps a terminating namespace around: the messages of its blocking
+/// conditions, or else its pending finalizers
+fn deletion_blockers(namespace: &Namespace) -> Vec<String> {
+    let status = namespace.status.as_ref();
+    let conditions: Vec<String> = status
+        .and_then(|s| s.conditions.as_ref())
+        .into_iter()
+        .flatten()
+        .filter(|c| c.status == "True" && BLOCKING_CONDITIONS.contains(&c.type_.as_str()))
+        .filter_map(|c| c.message.clone())
+        .collect();
+    if !conditions.is_empty() {
+        return conditions;
+    }
+    let spec_finalizers = namespace
+        .spec
+        .as_ref()
+        .and_then(|s| s.finalizers.clone())
+        .unwrap_or_default();
+    let meta_finalizers = namespace.metadata.finalizers.clone().unwrap_or_default();
+    spec_finalizers
+        .into_iter()
+        .chain(meta_finalizers)
+        .map(|f| format!("Waiting for finalizer {}", f))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use k8s_openapi::api::core::v1::{NamespaceCondition, NamespaceStatus};
+
+    #[test]
+    fn validates_names() {
+        assert!(validate_namespace_name("team-a").is_ok());
+        assert!(validate_namespace_name("a1").is_ok());
+        assert!(validate_namespace_name("").is_err());
+        assert!(validate_namespace_name("Team").is_err());
+        assert!(validate_namespace_name("-team").is_err());
+        assert!(validate_namespace_name("team_a").is_err());
+        assert!(validate_namespace_name(&"a".repeat(64)).is_err());
+    }
+
+    #[test]
+    fn reports_what_blocks_deletion() {
+        let condition = |type_: &str, status: &str, message: &str| NamespaceCondition {
+            type_: type_.to_string(),
+            status: status.to_string(),
+            message: Some(message.to_string()),
+            ..Default::default()
+        };
+        let mut namespace = Namespace {
+            spec: Some(NamespaceSpec {
+                finalizers: Some(vec!["kubernetes".to_string()]),
+            }),
+            status: Some(NamespaceStatus {
+                conditions: Some(vec![
+                    condition("NamespaceDeletionDiscoveryFailure", "False", "ok"),
+                    condition(
+                        "NamespaceFinalizersRemaining",
+                        "True",
+                        "Some content has finalizers remaining: example.com/cleanup in 1 resource instances",
+                    ),
+                ]),
+                phase: Some("Terminating".to_string()),
+            }),
+            ..Default::default()
+        };
+        assert_eq!(
+            deletion_blockers(&namespace),
+            ["Some content has finalizers remaining: example.com/cleanup in 1 resource instances"]
+        );
+
+        namespace.status = None;
+        assert_eq!(
+            deletion_blockers(&namespace),
+            ["Waiting for finalizer kubernetes"]
+        );
+    }
+}
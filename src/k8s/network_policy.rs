@@ -0,0 +1,372 @@
+//! NetworkPolicy overview — which workloads of a namespace may talk to which
+//!
+//! Pods are grouped into workloads (by their `app` labels or owner), and each
+//! pair is evaluated against the namespace's policies the way the policy
+//! controller would: a pod selected by a policy of a direction only accepts
+//! traffic one of those policies allows. Ports and `ipBlock` peers are not
+//! considered, and only traffic within the namespace is shown.
+
+use std::collections::BTreeMap;
+
+use anyhow::{Context, Result};
+use k8s_openapi::api::core::v1::{Namespace, Pod};
+use k8s_openapi::api::networking::v1::{NetworkPolicy, NetworkPolicyPeer};
+use k8s_openapi::apimachinery::pkg::apis::meta::v1::LabelSelector;
+use kube::{
+    api::{Api, ListParams},
+    Client,
+};
+
+type Labels = BTreeMap<String, String>;
+
+/// Policies and the resulting connectivity of one namespace
+#[derive(Debug, Clone, PartialEq)]
+pub struct NamespacePolicies {
+    pub namespace: String,
+    pub policies: Vec<PolicySummary>,
+    pub workloads: Vec<Workload>,
+    /// `allowed[from][to]` over `workloads`
+    pub allowed: Vec<Vec<bool>>,
+}
+
+/// One NetworkPolicy
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PolicySummary {
+    pub name: String,
+    /// Workloads the policy applies to
+    pub selects: usize,
+    /// "Ingress" and/or "Egress"
+    pub types: Vec<String>,
+}
+
+/// Pods sharing a workload name
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Workload {
+    pub name: String,
+    pub pods: usize,
+    pub ingress_isolated: bool,
+    pub egress_isolated: bool,
+}
+
+impl NamespacePolicies {
+    /// Workloads no other workload of the namespace (nor itself) may reach
+    pub fn unreachable(&self, to: usize) -> bool {
+        self.workloads[to].ingress_isolated && self.allowed.iter().all(|row| !row[to])
+    }
+}
+
+/// Fetch policies, pods and namespaces and evaluate every namespace that has
+/// pods or policies
+pub async fn get_network_policies(client: &Client) -> Result<Vec<NamespacePolicies>> {
+    let params = ListParams::default();
+    let policies: Api<NetworkPolicy> = Api::all(client.clone());
+    let pods: Api<Pod> = Api::all(client.clone());
+    let namespaces: Api<Namespace> = Api::all(client.clone());
+    let (policies, pods, namespaces) = tokio::try_join!(
+        policies.list(&params),
+        pods.list(&params),
+        namespaces.list(&params)
+    )
+    .context("Failed to list network policies")?;
+
+    let mut result = Vec::new();
+    for namespace in namespaces.items {
+        let name = namespace.metadata.name.unwrap_or_default();
+        let ns_labels = namespace.metadata.labels.unwrap_or_default();
+        let ns_policies: Vec<&NetworkPolicy> = policies
+            .items
+            .iter()
+            .filter(|p| p.metadata.namespace.as_deref() == Some(name.as_str()))
+            .collect();
+        let ns_pods: Vec<&Pod> = pods
+            .items
+            .iter()
+            .filter(|p| p.metadata.namespace.as_deref() == Some(name.as_str()))
+            .collect();
+        if ns_policies.is_empty() && ns_pods.is_empty() {
+            continue;
+        }
+        result.push(evaluate_namespace(name, &ns_labels, &ns_policies, &ns_pods));
+    }
+    Ok(result)
+}
+
+/// Evaluate one namespace's policies against its workloads
+fn evaluate_namespace(
+    namespace: String,
+    ns_labels: &Labels,
+    policies: &[&NetworkPolicy],
+    pods: &[&Pod],
+) -> NamespacePolicies {
+    // Workload name → (pod count, labels of its first pod)
+    let mut groups: BTreeMap<String, (usize, Labels)> = BTreeMap::new();
+    for pod in pods {
+        let labels = pod.metadata.labels.clone().unwrap_or_default();
+        groups
+            .entry(workload_name(pod))
+            .or_insert_with(|| (0, labels))
+            .0 += 1;
+    }
+    let labels: Vec<&Labels> = groups.values().map(|(_, l)| l).collect();
+
+    let rules: Vec<PolicyRules> = policies.iter().map(|p| PolicyRules::new(p)).collect();
+    let isolated = |to: &Labels, ingress: bool| {
+        rules
+            .iter()
+            .any(|r| r.selects(to) && if ingress { r.ingress } else { r.egress })
+    };
+
+    let allowed = labels
+        .iter()
+        .map(|from| {
+            labels
+                .iter()
+                .map(|to| {
+                    let ingress_ok = !isolated(to, true)
+                        || rules.iter().any(|r| {
+                            r.ingress && r.selects(to) && rules_allow(&r.from, from, ns_labels)
+                        });
+                    let egress_ok = !isolated(from, false)
+                        || rules.iter().any(|r| {
+                            r.egress && r.selects(from) && rules_allow(&r.to, to, ns_labels)
+                        });
+                    ingress_ok && egress_ok
+                })
+                .collect()
+        })
+        .collect();
+
+    let workloads = groups
+        .iter()
+        .map(|(name, (pods, labels))| Workload {
+            name: name.clone(),
+            pods: *pods,
+            ingress_isolated: isolated(labels, true),
+            egress_isolated: isolated(labels, false),
+        })
+        .collect();
+    let policies = rules
+        .iter()
+        .map(|r| PolicySummary {
+            name: r.name.clone(),
+            selects: labels.iter().filter(|l| r.selects(l)).count(),
+            types: [(r.ingress, "Ingress"), (r.egress, "Egress")]
+                .into_iter()
+                .filter(|(on, _)| *on)
+                .map(|(_, t)| t.to_string())
+                .collect(),
+        })
+        .collect();
+
+    NamespacePolicies {
+        namespace,
+        policies,
+        workloads,
+        allowed,
+    }
+}
+
+/// The parts of a policy connectivity depends on
+struct PolicyRules {
+    name: String,
+    pod_selector: LabelSelector,
+    ingress: bool,
+    egress: bool,
+    /// Peers of each ingress rule; None allows every source
+    from: Vec<Option<Vec<NetworkPolicyPeer>>>,
+    /// Peers of each egress rule; None allows every destination
+    to: Vec<Option<Vec<NetworkPolicyPeer>>>,
+}
+
+impl PolicyRules {
+    fn new(policy: &NetworkPolicy) -> Self {
+        let spec = policy.spec.clone().unwrap_or_default();
+        let egress_rules = spec.egress.unwrap_or_default();
+        // policyTypes defaults to Ingress, plus Egress when egress rules exist
+        let types = spec.policy_types.unwrap_or_else(|| {
+            let mut types = vec!["Ingress".to_string()];
+            if !egress_rules.is_empty() {
+                types.push("Egress".to_string());
+            }
+            types
+        });
+
+        Self {
+            name: policy.metadata.name.clone().unwrap_or_default(),
+            pod_selector: spec.pod_selector.unwrap_or_default(),
+            ingress: types.iter().any(|t| t == "Ingress"),
+            egress: types.iter().any(|t| t == "Egress"),
+            from: spec
+                .ingress
+                .unwrap_or_default()
+                .into_iter()
+                .map(|r| r.from.filter(|p| !p.is_empty()))
+                .collect(),
+            to: egress_rules
+                .into_iter()
+                .map(|r| r.to.filter(|p| !p.is_empty()))
+                .collect(),
+        }
+    }
+
+    fn selects(&self, labels: &Labels) -> bool {
+        selector_matches(&self.pod_selector, labels)
+    }
+}
+
+/// Whether any rule lets traffic through to/from a pod with `labels`
+fn rules_allow(
+    rules: &[Option<Vec<NetworkPolicyPeer>>],
+    labels: &Labels,
+    ns_labels: &Labels,
+) -> bool {
+    rules.iter().any(|peers| match peers {
+        None => true,
+        Some(peers) => peers
+            .iter()
+            .any(|peer| peer_matches(peer, labels, ns_labels)),
+    })
+}
+
+/// A peer without a namespaceSelector means the policy's own namespace
+fn peer_matches(peer: &NetworkPolicyPeer, labels: &Labels, ns_labels: &Labels) -> bool {
+    if peer.ip_block.is_some() {
+        return false;
+    }
+    let namespace_ok = peer
+        .namespace_selector
+        .as_ref()
+        .is_none_or(|s| selector_matches(s, ns_labels));
+    let pod_ok = peer
+        .pod_selector
+        .as_ref()
+        .is_none_or(|s| selector_matches(s, labels));
+    (peer.namespace_selector.is_some() || peer.pod_selector.is_some()) && namespace_ok && pod_ok
+}
+
+/// Standard label selector semantics; the empty selector matches everything
+fn selector_matches(selector: &LabelSelector, labels: &Labels) -> bool {
+    let labels_ok = selector
+        .match_labels
+        .iter()
+        .flatten()
+        .all(|(k, v)| labels.get(k) == Some(v));
+    let expressions_ok = selector.match_expressions.iter().flatten().all(|expr| {
+        let values = expr.values.as_deref().unwrap_or_default();
+        let value = labels.get(&expr.key);
+        match expr.operator.as_str() {
+            "In" => value.is_some_and(|v| values.contains(v)),
+            "NotIn" => value.is_none_or(|v| !values.contains(v)),
+            "Exists" => value.is_some(),
+            "DoesNotExist" => value.is_none(),
+            _ => false,
+        }
+    });
+    labels_ok && expressions_ok
+}
+
+/// Name of the workload a pod belongs to: its app label, else its owner
+/// (without the ReplicaSet hash), else the pod itself
+pub(super) fn workload_name(pod: &Pod) -> String {
+    let labels = pod.metadata.labels.as_ref();
+    let label = |key: &str| labels.and_then(|l| l.get(key)).cloned();
+    if let Some(name) = label("app.kubernetes.io/name").or_else(|| label("app")) {
+        return name;
+    }
+    if let Some(owner) = pod.metadata.owner_references.iter().flatten().next() {
+        if owner.kind == "ReplicaSet" {
+            if let Some((deployment, _)) = owner.name.rsplit_once('-') {
+                return deployment.to_string();
+            }
+        }
+        return owner.name.clone();
+    }
+    pod.metadata.name.clone().unwrap_or_default()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use k8s_openapi::api::networking::v1::{NetworkPolicyIngressRule, NetworkPolicySpec};
+    use k8s_openapi::apimachinery::pkg::apis::meta::v1::ObjectMeta;
+
+    fn pod(app: &str) -> Pod {
+        Pod {
+            metadata: ObjectMeta {
+                name: Some(format!("{}-0", app)),
+                labels: Some(Labels::from([("app".to_string(), app.to_string())])),
+                ..Default::default()
+            },
+            ..Default::default()
+        }
+    }
+
+    fn app_selector(app: &str) -> LabelSelector {
+        LabelSelector {
+            match_labels: Some(Labels::from([("app".to_string(), app.to_string())])),
+            ..Default::default()
+        }
+    }
+
+    /// Only `web` may reach `db`
+    fn db_policy() -> NetworkPolicy {
+        NetworkPolicy {
+            metadata: ObjectMeta {
+                name: Some("db-from-web".to_string()),
+                ..Default::default()
+            },
+            spec: Some(NetworkPolicySpec {
+                pod_selector: Some(app_selector("db")),
+                ingress: Some(vec![NetworkPolicyIngressRule {
+                    from: Some(vec![NetworkPolicyPeer {
+                        pod_selector: Some(app_selector("web")),
+                        ..Default::default()
+                    }]),
+                    ..Default::default()
+                }]),
+                ..Default::default()
+            }),
+        }
+    }
+
+    #[test]
+    fn ingress_policy_limits_sources() {
+        let pods = [pod("db"), pod("web"), pod("worker")];
+        let pods: Vec<&Pod> = pods.iter().collect();
+        let policy = db_policy();
+        let result = evaluate_namespace("apps".to_string(), &Labels::new(), &[&policy], &pods);
+
+        let names: Vec<&str> = result.workloads.iter().map(|w| w.name.as_str()).collect();
+        assert_eq!(names, ["db", "web", "worker"]);
+        assert!(result.workloads[0].ingress_isolated);
+        assert!(!result.workloads[0].egress_isolated);
+        // web → db allowed, worker → db and db → db denied, db → web allowed
+        assert!(result.allowed[1][0]);
+        assert!(!result.allowed[2][0]);
+        assert!(!result.allowed[0][0]);
+        assert!(result.allowed[0][1]);
+        assert!(!result.unreachable(0));
+        assert_eq!(result.policies[0].selects, 1);
+        assert_eq!(result.policies[0].types, ["Ingress"]);
+    }
+
+    #[test]
+    fn default_deny_isolates_everything() {
+        let pods = [pod("db"), pod("web")];
+        let pods: Vec<&Pod> = pods.iter().collect();
+        let deny = NetworkPolicy {
+            metadata: ObjectMeta {
+                name: Some("default-deny".to_string()),
+                ..Default::default()
+            },
+            spec: Some(NetworkPolicySpec {
+                pod_selector: Some(LabelSelector::default()),
+                ..Default::default()
+            }),
+        };
+        let result = evaluate_namespace("apps".to_string(), &Labels::new(), &[&deny], &pods);
+
+        assert!(result.allowed.iter().flatten().all(|&allowed| !allowed));
+        assert!(result.unreachable(0) && result.unreachable(1));
+    }
+}
@@ -0,0 +1,352 @@
+//! Crash-loop diagnosis — gathers what `kubectl describe`, `logs --previous`
+//! and `get events` would show for a failing pod and guesses the cause
+
+use anyhow::{Context, Result};
+use k8s_openapi::api::core::v1::{Container, ContainerStatus, Event, Pod, Probe};
+use k8s_openapi::apimachinery::pkg::util::intstr::IntOrString;
+use kube::{
+    api::{Api, ListParams, LogParams},
+    Client,
+};
+
+/// Log lines kept from the last run of each container
+const LOG_TAIL: i64 = 30;
+
+/// Most recent events shown
+const MAX_EVENTS: usize = 10;
+
+/// How a container's last run ended
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Termination {
+    pub exit_code: i32,
+    pub reason: Option<String>,
+    pub message: Option<String>,
+    pub finished_at: Option<String>,
+}
+
+/// Everything gathered about one container
+#[derive(Debug, Clone)]
+pub struct ContainerDiagnosis {
+    pub name: String,
+    pub image: String,
+    pub restarts: i32,
+    /// Reason and message of the current waiting state (e.g. CrashLoopBackOff)
+    pub waiting: Option<(String, String)>,
+    pub last_termination: Option<Termination>,
+    /// Logs of the previous run when it restarted, else of the current one
+    pub logs: Result<Vec<String>, String>,
+    pub probes: Vec<String>,
+    pub memory_limit: Option<String>,
+}
+
+/// Summary of a pod for the diagnose popup
+#[derive(Debug, Clone)]
+pub struct PodDiagnosis {
+    pub pod: String,
+    pub namespace: String,
+    pub phase: String,
+    pub containers: Vec<ContainerDiagnosis>,
+    /// Most recent events, oldest first, as "reason: message"
+    pub events: Vec<String>,
+    pub hints: Vec<String>,
+}
+
+pub async fn diagnose_pod(client: &Client, namespace: &str, name: &str) -> Result<PodDiagnosis> {
+    let pods: Api<Pod> = Api::namespaced(client.clone(), namespace);
+    let pod = pods
+        .get(name)
+        .await
+        .with_context(|| format!("Failed to get pod {}/{}", namespace, name))?;
+
+    let spec_containers = pod.spec.map(|s| s.containers).unwrap_or_default();
+    let status = pod.status.unwrap_or_default();
+    let statuses = status.container_statuses.unwrap_or_default();
+
+    let mut containers = Vec::new();
+    for container in &spec_containers {
+        let container_status = statuses.iter().find(|s| s.name == container.name);
+        let mut diagnosis = container_diagnosis(container, container_status);
+        let params = LogParams {
+            container: Some(container.name.clone()),
+            previous: diagnosis.restarts > 0,
+            tail_lines: Some(LOG_TAIL),
+            ..Default::default()
+        };
+        diagnosis.logs = pods
+            .logs(name, &params)
+            .await
+            .map(|logs| logs.lines().map(str::to_string).collect())
+            .map_err(|e| e.to_string());
+        containers.push(diagnosis);
+    }
+
+    let events_api: Api<Event> = Api::namespaced(client.clone(), namespace);
+    let lp = ListParams::default().fields(&format!(
+        "involvedObject.name={},involvedObject.kind=Pod",
+        name
+    ));
+    let mut event_list = events_api
+        .list(&lp)
+        .await
+        .context("Failed to fetch events")?
+        .items;
+    event_list.sort_by_key(|e| e.last_timestamp.clone().map(|t| t.0));
+    let events: Vec<String> = event_list
+        .into_iter()
+        .rev()
+        .take(MAX_EVENTS)
+        .rev()
+        .map(|e| {
+            let count = e.count.filter(|c| *c > 1).map(|c| format!(" (x{})", c));
+            format!(
+                "{}{}: {}",
+                e.reason.unwrap_or_default(),
+                count.unwrap_or_default(),
+                e.message.unwrap_or_default().trim()
+            )
+        })
+        .collect();
+
+    let hints = containers
+        .iter()
+        .flat_map(|c| container_hints(c, &events))
+        .collect();
+
+    Ok(PodDiagnosis {
+        pod: name.to_string(),
+        namespace: namespace.to_string(),
+        phase: status.phase.unwrap_or_default(),
+        containers,
+        events,
+        hints,
+    })
+}
+
+fn container_diagnosis(
+    container: &Container,
+    status: Option<&ContainerStatus>,
+) -> ContainerDiagnosis {
+    let state = status.and_then(|s| s.state.as_ref());
+    let waiting = state.and_then(|s| s.waiting.as_ref()).map(|w| {
+        (
+            w.reason.clone().unwrap_or_default(),
+            w.message.clone().unwrap_or_default(),
+        )
+    });
+    // A crash-looping container is waiting; its last run is in last_state
+    let terminated = status
+        .and_then(|s| s.last_state.as_ref())
+        .and_then(|s| s.terminated.as_ref())
+        .or_else(|| state.and_then(|s| s.terminated.as_ref()));
+    let last_termination = terminated.map(|t| Termination {
+        exit_code: t.exit_code,
+        reason: t.reason.clone(),
+        message: t.message.clone().filter(|m| !m.trim().is_empty()),
+        finished_at: t.finished_at.as_ref().map(|f| f.0.to_string()),
+    });
+
+    let probes = [
+        ("startup", &container.startup_probe),
+        ("liveness", &container.liveness_probe),
+        ("readiness", &container.readiness_probe),
+    ]
+    .into_iter()
+    .filter_map(|(kind, probe)| probe.as_ref().map(|p| describe_probe(kind, p)))
+    .collect();
+
+    ContainerDiagnosis {
+        name: container.name.clone(),
+        image: container.image.clone().unwrap_or_default(),
+        restarts: status.map(|s| s.restart_count).unwrap_or(0),
+        waiting,
+        last_termination,
+        logs: Ok(Vec::new()),
+        probes,
+        memory_limit: container
+            .resources
+            .as_ref()
+            .and_then(|r| r.limits.as_ref())
+            .and_then(|l| l.get("memory"))
+            .map(|q| q.0.clone()),
+    }
+}
+
+/// One-line probe summary, e.g. "liveness: GET :8080/healthz, delay 0s, every 10s, timeout 1s, 3 failures"
+pub fn describe_probe(kind: &str, probe: &Probe) -> String {
+    let action = if let Some(http) = &probe.http_get {
+        format!(
+            "GET {}:{}{}",
+            http.host.clone().unwrap_or_default(),
+            int_or_string(&http.port),
+            http.path.clone().unwrap_or_else(|| "/".to_string())
+        )
+    } else if let Some(tcp) = &probe.tcp_socket {
+        format!("TCP :{}", int_or_string(&tcp.port))
+    } else if let Some(exec) = &probe.exec {
+        format!(
+            "exec {}",
+            exec.command.clone().unwrap_or_default().join(" ")
+        )
+    } else if let Some(grpc) = &probe.grpc {
+        format!("gRPC :{}", grpc.port)
+    } else {
+        "no handler".to_string()
+    };
+    format!(
+        "{}: {}, delay {}s, every {}s, timeout {}s, {} failures",
+        kind,
+        action,
+        probe.initial_delay_seconds.unwrap_or(0),
+        probe.period_seconds.unwrap_or(10),
+        probe.timeout_seconds.unwrap_or(1),
+        probe.failure_threshold.unwrap_or(3)
+    )
+}
+
+fn int_or_string(value: &IntOrString) -> String {
+    match value {
+        IntOrString::Int(port) => port.to_string(),
+        IntOrString::String(name) => name.clone(),
+    }
+}
+
+/// What an exit code usually means
+pub fn exit_code_meaning(code: i32) -> Option<&'static str> {
+    Some(match code {
+        0 => "exited successfully",
+        1 => "application error",
+        2 => "misuse of shell builtin or bad arguments",
+        126 => "command found but not executable",
+        127 => "command not found",
+        130 => "interrupted (SIGINT)",
+        134 => "aborted (SIGABRT)",
+        137 => "killed (SIGKILL)",
+        139 => "segmentation fault (SIGSEGV)",
+        143 => "terminated (SIGTERM)",
+        _ => return None,
+    })
+}
+
+/// Likely causes for a container, from its last exit and the pod's events
+pub fn container_hints(container: &ContainerDiagnosis, events: &[String]) -> Vec<String> {
+    let mut hints = Vec::new();
+    let name = &container.name;
+    let liveness_failed = events.iter().any(|e| e.contains("Liveness probe failed"));
+    let startup_failed = events.iter().any(|e| e.contains("Startup probe failed"));
+
+    if let Some((reason, _)) = &container.waiting {
+        match reason.as_str() {
+            "CreateContainerConfigError" => hints.push(format!(
+                "{}: a ConfigMap or Secret referenced by env or envFrom is missing",
+                name
+            )),
+            "ErrImagePull" | "ImagePullBackOff" | "InvalidImageName" => hints.push(format!(
+                "{}: image {} can't be pulled; check the name and tag",
+                name, container.image
+            )),
+            _ => {}
+        }
+    }
+
+    let Some(exit) = &container.last_termination else {
+        return hints;
+    };
+    let oom = exit.reason.as_deref() == Some("OOMKilled");
+    if oom {
+        hints.push(match &container.memory_limit {
+            Some(limit) => format!(
+                "{}: out of memory at its {} limit; raise resources.limits.memory",
+                name, limit
+            ),
+            None => format!(
+                "{}: out of memory with no limit set; the node itself ran out",
+                name
+            ),
+        });
+    } else if (exit.exit_code == 137 || exit.exit_code == 143)
+        && (liveness_failed || startup_failed)
+    {
+        hints.push(format!(
+            "{}: restarted by a failing {} probe; check its path and port, or raise \
+             initialDelaySeconds/failureThreshold if the app starts slowly",
+            name,
+            if startup_failed {
+                "startup"
+            } else {
+                "liveness"
+            }
+        ));
+    } else {
+        match exit.exit_code {
+            0 => hints.push(format!(
+                "{}: the main process exits successfully; a Deployment needs it to keep running",
+                name
+            )),
+            126 | 127 => hints.push(format!(
+                "{}: the command can't be run; check command/args and the image's entrypoint",
+                name
+            )),
+            _ => {}
+        }
+    }
+    if exit.exit_code != 0
+        && !oom
+        && exit.message.is_none()
+        && matches!(&container.logs, Ok(lines) if lines.is_empty())
+    {
+        hints.push(format!(
+            "{}: it printed nothing before exiting; the entrypoint may fail before logging starts",
+            name
+        ));
+    }
+    hints
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn crashed(exit_code: i32, reason: &str) -> ContainerDiagnosis {
+        ContainerDiagnosis {
+            name: "api".to_string(),
+            image: "api:dev".to_string(),
+            restarts: 4,
+            waiting: Some(("CrashLoopBackOff".to_string(), String::new())),
+            last_termination: Some(Termination {
+                exit_code,
+                reason: Some(reason.to_string()),
+                ..Default::default()
+            }),
+            logs: Ok(vec!["starting".to_string()]),
+            probes: Vec::new(),
+            memory_limit: None,
+        }
+    }
+
+    #[test]
+    fn blames_memory_limit_for_oom_kills() {
+        let mut container = crashed(137, "OOMKilled");
+        container.memory_limit = Some("64Mi".to_string());
+        let hints = container_hints(&container, &[]);
+        assert_eq!(hints.len(), 1);
+        assert!(hints[0].contains("64Mi"));
+    }
+
+    #[test]
+    fn blames_liveness_probe_for_kills_after_probe_failures() {
+        let events = ["Unhealthy (x3): Liveness probe failed: connection refused".to_string()];
+        let hints = container_hints(&crashed(137, "Error"), &events);
+        assert!(hints[0].contains("liveness probe"));
+
+        let hints = container_hints(&crashed(137, "Error"), &[]);
+        assert!(hints.is_empty());
+    }
+
+    #[test]
+    fn notices_silent_exits() {
+        let mut container = crashed(1, "Error");
+        container.logs = Ok(Vec::new());
+        let hints = container_hints(&container, &[]);
+        assert!(hints[0].contains("printed nothing"));
+    }
+}
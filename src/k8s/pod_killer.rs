@@ -0,0 +1,104 @@
+//! Random pod killer
+//!
+//! Deletes one running pod matching `chaos.pod_killer` every interval until
+//! cancelled, so an app's self-healing can be watched during development.
+
+use std::collections::hash_map::RandomState;
+use std::hash::BuildHasher;
+
+use anyhow::{bail, Result};
+use tokio::sync::mpsc;
+
+use super::client::PodInfo;
+use super::K8sClient;
+use crate::config::PodKillerConfig;
+use crate::ui::components::OutputLine;
+
+/// Kill a random matching pod every `config.interval`, reporting each kill.
+/// Runs until the job is cancelled; failed rounds are reported and retried.
+pub async fn run_pod_killer(
+    k8s: &K8sClient,
+    config: &PodKillerConfig,
+    default_namespace: &str,
+    output_tx: &mpsc::Sender<OutputLine>,
+) -> Result<()> {
+    if config.selector.trim().is_empty() {
+        bail!("chaos.pod_killer.selector is empty; set it to the pods that may be killed");
+    }
+    let namespace = if config.namespace.is_empty() {
+        default_namespace
+    } else {
+        config.namespace.as_str()
+    };
+    let _ = output_tx
+        .send(OutputLine::info(format!(
+            "Killing a pod in {} matching {} every {:?}. Cancel the job to stop.",
+            namespace, config.selector, config.interval
+        )))
+        .await;
+
+    loop {
+        tokio::time::sleep(config.interval).await;
+        let line = match kill_one(k8s, namespace, &config.selector).await {
+            Ok(Some((pod, candidates))) => {
+                tracing::info!(namespace, pod = %pod, "Pod killer deleted pod");
+                OutputLine::warning(format!(
+                    "Killed {}/{} (1 of {} running)",
+                    namespace, pod, candidates
+                ))
+            }
+            Ok(None) => OutputLine::info(format!(
+                "No running pod in {} matches {}",
+                namespace, config.selector
+            )),
+            Err(e) => OutputLine::error(format!("Pod killer round failed: {:#}", e)),
+        };
+        let _ = output_tx.send(line).await;
+    }
+}
+
+/// Delete a random running pod; its name and how many there were to pick from
+async fn kill_one(
+    k8s: &K8sClient,
+    namespace: &str,
+    selector: &str,
+) -> Result<Option<(String, usize)>> {
+    let pods = k8s.list_pods(namespace, Some(selector)).await?;
+    let running: Vec<&PodInfo> = pods.iter().filter(|p| p.status == "Running").collect();
+    let Some(victim) = pick(&running, RandomState::new().hash_one(namespace)) else {
+        return Ok(None);
+    };
+    k8s.delete_pod(namespace, &victim.name).await?;
+    Ok(Some((victim.name.clone(), running.len())))
+}
+
+fn pick<'a>(pods: &[&'a PodInfo], seed: u64) -> Option<&'a PodInfo> {
+    if pods.is_empty() {
+        return None;
+    }
+    Some(pods[(seed % pods.len() as u64) as usize])
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pod(name: &str) -> PodInfo {
+        PodInfo {
+            name: name.to_string(),
+            namespace: "default".to_string(),
+            status: "Running".to_string(),
+            ready: true,
+            ip: None,
+        }
+    }
+
+    #[test]
+    fn pick_stays_within_the_candidates() {
+        let (a, b) = (pod("a"), pod("b"));
+        let pods = [&a, &b];
+        assert_eq!(pick(&pods, 0).map(|p| p.name.as_str()), Some("a"));
+        assert_eq!(pick(&pods, 7).map(|p| p.name.as_str()), Some("b"));
+        assert!(pick(&[], 3).is_none());
+    }
+}
@@ -0,0 +1,199 @@
+//! Policy violations of current workloads
+//!
+//! Reads Kyverno's policy reports (`wgpolicyk8s.io`) and the audit results
+//! Gatekeeper stores on its constraints (`constraints.gatekeeper.sh`).
+//! Whichever engine is not installed contributes nothing; it is an error only
+//! when neither is.
+
+use anyhow::{bail, Result};
+use kube::{
+    api::{Api, ApiResource, DynamicObject, ListParams},
+    Client,
+};
+use serde_json::Value;
+
+/// One resource failing one policy rule
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PolicyViolation {
+    /// Kyverno "policy/rule" or Gatekeeper "Kind/constraint"
+    pub policy: String,
+    /// "Kind/name"
+    pub resource: String,
+    /// Empty for cluster-scoped resources
+    pub namespace: String,
+    pub message: String,
+    /// Kyverno `fail` or Gatekeeper `deny`; otherwise a warning or audit-only
+    pub failed: bool,
+}
+
+fn report_resource(plural: &str, kind: &str) -> ApiResource {
+    ApiResource {
+        group: "wgpolicyk8s.io".to_string(),
+        version: "v1alpha2".to_string(),
+        api_version: "wgpolicyk8s.io/v1alpha2".to_string(),
+        kind: kind.to_string(),
+        plural: plural.to_string(),
+    }
+}
+
+/// Violations from every installed engine, failures first
+pub async fn get_policy_violations(client: &Client) -> Result<Vec<PolicyViolation>> {
+    let params = ListParams::default();
+    let mut violations = Vec::new();
+    let mut engine_found = false;
+
+    let reports: Api<DynamicObject> = Api::all_with(
+        client.clone(),
+        &report_resource("policyreports", "PolicyReport"),
+    );
+    let cluster_reports: Api<DynamicObject> = Api::all_with(
+        client.clone(),
+        &report_resource("clusterpolicyreports", "ClusterPolicyReport"),
+    );
+    for api in [reports, cluster_reports] {
+        if let Ok(list) = api.list(&params).await {
+            engine_found = true;
+            for report in list.items {
+                violations.extend(report_violations(&report.data));
+            }
+        }
+    }
+
+    if let Ok(group) = kube::discovery::group(client, "constraints.gatekeeper.sh").await {
+        engine_found = true;
+        for (resource, _) in group.recommended_resources() {
+            let constraints: Api<DynamicObject> = Api::all_with(client.clone(), &resource);
+            for constraint in constraints.list(&params).await?.items {
+                let name = constraint.metadata.name.unwrap_or_default();
+                violations.extend(constraint_violations(
+                    &resource.kind,
+                    &name,
+                    &constraint.data,
+                ));
+            }
+        }
+    }
+
+    if !engine_found {
+        bail!("No policy engine found; run Install Policy Engine first");
+    }
+    violations.sort_by(|a, b| {
+        (!a.failed, &a.namespace, &a.resource, &a.policy).cmp(&(
+            !b.failed,
+            &b.namespace,
+            &b.resource,
+            &b.policy,
+        ))
+    });
+    Ok(violations)
+}
+
+/// Failed and warned results of a (Cluster)PolicyReport. The resource is the
+/// result's own when listed, otherwise the report's scope.
+fn report_violations(report: &Value) -> Vec<PolicyViolation> {
+    let scope = report.get("scope");
+    let Some(results) = report.get("results").and_then(Value::as_array) else {
+        return Vec::new();
+    };
+    results
+        .iter()
+        .filter_map(|result| {
+            let outcome = result.get("result").and_then(Value::as_str)?;
+            if outcome != "fail" && outcome != "warn" {
+                return None;
+            }
+            let resource = result.get("resources").and_then(|r| r.get(0)).or(scope)?;
+            let policy = str_field(result, "policy");
+            let rule = str_field(result, "rule");
+            Some(PolicyViolation {
+                policy: if rule.is_empty() {
+                    policy
+                } else {
+                    format!("{}/{}", policy, rule)
+                },
+                resource: format!(
+                    "{}/{}",
+                    str_field(resource, "kind"),
+                    str_field(resource, "name")
+                ),
+                namespace: str_field(resource, "namespace"),
+                message: str_field(result, "message"),
+                failed: outcome == "fail",
+            })
+        })
+        .collect()
+}
+
+/// Audit violations Gatekeeper recorded in a constraint's status
+fn constraint_violations(kind: &str, name: &str, constraint: &Value) -> Vec<PolicyViolation> {
+    let Some(found) = constraint
+        .pointer("/status/violations")
+        .and_then(Value::as_array)
+    else {
+        return Vec::new();
+    };
+    found
+        .iter()
+        .map(|v| PolicyViolation {
+            policy: format!("{}/{}", kind, name),
+            resource: format!("{}/{}", str_field(v, "kind"), str_field(v, "name")),
+            namespace: str_field(v, "namespace"),
+            message: str_field(v, "message"),
+            failed: str_field(v, "enforcementAction") == "deny",
+        })
+        .collect()
+}
+
+fn str_field(value: &Value, key: &str) -> String {
+    value
+        .get(key)
+        .and_then(Value::as_str)
+        .unwrap_or_default()
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use serde_json::json;
+
+    #[test]
+    fn kyverno_reports_keep_failures_and_warnings() {
+        let report = json!({
+            "scope": { "kind": "Deployment", "name": "api", "namespace": "apps" },
+            "results": [
+                { "policy": "require-labels", "rule": "team", "result": "fail",
+                  "message": "label 'team' is required" },
+                { "policy": "disallow-latest", "rule": "tag", "result": "warn",
+                  "message": "use a pinned tag" },
+                { "policy": "run-as-non-root", "rule": "check", "result": "pass" }
+            ]
+        });
+        let violations = report_violations(&report);
+        assert_eq!(violations.len(), 2);
+        assert_eq!(violations[0].policy, "require-labels/team");
+        assert_eq!(violations[0].resource, "Deployment/api");
+        assert_eq!(violations[0].namespace, "apps");
+        assert!(violations[0].failed);
+        assert!(!violations[1].failed);
+    }
+
+    #[test]
+    fn gatekeeper_violations_come_from_the_constraint_status() {
+        let constraint = json!({
+            "status": { "violations": [
+                { "enforcementAction": "deny", "kind": "Namespace", "name": "scratch",
+                  "message": "you must provide labels: {\"owner\"}" },
+                { "enforcementAction": "dryrun", "kind": "Pod", "name": "web-1",
+                  "namespace": "apps", "message": "privileged" }
+            ]}
+        });
+        let violations = constraint_violations("K8sRequiredLabels", "ns-owner", &constraint);
+        assert_eq!(violations.len(), 2);
+        assert_eq!(violations[0].policy, "K8sRequiredLabels/ns-owner");
+        assert_eq!(violations[0].namespace, "");
+        assert!(violations[0].failed);
+        assert!(!violations[1].failed);
+        assert!(constraint_violations("K", "n", &json!({})).is_empty());
+    }
+}
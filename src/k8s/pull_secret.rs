@@ -0,0 +1,406 @@
+//! ImagePullSecret provisioning
+//!
+//! Private images need registry credentials in every namespace that pulls
+//! them. The credentials the Docker CLI already stores (`docker login`,
+//! inline or through a credential helper) become a
+//! `kubernetes.io/dockerconfigjson` Secret in each chosen namespace, and the
+//! namespace's `default` ServiceAccount lists it under `imagePullSecrets`,
+//! so pods that don't name a ServiceAccount pull with it.
+
+use std::collections::{BTreeMap, HashMap};
+use std::path::PathBuf;
+use std::process::Stdio;
+
+use anyhow::{anyhow, bail, Context, Result};
+use base64::Engine;
+use k8s_openapi::api::core::v1::{LocalObjectReference, Secret, ServiceAccount};
+use k8s_openapi::ByteString;
+use kube::{
+    api::{Api, ObjectMeta, Patch, PatchParams},
+    Client,
+};
+use serde::Deserialize;
+use tokio::io::AsyncWriteExt;
+use tokio::process::Command;
+use tokio::sync::mpsc;
+
+use crate::config::{expand_home, PullSecretConfig};
+use crate::ui::components::OutputLine;
+
+const FIELD_MANAGER: &str = "k3dev";
+
+/// Docker Hub's key in the Docker CLI config
+const DOCKER_HUB: &str = "https://index.docker.io/v1/";
+
+/// Credentials for one registry
+#[derive(Debug, Clone, PartialEq, Eq)]
+struct RegistryAuth {
+    /// Registry host as the Docker CLI config names it, e.g. "ghcr.io"
+    registry: String,
+    username: String,
+    password: String,
+}
+
+/// The parts of `~/.docker/config.json` that hold credentials
+#[derive(Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct DockerConfigFile {
+    #[serde(default)]
+    auths: BTreeMap<String, AuthEntry>,
+    /// Helper for every registry, e.g. "desktop" or "osxkeychain"
+    #[serde(default)]
+    creds_store: Option<String>,
+    /// Helpers for single registries
+    #[serde(default)]
+    cred_helpers: HashMap<String, String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+struct AuthEntry {
+    /// base64 of "user:password"
+    #[serde(default)]
+    auth: Option<String>,
+    #[serde(default)]
+    username: Option<String>,
+    #[serde(default)]
+    password: Option<String>,
+}
+
+/// What `docker-credential-<helper> get` prints
+#[derive(Debug, Deserialize)]
+#[serde(rename_all = "PascalCase")]
+struct HelperCredentials {
+    username: String,
+    secret: String,
+}
+
+/// How to get the password of a registry
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Source {
+    Inline { username: String, password: String },
+    Helper(String),
+}
+
+/// `$DOCKER_CONFIG/config.json`, else `~/.docker/config.json`, unless
+/// `docker_config` is set
+fn docker_config_path(config: &PullSecretConfig) -> Result<PathBuf> {
+    if let Some(path) = &config.docker_config {
+        return expand_home(std::path::Path::new(path));
+    }
+    if let Some(dir) = std::env::var_os("DOCKER_CONFIG") {
+        return Ok(PathBuf::from(dir).join("config.json"));
+    }
+    let home = dirs::home_dir().ok_or_else(|| anyhow!("No home directory"))?;
+    Ok(home.join(".docker").join("config.json"))
+}
+
+/// Where each wanted registry's credentials come from; all registries of the
+/// file when `wanted` is empty
+fn sources(file: &DockerConfigFile, wanted: &[String]) -> Result<Vec<(String, Source)>> {
+    let registries: Vec<String> = if wanted.is_empty() {
+        let mut all: Vec<String> = file
+            .auths
+            .keys()
+            .chain(file.cred_helpers.keys())
+            .cloned()
+            .collect();
+        all.sort();
+        all.dedup();
+        all
+    } else {
+        wanted.to_vec()
+    };
+    let mut found = Vec::new();
+    for registry in registries {
+        let entry = file.auths.get(&registry);
+        let inline = entry.and_then(|e| match (&e.username, &e.password, &e.auth) {
+            (Some(u), Some(p), _) => Some((u.clone(), p.clone())),
+            (_, _, Some(auth)) => decode_auth(auth),
+            _ => None,
+        });
+        let source = match (inline, file.cred_helpers.get(&registry), &file.creds_store) {
+            (Some((username, password)), _, _) => Source::Inline { username, password },
+            (None, Some(helper), _) | (None, None, Some(helper)) => Source::Helper(helper.clone()),
+            (None, None, None) => bail!(
+                "No credentials for {} in the Docker config; run docker login {}",
+                registry,
+                registry
+            ),
+        };
+        found.push((registry, source));
+    }
+    if found.is_empty() {
+        bail!("The Docker config has no registry credentials; run docker login first");
+    }
+    Ok(found)
+}
+
+/// "user:password" from a config `auth` value
+fn decode_auth(auth: &str) -> Option<(String, String)> {
+    let bytes = base64::engine::general_purpose::STANDARD
+        .decode(auth.trim())
+        .ok()?;
+    let text = String::from_utf8(bytes).ok()?;
+    let (user, password) = text.split_once(':')?;
+    Some((user.to_string(), password.to_string()))
+}
+
+/// Ask `docker-credential-<helper>` for a registry's credentials
+async fn helper_credentials(helper: &str, registry: &str) -> Result<(String, String)> {
+    let program = format!("docker-credential-{}", helper);
+    let mut child = Command::new(&program)
+        .arg("get")
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()
+        .with_context(|| format!("Failed to run {}", program))?;
+    if let Some(mut stdin) = child.stdin.take() {
+        stdin.write_all(registry.as_bytes()).await?;
+    }
+    let output = child.wait_with_output().await?;
+    if !output.status.success() {
+        bail!(
+            "{} has no credentials for {}: {}",
+            program,
+            registry,
+            String::from_utf8_lossy(&output.stdout).trim()
+        );
+    }
+    let creds: HelperCredentials = serde_json::from_slice(&output.stdout)
+        .with_context(|| format!("Unexpected output from {}", program))?;
+    Ok((creds.username, creds.secret))
+}
+
+/// Credentials of the configured registries from the Docker CLI config
+async fn load_credentials(config: &PullSecretConfig) -> Result<Vec<RegistryAuth>> {
+    let path = docker_config_path(config)?;
+    let content = std::fs::read_to_string(&path)
+        .with_context(|| format!("Failed to read {}; run docker login first", path.display()))?;
+    let file: DockerConfigFile = serde_json::from_str(&content)
+        .with_context(|| format!("Failed to parse {}", path.display()))?;
+    let mut auths = Vec::new();
+    for (registry, source) in sources(&file, &config.registries)? {
+        let (username, password) = match source {
+            Source::Inline { username, password } => (username, password),
+            Source::Helper(helper) => helper_credentials(&helper, &registry).await?,
+        };
+        auths.push(RegistryAuth {
+            registry,
+            username,
+            password,
+        });
+    }
+    Ok(auths)
+}
+
+/// The `.dockerconfigjson` of a pull secret
+fn dockerconfigjson(auths: &[RegistryAuth]) -> String {
+    let entries: serde_json::Map<String, serde_json::Value> = auths
+        .iter()
+        .map(|a| {
+            let auth = base64::engine::general_purpose::STANDARD
+                .encode(format!("{}:{}", a.username, a.password));
+            (
+                a.registry.clone(),
+                serde_json::json!({
+                    "username": a.username,
+                    "password": a.password,
+                    "auth": auth,
+                }),
+            )
+        })
+        .collect();
+    serde_json::json!({ "auths": entries }).to_string()
+}
+
+/// Registry names for messages ("docker.io" for Docker Hub)
+fn registry_label(registry: &str) -> &str {
+    if registry == DOCKER_HUB {
+        "docker.io"
+    } else {
+        registry
+    }
+}
+
+/// `current` with `name` added unless it is already there
+fn with_pull_secret(
+    current: Option<Vec<LocalObjectReference>>,
+    name: &str,
+) -> Option<Vec<LocalObjectReference>> {
+    let mut refs = current.unwrap_or_default();
+    if refs.iter().any(|r| r.name == name) {
+        return None;
+    }
+    refs.push(LocalObjectReference {
+        name: name.to_string(),
+    });
+    Some(refs)
+}
+
+/// Create or update the pull secret in `namespace` and add it to the
+/// `default` ServiceAccount. Returns whether the ServiceAccount changed.
+async fn provision(
+    client: &Client,
+    namespace: &str,
+    name: &str,
+    dockerconfigjson: &str,
+) -> Result<bool> {
+    let secrets: Api<Secret> = Api::namespaced(client.clone(), namespace);
+    let secret = Secret {
+        metadata: ObjectMeta {
+            name: Some(name.to_string()),
+            namespace: Some(namespace.to_string()),
+            labels: Some(BTreeMap::from([(
+                "app.kubernetes.io/managed-by".to_string(),
+                "k3dev".to_string(),
+            )])),
+            ..Default::default()
+        },
+        type_: Some("kubernetes.io/dockerconfigjson".to_string()),
+        data: Some(BTreeMap::from([(
+            ".dockerconfigjson".to_string(),
+            ByteString(dockerconfigjson.as_bytes().to_vec()),
+        )])),
+        ..Default::default()
+    };
+    secrets
+        .patch(
+            name,
+            &PatchParams::apply(FIELD_MANAGER).force(),
+            &Patch::Apply(&secret),
+        )
+        .await
+        .with_context(|| format!("Failed to apply secret {}/{}", namespace, name))?;
+
+    let accounts: Api<ServiceAccount> = Api::namespaced(client.clone(), namespace);
+    let account = accounts
+        .get("default")
+        .await
+        .with_context(|| format!("Failed to read {}/default ServiceAccount", namespace))?;
+    let Some(refs) = with_pull_secret(account.image_pull_secrets, name) else {
+        return Ok(false);
+    };
+    let patch = serde_json::json!({ "imagePullSecrets": refs });
+    accounts
+        .patch("default", &PatchParams::default(), &Patch::Merge(&patch))
+        .await
+        .with_context(|| format!("Failed to patch {}/default ServiceAccount", namespace))?;
+    Ok(true)
+}
+
+/// Provision the pull secret in each namespace, reporting as it goes
+pub async fn provision_namespaces(
+    client: &Client,
+    config: &PullSecretConfig,
+    namespaces: &[String],
+    output_tx: &mpsc::Sender<OutputLine>,
+) -> Result<()> {
+    let auths = load_credentials(config).await?;
+    let registries: Vec<&str> = auths.iter().map(|a| registry_label(&a.registry)).collect();
+    let _ = output_tx
+        .send(OutputLine::info(format!(
+            "Credentials for {}",
+            registries.join(", ")
+        )))
+        .await;
+    let json = dockerconfigjson(&auths);
+
+    let mut failed = 0;
+    for namespace in namespaces {
+        match provision(client, namespace, &config.name, &json).await {
+            Ok(patched) => {
+                let account = if patched {
+                    "added to default ServiceAccount"
+                } else {
+                    "default ServiceAccount already uses it"
+                };
+                let _ = output_tx
+                    .send(OutputLine::success(format!(
+                        "{}/{}: {}",
+                        namespace, config.name, account
+                    )))
+                    .await;
+            }
+            Err(e) => {
+                failed += 1;
+                let _ = output_tx
+                    .send(OutputLine::error(format!("{}: {:#}", namespace, e)))
+                    .await;
+            }
+        }
+    }
+    if failed > 0 {
+        bail!("{} of {} namespaces failed", failed, namespaces.len());
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn file(json: &str) -> DockerConfigFile {
+        serde_json::from_str(json).unwrap()
+    }
+
+    #[test]
+    fn sources_prefer_inline_auth_then_helpers() {
+        let file = file(
+            r#"{
+                "auths": {
+                    "ghcr.io": {"auth": "bWU6c2VjcmV0"},
+                    "https://index.docker.io/v1/": {}
+                },
+                "credsStore": "desktop",
+                "credHelpers": {"123.dkr.ecr.us-east-1.amazonaws.com": "ecr-login"}
+            }"#,
+        );
+        assert_eq!(
+            sources(&file, &[]).unwrap(),
+            [
+                (
+                    "123.dkr.ecr.us-east-1.amazonaws.com".to_string(),
+                    Source::Helper("ecr-login".to_string())
+                ),
+                (
+                    "ghcr.io".to_string(),
+                    Source::Inline {
+                        username: "me".to_string(),
+                        password: "secret".to_string()
+                    }
+                ),
+                (
+                    DOCKER_HUB.to_string(),
+                    Source::Helper("desktop".to_string())
+                ),
+            ]
+        );
+    }
+
+    #[test]
+    fn unknown_registry_is_an_error() {
+        let file = file(r#"{"auths": {"ghcr.io": {"auth": "bWU6c2VjcmV0"}}}"#);
+        let err = sources(&file, &["quay.io".to_string()]).unwrap_err();
+        assert!(err.to_string().contains("docker login quay.io"));
+        assert!(sources(&DockerConfigFile::default(), &[]).is_err());
+    }
+
+    #[test]
+    fn secret_and_service_account_contents() {
+        let json = dockerconfigjson(&[RegistryAuth {
+            registry: "ghcr.io".to_string(),
+            username: "me".to_string(),
+            password: "secret".to_string(),
+        }]);
+        let value: serde_json::Value = serde_json::from_str(&json).unwrap();
+        assert_eq!(value["auths"]["ghcr.io"]["auth"], "bWU6c2VjcmV0");
+
+        let existing = Some(vec![LocalObjectReference {
+            name: "other".to_string(),
+        }]);
+        let refs = with_pull_secret(existing, "k3dev-registry").unwrap();
+        assert_eq!(refs.len(), 2);
+        assert!(with_pull_secret(Some(refs), "k3dev-registry").is_none());
+    }
+}
@@ -0,0 +1,250 @@
+//! ResourceQuotas and LimitRanges per namespace
+//!
+//! Quota consumption comes from each quota's `status` (what the quota
+//! controller has counted), so it matches what admission will enforce.
+//! LimitRanges are listed with the defaults they inject into containers
+//! that set no requests or limits themselves.
+
+use std::collections::BTreeMap;
+
+use anyhow::{Context, Result};
+use k8s_openapi::api::core::v1::{LimitRange, ResourceQuota};
+use k8s_openapi::apimachinery::pkg::api::resource::Quantity;
+use kube::{
+    api::{Api, ListParams},
+    Client,
+};
+
+/// Quotas and limit ranges of one namespace
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct NamespaceQuotas {
+    pub namespace: String,
+    pub quotas: Vec<QuotaUsage>,
+    pub limits: Vec<LimitSummary>,
+}
+
+/// One resource of one ResourceQuota
+#[derive(Debug, Clone, PartialEq)]
+pub struct QuotaUsage {
+    pub quota: String,
+    /// e.g. "limits.memory", "pods"
+    pub resource: String,
+    pub used: String,
+    pub hard: String,
+}
+
+impl QuotaUsage {
+    /// Consumption as a fraction of the hard limit, if both parse
+    pub fn ratio(&self) -> Option<f64> {
+        let hard = parse_quantity(&self.hard)?;
+        let used = parse_quantity(&self.used)?;
+        if hard > 0.0 {
+            Some(used / hard)
+        } else if used > 0.0 {
+            Some(f64::INFINITY)
+        } else {
+            Some(0.0)
+        }
+    }
+}
+
+/// One resource of one LimitRange item
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct LimitSummary {
+    pub limit_range: String,
+    /// "Container", "Pod" or "PersistentVolumeClaim"
+    pub kind: String,
+    pub resource: String,
+    pub default_request: Option<String>,
+    pub default_limit: Option<String>,
+    pub max: Option<String>,
+}
+
+/// Namespaces with at least one quota or limit range, by name
+pub async fn get_quotas(client: &Client) -> Result<Vec<NamespaceQuotas>> {
+    let params = ListParams::default();
+    let quotas: Api<ResourceQuota> = Api::all(client.clone());
+    let limit_ranges: Api<LimitRange> = Api::all(client.clone());
+    let (quotas, limit_ranges) = tokio::try_join!(quotas.list(&params), limit_ranges.list(&params))
+        .context("Failed to list resource quotas")?;
+
+    let mut namespaces: BTreeMap<String, NamespaceQuotas> = BTreeMap::new();
+    for quota in &quotas.items {
+        namespace_entry(&mut namespaces, &quota.metadata.namespace)
+            .quotas
+            .extend(quota_usage(quota));
+    }
+    for range in &limit_ranges.items {
+        namespace_entry(&mut namespaces, &range.metadata.namespace)
+            .limits
+            .extend(limit_summaries(range));
+    }
+    Ok(namespaces.into_values().collect())
+}
+
+fn namespace_entry<'a>(
+    namespaces: &'a mut BTreeMap<String, NamespaceQuotas>,
+    namespace: &Option<String>,
+) -> &'a mut NamespaceQuotas {
+    let namespace = namespace.clone().unwrap_or_default();
+    namespaces
+        .entry(namespace.clone())
+        .or_insert_with(|| NamespaceQuotas {
+            namespace,
+            ..Default::default()
+        })
+}
+
+/// Every resource a quota sets a hard limit for, by resource name
+fn quota_usage(quota: &ResourceQuota) -> Vec<QuotaUsage> {
+    let name = quota.metadata.name.clone().unwrap_or_default();
+    let Some(status) = &quota.status else {
+        return Vec::new();
+    };
+    let used = status.used.clone().unwrap_or_default();
+    status
+        .hard
+        .iter()
+        .flatten()
+        .map(|(resource, hard)| QuotaUsage {
+            quota: name.clone(),
+            resource: resource.clone(),
+            // Not counted yet right after the quota was created
+            used: used
+                .get(resource)
+                .map(|q| q.0.clone())
+                .unwrap_or_else(|| "0".to_string()),
+            hard: hard.0.clone(),
+        })
+        .collect()
+}
+
+/// One row per resource of each item of a LimitRange
+fn limit_summaries(range: &LimitRange) -> Vec<LimitSummary> {
+    let name = range.metadata.name.clone().unwrap_or_default();
+    let mut summaries = Vec::new();
+    for item in range.spec.iter().flat_map(|s| &s.limits) {
+        let maps = [&item.default_request, &item.default, &item.max];
+        let mut resources: Vec<&String> = maps
+            .iter()
+            .filter_map(|m| m.as_ref())
+            .flat_map(|m| m.keys())
+            .collect();
+        resources.sort();
+        resources.dedup();
+        let value = |map: &Option<BTreeMap<String, Quantity>>, resource: &str| {
+            map.as_ref()
+                .and_then(|m| m.get(resource))
+                .map(|q| q.0.clone())
+        };
+        for resource in resources {
+            summaries.push(LimitSummary {
+                limit_range: name.clone(),
+                kind: item.type_.clone(),
+                resource: resource.clone(),
+                default_request: value(&item.default_request, resource),
+                default_limit: value(&item.default, resource),
+                max: value(&item.max, resource),
+            });
+        }
+    }
+    summaries
+}
+
+/// Binary suffixes first, so "Mi" is not read as "M"
+const QUANTITY_SUFFIXES: [(&str, f64); 14] = [
+    ("Ki", 1024.0),
+    ("Mi", 1048576.0),
+    ("Gi", 1073741824.0),
+    ("Ti", 1099511627776.0),
+    ("Pi", 1125899906842624.0),
+    ("Ei", 1152921504606846976.0),
+    ("m", 1e-3),
+    ("k", 1e3),
+    ("M", 1e6),
+    ("G", 1e9),
+    ("T", 1e12),
+    ("P", 1e15),
+    ("E", 1e18),
+    ("", 1.0),
+];
+
+/// A Kubernetes quantity ("500m", "2Gi", "1e3") as a plain number
+pub fn parse_quantity(quantity: &str) -> Option<f64> {
+    let quantity = quantity.trim();
+    QUANTITY_SUFFIXES.iter().find_map(|(suffix, multiplier)| {
+        let number = quantity.strip_suffix(suffix)?;
+        number.parse::<f64>().ok().map(|n| n * multiplier)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use k8s_openapi::api::core::v1::{LimitRangeItem, LimitRangeSpec, ResourceQuotaStatus};
+    use kube::api::ObjectMeta;
+
+    fn quantities(pairs: &[(&str, &str)]) -> Option<BTreeMap<String, Quantity>> {
+        Some(
+            pairs
+                .iter()
+                .map(|(k, v)| (k.to_string(), Quantity(v.to_string())))
+                .collect(),
+        )
+    }
+
+    #[test]
+    fn quantities_parse_with_suffixes() {
+        assert_eq!(parse_quantity("500m"), Some(0.5));
+        assert_eq!(parse_quantity("2"), Some(2.0));
+        assert_eq!(parse_quantity("1Gi"), Some(1073741824.0));
+        assert_eq!(parse_quantity("128M"), Some(128e6));
+        assert_eq!(parse_quantity("1e3"), Some(1000.0));
+        assert_eq!(parse_quantity("3x"), None);
+    }
+
+    #[test]
+    fn quota_usage_pairs_used_with_hard() {
+        let quota = ResourceQuota {
+            metadata: ObjectMeta {
+                name: Some("compute".to_string()),
+                ..Default::default()
+            },
+            status: Some(ResourceQuotaStatus {
+                hard: quantities(&[("limits.memory", "2Gi"), ("pods", "10")]),
+                used: quantities(&[("limits.memory", "512Mi")]),
+            }),
+            ..Default::default()
+        };
+        let usage = quota_usage(&quota);
+        assert_eq!(usage.len(), 2);
+        assert_eq!(usage[0].resource, "limits.memory");
+        assert_eq!(usage[0].ratio(), Some(0.25));
+        assert_eq!(usage[1].used, "0");
+        assert_eq!(usage[1].ratio(), Some(0.0));
+    }
+
+    #[test]
+    fn limit_ranges_list_each_resource_once() {
+        let range = LimitRange {
+            metadata: ObjectMeta {
+                name: Some("defaults".to_string()),
+                ..Default::default()
+            },
+            spec: Some(LimitRangeSpec {
+                limits: vec![LimitRangeItem {
+                    type_: "Container".to_string(),
+                    default: quantities(&[("cpu", "500m"), ("memory", "256Mi")]),
+                    default_request: quantities(&[("memory", "128Mi")]),
+                    ..Default::default()
+                }],
+            }),
+        };
+        let limits = limit_summaries(&range);
+        assert_eq!(limits.len(), 2);
+        assert_eq!(limits[0].resource, "cpu");
+        assert_eq!(limits[0].default_request, None);
+        assert_eq!(limits[1].default_request.as_deref(), Some("128Mi"));
+        assert_eq!(limits[1].default_limit.as_deref(), Some("256Mi"));
+    }
+}
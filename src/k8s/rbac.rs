@@ -0,0 +1,350 @@
+//! RBAC inspection — ServiceAccounts, Roles and RoleBindings per namespace,
+//! and `kubectl auth can-i` style checks
+//!
+//! Checks create a SelfSubjectAccessReview while impersonating the subject,
+//! so the answer comes from the API server's own authorizer.
+
+use std::collections::BTreeMap;
+
+use anyhow::{Context, Result};
+use k8s_openapi::api::authorization::v1::{
+    ResourceAttributes, SelfSubjectAccessReview, SelfSubjectAccessReviewSpec,
+};
+use k8s_openapi::api::core::v1::ServiceAccount;
+use k8s_openapi::api::rbac::v1::{ClusterRoleBinding, Role, RoleBinding, Subject};
+use kube::{
+    api::{Api, ListParams, PostParams},
+    Client,
+};
+
+use super::K8sClient;
+
+/// RBAC objects of one namespace
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct NamespaceRbac {
+    pub namespace: String,
+    pub service_accounts: Vec<String>,
+    pub roles: Vec<RoleSummary>,
+    /// RoleBindings of the namespace, plus ClusterRoleBindings naming one of
+    /// its ServiceAccounts
+    pub bindings: Vec<BindingSummary>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct RoleSummary {
+    pub name: String,
+    /// `verbs on resources`, one per rule
+    pub rules: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct BindingSummary {
+    pub name: String,
+    pub cluster_wide: bool,
+    /// `Role/name` or `ClusterRole/name`
+    pub role: String,
+    pub subjects: Vec<String>,
+}
+
+/// A parsed can-i question
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CanIQuery {
+    pub verb: String,
+    pub resource: String,
+    pub subresource: Option<String>,
+    pub group: String,
+    pub name: Option<String>,
+    pub namespace: String,
+    /// User to impersonate; None asks as the current user
+    pub user: Option<String>,
+}
+
+/// Answer to a can-i question
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct CanIAnswer {
+    pub allowed: bool,
+    pub reason: Option<String>,
+}
+
+/// Fetch ServiceAccounts, Roles and bindings of every namespace
+pub async fn get_rbac(client: &Client) -> Result<Vec<NamespaceRbac>> {
+    let params = ListParams::default();
+    let accounts: Api<ServiceAccount> = Api::all(client.clone());
+    let roles: Api<Role> = Api::all(client.clone());
+    let bindings: Api<RoleBinding> = Api::all(client.clone());
+    let cluster_bindings: Api<ClusterRoleBinding> = Api::all(client.clone());
+    let (accounts, roles, bindings, cluster_bindings) = tokio::try_join!(
+        accounts.list(&params),
+        roles.list(&params),
+        bindings.list(&params),
+        cluster_bindings.list(&params)
+    )
+    .context("Failed to list RBAC objects")?;
+
+    let mut namespaces: BTreeMap<String, NamespaceRbac> = BTreeMap::new();
+    for account in accounts.items {
+        let ns = account.metadata.namespace.unwrap_or_default();
+        namespace_entry(&mut namespaces, &ns)
+            .service_accounts
+            .push(account.metadata.name.unwrap_or_default());
+    }
+    for role in roles.items {
+        let ns = role.metadata.namespace.unwrap_or_default();
+        namespace_entry(&mut namespaces, &ns)
+            .roles
+            .push(RoleSummary {
+                name: role.metadata.name.unwrap_or_default(),
+                rules: role
+                    .rules
+                    .unwrap_or_default()
+                    .iter()
+                    .map(|r| {
+                        format!(
+                            "{} on {}",
+                            r.verbs.join(","),
+                            r.resources.as_deref().unwrap_or_default().join(",")
+                        )
+                    })
+                    .collect(),
+            });
+    }
+    for binding in bindings.items {
+        let ns = binding.metadata.namespace.unwrap_or_default();
+        namespace_entry(&mut namespaces, &ns)
+            .bindings
+            .push(BindingSummary {
+                name: binding.metadata.name.unwrap_or_default(),
+                cluster_wide: false,
+                role: format!("{}/{}", binding.role_ref.kind, binding.role_ref.name),
+                subjects: subject_names(binding.subjects.as_deref().unwrap_or_default(), &ns),
+            });
+    }
+    for binding in cluster_bindings.items {
+        let subjects = binding.subjects.unwrap_or_default();
+        let account_namespaces: Vec<String> = subjects
+            .iter()
+            .filter(|s| s.kind == "ServiceAccount")
+            .filter_map(|s| s.namespace.clone())
+            .collect();
+        for ns in account_namespaces {
+            let summary = BindingSummary {
+                name: binding.metadata.name.clone().unwrap_or_default(),
+                cluster_wide: true,
+                role: format!("{}/{}", binding.role_ref.kind, binding.role_ref.name),
+                subjects: subject_names(&subjects, &ns),
+            };
+            let bindings = &mut namespace_entry(&mut namespaces, &ns).bindings;
+            if !bindings.contains(&summary) {
+                bindings.push(summary);
+            }
+        }
+    }
+
+    Ok(namespaces.into_values().collect())
+}
+
+fn namespace_entry<'a>(
+    namespaces: &'a mut BTreeMap<String, NamespaceRbac>,
+    namespace: &str,
+) -> &'a mut NamespaceRbac {
+    namespaces
+        .entry(namespace.to_string())
+        .or_insert_with(|| NamespaceRbac {
+            namespace: namespace.to_string(),
+            ..Default::default()
+        })
+}
+
+/// Subjects as `kind/name`, qualifying ServiceAccounts of other namespaces
+fn subject_names(subjects: &[Subject], namespace: &str) -> Vec<String> {
+    subjects
+        .iter()
+        .map(|s| match s.namespace.as_deref() {
+            Some(ns) if s.kind == "ServiceAccount" && ns != namespace => {
+                format!("{}/{}/{}", s.kind, ns, s.name)
+            }
+            _ => format!("{}/{}", s.kind, s.name),
+        })
+        .collect()
+}
+
+/// API group of common resources, so `can-i list deployments` works without
+/// spelling out `deployments.apps`
+fn default_group(resource: &str) -> &'static str {
+    match resource {
+        "deployments" | "replicasets" | "statefulsets" | "daemonsets" | "controllerrevisions" => {
+            "apps"
+        }
+        "jobs" | "cronjobs" => "batch",
+        "ingresses" | "networkpolicies" | "ingressclasses" => "networking.k8s.io",
+        "roles" | "rolebindings" | "clusterroles" | "clusterrolebindings" => {
+            "rbac.authorization.k8s.io"
+        }
+        "horizontalpodautoscalers" => "autoscaling",
+        "poddisruptionbudgets" => "policy",
+        "storageclasses" | "volumeattachments" => "storage.k8s.io",
+        _ => "",
+    }
+}
+
+impl CanIQuery {
+    /// Parse `<verb> <resource>[/<subresource>] [<name>] [-n <namespace>]
+    /// [--as <who>]`. A resource may carry its group (`deployments.apps`).
+    /// `<who>` is a ServiceAccount of the namespace, `<namespace>/<account>`,
+    /// or any other user name as-is.
+    pub fn parse(input: &str, default_namespace: &str) -> Result<Self, String> {
+        let mut positional = Vec::new();
+        let mut namespace = default_namespace.to_string();
+        let mut who = None;
+        let mut tokens = input.split_whitespace();
+        while let Some(token) = tokens.next() {
+            match token {
+                "-n" | "--namespace" => {
+                    namespace = tokens
+                        .next()
+                        .ok_or_else(|| format!("{} needs a namespace", token))?
+                        .to_string();
+                }
+                "--as" => {
+                    who = Some(
+                        tokens
+                            .next()
+                            .ok_or("--as needs a ServiceAccount or user")?
+                            .to_string(),
+                    );
+                }
+                _ => positional.push(token),
+            }
+        }
+
+        let (verb, resource, name) = match positional.as_slice() {
+            [verb, resource] => (*verb, *resource, None),
+            [verb, resource, name] => (*verb, *resource, Some(name.to_string())),
+            _ => return Err("Usage: <verb> <resource> [name] [-n namespace] [--as who]".into()),
+        };
+        let (resource, subresource) = match resource.split_once('/') {
+            Some((r, sub)) => (r, Some(sub.to_string())),
+            None => (resource, None),
+        };
+        let (resource, group) = match resource.split_once('.') {
+            Some((r, group)) => (r.to_string(), group.to_string()),
+            None => (resource.to_string(), default_group(resource).to_string()),
+        };
+        let user = who.map(|who| {
+            if who.contains(':') || who.contains('@') {
+                who
+            } else if let Some((ns, account)) = who.split_once('/') {
+                format!("system:serviceaccount:{}:{}", ns, account)
+            } else {
+                format!("system:serviceaccount:{}:{}", namespace, who)
+            }
+        });
+
+        Ok(Self {
+            verb: verb.to_string(),
+            resource,
+            subresource,
+            group,
+            name,
+            namespace,
+            user,
+        })
+    }
+
+    /// Groups a ServiceAccount user belongs to; impersonation needs them for
+    /// bindings to `system:serviceaccounts[:<namespace>]` to apply
+    fn impersonated_groups(&self) -> Vec<String> {
+        let Some(rest) = self
+            .user
+            .as_deref()
+            .and_then(|u| u.strip_prefix("system:serviceaccount:"))
+        else {
+            return vec!["system:authenticated".to_string()];
+        };
+        let namespace = rest.split(':').next().unwrap_or_default();
+        vec![
+            "system:serviceaccounts".to_string(),
+            format!("system:serviceaccounts:{}", namespace),
+            "system:authenticated".to_string(),
+        ]
+    }
+}
+
+/// Ask the API server whether the query's subject may do it
+pub async fn can_i(client: &K8sClient, query: &CanIQuery) -> Result<CanIAnswer> {
+    let client = match &query.user {
+        Some(user) => client.impersonating(user, query.impersonated_groups())?,
+        None => client.client().clone(),
+    };
+    let review = SelfSubjectAccessReview {
+        spec: SelfSubjectAccessReviewSpec {
+            resource_attributes: Some(ResourceAttributes {
+                verb: Some(query.verb.clone()),
+                resource: Some(query.resource.clone()),
+                subresource: query.subresource.clone(),
+                group: Some(query.group.clone()),
+                name: query.name.clone(),
+                namespace: Some(query.namespace.clone()),
+                ..Default::default()
+            }),
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    let reviews: Api<SelfSubjectAccessReview> = Api::all(client);
+    let result = reviews
+        .create(&PostParams::default(), &review)
+        .await
+        .context("Access review failed")?;
+    let status = result.status.unwrap_or_default();
+    Ok(CanIAnswer {
+        allowed: status.allowed,
+        reason: status
+            .reason
+            .or(status.evaluation_error)
+            .filter(|r| !r.is_empty()),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parses_can_i_queries() {
+        let query = CanIQuery::parse("list deployments --as builder", "apps").unwrap();
+        assert_eq!(query.verb, "list");
+        assert_eq!(query.resource, "deployments");
+        assert_eq!(query.group, "apps");
+        assert_eq!(query.namespace, "apps");
+        assert_eq!(
+            query.user.as_deref(),
+            Some("system:serviceaccount:apps:builder")
+        );
+        assert_eq!(
+            query.impersonated_groups(),
+            [
+                "system:serviceaccounts",
+                "system:serviceaccounts:apps",
+                "system:authenticated"
+            ]
+        );
+
+        let query =
+            CanIQuery::parse("get pods/log web-0 -n prod --as ci/deployer", "default").unwrap();
+        assert_eq!(query.subresource.as_deref(), Some("log"));
+        assert_eq!(query.name.as_deref(), Some("web-0"));
+        assert_eq!(query.namespace, "prod");
+        assert_eq!(
+            query.user.as_deref(),
+            Some("system:serviceaccount:ci:deployer")
+        );
+
+        let query = CanIQuery::parse("create widgets.example.com", "default").unwrap();
+        assert_eq!(query.group, "example.com");
+        assert_eq!(query.user, None);
+
+        assert!(CanIQuery::parse("list", "default").is_err());
+        assert!(CanIQuery::parse("list pods --as", "default").is_err());
+    }
+}
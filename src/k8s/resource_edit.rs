@@ -0,0 +1,279 @@
+//! Fetch resources as editable YAML and apply edits with server-side apply
+
+use anyhow::{anyhow, Context, Result};
+use k8s_openapi::api::apps::v1::{Deployment, ReplicaSet};
+use k8s_openapi::api::core::v1::{ConfigMap, Pod};
+use k8s_openapi::api::networking::v1::Ingress;
+use kube::{
+    api::{Api, ApiResource, DynamicObject, Patch, PatchParams},
+    Client,
+};
+use std::fmt;
+
+/// Field manager name used for server-side apply
+const FIELD_MANAGER: &str = "k3dev";
+
+/// Resource kinds that can be edited from the TUI
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EditableKind {
+    Deployment,
+    ConfigMap,
+    Ingress,
+}
+
+impl EditableKind {
+    pub fn all() -> [EditableKind; 3] {
+        [
+            EditableKind::Deployment,
+            EditableKind::ConfigMap,
+            EditableKind::Ingress,
+        ]
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            EditableKind::Deployment => "deployment",
+            EditableKind::ConfigMap => "configmap",
+            EditableKind::Ingress => "ingress",
+        }
+    }
+
+    pub fn parse(s: &str) -> Option<Self> {
+        Self::all()
+            .into_iter()
+            .find(|k| k.as_str().eq_ignore_ascii_case(s.trim()))
+    }
+
+    fn api_resource(&self) -> ApiResource {
+        match self {
+            EditableKind::Deployment => ApiResource::erase::<Deployment>(&()),
+            EditableKind::ConfigMap => ApiResource::erase::<ConfigMap>(&()),
+            EditableKind::Ingress => ApiResource::erase::<Ingress>(&()),
+        }
+    }
+}
+
+/// A namespaced resource selected for editing
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResourceRef {
+    pub kind: EditableKind,
+    pub namespace: String,
+    pub name: String,
+}
+
+impl ResourceRef {
+    /// Parse `namespace/name`, or just `name` in `default_namespace`
+    pub fn parse(kind: EditableKind, target: &str, default_namespace: &str) -> Result<Self> {
+        let target = target.trim();
+        let (namespace, name) = match target.split_once('/') {
+            Some((ns, name)) => (ns.trim(), name.trim()),
+            None => (default_namespace, target),
+        };
+        if namespace.is_empty() || name.is_empty() || name.contains('/') {
+            return Err(anyhow!("Expected 'namespace/name', got '{}'", target));
+        }
+        Ok(Self {
+            kind,
+            namespace: namespace.to_string(),
+            name: name.to_string(),
+        })
+    }
+
+    fn api(&self, client: &Client) -> Api<DynamicObject> {
+        Api::namespaced_with(client.clone(), &self.namespace, &self.kind.api_resource())
+    }
+}
+
+impl fmt::Display for ResourceRef {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}/{}/{}", self.kind.as_str(), self.namespace, self.name)
+    }
+}
+
+/// Result of applying an edited manifest
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ApplyOutcome {
+    Applied,
+    /// The server rejected the apply because of a field-manager or
+    /// resourceVersion conflict; retrying with force takes ownership.
+    Conflict(String),
+}
+
+/// Find the Deployment that owns a pod (Pod → ReplicaSet → Deployment)
+pub async fn owning_deployment(
+    client: &Client,
+    namespace: &str,
+    pod_name: &str,
+) -> Result<ResourceRef> {
+    let pods: Api<Pod> = Api::namespaced(client.clone(), namespace);
+    let pod = pods
+        .get(pod_name)
+        .await
+        .with_context(|| format!("Failed to get pod {}", pod_name))?;
+
+    let rs_name = controller_of(&pod.metadata, "ReplicaSet")
+        .ok_or_else(|| anyhow!("Pod {} is not managed by a Deployment", pod_name))?;
+
+    let replica_sets: Api<ReplicaSet> = Api::namespaced(client.clone(), namespace);
+    let rs = replica_sets
+        .get(&rs_name)
+        .await
+        .with_context(|| format!("Failed to get ReplicaSet {}", rs_name))?;
+
+    let name = controller_of(&rs.metadata, "Deployment")
+        .ok_or_else(|| anyhow!("ReplicaSet {} is not managed by a Deployment", rs_name))?;
+
+    Ok(ResourceRef {
+        kind: EditableKind::Deployment,
+        namespace: namespace.to_string(),
+        name,
+    })
+}
+
+fn controller_of(
+    meta: &k8s_openapi::apimachinery::pkg::apis::meta::v1::ObjectMeta,
+    kind: &str,
+) -> Option<String> {
+    meta.owner_references
+        .as_ref()?
+        .iter()
+        .find(|o| o.kind == kind && o.controller.unwrap_or(false))
+        .map(|o| o.name.clone())
+}
+
+/// Fetch a resource and render it as YAML suitable for editing
+pub async fn fetch_resource_yaml(client: &Client, resource: &ResourceRef) -> Result<String> {
+    let obj = resource
+        .api(client)
+        .get(&resource.name)
+        .await
+        .with_context(|| format!("Failed to get {}", resource))?;
+
+    let mut value = serde_json::to_value(&obj)?;
+    strip_server_fields(&mut value);
+    Ok(serde_yml::to_string(&value)?)
+}
+
+/// Remove fields that are server-owned or noisy when editing.
+/// `resourceVersion` is kept so concurrent changes surface as a conflict.
+pub fn strip_server_fields(value: &mut serde_json::Value) {
+    let Some(obj) = value.as_object_mut() else {
+        return;
+    };
+    obj.remove("status");
+
+    if let Some(meta) = obj.get_mut("metadata").and_then(|m| m.as_object_mut()) {
+        for key in [
+            "managedFields",
+            "uid",
+            "creationTimestamp",
+            "generation",
+            "selfLink",
+        ] {
+            meta.remove(key);
+        }
+        if let Some(annotations) = meta.get_mut("annotations").and_then(|a| a.as_object_mut()) {
+            annotations.remove("kubectl.kubernetes.io/last-applied-configuration");
+            annotations.remove("deployment.kubernetes.io/revision");
+            if annotations.is_empty() {
+                meta.remove("annotations");
+            }
+        }
+    }
+}
+
+/// Apply an edited manifest with server-side apply.
+///
+/// With `force`, conflicting field managers are overridden and the stale
+/// `resourceVersion` is dropped.
+pub async fn apply_resource_yaml(
+    client: &Client,
+    resource: &ResourceRef,
+    yaml: &str,
+    force: bool,
+) -> Result<ApplyOutcome> {
+    let mut obj: DynamicObject =
+        serde_yml::from_str(yaml).context("Edited manifest is not valid YAML")?;
+
+    // Renaming or moving the object would create a new one instead of
+    // updating the edited resource.
+    if obj.metadata.name.as_deref() != Some(resource.name.as_str()) {
+        return Err(anyhow!("metadata.name must stay '{}'", resource.name));
+    }
+    if obj
+        .metadata
+        .namespace
+        .as_deref()
+        .is_some_and(|ns| ns != resource.namespace)
+    {
+        return Err(anyhow!(
+            "metadata.namespace must stay '{}'",
+            resource.namespace
+        ));
+    }
+
+    let mut params = PatchParams::apply(FIELD_MANAGER);
+    if force {
+        params = params.force();
+        obj.metadata.resource_version = None;
+    }
+
+    match resource
+        .api(client)
+        .patch(&resource.name, &params, &Patch::Apply(&obj))
+        .await
+    {
+        Ok(_) => Ok(ApplyOutcome::Applied),
+        Err(kube::Error::Api(e)) if e.code == 409 => Ok(ApplyOutcome::Conflict(e.message)),
+        Err(e) => Err(e).with_context(|| format!("Failed to apply {}", resource)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_resource_ref() {
+        let r = ResourceRef::parse(EditableKind::ConfigMap, "apps/settings", "default").unwrap();
+        assert_eq!(r.namespace, "apps");
+        assert_eq!(r.name, "settings");
+        assert_eq!(r.to_string(), "configmap/apps/settings");
+
+        let r = ResourceRef::parse(EditableKind::Ingress, "web", "default").unwrap();
+        assert_eq!(r.namespace, "default");
+        let r = ResourceRef::parse(EditableKind::Ingress, "web", "apps").unwrap();
+        assert_eq!(r.namespace, "apps");
+
+        assert!(ResourceRef::parse(EditableKind::Deployment, "a/b/c", "default").is_err());
+        assert!(ResourceRef::parse(EditableKind::Deployment, "ns/", "default").is_err());
+    }
+
+    #[test]
+    fn strip_removes_status_and_managed_fields() {
+        let mut value = serde_json::json!({
+            "apiVersion": "v1",
+            "kind": "ConfigMap",
+            "metadata": {
+                "name": "settings",
+                "resourceVersion": "42",
+                "uid": "abc",
+                "managedFields": [{}],
+                "annotations": {
+                    "kubectl.kubernetes.io/last-applied-configuration": "{}"
+                }
+            },
+            "data": { "key": "value" },
+            "status": {}
+        });
+        strip_server_fields(&mut value);
+
+        assert!(value.get("status").is_none());
+        let meta = &value["metadata"];
+        assert!(meta.get("managedFields").is_none());
+        assert!(meta.get("uid").is_none());
+        assert!(meta.get("annotations").is_none());
+        assert_eq!(meta["resourceVersion"], "42");
+        assert_eq!(value["data"]["key"], "value");
+    }
+}
@@ -0,0 +1,252 @@
+//! Config scaffolding from a running cluster
+//!
+//! `k3dev init --from-cluster` lists the Deployments outside the system
+//! namespaces and the Ingresses that route to them, and turns each
+//! Deployment into a command group with Logs, Shell and Restart entries.
+//! The ingress domain becomes `infrastructure.domain` when the hosts agree
+//! on one.
+
+use std::collections::{BTreeMap, HashMap};
+
+use anyhow::{Context, Result};
+use k8s_openapi::api::apps::v1::Deployment;
+use k8s_openapi::api::core::v1::Service;
+use k8s_openapi::api::networking::v1::Ingress;
+use kube::{
+    api::{Api, ListParams},
+    Client,
+};
+
+/// A Deployment to make a command group for
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Workload {
+    pub namespace: String,
+    pub name: String,
+    /// Label selector of its pods, e.g. "app=api,tier=web"
+    pub selector: String,
+    /// Ingress hosts routed to a Service selecting its pods
+    pub hosts: Vec<String>,
+}
+
+/// Namespaces that hold the cluster's own components
+fn is_system_namespace(namespace: &str) -> bool {
+    namespace.starts_with("kube-")
+}
+
+/// `k=v` pairs of a label map, in key order
+fn selector_string(labels: &BTreeMap<String, String>) -> String {
+    labels
+        .iter()
+        .map(|(k, v)| format!("{}={}", k, v))
+        .collect::<Vec<_>>()
+        .join(",")
+}
+
+/// Whether every label of `selector` is in `labels`
+fn selects(selector: &BTreeMap<String, String>, labels: &BTreeMap<String, String>) -> bool {
+    !selector.is_empty() && selector.iter().all(|(k, v)| labels.get(k) == Some(v))
+}
+
+/// Deployments of all non-system namespaces with the ingress hosts that
+/// reach them, sorted by namespace and name
+pub async fn discover_workloads(client: &Client) -> Result<Vec<Workload>> {
+    let lp = ListParams::default();
+    let deployments = Api::<Deployment>::all(client.clone())
+        .list(&lp)
+        .await
+        .context("Failed to list deployments")?;
+    let services = Api::<Service>::all(client.clone())
+        .list(&lp)
+        .await
+        .context("Failed to list services")?;
+    let ingresses = Api::<Ingress>::all(client.clone())
+        .list(&lp)
+        .await
+        .context("Failed to list ingresses")?;
+
+    // (namespace, service) -> hosts routed to it
+    let mut service_hosts: HashMap<(String, String), Vec<String>> = HashMap::new();
+    for ingress in &ingresses.items {
+        let namespace = ingress.metadata.namespace.clone().unwrap_or_default();
+        for rule in ingress.spec.iter().flat_map(|s| s.rules.iter().flatten()) {
+            let Some(host) = &rule.host else { continue };
+            for path in rule.http.iter().flat_map(|h| h.paths.iter()) {
+                if let Some(service) = &path.backend.service {
+                    let hosts = service_hosts
+                        .entry((namespace.clone(), service.name.clone()))
+                        .or_default();
+                    if !hosts.contains(host) {
+                        hosts.push(host.clone());
+                    }
+                }
+            }
+        }
+    }
+
+    let mut workloads = Vec::new();
+    for deployment in deployments.items {
+        let namespace = deployment.metadata.namespace.clone().unwrap_or_default();
+        let name = deployment.metadata.name.clone().unwrap_or_default();
+        if is_system_namespace(&namespace) {
+            continue;
+        }
+        let Some(spec) = &deployment.spec else {
+            continue;
+        };
+        let Some(match_labels) = &spec.selector.match_labels else {
+            continue;
+        };
+        let pod_labels = spec
+            .template
+            .metadata
+            .as_ref()
+            .and_then(|m| m.labels.clone())
+            .unwrap_or_default();
+
+        let mut hosts = Vec::new();
+        for service in &services.items {
+            if service.metadata.namespace.as_deref() != Some(namespace.as_str()) {
+                continue;
+            }
+            let selector = service
+                .spec
+                .as_ref()
+                .and_then(|s| s.selector.clone())
+                .unwrap_or_default();
+            if !selects(&selector, &pod_labels) {
+                continue;
+            }
+            let key = (
+                namespace.clone(),
+                service.metadata.name.clone().unwrap_or_default(),
+            );
+            for host in service_hosts.get(&key).into_iter().flatten() {
+                if !hosts.contains(host) {
+                    hosts.push(host.clone());
+                }
+            }
+        }
+
+        workloads.push(Workload {
+            namespace,
+            name,
+            selector: selector_string(match_labels),
+            hosts,
+        });
+    }
+    workloads.sort_by(|a, b| (&a.namespace, &a.name).cmp(&(&b.namespace, &b.name)));
+    Ok(workloads)
+}
+
+/// The domain most ingress hosts end in ("api.shop.test" -> "shop.test")
+pub fn common_domain(workloads: &[Workload]) -> Option<String> {
+    let mut counts: BTreeMap<&str, usize> = BTreeMap::new();
+    for host in workloads.iter().flat_map(|w| &w.hosts) {
+        if let Some((_, domain)) = host.split_once('.').filter(|(_, d)| !d.is_empty()) {
+            *counts.entry(domain).or_default() += 1;
+        }
+    }
+    counts
+        .into_iter()
+        .max_by_key(|(_, n)| *n)
+        .map(|(domain, _)| domain.to_string())
+}
+
+/// `commands:` section with a group per workload
+pub fn render_commands(workloads: &[Workload]) -> String {
+    let mut out = String::from(
+        r#"# =============================================================================
+# Custom Commands (one group per Deployment found in the cluster)
+# =============================================================================
+"#,
+    );
+    if workloads.is_empty() {
+        out.push_str("commands: []\n");
+        return out;
+    }
+    out.push_str("commands:\n");
+    for w in workloads {
+        out.push_str(&format!("  - name: \"{}\"\n", w.name));
+        if !w.hosts.is_empty() {
+            out.push_str(&format!("    # {}\n", w.hosts.join(", ")));
+        }
+        out.push_str(&format!(
+            r#"    commands:
+      - name: "Logs"
+        description: "Last 200 lines of {ns}/{name}"
+        exec:
+          target: {{ type: host }}
+          cmd: "kubectl logs -n {ns} deployment/{name} --all-containers --tail=200"
+      - name: "Shell"
+        exec:
+          target: {{ type: kubernetes, namespace: "{ns}", selector: "{selector}" }}
+          cmd: "/bin/sh"
+      - name: "Restart"
+        description: "Rolling restart of {ns}/{name}"
+        exec:
+          target: {{ type: host }}
+          cmd: "kubectl rollout restart -n {ns} deployment/{name} && kubectl rollout status -n {ns} deployment/{name}"
+"#,
+            ns = w.namespace,
+            name = w.name,
+            selector = w.selector,
+        ));
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Config;
+
+    fn workload(name: &str, hosts: &[&str]) -> Workload {
+        Workload {
+            namespace: "shop".to_string(),
+            name: name.to_string(),
+            selector: format!("app={},tier=web", name),
+            hosts: hosts.iter().map(|h| h.to_string()).collect(),
+        }
+    }
+
+    #[test]
+    fn commands_parse_as_config() {
+        let workloads = [workload("api", &["api.shop.test"]), workload("worker", &[])];
+        let yaml = render_commands(&workloads);
+        let config: Config = serde_yml::from_str(&yaml).unwrap();
+        assert_eq!(config.commands.len(), 2);
+        let names: Vec<&str> = config.commands[0]
+            .commands
+            .iter()
+            .map(|c| c.name.as_str())
+            .collect();
+        assert_eq!(names, ["Logs", "Shell", "Restart"]);
+        assert!(yaml.contains("selector: \"app=api,tier=web\""));
+        assert!(yaml.contains("kubectl rollout restart -n shop deployment/worker"));
+
+        let empty: Config = serde_yml::from_str(&render_commands(&[])).unwrap();
+        assert!(empty.commands.is_empty());
+    }
+
+    #[test]
+    fn domain_comes_from_the_most_common_host_suffix() {
+        let workloads = [
+            workload("api", &["api.shop.test", "admin.shop.test"]),
+            workload("docs", &["docs.example.com"]),
+        ];
+        assert_eq!(common_domain(&workloads).as_deref(), Some("shop.test"));
+        assert_eq!(common_domain(&[workload("api", &[])]), None);
+    }
+
+    #[test]
+    fn service_selector_must_match_pod_labels() {
+        let labels = BTreeMap::from([
+            ("app".to_string(), "api".to_string()),
+            ("tier".to_string(), "web".to_string()),
+        ]);
+        let selector = BTreeMap::from([("app".to_string(), "api".to_string())]);
+        assert!(selects(&selector, &labels));
+        assert!(!selects(&BTreeMap::new(), &labels));
+        assert!(!selects(&labels, &selector));
+    }
+}
@@ -0,0 +1,200 @@
+//! Seed data bundles
+//!
+//! A seed from `seeds:` pipes its SQL files into a command in the target pod,
+//! then runs its fixture commands there. The last seed applied is recorded
+//! in a ConfigMap in the cluster, so the record lives and dies with the
+//! cluster's data (including `k3dev volumes save`/`restore`).
+
+use std::collections::BTreeMap;
+
+use anyhow::{bail, Context, Result};
+use chrono::{DateTime, Utc};
+use k8s_openapi::api::core::v1::ConfigMap;
+use kube::{
+    api::{Api, ObjectMeta, Patch, PatchParams},
+    Client,
+};
+use tokio::sync::mpsc;
+
+use super::{K8sClient, PodExecutor};
+use crate::config::{expand_home, SeedConfig};
+use crate::ui::components::OutputLine;
+
+const FIELD_MANAGER: &str = "k3dev";
+
+/// ConfigMap holding the record of the last seed applied
+const RECORD_NAMESPACE: &str = "kube-system";
+const RECORD_NAME: &str = "k3dev-seed";
+
+/// Which seed was last applied to the cluster, and when
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SeedRecord {
+    pub name: String,
+    pub applied_at: DateTime<Utc>,
+}
+
+impl SeedRecord {
+    fn from_data(data: &BTreeMap<String, String>) -> Option<Self> {
+        Some(Self {
+            name: data.get("seed")?.clone(),
+            applied_at: data
+                .get("applied_at")
+                .and_then(|t| DateTime::parse_from_rfc3339(t).ok())
+                .map(|t| t.with_timezone(&Utc))
+                .unwrap_or_default(),
+        })
+    }
+
+    fn to_data(&self) -> BTreeMap<String, String> {
+        BTreeMap::from([
+            ("seed".to_string(), self.name.clone()),
+            ("applied_at".to_string(), self.applied_at.to_rfc3339()),
+        ])
+    }
+}
+
+/// The seed last applied to the cluster, if any
+pub async fn last_applied(client: &Client) -> Result<Option<SeedRecord>> {
+    let config_maps: Api<ConfigMap> = Api::namespaced(client.clone(), RECORD_NAMESPACE);
+    let config_map = config_maps
+        .get_opt(RECORD_NAME)
+        .await
+        .context("Failed to read the seed record")?;
+    Ok(config_map
+        .and_then(|cm| cm.data)
+        .and_then(|data| SeedRecord::from_data(&data)))
+}
+
+async fn record(client: &Client, record: &SeedRecord) -> Result<()> {
+    let config_maps: Api<ConfigMap> = Api::namespaced(client.clone(), RECORD_NAMESPACE);
+    let config_map = ConfigMap {
+        metadata: ObjectMeta {
+            name: Some(RECORD_NAME.to_string()),
+            namespace: Some(RECORD_NAMESPACE.to_string()),
+            labels: Some(BTreeMap::from([(
+                "app.kubernetes.io/managed-by".to_string(),
+                "k3dev".to_string(),
+            )])),
+            ..Default::default()
+        },
+        data: Some(record.to_data()),
+        ..Default::default()
+    };
+    config_maps
+        .patch(
+            RECORD_NAME,
+            &PatchParams::apply(FIELD_MANAGER).force(),
+            &Patch::Apply(&config_map),
+        )
+        .await
+        .context("Failed to record the applied seed")?;
+    Ok(())
+}
+
+/// Forward a command's output, stderr as warnings
+async fn send_output(output_tx: &mpsc::Sender<OutputLine>, stdout: &str, stderr: &str) {
+    for line in stdout.lines().filter(|l| !l.trim().is_empty()) {
+        let _ = output_tx.send(OutputLine::info(line)).await;
+    }
+    for line in stderr.lines().filter(|l| !l.trim().is_empty()) {
+        let _ = output_tx.send(OutputLine::warning(line)).await;
+    }
+}
+
+/// Apply `seed` to its target pod and record it, reporting as it goes.
+/// Stops at the first file or command that fails.
+pub async fn apply_seed(
+    k8s: &K8sClient,
+    seed: &SeedConfig,
+    default_namespace: &str,
+    output_tx: &mpsc::Sender<OutputLine>,
+) -> Result<SeedRecord> {
+    if seed.sql.is_empty() && seed.commands.is_empty() {
+        bail!("Seed '{}' has no sql files or commands", seed.name);
+    }
+    // Read everything first, so a missing file doesn't leave a half-applied seed
+    let mut files = Vec::with_capacity(seed.sql.len());
+    for path in &seed.sql {
+        let expanded = expand_home(std::path::Path::new(path))?;
+        let content = tokio::fs::read(&expanded)
+            .await
+            .with_context(|| format!("Failed to read {}", expanded.display()))?;
+        files.push((path.as_str(), content));
+    }
+
+    let namespace = if seed.namespace.is_empty() {
+        default_namespace
+    } else {
+        seed.namespace.as_str()
+    };
+    let container = (!seed.container.is_empty()).then_some(seed.container.as_str());
+    let executor = PodExecutor::new(k8s);
+    let pod = executor
+        .find_pod(
+            namespace,
+            Some(seed.selector.as_str()),
+            Some(seed.pod_name.as_str()),
+        )
+        .await
+        .context("Seed target pod not found")?;
+    let _ = output_tx
+        .send(OutputLine::info(format!(
+            "Seeding {}/{} with '{}'",
+            namespace, pod.name, seed.name
+        )))
+        .await;
+
+    for (path, content) in files {
+        let _ = output_tx
+            .send(OutputLine::info(format!("$ {} < {}", seed.sql_cmd, path)))
+            .await;
+        let result = executor
+            .exec_with_stdin(namespace, &pod.name, container, &seed.sql_cmd, content)
+            .await
+            .with_context(|| format!("Failed to load {}", path))?;
+        send_output(output_tx, &result.stdout, &result.stderr).await;
+        if result.exit_code != 0 {
+            bail!("{} failed with exit code {}", path, result.exit_code);
+        }
+    }
+    for command in &seed.commands {
+        let _ = output_tx
+            .send(OutputLine::info(format!("$ {}", command)))
+            .await;
+        let result = executor
+            .exec_simple(namespace, &pod.name, container, command)
+            .await
+            .with_context(|| format!("Failed to run {}", command))?;
+        send_output(output_tx, &result.stdout, &result.stderr).await;
+        if result.exit_code != 0 {
+            bail!("{} failed with exit code {}", command, result.exit_code);
+        }
+    }
+
+    let applied = SeedRecord {
+        name: seed.name.clone(),
+        applied_at: Utc::now(),
+    };
+    record(k8s.client(), &applied).await?;
+    let _ = output_tx
+        .send(OutputLine::success(format!("Seed '{}' applied", seed.name)))
+        .await;
+    Ok(applied)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn record_round_trips_through_config_map_data() {
+        let record = SeedRecord {
+            name: "demo".to_string(),
+            applied_at: DateTime::parse_from_rfc3339("2026-01-02T03:04:05Z")
+                .unwrap()
+                .with_timezone(&Utc),
+        };
+        assert_eq!(SeedRecord::from_data(&record.to_data()), Some(record));
+        assert_eq!(SeedRecord::from_data(&BTreeMap::new()), None);
+    }
+}
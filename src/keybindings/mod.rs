@@ -5,7 +5,7 @@
 
 mod resolver;
 
-pub use resolver::{KeyAction, KeybindingResolver};
+pub use resolver::{KeyAction, KeyContext, KeybindingResolver};
 
 use crossterm::event::{KeyCode, KeyModifiers};
 
@@ -89,6 +89,8 @@ impl KeyBinding {
             "left" | "arrowleft" => Ok(KeyCode::Left),
             "right" | "arrowright" => Ok(KeyCode::Right),
             "space" => Ok(KeyCode::Char(' ')),
+            // '+' is the modifier separator, so it needs a name
+            "plus" => Ok(KeyCode::Char('+')),
             "f1" => Ok(KeyCode::F(1)),
             "f2" => Ok(KeyCode::F(2)),
             "f3" => Ok(KeyCode::F(3)),
@@ -118,7 +120,17 @@ impl KeyBinding {
             other => other,
         };
 
-        self_code == normalized_code && self.modifiers == modifiers
+        // Terminals differ on whether Shift is reported for symbols like
+        // '?' or '+', so it is ignored for non-letter characters
+        let (self_mods, modifiers) = match normalized_code {
+            KeyCode::Char(c) if !c.is_ascii_alphabetic() => (
+                self.modifiers - KeyModifiers::SHIFT,
+                modifiers - KeyModifiers::SHIFT,
+            ),
+            _ => (self.modifiers, modifiers),
+        };
+
+        self_code == normalized_code && self_mods == modifiers
     }
 }
 
@@ -137,6 +149,7 @@ impl std::fmt::Display for KeyBinding {
         }
 
         let key_str = match self.code {
+            KeyCode::Char('+') => "Plus".to_string(),
             KeyCode::Char(c) => c.to_string(),
             KeyCode::Enter => "Enter".to_string(),
             KeyCode::Esc => "Esc".to_string(),
@@ -199,6 +212,16 @@ mod tests {
         assert!(!binding.matches(KeyCode::Char('c'), KeyModifiers::NONE));
     }
 
+    #[test]
+    fn test_symbols_ignore_shift() {
+        let plus = KeyBinding::parse("Plus").unwrap();
+        assert_eq!(plus.code, KeyCode::Char('+'));
+        assert!(plus.matches(KeyCode::Char('+'), KeyModifiers::NONE));
+        assert!(plus.matches(KeyCode::Char('+'), KeyModifiers::SHIFT));
+        assert!(!plus.matches(KeyCode::Char('+'), KeyModifiers::CONTROL));
+        assert_eq!(plus.to_string(), "Plus");
+    }
+
     #[test]
     fn test_display() {
         let binding = KeyBinding::parse("Ctrl+Shift+p").unwrap();
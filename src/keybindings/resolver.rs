@@ -17,6 +17,9 @@ pub enum KeyAction {
     CommandPalette,
     UpdateHosts,
     Cancel,
+    ShowOutput,
+    ReconcileTraefik,
+    OpenWhenReady,
 
     // Navigation actions
     MoveUp,
@@ -25,6 +28,48 @@ pub enum KeyAction {
     MoveRight,
     ToggleFocus,
     Execute,
+    PageUp,
+    PageDown,
+
+    // Layout and focus actions
+    Search,
+    GrowMenu,
+    ShrinkMenu,
+    FocusMenu,
+    FocusPods,
+    FocusActions,
+    CycleLayout,
+    ZoomPanel,
+    JumpBreadcrumb,
+    PreviewCommand,
+    ExpandAll,
+    CollapseAll,
+
+    // Popup and form actions
+    Close,
+    NextField,
+    PrevField,
+    Apply,
+    ForceApply,
+    SortTable,
+    ReverseSort,
+    ToggleRaw,
+
+    // Pod list actions
+    PodLogs,
+    PodDescribe,
+    PodTimeline,
+    PodVolumes,
+    PodShell,
+    PodCapture,
+    EditResource,
+    OverrideImage,
+    MountHostPath,
+    UnmountHostPath,
+    DiagnosePod,
+    PodTop,
+    PodShellPane,
+    PodLogsPane,
 
     // Custom command by path (e.g., "Group Name/Command Name")
     CustomCommand(String),
@@ -33,11 +78,267 @@ pub enum KeyAction {
     None,
 }
 
+impl KeyAction {
+    /// All built-in actions, in cheat-sheet order
+    pub fn builtin() -> [KeyAction; 51] {
+        [
+            KeyAction::Quit,
+            KeyAction::Help,
+            KeyAction::Refresh,
+            KeyAction::CommandPalette,
+            KeyAction::UpdateHosts,
+            KeyAction::Cancel,
+            KeyAction::ShowOutput,
+            KeyAction::ReconcileTraefik,
+            KeyAction::OpenWhenReady,
+            KeyAction::MoveUp,
+            KeyAction::MoveDown,
+            KeyAction::MoveLeft,
+            KeyAction::MoveRight,
+            KeyAction::ToggleFocus,
+            KeyAction::Execute,
+            KeyAction::PageUp,
+            KeyAction::PageDown,
+            KeyAction::Search,
+            KeyAction::GrowMenu,
+            KeyAction::ShrinkMenu,
+            KeyAction::FocusMenu,
+            KeyAction::FocusPods,
+            KeyAction::FocusActions,
+            KeyAction::CycleLayout,
+            KeyAction::ZoomPanel,
+            KeyAction::JumpBreadcrumb,
+            KeyAction::PreviewCommand,
+            KeyAction::ExpandAll,
+            KeyAction::CollapseAll,
+            KeyAction::Close,
+            KeyAction::NextField,
+            KeyAction::PrevField,
+            KeyAction::Apply,
+            KeyAction::ForceApply,
+            KeyAction::SortTable,
+            KeyAction::ReverseSort,
+            KeyAction::ToggleRaw,
+            KeyAction::PodLogs,
+            KeyAction::PodDescribe,
+            KeyAction::PodTimeline,
+            KeyAction::PodVolumes,
+            KeyAction::PodShell,
+            KeyAction::PodCapture,
+            KeyAction::EditResource,
+            KeyAction::OverrideImage,
+            KeyAction::MountHostPath,
+            KeyAction::UnmountHostPath,
+            KeyAction::DiagnosePod,
+            KeyAction::PodTop,
+            KeyAction::PodShellPane,
+            KeyAction::PodLogsPane,
+        ]
+    }
+
+    /// Look up a built-in action by its `keybindings:` config key
+    pub fn from_config_key(key: &str) -> Option<KeyAction> {
+        Self::builtin()
+            .into_iter()
+            .find(|a| a.config_key() == Some(key))
+    }
+
+    /// The `keybindings:` config key that remaps this action
+    pub fn config_key(&self) -> Option<&'static str> {
+        Some(match self {
+            KeyAction::Quit => "quit",
+            KeyAction::Help => "help",
+            KeyAction::Refresh => "refresh",
+            KeyAction::CommandPalette => "command_palette",
+            KeyAction::UpdateHosts => "update_hosts",
+            KeyAction::Cancel => "cancel",
+            KeyAction::ShowOutput => "show_output",
+            KeyAction::ReconcileTraefik => "reconcile_traefik",
+            KeyAction::OpenWhenReady => "open_when_ready",
+            KeyAction::MoveUp => "move_up",
+            KeyAction::MoveDown => "move_down",
+            KeyAction::MoveLeft => "move_left",
+            KeyAction::MoveRight => "move_right",
+            KeyAction::ToggleFocus => "toggle_focus",
+            KeyAction::Execute => "execute",
+            KeyAction::PageUp => "page_up",
+            KeyAction::PageDown => "page_down",
+            KeyAction::Search => "search",
+            KeyAction::GrowMenu => "grow_menu",
+            KeyAction::ShrinkMenu => "shrink_menu",
+            KeyAction::FocusMenu => "focus_menu",
+            KeyAction::FocusPods => "focus_pods",
+            KeyAction::FocusActions => "focus_actions",
+            KeyAction::CycleLayout => "cycle_layout",
+            KeyAction::ZoomPanel => "zoom_panel",
+            KeyAction::JumpBreadcrumb => "jump_breadcrumb",
+            KeyAction::PreviewCommand => "preview_command",
+            KeyAction::ExpandAll => "expand_all",
+            KeyAction::CollapseAll => "collapse_all",
+            KeyAction::Close => "close",
+            KeyAction::NextField => "next_field",
+            KeyAction::PrevField => "prev_field",
+            KeyAction::Apply => "apply",
+            KeyAction::ForceApply => "force_apply",
+            KeyAction::SortTable => "sort_table",
+            KeyAction::ReverseSort => "reverse_sort",
+            KeyAction::ToggleRaw => "toggle_raw",
+            KeyAction::PodLogs => "pod_logs",
+            KeyAction::PodDescribe => "pod_describe",
+            KeyAction::PodTimeline => "pod_timeline",
+            KeyAction::PodVolumes => "pod_volumes",
+            KeyAction::PodShell => "pod_shell",
+            KeyAction::PodCapture => "pod_capture",
+            KeyAction::EditResource => "edit_resource",
+            KeyAction::OverrideImage => "override_image",
+            KeyAction::MountHostPath => "mount_host_path",
+            KeyAction::UnmountHostPath => "unmount_host_path",
+            KeyAction::DiagnosePod => "diagnose_pod",
+            KeyAction::PodTop => "pod_top",
+            KeyAction::PodShellPane => "pod_shell_pane",
+            KeyAction::PodLogsPane => "pod_logs_pane",
+            KeyAction::CustomCommand(_) | KeyAction::None => return None,
+        })
+    }
+
+    /// Short human-readable description
+    pub fn description(&self) -> String {
+        match self {
+            KeyAction::Quit => "Quit application".to_string(),
+            KeyAction::Help => "Show/hide help overlay".to_string(),
+            KeyAction::Refresh => "Refresh data".to_string(),
+            KeyAction::CommandPalette => "Open command palette".to_string(),
+            KeyAction::UpdateHosts => "Update /etc/hosts".to_string(),
+            KeyAction::Cancel => "Cancel running command".to_string(),
+            KeyAction::ShowOutput => "Show running command output".to_string(),
+            KeyAction::ReconcileTraefik => "Reconcile drifted Traefik config".to_string(),
+            KeyAction::OpenWhenReady => "Open ingress URL once healthy".to_string(),
+            KeyAction::MoveUp => "Move up".to_string(),
+            KeyAction::MoveDown => "Move down".to_string(),
+            KeyAction::MoveLeft => "Move left / Collapse".to_string(),
+            KeyAction::MoveRight => "Move right / Expand".to_string(),
+            KeyAction::ToggleFocus => "Switch focus".to_string(),
+            KeyAction::Execute => "Execute / Toggle".to_string(),
+            KeyAction::PageUp => "Scroll detail panel up".to_string(),
+            KeyAction::PageDown => "Scroll detail panel down".to_string(),
+            KeyAction::Search => "Search/filter menu".to_string(),
+            KeyAction::GrowMenu => "Increase menu width".to_string(),
+            KeyAction::ShrinkMenu => "Decrease menu width".to_string(),
+            KeyAction::FocusMenu => "Focus commands".to_string(),
+            KeyAction::FocusPods => "Focus pods".to_string(),
+            KeyAction::FocusActions => "Focus cluster actions".to_string(),
+            KeyAction::CycleLayout => "Cycle panel layout".to_string(),
+            KeyAction::ZoomPanel => "Zoom focused panel".to_string(),
+            KeyAction::JumpBreadcrumb => "Jump along the breadcrumb".to_string(),
+            KeyAction::PreviewCommand => "Preview command before running".to_string(),
+            KeyAction::ExpandAll => "Expand all menu entries".to_string(),
+            KeyAction::CollapseAll => "Collapse all menu entries".to_string(),
+            KeyAction::Close => "Close / Cancel".to_string(),
+            KeyAction::NextField => "Next field".to_string(),
+            KeyAction::PrevField => "Previous field".to_string(),
+            KeyAction::Apply => "Apply edited resource".to_string(),
+            KeyAction::ForceApply => "Force-apply edit / finalize stuck namespace".to_string(),
+            KeyAction::SortTable => "Sort table by next column".to_string(),
+            KeyAction::ReverseSort => "Reverse table sort".to_string(),
+            KeyAction::ToggleRaw => "Toggle table / raw text".to_string(),
+            KeyAction::PodLogs => "Logs tab".to_string(),
+            KeyAction::PodDescribe => "Describe tab".to_string(),
+            KeyAction::PodTimeline => "Timeline tab".to_string(),
+            KeyAction::PodVolumes => "Volumes tab".to_string(),
+            KeyAction::PodShell => "Shell tab".to_string(),
+            KeyAction::PodCapture => "Capture tab".to_string(),
+            KeyAction::EditResource => "Edit owning Deployment".to_string(),
+            KeyAction::OverrideImage => "Override Deployment image".to_string(),
+            KeyAction::MountHostPath => "Mount host directory".to_string(),
+            KeyAction::UnmountHostPath => "Remove host mounts".to_string(),
+            KeyAction::DiagnosePod => "Diagnose crash loop".to_string(),
+            KeyAction::PodTop => "Processes in pod".to_string(),
+            KeyAction::PodShellPane => "Shell in tmux/zellij pane".to_string(),
+            KeyAction::PodLogsPane => "Follow logs in tmux/zellij pane".to_string(),
+            KeyAction::CustomCommand(path) => format!("Run '{}'", path),
+            KeyAction::None => String::new(),
+        }
+    }
+
+    /// Name used in conflict reports
+    fn label(&self) -> String {
+        match self {
+            KeyAction::CustomCommand(path) => format!("custom:{}", path),
+            other => other.config_key().unwrap_or("none").to_string(),
+        }
+    }
+}
+
+/// Where a key press is resolved. Each mode looks up its own bindings, so the
+/// same key can mean different things in the pod list and in a popup.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum KeyContext {
+    Normal,
+    PodStats,
+    OutputPopup,
+    Input,
+    Palette,
+}
+
+impl KeyContext {
+    pub fn all() -> [KeyContext; 5] {
+        [
+            KeyContext::Normal,
+            KeyContext::PodStats,
+            KeyContext::OutputPopup,
+            KeyContext::Input,
+            KeyContext::Palette,
+        ]
+    }
+
+    /// Key under `keybindings.contexts`
+    pub fn config_key(&self) -> &'static str {
+        match self {
+            KeyContext::Normal => "normal",
+            KeyContext::PodStats => "pod_stats",
+            KeyContext::OutputPopup => "output_popup",
+            KeyContext::Input => "input",
+            KeyContext::Palette => "palette",
+        }
+    }
+
+    pub fn from_config_key(key: &str) -> Option<KeyContext> {
+        Self::all().into_iter().find(|c| c.config_key() == key)
+    }
+
+    pub fn title(&self) -> &'static str {
+        match self {
+            KeyContext::Normal => "Main Screen",
+            KeyContext::PodStats => "Pod List",
+            KeyContext::OutputPopup => "Output Popup",
+            KeyContext::Input => "Input Form",
+            KeyContext::Palette => "Command Palette",
+        }
+    }
+
+    /// Whether keys not bound in this context fall through to the global bindings
+    fn inherits_global(&self) -> bool {
+        matches!(self, KeyContext::Normal | KeyContext::PodStats)
+    }
+}
+
+/// A key bound to more than one action
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KeyConflict {
+    pub context: KeyContext,
+    pub key: String,
+    pub actions: Vec<String>,
+}
+
 /// Resolves key events to actions based on configuration
 pub struct KeybindingResolver {
-    bindings: HashMap<KeyBinding, KeyAction>,
+    // Per-context bindings; `Normal` holds the global ones
+    bindings: HashMap<KeyContext, HashMap<KeyBinding, KeyAction>>,
     // Keep track of original binding strings for help display
-    binding_display: HashMap<KeyAction, String>,
+    binding_display: HashMap<(KeyContext, KeyAction), String>,
+    // Original string for every bound key, for the cheat sheet
+    key_names: HashMap<KeyBinding, String>,
+    conflicts: Vec<KeyConflict>,
 }
 
 impl KeybindingResolver {
@@ -51,6 +352,8 @@ impl KeybindingResolver {
         let mut resolver = Self {
             bindings: HashMap::new(),
             binding_display: HashMap::new(),
+            key_names: HashMap::new(),
+            conflicts: Vec::new(),
         };
 
         // Register default bindings
@@ -62,6 +365,9 @@ impl KeybindingResolver {
         resolver.register_default("H", KeyAction::UpdateHosts);
         resolver.register_default("Ctrl+c", KeyAction::Cancel);
         resolver.register_default("Ctrl+q", KeyAction::Quit);
+        resolver.register_default("o", KeyAction::ShowOutput);
+        resolver.register_default("F", KeyAction::ReconcileTraefik);
+        resolver.register_default("O", KeyAction::OpenWhenReady);
 
         // Navigation defaults
         resolver.register_default("k", KeyAction::MoveUp);
@@ -74,15 +380,101 @@ impl KeybindingResolver {
         resolver.register_default("Right", KeyAction::MoveRight);
         resolver.register_default("Tab", KeyAction::ToggleFocus);
         resolver.register_default("Enter", KeyAction::Execute);
+        resolver.register_default("PageUp", KeyAction::PageUp);
+        resolver.register_default("PageDown", KeyAction::PageDown);
+
+        // Layout and focus defaults
+        resolver.register_default("/", KeyAction::Search);
+        resolver.register_default("Plus", KeyAction::GrowMenu);
+        resolver.register_default("=", KeyAction::GrowMenu);
+        resolver.register_default("-", KeyAction::ShrinkMenu);
+        resolver.register_default("_", KeyAction::ShrinkMenu);
+        resolver.register_default("1", KeyAction::FocusMenu);
+        resolver.register_default("2", KeyAction::FocusPods);
+        resolver.register_default("3", KeyAction::FocusActions);
+        resolver.register_default("L", KeyAction::CycleLayout);
+        resolver.register_default("z", KeyAction::ZoomPanel);
+        resolver.register_default("b", KeyAction::JumpBreadcrumb);
+        resolver.register_default("p", KeyAction::PreviewCommand);
+        resolver.register_default("E", KeyAction::ExpandAll);
+        resolver.register_default("C", KeyAction::CollapseAll);
+
+        // Pod list (falls back to the global bindings)
+        let ctx = KeyContext::PodStats;
+        resolver.register_context_default(ctx, "l", KeyAction::PodLogs);
+        resolver.register_context_default(ctx, "d", KeyAction::PodDescribe);
+        resolver.register_context_default(ctx, "t", KeyAction::PodTimeline);
+        resolver.register_context_default(ctx, "v", KeyAction::PodVolumes);
+        resolver.register_context_default(ctx, "e", KeyAction::PodShell);
+        resolver.register_context_default(ctx, "c", KeyAction::PodCapture);
+        resolver.register_context_default(ctx, "E", KeyAction::EditResource);
+        resolver.register_context_default(ctx, "I", KeyAction::OverrideImage);
+        resolver.register_context_default(ctx, "M", KeyAction::MountHostPath);
+        resolver.register_context_default(ctx, "U", KeyAction::UnmountHostPath);
+        resolver.register_context_default(ctx, "D", KeyAction::DiagnosePod);
+        resolver.register_context_default(ctx, "p", KeyAction::PodTop);
+        resolver.register_context_default(ctx, "Ctrl+e", KeyAction::PodShellPane);
+        resolver.register_context_default(ctx, "Ctrl+l", KeyAction::PodLogsPane);
+
+        // Output popup
+        let ctx = KeyContext::OutputPopup;
+        resolver.register_context_default(ctx, "Esc", KeyAction::Close);
+        resolver.register_context_default(ctx, "Enter", KeyAction::Close);
+        resolver.register_context_default(ctx, "q", KeyAction::Close);
+        resolver.register_context_default(ctx, "k", KeyAction::MoveUp);
+        resolver.register_context_default(ctx, "Up", KeyAction::MoveUp);
+        resolver.register_context_default(ctx, "j", KeyAction::MoveDown);
+        resolver.register_context_default(ctx, "Down", KeyAction::MoveDown);
+        resolver.register_context_default(ctx, "h", KeyAction::MoveLeft);
+        resolver.register_context_default(ctx, "Left", KeyAction::MoveLeft);
+        resolver.register_context_default(ctx, "l", KeyAction::MoveRight);
+        resolver.register_context_default(ctx, "Right", KeyAction::MoveRight);
+        resolver.register_context_default(ctx, "a", KeyAction::Apply);
+        resolver.register_context_default(ctx, "f", KeyAction::ForceApply);
+        resolver.register_context_default(ctx, "s", KeyAction::SortTable);
+        resolver.register_context_default(ctx, "S", KeyAction::ReverseSort);
+        resolver.register_context_default(ctx, "t", KeyAction::ToggleRaw);
+        resolver.register_context_default(ctx, "z", KeyAction::ZoomPanel);
+        resolver.register_context_default(ctx, "?", KeyAction::Help);
+
+        // Input form (letters are typed into fields, so only special keys are bound)
+        let ctx = KeyContext::Input;
+        resolver.register_context_default(ctx, "Esc", KeyAction::Close);
+        resolver.register_context_default(ctx, "Tab", KeyAction::NextField);
+        resolver.register_context_default(ctx, "BackTab", KeyAction::PrevField);
+        resolver.register_context_default(ctx, "Up", KeyAction::MoveUp);
+        resolver.register_context_default(ctx, "Down", KeyAction::MoveDown);
+        resolver.register_context_default(ctx, "Left", KeyAction::MoveLeft);
+        resolver.register_context_default(ctx, "Right", KeyAction::MoveRight);
+        resolver.register_context_default(ctx, "Enter", KeyAction::Execute);
+
+        // Command palette
+        let ctx = KeyContext::Palette;
+        resolver.register_context_default(ctx, "Esc", KeyAction::Close);
+        resolver.register_context_default(ctx, "Enter", KeyAction::Execute);
+        resolver.register_context_default(ctx, "Up", KeyAction::MoveUp);
+        resolver.register_context_default(ctx, "Ctrl+k", KeyAction::MoveUp);
+        resolver.register_context_default(ctx, "Ctrl+p", KeyAction::MoveUp);
+        resolver.register_context_default(ctx, "Down", KeyAction::MoveDown);
+        resolver.register_context_default(ctx, "Ctrl+j", KeyAction::MoveDown);
+        resolver.register_context_default(ctx, "Ctrl+n", KeyAction::MoveDown);
 
         resolver
     }
 
     fn register_default(&mut self, key_str: &str, action: KeyAction) {
+        self.register_context_default(KeyContext::Normal, key_str, action);
+    }
+
+    fn register_context_default(&mut self, context: KeyContext, key_str: &str, action: KeyAction) {
         if let Ok(binding) = KeyBinding::parse(key_str) {
-            self.bindings.insert(binding, action.clone());
+            self.key_names.insert(binding.clone(), key_str.to_string());
+            self.bindings
+                .entry(context)
+                .or_default()
+                .insert(binding, action.clone());
             self.binding_display
-                .entry(action)
+                .entry((context, action))
                 .or_insert_with(|| key_str.to_string());
         }
     }
@@ -100,56 +492,234 @@ impl KeybindingResolver {
 
     fn apply_config(&mut self, config: &KeybindingsConfig) {
         // Built-in action remaps
-        self.remap_action(&config.quit, KeyAction::Quit);
-        self.remap_action(&config.help, KeyAction::Help);
-        self.remap_action(&config.refresh, KeyAction::Refresh);
-        self.remap_action(&config.command_palette, KeyAction::CommandPalette);
-        self.remap_action(&config.update_hosts, KeyAction::UpdateHosts);
-        self.remap_action(&config.cancel, KeyAction::Cancel);
-
-        // Navigation remaps
-        self.remap_action(&config.move_up, KeyAction::MoveUp);
-        self.remap_action(&config.move_down, KeyAction::MoveDown);
-        self.remap_action(&config.move_left, KeyAction::MoveLeft);
-        self.remap_action(&config.move_right, KeyAction::MoveRight);
-        self.remap_action(&config.toggle_focus, KeyAction::ToggleFocus);
-        self.remap_action(&config.execute, KeyAction::Execute);
-
-        // Custom command bindings
-        for (key_str, command_path) in &config.custom {
-            if let Ok(binding) = KeyBinding::parse(key_str) {
-                self.bindings
-                    .insert(binding, KeyAction::CustomCommand(command_path.clone()));
-            }
+        let remaps: Vec<(KeyAction, &String)> = config
+            .builtin()
+            .into_iter()
+            .filter_map(|(name, key)| Some((KeyAction::from_config_key(name)?, key?)))
+            .collect();
+        self.remap(KeyContext::Normal, remaps);
+
+        // Custom command bindings (sorted so conflict reports are stable)
+        let mut custom: Vec<_> = config.custom.iter().collect();
+        custom.sort();
+        for (key_str, command_path) in custom {
+            self.bind(
+                KeyContext::Normal,
+                key_str,
+                KeyAction::CustomCommand(command_path.clone()),
+            );
+        }
+
+        // Per-context remaps; unknown names are reported by the config validator
+        let mut contexts: Vec<_> = config.contexts.iter().collect();
+        contexts.sort_by(|a, b| a.0.cmp(b.0));
+        for (context_name, actions) in contexts {
+            let Some(context) = KeyContext::from_config_key(context_name) else {
+                continue;
+            };
+            let mut remaps: Vec<(KeyAction, &String)> = actions
+                .iter()
+                .filter_map(|(name, key)| Some((KeyAction::from_config_key(name)?, key)))
+                .collect();
+            remaps.sort_by_key(|(action, _)| action.config_key());
+            self.remap(context, remaps);
         }
     }
 
-    fn remap_action(&mut self, key_opt: &Option<String>, action: KeyAction) {
-        if let Some(key_str) = key_opt {
-            // Remove all existing bindings for this action
-            self.bindings.retain(|_, v| v != &action);
+    /// Replace the keys of each action in a context
+    fn remap(&mut self, context: KeyContext, remaps: Vec<(KeyAction, &String)>) {
+        // Drop the defaults of every remapped action first so that a remap
+        // onto another remapped action's old key is not reported as a conflict
+        if let Some(bindings) = self.bindings.get_mut(&context) {
+            for (action, _) in &remaps {
+                bindings.retain(|_, v| v != action);
+            }
+        }
 
-            // Add new binding
-            if let Ok(binding) = KeyBinding::parse(key_str) {
-                self.bindings.insert(binding, action.clone());
-                self.binding_display.insert(action, key_str.clone());
+        for (action, key_str) in remaps {
+            self.bind(context, key_str, action.clone());
+            self.binding_display
+                .insert((context, action), key_str.clone());
+        }
+    }
+
+    /// Bind a configured key, recording a conflict if it was already taken
+    fn bind(&mut self, context: KeyContext, key_str: &str, action: KeyAction) {
+        let Ok(binding) = KeyBinding::parse(key_str) else {
+            return;
+        };
+        let bindings = self.bindings.entry(context).or_default();
+        if let Some(existing) = bindings.get(&binding) {
+            if existing != &action {
+                let label = existing.label();
+                match self
+                    .conflicts
+                    .iter_mut()
+                    .find(|c| c.context == context && c.key == key_str)
+                {
+                    Some(conflict) => conflict.actions.push(action.label()),
+                    None => self.conflicts.push(KeyConflict {
+                        context,
+                        key: key_str.to_string(),
+                        actions: vec![label, action.label()],
+                    }),
+                }
             }
         }
+        bindings.insert(binding.clone(), action);
+        self.key_names.insert(binding, key_str.to_string());
     }
 
-    /// Resolve a key event to an action
+    /// Resolve a key event to a global action
     pub fn resolve(&self, code: KeyCode, modifiers: KeyModifiers) -> KeyAction {
-        for (binding, action) in &self.bindings {
-            if binding.matches(code, modifiers) {
-                return action.clone();
+        self.resolve_in(KeyContext::Normal, code, modifiers)
+    }
+
+    /// Resolve a key event in a context, falling back to the global
+    /// bindings for contexts that inherit them
+    pub fn resolve_in(
+        &self,
+        context: KeyContext,
+        code: KeyCode,
+        modifiers: KeyModifiers,
+    ) -> KeyAction {
+        let lookup = |context: KeyContext| {
+            self.bindings.get(&context).and_then(|bindings| {
+                bindings
+                    .iter()
+                    .find(|(binding, _)| binding.matches(code, modifiers))
+                    .map(|(_, action)| action.clone())
+            })
+        };
+
+        lookup(context)
+            .or_else(|| {
+                (context != KeyContext::Normal && context.inherits_global())
+                    .then(|| lookup(KeyContext::Normal))
+                    .flatten()
+            })
+            .unwrap_or(KeyAction::None)
+    }
+
+    /// Get the display string for an action's global keybinding
+    pub fn get_binding_display(&self, action: &KeyAction) -> Option<&str> {
+        self.binding_display
+            .get(&(KeyContext::Normal, action.clone()))
+            .map(|s| s.as_str())
+    }
+
+    /// All global keys bound to an action, sorted
+    pub fn keys_for(&self, action: &KeyAction) -> Vec<&str> {
+        self.keys_in(KeyContext::Normal, action)
+    }
+
+    /// All keys bound to an action in one context (without fallback), sorted
+    pub fn keys_in(&self, context: KeyContext, action: &KeyAction) -> Vec<&str> {
+        let mut keys: Vec<&str> = self
+            .bindings
+            .get(&context)
+            .into_iter()
+            .flatten()
+            .filter(|(_, a)| *a == action)
+            .filter_map(|(b, _)| self.key_names.get(b).map(String::as_str))
+            .collect();
+        keys.sort_unstable();
+        keys
+    }
+
+    /// Actions bound in one context (without fallback) with their keys, in
+    /// cheat-sheet order
+    pub fn context_bindings(&self, context: KeyContext) -> Vec<(Vec<&str>, KeyAction)> {
+        KeyAction::builtin()
+            .into_iter()
+            .filter_map(|action| {
+                let keys = self.keys_in(context, &action);
+                (!keys.is_empty()).then_some((keys, action))
+            })
+            .collect()
+    }
+
+    /// Keys bound to more than one action by the configuration (the last one wins)
+    pub fn conflicts(&self) -> &[KeyConflict] {
+        &self.conflicts
+    }
+
+    /// Configured global keys that the pod list handles itself, as
+    /// (key, action label, pod list action)
+    pub fn panel_shadowed(&self, config: &KeybindingsConfig) -> Vec<(String, String, String)> {
+        let configured = config
+            .builtin()
+            .into_iter()
+            .filter_map(|(name, key)| Some((key?.clone(), name.to_string())))
+            .chain(
+                config
+                    .custom
+                    .iter()
+                    .map(|(key, path)| (key.clone(), format!("custom:{}", path))),
+            );
+        let Some(pod_bindings) = self.bindings.get(&KeyContext::PodStats) else {
+            return Vec::new();
+        };
+
+        let mut shadowed = Vec::new();
+        for (key, label) in configured {
+            let Ok(binding) = KeyBinding::parse(&key) else {
+                continue;
+            };
+            if let Some(action) = pod_bindings.get(&binding) {
+                shadowed.push((key, label, action.description()));
             }
         }
-        KeyAction::None
+        shadowed.sort();
+        shadowed
     }
 
-    /// Get the display string for an action's keybinding
-    pub fn get_binding_display(&self, action: &KeyAction) -> Option<&str> {
-        self.binding_display.get(action).map(|s| s.as_str())
+    /// Render the effective keymap as a markdown cheat sheet
+    pub fn export_markdown(&self) -> String {
+        let mut out = String::from("# k3dev Keybindings\n");
+
+        for context in KeyContext::all() {
+            out.push_str(&format!("\n## {}\n\n", context.title()));
+            if context != KeyContext::Normal && context.inherits_global() {
+                out.push_str("Keys not listed here work as on the main screen.\n\n");
+            }
+            out.push_str("| Key | Action | Config key |\n|-----|--------|------------|\n");
+            for (keys, action) in self.context_bindings(context) {
+                let keys = keys
+                    .iter()
+                    .map(|k| format!("`{}`", k))
+                    .collect::<Vec<_>>()
+                    .join(" / ");
+                out.push_str(&format!(
+                    "| {} | {} | `{}` |\n",
+                    keys,
+                    action.description(),
+                    action.config_key().unwrap_or_default()
+                ));
+            }
+        }
+
+        let mut custom: Vec<(&str, &str)> = self
+            .bindings
+            .get(&KeyContext::Normal)
+            .into_iter()
+            .flatten()
+            .filter_map(|(b, a)| match a {
+                KeyAction::CustomCommand(path) => {
+                    Some((self.key_names.get(b)?.as_str(), path.as_str()))
+                }
+                _ => None,
+            })
+            .collect();
+        if !custom.is_empty() {
+            custom.sort_unstable();
+            out.push_str("\n## Custom Commands\n\n| Key | Command |\n|-----|---------|\n");
+            for (key, path) in custom {
+                out.push_str(&format!("| `{}` | {} |\n", key, path));
+            }
+        }
+
+        out
     }
 }
 
@@ -229,4 +799,128 @@ mod tests {
             _ => panic!("expected CustomCommand action"),
         }
     }
+
+    #[test]
+    fn test_remap_onto_default_is_a_conflict() {
+        let config = KeybindingsConfig {
+            refresh: Some("j".to_string()),
+            // Swapping two remapped actions is not a conflict
+            focus_menu: Some("2".to_string()),
+            focus_pods: Some("1".to_string()),
+            ..Default::default()
+        };
+
+        let resolver = KeybindingResolver::from_config(Some(&config));
+
+        assert_eq!(
+            resolver.conflicts(),
+            &[KeyConflict {
+                context: KeyContext::Normal,
+                key: "j".to_string(),
+                actions: vec!["move_down".to_string(), "refresh".to_string()],
+            }]
+        );
+        assert_eq!(
+            resolver.resolve(KeyCode::Char('2'), KeyModifiers::NONE),
+            KeyAction::FocusMenu
+        );
+    }
+
+    #[test]
+    fn test_panel_shadowed_keys() {
+        let mut custom = HashMap::new();
+        custom.insert("t".to_string(), "Tests/Run".to_string());
+        let config = KeybindingsConfig {
+            custom,
+            ..Default::default()
+        };
+
+        let resolver = KeybindingResolver::from_config(Some(&config));
+        let shadowed = resolver.panel_shadowed(&config);
+        assert_eq!(shadowed.len(), 1);
+        assert_eq!(shadowed[0].1, "custom:Tests/Run");
+        assert_eq!(shadowed[0].2, "Timeline tab");
+    }
+
+    #[test]
+    fn test_export_markdown_lists_every_action() {
+        let config = KeybindingsConfig {
+            search: Some("Ctrl+f".to_string()),
+            ..Default::default()
+        };
+        let markdown = KeybindingResolver::from_config(Some(&config)).export_markdown();
+
+        for action in KeyAction::builtin() {
+            assert!(markdown.contains(&format!("`{}`", action.config_key().unwrap())));
+        }
+        assert!(markdown.contains("| `Ctrl+f` | Search/filter menu |"));
+        assert!(markdown.contains("| `=` / `Plus` | Increase menu width |"));
+    }
+
+    #[test]
+    fn test_context_bindings_override_and_fall_back() {
+        let resolver = KeybindingResolver::new();
+
+        // 'l' opens logs in the pod list but moves right elsewhere
+        assert_eq!(
+            resolver.resolve_in(KeyContext::PodStats, KeyCode::Char('l'), KeyModifiers::NONE),
+            KeyAction::PodLogs
+        );
+        assert_eq!(
+            resolver.resolve(KeyCode::Char('l'), KeyModifiers::NONE),
+            KeyAction::MoveRight
+        );
+
+        // The pod list inherits global keys, popups do not
+        assert_eq!(
+            resolver.resolve_in(KeyContext::PodStats, KeyCode::Char('r'), KeyModifiers::NONE),
+            KeyAction::Refresh
+        );
+        assert_eq!(
+            resolver.resolve_in(
+                KeyContext::OutputPopup,
+                KeyCode::Char('r'),
+                KeyModifiers::NONE
+            ),
+            KeyAction::None
+        );
+        assert_eq!(
+            resolver.resolve_in(
+                KeyContext::OutputPopup,
+                KeyCode::Char('q'),
+                KeyModifiers::NONE
+            ),
+            KeyAction::Close
+        );
+    }
+
+    #[test]
+    fn test_context_remap() {
+        let mut popup = HashMap::new();
+        popup.insert("close".to_string(), "x".to_string());
+        popup.insert("apply".to_string(), "y".to_string());
+        let config = KeybindingsConfig {
+            contexts: HashMap::from([("output_popup".to_string(), popup)]),
+            ..Default::default()
+        };
+
+        let resolver = KeybindingResolver::from_config(Some(&config));
+        let ctx = KeyContext::OutputPopup;
+
+        assert_eq!(
+            resolver.resolve_in(ctx, KeyCode::Char('x'), KeyModifiers::NONE),
+            KeyAction::Close
+        );
+        assert_eq!(
+            resolver.resolve_in(ctx, KeyCode::Esc, KeyModifiers::NONE),
+            KeyAction::None
+        );
+        assert_eq!(resolver.keys_in(ctx, &KeyAction::Apply), vec!["y"]);
+        // Global bindings are untouched
+        assert_eq!(
+            resolver.resolve(KeyCode::Esc, KeyModifiers::NONE),
+            KeyAction::Quit
+        );
+        assert!(resolver.conflicts().is_empty());
+    }
 }
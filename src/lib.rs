@@ -1,11 +1,19 @@
+pub mod api;
 pub mod app;
+pub mod browser;
 pub mod capture;
 pub mod cli;
 pub mod cluster;
 pub mod commands;
+pub mod completions;
 pub mod config;
+pub mod crash;
+pub mod error;
 pub mod hooks;
+pub mod ide_tasks;
 pub mod k8s;
 pub mod keybindings;
 pub mod logging;
+pub mod multiplexer;
+pub mod smoke;
 pub mod ui;
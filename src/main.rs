@@ -1,5 +1,5 @@
 use anyhow::Result;
-use clap::{Parser, Subcommand};
+use clap::{CommandFactory, Parser, Subcommand};
 use crossterm::{
     event::{DisableMouseCapture, EnableMouseCapture},
     execute,
@@ -8,18 +8,25 @@ use crossterm::{
 use ratatui::backend::CrosstermBackend;
 use ratatui::Terminal;
 use std::io::{self, Write};
-use std::panic;
 
+mod api;
 mod app;
+mod browser;
 mod capture;
 mod cli;
 mod cluster;
 mod commands;
+mod completions;
 mod config;
+mod crash;
+mod error;
 mod hooks;
+mod ide_tasks;
 mod k8s;
 mod keybindings;
 mod logging;
+mod multiplexer;
+mod smoke;
 mod ui;
 
 use app::App;
@@ -34,6 +41,14 @@ struct Cli {
     #[arg(short, long, global = true)]
     config: Option<String>,
 
+    /// Command tags to show, comma-separated (replaces `tags:` in the config)
+    #[arg(long, global = true, value_delimiter = ',')]
+    tags: Option<Vec<String>>,
+
+    /// Run no hooks; in the TUI, also no background refreshes or automatic /etc/hosts checks
+    #[arg(long, global = true)]
+    safe: bool,
+
     #[command(subcommand)]
     command: Option<CliCommand>,
 }
@@ -41,14 +56,23 @@ struct Cli {
 #[derive(Subcommand)]
 enum CliCommand {
     /// Start the cluster
-    Start,
+    Start {
+        /// Wait for services and the smoke tests; exit 1 if any of them fails
+        #[arg(long)]
+        smoke_tests: bool,
+    },
     /// Stop the cluster
     Stop,
     /// Restart the cluster
     Restart,
     /// Destroy the cluster
     #[command(alias = "delete")]
-    Destroy,
+    Destroy {
+        /// Save the data volumes to a tar file first; keep the cluster if that fails
+        /// (default path: ./k3dev-volumes-<cluster>-<timestamp>.tar)
+        #[arg(long, value_name = "PATH")]
+        export_volumes: Option<Option<String>>,
+    },
     /// Show cluster info
     Info,
     /// Delete all snapshot images
@@ -59,6 +83,15 @@ enum CliCommand {
     Preflight,
     /// Update /etc/hosts with ingress entries
     UpdateHosts,
+    /// Restore /etc/hosts from the backup taken before k3dev's last edit
+    RestoreHosts,
+    /// Show what applying the configured manifests would change (exit 1 if anything)
+    Diff,
+    /// Manage the helper that updates /etc/hosts without a password prompt
+    HostsHelper {
+        #[command(subcommand)]
+        command: HostsHelperCommand,
+    },
     /// List pods with status
     Pods {
         /// Namespace to list pods from (default: all namespaces)
@@ -69,9 +102,9 @@ enum CliCommand {
     Logs {
         /// Pod name
         pod: String,
-        /// Namespace (default: "default")
-        #[arg(short, long, default_value = "default")]
-        namespace: String,
+        /// Namespace (default: cluster.default_namespace from the config)
+        #[arg(short, long)]
+        namespace: Option<String>,
         /// Container name (for multi-container pods)
         #[arg(long)]
         container: Option<String>,
@@ -86,33 +119,33 @@ enum CliCommand {
     Describe {
         /// Pod name
         pod: String,
-        /// Namespace (default: "default")
-        #[arg(short, long, default_value = "default")]
-        namespace: String,
+        /// Namespace (default: cluster.default_namespace from the config)
+        #[arg(short, long)]
+        namespace: Option<String>,
     },
     /// Delete a pod
     DeletePod {
         /// Pod name
         pod: String,
-        /// Namespace (default: "default")
-        #[arg(short, long, default_value = "default")]
-        namespace: String,
+        /// Namespace (default: cluster.default_namespace from the config)
+        #[arg(short, long)]
+        namespace: Option<String>,
     },
     /// Restart a pod (delete and let deployment recreate)
     RestartPod {
         /// Pod name
         pod: String,
-        /// Namespace (default: "default")
-        #[arg(short, long, default_value = "default")]
-        namespace: String,
+        /// Namespace (default: cluster.default_namespace from the config)
+        #[arg(short, long)]
+        namespace: Option<String>,
     },
     /// Execute a shell in a pod
     Exec {
         /// Pod name
         pod: String,
-        /// Namespace (default: "default")
-        #[arg(short, long, default_value = "default")]
-        namespace: String,
+        /// Namespace (default: cluster.default_namespace from the config)
+        #[arg(short, long)]
+        namespace: Option<String>,
         /// Container name (for multi-container pods)
         #[arg(long)]
         container: Option<String>,
@@ -120,6 +153,75 @@ enum CliCommand {
         #[arg(long, default_value = "/bin/sh")]
         cmd: String,
     },
+    /// Run a configured command (e.g. "App/Migrate") without the TUI
+    Run {
+        /// Command path: group and command names separated by "/"
+        path: String,
+        /// Placeholder value, e.g. --set version=42 (repeatable)
+        #[arg(long = "set", value_name = "KEY=VALUE")]
+        set: Vec<String>,
+    },
+    /// List runnable command paths from the config (one per line, or --json)
+    ListCommands {
+        /// Print name, description, target and inputs as JSON
+        #[arg(long)]
+        json: bool,
+    },
+    /// Write editor tasks that run each configured command via `k3dev run`
+    ExportTasks {
+        /// Editor: vscode (.vscode/tasks.json) or jetbrains (.run/*.run.xml)
+        ide: ide_tasks::Ide,
+        /// Project directory to write into
+        #[arg(long, default_value = ".")]
+        dir: String,
+    },
+    /// Write a starter config (to --config, else ./k3dev.yml) from a template or a running cluster
+    Init {
+        /// Services and commands to start with
+        #[arg(long, value_enum, default_value_t = config::Template::Minimal)]
+        template: config::Template,
+        /// Make a command group per Deployment of the cluster kubectl points at
+        #[arg(long, conflicts_with = "template")]
+        from_cluster: bool,
+        /// Kubeconfig context to inspect with --from-cluster (default: current)
+        #[arg(long, requires = "from_cluster")]
+        context: Option<String>,
+    },
+    /// Print a shell completion script
+    Completions {
+        /// Shell to generate the script for
+        shell: completions::Shell,
+    },
+    /// Collect logs, redacted config, listings and events into a tarball for bug reports
+    SupportBundle {
+        /// Output path (default: ./k3dev-support-<cluster>-<timestamp>.tar)
+        #[arg(short, long)]
+        out: Option<String>,
+    },
+    /// Save the cluster's data volumes to a tar file, or restore them into a fresh cluster
+    Volumes {
+        #[command(subcommand)]
+        command: VolumesCommand,
+    },
+    /// Serve the local HTTP API (status, command catalog, command runs) without the TUI
+    Serve {
+        /// Address to listen on (default: api.listen from the config, else 127.0.0.1:7373)
+        #[arg(long)]
+        listen: Option<String>,
+    },
+    /// Mirror the output of the TUI running for this cluster, read-only, until Ctrl-C
+    Attach,
+    /// List clusters of deleted git branches (infrastructure.per_branch); --yes removes them
+    PruneBranches {
+        /// Remove the stopped ones instead of only listing them
+        #[arg(long)]
+        yes: bool,
+    },
+    /// Keybinding utilities
+    Keys {
+        #[command(subcommand)]
+        command: KeysCommand,
+    },
     /// Run docker CLI against the cluster's Docker daemon (bypasses Docker Desktop proxy)
     #[command(trailing_var_arg = true)]
     Docker {
@@ -139,9 +241,9 @@ enum CliCommand {
         /// Pod name to capture from (joins the pod's pause container netns).
         #[arg(long)]
         pod: Option<String>,
-        /// Namespace for `--pod`.
-        #[arg(short, long, default_value = "default")]
-        namespace: String,
+        /// Namespace for `--pod` (default: cluster.default_namespace).
+        #[arg(short, long)]
+        namespace: Option<String>,
         /// Docker container name to capture from directly.
         #[arg(long)]
         container: Option<String>,
@@ -172,13 +274,44 @@ enum CliCommand {
     },
 }
 
+#[derive(Subcommand)]
+enum KeysCommand {
+    /// Print the effective keymap as a markdown cheat sheet
+    Export,
+}
+
+#[derive(Subcommand)]
+enum VolumesCommand {
+    /// Write the rancher data and PV storage of the stopped cluster to a tar file
+    Save {
+        /// Output path (default: ./k3dev-volumes-<cluster>-<timestamp>.tar)
+        #[arg(short, long)]
+        out: Option<String>,
+    },
+    /// Restore a saved tar file into the volumes of a destroyed cluster; start it afterwards
+    Restore {
+        /// File written by `k3dev volumes save`
+        file: String,
+    },
+}
+
+#[derive(Subcommand)]
+enum HostsHelperCommand {
+    /// Install the helper for the config's cluster and domain (asks for the sudo password once)
+    Install,
+    /// Remove the helper
+    Uninstall,
+    /// Show whether the helper is installed
+    Status,
+}
+
 impl CliCommand {
     fn as_cluster_action(&self) -> Option<ClusterAction> {
         match self {
-            CliCommand::Start => Some(ClusterAction::Start),
+            CliCommand::Start { .. } => Some(ClusterAction::Start),
             CliCommand::Stop => Some(ClusterAction::Stop),
             CliCommand::Restart => Some(ClusterAction::Restart),
-            CliCommand::Destroy => Some(ClusterAction::Destroy),
+            CliCommand::Destroy { .. } => Some(ClusterAction::Destroy),
             CliCommand::Info => Some(ClusterAction::Info),
             CliCommand::DeleteSnapshots => Some(ClusterAction::DeleteSnapshots),
             _ => None,
@@ -218,99 +351,189 @@ async fn main() -> Result<()> {
 
     let cli = Cli::parse();
 
+    // Read by every config load, including reloads and the HTTP API
+    if let Some(tags) = &cli.tags {
+        std::env::set_var(config::TAGS_ENV, tags.join(","));
+    }
+    if cli.safe {
+        std::env::set_var(config::SAFE_ENV, "1");
+    }
+
     // If a subcommand was given, run headlessly (no TUI)
     if let Some(cmd) = &cli.command {
         let config_path = cli.config.as_deref();
-        let exit_code = match cmd {
-            CliCommand::Docker { args } => run_docker_passthrough(args, config_path).await?,
-            CliCommand::Diagnostics => cli::run_cli_diagnostics(config_path).await?,
-            CliCommand::Preflight => cli::run_cli_preflight(config_path).await?,
-            CliCommand::UpdateHosts => cli::run_cli_update_hosts(config_path).await?,
-            CliCommand::Pods { namespace } => {
-                cli::run_cli_pods(config_path, namespace.as_deref()).await?
-            }
-            CliCommand::Logs {
-                pod,
-                namespace,
-                container,
-                tail,
-                follow,
-            } => {
-                cli::run_cli_logs(
-                    config_path,
+        let namespace_or_default = |namespace: &Option<String>| {
+            namespace
+                .clone()
+                .unwrap_or_else(|| cli::default_namespace(config_path))
+        };
+        // Errors are printed with their remediation and exit code instead of
+        // anyhow's debug output
+        let result: Result<i32> = async {
+            Ok(match cmd {
+                CliCommand::Docker { args } => run_docker_passthrough(args, config_path).await?,
+                CliCommand::Diagnostics => cli::run_cli_diagnostics(config_path).await?,
+                CliCommand::Keys {
+                    command: KeysCommand::Export,
+                } => cli::run_cli_keys_export(config_path)?,
+                CliCommand::Preflight => cli::run_cli_preflight(config_path).await?,
+                CliCommand::SupportBundle { out } => {
+                    cli::run_cli_support_bundle(config_path, out.as_deref()).await?
+                }
+                CliCommand::Volumes { command } => match command {
+                    VolumesCommand::Save { out } => {
+                        cli::run_cli_volumes(config_path, true, out.as_deref()).await?
+                    }
+                    VolumesCommand::Restore { file } => {
+                        cli::run_cli_volumes(config_path, false, Some(file)).await?
+                    }
+                },
+                CliCommand::PruneBranches { yes } => {
+                    cli::run_cli_prune_branches(config_path, *yes).await?
+                }
+                CliCommand::Attach => cli::run_cli_attach(config_path).await?,
+                CliCommand::Serve { listen } => {
+                    cli::run_cli_serve(config_path, listen.as_deref()).await?
+                }
+                CliCommand::Run { path, set } => {
+                    cli::run_cli_command(config_path, path, set).await?
+                }
+                CliCommand::ListCommands { json } => {
+                    cli::run_cli_list_commands(config_path, *json)?
+                }
+                CliCommand::ExportTasks { ide, dir } => {
+                    cli::run_cli_export_tasks(config_path, *ide, dir)?
+                }
+                CliCommand::Init {
+                    template,
+                    from_cluster,
+                    context,
+                } => {
+                    if *from_cluster {
+                        cli::run_cli_init_from_cluster(config_path, context.as_deref()).await?
+                    } else {
+                        cli::run_cli_init(config_path, *template)?
+                    }
+                }
+                CliCommand::Completions { shell } => {
+                    let subcommands: Vec<completions::Subcommand> = Cli::command()
+                        .get_subcommands()
+                        .map(|c| completions::Subcommand {
+                            name: c.get_name().to_string(),
+                            about: c.get_about().map(|a| a.to_string()).unwrap_or_default(),
+                        })
+                        .collect();
+                    print!("{}", completions::generate(*shell, &subcommands));
+                    0
+                }
+                CliCommand::UpdateHosts => cli::run_cli_update_hosts(config_path).await?,
+                CliCommand::RestoreHosts => cli::run_cli_restore_hosts().await?,
+                CliCommand::Diff => cli::run_cli_diff(config_path).await?,
+                CliCommand::HostsHelper { command } => match command {
+                    HostsHelperCommand::Install => cli::run_cli_hosts_helper_install(config_path)?,
+                    HostsHelperCommand::Uninstall => cli::run_cli_hosts_helper_uninstall()?,
+                    HostsHelperCommand::Status => cli::run_cli_hosts_helper_status(),
+                },
+                CliCommand::Pods { namespace } => {
+                    cli::run_cli_pods(config_path, namespace.as_deref()).await?
+                }
+                CliCommand::Logs {
                     pod,
                     namespace,
-                    container.as_deref(),
-                    *tail,
-                    *follow,
-                )
-                .await?
-            }
-            CliCommand::Describe { pod, namespace } => {
-                cli::run_cli_describe(config_path, pod, namespace).await?
-            }
-            CliCommand::DeletePod { pod, namespace } => {
-                cli::run_cli_delete_pod(config_path, pod, namespace).await?
-            }
-            CliCommand::RestartPod { pod, namespace } => {
-                cli::run_cli_restart_pod(config_path, pod, namespace).await?
-            }
-            CliCommand::Exec {
-                pod,
-                namespace,
-                container,
-                cmd: shell_cmd,
-            } => {
-                cli::run_cli_exec(config_path, pod, namespace, container.as_deref(), shell_cmd)
+                    container,
+                    tail,
+                    follow,
+                } => {
+                    cli::run_cli_logs(
+                        config_path,
+                        pod,
+                        &namespace_or_default(namespace),
+                        container.as_deref(),
+                        *tail,
+                        *follow,
+                    )
                     .await?
-            }
-            CliCommand::Capture {
-                pod,
-                namespace,
-                container,
-                iface,
-                filter,
-                duration,
-                max_bytes,
-                out,
-                out_dir,
-                image,
-                open,
-            } => {
-                cli::run_cli_capture(
-                    config_path,
-                    pod.as_deref(),
+                }
+                CliCommand::Describe { pod, namespace } => {
+                    cli::run_cli_describe(config_path, pod, &namespace_or_default(namespace))
+                        .await?
+                }
+                CliCommand::DeletePod { pod, namespace } => {
+                    cli::run_cli_delete_pod(config_path, pod, &namespace_or_default(namespace))
+                        .await?
+                }
+                CliCommand::RestartPod { pod, namespace } => {
+                    cli::run_cli_restart_pod(config_path, pod, &namespace_or_default(namespace))
+                        .await?
+                }
+                CliCommand::Exec {
+                    pod,
                     namespace,
-                    container.as_deref(),
-                    iface.clone(),
-                    filter.as_deref(),
-                    duration.as_deref(),
-                    max_bytes.as_deref(),
-                    out.as_deref(),
-                    out_dir.as_deref(),
-                    image.as_deref(),
-                    *open,
-                )
-                .await?
-            }
-            _ => {
-                if let Some(action) = cmd.as_cluster_action() {
-                    cli::run_cli_action(action, config_path).await?
-                } else {
-                    0
+                    container,
+                    cmd: shell_cmd,
+                } => {
+                    cli::run_cli_exec(
+                        config_path,
+                        pod,
+                        &namespace_or_default(namespace),
+                        container.as_deref(),
+                        shell_cmd,
+                    )
+                    .await?
                 }
-            }
-        };
+                CliCommand::Capture {
+                    pod,
+                    namespace,
+                    container,
+                    iface,
+                    filter,
+                    duration,
+                    max_bytes,
+                    out,
+                    out_dir,
+                    image,
+                    open,
+                } => {
+                    cli::run_cli_capture(
+                        config_path,
+                        pod.as_deref(),
+                        &namespace_or_default(namespace),
+                        container.as_deref(),
+                        iface.clone(),
+                        filter.as_deref(),
+                        duration.as_deref(),
+                        max_bytes.as_deref(),
+                        out.as_deref(),
+                        out_dir.as_deref(),
+                        image.as_deref(),
+                        *open,
+                    )
+                    .await?
+                }
+                _ => {
+                    if let Some(action) = cmd.as_cluster_action() {
+                        let smoke_tests = matches!(cmd, CliCommand::Start { smoke_tests: true });
+                        let export_volumes = match cmd {
+                            CliCommand::Destroy { export_volumes } => export_volumes.clone(),
+                            _ => None,
+                        };
+                        cli::run_cli_action(action, config_path, smoke_tests, export_volumes)
+                            .await?
+                    } else {
+                        0
+                    }
+                }
+            })
+        }
+        .await;
+        let exit_code = result.unwrap_or_else(|e| cli::report_error(&e));
         std::process::exit(exit_code);
     }
 
     // Otherwise, launch the TUI
-    // Set up panic hook to restore terminal on panic
-    let original_hook = panic::take_hook();
-    panic::set_hook(Box::new(move |panic_info| {
-        restore_terminal();
-        original_hook(panic_info);
-    }));
+    // Log panics with their backtrace; the terminal is restored before one on
+    // this thread ends k3dev, background tasks' panics are shown in a popup
+    crash::install_hook(restore_terminal);
 
     enable_raw_mode()?;
     let mut stdout = io::stdout();
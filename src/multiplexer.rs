@@ -0,0 +1,146 @@
+//! tmux and zellij integration
+//!
+//! When k3dev runs inside a terminal multiplexer, pod shells and log follows
+//! can open next to it as `k3dev exec` / `k3dev logs -f` in a pane of their
+//! own instead of the detail panel.
+
+use std::path::Path;
+use std::process::Command;
+
+use anyhow::{bail, Context, Result};
+
+use crate::config::PaneTarget;
+
+/// A terminal multiplexer k3dev runs inside
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Multiplexer {
+    Tmux,
+    Zellij,
+}
+
+impl Multiplexer {
+    /// The multiplexer this process runs in, from the variables each sets.
+    /// Nested ones set both; tmux is checked first.
+    pub fn detect() -> Option<Self> {
+        Self::from_env(|name| std::env::var_os(name).is_some_and(|v| !v.is_empty()))
+    }
+
+    fn from_env(is_set: impl Fn(&str) -> bool) -> Option<Self> {
+        if is_set("TMUX") {
+            Some(Multiplexer::Tmux)
+        } else if is_set("ZELLIJ") {
+            Some(Multiplexer::Zellij)
+        } else {
+            None
+        }
+    }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            Multiplexer::Tmux => "tmux",
+            Multiplexer::Zellij => "zellij",
+        }
+    }
+
+    /// Arguments that make the multiplexer run `command` in `cwd`, in a new
+    /// pane or window titled `title`
+    fn open_args(
+        self,
+        target: PaneTarget,
+        title: &str,
+        cwd: &Path,
+        command: &[String],
+    ) -> Vec<String> {
+        let cwd = cwd.display().to_string();
+        let mut args: Vec<String> = match self {
+            Multiplexer::Tmux => {
+                let mut args = match target {
+                    PaneTarget::Pane => vec!["split-window".into(), "-h".into()],
+                    PaneTarget::Window => vec!["new-window".into(), "-n".into(), title.into()],
+                };
+                args.extend(["-c".into(), cwd]);
+                args
+            }
+            Multiplexer::Zellij => {
+                let mut args = vec!["run".into(), "--name".into(), title.into()];
+                match target {
+                    PaneTarget::Pane => args.extend(["--direction".into(), "right".into()]),
+                    PaneTarget::Window => args.push("--floating".into()),
+                }
+                args.extend(["--cwd".into(), cwd]);
+                args
+            }
+        };
+        args.push("--".into());
+        args.extend(command.iter().cloned());
+        args
+    }
+
+    /// Run `command` in a new pane or window titled `title`
+    pub fn open(self, target: PaneTarget, title: &str, command: &[String]) -> Result<()> {
+        let cwd = std::env::current_dir().context("No working directory")?;
+        let output = Command::new(self.name())
+            .args(self.open_args(target, title, &cwd, command))
+            .output()
+            .with_context(|| format!("Failed to run {}", self.name()))?;
+        if !output.status.success() {
+            bail!(
+                "{} failed: {}",
+                self.name(),
+                String::from_utf8_lossy(&output.stderr).trim()
+            );
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detects_from_the_environment() {
+        assert_eq!(Multiplexer::from_env(|_| false), None);
+        assert_eq!(
+            Multiplexer::from_env(|name| name == "ZELLIJ"),
+            Some(Multiplexer::Zellij)
+        );
+        assert_eq!(Multiplexer::from_env(|_| true), Some(Multiplexer::Tmux));
+    }
+
+    #[test]
+    fn command_follows_the_separator() {
+        let command = vec!["k3dev".to_string(), "exec".to_string(), "web-1".to_string()];
+        let cwd = Path::new("/src/app");
+        assert_eq!(
+            Multiplexer::Tmux.open_args(PaneTarget::Window, "web-1", cwd, &command),
+            [
+                "new-window",
+                "-n",
+                "web-1",
+                "-c",
+                "/src/app",
+                "--",
+                "k3dev",
+                "exec",
+                "web-1"
+            ]
+        );
+        assert_eq!(
+            Multiplexer::Zellij.open_args(PaneTarget::Pane, "web-1", cwd, &command),
+            [
+                "run",
+                "--name",
+                "web-1",
+                "--direction",
+                "right",
+                "--cwd",
+                "/src/app",
+                "--",
+                "k3dev",
+                "exec",
+                "web-1"
+            ]
+        );
+    }
+}
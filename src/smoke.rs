@@ -0,0 +1,207 @@
+//! Smoke tests run after services are deployed
+//!
+//! Each `smoke_tests` entry requests a URL or runs a command until it passes
+//! or its timeout is up. The report goes to the output and, like hook
+//! completions, to anyone subscribed: the TUI shows it in a popup.
+
+use std::time::{Duration, Instant};
+
+use anyhow::Result;
+use once_cell::sync::Lazy;
+use tokio::sync::{broadcast, mpsc};
+
+use crate::cluster::DockerManager;
+use crate::commands::check_visible;
+use crate::config::{SmokeTest, VisibleCheck};
+use crate::k8s::K8sClient;
+use crate::ui::components::OutputLine;
+
+/// Time between two attempts of a failing test
+const RETRY_INTERVAL: Duration = Duration::from_secs(2);
+
+/// Longest a single attempt may take
+const ATTEMPT_TIMEOUT: Duration = Duration::from_secs(10);
+
+/// Outcome of one smoke test
+#[derive(Debug, Clone)]
+pub struct SmokeResult {
+    pub name: String,
+    pub passed: bool,
+    /// What the last attempt saw, e.g. "HTTP 503"
+    pub detail: String,
+    pub elapsed: Duration,
+}
+
+impl SmokeResult {
+    pub fn output_line(&self) -> OutputLine {
+        let text = format!(
+            "{} {} ({:.1}s): {}",
+            if self.passed { "✓" } else { "✗" },
+            self.name,
+            self.elapsed.as_secs_f64(),
+            self.detail
+        );
+        if self.passed {
+            OutputLine::success(text)
+        } else {
+            OutputLine::error(text)
+        }
+    }
+}
+
+/// Outcome of every smoke test after one deployment
+#[derive(Debug, Clone, Default)]
+pub struct SmokeReport {
+    pub results: Vec<SmokeResult>,
+}
+
+impl SmokeReport {
+    pub fn all_passed(&self) -> bool {
+        self.results.iter().all(|r| r.passed)
+    }
+
+    /// "3/4 smoke tests passed"
+    pub fn summary(&self) -> String {
+        let passed = self.results.iter().filter(|r| r.passed).count();
+        format!("{}/{} smoke tests passed", passed, self.results.len())
+    }
+}
+
+/// Smoke tests run inside cluster starts, which only carry an output channel,
+/// so reports are broadcast process-wide like hook completions
+static SMOKE_REPORTS: Lazy<broadcast::Sender<SmokeReport>> = Lazy::new(|| broadcast::channel(4).0);
+
+/// Subscribe to smoke test reports
+pub fn subscribe_reports() -> broadcast::Receiver<SmokeReport> {
+    SMOKE_REPORTS.subscribe()
+}
+
+/// Run every test at once, print each result as it ends and the summary,
+/// and broadcast the report
+pub async fn run_smoke_tests(
+    tests: &[SmokeTest],
+    k8s: Option<&K8sClient>,
+    docker: Option<&DockerManager>,
+    output_tx: &mpsc::Sender<OutputLine>,
+) -> SmokeReport {
+    let _ = output_tx
+        .send(OutputLine::info(format!(
+            "Running {} smoke tests...",
+            tests.len()
+        )))
+        .await;
+
+    let results = futures::future::join_all(tests.iter().map(|test| async move {
+        let result = run_test(test, k8s, docker).await;
+        let _ = output_tx.send(result.output_line()).await;
+        result
+    }))
+    .await;
+
+    let report = SmokeReport { results };
+    let summary = report.summary();
+    let _ = output_tx
+        .send(if report.all_passed() {
+            OutputLine::success(summary)
+        } else {
+            OutputLine::error(summary)
+        })
+        .await;
+    // Err only means nobody is subscribed
+    let _ = SMOKE_REPORTS.send(report.clone());
+    report
+}
+
+/// Try a test until it passes or its timeout is up
+async fn run_test(
+    test: &SmokeTest,
+    k8s: Option<&K8sClient>,
+    docker: Option<&DockerManager>,
+) -> SmokeResult {
+    let started = Instant::now();
+    loop {
+        let (passed, detail) = match attempt(test, k8s, docker).await {
+            Ok(()) => (true, "passed".to_string()),
+            Err(e) => (false, format!("{:#}", e)),
+        };
+        if passed || started.elapsed() + RETRY_INTERVAL > test.timeout {
+            return SmokeResult {
+                name: test.name.clone(),
+                passed,
+                detail,
+                elapsed: started.elapsed(),
+            };
+        }
+        tokio::time::sleep(RETRY_INTERVAL).await;
+    }
+}
+
+async fn attempt(
+    test: &SmokeTest,
+    k8s: Option<&K8sClient>,
+    docker: Option<&DockerManager>,
+) -> Result<()> {
+    if test.url.is_empty() && test.exec.is_none() {
+        anyhow::bail!("nothing to check: set url or exec");
+    }
+    if !test.url.is_empty() {
+        check_url(&test.url, test.status).await?;
+    }
+    if let Some(exec) = &test.exec {
+        let check = VisibleCheck::Exec(exec.clone());
+        if !check_visible(&check, k8s, docker, ATTEMPT_TIMEOUT).await? {
+            anyhow::bail!("`{}` failed", exec.cmd);
+        }
+    }
+    Ok(())
+}
+
+async fn check_url(url: &str, expected: Option<u16>) -> Result<()> {
+    let client = reqwest::Client::builder()
+        .danger_accept_invalid_certs(true)
+        .connect_timeout(Duration::from_secs(2))
+        .timeout(ATTEMPT_TIMEOUT)
+        .redirect(reqwest::redirect::Policy::none())
+        .build()?;
+    let status = client.get(url).send().await?.status();
+    if status_matches(status.as_u16(), expected) {
+        Ok(())
+    } else {
+        anyhow::bail!("HTTP {}", status.as_u16())
+    }
+}
+
+fn status_matches(status: u16, expected: Option<u16>) -> bool {
+    match expected {
+        Some(expected) => status == expected,
+        None => (200..300).contains(&status),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn any_2xx_passes_unless_a_status_is_set() {
+        assert!(status_matches(204, None));
+        assert!(!status_matches(302, None));
+        assert!(status_matches(302, Some(302)));
+        assert!(!status_matches(200, Some(302)));
+    }
+
+    #[test]
+    fn summary_counts_passed_tests() {
+        let result = |passed| SmokeResult {
+            name: "web".to_string(),
+            passed,
+            detail: String::new(),
+            elapsed: Duration::ZERO,
+        };
+        let report = SmokeReport {
+            results: vec![result(true), result(false)],
+        };
+        assert!(!report.all_passed());
+        assert_eq!(report.summary(), "1/2 smoke tests passed");
+    }
+}
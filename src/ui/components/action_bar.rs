@@ -1,16 +1,37 @@
 use std::path::PathBuf;
+use std::time::Instant;
 
 use ratatui::{
     layout::{Alignment, Rect},
-    style::Modifier,
+    style::{Modifier, Style},
     text::{Line, Span},
     widgets::Paragraph,
     Frame,
 };
 
+use super::{PodSort, ResourceUsage};
+use crate::cluster::{ClusterState, ReadinessState, ReadinessUpdate};
+use crate::config::ReadinessGate;
 use crate::ui::styles::Styles;
 use crate::ui::theme::Theme;
 
+/// Width of a usage gauge bar in cells
+const GAUGE_WIDTH: usize = 6;
+
+/// Usage (percent of host) at which a gauge turns warning / error colored
+const GAUGE_WARNING_PERCENT: f64 = 70.0;
+const GAUGE_CRITICAL_PERCENT: f64 = 90.0;
+
+/// Longest running-command badge, in characters
+const BACKGROUND_RUN_WIDTH: usize = 48;
+
+/// Frames of the spinner next to an awaited URL, one per SPINNER_INTERVAL
+const SPINNER: [char; 10] = ['⠋', '⠙', '⠹', '⠸', '⠼', '⠴', '⠦', '⠧', '⠇', '⠏'];
+pub const SPINNER_INTERVAL: std::time::Duration = std::time::Duration::from_millis(100);
+
+/// Longest workspace tab label, in characters
+const WORKSPACE_LABEL_WIDTH: usize = 24;
+
 /// Cluster action definition
 #[derive(Debug, Clone)]
 pub struct Action {
@@ -29,6 +50,7 @@ pub enum ClusterAction {
     Restart,
     Destroy,
     Info,
+    Logs,
     DeleteSnapshots,
     Diagnostics,
     PreflightCheck,
@@ -42,6 +64,7 @@ impl ClusterAction {
             ClusterAction::Restart => "restart",
             ClusterAction::Destroy => "destroy",
             ClusterAction::Info => "info",
+            ClusterAction::Logs => "logs",
             ClusterAction::DeleteSnapshots => "delete-snapshots",
             ClusterAction::Diagnostics => "diagnostics",
             ClusterAction::PreflightCheck => "preflight-check",
@@ -49,6 +72,17 @@ impl ClusterAction {
     }
 }
 
+/// What clicking the right side of the bar opens
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BarLink {
+    /// Pods sorted by a gauge's resource
+    Sort(PodSort),
+    /// Details of the component the cluster is degraded by
+    ClusterHealth,
+    /// How the live Traefik config differs from k3dev's
+    TraefikDrift,
+}
+
 /// Action bar component for cluster operations
 pub struct ActionBar {
     actions: Vec<Action>,
@@ -56,6 +90,23 @@ pub struct ActionBar {
     styles: Styles,
     cluster_name: Option<String>,
     config_path: Option<PathBuf>,
+    /// Lifecycle state and why it's degraded; hidden until first known
+    cluster_state: ClusterState,
+    degraded_reason: Option<String>,
+    /// Summary of how the live Traefik config drifted
+    traefik_drift: Option<String>,
+    /// Seed last applied to the cluster
+    seed: Option<String>,
+    /// Components still coming up after start returned
+    readiness: Vec<ReadinessUpdate>,
+    usage: Option<ResourceUsage>,
+    /// Popup command still running after its popup was closed
+    background_run: Option<String>,
+    /// URL opened once it's healthy, and since when it's awaited
+    awaited_url: Option<(String, Instant)>,
+    /// Workspace tab labels and the active tab; hidden with a single tab
+    workspaces: Vec<String>,
+    active_workspace: usize,
 }
 
 impl ActionBar {
@@ -101,6 +152,13 @@ impl ActionBar {
                     enabled: true,
                     shortcut: Some('I'),
                 },
+                Action {
+                    id: "logs".to_string(),
+                    label: "Logs".to_string(),
+                    icon: "≡".to_string(),
+                    enabled: true,
+                    shortcut: Some('L'),
+                },
                 Action {
                     id: "preflight".to_string(),
                     label: "Preflight".to_string(),
@@ -120,6 +178,16 @@ impl ActionBar {
             styles: Styles::from_theme(theme),
             cluster_name: None,
             config_path: None,
+            cluster_state: ClusterState::Unknown,
+            degraded_reason: None,
+            traefik_drift: None,
+            seed: None,
+            readiness: Vec::new(),
+            usage: None,
+            background_run: None,
+            awaited_url: None,
+            workspaces: Vec::new(),
+            active_workspace: 0,
         }
     }
 
@@ -133,6 +201,76 @@ impl ActionBar {
         self.config_path = path;
     }
 
+    /// Show the cluster's lifecycle state
+    pub fn set_cluster_state(&mut self, state: ClusterState, degraded_reason: Option<String>) {
+        self.cluster_state = state;
+        self.degraded_reason = degraded_reason;
+    }
+
+    /// Show (or hide) the Traefik drift badge
+    pub fn set_traefik_drift(&mut self, summary: Option<String>) {
+        self.traefik_drift = summary;
+    }
+
+    /// Show (or hide) the last applied seed
+    pub fn set_seed(&mut self, name: Option<String>) {
+        self.seed = name;
+    }
+
+    /// Track a readiness gate. Badges disappear once every gate is ready.
+    pub fn set_readiness(&mut self, update: ReadinessUpdate) {
+        match self.readiness.iter_mut().find(|r| r.gate == update.gate) {
+            Some(existing) => existing.state = update.state,
+            None => self.readiness.push(update),
+        }
+        if self
+            .readiness
+            .iter()
+            .all(|r| r.state == ReadinessState::Ready)
+        {
+            self.readiness.clear();
+        }
+    }
+
+    /// Show aggregate pod usage gauges (None hides them)
+    pub fn set_usage(&mut self, usage: Option<ResourceUsage>) {
+        self.usage = usage;
+    }
+
+    /// Show a running-command badge with its latest output (None hides it)
+    pub fn set_background_run(&mut self, label: Option<String>) {
+        self.background_run = label;
+    }
+
+    /// Show a spinner for a URL waiting to turn healthy (None hides it)
+    pub fn set_awaited_url(&mut self, url: Option<String>) {
+        self.awaited_url = url.map(|url| (url, Instant::now()));
+    }
+
+    /// Whether something on the bar animates and needs frequent redraws
+    pub fn is_animating(&self) -> bool {
+        self.awaited_url.is_some()
+    }
+
+    /// Show workspace tabs before the actions
+    pub fn set_workspaces(&mut self, labels: Vec<String>, active: usize) {
+        self.workspaces = labels;
+        self.active_workspace = active;
+    }
+
+    /// Gates that gave up waiting
+    pub fn timed_out_readiness(&self) -> Vec<ReadinessGate> {
+        self.readiness
+            .iter()
+            .filter(|r| r.state == ReadinessState::TimedOut)
+            .map(|r| r.gate)
+            .collect()
+    }
+
+    pub fn clear_readiness(&mut self) {
+        self.readiness.clear();
+    }
+
     pub fn move_left(&mut self) {
         if self.selected_index > 0 {
             self.selected_index -= 1;
@@ -207,6 +345,7 @@ impl ActionBar {
             "restart" => Some(ClusterAction::Restart),
             "destroy" => Some(ClusterAction::Destroy),
             "info" => Some(ClusterAction::Info),
+            "logs" => Some(ClusterAction::Logs),
             "diagnostics" => Some(ClusterAction::Diagnostics),
             "preflight" => Some(ClusterAction::PreflightCheck),
             _ => None,
@@ -219,6 +358,10 @@ impl ActionBar {
         // Each action is: icon (1-2 chars) + space + label + separator " │ " (3 chars)
         // Approximate: "▶ Start │ " = ~10 chars per action
         let mut pos = 3; // Start after focus-stripe prefix ("▌ " or "  ")
+        let tabs = self.workspace_tabs();
+        if !tabs.is_empty() {
+            pos += tabs.iter().map(|t| t.chars().count()).sum::<usize>() + 3;
+        }
         for (i, action) in self.actions.iter().enumerate() {
             if !action.enabled {
                 continue;
@@ -232,6 +375,44 @@ impl ActionBar {
         None
     }
 
+    /// Get the workspace tab at x position (for mouse click handling)
+    pub fn workspace_at_x(&self, x: usize) -> Option<usize> {
+        // After the focus stripe and the "[name] " cluster badge
+        let mut pos = 2 + self
+            .cluster_name
+            .as_ref()
+            .map_or(0, |name| name.chars().count() + 3);
+        for (i, tab) in self.workspace_tabs().iter().enumerate() {
+            let width = tab.chars().count();
+            if x >= pos && x < pos + width {
+                return Some(i);
+            }
+            pos += width;
+        }
+        None
+    }
+
+    /// Tab strip text, one entry per workspace; empty with a single one
+    fn workspace_tabs(&self) -> Vec<String> {
+        if self.workspaces.len() < 2 {
+            return Vec::new();
+        }
+        self.workspaces
+            .iter()
+            .enumerate()
+            .map(|(i, label)| {
+                let label: String = if label.chars().count() > WORKSPACE_LABEL_WIDTH {
+                    let mut cut: String = label.chars().take(WORKSPACE_LABEL_WIDTH - 1).collect();
+                    cut.push('…');
+                    cut
+                } else {
+                    label.clone()
+                };
+                format!(" {}:{} ", i + 1, label)
+            })
+            .collect()
+    }
+
     /// Select action by index
     pub fn select_index(&mut self, index: usize) {
         if index < self.actions.len() {
@@ -260,6 +441,20 @@ impl ActionBar {
             spans.push(Span::styled(format!("[{}] ", name), badge_style));
         }
 
+        // Workspace tabs, the active one highlighted
+        let tabs = self.workspace_tabs();
+        if !tabs.is_empty() {
+            for (i, tab) in tabs.into_iter().enumerate() {
+                let style = if i == self.active_workspace {
+                    self.styles.action_selected
+                } else {
+                    self.styles.muted_text
+                };
+                spans.push(Span::styled(tab, style));
+            }
+            spans.push(Span::styled(" ┃ ", self.styles.muted_text));
+        }
+
         let last_visible = self.actions.iter().rposition(|a| a.enabled).unwrap_or(0);
 
         for (i, action) in self.actions.iter().enumerate() {
@@ -307,7 +502,114 @@ impl ActionBar {
         let paragraph = Paragraph::new(line);
         frame.render_widget(paragraph, area);
 
-        // Render config path right-aligned
+        // Render gauges, readiness badges and config path right-aligned
+        let right: Vec<Span> = self
+            .right_spans()
+            .into_iter()
+            .map(|(span, _)| span)
+            .collect();
+        if !right.is_empty() {
+            let right_paragraph = Paragraph::new(Line::from(right)).alignment(Alignment::Right);
+            frame.render_widget(right_paragraph, area);
+        }
+    }
+
+    /// Right-aligned part of the bar; clickable spans carry what they open
+    fn right_spans(&self) -> Vec<(Span<'static>, Option<BarLink>)> {
+        let mut right = Vec::new();
+        let state_style = match self.cluster_state {
+            ClusterState::Running => Some(self.styles.success_text),
+            ClusterState::Degraded | ClusterState::RuntimeDown => Some(self.styles.error_text),
+            ClusterState::Starting | ClusterState::Stopping | ClusterState::Paused => {
+                Some(self.styles.warning_text)
+            }
+            ClusterState::Stopped | ClusterState::NotCreated => Some(self.styles.muted_text),
+            ClusterState::Unknown => None,
+        };
+        if let Some(style) = state_style {
+            let text = match &self.degraded_reason {
+                Some(reason) => format!("● {}: {}  ", self.cluster_state.label(), reason),
+                None => format!("● {}  ", self.cluster_state.label()),
+            };
+            let link =
+                (self.cluster_state == ClusterState::Degraded).then_some(BarLink::ClusterHealth);
+            right.push((Span::styled(text, style), link));
+        }
+        if let Some(summary) = &self.traefik_drift {
+            right.push((
+                Span::styled(
+                    format!("⚠ Traefik drift: {}  ", summary),
+                    self.styles.warning_text,
+                ),
+                Some(BarLink::TraefikDrift),
+            ));
+        }
+        if let Some(seed) = &self.seed {
+            right.push((
+                Span::styled(format!("⛁ seed: {}  ", seed), self.styles.muted_text),
+                None,
+            ));
+        }
+        if let Some(label) = &self.background_run {
+            let label: String = if label.chars().count() > BACKGROUND_RUN_WIDTH {
+                let mut cut: String = label.chars().take(BACKGROUND_RUN_WIDTH - 1).collect();
+                cut.push('…');
+                cut
+            } else {
+                label.clone()
+            };
+            right.push((
+                Span::styled(format!("⟳ {}  ", label), self.styles.warning_text),
+                None,
+            ));
+        }
+        if let Some((url, since)) = &self.awaited_url {
+            let frame = (since.elapsed().as_millis() / SPINNER_INTERVAL.as_millis()) as usize;
+            right.push((
+                Span::styled(
+                    format!("{} waiting for {}  ", SPINNER[frame % SPINNER.len()], url),
+                    self.styles.info_text,
+                ),
+                None,
+            ));
+        }
+        if let Some(usage) = &self.usage {
+            let memory = format!(
+                "{:.1}/{:.0}G",
+                usage.memory_used_mb / 1024.0,
+                usage.capacity.memory_mb / 1024.0
+            );
+            for (label, ratio, value, sort) in [
+                (
+                    "CPU",
+                    usage.cpu_ratio(),
+                    format!("{:.0}%", usage.cpu_ratio()),
+                    PodSort::Cpu,
+                ),
+                ("MEM", usage.memory_ratio(), memory, PodSort::Memory),
+            ] {
+                let style = self.gauge_style(ratio);
+                let filled =
+                    ((ratio.clamp(0.0, 100.0) / 100.0) * GAUGE_WIDTH as f64).round() as usize;
+                let bar = format!("{}{}", "█".repeat(filled), "░".repeat(GAUGE_WIDTH - filled));
+                right.push((
+                    Span::styled(format!("{} {} {}", label, bar, value), style),
+                    Some(BarLink::Sort(sort)),
+                ));
+                right.push((Span::raw("  "), None));
+            }
+        }
+        for r in &self.readiness {
+            let (icon, style) = match r.state {
+                ReadinessState::Waiting => ("◌", self.styles.warning_text),
+                ReadinessState::Ready => ("✓", self.styles.success_text),
+                ReadinessState::TimedOut => ("✗", self.styles.error_text),
+            };
+            right.push((
+                Span::styled(format!("{} {}  ", icon, r.gate.name()), style),
+                None,
+            ));
+        }
         if let Some(path) = &self.config_path {
             let path_display = path.to_string_lossy();
             let home = dirs::home_dir();
@@ -318,10 +620,34 @@ impl ActionBar {
                     .unwrap_or_else(|| path_display.to_string()),
                 None => path_display.to_string(),
             };
-            let config_line = Line::from(Span::styled(short_path, self.styles.muted_text));
-            let config_paragraph = Paragraph::new(config_line).alignment(Alignment::Right);
-            frame.render_widget(config_paragraph, area);
+            right.push((Span::styled(short_path, self.styles.muted_text), None));
         }
+        right
+    }
+
+    fn gauge_style(&self, ratio: f64) -> Style {
+        if ratio >= GAUGE_CRITICAL_PERCENT {
+            self.styles.error_text
+        } else if ratio >= GAUGE_WARNING_PERCENT {
+            self.styles.warning_text
+        } else {
+            self.styles.success_text
+        }
+    }
+
+    /// Link at x within a bar of the given width (for mouse click handling)
+    pub fn link_at_x(&self, x: usize, width: usize) -> Option<BarLink> {
+        let spans = self.right_spans();
+        let total: usize = spans.iter().map(|(span, _)| span.width()).sum();
+        let mut pos = width.saturating_sub(total);
+        for (span, link) in spans {
+            let end = pos + span.width();
+            if x >= pos && x < end {
+                return link;
+            }
+            pos = end;
+        }
+        None
     }
 
     /// Render actions as a vertical list (for stopped screen)
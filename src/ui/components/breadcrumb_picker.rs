@@ -0,0 +1,151 @@
+//! Breadcrumb jump picker
+//!
+//! Lists every command group, then the siblings at each level of the
+//! selected item's breadcrumb, so any ancestor or neighbour is one pick away
+//! without scrolling the tree or typing a search.
+
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Clear, Paragraph},
+    Frame,
+};
+
+use super::JumpTarget;
+use crate::ui::styles::Styles;
+use crate::ui::theme::Theme;
+
+/// Breadcrumb jump overlay
+pub struct BreadcrumbPicker {
+    styles: Styles,
+    targets: Vec<JumpTarget>,
+    selected: usize,
+}
+
+impl BreadcrumbPicker {
+    pub fn new() -> Self {
+        Self::with_theme(Theme::default())
+    }
+
+    pub fn with_theme(theme: Theme) -> Self {
+        Self {
+            styles: Styles::from_theme(theme),
+            targets: Vec::new(),
+            selected: 0,
+        }
+    }
+
+    /// Offer `targets`, starting on the deepest breadcrumb entry
+    pub fn open(&mut self, targets: Vec<JumpTarget>) {
+        self.selected = targets.iter().rposition(|t| t.on_path).unwrap_or(0);
+        self.targets = targets;
+    }
+
+    pub fn select_up(&mut self) {
+        self.selected = self.selected.saturating_sub(1);
+    }
+
+    pub fn select_down(&mut self) {
+        if self.selected + 1 < self.targets.len() {
+            self.selected += 1;
+        }
+    }
+
+    /// Path of the highlighted entry
+    pub fn selected_path(&self) -> Option<&[usize]> {
+        self.targets
+            .get(self.selected)
+            .map(|t| t.item_path.as_slice())
+    }
+
+    pub fn render(&self, frame: &mut Frame, area: Rect) {
+        let popup_area = centered_rect(50, 60, area);
+        frame.render_widget(Clear, popup_area);
+
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Double)
+            .border_style(self.styles.border_focused)
+            .title(" Jump To ")
+            .title_bottom(
+                Line::from(vec![
+                    Span::styled(" Esc close ", self.styles.muted_text),
+                    Span::styled(" j/k select ", self.styles.muted_text),
+                    Span::styled(" Enter jump ", self.styles.muted_text),
+                ])
+                .right_aligned(),
+            );
+        let inner = block.inner(popup_area);
+        frame.render_widget(block, popup_area);
+
+        // A heading before each level: the groups, then what's inside the
+        // breadcrumb entry one level up
+        let mut lines = Vec::new();
+        let mut selected_line = 0;
+        let mut level = None;
+        let mut parent = "";
+        for (i, target) in self.targets.iter().enumerate() {
+            if level != Some(target.level) {
+                let heading = if target.level == 0 {
+                    "Groups".to_string()
+                } else {
+                    format!("In {}", parent)
+                };
+                if level.is_some() {
+                    lines.push(Line::default());
+                }
+                lines.push(Line::from(Span::styled(heading, self.styles.group_header)));
+                level = Some(target.level);
+            }
+            if target.on_path {
+                parent = &target.name;
+            }
+            let marker = if target.on_path { "● " } else { "  " };
+            let style = if i == self.selected {
+                self.styles.selected
+            } else if target.on_path {
+                self.styles.title
+            } else {
+                self.styles.normal_text
+            };
+            if i == self.selected {
+                selected_line = lines.len();
+            }
+            lines.push(Line::from(Span::styled(
+                format!("  {}{}", marker, target.name),
+                style,
+            )));
+        }
+
+        let height = inner.height as usize;
+        let scroll = (selected_line + 1).saturating_sub(height);
+        frame.render_widget(Paragraph::new(lines).scroll((scroll as u16, 0)), inner);
+    }
+}
+
+impl Default for BreadcrumbPicker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Helper to create a centered rect
+fn centered_rect(percent_x: u16, percent_y: u16, area: Rect) -> Rect {
+    let popup_layout = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Percentage((100 - percent_y) / 2),
+            Constraint::Percentage(percent_y),
+            Constraint::Percentage((100 - percent_y) / 2),
+        ])
+        .split(area);
+
+    Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Percentage((100 - percent_x) / 2),
+            Constraint::Percentage(percent_x),
+            Constraint::Percentage((100 - percent_x) / 2),
+        ])
+        .split(popup_layout[1])[1]
+}
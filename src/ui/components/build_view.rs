@@ -0,0 +1,194 @@
+//! Build panel
+//!
+//! One row per Dockerfile stage of the running image build: how many of its
+//! steps are done, whether they came from the cache, the step running now
+//! and how long the stage took. The last lines the build printed are shown
+//! below; the job's output popup has all of them.
+
+use std::time::Duration;
+
+use chrono::Utc;
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::Modifier,
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Cell, Clear, Paragraph, Row, Table},
+    Frame,
+};
+
+use crate::cluster::image_build::{BuildProgress, BuildStage};
+use crate::ui::styles::Styles;
+use crate::ui::theme::Theme;
+
+/// Build panel overlay
+pub struct BuildView {
+    styles: Styles,
+    /// "Build api (api:dev)"
+    title: String,
+    progress: BuildProgress,
+}
+
+impl BuildView {
+    pub fn new() -> Self {
+        Self::with_theme(Theme::default())
+    }
+
+    pub fn with_theme(theme: Theme) -> Self {
+        Self {
+            styles: Styles::from_theme(theme),
+            title: String::new(),
+            progress: BuildProgress::default(),
+        }
+    }
+
+    /// Start showing a new build
+    pub fn reset(&mut self, title: String) {
+        self.title = title;
+        self.progress = BuildProgress::default();
+    }
+
+    pub fn update(&mut self, progress: BuildProgress) {
+        self.progress = progress;
+    }
+
+    /// `state` is the build job's state ("running", "done", "failed", ...)
+    pub fn render(&self, frame: &mut Frame, area: Rect, state: &str) {
+        let popup_area = centered_rect(85, 70, area);
+        frame.render_widget(Clear, popup_area);
+
+        let state_style = match state {
+            "done" => self.styles.success_text,
+            "failed" | "cancelled" => self.styles.error_text,
+            _ => self.styles.warning_text,
+        };
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Double)
+            .border_style(self.styles.border_focused)
+            .title(Line::from(vec![
+                Span::raw(format!(" {} ", self.title)),
+                Span::styled(format!("[{}] ", state), state_style),
+            ]))
+            .title_bottom(
+                Line::from(vec![
+                    Span::styled(" Esc close ", self.styles.muted_text),
+                    Span::styled(" o full output ", self.styles.muted_text),
+                    Span::styled(" Ctrl+C cancel ", self.styles.muted_text),
+                ])
+                .right_aligned(),
+            );
+        let inner = block.inner(popup_area);
+        frame.render_widget(block, popup_area);
+
+        let rows = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(3), Constraint::Length(10)])
+            .split(inner);
+
+        if self.progress.stages.is_empty() {
+            let msg = Paragraph::new(Span::styled(
+                "  Waiting for BuildKit...",
+                self.styles.muted_text,
+            ));
+            frame.render_widget(msg, rows[0]);
+        } else {
+            let table_rows = self.progress.stages.iter().map(|s| self.stage_row(s));
+            let table = Table::new(
+                table_rows,
+                [
+                    Constraint::Length(16),
+                    Constraint::Length(8),
+                    Constraint::Length(14),
+                    Constraint::Fill(1),
+                ],
+            )
+            .header(
+                Row::new(["STAGE", "STEPS", "STATE", "STEP"])
+                    .style(self.styles.normal_text.add_modifier(Modifier::BOLD)),
+            );
+            frame.render_widget(table, rows[0]);
+        }
+
+        let mut logs = vec![Line::from(Span::styled(
+            "Recent output",
+            self.styles.normal_text.add_modifier(Modifier::BOLD),
+        ))];
+        logs.extend(
+            self.progress
+                .recent_logs
+                .iter()
+                .map(|l| Line::from(Span::styled(format!("  {}", l), self.styles.muted_text))),
+        );
+        frame.render_widget(Paragraph::new(logs), rows[1]);
+    }
+
+    fn stage_row(&self, stage: &BuildStage) -> Row<'static> {
+        let (state, style, step) = if let Some(error) = &stage.error {
+            ("failed".to_string(), self.styles.error_text, error.clone())
+        } else if let Some(running) = &stage.running {
+            let elapsed = running
+                .started
+                .and_then(|s| (Utc::now() - s).to_std().ok())
+                .unwrap_or_default();
+            (
+                format!("running {}", format_duration(elapsed)),
+                self.styles.warning_text,
+                running.name.clone(),
+            )
+        } else if stage.done < stage.total {
+            ("waiting".to_string(), self.styles.muted_text, String::new())
+        } else if stage.cached {
+            ("cached".to_string(), self.styles.info_text, String::new())
+        } else {
+            let took = stage.elapsed.map(format_duration).unwrap_or_default();
+            (
+                format!("done {}", took).trim_end().to_string(),
+                self.styles.success_text,
+                String::new(),
+            )
+        };
+        Row::new(vec![
+            Cell::from(stage.name.clone()),
+            Cell::from(format!("{}/{}", stage.done, stage.total)),
+            Cell::from(Span::styled(state, style)),
+            Cell::from(Span::styled(step, self.styles.muted_text)),
+        ])
+        .style(self.styles.normal_text)
+    }
+}
+
+impl Default for BuildView {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// "850ms", "2.4s"
+fn format_duration(duration: Duration) -> String {
+    if duration.as_millis() < 1000 {
+        format!("{}ms", duration.as_millis())
+    } else {
+        format!("{:.1}s", duration.as_secs_f64())
+    }
+}
+
+/// Helper to create a centered rect
+fn centered_rect(percent_x: u16, percent_y: u16, area: Rect) -> Rect {
+    let popup_layout = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Percentage((100 - percent_y) / 2),
+            Constraint::Percentage(percent_y),
+            Constraint::Percentage((100 - percent_y) / 2),
+        ])
+        .split(area);
+
+    Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Percentage((100 - percent_x) / 2),
+            Constraint::Percentage(percent_x),
+            Constraint::Percentage((100 - percent_x) / 2),
+        ])
+        .split(popup_layout[1])[1]
+}
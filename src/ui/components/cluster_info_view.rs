@@ -0,0 +1,335 @@
+//! Cluster info dashboard
+//!
+//! Shows the cluster as a set of cards (status, ports, nodes, namespaces and
+//! component health) instead of a text dump. The app refreshes the snapshot
+//! while the view is open.
+
+use std::time::Instant;
+
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Cell, Clear, Paragraph, Row, Table},
+    Frame,
+};
+
+use crate::cluster::{ClusterInfo, ClusterStatus};
+use crate::ui::styles::Styles;
+use crate::ui::theme::Theme;
+
+/// Cluster info overlay
+pub struct ClusterInfoView {
+    styles: Styles,
+    info: Option<ClusterInfo>,
+    updated_at: Option<Instant>,
+}
+
+impl ClusterInfoView {
+    pub fn new() -> Self {
+        Self::with_theme(Theme::default())
+    }
+
+    pub fn with_theme(theme: Theme) -> Self {
+        Self {
+            styles: Styles::from_theme(theme),
+            info: None,
+            updated_at: None,
+        }
+    }
+
+    /// Forget the previous snapshot before opening the view
+    pub fn reset(&mut self) {
+        self.info = None;
+        self.updated_at = None;
+    }
+
+    pub fn update(&mut self, info: ClusterInfo) {
+        self.info = Some(info);
+        self.updated_at = Some(Instant::now());
+    }
+
+    pub fn render(&self, frame: &mut Frame, area: Rect) {
+        let popup_area = centered_rect(85, 85, area);
+        frame.render_widget(Clear, popup_area);
+
+        let updated = match self.updated_at {
+            Some(at) => format!(" updated {}s ago ", at.elapsed().as_secs()),
+            None => " loading... ".to_string(),
+        };
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Double)
+            .border_style(self.styles.border_focused)
+            .title(" Cluster Info ")
+            .title_bottom(
+                Line::from(vec![
+                    Span::styled(" Esc close ", self.styles.muted_text),
+                    Span::styled(" r refresh ", self.styles.muted_text),
+                    Span::styled(updated, self.styles.muted_text),
+                ])
+                .right_aligned(),
+            );
+        let inner = block.inner(popup_area);
+        frame.render_widget(block, popup_area);
+
+        let Some(info) = &self.info else {
+            let msg = Paragraph::new(Span::styled(
+                "  Loading cluster info...",
+                self.styles.muted_text,
+            ));
+            frame.render_widget(msg, inner);
+            return;
+        };
+
+        let running = info.status == ClusterStatus::Running;
+        let node_height = if running {
+            (info.nodes.len() as u16 + 3).min(8)
+        } else {
+            0
+        };
+        let rows = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(9),
+                Constraint::Length(node_height),
+                Constraint::Min(0),
+            ])
+            .split(inner);
+        let top = Layout::horizontal([Constraint::Percentage(50), Constraint::Percentage(50)])
+            .split(rows[0]);
+
+        self.render_status(frame, top[0], info);
+        self.render_ports(frame, top[1], info);
+
+        if !running {
+            let msg = Paragraph::new(Span::styled(
+                "  Nodes, namespaces and components are shown once the cluster is running.",
+                self.styles.muted_text,
+            ));
+            frame.render_widget(msg, rows[2]);
+            return;
+        }
+
+        self.render_nodes(frame, rows[1], info);
+        let bottom = Layout::horizontal([Constraint::Percentage(50), Constraint::Percentage(50)])
+            .split(rows[2]);
+        self.render_namespaces(frame, bottom[0], info);
+        self.render_components(frame, bottom[1], info);
+    }
+
+    fn card(&self, title: &str) -> Block<'static> {
+        Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(self.styles.border_unfocused)
+            .title(Span::styled(format!(" {} ", title), self.styles.title))
+    }
+
+    fn header_style(&self) -> Style {
+        self.styles.normal_text.add_modifier(Modifier::BOLD)
+    }
+
+    fn render_status(&self, frame: &mut Frame, area: Rect, info: &ClusterInfo) {
+        let status_style = match info.status {
+            ClusterStatus::Running => self.styles.success_text,
+            ClusterStatus::Starting | ClusterStatus::Paused => self.styles.warning_text,
+            _ => self.styles.error_text,
+        };
+        let (ready_nodes, nodes) = info.ready_nodes();
+        let (running_pods, pods) = info.running_pods();
+
+        let field = |label: &str, value: String, style: Style| {
+            Line::from(vec![
+                Span::styled(format!("{:<11}", label), self.styles.muted_text),
+                Span::styled(value, style),
+            ])
+        };
+        let mut lines = vec![
+            field("Status", format!("{:?}", info.status), status_style),
+            field(
+                "Version",
+                info.version.clone().unwrap_or_else(|| "-".to_string()),
+                self.styles.normal_text,
+            ),
+            field("Container", info.container.clone(), self.styles.normal_text),
+            field("Image", info.image.clone(), self.styles.normal_text),
+            field("Domain", info.domain.clone(), self.styles.normal_text),
+        ];
+        if info.status == ClusterStatus::Running {
+            let count_style = |ready: usize, total: usize| {
+                if ready == total {
+                    self.styles.success_text
+                } else {
+                    self.styles.warning_text
+                }
+            };
+            lines.push(field(
+                "Nodes",
+                format!("{}/{} ready", ready_nodes, nodes),
+                count_style(ready_nodes, nodes),
+            ));
+            lines.push(field(
+                "Pods",
+                format!("{}/{} running", running_pods, pods),
+                count_style(running_pods, pods),
+            ));
+        }
+
+        frame.render_widget(Paragraph::new(lines).block(self.card("Status")), area);
+    }
+
+    fn render_ports(&self, frame: &mut Frame, area: Rect, info: &ClusterInfo) {
+        let rows = info.ports.iter().map(|p| {
+            Row::new(vec![
+                Cell::from(p.host.to_string()),
+                Cell::from(p.container.to_string()),
+                Cell::from(p.purpose),
+            ])
+            .style(self.styles.normal_text)
+        });
+        let table = Table::new(
+            rows,
+            [
+                Constraint::Length(6),
+                Constraint::Length(10),
+                Constraint::Fill(1),
+            ],
+        )
+        .header(Row::new(["HOST", "CONTAINER", "PURPOSE"]).style(self.header_style()))
+        .block(self.card("Ports"));
+        frame.render_widget(table, area);
+    }
+
+    fn render_nodes(&self, frame: &mut Frame, area: Rect, info: &ClusterInfo) {
+        let rows = info.nodes.iter().map(|n| {
+            let status_style = if n.status == "Ready" {
+                self.styles.success_text
+            } else {
+                self.styles.error_text
+            };
+            Row::new(vec![
+                Cell::from(n.name.as_str()),
+                Cell::from(Span::styled(n.status.as_str(), status_style)),
+                Cell::from(if n.roles.is_empty() {
+                    "<none>"
+                } else {
+                    n.roles.as_str()
+                }),
+                Cell::from(n.internal_ip.as_deref().unwrap_or("<none>")),
+                Cell::from(n.version.as_str()),
+            ])
+            .style(self.styles.normal_text)
+        });
+        let table = Table::new(
+            rows,
+            [
+                Constraint::Fill(2),
+                Constraint::Length(10),
+                Constraint::Fill(2),
+                Constraint::Length(16),
+                Constraint::Fill(1),
+            ],
+        )
+        .header(
+            Row::new(["NAME", "STATUS", "ROLES", "INTERNAL-IP", "VERSION"])
+                .style(self.header_style()),
+        )
+        .block(self.card("Nodes"));
+        frame.render_widget(table, area);
+    }
+
+    fn render_namespaces(&self, frame: &mut Frame, area: Rect, info: &ClusterInfo) {
+        // The mesh column only shows once a namespace is in a mesh
+        let mesh = info.has_mesh();
+        let rows = info.namespaces.iter().map(|ns| {
+            let style = if ns.running < ns.pods {
+                self.styles.warning_text
+            } else {
+                self.styles.normal_text
+            };
+            let mut cells = vec![
+                Cell::from(ns.name.as_str()),
+                Cell::from(ns.pods.to_string()),
+                Cell::from(ns.running.to_string()),
+            ];
+            if mesh {
+                let mesh_style = if ns.injection && ns.meshed < ns.pods {
+                    self.styles.warning_text
+                } else {
+                    self.styles.success_text
+                };
+                cells.push(Cell::from(Span::styled(ns.mesh_label(), mesh_style)));
+            }
+            Row::new(cells).style(style)
+        });
+        let mut widths = vec![
+            Constraint::Fill(1),
+            Constraint::Length(5),
+            Constraint::Length(8),
+        ];
+        let mut header = vec!["NAMESPACE", "PODS", "RUNNING"];
+        if mesh {
+            widths.push(Constraint::Length(9));
+            header.push("MESH");
+        }
+        let table = Table::new(rows, widths)
+            .header(Row::new(header).style(self.header_style()))
+            .block(self.card("Namespaces"));
+        frame.render_widget(table, area);
+    }
+
+    fn render_components(&self, frame: &mut Frame, area: Rect, info: &ClusterInfo) {
+        let rows = info.components.iter().map(|c| {
+            let style = if c.is_ready() {
+                self.styles.success_text
+            } else {
+                self.styles.error_text
+            };
+            Row::new(vec![
+                Cell::from(Span::styled("●", style)),
+                Cell::from(c.name.as_str()),
+                Cell::from(Span::styled(format!("{}/{}", c.ready, c.desired), style)),
+            ])
+            .style(self.styles.normal_text)
+        });
+        let table = Table::new(
+            rows,
+            [
+                Constraint::Length(1),
+                Constraint::Fill(1),
+                Constraint::Length(6),
+            ],
+        )
+        .header(Row::new(["", "COMPONENT", "READY"]).style(self.header_style()))
+        .block(self.card("Components"));
+        frame.render_widget(table, area);
+    }
+}
+
+impl Default for ClusterInfoView {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Helper to create a centered rect
+fn centered_rect(percent_x: u16, percent_y: u16, area: Rect) -> Rect {
+    let popup_layout = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Percentage((100 - percent_y) / 2),
+            Constraint::Percentage(percent_y),
+            Constraint::Percentage((100 - percent_y) / 2),
+        ])
+        .split(area);
+
+    Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Percentage((100 - percent_x) / 2),
+            Constraint::Percentage(percent_x),
+            Constraint::Percentage((100 - percent_x) / 2),
+        ])
+        .split(popup_layout[1])[1]
+}
@@ -8,7 +8,9 @@ use ratatui::{
     Frame,
 };
 
+use crate::cluster::{ChaosTool, ClusterComponent};
 use crate::commands::PaletteCommandId;
+use crate::config::CommandColor;
 use crate::ui::styles::Styles;
 use crate::ui::theme::Theme;
 
@@ -22,12 +24,22 @@ pub struct PaletteCommand {
     pub shortcut: Option<String>,
     pub category: CommandCategory,
     pub description: Option<String>,
+    /// Shown before the name
+    pub icon: Option<String>,
+    /// Color hint for the name
+    pub color: Option<CommandColor>,
 }
 
 /// Command categories
 #[derive(Debug, Clone, PartialEq)]
 pub enum CommandCategory {
     Cluster,
+    /// Setup steps repeated on a running cluster
+    Maintenance,
+    /// Bundles from `seeds:`
+    Seeds,
+    /// Tools that break the cluster on purpose
+    Chaos,
     Navigation,
     Application,
     Custom(String),
@@ -37,6 +49,9 @@ impl CommandCategory {
     fn as_str(&self) -> &str {
         match self {
             CommandCategory::Cluster => "Cluster",
+            CommandCategory::Maintenance => "Cluster maintenance",
+            CommandCategory::Seeds => "Seed data",
+            CommandCategory::Chaos => "Chaos",
             CommandCategory::Navigation => "Navigation",
             CommandCategory::Application => "App",
             CommandCategory::Custom(name) => name.as_str(),
@@ -47,9 +62,12 @@ impl CommandCategory {
     fn badge_style(&self, styles: &Styles) -> ratatui::style::Style {
         match self {
             CommandCategory::Cluster => styles.warning_text, // amber/orange
+            CommandCategory::Maintenance => styles.warning_text,
+            CommandCategory::Seeds => styles.primary,
+            CommandCategory::Chaos => styles.error_text,
             CommandCategory::Navigation => styles.success_text, // green
-            CommandCategory::Application => styles.primary,  // primary color
-            CommandCategory::Custom(_) => styles.muted_text, // muted
+            CommandCategory::Application => styles.primary,     // primary color
+            CommandCategory::Custom(_) => styles.muted_text,    // muted
         }
     }
 }
@@ -71,13 +89,15 @@ impl CommandPalette {
     }
 
     pub fn with_theme(theme: Theme) -> Self {
-        let commands = vec![
+        let mut commands = vec![
             // Cluster actions
             PaletteCommand {
                 id: PaletteCommandId::ClusterStart,
                 name: "Start Cluster".to_string(),
                 shortcut: None,
                 category: CommandCategory::Cluster,
+                icon: None,
+                color: None,
                 description: Some("Start the k3s cluster container".to_string()),
             },
             PaletteCommand {
@@ -85,6 +105,8 @@ impl CommandPalette {
                 name: "Stop Cluster".to_string(),
                 shortcut: None,
                 category: CommandCategory::Cluster,
+                icon: None,
+                color: None,
                 description: Some("Stop the k3s cluster container".to_string()),
             },
             PaletteCommand {
@@ -92,6 +114,8 @@ impl CommandPalette {
                 name: "Restart Cluster".to_string(),
                 shortcut: None,
                 category: CommandCategory::Cluster,
+                icon: None,
+                color: None,
                 description: Some("Stop and start the cluster".to_string()),
             },
             PaletteCommand {
@@ -99,6 +123,8 @@ impl CommandPalette {
                 name: "Destroy Cluster".to_string(),
                 shortcut: None,
                 category: CommandCategory::Cluster,
+                icon: None,
+                color: Some(CommandColor::Danger),
                 description: Some("Permanently delete the cluster and all data".to_string()),
             },
             PaletteCommand {
@@ -106,6 +132,8 @@ impl CommandPalette {
                 name: "Cluster Info".to_string(),
                 shortcut: None,
                 category: CommandCategory::Cluster,
+                icon: None,
+                color: None,
                 description: Some("Show cluster status and configuration".to_string()),
             },
             PaletteCommand {
@@ -113,6 +141,8 @@ impl CommandPalette {
                 name: "Delete Snapshots".to_string(),
                 shortcut: None,
                 category: CommandCategory::Cluster,
+                icon: None,
+                color: None,
                 description: Some(
                     "Remove all snapshot images (next start will be slower)".to_string(),
                 ),
@@ -122,6 +152,8 @@ impl CommandPalette {
                 name: "Run Diagnostics".to_string(),
                 shortcut: None,
                 category: CommandCategory::Cluster,
+                icon: None,
+                color: None,
                 description: Some("Run cluster health checks and diagnostics".to_string()),
             },
             PaletteCommand {
@@ -129,16 +161,299 @@ impl CommandPalette {
                 name: "Preflight Check".to_string(),
                 shortcut: None,
                 category: CommandCategory::Cluster,
+                icon: None,
+                color: None,
                 description: Some(
                     "Check if cluster can start (ports, Docker, prerequisites)".to_string(),
                 ),
             },
+            PaletteCommand {
+                id: PaletteCommandId::ClusterTakeControl,
+                name: "Take Over Cluster Control".to_string(),
+                shortcut: None,
+                category: CommandCategory::Cluster,
+                icon: None,
+                color: None,
+                description: Some(
+                    "Ask the other k3dev managing this cluster to hand over control".to_string(),
+                ),
+            },
+            PaletteCommand {
+                id: PaletteCommandId::ClusterSharePort,
+                name: "Share Ingress Port".to_string(),
+                shortcut: None,
+                category: CommandCategory::Cluster,
+                icon: None,
+                color: None,
+                description: Some(
+                    "Publish the HTTP or HTTPS port on the share host over SSH".to_string(),
+                ),
+            },
+            PaletteCommand {
+                id: PaletteCommandId::ClusterStopSharing,
+                name: "Stop Sharing".to_string(),
+                shortcut: None,
+                category: CommandCategory::Cluster,
+                icon: None,
+                color: None,
+                description: Some("Close the SSH forwards of shared ports".to_string()),
+            },
+            PaletteCommand {
+                id: PaletteCommandId::ClusterOpenTunnel,
+                name: "Open Public Tunnel".to_string(),
+                shortcut: None,
+                category: CommandCategory::Cluster,
+                icon: None,
+                color: None,
+                description: Some(
+                    "Expose an ingress host on a public URL through cloudflared or ngrok"
+                        .to_string(),
+                ),
+            },
+            PaletteCommand {
+                id: PaletteCommandId::ClusterCloseTunnels,
+                name: "Close Public Tunnels".to_string(),
+                shortcut: None,
+                category: CommandCategory::Cluster,
+                icon: None,
+                color: None,
+                description: Some("Stop the tunnel clients of ingress hosts".to_string()),
+            },
+            PaletteCommand {
+                id: PaletteCommandId::ClusterLogs,
+                name: "K3s Server Logs".to_string(),
+                shortcut: None,
+                category: CommandCategory::Cluster,
+                icon: None,
+                color: None,
+                description: Some("Follow the k3s container's own logs".to_string()),
+            },
+            PaletteCommand {
+                id: PaletteCommandId::ClusterEditResource,
+                name: "Edit Resource".to_string(),
+                shortcut: None,
+                category: CommandCategory::Cluster,
+                icon: None,
+                color: None,
+                description: Some(
+                    "Edit a Deployment, ConfigMap or Ingress in $EDITOR and apply it".to_string(),
+                ),
+            },
+            PaletteCommand {
+                id: PaletteCommandId::ClusterServices,
+                name: "Services".to_string(),
+                shortcut: None,
+                category: CommandCategory::Cluster,
+                icon: None,
+                color: None,
+                description: Some(
+                    "List Services with endpoints and whether their ports answer".to_string(),
+                ),
+            },
+            PaletteCommand {
+                id: PaletteCommandId::ClusterNetworkPolicies,
+                name: "Network Policies".to_string(),
+                shortcut: None,
+                category: CommandCategory::Cluster,
+                icon: None,
+                color: None,
+                description: Some(
+                    "Show which workloads NetworkPolicies let talk to each other".to_string(),
+                ),
+            },
+            PaletteCommand {
+                id: PaletteCommandId::ClusterDependencyMap,
+                name: "Dependency Map".to_string(),
+                shortcut: None,
+                category: CommandCategory::Cluster,
+                icon: None,
+                color: None,
+                description: Some(
+                    "Graph which workloads call which, from Services and *_HOST env vars"
+                        .to_string(),
+                ),
+            },
+            PaletteCommand {
+                id: PaletteCommandId::ClusterRbac,
+                name: "RBAC Inspector".to_string(),
+                shortcut: None,
+                category: CommandCategory::Cluster,
+                icon: None,
+                color: None,
+                description: Some(
+                    "Browse ServiceAccounts, Roles and bindings and check what they can do"
+                        .to_string(),
+                ),
+            },
+            PaletteCommand {
+                id: PaletteCommandId::ClusterCreateNamespace,
+                name: "Create Namespace".to_string(),
+                shortcut: None,
+                category: CommandCategory::Cluster,
+                icon: None,
+                color: None,
+                description: Some("Create a new namespace".to_string()),
+            },
+            PaletteCommand {
+                id: PaletteCommandId::ClusterDeleteNamespace,
+                name: "Delete Namespace".to_string(),
+                shortcut: None,
+                category: CommandCategory::Cluster,
+                icon: None,
+                color: None,
+                description: Some(
+                    "Delete a namespace and everything in it, forcing stuck finalizers".to_string(),
+                ),
+            },
+            PaletteCommand {
+                id: PaletteCommandId::ClusterDiffManifests,
+                name: "Diff Manifests".to_string(),
+                shortcut: None,
+                category: CommandCategory::Cluster,
+                icon: None,
+                color: None,
+                description: Some(
+                    "Show what applying the configured manifests would change".to_string(),
+                ),
+            },
+            PaletteCommand {
+                id: PaletteCommandId::ClusterInstallMesh,
+                name: "Install Service Mesh".to_string(),
+                shortcut: None,
+                category: CommandCategory::Cluster,
+                icon: None,
+                color: None,
+                description: Some(
+                    "Install Linkerd or Istio (infrastructure.service_mesh) with its CLI"
+                        .to_string(),
+                ),
+            },
+            PaletteCommand {
+                id: PaletteCommandId::ClusterUninstallMesh,
+                name: "Uninstall Service Mesh".to_string(),
+                shortcut: None,
+                category: CommandCategory::Cluster,
+                icon: None,
+                color: None,
+                description: Some("Remove the installed service mesh".to_string()),
+            },
+            PaletteCommand {
+                id: PaletteCommandId::ClusterInstallPolicyEngine,
+                name: "Install Policy Engine".to_string(),
+                shortcut: None,
+                category: CommandCategory::Cluster,
+                icon: None,
+                color: None,
+                description: Some(
+                    "Install Kyverno or Gatekeeper (infrastructure.policy_engine)".to_string(),
+                ),
+            },
+            PaletteCommand {
+                id: PaletteCommandId::ClusterUninstallPolicyEngine,
+                name: "Uninstall Policy Engine".to_string(),
+                shortcut: None,
+                category: CommandCategory::Cluster,
+                icon: None,
+                color: None,
+                description: Some("Remove the installed policy engine".to_string()),
+            },
+            PaletteCommand {
+                id: PaletteCommandId::ClusterPolicyViolations,
+                name: "Policy Violations".to_string(),
+                shortcut: None,
+                category: CommandCategory::Cluster,
+                icon: None,
+                color: None,
+                description: Some(
+                    "Workloads failing the policies of Kyverno or Gatekeeper".to_string(),
+                ),
+            },
+            PaletteCommand {
+                id: PaletteCommandId::ClusterQuotas,
+                name: "Resource Quotas".to_string(),
+                shortcut: None,
+                category: CommandCategory::Cluster,
+                icon: None,
+                color: None,
+                description: Some(
+                    "Quota consumption and LimitRange defaults per namespace".to_string(),
+                ),
+            },
+            PaletteCommand {
+                id: PaletteCommandId::ClusterRuntime,
+                name: "Container Runtime".to_string(),
+                shortcut: None,
+                category: CommandCategory::Cluster,
+                icon: None,
+                color: None,
+                description: Some(
+                    "Pods and containers as crictl sees them, checked against Docker".to_string(),
+                ),
+            },
+            PaletteCommand {
+                id: PaletteCommandId::ClusterPvBrowser,
+                name: "PV Data".to_string(),
+                shortcut: None,
+                category: CommandCategory::Cluster,
+                icon: None,
+                color: None,
+                description: Some(
+                    "Browse PV directories; download one to the host or wipe it".to_string(),
+                ),
+            },
+            PaletteCommand {
+                id: PaletteCommandId::ClusterSnapshots,
+                name: "Snapshots".to_string(),
+                shortcut: None,
+                category: CommandCategory::Cluster,
+                icon: None,
+                color: None,
+                description: Some(
+                    "Save the cluster as a named snapshot or restart it from one".to_string(),
+                ),
+            },
+            PaletteCommand {
+                id: PaletteCommandId::ClusterK3sVersion,
+                name: "k3s Version".to_string(),
+                shortcut: None,
+                category: CommandCategory::Cluster,
+                icon: None,
+                color: None,
+                description: Some(
+                    "Pick a recent k3s release for infrastructure.k3s_version".to_string(),
+                ),
+            },
+            PaletteCommand {
+                id: PaletteCommandId::ClusterBuildImage,
+                name: "Build Image".to_string(),
+                shortcut: None,
+                category: CommandCategory::Cluster,
+                icon: None,
+                color: None,
+                description: Some(
+                    "Build an image from builds: with BuildKit and show its stages".to_string(),
+                ),
+            },
+            PaletteCommand {
+                id: PaletteCommandId::ClusterPullSecret,
+                name: "Provision Pull Secret".to_string(),
+                shortcut: None,
+                category: CommandCategory::Cluster,
+                icon: None,
+                color: None,
+                description: Some(
+                    "Copy docker login credentials into namespaces and their default ServiceAccount"
+                        .to_string(),
+                ),
+            },
             // Application commands
             PaletteCommand {
                 id: PaletteCommandId::AppRefresh,
                 name: "Refresh All".to_string(),
                 shortcut: Some("r".to_string()),
                 category: CommandCategory::Application,
+                icon: None,
+                color: None,
                 description: Some("Refresh ingress, hosts, and port forwards".to_string()),
             },
             PaletteCommand {
@@ -146,20 +461,75 @@ impl CommandPalette {
                 name: "Update /etc/hosts".to_string(),
                 shortcut: Some("H".to_string()),
                 category: CommandCategory::Application,
+                icon: None,
+                color: None,
                 description: Some("Add missing ingress hosts to /etc/hosts".to_string()),
             },
+            PaletteCommand {
+                id: PaletteCommandId::AppRestoreHosts,
+                name: "Restore /etc/hosts Backup".to_string(),
+                shortcut: None,
+                category: CommandCategory::Application,
+                icon: None,
+                color: None,
+                description: Some(
+                    "Put back /etc/hosts as it was before k3dev's last edit".to_string(),
+                ),
+            },
+            PaletteCommand {
+                id: PaletteCommandId::AppEditConfig,
+                name: "Edit Config".to_string(),
+                shortcut: None,
+                category: CommandCategory::Application,
+                icon: None,
+                color: None,
+                description: Some("Open the config file in $EDITOR and reload it".to_string()),
+            },
             PaletteCommand {
                 id: PaletteCommandId::AppHelp,
                 name: "Show Help".to_string(),
                 shortcut: Some("?".to_string()),
                 category: CommandCategory::Application,
+                icon: None,
+                color: None,
                 description: Some("Display keyboard shortcuts and help".to_string()),
             },
+            PaletteCommand {
+                id: PaletteCommandId::AppNotifications,
+                name: "Notification History".to_string(),
+                shortcut: None,
+                category: CommandCategory::Application,
+                icon: None,
+                color: None,
+                description: Some("Show recent toast notifications".to_string()),
+            },
+            PaletteCommand {
+                id: PaletteCommandId::AppRefreshTasks,
+                name: "Refresh Tasks".to_string(),
+                shortcut: None,
+                category: CommandCategory::Application,
+                icon: None,
+                color: None,
+                description: Some(
+                    "Show background refresh timings, errors and skipped runs".to_string(),
+                ),
+            },
+            PaletteCommand {
+                id: PaletteCommandId::AppHookRuns,
+                name: "Hooks".to_string(),
+                shortcut: None,
+                category: CommandCategory::Application,
+                icon: None,
+                color: None,
+                description: Some("List past hook runs and reopen their output".to_string()),
+            },
             PaletteCommand {
                 id: PaletteCommandId::AppQuit,
                 name: "Quit Application".to_string(),
                 shortcut: Some("q".to_string()),
                 category: CommandCategory::Application,
+                icon: None,
+                color: None,
                 description: Some("Exit the application".to_string()),
             },
             // Navigation commands
@@ -168,6 +538,8 @@ impl CommandPalette {
                 name: "Focus Menu".to_string(),
                 shortcut: Some("Tab".to_string()),
                 category: CommandCategory::Navigation,
+                icon: None,
+                color: None,
                 description: Some("Switch focus to the command menu".to_string()),
             },
             PaletteCommand {
@@ -175,9 +547,51 @@ impl CommandPalette {
                 name: "Focus Action Bar".to_string(),
                 shortcut: Some("Tab".to_string()),
                 category: CommandCategory::Navigation,
+                icon: None,
+                color: None,
                 description: Some("Switch focus to the action bar".to_string()),
             },
+            PaletteCommand {
+                id: PaletteCommandId::NavPodsByCpu,
+                name: "Pods by CPU".to_string(),
+                shortcut: None,
+                category: CommandCategory::Navigation,
+                icon: None,
+                color: None,
+                description: Some("Focus the pod list sorted by CPU usage".to_string()),
+            },
+            PaletteCommand {
+                id: PaletteCommandId::NavPodsByMemory,
+                name: "Pods by Memory".to_string(),
+                shortcut: None,
+                category: CommandCategory::Navigation,
+                icon: None,
+                color: None,
+                description: Some("Focus the pod list sorted by memory usage".to_string()),
+            },
         ];
+        commands.extend(
+            ClusterComponent::ALL
+                .into_iter()
+                .map(|component| PaletteCommand {
+                    id: PaletteCommandId::ClusterRedeploy(component),
+                    name: component.action_label().to_string(),
+                    shortcut: None,
+                    category: CommandCategory::Maintenance,
+                    icon: None,
+                    color: None,
+                    description: Some(component.description().to_string()),
+                }),
+        );
+        commands.extend(ChaosTool::ALL.into_iter().map(|tool| PaletteCommand {
+            id: PaletteCommandId::Chaos(tool),
+            name: tool.label().to_string(),
+            shortcut: None,
+            category: CommandCategory::Chaos,
+            icon: None,
+            color: None,
+            description: Some(tool.description().to_string()),
+        }));
 
         let filtered: Vec<usize> = (0..commands.len()).collect();
 
@@ -202,15 +616,21 @@ impl CommandPalette {
         &mut self,
         command_groups: &[crate::config::CommandGroup],
         hidden: &HashSet<Vec<usize>>,
+        auto_icons: bool,
     ) {
         // Strip any existing custom entries so repeated calls don't duplicate.
         self.commands
             .retain(|c| !matches!(c.id, PaletteCommandId::Custom(_)));
 
         for (group_idx, group) in command_groups.iter().enumerate() {
+            // Catalog commands carry their source in the category column
+            let category = match &group.source {
+                Some(source) => format!("{} ⇣ {}", group.name, source),
+                None => group.name.clone(),
+            };
             for (entry_idx, entry) in group.commands.iter().enumerate() {
                 let path = vec![group_idx, entry_idx];
-                self.add_command_entry(&group.name, &group.name, entry, path, hidden);
+                self.add_command_entry(&group.name, &category, entry, path, hidden, auto_icons);
             }
         }
         // Update filtered to include the refreshed command list
@@ -220,6 +640,26 @@ impl CommandPalette {
         }
     }
 
+    /// Add a Load Seed entry per bundle, replacing those of an earlier config
+    pub fn load_seeds(&mut self, seeds: &[crate::config::SeedConfig]) {
+        self.commands
+            .retain(|c| !matches!(c.id, PaletteCommandId::LoadSeed(_)));
+        self.commands
+            .extend(seeds.iter().map(|seed| PaletteCommand {
+                id: PaletteCommandId::LoadSeed(seed.name.clone()),
+                name: format!("Load Seed: {}", seed.name),
+                shortcut: None,
+                category: CommandCategory::Seeds,
+                icon: None,
+                color: None,
+                description: seed.description.clone(),
+            }));
+        self.filtered = (0..self.commands.len()).collect();
+        if self.selected_index >= self.filtered.len() {
+            self.selected_index = self.filtered.len().saturating_sub(1);
+        }
+    }
+
     /// Recursively register one command entry, respecting `visible` hides.
     fn add_command_entry(
         &mut self,
@@ -228,6 +668,7 @@ impl CommandPalette {
         entry: &crate::config::CommandEntry,
         path: Vec<usize>,
         hidden: &HashSet<Vec<usize>>,
+        auto_icons: bool,
     ) {
         if hidden.contains(&path) {
             return;
@@ -241,6 +682,8 @@ impl CommandPalette {
                 name: entry.name.clone(),
                 shortcut: None,
                 category: CommandCategory::Custom(category.to_string()),
+                icon: entry.display_icon(auto_icons),
+                color: entry.display_color(),
                 description: entry.description.clone(),
             });
         }
@@ -248,7 +691,14 @@ impl CommandPalette {
         for (child_idx, child) in entry.commands.iter().enumerate() {
             let mut child_path = path.clone();
             child_path.push(child_idx);
-            self.add_command_entry(&display_path, category, child, child_path, hidden);
+            self.add_command_entry(
+                &display_path,
+                category,
+                child,
+                child_path,
+                hidden,
+                auto_icons,
+            );
         }
     }
 
@@ -486,11 +936,14 @@ impl CommandPalette {
         ));
 
         // Command name
-        let name_style = if is_selected {
-            self.styles.selected
-        } else {
-            self.styles.normal_text
+        let name_style = match cmd.color {
+            _ if is_selected => self.styles.selected,
+            Some(color) => self.styles.command_color(color),
+            None => self.styles.normal_text,
         };
+        if let Some(icon) = &cmd.icon {
+            spans.push(Span::styled(format!("{} ", icon), name_style));
+        }
         spans.push(Span::styled(&cmd.name, name_style));
 
         // Shortcut hint
@@ -0,0 +1,235 @@
+//! Command preview popup
+//!
+//! Shows what a command will run before it runs: the target, the working
+//! directory and the command line with config placeholders already filled
+//! in. `@name` tokens left in it are asked for when the command starts and
+//! are highlighted.
+
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Clear, Paragraph, Wrap},
+    Frame,
+};
+
+use crate::config::{get_exec_placeholders, CommandEntry, ExecutionTarget};
+use crate::ui::styles::Styles;
+use crate::ui::theme::Theme;
+
+/// Preview of a command about to run
+pub struct CommandPreview {
+    styles: Styles,
+    command: Option<CommandEntry>,
+}
+
+impl CommandPreview {
+    pub fn new() -> Self {
+        Self::with_theme(Theme::default())
+    }
+
+    pub fn with_theme(theme: Theme) -> Self {
+        Self {
+            styles: Styles::from_theme(theme),
+            command: None,
+        }
+    }
+
+    /// Preview `command`
+    pub fn open(&mut self, command: CommandEntry) {
+        self.command = Some(command);
+    }
+
+    /// The previewed command, leaving the preview empty
+    pub fn take(&mut self) -> Option<CommandEntry> {
+        self.command.take()
+    }
+
+    pub fn render(&self, frame: &mut Frame, area: Rect) {
+        let Some(command) = &self.command else {
+            return;
+        };
+        let Some(exec) = &command.exec else {
+            return;
+        };
+        let popup_area = centered_rect(70, 60, area);
+        frame.render_widget(Clear, popup_area);
+
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Double)
+            .border_style(self.styles.border_focused)
+            .title(format!(" Preview: {} ", command.name))
+            .title_bottom(
+                Line::from(vec![
+                    Span::styled(" Esc close ", self.styles.muted_text),
+                    Span::styled(" Enter run ", self.styles.muted_text),
+                ])
+                .right_aligned(),
+            );
+        let inner = block.inner(popup_area);
+        frame.render_widget(block, popup_area);
+
+        let placeholder = self.styles.warning_text.add_modifier(Modifier::BOLD);
+        let mut lines = Vec::new();
+        if let Some(description) = &command.description {
+            lines.push(Line::from(Span::styled(
+                description.as_str(),
+                self.styles.muted_text,
+            )));
+            lines.push(Line::default());
+        }
+
+        let target = match &exec.target {
+            ExecutionTarget::Host => "host".to_string(),
+            ExecutionTarget::Script => "script".to_string(),
+            ExecutionTarget::Lua => "lua".to_string(),
+            ExecutionTarget::Docker { container } => format!("docker {}", container),
+            ExecutionTarget::Kubernetes {
+                namespace,
+                selector,
+                pod_name,
+                container,
+            } => {
+                let pod = if pod_name.is_empty() {
+                    selector
+                } else {
+                    pod_name
+                };
+                let mut target = format!("kubernetes {}/{}", namespace, pod);
+                if !container.is_empty() {
+                    target.push_str(&format!(" ({})", container));
+                }
+                target
+            }
+        };
+        lines.push(self.field("Target", &target, placeholder));
+        if !exec.workdir.is_empty() {
+            lines.push(self.field("Workdir", &exec.workdir, placeholder));
+        }
+
+        lines.push(Line::default());
+        lines.push(Line::from(Span::styled("Command", self.styles.title)));
+        for cmd_line in exec.cmd.lines() {
+            let mut spans = vec![Span::raw("  ")];
+            spans.extend(self.highlighted(cmd_line, self.styles.normal_text, placeholder));
+            lines.push(Line::from(spans));
+        }
+
+        let inputs = get_exec_placeholders(exec);
+        if !inputs.is_empty() {
+            lines.push(Line::default());
+            lines.push(Line::from(Span::styled("Asked for", self.styles.title)));
+            for name in inputs {
+                let prompt = match exec.input.get(&name) {
+                    Some(input) => match input.default_value() {
+                        Some(default) => format!("{} (default {})", input.prompt(), default),
+                        None => input.prompt().to_string(),
+                    },
+                    None => format!("Enter {}:", name),
+                };
+                lines.push(Line::from(vec![
+                    Span::styled(format!("  @{}", name), placeholder),
+                    Span::styled(format!("  {}", prompt), self.styles.muted_text),
+                ]));
+            }
+        }
+
+        frame.render_widget(Paragraph::new(lines).wrap(Wrap { trim: false }), inner);
+    }
+
+    /// A `label  value` row
+    fn field(&self, label: &str, value: &str, placeholder: Style) -> Line<'static> {
+        let mut spans = vec![Span::styled(format!("{:<9}", label), self.styles.title)];
+        spans.extend(self.highlighted(value, self.styles.normal_text, placeholder));
+        Line::from(spans)
+    }
+
+    fn highlighted(&self, text: &str, normal: Style, placeholder: Style) -> Vec<Span<'static>> {
+        split_placeholders(text)
+            .into_iter()
+            .map(|(part, is_placeholder)| {
+                Span::styled(part, if is_placeholder { placeholder } else { normal })
+            })
+            .collect()
+    }
+}
+
+impl Default for CommandPreview {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Split `text` into runs, flagging the `@name` tokens
+fn split_placeholders(text: &str) -> Vec<(String, bool)> {
+    let mut parts = Vec::new();
+    let mut plain = String::new();
+    let mut chars = text.chars().peekable();
+    while let Some(c) = chars.next() {
+        let starts_name = chars
+            .peek()
+            .is_some_and(|next| next.is_alphanumeric() || *next == '_');
+        if c != '@' || !starts_name {
+            plain.push(c);
+            continue;
+        }
+        if !plain.is_empty() {
+            parts.push((std::mem::take(&mut plain), false));
+        }
+        let mut token = String::from('@');
+        while let Some(&next) = chars.peek() {
+            if !(next.is_alphanumeric() || next == '_') {
+                break;
+            }
+            token.push(next);
+            chars.next();
+        }
+        parts.push((token, true));
+    }
+    if !plain.is_empty() {
+        parts.push((plain, false));
+    }
+    parts
+}
+
+/// Helper to create a centered rect
+fn centered_rect(percent_x: u16, percent_y: u16, area: Rect) -> Rect {
+    let popup_layout = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Percentage((100 - percent_y) / 2),
+            Constraint::Percentage(percent_y),
+            Constraint::Percentage((100 - percent_y) / 2),
+        ])
+        .split(area);
+
+    Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Percentage((100 - percent_x) / 2),
+            Constraint::Percentage(percent_x),
+            Constraint::Percentage((100 - percent_x) / 2),
+        ])
+        .split(popup_layout[1])[1]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn placeholders_are_split_out() {
+        assert_eq!(
+            split_placeholders("deploy --env @env to me@ @x_1."),
+            [
+                ("deploy --env ".to_string(), false),
+                ("@env".to_string(), true),
+                (" to me@ ".to_string(), false),
+                ("@x_1".to_string(), true),
+                (".".to_string(), false),
+            ]
+        );
+        assert!(split_placeholders("").is_empty());
+    }
+}
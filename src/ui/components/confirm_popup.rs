@@ -15,6 +15,8 @@ pub struct ConfirmPopup {
     styles: Styles,
     title: String,
     message: String,
+    /// Key and label of a third answer next to yes and no
+    alternative: Option<(String, String)>,
 }
 
 impl ConfirmPopup {
@@ -27,6 +29,7 @@ impl ConfirmPopup {
             styles: Styles::from_theme(theme),
             title: "Confirm".to_string(),
             message: "Are you sure?".to_string(),
+            alternative: None,
         }
     }
 
@@ -34,6 +37,16 @@ impl ConfirmPopup {
     pub fn set_content(&mut self, title: &str, message: &str) {
         self.title = title.to_string();
         self.message = message.to_string();
+        self.alternative = None;
+    }
+
+    /// Offer a third answer; reset by [`ConfirmPopup::set_content`]
+    pub fn set_alternative(&mut self, key: &str, label: &str) {
+        self.alternative = Some((key.to_string(), label.to_string()));
+    }
+
+    pub fn has_alternative(&self) -> bool {
+        self.alternative.is_some()
     }
 
     pub fn render(&self, frame: &mut Frame, area: Rect) {
@@ -70,14 +83,24 @@ impl ConfirmPopup {
         frame.render_widget(message, chunks[0]);
 
         // Render button hints
-        let buttons = Line::from(vec![
+        let mut buttons = vec![
             Span::styled("[", self.styles.muted_text),
             Span::styled("y", self.styles.error_text),
             Span::styled("] Yes  ", self.styles.muted_text),
+        ];
+        if let Some((key, label)) = &self.alternative {
+            buttons.extend([
+                Span::styled("[", self.styles.muted_text),
+                Span::styled(key.as_str(), self.styles.warning_text),
+                Span::styled(format!("] {}  ", label), self.styles.muted_text),
+            ]);
+        }
+        buttons.extend([
             Span::styled("[", self.styles.muted_text),
             Span::styled("n/Esc", self.styles.success_text),
             Span::styled("] No", self.styles.muted_text),
         ]);
+        let buttons = Line::from(buttons);
         let buttons_para = Paragraph::new(buttons).centered();
         frame.render_widget(buttons_para, chunks[1]);
     }
@@ -109,3 +132,19 @@ fn centered_rect(percent_x: u16, percent_y: u16, area: Rect) -> Rect {
         ])
         .split(popup_layout[1])[1]
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn new_content_drops_the_alternative() {
+        let mut popup = ConfirmPopup::new();
+        popup.set_content("Destroy Cluster", "Sure?");
+        popup.set_alternative("e", "Export volumes, then destroy");
+        assert!(popup.has_alternative());
+
+        popup.set_content("Delete Namespace", "Sure?");
+        assert!(!popup.has_alternative());
+    }
+}
@@ -0,0 +1,190 @@
+//! Popup for panics in background tasks
+//!
+//! Shows the panic message, where it happened and the backtrace. `w` writes
+//! a crash report shaped like a GitHub issue; the popup stays until every
+//! panic it collected was dismissed.
+
+use std::path::PathBuf;
+
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::Modifier,
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Clear, Paragraph, Wrap},
+    Frame,
+};
+
+use crate::crash::{PanicReport, ISSUES_URL};
+use crate::ui::styles::Styles;
+use crate::ui::theme::Theme;
+
+/// Crash overlay
+pub struct CrashView {
+    styles: Styles,
+    /// Oldest first; the first one is shown
+    reports: Vec<PanicReport>,
+    /// Result of writing the shown report
+    written: Option<Result<PathBuf, String>>,
+    scroll: u16,
+}
+
+impl CrashView {
+    pub fn new() -> Self {
+        Self::with_theme(Theme::default())
+    }
+
+    pub fn with_theme(theme: Theme) -> Self {
+        Self {
+            styles: Styles::from_theme(theme),
+            reports: Vec::new(),
+            written: None,
+            scroll: 0,
+        }
+    }
+
+    pub fn push(&mut self, report: PanicReport) {
+        self.reports.push(report);
+    }
+
+    /// The panic shown
+    pub fn current(&self) -> Option<&PanicReport> {
+        self.reports.first()
+    }
+
+    pub fn set_written(&mut self, result: Result<PathBuf, String>) {
+        self.written = Some(result);
+    }
+
+    /// Drop the shown panic; returns whether more are left
+    pub fn dismiss(&mut self) -> bool {
+        if !self.reports.is_empty() {
+            self.reports.remove(0);
+        }
+        self.written = None;
+        self.scroll = 0;
+        !self.reports.is_empty()
+    }
+
+    pub fn scroll_up(&mut self) {
+        self.scroll = self.scroll.saturating_sub(1);
+    }
+
+    pub fn scroll_down(&mut self) {
+        let lines = self.current().map_or(0, |r| r.backtrace.lines().count());
+        if (self.scroll as usize) + 1 < lines {
+            self.scroll += 1;
+        }
+    }
+
+    pub fn render(&self, frame: &mut Frame, area: Rect) {
+        let Some(report) = self.current() else {
+            return;
+        };
+        let popup_area = centered_rect(85, 75, area);
+        frame.render_widget(Clear, popup_area);
+
+        let title = match self.reports.len() {
+            1 => " Background task panicked ".to_string(),
+            n => format!(" Background task panicked (1 of {}) ", n),
+        };
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Double)
+            .border_style(self.styles.error_text)
+            .title(title)
+            .title_bottom(
+                Line::from(vec![
+                    Span::styled(" Esc dismiss ", self.styles.muted_text),
+                    Span::styled(" j/k scroll ", self.styles.muted_text),
+                    Span::styled(" w write crash report ", self.styles.muted_text),
+                ])
+                .right_aligned(),
+            );
+        let inner = block.inner(popup_area);
+        frame.render_widget(block, popup_area);
+
+        let rows = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(4),
+                Constraint::Min(0),
+                Constraint::Length(2),
+            ])
+            .split(inner);
+
+        let header = Paragraph::new(vec![
+            Line::from(Span::styled(
+                format!("  {}", report.summary()),
+                self.styles.error_text.add_modifier(Modifier::BOLD),
+            )),
+            Line::from(Span::styled(
+                format!(
+                    "  thread {} at {}",
+                    report.thread,
+                    report.time.format("%H:%M:%S")
+                ),
+                self.styles.muted_text,
+            )),
+            Line::from(Span::styled(
+                "  k3dev keeps running, but whatever that task did has stopped. The log has the same backtrace.",
+                self.styles.normal_text,
+            )),
+        ])
+        .wrap(Wrap { trim: false });
+        frame.render_widget(header, rows[0]);
+
+        let backtrace: Vec<Line> = report
+            .backtrace
+            .lines()
+            .map(|l| Line::from(Span::styled(l.to_string(), self.styles.muted_text)))
+            .collect();
+        frame.render_widget(Paragraph::new(backtrace).scroll((self.scroll, 0)), rows[1]);
+
+        let status = match &self.written {
+            None => Line::from(Span::styled(
+                format!(
+                    "  Found a bug? Write a crash report and attach it at {}",
+                    ISSUES_URL
+                ),
+                self.styles.normal_text,
+            )),
+            Some(Ok(path)) => Line::from(Span::styled(
+                format!(
+                    "  Crash report written to {}; attach it at {}",
+                    path.display(),
+                    ISSUES_URL
+                ),
+                self.styles.success_text,
+            )),
+            Some(Err(e)) => Line::from(Span::styled(format!("  {}", e), self.styles.error_text)),
+        };
+        frame.render_widget(Paragraph::new(status).wrap(Wrap { trim: false }), rows[2]);
+    }
+}
+
+impl Default for CrashView {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Helper to create a centered rect
+fn centered_rect(percent_x: u16, percent_y: u16, area: Rect) -> Rect {
+    let popup_layout = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Percentage((100 - percent_y) / 2),
+            Constraint::Percentage(percent_y),
+            Constraint::Percentage((100 - percent_y) / 2),
+        ])
+        .split(area);
+
+    Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Percentage((100 - percent_x) / 2),
+            Constraint::Percentage(percent_x),
+            Constraint::Percentage((100 - percent_x) / 2),
+        ])
+        .split(popup_layout[1])[1]
+}
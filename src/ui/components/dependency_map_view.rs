@@ -0,0 +1,220 @@
+//! Service dependency map
+//!
+//! One section per namespace with an ASCII tree per workload: the Services
+//! it answers, who calls it, and the workloads it calls with the env var
+//! that names them. Calls to Services no pod answers are flagged.
+
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::Modifier,
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Clear, Paragraph},
+    Frame,
+};
+
+use crate::k8s::dependency_map::{DependencyNode, NamespaceDependencies};
+use crate::ui::styles::Styles;
+use crate::ui::theme::Theme;
+
+/// Dependency map overlay
+pub struct DependencyMapView {
+    styles: Styles,
+    namespaces: Result<Vec<NamespaceDependencies>, String>,
+    loaded: bool,
+    scroll: usize,
+}
+
+impl DependencyMapView {
+    pub fn new() -> Self {
+        Self::with_theme(Theme::default())
+    }
+
+    pub fn with_theme(theme: Theme) -> Self {
+        Self {
+            styles: Styles::from_theme(theme),
+            namespaces: Ok(Vec::new()),
+            loaded: false,
+            scroll: 0,
+        }
+    }
+
+    /// Forget the previous result before opening the view
+    pub fn reset(&mut self) {
+        self.namespaces = Ok(Vec::new());
+        self.loaded = false;
+        self.scroll = 0;
+    }
+
+    pub fn update(&mut self, namespaces: Result<Vec<NamespaceDependencies>, String>) {
+        self.namespaces = namespaces;
+        self.loaded = true;
+        self.scroll = self.scroll.min(self.line_count().saturating_sub(1));
+    }
+
+    pub fn scroll_up(&mut self) {
+        self.scroll = self.scroll.saturating_sub(1);
+    }
+
+    pub fn scroll_down(&mut self) {
+        if self.scroll + 1 < self.line_count() {
+            self.scroll += 1;
+        }
+    }
+
+    fn line_count(&self) -> usize {
+        self.lines().len()
+    }
+
+    pub fn render(&self, frame: &mut Frame, area: Rect) {
+        let popup_area = centered_rect(85, 85, area);
+        frame.render_widget(Clear, popup_area);
+
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Double)
+            .border_style(self.styles.border_focused)
+            .title(" Dependency Map ")
+            .title_bottom(
+                Line::from(vec![
+                    Span::styled(" Esc close ", self.styles.muted_text),
+                    Span::styled(" j/k scroll ", self.styles.muted_text),
+                    Span::styled(" r reload ", self.styles.muted_text),
+                ])
+                .right_aligned(),
+            );
+        let inner = block.inner(popup_area);
+        frame.render_widget(block, popup_area);
+
+        match &self.namespaces {
+            Err(e) => {
+                let msg = Paragraph::new(Span::styled(format!("  {}", e), self.styles.error_text));
+                frame.render_widget(msg, inner);
+            }
+            Ok(list) if list.is_empty() => {
+                let text = if self.loaded {
+                    "  No workloads running."
+                } else {
+                    "  Loading services and pods..."
+                };
+                let msg = Paragraph::new(Span::styled(text, self.styles.muted_text));
+                frame.render_widget(msg, inner);
+            }
+            Ok(_) => {
+                let lines: Vec<Line> = self.lines().into_iter().skip(self.scroll).collect();
+                frame.render_widget(Paragraph::new(lines), inner);
+            }
+        }
+    }
+
+    fn lines(&self) -> Vec<Line<'static>> {
+        let Ok(namespaces) = &self.namespaces else {
+            return Vec::new();
+        };
+        let mut lines = Vec::new();
+        for ns in namespaces {
+            if !lines.is_empty() {
+                lines.push(Line::default());
+            }
+            lines.push(Line::from(Span::styled(
+                format!(" {}", ns.namespace),
+                self.styles.title.add_modifier(Modifier::BOLD),
+            )));
+            let target_width = ns
+                .workloads
+                .iter()
+                .flat_map(|w| &w.calls)
+                .map(|d| d.target.chars().count())
+                .max()
+                .unwrap_or(0);
+            for workload in &ns.workloads {
+                lines.extend(self.workload_lines(workload, target_width));
+            }
+        }
+        lines
+    }
+
+    fn workload_lines(&self, workload: &DependencyNode, target_width: usize) -> Vec<Line<'static>> {
+        let pods = if workload.pods == 1 {
+            "1 pod".to_string()
+        } else {
+            format!("{} pods", workload.pods)
+        };
+        let mut head = vec![
+            Span::styled(
+                format!("   {}", workload.name),
+                self.styles.normal_text.add_modifier(Modifier::BOLD),
+            ),
+            Span::styled(format!("  {}", pods), self.styles.muted_text),
+        ];
+        if !workload.services.is_empty() {
+            head.push(Span::styled(
+                format!(" · svc {}", workload.services.join(", ")),
+                self.styles.muted_text,
+            ));
+        }
+        if !workload.callers.is_empty() {
+            head.push(Span::styled(
+                format!("   ◀── {}", workload.callers.join(", ")),
+                self.styles.success_text,
+            ));
+        }
+
+        let mut lines = vec![Line::from(head)];
+        for (i, call) in workload.calls.iter().enumerate() {
+            let branch = if i + 1 == workload.calls.len() {
+                "└──▶"
+            } else {
+                "├──▶"
+            };
+            let target_style = if call.backed {
+                self.styles.normal_text
+            } else {
+                self.styles.warning_text
+            };
+            let mut via = format!("  {}", call.via);
+            if call.service != call.target {
+                via.push_str(&format!(" (svc {})", call.service));
+            }
+            let mut spans = vec![
+                Span::styled(format!("   {} ", branch), self.styles.muted_text),
+                Span::styled(
+                    format!("{:<w$}", call.target, w = target_width),
+                    target_style,
+                ),
+                Span::styled(via, self.styles.muted_text),
+            ];
+            if !call.backed {
+                spans.push(Span::styled("  no pods", self.styles.warning_text));
+            }
+            lines.push(Line::from(spans));
+        }
+        lines
+    }
+}
+
+impl Default for DependencyMapView {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Helper to create a centered rect
+fn centered_rect(percent_x: u16, percent_y: u16, area: Rect) -> Rect {
+    let popup_layout = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Percentage((100 - percent_y) / 2),
+            Constraint::Percentage(percent_y),
+            Constraint::Percentage((100 - percent_y) / 2),
+        ])
+        .split(area);
+
+    Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Percentage((100 - percent_x) / 2),
+            Constraint::Percentage(percent_x),
+            Constraint::Percentage((100 - percent_x) / 2),
+        ])
+        .split(popup_layout[1])[1]
+}
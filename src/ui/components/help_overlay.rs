@@ -6,7 +6,7 @@ use ratatui::{
     Frame,
 };
 
-use crate::keybindings::KeybindingResolver;
+use crate::keybindings::{KeyContext, KeybindingResolver};
 use crate::ui::styles::Styles;
 use crate::ui::theme::Theme;
 
@@ -14,13 +14,19 @@ use crate::ui::theme::Theme;
 struct HelpSection {
     title: String,
     bindings: Vec<(String, String)>,
+    /// Contexts in which this section is shown
+    contexts: Vec<KeyContext>,
 }
 
-/// Help overlay component showing all keybindings
+/// Main screen contexts (the pod list inherits the global bindings)
+const MAIN: [KeyContext; 2] = [KeyContext::Normal, KeyContext::PodStats];
+
+/// Help overlay component showing the keybindings for the current mode
 pub struct HelpOverlay {
     styles: Styles,
     scroll_offset: usize,
     sections: Vec<HelpSection>,
+    context: KeyContext,
     total_lines: usize, // Cache total line count
 }
 
@@ -30,19 +36,54 @@ impl HelpOverlay {
     }
 
     pub fn with_theme(theme: Theme) -> Self {
-        let sections = Self::default_sections();
-        let total_lines = Self::count_lines(&sections);
-        Self {
+        let mut overlay = Self {
             styles: Styles::from_theme(theme),
             scroll_offset: 0,
-            sections,
-            total_lines,
-        }
+            sections: Self::default_sections(),
+            context: KeyContext::Normal,
+            total_lines: 0,
+        };
+        overlay.total_lines = overlay.count_lines();
+        overlay
     }
 
-    /// Count total lines in sections
-    fn count_lines(sections: &[HelpSection]) -> usize {
-        sections.iter().map(|s| s.bindings.len() + 3).sum() // +3 for title, empty line before and after
+    /// Sections relevant to the current context
+    fn visible_sections(&self) -> impl Iterator<Item = &HelpSection> {
+        self.sections
+            .iter()
+            .filter(|s| s.contexts.contains(&self.context))
+    }
+
+    /// Count total lines in the visible sections
+    fn count_lines(&self) -> usize {
+        self.visible_sections().map(|s| s.bindings.len() + 3).sum() // +3 for title, empty line before and after
+    }
+
+    /// Show the bindings for a context
+    pub fn set_context(&mut self, context: KeyContext) {
+        self.context = context;
+        self.scroll_offset = 0;
+        self.total_lines = self.count_lines();
+    }
+
+    /// Build a section listing every binding of a context
+    fn context_section(
+        resolver: &KeybindingResolver,
+        context: KeyContext,
+        extra: Vec<(String, String)>,
+    ) -> HelpSection {
+        let mut bindings = extra;
+        bindings.extend(
+            resolver
+                .context_bindings(context)
+                .into_iter()
+                .map(|(keys, action)| (keys.join(" / "), action.description())),
+        );
+        HelpSection {
+            title: context.title().to_string(),
+            bindings,
+            contexts: vec![context],
+        }
     }
 
     /// Update sections based on keybinding configuration
@@ -56,6 +97,15 @@ impl HelpOverlay {
                 .map(|s| s.to_string())
                 .unwrap_or_else(|| default.to_string())
         };
+        // Every key bound to an action, for actions with several defaults
+        let all_keys = |action: &KeyAction, default: &str| -> String {
+            let keys = resolver.keys_for(action);
+            if keys.is_empty() {
+                default.to_string()
+            } else {
+                keys.join(" / ")
+            }
+        };
 
         self.sections = vec![
             HelpSection {
@@ -83,7 +133,12 @@ impl HelpOverlay {
                         "Switch focus (menu/actions)".to_string(),
                     ),
                     (
-                        "1 / 2 / 3".to_string(),
+                        format!(
+                            "{} / {} / {}",
+                            get_binding(&KeyAction::FocusMenu, "1"),
+                            get_binding(&KeyAction::FocusPods, "2"),
+                            get_binding(&KeyAction::FocusActions, "3")
+                        ),
                         "Focus Commands / Pods / Actions".to_string(),
                     ),
                     (
@@ -91,6 +146,7 @@ impl HelpOverlay {
                         "Execute / Toggle".to_string(),
                     ),
                 ],
+                contexts: MAIN.to_vec(),
             },
             HelpSection {
                 title: "Cluster Actions".to_string(),
@@ -98,18 +154,13 @@ impl HelpOverlay {
                     ("Tab → ←/→".to_string(), "Select cluster action".to_string()),
                     ("Enter".to_string(), "Execute selected action".to_string()),
                 ],
+                contexts: vec![KeyContext::Normal],
             },
-            HelpSection {
-                title: "Pod Actions".to_string(),
-                bindings: vec![
-                    ("Enter".to_string(), "Open pod context menu".to_string()),
-                    (
-                        "l / e / d".to_string(),
-                        "Logs / Exec / Describe".to_string(),
-                    ),
-                    ("x / r".to_string(), "Delete / Restart pod".to_string()),
-                ],
-            },
+            Self::context_section(
+                resolver,
+                KeyContext::PodStats,
+                vec![("Enter".to_string(), "Open pod context menu".to_string())],
+            ),
             HelpSection {
                 title: "Commands".to_string(),
                 bindings: vec![
@@ -121,11 +172,34 @@ impl HelpOverlay {
                         get_binding(&KeyAction::UpdateHosts, "H"),
                         "Update /etc/hosts".to_string(),
                     ),
+                    (
+                        get_binding(&KeyAction::ReconcileTraefik, "F"),
+                        "Reconcile drifted Traefik config".to_string(),
+                    ),
                     (
                         get_binding(&KeyAction::CommandPalette, ":"),
                         "Open command palette".to_string(),
                     ),
-                    ("/".to_string(), "Search/filter menu".to_string()),
+                    (
+                        get_binding(&KeyAction::Search, "/"),
+                        "Search/filter menu".to_string(),
+                    ),
+                    (
+                        get_binding(&KeyAction::JumpBreadcrumb, "b"),
+                        "Jump to a group or sibling command".to_string(),
+                    ),
+                    (
+                        get_binding(&KeyAction::PreviewCommand, "p"),
+                        "Preview the selected command".to_string(),
+                    ),
+                    (
+                        format!(
+                            "{}/{}",
+                            get_binding(&KeyAction::ExpandAll, "E"),
+                            get_binding(&KeyAction::CollapseAll, "C")
+                        ),
+                        "Expand/collapse all menu entries".to_string(),
+                    ),
                     (
                         get_binding(&KeyAction::Help, "?"),
                         "Toggle this help".to_string(),
@@ -139,21 +213,50 @@ impl HelpOverlay {
                         "Cancel running command".to_string(),
                     ),
                 ],
+                contexts: MAIN.to_vec(),
             },
             HelpSection {
                 title: "Panel Resize".to_string(),
                 bindings: vec![
-                    ("+ / =".to_string(), "Increase menu width".to_string()),
-                    ("- / _".to_string(), "Decrease menu width".to_string()),
+                    (
+                        all_keys(&KeyAction::GrowMenu, "+ / ="),
+                        "Increase menu width".to_string(),
+                    ),
+                    (
+                        all_keys(&KeyAction::ShrinkMenu, "- / _"),
+                        "Decrease menu width".to_string(),
+                    ),
+                    (
+                        all_keys(&KeyAction::CycleLayout, "L"),
+                        "Cycle layout (classic / docked / columns)".to_string(),
+                    ),
+                    (
+                        all_keys(&KeyAction::ZoomPanel, "z"),
+                        "Zoom focused panel / restore".to_string(),
+                    ),
+                ],
+                contexts: MAIN.to_vec(),
+            },
+            HelpSection {
+                title: "Workspaces".to_string(),
+                bindings: vec![
+                    ("gt / gT".to_string(), "Next / previous tab".to_string()),
+                    ("gn".to_string(), "Open a new tab".to_string()),
+                    ("gc".to_string(), "Close the tab".to_string()),
                 ],
+                contexts: MAIN.to_vec(),
             },
             HelpSection {
                 title: "Mouse".to_string(),
                 bindings: vec![("Click".to_string(), "Select and execute".to_string())],
+                contexts: vec![KeyContext::Normal],
             },
+            Self::context_section(resolver, KeyContext::OutputPopup, Vec::new()),
+            Self::context_section(resolver, KeyContext::Input, Vec::new()),
+            Self::context_section(resolver, KeyContext::Palette, Vec::new()),
         ];
 
-        self.total_lines = Self::count_lines(&self.sections);
+        self.total_lines = self.count_lines();
     }
 
     fn default_sections() -> Vec<HelpSection> {
@@ -169,6 +272,7 @@ impl HelpOverlay {
                     ("Tab".to_string(), "Switch focus (menu/actions)".to_string()),
                     ("Enter".to_string(), "Execute / Toggle".to_string()),
                 ],
+                contexts: MAIN.to_vec(),
             },
             HelpSection {
                 title: "Cluster Actions".to_string(),
@@ -176,6 +280,7 @@ impl HelpOverlay {
                     ("Tab → ←/→".to_string(), "Select cluster action".to_string()),
                     ("Enter".to_string(), "Execute selected action".to_string()),
                 ],
+                contexts: vec![KeyContext::Normal],
             },
             HelpSection {
                 title: "Commands".to_string(),
@@ -187,10 +292,12 @@ impl HelpOverlay {
                     ("q".to_string(), "Quit application".to_string()),
                     ("Ctrl+C".to_string(), "Cancel running command".to_string()),
                 ],
+                contexts: MAIN.to_vec(),
             },
             HelpSection {
                 title: "Mouse".to_string(),
                 bindings: vec![("Click".to_string(), "Select and execute".to_string())],
+                contexts: vec![KeyContext::Normal],
             },
         ]
     }
@@ -241,7 +348,10 @@ impl HelpOverlay {
             .borders(Borders::ALL)
             .border_type(BorderType::Double)
             .border_style(self.styles.border_focused)
-            .title(" Help - Press ? or Esc to close ")
+            .title(format!(
+                " Help: {} - Press ? or Esc to close ",
+                self.context.title()
+            ))
             .title_bottom(
                 Line::from(Span::styled(
                     format!("{}PgUp/PgDn to scroll ", scroll_indicator),
@@ -256,7 +366,7 @@ impl HelpOverlay {
         // Build help content
         let mut lines: Vec<Line> = Vec::new();
 
-        for section in &self.sections {
+        for section in self.visible_sections() {
             // Section title
             lines.push(Line::from(Span::styled(
                 format!("━━ {} ━━", section.title),
@@ -0,0 +1,159 @@
+//! Hooks view
+//!
+//! Every hook command that ran since k3dev started, newest first, with its
+//! status and duration. Each run keeps its own output, so Enter shows what a
+//! single hook printed instead of the interleaved output stream.
+
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::Modifier,
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Cell, Clear, Paragraph, Row, Table},
+    Frame,
+};
+
+use crate::hooks::HookRun;
+use crate::ui::styles::Styles;
+use crate::ui::theme::Theme;
+
+/// Runs kept; older ones are dropped
+const MAX_RUNS: usize = 100;
+
+/// Hooks overlay
+pub struct HookRunsView {
+    styles: Styles,
+    runs: Vec<HookRun>,
+    selected: usize,
+}
+
+impl HookRunsView {
+    pub fn new() -> Self {
+        Self::with_theme(Theme::default())
+    }
+
+    pub fn with_theme(theme: Theme) -> Self {
+        Self {
+            styles: Styles::from_theme(theme),
+            runs: Vec::new(),
+            selected: 0,
+        }
+    }
+
+    /// Add a finished run on top, keeping the selected run selected
+    pub fn push(&mut self, run: HookRun) {
+        self.runs.insert(0, run);
+        self.runs.truncate(MAX_RUNS);
+        if self.selected > 0 {
+            self.selected = (self.selected + 1).min(self.runs.len() - 1);
+        }
+    }
+
+    pub fn select_up(&mut self) {
+        self.selected = self.selected.saturating_sub(1);
+    }
+
+    pub fn select_down(&mut self) {
+        if self.selected + 1 < self.runs.len() {
+            self.selected += 1;
+        }
+    }
+
+    /// The highlighted run
+    pub fn selected(&self) -> Option<&HookRun> {
+        self.runs.get(self.selected)
+    }
+
+    pub fn render(&self, frame: &mut Frame, area: Rect) {
+        let popup_area = centered_rect(85, 60, area);
+        frame.render_widget(Clear, popup_area);
+
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Double)
+            .border_style(self.styles.border_focused)
+            .title(" Hooks ")
+            .title_bottom(
+                Line::from(vec![
+                    Span::styled(" Esc close ", self.styles.muted_text),
+                    Span::styled(" j/k select ", self.styles.muted_text),
+                    Span::styled(" Enter output ", self.styles.muted_text),
+                ])
+                .right_aligned(),
+            );
+        let inner = block.inner(popup_area);
+        frame.render_widget(block, popup_area);
+
+        if self.runs.is_empty() {
+            let msg = Paragraph::new(Span::styled(
+                "  No hooks have run yet.",
+                self.styles.muted_text,
+            ));
+            frame.render_widget(msg, inner);
+            return;
+        }
+
+        let rows = self.runs.iter().enumerate().map(|(i, run)| {
+            let status = if run.success {
+                Span::styled("ok", self.styles.success_text)
+            } else {
+                Span::styled("failed", self.styles.error_text)
+            };
+            Row::new(vec![
+                Cell::from(run.started.format("%H:%M:%S").to_string()),
+                Cell::from(run.event.as_str()),
+                Cell::from(run.name.clone()),
+                Cell::from(format!("{:.1}s", run.duration.as_secs_f64())),
+                Cell::from(status),
+                Cell::from(run.output.len().to_string()),
+            ])
+            .style(if i == self.selected {
+                self.styles.selected
+            } else {
+                self.styles.normal_text
+            })
+        });
+        let table = Table::new(
+            rows,
+            [
+                Constraint::Length(9),
+                Constraint::Length(22),
+                Constraint::Fill(1),
+                Constraint::Length(9),
+                Constraint::Length(7),
+                Constraint::Length(6),
+            ],
+        )
+        .header(
+            Row::new(["STARTED", "EVENT", "HOOK", "DURATION", "STATUS", "LINES"])
+                .style(self.styles.normal_text.add_modifier(Modifier::BOLD)),
+        );
+        frame.render_widget(table, inner);
+    }
+}
+
+impl Default for HookRunsView {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Helper to create a centered rect
+fn centered_rect(percent_x: u16, percent_y: u16, area: Rect) -> Rect {
+    let popup_layout = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Percentage((100 - percent_y) / 2),
+            Constraint::Percentage(percent_y),
+            Constraint::Percentage((100 - percent_y) / 2),
+        ])
+        .split(area);
+
+    Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Percentage((100 - percent_x) / 2),
+            Constraint::Percentage(percent_x),
+            Constraint::Percentage((100 - percent_x) / 2),
+        ])
+        .split(popup_layout[1])[1]
+}
@@ -0,0 +1,208 @@
+//! k3s version picker
+//!
+//! The newest patch of each recent Kubernetes minor, as published on Docker
+//! Hub. Releases that no longer get patches and ones without an image for
+//! the Docker host's architecture are marked; Enter writes the selected
+//! release to `infrastructure.k3s_version`.
+
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Cell, Clear, Paragraph, Row, Table},
+    Frame,
+};
+
+use crate::cluster::k3s_versions::{is_end_of_life, K3sVersion, VersionChoice};
+use crate::ui::styles::Styles;
+use crate::ui::theme::Theme;
+
+/// k3s version overlay
+pub struct K3sVersionView {
+    styles: Styles,
+    choices: Result<Vec<VersionChoice>, String>,
+    loaded: bool,
+    /// The configured `k3s_version`
+    current: String,
+    /// Docker host architecture, once known
+    arch: Option<String>,
+    selected: usize,
+}
+
+impl K3sVersionView {
+    pub fn new() -> Self {
+        Self::with_theme(Theme::default())
+    }
+
+    pub fn with_theme(theme: Theme) -> Self {
+        Self {
+            styles: Styles::from_theme(theme),
+            choices: Ok(Vec::new()),
+            loaded: false,
+            current: String::new(),
+            arch: None,
+            selected: 0,
+        }
+    }
+
+    /// Forget the previous list before opening the view
+    pub fn reset(&mut self, current: &str) {
+        self.choices = Ok(Vec::new());
+        self.loaded = false;
+        self.current = current.to_string();
+        self.arch = None;
+        self.selected = 0;
+    }
+
+    pub fn update(&mut self, choices: Result<Vec<VersionChoice>, String>, arch: Option<String>) {
+        // Start on the configured release when it is listed
+        let current = K3sVersion::parse(&self.current);
+        self.selected = choices
+            .as_ref()
+            .ok()
+            .and_then(|list| list.iter().position(|c| Some(c.version) == current))
+            .unwrap_or(0);
+        self.choices = choices;
+        self.arch = arch;
+        self.loaded = true;
+    }
+
+    pub fn select_up(&mut self) {
+        self.selected = self.selected.saturating_sub(1);
+    }
+
+    pub fn select_down(&mut self) {
+        let len = self.choices.as_ref().map_or(0, Vec::len);
+        if self.selected + 1 < len {
+            self.selected += 1;
+        }
+    }
+
+    /// The highlighted release
+    pub fn selected(&self) -> Option<&VersionChoice> {
+        self.choices.as_ref().ok()?.get(self.selected)
+    }
+
+    pub fn render(&self, frame: &mut Frame, area: Rect) {
+        let popup_area = centered_rect(60, 50, area);
+        frame.render_widget(Clear, popup_area);
+
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Double)
+            .border_style(self.styles.border_focused)
+            .title(format!(" k3s Version (configured: {}) ", self.current))
+            .title_bottom(
+                Line::from(vec![
+                    Span::styled(" Esc close ", self.styles.muted_text),
+                    Span::styled(" j/k select ", self.styles.muted_text),
+                    Span::styled(" Enter use version ", self.styles.muted_text),
+                ])
+                .right_aligned(),
+            );
+        let inner = block.inner(popup_area);
+        frame.render_widget(block, popup_area);
+
+        let list = match &self.choices {
+            Err(e) => {
+                let msg = Paragraph::new(Span::styled(format!("  {}", e), self.styles.error_text));
+                frame.render_widget(msg, inner);
+                return;
+            }
+            Ok(list) if list.is_empty() => {
+                let text = if self.loaded {
+                    "  Docker Hub lists no stable k3s releases."
+                } else {
+                    "  Loading releases from Docker Hub..."
+                };
+                let msg = Paragraph::new(Span::styled(text, self.styles.muted_text));
+                frame.render_widget(msg, inner);
+                return;
+            }
+            Ok(list) => list,
+        };
+
+        let current = K3sVersion::parse(&self.current);
+        let rows = list.iter().enumerate().map(|(i, choice)| {
+            let marker = if Some(choice.version) == current {
+                "●"
+            } else {
+                " "
+            };
+            let (note, note_style) = self.note(choice, list);
+            Row::new(vec![
+                Cell::from(marker),
+                Cell::from(choice.version.to_string()),
+                Cell::from(format!("{}.{}", choice.version.major, choice.version.minor)),
+                Cell::from(Span::styled(
+                    choice.architectures.join(", "),
+                    self.styles.muted_text,
+                )),
+                Cell::from(Span::styled(note, note_style)),
+            ])
+            .style(if i == self.selected {
+                self.styles.selected
+            } else {
+                self.styles.normal_text
+            })
+        });
+        let table = Table::new(
+            rows,
+            [
+                Constraint::Length(1),
+                Constraint::Length(16),
+                Constraint::Length(6),
+                Constraint::Length(24),
+                Constraint::Fill(1),
+            ],
+        )
+        .header(
+            Row::new(["", "RELEASE", "KUBE", "ARCHITECTURES", ""])
+                .style(self.styles.normal_text.add_modifier(Modifier::BOLD)),
+        );
+        frame.render_widget(table, inner);
+    }
+
+    /// Why a release is a poor choice, if it is
+    fn note(&self, choice: &VersionChoice, list: &[VersionChoice]) -> (String, Style) {
+        if let Some(arch) = &self.arch {
+            if !choice.architectures.is_empty() && !choice.architectures.contains(arch) {
+                return (format!("no {} image", arch), self.styles.error_text);
+            }
+        }
+        if is_end_of_life(&choice.version, list) {
+            return ("end of life".to_string(), self.styles.warning_text);
+        }
+        if list.first() == Some(choice) {
+            return ("latest".to_string(), self.styles.success_text);
+        }
+        (String::new(), self.styles.normal_text)
+    }
+}
+
+impl Default for K3sVersionView {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Helper to create a centered rect
+fn centered_rect(percent_x: u16, percent_y: u16, area: Rect) -> Rect {
+    let popup_layout = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Percentage((100 - percent_y) / 2),
+            Constraint::Percentage(percent_y),
+            Constraint::Percentage((100 - percent_y) / 2),
+        ])
+        .split(area);
+
+    Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Percentage((100 - percent_x) / 2),
+            Constraint::Percentage(percent_x),
+            Constraint::Percentage((100 - percent_x) / 2),
+        ])
+        .split(popup_layout[1])[1]
+}
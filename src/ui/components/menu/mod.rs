@@ -4,15 +4,22 @@
 //! ingress entries, and forwarded ports in a hierarchical tree structure.
 
 mod render;
+mod state;
 
+use std::cell::Cell;
 use std::collections::{HashMap, HashSet};
 
 use crate::app::{InfoBlockResult, InfoBlockStatus};
-use crate::cluster::{IngressEntry, IngressHealthStatus};
-use crate::config::{CommandEntry, CommandGroup, Config};
+use crate::cluster::health_history::HealthHistory;
+use crate::cluster::{HealthCheck, IngressEntry, IngressHealthStatus};
+use crate::config::{
+    CommandColor, CommandEntry, CommandGroup, Config, IngressHealthConfig, Thresholds,
+};
 use crate::ui::styles::Styles;
 use crate::ui::theme::Theme;
 
+pub use state::MenuState;
+
 /// A flattened menu item for display
 #[derive(Debug, Clone)]
 pub struct FlatMenuItem {
@@ -25,6 +32,21 @@ pub struct FlatMenuItem {
     pub command: Option<CommandEntry>,
     pub group_index: usize,
     pub item_path: Vec<usize>,
+    /// Catalog a group header came from, shown after its name
+    pub source: Option<String>,
+    /// Color hint of a command
+    pub color: Option<CommandColor>,
+}
+
+/// A group or command offered by the breadcrumb jump picker
+#[derive(Debug, Clone, PartialEq)]
+pub struct JumpTarget {
+    /// Depth in the tree; 0 for groups
+    pub level: usize,
+    pub name: String,
+    pub item_path: Vec<usize>,
+    /// Part of the current breadcrumb
+    pub on_path: bool,
 }
 
 /// Active port forward from kubectl port-forward or similar
@@ -35,6 +57,13 @@ pub struct ActivePortForward {
     pub target: String, // e.g., "pod/my-pod", "svc/my-service"
 }
 
+/// Ingress port published on another host with the Share action
+#[derive(Debug, Clone)]
+pub struct SharedPort {
+    pub local_port: u16,
+    pub remote: String, // e.g., "demo.example.com:8080"
+}
+
 /// Display row for a sidebar info block.
 #[derive(Debug, Clone)]
 pub struct InfoBlockView {
@@ -44,6 +73,8 @@ pub struct InfoBlockView {
     pub status: InfoBlockStatus,
     /// Whether this block is currently hidden by a `visible` gate.
     pub hidden: bool,
+    /// Levels the output's first number is coloured by
+    pub thresholds: Option<Thresholds>,
 }
 
 /// Hierarchical command menu component
@@ -51,13 +82,21 @@ pub struct Menu {
     pub(super) items: Vec<CommandGroup>,
     pub(super) flat_items: Vec<FlatMenuItem>,
     expanded: Vec<bool>, // Track expanded state for groups
+    // Paths of nested entries the user collapsed; the rest stay expanded
+    pub(super) collapsed_entries: HashSet<Vec<usize>>,
     pub(super) selected_index: usize,
-    pub(super) scroll_offset: usize,
+    /// First command row on screen, kept in view of the selection by render
+    pub(super) scroll_offset: Cell<usize>,
     pub(super) styles: Styles,
     // Ingress entries with paths and health status
     pub(super) ingress_entries: Vec<IngressEntry>,
     pub(super) ingress_health: HashMap<String, IngressHealthStatus>, // Key: "host|path"
+    pub(super) ingress_history: HealthHistory,
+    /// Latency budgets of the ingress paths
+    pub(super) ingress_health_config: IngressHealthConfig,
     pub(super) ingress_expanded: bool,
+    // Public tunnel URLs of ingress hosts (host → URL)
+    pub(super) tunnel_urls: HashMap<String, String>,
     // Hosts that are missing from /etc/hosts (should blink)
     pub(super) missing_hosts: HashSet<String>,
     // Manual blink state (toggled by app loop)
@@ -66,6 +105,8 @@ pub struct Menu {
     pub(super) forwarded_ports: Vec<(u16, u16)>,
     // Active port forwards from kubectl port-forward
     pub(super) active_port_forwards: Vec<ActivePortForward>,
+    // Ports shared over SSH remote forwards
+    pub(super) shared_ports: Vec<SharedPort>,
     // Search/filter state
     pub(super) search_mode: bool,
     pub(super) search_query: String,
@@ -78,6 +119,8 @@ pub struct Menu {
     pub(super) info_blocks: Vec<InfoBlockView>,
     // Command entry paths currently hidden by `visible` gates.
     pub(super) hidden_command_paths: HashSet<Vec<usize>>,
+    // Give commands an icon for their target when they have none (`ui.command_icons`)
+    command_icons: bool,
 }
 
 impl Menu {
@@ -90,16 +133,21 @@ impl Menu {
             items: Vec::new(),
             flat_items: Vec::new(),
             expanded: Vec::new(),
+            collapsed_entries: HashSet::new(),
             selected_index: 0,
-            scroll_offset: 0,
+            scroll_offset: Cell::new(0),
             styles: Styles::from_theme(theme),
             ingress_entries: Vec::new(),
             ingress_health: HashMap::new(),
+            ingress_history: HealthHistory::default(),
+            ingress_health_config: IngressHealthConfig::default(),
             ingress_expanded: true,
+            tunnel_urls: HashMap::new(),
             missing_hosts: HashSet::new(),
             blink_visible: true,
             forwarded_ports: Vec::new(),
             active_port_forwards: Vec::new(),
+            shared_ports: Vec::new(),
             search_mode: false,
             search_query: String::new(),
             filtered_indices: Vec::new(),
@@ -108,6 +156,7 @@ impl Menu {
             selected_ingress_path: 0,
             info_blocks: Vec::new(),
             hidden_command_paths: HashSet::new(),
+            command_icons: true,
         }
     }
 
@@ -120,12 +169,32 @@ impl Menu {
 
     /// Update ingress entries list
     pub fn set_ingress_entries(&mut self, entries: Vec<IngressEntry>) {
+        let keys: HashSet<String> = entries
+            .iter()
+            .flat_map(|e| e.paths.iter().map(move |p| format!("{}|{}", e.host, p)))
+            .collect();
+        self.ingress_health.retain(|key, _| keys.contains(key));
+        self.ingress_history.retain(|key| keys.contains(key));
         self.ingress_entries = entries;
     }
 
-    /// Update ingress health status (key format: "host|path")
-    pub fn set_ingress_health(&mut self, health: HashMap<String, IngressHealthStatus>) {
-        self.ingress_health = health;
+    /// Record a round of ingress health checks (key format: "host|path").
+    /// Endpoints left out of the round (backing off) keep their last status.
+    pub fn set_ingress_health(&mut self, checks: HashMap<String, HealthCheck>) {
+        self.ingress_history.record(&checks);
+        self.ingress_health
+            .extend(checks.into_iter().map(|(key, check)| (key, check.status)));
+    }
+
+    /// Forget ingress health and its history (cluster stopped)
+    pub fn clear_ingress_health(&mut self) {
+        self.ingress_health.clear();
+        self.ingress_history.clear();
+    }
+
+    /// Update the public tunnel URLs shown under their ingress hosts
+    pub fn set_tunnel_urls(&mut self, urls: HashMap<String, String>) {
+        self.tunnel_urls = urls;
     }
 
     /// Update missing hosts (hosts not in /etc/hosts)
@@ -143,6 +212,11 @@ impl Menu {
         self.active_port_forwards = forwards;
     }
 
+    /// Update the ports shared over SSH
+    pub fn set_shared_ports(&mut self, ports: Vec<SharedPort>) {
+        self.shared_ports = ports;
+    }
+
     /// Seed the info blocks with initial placeholder rows so headers render
     /// before the first refresh completes.
     pub fn set_info_blocks(&mut self, blocks: Vec<InfoBlockView>) {
@@ -305,14 +379,25 @@ impl Menu {
         // At the end, stay at last item
     }
 
-    /// Get the currently selected ingress URL (if any)
-    pub fn selected_ingress_url(&self) -> Option<String> {
+    /// Host and path of the selected ingress path (if any)
+    pub fn selected_ingress_endpoint(&self) -> Option<(&str, &str)> {
         if !self.ingress_selected {
             return None;
         }
         let entry = self.ingress_entries.get(self.selected_ingress_entry)?;
         let path = entry.paths.get(self.selected_ingress_path)?;
-        Some(format!("http://{}{}", entry.host, path))
+        Some((&entry.host, path))
+    }
+
+    /// Host of the selected ingress path (if any)
+    pub fn selected_ingress_host(&self) -> Option<&str> {
+        self.selected_ingress_endpoint().map(|(host, _)| host)
+    }
+
+    /// Get the currently selected ingress URL (if any)
+    pub fn selected_ingress_url(&self) -> Option<String> {
+        let (host, path) = self.selected_ingress_endpoint()?;
+        Some(format!("http://{}{}", host, path))
     }
 
     // === Config Building ===
@@ -320,9 +405,12 @@ impl Menu {
     /// Build menu from config
     pub fn build_from_config(&mut self, config: &Config) {
         self.items = config.commands.clone();
+        self.command_icons = config.ui.command_icons;
+        self.ingress_health_config = config.ingress_health.clone();
 
         // Initialize expanded state - all groups start expanded
         self.expanded = vec![true; self.items.len()];
+        self.collapsed_entries.clear();
 
         self.rebuild_flat_items();
     }
@@ -349,6 +437,7 @@ impl Menu {
 
         for (group_idx, (name, icon, commands, is_expanded)) in items_data.into_iter().enumerate() {
             // Add group header
+            let source = self.items[group_idx].source.clone();
             self.flat_items.push(FlatMenuItem {
                 name,
                 icon,
@@ -359,6 +448,8 @@ impl Menu {
                 command: None,
                 group_index: group_idx,
                 item_path: vec![group_idx],
+                source,
+                color: None,
             });
 
             // If group is expanded, add its children
@@ -391,12 +482,13 @@ impl Menu {
             }
 
             let has_children = !entry.commands.is_empty();
-            let is_expanded = has_children; // Nested items always expanded for simplicity
+            let is_expanded = has_children && !self.collapsed_entries.contains(&path);
             let children = entry.commands.clone();
 
             self.flat_items.push(FlatMenuItem {
                 name: entry.name.clone(),
-                icon: String::new(),
+                icon: entry.display_icon(self.command_icons).unwrap_or_default(),
+                color: entry.display_color(),
                 level,
                 is_group: false,
                 is_expanded,
@@ -408,6 +500,7 @@ impl Menu {
                 },
                 group_index: group_idx,
                 item_path: path.clone(),
+                source: None,
             });
 
             // Recurse if has children
@@ -480,22 +573,37 @@ impl Menu {
 
     /// Toggle expansion of current item
     pub fn toggle(&mut self) {
-        if let Some(item) = self.flat_items.get(self.selected_index) {
-            if item.is_group && item.has_children {
-                let group_idx = item.group_index;
-                if let Some(exp) = self.expanded.get_mut(group_idx) {
-                    *exp = !*exp;
-                }
-                self.rebuild_flat_items();
+        let Some(item) = self.flat_items.get(self.selected_index) else {
+            return;
+        };
+        if !item.has_children {
+            return;
+        }
+        if item.is_group {
+            let group_idx = item.group_index;
+            if let Some(exp) = self.expanded.get_mut(group_idx) {
+                *exp = !*exp;
+            }
+        } else {
+            let path = item.item_path.clone();
+            if !self.collapsed_entries.remove(&path) {
+                self.collapsed_entries.insert(path);
             }
         }
+        self.rebuild_flat_items();
     }
 
     /// Collapse current item or parent
     pub fn collapse(&mut self) {
-        if let Some(item) = self.flat_items.get(self.selected_index) {
-            if item.is_group && item.is_expanded {
-                self.toggle();
+        let Some(item) = self.flat_items.get(self.selected_index) else {
+            return;
+        };
+        if item.has_children && item.is_expanded {
+            self.toggle();
+        } else if item.item_path.len() > 1 {
+            let parent = &item.item_path[..item.item_path.len() - 1];
+            if let Some(index) = self.flat_items.iter().position(|i| i.item_path == parent) {
+                self.selected_index = index;
             }
         }
     }
@@ -503,12 +611,54 @@ impl Menu {
     /// Expand current item
     pub fn expand(&mut self) {
         if let Some(item) = self.flat_items.get(self.selected_index) {
-            if item.is_group && !item.is_expanded {
+            if item.has_children && !item.is_expanded {
                 self.toggle();
             }
         }
     }
 
+    /// Expand every group and nested entry, keeping the selection
+    pub fn expand_all(&mut self) {
+        let selected = self.selected_item().map(|item| item.item_path.clone());
+        self.expanded.iter_mut().for_each(|exp| *exp = true);
+        self.collapsed_entries.clear();
+        self.rebuild_flat_items();
+        if let Some(path) = selected {
+            self.select_path(&path);
+        }
+    }
+
+    /// Collapse every group and nested entry, selecting the current group
+    pub fn collapse_all(&mut self) {
+        let group = self.selected_item().map(|item| item.group_index);
+        self.expanded.iter_mut().for_each(|exp| *exp = false);
+        let mut paths = Vec::new();
+        for (group_idx, group) in self.items.iter().enumerate() {
+            collect_parent_paths(&group.commands, vec![group_idx], &mut paths);
+        }
+        self.collapsed_entries.extend(paths);
+        self.ingress_selected = false;
+        self.rebuild_flat_items();
+        if let Some(group) = group {
+            self.select_path(&[group]);
+        }
+    }
+
+    /// Select the visible item at `item_path`, if there is one
+    fn select_path(&mut self, item_path: &[usize]) -> bool {
+        match self
+            .flat_items
+            .iter()
+            .position(|item| item.item_path == item_path)
+        {
+            Some(index) => {
+                self.selected_index = index;
+                true
+            }
+            None => false,
+        }
+    }
+
     // === Selection ===
 
     /// Get selected item
@@ -516,12 +666,117 @@ impl Menu {
         self.flat_items.get(self.selected_index)
     }
 
+    /// Description of the selected command, shown under it
+    pub fn selected_description(&self) -> Option<&str> {
+        if self.ingress_selected {
+            return None;
+        }
+        self.selected_item()?
+            .command
+            .as_ref()?
+            .description
+            .as_deref()
+            .filter(|d| !d.is_empty())
+    }
+
+    /// Every group, then the siblings at each level of the selected item's
+    /// breadcrumb
+    pub fn jump_targets(&self) -> Vec<JumpTarget> {
+        let Some(current) = self.selected_item().map(|i| i.item_path.clone()) else {
+            return Vec::new();
+        };
+        let mut targets: Vec<JumpTarget> = self
+            .items
+            .iter()
+            .enumerate()
+            .map(|(i, group)| JumpTarget {
+                level: 0,
+                name: group.name.clone(),
+                item_path: vec![i],
+                on_path: current.first() == Some(&i),
+            })
+            .collect();
+        for depth in 1..current.len() {
+            let parent = &current[..depth];
+            for (i, entry) in self.children_of(parent).iter().enumerate() {
+                let mut item_path = parent.to_vec();
+                item_path.push(i);
+                if self.hidden_command_paths.contains(&item_path) {
+                    continue;
+                }
+                targets.push(JumpTarget {
+                    level: depth,
+                    name: entry.name.clone(),
+                    item_path,
+                    on_path: current.get(depth) == Some(&i),
+                });
+            }
+        }
+        targets
+    }
+
+    /// Select the group or command at `item_path`, expanding its group
+    /// first. Returns false if it isn't in the menu.
+    pub fn jump_to(&mut self, item_path: &[usize]) -> bool {
+        let mut rebuild = false;
+        if let (true, Some(expanded)) = (
+            item_path.len() > 1,
+            item_path.first().and_then(|g| self.expanded.get_mut(*g)),
+        ) {
+            rebuild |= !*expanded;
+            *expanded = true;
+        }
+        // Nested entries on the way down have to be open too
+        for depth in 2..item_path.len() {
+            rebuild |= self.collapsed_entries.remove(&item_path[..depth]);
+        }
+        if rebuild {
+            self.rebuild_flat_items();
+        }
+        if self.select_path(item_path) {
+            self.ingress_selected = false;
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Entries directly under a group or command
+    pub(super) fn children_of(&self, parent: &[usize]) -> &[CommandEntry] {
+        let Some((group, rest)) = parent.split_first() else {
+            return &[];
+        };
+        let Some(group) = self.items.get(*group) else {
+            return &[];
+        };
+        let mut entries = group.commands.as_slice();
+        for i in rest {
+            match entries.get(*i) {
+                Some(entry) => entries = &entry.commands,
+                None => return &[],
+            }
+        }
+        entries
+    }
+
+    /// Select the item at `index`, clamped to the last one
+    pub fn select_index(&mut self, index: usize) {
+        self.selected_index = index.min(self.flat_items.len().saturating_sub(1));
+    }
+
     /// Select item at the given row (for mouse click handling)
     /// Returns true if a valid item was selected
     pub fn select_at_row(&mut self, row: usize) -> bool {
         // Account for breadcrumb line
-        let adjusted_row = row.saturating_sub(1);
-        let target_index = self.scroll_offset + adjusted_row;
+        let mut adjusted_row = row.saturating_sub(1);
+        // ... and for the description row under the selection
+        let selected_row = self.selected_index.checked_sub(self.scroll_offset.get());
+        if let (Some(selected_row), true) = (selected_row, self.selected_description().is_some()) {
+            if adjusted_row > selected_row {
+                adjusted_row -= 1;
+            }
+        }
+        let target_index = self.scroll_offset.get() + adjusted_row;
         if target_index < self.flat_items.len() {
             self.selected_index = target_index;
             true
@@ -536,3 +791,20 @@ impl Default for Menu {
         Self::new()
     }
 }
+
+/// Paths of the entries under `parent_path` that have children of their own
+fn collect_parent_paths(
+    entries: &[CommandEntry],
+    parent_path: Vec<usize>,
+    out: &mut Vec<Vec<usize>>,
+) {
+    for (idx, entry) in entries.iter().enumerate() {
+        if entry.commands.is_empty() {
+            continue;
+        }
+        let mut path = parent_path.clone();
+        path.push(idx);
+        collect_parent_paths(&entry.commands, path.clone(), out);
+        out.push(path);
+    }
+}
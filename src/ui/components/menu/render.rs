@@ -2,6 +2,8 @@
 //!
 //! This module contains the render logic for the Menu component.
 
+use std::time::Duration;
+
 use ratatui::{
     layout::Rect,
     style::{Modifier, Style},
@@ -14,7 +16,9 @@ use ratatui::{
 
 use super::Menu;
 use crate::app::InfoBlockStatus;
+use crate::cluster::health_history::HealthTrend;
 use crate::cluster::IngressHealthStatus;
+use crate::config::ThresholdLevel;
 
 impl Menu {
     /// Get health style for status
@@ -27,30 +31,23 @@ impl Menu {
         }
     }
 
-    /// Get breadcrumb path for current selection
+    /// Get breadcrumb path for current selection: the group, every
+    /// enclosing command and the item itself
     pub(super) fn get_breadcrumb_path(&self) -> Vec<String> {
-        if let Some(item) = self.flat_items.get(self.selected_index) {
-            let mut path = Vec::new();
-
-            // Build path from item_path indices
-            if !item.item_path.is_empty() {
-                // First element is group index
-                if let Some(group_idx) = item.item_path.first() {
-                    if let Some(group) = self.items.get(*group_idx) {
-                        path.push(group.name.clone());
-                    }
-                }
-
-                // Add item name if it's not a group header
-                if !item.is_group {
-                    path.push(item.name.clone());
-                }
+        let Some(item) = self.flat_items.get(self.selected_index) else {
+            return Vec::new();
+        };
+        let Some(group) = item.item_path.first().and_then(|g| self.items.get(*g)) else {
+            return Vec::new();
+        };
+        let mut path = vec![group.name.clone()];
+        for depth in 1..item.item_path.len() {
+            let parent = &item.item_path[..depth];
+            if let Some(entry) = self.children_of(parent).get(item.item_path[depth]) {
+                path.push(entry.name.clone());
             }
-
-            path
-        } else {
-            Vec::new()
         }
+        path
     }
 
     /// Get the longest visible line width across all sections (for auto-width calculation)
@@ -82,6 +79,13 @@ impl Menu {
                 if host_w > max_width {
                     max_width = host_w;
                 }
+                // Tunnel line: "    ↗ {url}"
+                if let Some(url) = self.tunnel_urls.get(&entry.host) {
+                    let url_w = 6 + url.chars().count() as u16;
+                    if url_w > max_width {
+                        max_width = url_w;
+                    }
+                }
                 // Path lines: "  ▸└ ● {path}"
                 for path in &entry.paths {
                     let path_w = 6 + 2 + path.chars().count() as u16;
@@ -115,6 +119,16 @@ impl Menu {
                 max_width = pf_w;
             }
         }
+        for shared in &self.shared_ports {
+            // "    {port} → {remote}"
+            let shared_w = 4
+                + shared.local_port.to_string().len() as u16
+                + 3
+                + shared.remote.chars().count() as u16;
+            if shared_w > max_width {
+                max_width = shared_w;
+            }
+        }
 
         max_width
     }
@@ -205,7 +219,8 @@ impl Menu {
             let total_paths: usize = self
                 .ingress_entries
                 .iter()
-                .map(|e| 1 + e.paths.len()) // 1 for host line + paths
+                // 1 for host line + tunnel URL + paths
+                .map(|e| 1 + usize::from(self.tunnel_urls.contains_key(&e.host)) + e.paths.len())
                 .sum();
             2 + total_paths
         } else {
@@ -216,7 +231,8 @@ impl Menu {
         // Calculate how many lines the forwarded ports section will take
         let has_static_ports = !self.forwarded_ports.is_empty();
         let has_active_ports = !self.active_port_forwards.is_empty();
-        let ports_lines = if !has_static_ports && !has_active_ports {
+        let has_shared_ports = !self.shared_ports.is_empty();
+        let ports_lines = if !has_static_ports && !has_active_ports && !has_shared_ports {
             0
         } else {
             // Separator + Header + static ports + (subheader + active ports if any)
@@ -227,6 +243,9 @@ impl Menu {
             if has_active_ports {
                 count += 1 + self.active_port_forwards.len(); // subheader + ports
             }
+            if has_shared_ports {
+                count += 1 + self.shared_ports.len(); // subheader + ports
+            }
             count
         };
 
@@ -255,14 +274,30 @@ impl Menu {
             .saturating_sub(info_blocks_lines)
             .saturating_sub(1);
 
+        // The selected command's description takes a row under it
+        let description = self.selected_description();
+        let item_rows = commands_height.saturating_sub(usize::from(description.is_some()));
+
+        // Scroll just enough to keep the selection on screen
+        let mut scroll_offset = self
+            .scroll_offset
+            .get()
+            .min(self.flat_items.len().saturating_sub(item_rows));
+        if self.selected_index < scroll_offset {
+            scroll_offset = self.selected_index;
+        } else if item_rows > 0 && self.selected_index >= scroll_offset + item_rows {
+            scroll_offset = self.selected_index + 1 - item_rows;
+        }
+        self.scroll_offset.set(scroll_offset);
+
         // Build command lines
         let command_lines: Vec<Line> = self
             .flat_items
             .iter()
             .enumerate()
-            .skip(self.scroll_offset)
-            .take(commands_height)
-            .map(|(idx, item)| {
+            .skip(scroll_offset)
+            .take(item_rows)
+            .flat_map(|(idx, item)| {
                 let is_selected = idx == self.selected_index;
                 let is_match = self.search_query.is_empty() || self.filtered_indices.contains(&idx);
 
@@ -283,7 +318,7 @@ impl Menu {
                     "  "
                 };
 
-                // Item icon (only for groups)
+                // Group or command icon
                 let icon = if !item.icon.is_empty() {
                     format!("{} ", item.icon)
                 } else {
@@ -299,11 +334,28 @@ impl Menu {
                     self.styles.selected
                 } else if item.is_group {
                     self.styles.group_header
+                } else if let Some(color) = item.color {
+                    self.styles.command_color(color)
                 } else {
                     self.styles.normal_text
                 };
 
-                Line::from(Span::styled(text, style))
+                let mut spans = vec![Span::styled(text, style)];
+                // Catalog groups are read-only; say where they come from
+                if let Some(source) = &item.source {
+                    spans.push(Span::styled(
+                        format!("  ⇣ {}", source),
+                        self.styles.muted_text,
+                    ));
+                }
+                let mut rows = vec![Line::from(spans)];
+                if let (true, Some(description)) = (is_selected, description) {
+                    rows.push(Line::from(Span::styled(
+                        format!("    {}  {}", indent, description),
+                        self.styles.muted_text,
+                    )));
+                }
+                rows
             })
             .collect();
 
@@ -326,12 +378,12 @@ impl Menu {
         frame.render_widget(paragraph, inner);
 
         // Render scrollbar if there are more items than visible
-        if self.flat_items.len() > commands_height {
+        if self.flat_items.len() > item_rows {
             let scrollbar = Scrollbar::new(ScrollbarOrientation::VerticalRight)
                 .begin_symbol(Some("↑"))
                 .end_symbol(Some("↓"));
             let mut scrollbar_state =
-                ScrollbarState::new(self.flat_items.len()).position(self.scroll_offset);
+                ScrollbarState::new(self.flat_items.len()).position(scroll_offset);
             frame.render_stateful_widget(scrollbar, inner, &mut scrollbar_state);
         }
     }
@@ -382,6 +434,13 @@ impl Menu {
                     )));
                 }
 
+                if let Some(url) = self.tunnel_urls.get(&entry.host) {
+                    lines.push(Line::from(Span::styled(
+                        format!("    ↗ {}", url),
+                        self.styles.info_text,
+                    )));
+                }
+
                 // Add paths under host
                 let path_count = entry.paths.len();
                 for (path_idx, path) in entry.paths.iter().enumerate() {
@@ -391,7 +450,19 @@ impl Menu {
                         .get(&key)
                         .copied()
                         .unwrap_or(IngressHealthStatus::Unknown);
-                    let health_style = self.health_style(health);
+                    let trend = self.ingress_history.trend(&key);
+                    let budget = self
+                        .ingress_health_config
+                        .latency_budget(&entry.host, path)
+                        .filter(|budget| self.ingress_history.over_budget(&key, *budget));
+                    let (dot, health_style) = match trend {
+                        HealthTrend::Flapping => ("◐", self.styles.info_text),
+                        // Up, but consistently slower than its budget
+                        _ if budget.is_some() && health == IngressHealthStatus::Healthy => {
+                            (health.dot(), self.styles.warning_text)
+                        }
+                        _ => (health.dot(), self.health_style(health)),
+                    };
 
                     // Tree branch character
                     let branch = if path_idx == path_count - 1 {
@@ -412,7 +483,7 @@ impl Menu {
                         self.styles.normal_text
                     };
 
-                    lines.push(Line::from(vec![
+                    let mut spans = vec![
                         Span::styled(
                             format!("  {}{} ", cursor, branch),
                             if is_path_selected {
@@ -421,17 +492,58 @@ impl Menu {
                                 self.styles.muted_text
                             },
                         ),
-                        Span::styled(health.dot(), health_style),
+                        Span::styled(dot, health_style),
                         Span::styled(format!(" {}", path), path_style),
-                    ]));
+                    ];
+                    // History and latency only when they fit beside the path
+                    let used = 6 + path.chars().count();
+                    let history = self.health_history_spans(&key, budget);
+                    let history_width: usize = history.iter().map(|s| s.width()).sum();
+                    if used + history_width <= inner.width as usize {
+                        spans.extend(history);
+                    }
+                    lines.push(Line::from(spans));
                 }
             }
         }
     }
 
+    /// Mini history (oldest first) and average latency of an endpoint, with
+    /// the budget it keeps missing
+    fn health_history_spans(
+        &self,
+        key: &str,
+        missed_budget: Option<Duration>,
+    ) -> Vec<Span<'static>> {
+        let mut spans = vec![Span::raw(" ")];
+        for check in self.ingress_history.checks(key) {
+            spans.push(Span::styled("▪", self.health_style(check.status)));
+        }
+        if let Some(latency) = self.ingress_history.average_latency(key) {
+            let span = match missed_budget {
+                Some(budget) => Span::styled(
+                    format!(" {}ms > {}ms", latency.as_millis(), budget.as_millis()),
+                    self.styles.warning_text,
+                ),
+                None => Span::styled(
+                    format!(" {}ms", latency.as_millis()),
+                    self.styles.muted_text,
+                ),
+            };
+            spans.push(span);
+        }
+        if spans.len() == 1 {
+            spans.clear();
+        }
+        spans
+    }
+
     /// Render forwarded ports section
     fn render_ports_section(&self, lines: &mut Vec<Line>, inner: &Rect) {
-        if self.forwarded_ports.is_empty() && self.active_port_forwards.is_empty() {
+        if self.forwarded_ports.is_empty()
+            && self.active_port_forwards.is_empty()
+            && self.shared_ports.is_empty()
+        {
             return;
         }
 
@@ -474,6 +586,21 @@ impl Menu {
                 )));
             }
         }
+
+        // Add ports shared over SSH
+        if !self.shared_ports.is_empty() {
+            lines.push(Line::from(Span::styled(
+                "  🌐 Shared",
+                self.styles.muted_text,
+            )));
+            for shared in &self.shared_ports {
+                let port_text = format!("    {} → {}", shared.local_port, shared.remote);
+                lines.push(Line::from(Span::styled(
+                    port_text,
+                    self.styles.success_text,
+                )));
+            }
+        }
     }
 
     /// Render user-configured info blocks below the ports section.
@@ -509,11 +636,14 @@ impl Menu {
                     lines.push(Line::from(Span::styled("  —", self.styles.muted_text)));
                 }
                 _ => {
+                    let style = match block.thresholds.and_then(|t| t.judge(&block.output)) {
+                        Some(ThresholdLevel::Ok) => self.styles.success_text,
+                        Some(ThresholdLevel::Warn) => self.styles.warning_text,
+                        Some(ThresholdLevel::Critical) => self.styles.error_text,
+                        None => self.styles.normal_text,
+                    };
                     for line in block.output.lines() {
-                        lines.push(Line::from(Span::styled(
-                            format!("  {}", line),
-                            self.styles.normal_text,
-                        )));
+                        lines.push(Line::from(Span::styled(format!("  {}", line), style)));
                     }
                 }
             }
@@ -0,0 +1,212 @@
+//! Menu state kept across restarts
+//!
+//! Collapsed groups and nested entries, the selected entry and the scroll position are saved
+//! per config file in `<XDG_DATA_HOME>/k3dev/menu-state.json` when k3dev
+//! exits, and put back when the same config is loaded again.
+
+use std::collections::HashMap;
+use std::path::{Path, PathBuf};
+
+use anyhow::{Context, Result};
+use serde::{Deserialize, Serialize};
+
+use super::Menu;
+
+/// What the menu looked like when k3dev last exited
+#[derive(Debug, Clone, Default, PartialEq, Serialize, Deserialize)]
+pub struct MenuState {
+    /// Names of the collapsed groups
+    #[serde(default)]
+    pub collapsed_groups: Vec<String>,
+    /// Paths of the collapsed nested entries
+    #[serde(default)]
+    pub collapsed_entries: Vec<Vec<usize>>,
+    /// Path of the selected group or command
+    #[serde(default)]
+    pub selected: Vec<usize>,
+    #[serde(default)]
+    pub scroll_offset: usize,
+}
+
+impl MenuState {
+    /// State saved for a config file, if any
+    pub fn load(config_path: &Path) -> Option<MenuState> {
+        load_from(&state_file()?, config_path)
+    }
+
+    /// Save the state for a config file, keeping other configs' states
+    pub fn save(&self, config_path: &Path) -> Result<()> {
+        let file = state_file().context("No data directory to save the menu state in")?;
+        save_to(&file, config_path, self)
+    }
+}
+
+impl Menu {
+    pub fn state(&self) -> MenuState {
+        MenuState {
+            collapsed_groups: self
+                .items
+                .iter()
+                .zip(&self.expanded)
+                .filter(|(_, expanded)| !**expanded)
+                .map(|(group, _)| group.name.clone())
+                .collect(),
+            collapsed_entries: {
+                let mut paths: Vec<_> = self.collapsed_entries.iter().cloned().collect();
+                paths.sort();
+                paths
+            },
+            selected: self
+                .selected_item()
+                .map(|item| item.item_path.clone())
+                .unwrap_or_default(),
+            scroll_offset: self.scroll_offset.get(),
+        }
+    }
+
+    /// Put back a saved state; groups and entries that no longer exist are
+    /// ignored
+    pub fn restore_state(&mut self, state: &MenuState) {
+        self.expanded = self
+            .items
+            .iter()
+            .map(|group| !state.collapsed_groups.contains(&group.name))
+            .collect();
+        self.collapsed_entries = state.collapsed_entries.iter().cloned().collect();
+        self.rebuild_flat_items();
+        if let Some(index) = self
+            .flat_items
+            .iter()
+            .position(|item| item.item_path == state.selected)
+        {
+            self.selected_index = index;
+        }
+        self.scroll_offset.set(state.scroll_offset);
+    }
+}
+
+fn state_file() -> Option<PathBuf> {
+    dirs::data_local_dir().map(|d| d.join("k3dev").join("menu-state.json"))
+}
+
+/// States of every config, keyed by config path
+fn read_all(file: &Path) -> HashMap<String, MenuState> {
+    std::fs::read_to_string(file)
+        .ok()
+        .and_then(|content| serde_json::from_str(&content).ok())
+        .unwrap_or_default()
+}
+
+fn key(config_path: &Path) -> String {
+    config_path
+        .canonicalize()
+        .unwrap_or_else(|_| config_path.to_path_buf())
+        .display()
+        .to_string()
+}
+
+fn load_from(file: &Path, config_path: &Path) -> Option<MenuState> {
+    read_all(file).remove(&key(config_path))
+}
+
+fn save_to(file: &Path, config_path: &Path, state: &MenuState) -> Result<()> {
+    let mut all = read_all(file);
+    all.insert(key(config_path), state.clone());
+    if let Some(dir) = file.parent() {
+        std::fs::create_dir_all(dir)
+            .with_context(|| format!("Failed to create {}", dir.display()))?;
+    }
+    let content = serde_json::to_string_pretty(&all)?;
+    std::fs::write(file, content).with_context(|| format!("Failed to write {}", file.display()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::config::Config;
+
+    fn menu() -> Menu {
+        let config: Config = serde_yml::from_str(
+            r#"
+commands:
+  - name: Build
+    commands:
+      - name: api
+      - name: web
+  - name: Deploy
+    commands:
+      - name: all
+      - name: services
+        commands:
+          - name: api
+          - name: web
+"#,
+        )
+        .unwrap();
+        let mut menu = Menu::new();
+        menu.build_from_config(&config);
+        menu
+    }
+
+    #[test]
+    fn collapsed_groups_and_selection_are_restored() {
+        let mut menu = menu();
+        menu.toggle();
+        menu.move_down();
+        menu.move_down();
+        let state = menu.state();
+        assert_eq!(state.collapsed_groups, ["Build"]);
+        assert_eq!(state.selected, [1, 0]);
+
+        let mut restored = self::menu();
+        restored.restore_state(&state);
+        assert_eq!(restored.flat_items().len(), 6);
+        assert_eq!(restored.selected_item().unwrap().name, "all");
+    }
+
+    #[test]
+    fn nested_entries_collapse_on_their_own() {
+        let mut menu = menu();
+        menu.jump_to(&[1, 1]);
+        menu.toggle();
+        assert_eq!(menu.flat_items().len(), 6);
+        assert_eq!(menu.state().collapsed_entries, [vec![1, 1]]);
+
+        // Left on a child selects its parent entry
+        menu.expand();
+        menu.move_down();
+        menu.collapse();
+        assert_eq!(menu.selected_item().unwrap().item_path, [1, 1]);
+
+        menu.collapse_all();
+        assert_eq!(menu.flat_items().len(), 2);
+        assert_eq!(menu.selected_item().unwrap().name, "Deploy");
+        assert!(menu.jump_to(&[1, 1, 0]));
+        assert_eq!(menu.flat_items().len(), 6);
+
+        menu.collapse_all();
+        menu.expand_all();
+        assert_eq!(menu.flat_items().len(), 8);
+        assert_eq!(menu.selected_item().unwrap().name, "Deploy");
+    }
+
+    #[test]
+    fn states_are_kept_per_config() {
+        let dir = std::env::temp_dir().join(format!("k3dev-menu-state-{}", std::process::id()));
+        let file = dir.join("menu-state.json");
+        let first = MenuState {
+            collapsed_groups: vec!["Build".to_string()],
+            collapsed_entries: vec![vec![1, 0]],
+            selected: vec![1],
+            scroll_offset: 0,
+        };
+        save_to(&file, Path::new("/a/k3dev.yml"), &first).unwrap();
+        save_to(&file, Path::new("/b/k3dev.yml"), &MenuState::default()).unwrap();
+
+        let loaded = load_from(&file, Path::new("/a/k3dev.yml"));
+        let missing = load_from(&file, Path::new("/c/k3dev.yml"));
+        let _ = std::fs::remove_dir_all(&dir);
+        assert_eq!(loaded, Some(first));
+        assert_eq!(missing, None);
+    }
+}
@@ -1,24 +1,67 @@
 mod action_bar;
+mod breadcrumb_picker;
+mod build_view;
+mod cluster_info_view;
 mod command_palette;
+mod command_preview;
 mod confirm_popup;
+mod crash_view;
+mod dependency_map_view;
 mod diagnostics_overlay;
 mod help_overlay;
+mod hook_runs_view;
 mod input_form;
+mod k3s_version_view;
 mod menu;
+mod network_policy_view;
 mod output;
 mod output_popup;
+mod output_table;
 mod pod_detail_panel;
 mod pod_stats;
+mod pod_top_view;
+mod policy_view;
+mod pv_browser_view;
+mod quotas_view;
+mod rbac_view;
+mod refresh_tasks_view;
+mod runtime_view;
+mod services_view;
+mod setup_wizard;
 pub mod shell_view;
+mod snapshots_view;
+mod toast;
+mod transcript;
 
-pub use action_bar::{ActionBar, ClusterAction};
+pub use action_bar::{ActionBar, BarLink, ClusterAction, SPINNER_INTERVAL};
+pub use breadcrumb_picker::BreadcrumbPicker;
+pub use build_view::BuildView;
+pub use cluster_info_view::ClusterInfoView;
 pub use command_palette::CommandPalette;
+pub use command_preview::CommandPreview;
 pub use confirm_popup::ConfirmPopup;
+pub use crash_view::CrashView;
+pub use dependency_map_view::DependencyMapView;
 pub use diagnostics_overlay::DiagnosticsOverlay;
 pub use help_overlay::HelpOverlay;
+pub use hook_runs_view::HookRunsView;
 pub use input_form::InputForm;
-pub use menu::{ActivePortForward, InfoBlockView, Menu};
-pub use output::{Output, OutputLine, OutputType};
+pub use k3s_version_view::K3sVersionView;
+pub use menu::{ActivePortForward, InfoBlockView, JumpTarget, Menu, MenuState, SharedPort};
+pub use network_policy_view::NetworkPolicyView;
+pub use output::{Output, OutputLimit, OutputLine, OutputMirror, OutputType, DEFAULT_MAX_LINES};
 pub use output_popup::OutputPopup;
 pub use pod_detail_panel::{DetailTab, PodDetailPanel};
-pub use pod_stats::{ContainerPullInfo, PodStat, PodState, PodStats};
+pub use pod_stats::{ContainerPullInfo, PodSort, PodStat, PodState, PodStats, ResourceUsage};
+pub use pod_top_view::PodTopView;
+pub use policy_view::PolicyView;
+pub use pv_browser_view::PvBrowserView;
+pub use quotas_view::QuotasView;
+pub use rbac_view::RbacView;
+pub use refresh_tasks_view::RefreshTasksView;
+pub use runtime_view::RuntimeView;
+pub use services_view::ServicesView;
+pub use setup_wizard::{SetupWizard, WizardOutcome};
+pub use snapshots_view::SnapshotsView;
+pub use toast::ToastOverlay;
+pub use transcript::Transcript;
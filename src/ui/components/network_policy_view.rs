@@ -0,0 +1,304 @@
+//! NetworkPolicy overview
+//!
+//! Shows one namespace at a time: its policies, its workloads with their
+//! isolation, and a from → to matrix of which workloads may talk. Workloads
+//! nothing in the namespace can reach are flagged.
+
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::Modifier,
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Cell, Clear, Paragraph, Row, Table},
+    Frame,
+};
+
+use crate::k8s::NamespacePolicies;
+use crate::ui::styles::Styles;
+use crate::ui::theme::Theme;
+
+/// Widest workload name shown in the matrix
+const MAX_NAME_WIDTH: usize = 24;
+
+/// NetworkPolicy overlay
+pub struct NetworkPolicyView {
+    styles: Styles,
+    namespaces: Result<Vec<NamespacePolicies>, String>,
+    loaded: bool,
+    selected: usize,
+    /// Whether the cluster runs the network-policy controller
+    enforced: bool,
+}
+
+impl NetworkPolicyView {
+    pub fn new() -> Self {
+        Self::with_theme(Theme::default())
+    }
+
+    pub fn with_theme(theme: Theme) -> Self {
+        Self {
+            styles: Styles::from_theme(theme),
+            namespaces: Ok(Vec::new()),
+            loaded: false,
+            selected: 0,
+            enforced: false,
+        }
+    }
+
+    /// Forget the previous result before opening the view
+    pub fn reset(&mut self, enforced: bool) {
+        self.namespaces = Ok(Vec::new());
+        self.loaded = false;
+        self.selected = 0;
+        self.enforced = enforced;
+    }
+
+    /// Show new results; the first time, start at a namespace with policies
+    pub fn update(&mut self, namespaces: Result<Vec<NamespacePolicies>, String>) {
+        if let Ok(list) = &namespaces {
+            if !self.loaded {
+                self.selected = list
+                    .iter()
+                    .position(|ns| !ns.policies.is_empty())
+                    .unwrap_or(0);
+            }
+            self.selected = self.selected.min(list.len().saturating_sub(1));
+        }
+        self.namespaces = namespaces;
+        self.loaded = true;
+    }
+
+    pub fn next_namespace(&mut self) {
+        if let Ok(list) = &self.namespaces {
+            if !list.is_empty() {
+                self.selected = (self.selected + 1) % list.len();
+            }
+        }
+    }
+
+    pub fn prev_namespace(&mut self) {
+        if let Ok(list) = &self.namespaces {
+            if !list.is_empty() {
+                self.selected = (self.selected + list.len() - 1) % list.len();
+            }
+        }
+    }
+
+    pub fn render(&self, frame: &mut Frame, area: Rect) {
+        let popup_area = centered_rect(90, 85, area);
+        frame.render_widget(Clear, popup_area);
+
+        let current = self
+            .namespaces
+            .as_ref()
+            .ok()
+            .and_then(|list| list.get(self.selected).map(|ns| (ns, list.len())));
+        let title = match current {
+            Some((ns, count)) => format!(
+                " Network Policies · {} ({}/{}) ",
+                ns.namespace,
+                self.selected + 1,
+                count
+            ),
+            None => " Network Policies ".to_string(),
+        };
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Double)
+            .border_style(self.styles.border_focused)
+            .title(title)
+            .title_bottom(
+                Line::from(vec![
+                    Span::styled(" Esc close ", self.styles.muted_text),
+                    Span::styled(" h/l namespace ", self.styles.muted_text),
+                    Span::styled(" r refresh ", self.styles.muted_text),
+                ])
+                .right_aligned(),
+            );
+        let inner = block.inner(popup_area);
+        frame.render_widget(block, popup_area);
+
+        let banner_height = if self.enforced { 0 } else { 2 };
+        let rows = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(banner_height), Constraint::Min(0)])
+            .split(inner);
+        if !self.enforced {
+            let banner = Paragraph::new(Span::styled(
+                "  Policies are not enforced: set infrastructure.network_policy: true and recreate the cluster.",
+                self.styles.warning_text,
+            ));
+            frame.render_widget(banner, rows[0]);
+        }
+
+        let ns = match (&self.namespaces, current) {
+            (Err(e), _) => {
+                let msg = Paragraph::new(Span::styled(format!("  {}", e), self.styles.error_text));
+                frame.render_widget(msg, rows[1]);
+                return;
+            }
+            (Ok(_), Some((ns, _))) => ns,
+            (Ok(_), None) => {
+                let text = if self.loaded {
+                    "  No namespaces with pods or policies."
+                } else {
+                    "  Loading network policies..."
+                };
+                let msg = Paragraph::new(Span::styled(text, self.styles.muted_text));
+                frame.render_widget(msg, rows[1]);
+                return;
+            }
+        };
+
+        let policy_height = (ns.policies.len().max(1) as u16 + 3).min(10);
+        let sections = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(policy_height), Constraint::Min(0)])
+            .split(rows[1]);
+        self.render_policies(frame, sections[0], ns);
+        self.render_matrix(frame, sections[1], ns);
+    }
+
+    fn card(&self, title: &str) -> Block<'static> {
+        Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(self.styles.border_unfocused)
+            .title(Span::styled(format!(" {} ", title), self.styles.title))
+    }
+
+    fn render_policies(&self, frame: &mut Frame, area: Rect, ns: &NamespacePolicies) {
+        if ns.policies.is_empty() {
+            let msg = Paragraph::new(Span::styled(
+                "No policies: every workload accepts all traffic.",
+                self.styles.muted_text,
+            ))
+            .block(self.card("Policies"));
+            frame.render_widget(msg, area);
+            return;
+        }
+        let rows = ns.policies.iter().map(|p| {
+            Row::new(vec![
+                Cell::from(p.name.as_str()),
+                Cell::from(p.types.join(", ")),
+                Cell::from(p.selects.to_string()),
+            ])
+            .style(self.styles.normal_text)
+        });
+        let table = Table::new(
+            rows,
+            [
+                Constraint::Fill(1),
+                Constraint::Length(16),
+                Constraint::Length(9),
+            ],
+        )
+        .header(
+            Row::new(["NAME", "TYPES", "WORKLOADS"])
+                .style(self.styles.normal_text.add_modifier(Modifier::BOLD)),
+        )
+        .block(self.card("Policies"));
+        frame.render_widget(table, area);
+    }
+
+    fn render_matrix(&self, frame: &mut Frame, area: Rect, ns: &NamespacePolicies) {
+        let name_width = ns
+            .workloads
+            .iter()
+            .map(|w| w.name.chars().count())
+            .max()
+            .unwrap_or(0)
+            .min(MAX_NAME_WIDTH);
+
+        // Columns are numbered; rows carry the names
+        let mut header = vec![Cell::from(format!(
+            "{:>3} {:<w$}",
+            "",
+            "from ↓ / to →",
+            w = name_width
+        ))];
+        header.extend((1..=ns.workloads.len()).map(|i| Cell::from(format!("{:>3}", i))));
+        header.push(Cell::from("  ISOLATION"));
+
+        let rows = ns.workloads.iter().enumerate().map(|(from, w)| {
+            let name: String = w.name.chars().take(name_width).collect();
+            let name_style = if ns.unreachable(from) {
+                self.styles.error_text
+            } else {
+                self.styles.normal_text
+            };
+            let mut cells = vec![Cell::from(Span::styled(
+                format!("{:>3} {:<w$}", from + 1, name, w = name_width),
+                name_style,
+            ))];
+            cells.extend(ns.allowed[from].iter().map(|&allowed| {
+                if allowed {
+                    Cell::from(Span::styled("  ✓", self.styles.success_text))
+                } else {
+                    Cell::from(Span::styled("  ·", self.styles.muted_text))
+                }
+            }));
+            cells.push(Cell::from(self.isolation_line(ns, from)));
+            Row::new(cells)
+        });
+
+        let mut widths = vec![Constraint::Length(name_width as u16 + 4)];
+        widths.extend(ns.workloads.iter().map(|_| Constraint::Length(3)));
+        widths.push(Constraint::Fill(1));
+        let table = Table::new(rows, widths)
+            .header(Row::new(header).style(self.styles.normal_text.add_modifier(Modifier::BOLD)))
+            .block(self.card("Connectivity"));
+        frame.render_widget(table, area);
+    }
+
+    fn isolation_line(&self, ns: &NamespacePolicies, index: usize) -> Line<'static> {
+        let w = &ns.workloads[index];
+        let mut parts = Vec::new();
+        if w.ingress_isolated {
+            parts.push("ingress");
+        }
+        if w.egress_isolated {
+            parts.push("egress");
+        }
+        if ns.unreachable(index) {
+            return Line::from(Span::styled(
+                format!("  {} · unreachable", parts.join(" + ")),
+                self.styles.error_text,
+            ));
+        }
+        if parts.is_empty() {
+            Line::from(Span::styled("  open", self.styles.muted_text))
+        } else {
+            Line::from(Span::styled(
+                format!("  {}", parts.join(" + ")),
+                self.styles.warning_text,
+            ))
+        }
+    }
+}
+
+impl Default for NetworkPolicyView {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Helper to create a centered rect
+fn centered_rect(percent_x: u16, percent_y: u16, area: Rect) -> Rect {
+    let popup_layout = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Percentage((100 - percent_y) / 2),
+            Constraint::Percentage(percent_y),
+            Constraint::Percentage((100 - percent_y) / 2),
+        ])
+        .split(area);
+
+    Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Percentage((100 - percent_x) / 2),
+            Constraint::Percentage(percent_x),
+            Constraint::Percentage((100 - percent_x) / 2),
+        ])
+        .split(popup_layout[1])[1]
+}
@@ -1,7 +1,24 @@
+//! Output lines and the size-limited buffers that hold them
+//!
+//! A command may print millions of lines; buffers keep the newest
+//! `max_lines` and count what they dropped, optionally appending the dropped
+//! lines to a spill file so the full output survives on disk.
+
+use std::collections::VecDeque;
+use std::fs::{File, OpenOptions};
+use std::io::{BufWriter, Write};
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+
 use chrono::Local;
+use tokio::sync::broadcast;
 
+use super::transcript::Transcript;
 use crate::ui::theme::Theme;
 
+/// Lines a buffer keeps unless configured otherwise
+pub const DEFAULT_MAX_LINES: usize = 10_000;
+
 /// Output line type for coloring
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum OutputType {
@@ -9,6 +26,8 @@ pub enum OutputType {
     Success,
     Error,
     Warning,
+    /// What to do about the error above it
+    Hint,
 }
 
 /// A single line of output
@@ -51,13 +70,212 @@ impl OutputLine {
             timestamp: Local::now(),
         }
     }
+
+    pub fn hint(content: impl Into<String>) -> Self {
+        Self {
+            content: content.into(),
+            output_type: OutputType::Hint,
+            timestamp: Local::now(),
+        }
+    }
+}
+
+impl AsRef<str> for OutputLine {
+    fn as_ref(&self) -> &str {
+        &self.content
+    }
+}
+
+impl OutputLine {
+    /// The line as written to spill files and transcripts
+    pub fn to_text(&self) -> String {
+        format!("{} {}", self.timestamp.format("[%H:%M:%S]"), self.content)
+    }
+}
+
+/// How much output a buffer keeps
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OutputLimit {
+    pub max_lines: usize,
+    /// File the dropped lines are appended to
+    pub spill: Option<PathBuf>,
+}
+
+impl Default for OutputLimit {
+    fn default() -> Self {
+        Self {
+            max_lines: DEFAULT_MAX_LINES,
+            spill: None,
+        }
+    }
+}
+
+/// Output lines, the oldest dropped once there are more than the limit.
+///
+/// Lines are dropped an eighth of the limit at a time, so adding a line
+/// stays cheap however much a command prints.
+#[derive(Debug, Default)]
+pub struct OutputBuffer {
+    lines: Vec<OutputLine>,
+    limit: OutputLimit,
+    truncated: usize,
+    /// Opened when the first line is dropped
+    spill: Option<BufWriter<File>>,
+    /// Where the dropped lines can be read, shown in the truncation notice
+    spilled_to: Option<PathBuf>,
+}
+
+impl OutputBuffer {
+    pub fn with_limit(limit: OutputLimit) -> Self {
+        Self {
+            spilled_to: limit.spill.clone(),
+            limit,
+            ..Default::default()
+        }
+    }
+
+    /// Replace the limit; a changed spill file is used for the next lines
+    /// dropped, the count of those already dropped stays
+    pub fn set_limit(&mut self, limit: OutputLimit) {
+        if limit.spill != self.limit.spill {
+            self.spill = None;
+            self.spilled_to = limit.spill.clone();
+        }
+        self.limit = limit;
+    }
+
+    pub fn lines(&self) -> &[OutputLine] {
+        &self.lines
+    }
+
+    /// Add a line; returns how many old lines were dropped to make room
+    pub fn push(&mut self, line: OutputLine) -> usize {
+        self.lines.push(line);
+        let max = self.limit.max_lines.max(1);
+        if self.lines.len() <= max {
+            return 0;
+        }
+        let dropped = self.lines.len() - (max - max / 8);
+        self.spill_lines(dropped);
+        self.lines.drain(..dropped);
+        self.truncated += dropped;
+        dropped
+    }
+
+    /// Drop every line; the limit stays and a new spill file is started
+    pub fn clear(&mut self) {
+        *self = Self::with_limit(self.limit.clone());
+    }
+
+    /// A copy for display: same lines and counts, never writes the spill file
+    pub fn view(&self) -> Self {
+        Self {
+            lines: self.lines.clone(),
+            limit: OutputLimit {
+                max_lines: self.limit.max_lines,
+                spill: None,
+            },
+            truncated: self.truncated,
+            spill: None,
+            spilled_to: self.spilled_to.clone(),
+        }
+    }
+
+    /// "… 1200 earlier lines truncated" once lines were dropped
+    pub fn truncation_notice(&self) -> Option<String> {
+        if self.truncated == 0 {
+            return None;
+        }
+        let mut notice = format!("… {} earlier lines truncated", self.truncated);
+        if let Some(path) = &self.spilled_to {
+            notice.push_str(&format!(" (full output in {})", path.display()));
+        }
+        Some(notice)
+    }
+
+    /// Append the first `count` lines to the spill file, giving up on it
+    /// after the first error
+    fn spill_lines(&mut self, count: usize) {
+        let Some(path) = &self.limit.spill else {
+            return;
+        };
+        let result = (|| {
+            if self.spill.is_none() {
+                if let Some(dir) = path.parent() {
+                    std::fs::create_dir_all(dir)?;
+                }
+                let file = OpenOptions::new().create(true).append(true).open(path)?;
+                self.spill = Some(BufWriter::new(file));
+            }
+            let writer = self.spill.as_mut().expect("spill file was just opened");
+            for line in &self.lines[..count] {
+                writeln!(writer, "{}", line.to_text())?;
+            }
+            writer.flush()
+        })();
+        if let Err(e) = result {
+            tracing::warn!(path = %path.display(), error = %e, "Failed to write output spill file");
+            self.limit.spill = None;
+            self.spill = None;
+            self.spilled_to = None;
+        }
+    }
+}
+
+/// Lines a mirror replays to a terminal that attaches late
+const MIRROR_BACKLOG: usize = 200;
+
+/// Copies of the output for `k3dev attach`: the latest lines and a feed of
+/// the ones that follow
+#[derive(Debug, Clone)]
+pub struct OutputMirror {
+    feed: broadcast::Sender<OutputLine>,
+    recent: Arc<Mutex<VecDeque<OutputLine>>>,
+}
+
+impl OutputMirror {
+    pub fn new() -> Self {
+        Self {
+            feed: broadcast::channel(MIRROR_BACKLOG).0,
+            recent: Arc::default(),
+        }
+    }
+
+    pub fn send(&self, line: OutputLine) {
+        if let Ok(mut recent) = self.recent.lock() {
+            if recent.len() == MIRROR_BACKLOG {
+                recent.pop_front();
+            }
+            recent.push_back(line.clone());
+            // Under the lock, so a subscriber gets every line exactly once
+            let _ = self.feed.send(line);
+        }
+    }
+
+    /// The latest lines, and a receiver for every line after them
+    pub fn subscribe(&self) -> (Vec<OutputLine>, broadcast::Receiver<OutputLine>) {
+        match self.recent.lock() {
+            Ok(recent) => (recent.iter().cloned().collect(), self.feed.subscribe()),
+            Err(_) => (Vec::new(), self.feed.subscribe()),
+        }
+    }
+}
+
+impl Default for OutputMirror {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 /// Output panel component (used as internal buffer, rendering done via OutputPopup)
 pub struct Output {
     title: String,
-    lines: Vec<OutputLine>,
+    lines: OutputBuffer,
     scroll_position: usize,
+    /// Where every line and action of the session is recorded
+    transcript: Option<Transcript>,
+    /// Where attached terminals read the output
+    mirror: Option<OutputMirror>,
 }
 
 impl Output {
@@ -68,8 +286,38 @@ impl Output {
     pub fn with_theme(_theme: Theme) -> Self {
         Self {
             title: "Output".to_string(),
-            lines: Vec::new(),
+            lines: OutputBuffer::default(),
             scroll_position: 0,
+            transcript: None,
+            mirror: None,
+        }
+    }
+
+    /// Record the rest of the session's output to `transcript`
+    pub fn record_to(&mut self, transcript: Transcript) {
+        self.transcript = Some(transcript);
+    }
+
+    /// Copy the rest of the session's output to `mirror`
+    pub fn mirror_to(&mut self, mirror: OutputMirror) {
+        self.mirror = Some(mirror);
+    }
+
+    /// Note an action the user started in the transcript and the mirror
+    pub fn record_action(&mut self, title: &str) {
+        if let Some(mirror) = &self.mirror {
+            mirror.send(OutputLine::info(format!("▶ {}", title)));
+        }
+        if let Some(transcript) = &mut self.transcript {
+            if let Err(e) = transcript.action(title) {
+                self.stop_recording(e);
+            }
+        }
+    }
+
+    fn stop_recording(&mut self, error: std::io::Error) {
+        if let Some(transcript) = self.transcript.take() {
+            tracing::warn!(path = %transcript.path().display(), error = %error, "Failed to write transcript");
         }
     }
 
@@ -77,14 +325,38 @@ impl Output {
         self.title = title.into();
     }
 
+    pub fn title(&self) -> &str {
+        &self.title
+    }
+
+    pub fn lines(&self) -> &[OutputLine] {
+        self.lines.lines()
+    }
+
+    pub fn buffer(&self) -> &OutputBuffer {
+        &self.lines
+    }
+
+    pub fn set_limit(&mut self, limit: OutputLimit) {
+        self.lines.set_limit(limit);
+    }
+
     pub fn clear(&mut self) {
         self.lines.clear();
         self.scroll_position = 0;
     }
 
     pub fn add_line(&mut self, line: OutputLine) {
+        if let Some(mirror) = &self.mirror {
+            mirror.send(line.clone());
+        }
+        if let Some(transcript) = &mut self.transcript {
+            if let Err(e) = transcript.line(&line) {
+                self.stop_recording(e);
+            }
+        }
         self.lines.push(line);
-        self.scroll_position = self.lines.len();
+        self.scroll_position = self.lines.lines().len();
     }
 
     pub fn add_info(&mut self, content: impl Into<String>) {
@@ -109,3 +381,54 @@ impl Default for Output {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn limit(max_lines: usize, spill: Option<PathBuf>) -> OutputLimit {
+        OutputLimit { max_lines, spill }
+    }
+
+    #[test]
+    fn oldest_lines_are_dropped_in_chunks() {
+        let mut buffer = OutputBuffer::with_limit(limit(16, None));
+        for i in 0..16 {
+            assert_eq!(buffer.push(OutputLine::info(i.to_string())), 0);
+        }
+        assert!(buffer.truncation_notice().is_none());
+
+        assert_eq!(buffer.push(OutputLine::info("16")), 3);
+        assert_eq!(buffer.lines().len(), 14);
+        assert_eq!(buffer.lines()[0].content, "3");
+        assert_eq!(
+            buffer.truncation_notice().as_deref(),
+            Some("… 3 earlier lines truncated")
+        );
+
+        buffer.clear();
+        assert_eq!(buffer.truncated, 0);
+        assert_eq!(buffer.limit.max_lines, 16);
+    }
+
+    #[test]
+    fn dropped_lines_are_spilled_to_a_file() {
+        let path = std::env::temp_dir().join(format!("k3dev-spill-{}.log", std::process::id()));
+        let _ = std::fs::remove_file(&path);
+        let mut buffer = OutputBuffer::with_limit(limit(2, Some(path.clone())));
+        for line in ["a", "b", "c", "d"] {
+            buffer.push(OutputLine::info(line));
+        }
+        let view = buffer.view();
+        assert_eq!(view.lines().len(), 2);
+        assert!(view
+            .truncation_notice()
+            .unwrap()
+            .ends_with(&format!("(full output in {})", path.display())));
+
+        let spilled = std::fs::read_to_string(&path).unwrap();
+        let contents: Vec<&str> = spilled.lines().map(|l| &l[11..]).collect();
+        assert_eq!(contents, ["a", "b"]);
+        let _ = std::fs::remove_file(&path);
+    }
+}
@@ -1,24 +1,84 @@
 //! Output popup component for displaying command output
 //!
 //! Renders a centered modal popup showing command output with scrolling support.
+//! Column-aligned listings are shown as sortable tables; the raw text view
+//! keeps the original lines for copying. Once the output outgrows its limit,
+//! a notice above the lines says how many were dropped.
+//!
+//! Only the lines in view are turned into widgets. Tables are detected and
+//! wrapped heights measured as lines arrive, so a frame costs the same with
+//! ten lines of output as with ten thousand.
 
 use ratatui::{
     layout::{Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
     text::{Line, Span},
-    widgets::{Block, BorderType, Borders, Clear, Paragraph, Wrap},
+    widgets::{Block, BorderType, Borders, Cell, Clear, Paragraph, Row, Table, Wrap},
     Frame,
 };
+use std::cell::{Cell as StdCell, Ref, RefCell};
+use std::collections::HashMap;
 
 use crate::ui::styles::Styles;
 use crate::ui::theme::Theme;
 
-use super::output::{OutputLine, OutputType};
+use super::output::{OutputBuffer, OutputLimit, OutputLine, OutputType};
+use super::output_table::{extend_tables, TableBlock, TableSort};
+
+/// Width of the "[HH:MM:SS] " prefix on text lines
+const TIMESTAMP_WIDTH: usize = 11;
+
+/// Tables detected in the first `scanned` lines
+#[derive(Default)]
+struct TableCache {
+    blocks: Vec<TableBlock>,
+    scanned: usize,
+}
+
+/// Rows each line wraps to at `width`, measured once per line
+#[derive(Default)]
+struct LineMetrics {
+    width: u16,
+    rows: Vec<usize>,
+}
+
+impl LineMetrics {
+    /// Measure the lines added since the last call; all of them again when
+    /// the width changed
+    fn update(&mut self, lines: &[OutputLine], width: u16) {
+        if width != self.width || self.rows.len() > lines.len() {
+            self.width = width;
+            self.rows.clear();
+        }
+        let width = width.max(1) as usize;
+        let measured = self.rows.len();
+        self.rows.extend(lines[measured..].iter().map(|l| {
+            let chars = TIMESTAMP_WIDTH + l.content.chars().count();
+            chars.div_ceil(width).max(1)
+        }));
+    }
+}
 
 /// A centered popup for displaying command output
 pub struct OutputPopup {
     title: String,
-    lines: Vec<OutputLine>,
+    buffer: OutputBuffer,
+    /// Limit the buffer gets back on `clear`
+    limit: OutputLimit,
     scroll_position: usize,
+    /// Extra key hints shown before the default ones (e.g. "[a] Apply")
+    actions: Option<String>,
+    /// Tables detected in the buffer, brought up to date lazily
+    tables: RefCell<TableCache>,
+    metrics: RefCell<LineMetrics>,
+    /// First line of the last page at the last render
+    bottom_start: StdCell<usize>,
+    /// Sort state per table, keyed by the table's header line
+    sorts: HashMap<usize, TableSort>,
+    /// Show the original text instead of tables
+    raw: bool,
+    /// Fill the whole frame instead of a centered box
+    zoomed: bool,
     styles: Styles,
 }
 
@@ -30,8 +90,16 @@ impl OutputPopup {
     pub fn with_theme(theme: Theme) -> Self {
         Self {
             title: "Output".to_string(),
-            lines: Vec::new(),
+            buffer: OutputBuffer::default(),
+            limit: OutputLimit::default(),
             scroll_position: 0,
+            actions: None,
+            tables: RefCell::default(),
+            metrics: RefCell::default(),
+            bottom_start: StdCell::new(0),
+            sorts: HashMap::new(),
+            raw: false,
+            zoomed: false,
             styles: Styles::from_theme(theme),
         }
     }
@@ -40,36 +108,192 @@ impl OutputPopup {
         self.title = title.into();
     }
 
+    /// Switch between the centered box and the whole frame
+    pub fn toggle_zoom(&mut self) {
+        self.zoomed = !self.zoomed;
+    }
+
+    pub fn set_actions(&mut self, actions: Option<String>) {
+        self.actions = actions;
+    }
+
+    /// Limit for output that isn't a job's; applies from the next `clear`
+    pub fn set_limit(&mut self, limit: OutputLimit) {
+        self.limit = limit;
+    }
+
     pub fn clear(&mut self) {
-        self.lines.clear();
+        self.buffer = OutputBuffer::with_limit(self.limit.clone());
         self.scroll_position = 0;
+        self.forget_layout();
+        self.sorts.clear();
+        self.raw = false;
+    }
+
+    /// Show a copy of a job's output, which keeps the job's limit
+    pub fn show_buffer(&mut self, buffer: &OutputBuffer) {
+        self.clear();
+        self.buffer = buffer.view();
     }
 
     pub fn add_line(&mut self, line: OutputLine) {
-        self.lines.push(line);
+        let dropped = self.buffer.push(line);
+        if dropped > 0 {
+            // Tables and metrics are keyed by line index, which just shifted
+            self.forget_layout();
+            self.sorts.clear();
+        }
         self.scroll_to_bottom_if_at_end();
     }
 
-    pub fn scroll_up(&mut self) {
-        if self.scroll_position > 0 {
-            self.scroll_position -= 1;
+    fn lines(&self) -> &[OutputLine] {
+        self.buffer.lines()
+    }
+
+    fn forget_layout(&self) {
+        self.tables.take();
+        self.metrics.take();
+    }
+
+    fn tables(&self) -> Ref<'_, [TableBlock]> {
+        let scanned = self.tables.borrow().scanned;
+        let lines = self.lines();
+        if scanned < lines.len() {
+            let mut cache = self.tables.borrow_mut();
+            extend_tables(&mut cache.blocks, lines, scanned);
+            cache.scanned = lines.len();
+        }
+        Ref::map(self.tables.borrow(), |t| t.blocks.as_slice())
+    }
+
+    /// Whether the output contains column-aligned tables
+    pub fn has_tables(&self) -> bool {
+        !self.tables().is_empty()
+    }
+
+    /// Switch between the table view and the original text
+    pub fn toggle_raw(&mut self) {
+        self.raw = !self.raw;
+    }
+
+    /// The table at or below the top of the view, which sort keys act on
+    fn active_table(&self) -> Option<(usize, usize)> {
+        let top = self
+            .scroll_position
+            .min(self.lines().len().saturating_sub(1));
+        let tables = self.tables();
+        tables
+            .iter()
+            .find(|t| t.end() > top)
+            .or(tables.last())
+            .map(|t| (t.start, t.headers.len()))
+    }
+
+    /// Sort the active table by the next column, ending unsorted after the last
+    pub fn cycle_sort(&mut self) {
+        if self.raw {
+            return;
         }
+        let Some((start, columns)) = self.active_table() else {
+            return;
+        };
+        let next = match self.sorts.get(&start) {
+            None => Some(0),
+            Some(sort) if sort.column + 1 < columns => Some(sort.column + 1),
+            Some(_) => None,
+        };
+        match next {
+            Some(column) => self.sorts.insert(
+                start,
+                TableSort {
+                    column,
+                    descending: false,
+                },
+            ),
+            None => self.sorts.remove(&start),
+        };
     }
 
-    pub fn scroll_down(&mut self, visible_lines: usize) {
-        let max_scroll = self.lines.len().saturating_sub(visible_lines);
-        if self.scroll_position < max_scroll {
+    /// Reverse the active table's sort direction
+    pub fn reverse_sort(&mut self) {
+        if self.raw {
+            return;
+        }
+        let Some((start, _)) = self.active_table() else {
+            return;
+        };
+        self.sorts
+            .entry(start)
+            .and_modify(|sort| sort.descending = !sort.descending)
+            .or_insert(TableSort {
+                column: 0,
+                descending: true,
+            });
+    }
+
+    pub fn scroll_up(&mut self) {
+        self.scroll_position = self
+            .scroll_position
+            .min(self.bottom_start.get())
+            .saturating_sub(1);
+    }
+
+    pub fn scroll_down(&mut self) {
+        if self.scroll_position < self.bottom_start.get() {
             self.scroll_position += 1;
         }
     }
 
+    /// Lines `[start, end)` that fill `height` rows of `width`: from the
+    /// scroll position, or the last page when scrolled past it. Only the
+    /// lines near the view are looked at.
+    fn window(&self, width: u16, height: usize) -> (usize, usize) {
+        let lines = self.lines();
+        let tables = self.tables();
+        let mut metrics = self.metrics.borrow_mut();
+        metrics.update(lines, width);
+        // Table lines take one row each, text lines wrap
+        let rows = |i: usize| {
+            let in_table = !self.raw && {
+                let next = tables.partition_point(|t| t.end() <= i);
+                tables.get(next).is_some_and(|t| t.contains(i))
+            };
+            if in_table {
+                1
+            } else {
+                metrics.rows[i]
+            }
+        };
+
+        let mut bottom = lines.len();
+        let mut used = 0;
+        while bottom > 0 && used + rows(bottom - 1) <= height {
+            used += rows(bottom - 1);
+            bottom -= 1;
+        }
+        // A last line taller than the view still shows its start
+        if bottom == lines.len() {
+            bottom = bottom.saturating_sub(1);
+        }
+        self.bottom_start.set(bottom);
+
+        let start = self.scroll_position.min(bottom);
+        let mut end = start;
+        let mut used = 0;
+        while end < lines.len() && used < height {
+            used += rows(end);
+            end += 1;
+        }
+        (start, end)
+    }
+
     fn scroll_to_bottom_if_at_end(&mut self) {
         // Auto-scroll only if we're already near the bottom
-        self.scroll_position = self.lines.len();
+        self.scroll_position = self.lines().len();
     }
 
     pub fn scroll_to_bottom(&mut self) {
-        self.scroll_position = self.lines.len();
+        self.scroll_position = self.lines().len();
     }
 
     /// Create a centered rectangle for the popup
@@ -95,7 +319,11 @@ impl OutputPopup {
 
     pub fn render(&self, frame: &mut Frame, area: Rect) {
         // Create centered popup area (70% width, 60% height)
-        let popup_area = Self::centered_rect(70, 60, area);
+        let popup_area = if self.zoomed {
+            area
+        } else {
+            Self::centered_rect(70, 60, area)
+        };
 
         // Clear the background
         frame.render_widget(Clear, popup_area);
@@ -109,10 +337,46 @@ impl OutputPopup {
 
         let inner = block.inner(popup_area);
         frame.render_widget(block, popup_area);
+        self.render_body(frame, inner, true);
+    }
+
+    /// Render as a panel of the main screen, following the newest lines.
+    /// The popup opens on the same output for scrolling and sorting.
+    pub fn render_docked(&self, frame: &mut Frame, area: Rect) {
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(self.styles.border_unfocused)
+            .title(Span::styled(
+                format!(" {} ", self.title),
+                self.styles.normal_text,
+            ));
 
-        // Reserve space for hint at bottom
-        let content_height = inner.height.saturating_sub(2);
-        let content_area = Rect::new(inner.x, inner.y, inner.width, content_height);
+        let inner = block.inner(area);
+        frame.render_widget(block, area);
+        self.render_body(frame, inner, false);
+    }
+
+    /// Lines, truncation notice and, in the popup, the key hints
+    fn render_body(&self, frame: &mut Frame, inner: Rect, with_hint: bool) {
+        // Reserve space for the truncation notice at the top and hint at bottom
+        let notice = self.buffer.truncation_notice();
+        let notice_height = u16::from(notice.is_some());
+        if let Some(notice) = notice {
+            let notice_area = Rect::new(inner.x, inner.y, inner.width, 1);
+            frame.render_widget(
+                Paragraph::new(Span::styled(notice, self.styles.muted_text)),
+                notice_area,
+            );
+        }
+        let hint_height = if with_hint { 2 } else { 0 };
+        let content_height = inner.height.saturating_sub(hint_height + notice_height);
+        let content_area = Rect::new(
+            inner.x,
+            inner.y + notice_height,
+            inner.width,
+            content_height,
+        );
         let hint_area = Rect::new(
             inner.x,
             inner.y + inner.height.saturating_sub(1),
@@ -120,40 +384,15 @@ impl OutputPopup {
             1,
         );
 
-        let visible_lines = content_area.height as usize;
-
-        // Adjust scroll position
-        let scroll_pos = if self.scroll_position > self.lines.len().saturating_sub(visible_lines) {
-            self.lines.len().saturating_sub(visible_lines)
+        let (start, end) = self.window(content_area.width, content_area.height as usize);
+        if self.raw {
+            self.render_text(frame, content_area, &self.lines()[start..end]);
         } else {
-            self.scroll_position
-        };
-
-        let end = (scroll_pos + visible_lines).min(self.lines.len());
-
-        // Build lines for rendering
-        let text_lines: Vec<Line> = self.lines[scroll_pos..end]
-            .iter()
-            .map(|line| {
-                let style = match line.output_type {
-                    OutputType::Info => self.styles.normal_text,
-                    OutputType::Success => self.styles.success_text,
-                    OutputType::Error => self.styles.error_text,
-                    OutputType::Warning => self.styles.warning_text,
-                };
-                let timestamp = line.timestamp.format("[%H:%M:%S]").to_string();
-                Line::from(vec![
-                    Span::styled(format!("{} ", timestamp), self.styles.muted_text),
-                    Span::styled(&line.content, style),
-                ])
-            })
-            .collect();
-
-        let paragraph = Paragraph::new(text_lines).wrap(Wrap { trim: false });
-        frame.render_widget(paragraph, content_area);
+            self.render_segments(frame, content_area, start, end);
+        }
 
         // Scroll indicator if there's more content below
-        if self.lines.len() > visible_lines && scroll_pos + visible_lines < self.lines.len() {
+        if end < self.lines().len() {
             let indicator_area = Rect::new(
                 content_area.x,
                 content_area.y + content_area.height.saturating_sub(1),
@@ -166,18 +405,200 @@ impl OutputPopup {
             frame.render_widget(indicator, indicator_area);
         }
 
+        if !with_hint {
+            return;
+        }
+
         // Render hint
-        let hint = Paragraph::new(Line::from(Span::styled(
+        let mut hint_spans = Vec::new();
+        if let Some(actions) = &self.actions {
+            hint_spans.push(Span::styled(
+                format!("{}  ", actions),
+                self.styles.info_text,
+            ));
+        }
+        if self.has_tables() {
+            hint_spans.push(Span::styled(
+                if self.raw {
+                    "[t] Table  "
+                } else {
+                    "[s/S] Sort  [t] Raw text  "
+                },
+                self.styles.muted_text,
+            ));
+        }
+        hint_spans.push(Span::styled(
             "[↑/k] Up  [↓/j] Down  [Esc/Enter] Close",
             self.styles.muted_text,
-        )))
-        .centered();
+        ));
+        let hint = Paragraph::new(Line::from(hint_spans)).centered();
         frame.render_widget(hint, hint_area);
     }
 }
 
+impl OutputPopup {
+    /// Render lines `[start, end)` with detected tables drawn as tables and
+    /// everything else as text
+    fn render_segments(&self, frame: &mut Frame, area: Rect, start: usize, end: usize) {
+        let tables = self.tables();
+        let metrics = self.metrics.borrow();
+        let mut y = area.y;
+        let mut i = start;
+        while i < end && y < area.bottom() {
+            let remaining = area.bottom() - y;
+            let next = tables.partition_point(|t| t.end() <= i);
+            match tables.get(next).filter(|t| t.contains(i)) {
+                Some(table) => {
+                    let to = table.end().min(end);
+                    let height = ((to - i) as u16).min(remaining);
+                    let rect = Rect::new(area.x, y, area.width, height);
+                    self.render_table(frame, rect, table, i - table.start, to - table.start);
+                    y += height;
+                    i = to;
+                }
+                None => {
+                    let to = tables.get(next).map_or(end, |t| t.start).min(end);
+                    let wrapped: usize = metrics.rows[i..to].iter().sum();
+                    let height = (wrapped.min(u16::MAX as usize) as u16).min(remaining);
+                    let lines = &self.lines()[i..to];
+                    self.render_text(frame, Rect::new(area.x, y, area.width, height), lines);
+                    y += height;
+                    i = to;
+                }
+            }
+        }
+    }
+
+    fn render_text(&self, frame: &mut Frame, area: Rect, lines: &[OutputLine]) {
+        let text_lines: Vec<Line> = lines
+            .iter()
+            .map(|line| {
+                let timestamp = line.timestamp.format("[%H:%M:%S]").to_string();
+                Line::from(vec![
+                    Span::styled(format!("{} ", timestamp), self.styles.muted_text),
+                    Span::styled(&line.content, self.line_style(line)),
+                ])
+            })
+            .collect();
+
+        let paragraph = Paragraph::new(text_lines).wrap(Wrap { trim: false });
+        frame.render_widget(paragraph, area);
+    }
+
+    /// Render the part of a table between offsets `[from, to)`, where offset 0
+    /// is the header and offset `n` is the n-th row in display order
+    fn render_table(
+        &self,
+        frame: &mut Frame,
+        area: Rect,
+        table: &TableBlock,
+        from: usize,
+        to: usize,
+    ) {
+        let sort = self.sorts.get(&table.start).copied();
+        let order = table.sorted_order(sort);
+
+        let headers: Vec<String> = table
+            .headers
+            .iter()
+            .enumerate()
+            .map(|(col, name)| match sort {
+                Some(s) if s.column == col => {
+                    format!("{} {}", name, if s.descending { "▼" } else { "▲" })
+                }
+                _ => name.clone(),
+            })
+            .collect();
+
+        // Widths cover every row so columns do not shift while scrolling
+        let widths: Vec<Constraint> = headers
+            .iter()
+            .zip(&table.widths)
+            .map(|(header, &width)| Constraint::Length(width.max(header.chars().count()) as u16))
+            .collect();
+
+        let rows: Vec<Row> = order[from.saturating_sub(1)..to - 1]
+            .iter()
+            .map(|&r| {
+                let style = self.line_style(&self.lines()[table.start + 1 + r]);
+                Row::new(table.rows[r].iter().map(|c| Cell::from(c.as_str()))).style(style)
+            })
+            .collect();
+
+        let mut widget = Table::new(rows, widths).column_spacing(2);
+        if from == 0 {
+            let header_style = self.styles.normal_text.add_modifier(Modifier::BOLD);
+            widget = widget.header(Row::new(headers).style(header_style));
+        }
+        frame.render_widget(widget, area);
+    }
+
+    fn line_style(&self, line: &OutputLine) -> Style {
+        match line.output_type {
+            OutputType::Info => self.styles.normal_text,
+            OutputType::Success => self.styles.success_text,
+            OutputType::Error => self.styles.error_text,
+            OutputType::Warning => self.styles.warning_text,
+            OutputType::Hint => self.styles.info_text.add_modifier(Modifier::ITALIC),
+        }
+    }
+}
+
 impl Default for OutputPopup {
     fn default() -> Self {
         Self::new()
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn popup(lines: &[&str]) -> OutputPopup {
+        let mut popup = OutputPopup::new();
+        for line in lines {
+            popup.add_line(OutputLine::info(*line));
+        }
+        popup
+    }
+
+    #[test]
+    fn sort_cycles_through_columns_of_active_table() {
+        let mut popup = popup(&["NAME   STATUS", "b      Ready", "a      NotReady"]);
+        assert!(popup.has_tables());
+
+        popup.cycle_sort();
+        assert_eq!(popup.sorts[&0].column, 0);
+        popup.reverse_sort();
+        assert!(popup.sorts[&0].descending);
+        popup.cycle_sort();
+        assert_eq!(popup.sorts[&0].column, 1);
+        popup.cycle_sort();
+        assert!(popup.sorts.is_empty());
+    }
+
+    #[test]
+    fn window_fills_the_view_with_wrapped_lines() {
+        let mut popup = popup(&["0", "1", "2", "3", "4", "5"]);
+        assert_eq!(popup.window(100, 4), (2, 6));
+        popup.scroll_up();
+        assert_eq!(popup.window(100, 4), (1, 5));
+        popup.scroll_down();
+        popup.scroll_down();
+        assert_eq!(popup.window(100, 4), (2, 6));
+
+        // 11 + 200 chars wrap to three rows at width 100
+        popup.add_line(OutputLine::info("x".repeat(200)));
+        assert_eq!(popup.window(100, 4), (5, 7));
+        assert_eq!(popup.window(300, 4), (3, 7));
+    }
+
+    #[test]
+    fn new_output_invalidates_detected_tables() {
+        let mut popup = popup(&["Starting..."]);
+        assert!(!popup.has_tables());
+        popup.add_line(OutputLine::info("NAME   READY"));
+        popup.add_line(OutputLine::info("web    1/1"));
+        assert!(popup.has_tables());
+    }
+}
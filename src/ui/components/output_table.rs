@@ -0,0 +1,291 @@
+//! Detection of column-aligned output
+//!
+//! kubectl-style listings (a header of upper-case column names followed by
+//! rows padded to the same offsets) are parsed into cells so the output popup
+//! can show them as a sortable table instead of preformatted text.
+
+use std::cmp::Ordering;
+
+/// A table found in the output
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TableBlock {
+    /// Index of the header line
+    pub start: usize,
+    pub headers: Vec<String>,
+    pub rows: Vec<Vec<String>>,
+    /// Widest cell of each column in chars, header included
+    pub widths: Vec<usize>,
+}
+
+impl TableBlock {
+    /// Index one past the last row line
+    pub fn end(&self) -> usize {
+        self.start + 1 + self.rows.len()
+    }
+
+    pub fn contains(&self, line: usize) -> bool {
+        line >= self.start && line < self.end()
+    }
+
+    /// Row indices in display order
+    pub fn sorted_order(&self, sort: Option<TableSort>) -> Vec<usize> {
+        let mut order: Vec<usize> = (0..self.rows.len()).collect();
+        if let Some(sort) = sort {
+            order.sort_by(|&a, &b| {
+                let ord = compare_cells(&self.rows[a][sort.column], &self.rows[b][sort.column]);
+                if sort.descending {
+                    ord.reverse()
+                } else {
+                    ord
+                }
+            });
+        }
+        order
+    }
+}
+
+/// Sort column and direction of one table
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TableSort {
+    pub column: usize,
+    pub descending: bool,
+}
+
+/// Find every table in the output lines
+pub fn detect_tables<S: AsRef<str>>(lines: &[S]) -> Vec<TableBlock> {
+    let mut tables = Vec::new();
+    let mut i = 0;
+    while i < lines.len() {
+        let Some(columns) = header_columns(lines[i].as_ref()) else {
+            i += 1;
+            continue;
+        };
+
+        let rows: Vec<Vec<String>> = lines[i + 1..]
+            .iter()
+            .map_while(|line| parse_row(line.as_ref(), &columns))
+            .collect();
+        if rows.is_empty() {
+            i += 1;
+            continue;
+        }
+
+        let headers: Vec<String> = columns.into_iter().map(|(_, name)| name).collect();
+        let widths = (0..headers.len())
+            .map(|col| {
+                rows.iter()
+                    .map(|row| row[col].chars().count())
+                    .chain(std::iter::once(headers[col].chars().count()))
+                    .max()
+                    .unwrap_or(0)
+            })
+            .collect();
+        let block = TableBlock {
+            start: i,
+            headers,
+            rows,
+            widths,
+        };
+        i = block.end();
+        tables.push(block);
+    }
+    tables
+}
+
+/// Bring `tables`, detected when the output had `scanned` lines, up to date
+/// with `lines`. Only the last table can still grow and only the lines after
+/// it can start a new one, so detection resumes there instead of at the top.
+pub fn extend_tables<S: AsRef<str>>(tables: &mut Vec<TableBlock>, lines: &[S], scanned: usize) {
+    let resume = match tables.last() {
+        Some(last) if last.end() >= scanned => {
+            let start = last.start;
+            tables.pop();
+            start
+        }
+        Some(last) => last.end().max(scanned.saturating_sub(1)),
+        None => scanned.saturating_sub(1),
+    };
+    tables.extend(detect_tables(&lines[resume..]).into_iter().map(|mut t| {
+        t.start += resume;
+        t
+    }));
+}
+
+/// Column start offsets (in chars) and names of a header line.
+/// Columns are separated by two or more spaces, so multi-word names such as
+/// `NOMINATED NODE` stay together.
+fn header_columns(line: &str) -> Option<Vec<(usize, String)>> {
+    let chars: Vec<char> = line.trim_end().chars().collect();
+    if chars.first().is_none_or(|c| !c.is_ascii_uppercase()) {
+        return None;
+    }
+
+    let mut columns: Vec<(usize, String)> = Vec::new();
+    let mut pos = 0;
+    while pos < chars.len() {
+        let start = pos;
+        while pos < chars.len()
+            && !(chars[pos] == ' ' && chars.get(pos + 1).is_none_or(|&c| c == ' '))
+        {
+            pos += 1;
+        }
+        let name: String = chars[start..pos].iter().collect();
+        if !name.split(' ').all(is_header_word) {
+            return None;
+        }
+        columns.push((start, name));
+        while pos < chars.len() && chars[pos] == ' ' {
+            pos += 1;
+        }
+    }
+
+    (columns.len() >= 2).then_some(columns)
+}
+
+fn is_header_word(word: &str) -> bool {
+    word.starts_with(|c: char| c.is_ascii_uppercase())
+        && word
+            .chars()
+            .all(|c| c.is_ascii_uppercase() || c.is_ascii_digit() || "-_()%/.".contains(c))
+}
+
+/// Split a row at the header's column offsets. Rows whose values overflow
+/// their column are split on whitespace instead when the cell count matches.
+fn parse_row(line: &str, columns: &[(usize, String)]) -> Option<Vec<String>> {
+    let chars: Vec<char> = line.trim_end().chars().collect();
+    if chars.first().is_none_or(|c| c.is_whitespace()) || chars.iter().any(|c| c.is_control()) {
+        return None;
+    }
+
+    let last_start = columns.last()?.0;
+    let aligned = chars.len() > last_start
+        && columns
+            .iter()
+            .skip(1)
+            .all(|&(start, _)| chars[start - 1] == ' ' && chars[start] != ' ');
+    if aligned {
+        let cells = columns
+            .iter()
+            .enumerate()
+            .map(|(i, &(start, _))| {
+                let end = columns.get(i + 1).map_or(chars.len(), |c| c.0);
+                chars[start..end]
+                    .iter()
+                    .collect::<String>()
+                    .trim()
+                    .to_string()
+            })
+            .collect();
+        return Some(cells);
+    }
+
+    let cells: Vec<String> = line.split_whitespace().map(str::to_string).collect();
+    (cells.len() == columns.len()).then_some(cells)
+}
+
+/// Compare two cells, numerically when both are numbers (optionally with a
+/// trailing `%`)
+fn compare_cells(a: &str, b: &str) -> Ordering {
+    let number = |s: &str| s.trim_end_matches('%').parse::<f64>().ok();
+    match (number(a), number(b)) {
+        (Some(x), Some(y)) => x.partial_cmp(&y).unwrap_or(Ordering::Equal),
+        _ => a.cmp(b),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn detects_info_style_tables() {
+        let lines = [
+            "=== Nodes ===",
+            "NAME                 STATUS     ROLES           INTERNAL-IP     VERSION",
+            "k3s-server           Ready      control-plane   172.18.0.2      v1.31.2+k3s1",
+            "a-very-long-node-name-here Ready control-plane 172.18.0.3 v1.31.2+k3s1",
+            "",
+            "\n=== System Pods ===",
+        ];
+        let tables = detect_tables(&lines);
+        assert_eq!(tables.len(), 1);
+
+        let table = &tables[0];
+        assert_eq!(table.start, 1);
+        assert_eq!(table.end(), 4);
+        assert_eq!(table.headers[3], "INTERNAL-IP");
+        assert_eq!(
+            table.rows[0],
+            [
+                "k3s-server",
+                "Ready",
+                "control-plane",
+                "172.18.0.2",
+                "v1.31.2+k3s1"
+            ]
+        );
+        assert_eq!(table.rows[1][0], "a-very-long-node-name-here");
+    }
+
+    #[test]
+    fn keeps_multi_word_headers_together() {
+        let lines = [
+            "NAME    READY   NOMINATED NODE   AGE",
+            "web-1   1/1     <none>           5m",
+        ];
+        let tables = detect_tables(&lines);
+        assert_eq!(
+            tables[0].headers,
+            ["NAME", "READY", "NOMINATED NODE", "AGE"]
+        );
+        assert_eq!(tables[0].rows[0][2], "<none>");
+    }
+
+    #[test]
+    fn ignores_plain_text() {
+        let lines = [
+            "Starting cluster...",
+            "ERROR  something failed",
+            "  details follow",
+            "DONE",
+        ];
+        assert!(detect_tables(&lines).is_empty());
+    }
+
+    #[test]
+    fn extending_matches_detecting_from_scratch() {
+        let lines = [
+            "Installing...",
+            "NAME   READY",
+            "web    1/1",
+            "db     0/1",
+            "done",
+            "KEY   VALUE",
+            "a     1",
+        ];
+        let mut tables = Vec::new();
+        for scanned in 0..lines.len() {
+            extend_tables(&mut tables, &lines[..scanned + 1], scanned);
+            assert_eq!(tables, detect_tables(&lines[..scanned + 1]));
+        }
+        assert_eq!(tables.len(), 2);
+        assert_eq!(tables[0].widths, [4, 5]);
+    }
+
+    #[test]
+    fn sorts_numbers_numerically() {
+        let lines = ["NAME   RESTARTS", "a      10", "b      9", "c      100"];
+        let table = &detect_tables(&lines)[0];
+        let sort = TableSort {
+            column: 1,
+            descending: false,
+        };
+        assert_eq!(table.sorted_order(Some(sort)), [1, 0, 2]);
+        let sort = TableSort {
+            column: 0,
+            descending: true,
+        };
+        assert_eq!(table.sorted_order(Some(sort)), [2, 1, 0]);
+        assert_eq!(table.sorted_order(None), [0, 1, 2]);
+    }
+}
@@ -50,6 +50,17 @@ impl DetailTab {
     fn count() -> usize {
         6
     }
+
+    pub fn name(self) -> &'static str {
+        match self {
+            DetailTab::Logs => "logs",
+            DetailTab::Describe => "describe",
+            DetailTab::Timeline => "timeline",
+            DetailTab::Volumes => "volumes",
+            DetailTab::Shell => "shell",
+            DetailTab::Capture => "capture",
+        }
+    }
 }
 
 /// Per-tab capture state for the Capture tab.
@@ -1,6 +1,7 @@
 //! Pod stats component showing running pods with Docker-based metrics
 
-use std::collections::HashSet;
+use std::cmp::Ordering;
+use std::collections::{HashMap, HashSet};
 
 use chrono::{DateTime, Utc};
 use ratatui::{
@@ -13,7 +14,7 @@ use ratatui::{
     Frame,
 };
 
-use crate::cluster::PullPhase;
+use crate::cluster::{HostCapacity, PullPhase};
 use crate::ui::styles::Styles;
 use crate::ui::theme::Theme;
 
@@ -127,6 +128,12 @@ impl PodStat {
             && self.cpu_limit_millicores < CPU_UNLIMITED_THRESHOLD_MILLICORES
     }
 
+    /// A running pod with neither a CPU nor a memory limit can take the
+    /// whole Docker host
+    pub fn has_no_limits(&self) -> bool {
+        matches!(self.state, PodState::Running) && !self.has_cpu_limit() && !self.has_memory_limit()
+    }
+
     /// Get CPU usage as percentage of limit (if limit exists)
     pub fn cpu_percent_of_limit(&self) -> f64 {
         if self.cpu_limit_millicores > 0.0 {
@@ -138,9 +145,97 @@ impl PodStat {
     }
 }
 
+/// Order of the pod list
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum PodSort {
+    /// By namespace, then pod name
+    #[default]
+    Name,
+    /// Busiest first
+    Cpu,
+    Memory,
+}
+
+impl PodSort {
+    fn metric(&self, pod: &PodStat) -> f64 {
+        match self {
+            PodSort::Name => 0.0,
+            PodSort::Cpu => pod.cpu_percent,
+            PodSort::Memory => pod.memory_used_mb,
+        }
+    }
+
+    fn label(&self) -> Option<&'static str> {
+        match self {
+            PodSort::Name => None,
+            PodSort::Cpu => Some("CPU"),
+            PodSort::Memory => Some("MEM"),
+        }
+    }
+
+    /// Sort pods. Metric sorts keep namespaces grouped: namespaces are
+    /// ordered by their busiest pod, pods within by the metric.
+    pub fn apply(&self, pods: &mut [PodStat]) {
+        if *self == PodSort::Name {
+            pods.sort_by(|a, b| {
+                a.namespace
+                    .cmp(&b.namespace)
+                    .then_with(|| a.name.cmp(&b.name))
+            });
+            return;
+        }
+        let mut busiest: HashMap<String, f64> = HashMap::new();
+        for pod in pods.iter() {
+            let entry = busiest.entry(pod.namespace.clone()).or_default();
+            *entry = entry.max(self.metric(pod));
+        }
+        let desc = |a: f64, b: f64| b.partial_cmp(&a).unwrap_or(Ordering::Equal);
+        pods.sort_by(|a, b| {
+            desc(busiest[&a.namespace], busiest[&b.namespace])
+                .then_with(|| a.namespace.cmp(&b.namespace))
+                .then_with(|| desc(self.metric(a), self.metric(b)))
+                .then_with(|| a.name.cmp(&b.name))
+        });
+    }
+}
+
+/// Aggregate CPU/memory of all pods against what the host offers
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ResourceUsage {
+    /// Sum of pod CPU (100 = one core)
+    pub cpu_percent: f64,
+    pub memory_used_mb: f64,
+    pub capacity: HostCapacity,
+}
+
+impl ResourceUsage {
+    pub fn from_pods(pods: &[PodStat], capacity: HostCapacity) -> Self {
+        Self {
+            cpu_percent: pods.iter().map(|p| p.cpu_percent).sum(),
+            memory_used_mb: pods.iter().map(|p| p.memory_used_mb).sum(),
+            capacity,
+        }
+    }
+
+    /// CPU use as a percentage of all host cores
+    pub fn cpu_ratio(&self) -> f64 {
+        self.cpu_percent / self.capacity.cpus as f64
+    }
+
+    /// Memory use as a percentage of host memory
+    pub fn memory_ratio(&self) -> f64 {
+        if self.capacity.memory_mb > 0.0 {
+            self.memory_used_mb / self.capacity.memory_mb * 100.0
+        } else {
+            0.0
+        }
+    }
+}
+
 /// Pod stats panel component
 pub struct PodStats {
     pods: Vec<PodStat>,
+    sort: PodSort,
     scroll_offset: usize,
     selected_index: usize,
     styles: Styles,
@@ -156,6 +251,7 @@ impl PodStats {
     pub fn with_theme(theme: Theme) -> Self {
         Self {
             pods: Vec::new(),
+            sort: PodSort::default(),
             scroll_offset: 0,
             selected_index: 0,
             styles: Styles::from_theme(theme),
@@ -163,7 +259,8 @@ impl PodStats {
         }
     }
 
-    pub fn set_pods(&mut self, pods: Vec<PodStat>) {
+    pub fn set_pods(&mut self, mut pods: Vec<PodStat>) {
+        self.sort.apply(&mut pods);
         self.pods = pods;
         // Reset scroll and selection if pods changed significantly
         if self.scroll_offset > self.pods.len() {
@@ -174,6 +271,35 @@ impl PodStats {
         }
     }
 
+    pub fn sort(&self) -> PodSort {
+        self.sort
+    }
+
+    /// Re-sort the list and select its first pod
+    pub fn set_sort(&mut self, sort: PodSort) {
+        self.sort = sort;
+        self.sort.apply(&mut self.pods);
+        self.selected_index = 0;
+        self.scroll_offset = 0;
+    }
+
+    /// Panel title, with the sort when it isn't the default
+    pub fn title(&self, focused: bool) -> String {
+        let mut name = match self.sort.label() {
+            Some(label) => format!("Pods · by {}", label),
+            None => "Pods".to_string(),
+        };
+        let unlimited = self.pods.iter().filter(|p| p.has_no_limits()).count();
+        if unlimited > 0 {
+            name.push_str(&format!(" · {} without limits", unlimited));
+        }
+        if focused {
+            format!(" \u{25b6} {} \u{25c0} ", name)
+        } else {
+            format!("   {} ", name)
+        }
+    }
+
     /// Get the currently selected pod (if any)
     pub fn selected_pod(&self) -> Option<&PodStat> {
         self.pods.get(self.selected_index)
@@ -286,11 +412,7 @@ impl PodStats {
             BorderType::Rounded
         };
 
-        let title = if focused {
-            " ▶ Pods ◀ "
-        } else {
-            "   Pods "
-        };
+        let title = self.title(focused);
 
         let title_style = if focused {
             self.styles.title.add_modifier(Modifier::BOLD)
@@ -475,9 +597,11 @@ impl PodStats {
                         .bg(mem_base_style.fg.unwrap_or(ratatui::style::Color::Green));
                     let mem_empty_style = self.styles.muted_text;
 
-                    // Show arch mismatch warning icon before pod name
-                    let (arch_prefix, name_w) = if pod.arch_mismatch {
+                    // Flag arch mismatches, then pods without limits, before the name
+                    let (warning_prefix, name_w) = if pod.arch_mismatch {
                         ("\u{26a0} ", name_width.saturating_sub(2))
+                    } else if pod.has_no_limits() {
+                        ("\u{221e} ", name_width.saturating_sub(2))
                     } else {
                         ("", name_width)
                     };
@@ -491,8 +615,8 @@ impl PodStats {
                         },
                     )];
 
-                    if pod.arch_mismatch {
-                        line_spans.push(Span::styled(arch_prefix, self.styles.warning_text));
+                    if !warning_prefix.is_empty() {
+                        line_spans.push(Span::styled(warning_prefix, self.styles.warning_text));
                     }
 
                     line_spans.push(Span::styled(
@@ -969,6 +1093,69 @@ fn format_elapsed(duration: chrono::Duration) -> String {
 mod tests {
     use super::*;
 
+    fn pod(namespace: &str, name: &str, cpu: f64, memory: f64) -> PodStat {
+        PodStat {
+            name: name.to_string(),
+            namespace: namespace.to_string(),
+            state: PodState::Running,
+            cpu_percent: cpu,
+            cpu_limit_millicores: 0.0,
+            memory_used_mb: memory,
+            memory_limit_mb: 0.0,
+            arch_mismatch: false,
+        }
+    }
+
+    #[test]
+    fn metric_sort_keeps_namespaces_grouped() {
+        let mut pods = vec![
+            pod("apps", "web", 5.0, 300.0),
+            pod("kube-system", "coredns", 2.0, 40.0),
+            pod("apps", "worker", 80.0, 100.0),
+            pod("kube-system", "traefik", 1.0, 60.0),
+        ];
+        let names = |pods: &[PodStat]| pods.iter().map(|p| p.name.clone()).collect::<Vec<_>>();
+
+        PodSort::Cpu.apply(&mut pods);
+        assert_eq!(names(&pods), ["worker", "web", "coredns", "traefik"]);
+
+        PodSort::Memory.apply(&mut pods);
+        assert_eq!(names(&pods), ["web", "worker", "traefik", "coredns"]);
+
+        PodSort::Name.apply(&mut pods);
+        assert_eq!(names(&pods), ["web", "worker", "coredns", "traefik"]);
+    }
+
+    #[test]
+    fn pods_without_any_limit_are_flagged() {
+        let mut unlimited = pod("apps", "web", 5.0, 300.0);
+        unlimited.memory_limit_mb = 16384.0; // host RAM, i.e. no limit
+        assert!(unlimited.has_no_limits());
+
+        let mut memory_only = unlimited.clone();
+        memory_only.memory_limit_mb = 512.0;
+        assert!(!memory_only.has_no_limits());
+
+        unlimited.state = PodState::Waiting {
+            reason: "Pending".to_string(),
+        };
+        assert!(!unlimited.has_no_limits());
+    }
+
+    #[test]
+    fn usage_is_relative_to_host_capacity() {
+        let pods = vec![pod("a", "x", 150.0, 1024.0), pod("a", "y", 50.0, 1024.0)];
+        let usage = ResourceUsage::from_pods(
+            &pods,
+            HostCapacity {
+                cpus: 4,
+                memory_mb: 8192.0,
+            },
+        );
+        assert!((usage.cpu_ratio() - 50.0).abs() < 0.01);
+        assert!((usage.memory_ratio() - 25.0).abs() < 0.01);
+    }
+
     #[test]
     fn test_format_bytes_progress_same_unit() {
         // Both values in MB range
@@ -0,0 +1,199 @@
+//! Live process list of a pod
+//!
+//! Shows the processes of every container in the selected pod, busiest
+//! first. The app refreshes the list while the popup is open.
+
+use std::time::Instant;
+
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::Modifier,
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Cell, Clear, Paragraph, Row, Table},
+    Frame,
+};
+
+use crate::cluster::PodProcess;
+use crate::ui::styles::Styles;
+use crate::ui::theme::Theme;
+
+/// %CPU from which a process is highlighted
+const BUSY_CPU_PERCENT: f64 = 50.0;
+
+/// Pod top overlay
+pub struct PodTopView {
+    styles: Styles,
+    pod: String,
+    namespace: String,
+    processes: Result<Vec<PodProcess>, String>,
+    updated_at: Option<Instant>,
+    scroll: usize,
+}
+
+impl PodTopView {
+    pub fn new() -> Self {
+        Self::with_theme(Theme::default())
+    }
+
+    pub fn with_theme(theme: Theme) -> Self {
+        Self {
+            styles: Styles::from_theme(theme),
+            pod: String::new(),
+            namespace: String::new(),
+            processes: Ok(Vec::new()),
+            updated_at: None,
+            scroll: 0,
+        }
+    }
+
+    /// Start showing a pod, forgetting the previous one
+    pub fn open(&mut self, pod: &str, namespace: &str) {
+        self.pod = pod.to_string();
+        self.namespace = namespace.to_string();
+        self.processes = Ok(Vec::new());
+        self.updated_at = None;
+        self.scroll = 0;
+    }
+
+    pub fn pod(&self) -> (&str, &str) {
+        (&self.pod, &self.namespace)
+    }
+
+    pub fn update(&mut self, processes: Result<Vec<PodProcess>, String>) {
+        if let Ok(list) = &processes {
+            self.scroll = self.scroll.min(list.len().saturating_sub(1));
+        }
+        self.processes = processes;
+        self.updated_at = Some(Instant::now());
+    }
+
+    pub fn scroll_up(&mut self) {
+        self.scroll = self.scroll.saturating_sub(1);
+    }
+
+    pub fn scroll_down(&mut self) {
+        if let Ok(list) = &self.processes {
+            if self.scroll + 1 < list.len() {
+                self.scroll += 1;
+            }
+        }
+    }
+
+    pub fn render(&self, frame: &mut Frame, area: Rect) {
+        let popup_area = centered_rect(85, 75, area);
+        frame.render_widget(Clear, popup_area);
+
+        let updated = match self.updated_at {
+            Some(at) => format!(" updated {}s ago ", at.elapsed().as_secs()),
+            None => " loading... ".to_string(),
+        };
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Double)
+            .border_style(self.styles.border_focused)
+            .title(format!(" Top · {}/{} ", self.namespace, self.pod))
+            .title_bottom(
+                Line::from(vec![
+                    Span::styled(" Esc close ", self.styles.muted_text),
+                    Span::styled(" j/k scroll ", self.styles.muted_text),
+                    Span::styled(" r refresh ", self.styles.muted_text),
+                    Span::styled(updated, self.styles.muted_text),
+                ])
+                .right_aligned(),
+            );
+        let inner = block.inner(popup_area);
+        frame.render_widget(block, popup_area);
+
+        let processes = match &self.processes {
+            Err(e) => {
+                let msg = Paragraph::new(Span::styled(format!("  {}", e), self.styles.error_text));
+                frame.render_widget(msg, inner);
+                return;
+            }
+            Ok(list) if list.is_empty() => {
+                let text = if self.updated_at.is_some() {
+                    "  No processes running."
+                } else {
+                    "  Loading processes..."
+                };
+                let msg = Paragraph::new(Span::styled(text, self.styles.muted_text));
+                frame.render_widget(msg, inner);
+                return;
+            }
+            Ok(list) => list,
+        };
+
+        let rows = processes.iter().skip(self.scroll).map(|p| {
+            let style = if p.cpu_percent >= BUSY_CPU_PERCENT {
+                self.styles.warning_text
+            } else {
+                self.styles.normal_text
+            };
+            Row::new(vec![
+                Cell::from(p.container.as_str()),
+                Cell::from(p.pid.as_str()),
+                Cell::from(p.user.as_str()),
+                Cell::from(format!("{:.1}", p.cpu_percent)),
+                Cell::from(format!("{:.1}", p.memory_percent)),
+                Cell::from(format_rss(p.rss_kb)),
+                Cell::from(p.command.as_str()),
+            ])
+            .style(style)
+        });
+        let table = Table::new(
+            rows,
+            [
+                Constraint::Length(16),
+                Constraint::Length(8),
+                Constraint::Length(10),
+                Constraint::Length(6),
+                Constraint::Length(6),
+                Constraint::Length(8),
+                Constraint::Fill(1),
+            ],
+        )
+        .header(
+            Row::new(["CONTAINER", "PID", "USER", "%CPU", "%MEM", "RSS", "COMMAND"])
+                .style(self.styles.normal_text.add_modifier(Modifier::BOLD)),
+        );
+        frame.render_widget(table, inner);
+    }
+}
+
+impl Default for PodTopView {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Resident memory in the largest fitting unit
+fn format_rss(kb: u64) -> String {
+    if kb >= 1024 * 1024 {
+        format!("{:.1}G", kb as f64 / (1024.0 * 1024.0))
+    } else if kb >= 1024 {
+        format!("{:.1}M", kb as f64 / 1024.0)
+    } else {
+        format!("{}K", kb)
+    }
+}
+
+/// Helper to create a centered rect
+fn centered_rect(percent_x: u16, percent_y: u16, area: Rect) -> Rect {
+    let popup_layout = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Percentage((100 - percent_y) / 2),
+            Constraint::Percentage(percent_y),
+            Constraint::Percentage((100 - percent_y) / 2),
+        ])
+        .split(area);
+
+    Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Percentage((100 - percent_x) / 2),
+            Constraint::Percentage(percent_x),
+            Constraint::Percentage((100 - percent_x) / 2),
+        ])
+        .split(popup_layout[1])[1]
+}
@@ -0,0 +1,198 @@
+//! Policy violations overview
+//!
+//! Lists what the installed policy engine reports against current workloads:
+//! the policy, the resource and the engine's message. Failures are red,
+//! warnings and audit-only (`dryrun`) results amber.
+
+use std::time::Instant;
+
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Cell, Clear, Paragraph, Row, Table},
+    Frame,
+};
+
+use crate::k8s::PolicyViolation;
+use crate::ui::styles::Styles;
+use crate::ui::theme::Theme;
+
+/// Policy violations overlay
+pub struct PolicyView {
+    styles: Styles,
+    violations: Result<Vec<PolicyViolation>, String>,
+    updated_at: Option<Instant>,
+    scroll: usize,
+}
+
+impl PolicyView {
+    pub fn new() -> Self {
+        Self::with_theme(Theme::default())
+    }
+
+    pub fn with_theme(theme: Theme) -> Self {
+        Self {
+            styles: Styles::from_theme(theme),
+            violations: Ok(Vec::new()),
+            updated_at: None,
+            scroll: 0,
+        }
+    }
+
+    /// Forget the previous list before opening the view
+    pub fn reset(&mut self) {
+        self.violations = Ok(Vec::new());
+        self.updated_at = None;
+        self.scroll = 0;
+    }
+
+    pub fn update(&mut self, violations: Result<Vec<PolicyViolation>, String>) {
+        if let Ok(list) = &violations {
+            self.scroll = self.scroll.min(list.len().saturating_sub(1));
+        }
+        self.violations = violations;
+        self.updated_at = Some(Instant::now());
+    }
+
+    pub fn scroll_up(&mut self) {
+        self.scroll = self.scroll.saturating_sub(1);
+    }
+
+    pub fn scroll_down(&mut self) {
+        if let Ok(list) = &self.violations {
+            if self.scroll + 1 < list.len() {
+                self.scroll += 1;
+            }
+        }
+    }
+
+    pub fn render(&self, frame: &mut Frame, area: Rect) {
+        let popup_area = centered_rect(90, 80, area);
+        frame.render_widget(Clear, popup_area);
+
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Double)
+            .border_style(self.styles.border_focused)
+            .title(" Policy Violations ")
+            .title_bottom(
+                Line::from(vec![
+                    Span::styled(" Esc close ", self.styles.muted_text),
+                    Span::styled(" j/k scroll ", self.styles.muted_text),
+                    Span::styled(" r reload ", self.styles.muted_text),
+                ])
+                .right_aligned(),
+            );
+        let inner = block.inner(popup_area);
+        frame.render_widget(block, popup_area);
+
+        let violations = match &self.violations {
+            Err(e) => {
+                let msg = Paragraph::new(Span::styled(format!("  {}", e), self.styles.error_text));
+                frame.render_widget(msg, inner);
+                return;
+            }
+            Ok(list) if list.is_empty() => {
+                let (text, style) = if self.updated_at.is_some() {
+                    ("  No violations reported.", self.styles.success_text)
+                } else {
+                    ("  Loading policy reports...", self.styles.muted_text)
+                };
+                frame.render_widget(Paragraph::new(Span::styled(text, style)), inner);
+                return;
+            }
+            Ok(list) => list,
+        };
+
+        let rows = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(2), Constraint::Min(0)])
+            .split(inner);
+        frame.render_widget(self.summary(violations), rows[0]);
+
+        let table_rows = violations.iter().skip(self.scroll).map(|v| {
+            let style = self.violation_style(v);
+            Row::new(vec![
+                Cell::from(Span::styled("●", style)),
+                Cell::from(v.namespace.as_str()),
+                Cell::from(v.resource.as_str()),
+                Cell::from(v.policy.as_str()),
+                Cell::from(v.message.as_str()),
+            ])
+            .style(self.styles.normal_text)
+        });
+        let table = Table::new(
+            table_rows,
+            [
+                Constraint::Length(1),
+                Constraint::Fill(1),
+                Constraint::Fill(2),
+                Constraint::Fill(2),
+                Constraint::Fill(4),
+            ],
+        )
+        .header(
+            Row::new(["", "NAMESPACE", "RESOURCE", "POLICY", "MESSAGE"])
+                .style(self.styles.normal_text.add_modifier(Modifier::BOLD)),
+        );
+        frame.render_widget(table, rows[1]);
+    }
+
+    fn violation_style(&self, violation: &PolicyViolation) -> Style {
+        if violation.failed {
+            self.styles.error_text
+        } else {
+            self.styles.warning_text
+        }
+    }
+
+    fn summary(&self, violations: &[PolicyViolation]) -> Paragraph<'static> {
+        let failed = violations.iter().filter(|v| v.failed).count();
+        let warned = violations.len() - failed;
+        let mut spans = vec![Span::styled(
+            format!("  {} violations", violations.len()),
+            self.styles.normal_text,
+        )];
+        if failed > 0 {
+            spans.push(Span::styled(
+                format!(" · {} failing", failed),
+                self.styles.error_text,
+            ));
+        }
+        if warned > 0 {
+            spans.push(Span::styled(
+                format!(" · {} warnings or audit-only", warned),
+                self.styles.warning_text,
+            ));
+        }
+        Paragraph::new(Line::from(spans))
+    }
+}
+
+impl Default for PolicyView {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Helper to create a centered rect
+fn centered_rect(percent_x: u16, percent_y: u16, area: Rect) -> Rect {
+    let popup_layout = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Percentage((100 - percent_y) / 2),
+            Constraint::Percentage(percent_y),
+            Constraint::Percentage((100 - percent_y) / 2),
+        ])
+        .split(area);
+
+    Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Percentage((100 - percent_x) / 2),
+            Constraint::Percentage(percent_x),
+            Constraint::Percentage((100 - percent_x) / 2),
+        ])
+        .split(popup_layout[1])[1]
+}
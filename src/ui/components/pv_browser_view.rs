@@ -0,0 +1,202 @@
+//! PV data browser
+//!
+//! The PV directories under the local-path storage path with their size,
+//! file count and last change. `d` copies the selected one to the host and
+//! `W` empties it after a confirmation.
+
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::Modifier,
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Cell, Clear, Paragraph, Row, Table},
+    Frame,
+};
+
+use crate::cluster::PvDirectory;
+use crate::ui::styles::Styles;
+use crate::ui::theme::Theme;
+
+/// PV data overlay
+pub struct PvBrowserView {
+    styles: Styles,
+    dirs: Result<Vec<PvDirectory>, String>,
+    loaded: bool,
+    /// Storage path the directories were listed from
+    storage_path: String,
+    selected: usize,
+}
+
+impl PvBrowserView {
+    pub fn new() -> Self {
+        Self::with_theme(Theme::default())
+    }
+
+    pub fn with_theme(theme: Theme) -> Self {
+        Self {
+            styles: Styles::from_theme(theme),
+            dirs: Ok(Vec::new()),
+            loaded: false,
+            storage_path: String::new(),
+            selected: 0,
+        }
+    }
+
+    /// Forget the previous listing before opening the view
+    pub fn reset(&mut self, storage_path: &str) {
+        self.dirs = Ok(Vec::new());
+        self.loaded = false;
+        self.storage_path = storage_path.to_string();
+        self.selected = 0;
+    }
+
+    /// Show a new listing, keeping the selected directory when it's still there
+    pub fn update(&mut self, dirs: Result<Vec<PvDirectory>, String>) {
+        let previous = self.selected().map(|d| d.dir_name.clone());
+        self.selected = match (&dirs, previous) {
+            (Ok(list), Some(name)) => list
+                .iter()
+                .position(|d| d.dir_name == name)
+                .unwrap_or(self.selected.min(list.len().saturating_sub(1))),
+            _ => 0,
+        };
+        self.dirs = dirs;
+        self.loaded = true;
+    }
+
+    pub fn select_up(&mut self) {
+        self.selected = self.selected.saturating_sub(1);
+    }
+
+    pub fn select_down(&mut self) {
+        let len = self.dirs.as_ref().map_or(0, Vec::len);
+        if self.selected + 1 < len {
+            self.selected += 1;
+        }
+    }
+
+    /// The highlighted directory
+    pub fn selected(&self) -> Option<&PvDirectory> {
+        self.dirs.as_ref().ok()?.get(self.selected)
+    }
+
+    pub fn render(&self, frame: &mut Frame, area: Rect) {
+        let popup_area = centered_rect(80, 60, area);
+        frame.render_widget(Clear, popup_area);
+
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Double)
+            .border_style(self.styles.border_focused)
+            .title(format!(" PV Data ({}) ", self.storage_path))
+            .title_bottom(
+                Line::from(vec![
+                    Span::styled(" Esc close ", self.styles.muted_text),
+                    Span::styled(" j/k select ", self.styles.muted_text),
+                    Span::styled(" d download ", self.styles.muted_text),
+                    Span::styled(" W wipe ", self.styles.muted_text),
+                    Span::styled(" r reload ", self.styles.muted_text),
+                ])
+                .right_aligned(),
+            );
+        let inner = block.inner(popup_area);
+        frame.render_widget(block, popup_area);
+
+        let list = match &self.dirs {
+            Err(e) => {
+                let msg = Paragraph::new(Span::styled(format!("  {}", e), self.styles.error_text));
+                frame.render_widget(msg, inner);
+                return;
+            }
+            Ok(list) if list.is_empty() => {
+                let text = if self.loaded {
+                    "  No PV directories yet. They appear once a claim is bound."
+                } else {
+                    "  Listing PV directories..."
+                };
+                let msg = Paragraph::new(Span::styled(text, self.styles.muted_text));
+                frame.render_widget(msg, inner);
+                return;
+            }
+            Ok(list) => list,
+        };
+
+        let rows = list.iter().enumerate().map(|(i, dir)| {
+            let modified = dir
+                .modified
+                .and_then(|ts| chrono::DateTime::from_timestamp(ts, 0))
+                .map(|t| {
+                    t.with_timezone(&chrono::Local)
+                        .format("%Y-%m-%d %H:%M")
+                        .to_string()
+                })
+                .unwrap_or_else(|| "-".to_string());
+            Row::new(vec![
+                Cell::from(dir.label()),
+                Cell::from(format_bytes(dir.size_bytes)),
+                Cell::from(dir.files.to_string()),
+                Cell::from(modified),
+                Cell::from(Span::styled(dir.dir_name.clone(), self.styles.muted_text)),
+            ])
+            .style(if i == self.selected {
+                self.styles.selected
+            } else {
+                self.styles.normal_text
+            })
+        });
+        let table = Table::new(
+            rows,
+            [
+                Constraint::Length(32),
+                Constraint::Length(9),
+                Constraint::Length(7),
+                Constraint::Length(17),
+                Constraint::Fill(1),
+            ],
+        )
+        .header(
+            Row::new(["CLAIM", "SIZE", "FILES", "MODIFIED", "DIRECTORY"])
+                .style(self.styles.normal_text.add_modifier(Modifier::BOLD)),
+        );
+        frame.render_widget(table, inner);
+    }
+}
+
+impl Default for PvBrowserView {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Format bytes as human-readable (Ki/Mi/Gi)
+fn format_bytes(bytes: u64) -> String {
+    if bytes >= 1024 * 1024 * 1024 {
+        format!("{:.1}Gi", bytes as f64 / (1024.0 * 1024.0 * 1024.0))
+    } else if bytes >= 1024 * 1024 {
+        format!("{:.1}Mi", bytes as f64 / (1024.0 * 1024.0))
+    } else if bytes >= 1024 {
+        format!("{:.0}Ki", bytes as f64 / 1024.0)
+    } else {
+        format!("{}B", bytes)
+    }
+}
+
+/// Helper to create a centered rect
+fn centered_rect(percent_x: u16, percent_y: u16, area: Rect) -> Rect {
+    let popup_layout = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Percentage((100 - percent_y) / 2),
+            Constraint::Percentage(percent_y),
+            Constraint::Percentage((100 - percent_y) / 2),
+        ])
+        .split(area);
+
+    Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Percentage((100 - percent_x) / 2),
+            Constraint::Percentage(percent_x),
+            Constraint::Percentage((100 - percent_x) / 2),
+        ])
+        .split(popup_layout[1])[1]
+}
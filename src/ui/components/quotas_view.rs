@@ -0,0 +1,229 @@
+//! Resource quotas overview
+//!
+//! One section per namespace: each ResourceQuota resource with a bar of its
+//! consumption against the hard limit (amber from 70%, red from 90%), then
+//! the defaults and maximums its LimitRanges apply to containers.
+
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Clear, Paragraph},
+    Frame,
+};
+
+use crate::k8s::quotas::{LimitSummary, NamespaceQuotas, QuotaUsage};
+use crate::ui::styles::Styles;
+use crate::ui::theme::Theme;
+
+/// Width of the consumption bars
+const BAR_WIDTH: usize = 20;
+
+/// Resource quotas overlay
+pub struct QuotasView {
+    styles: Styles,
+    namespaces: Result<Vec<NamespaceQuotas>, String>,
+    loaded: bool,
+    scroll: usize,
+}
+
+impl QuotasView {
+    pub fn new() -> Self {
+        Self::with_theme(Theme::default())
+    }
+
+    pub fn with_theme(theme: Theme) -> Self {
+        Self {
+            styles: Styles::from_theme(theme),
+            namespaces: Ok(Vec::new()),
+            loaded: false,
+            scroll: 0,
+        }
+    }
+
+    /// Forget the previous result before opening the view
+    pub fn reset(&mut self) {
+        self.namespaces = Ok(Vec::new());
+        self.loaded = false;
+        self.scroll = 0;
+    }
+
+    pub fn update(&mut self, namespaces: Result<Vec<NamespaceQuotas>, String>) {
+        self.namespaces = namespaces;
+        self.loaded = true;
+        self.scroll = self.scroll.min(self.line_count().saturating_sub(1));
+    }
+
+    pub fn scroll_up(&mut self) {
+        self.scroll = self.scroll.saturating_sub(1);
+    }
+
+    pub fn scroll_down(&mut self) {
+        if self.scroll + 1 < self.line_count() {
+            self.scroll += 1;
+        }
+    }
+
+    fn line_count(&self) -> usize {
+        self.lines().len()
+    }
+
+    pub fn render(&self, frame: &mut Frame, area: Rect) {
+        let popup_area = centered_rect(85, 80, area);
+        frame.render_widget(Clear, popup_area);
+
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Double)
+            .border_style(self.styles.border_focused)
+            .title(" Resource Quotas ")
+            .title_bottom(
+                Line::from(vec![
+                    Span::styled(" Esc close ", self.styles.muted_text),
+                    Span::styled(" j/k scroll ", self.styles.muted_text),
+                    Span::styled(" r reload ", self.styles.muted_text),
+                ])
+                .right_aligned(),
+            );
+        let inner = block.inner(popup_area);
+        frame.render_widget(block, popup_area);
+
+        match &self.namespaces {
+            Err(e) => {
+                let msg = Paragraph::new(Span::styled(format!("  {}", e), self.styles.error_text));
+                frame.render_widget(msg, inner);
+            }
+            Ok(list) if list.is_empty() => {
+                let text = if self.loaded {
+                    "  No ResourceQuotas or LimitRanges in any namespace."
+                } else {
+                    "  Loading quotas..."
+                };
+                let msg = Paragraph::new(Span::styled(text, self.styles.muted_text));
+                frame.render_widget(msg, inner);
+            }
+            Ok(_) => {
+                let lines: Vec<Line> = self.lines().into_iter().skip(self.scroll).collect();
+                frame.render_widget(Paragraph::new(lines), inner);
+            }
+        }
+    }
+
+    fn lines(&self) -> Vec<Line<'static>> {
+        let Ok(namespaces) = &self.namespaces else {
+            return Vec::new();
+        };
+        let mut lines = Vec::new();
+        for ns in namespaces {
+            if !lines.is_empty() {
+                lines.push(Line::default());
+            }
+            lines.push(Line::from(Span::styled(
+                format!(" {}", ns.namespace),
+                self.styles.title.add_modifier(Modifier::BOLD),
+            )));
+            let resource_width = ns
+                .quotas
+                .iter()
+                .map(|q| q.resource.len())
+                .max()
+                .unwrap_or(0);
+            for quota in &ns.quotas {
+                lines.push(self.quota_line(quota, resource_width));
+            }
+            if !ns.limits.is_empty() {
+                lines.push(Line::from(Span::styled(
+                    "   LimitRange defaults",
+                    self.styles.muted_text,
+                )));
+                for limit in &ns.limits {
+                    lines.push(self.limit_line(limit));
+                }
+            }
+        }
+        lines
+    }
+
+    fn quota_line(&self, quota: &QuotaUsage, resource_width: usize) -> Line<'static> {
+        let label = format!("   {:<w$}  ", quota.resource, w = resource_width);
+        let amount = format!("  {} / {}", quota.used, quota.hard);
+        let Some(ratio) = quota.ratio() else {
+            return Line::from(vec![
+                Span::styled(label, self.styles.normal_text),
+                Span::styled(amount, self.styles.muted_text),
+            ]);
+        };
+        let style = self.ratio_style(ratio);
+        let filled = ((ratio.min(1.0) * BAR_WIDTH as f64).round() as usize).min(BAR_WIDTH);
+        let percent = if ratio.is_finite() {
+            format!("{:>4.0}%", ratio * 100.0)
+        } else {
+            "   ∞%".to_string()
+        };
+        Line::from(vec![
+            Span::styled(label, self.styles.normal_text),
+            Span::styled("█".repeat(filled), style),
+            Span::styled("░".repeat(BAR_WIDTH - filled), self.styles.muted_text),
+            Span::styled(format!(" {}", percent), style),
+            Span::styled(amount, self.styles.muted_text),
+            Span::styled(format!("  ({})", quota.quota), self.styles.muted_text),
+        ])
+    }
+
+    fn limit_line(&self, limit: &LimitSummary) -> Line<'static> {
+        let value = |v: &Option<String>| v.clone().unwrap_or_else(|| "-".to_string());
+        Line::from(vec![
+            Span::styled(
+                format!("   {:<10} {:<18}", limit.kind, limit.resource),
+                self.styles.normal_text,
+            ),
+            Span::styled(
+                format!(
+                    "request {:<8} limit {:<8} max {:<8}",
+                    value(&limit.default_request),
+                    value(&limit.default_limit),
+                    value(&limit.max)
+                ),
+                self.styles.muted_text,
+            ),
+            Span::styled(format!("  ({})", limit.limit_range), self.styles.muted_text),
+        ])
+    }
+
+    fn ratio_style(&self, ratio: f64) -> Style {
+        if ratio >= 0.9 {
+            self.styles.error_text
+        } else if ratio >= 0.7 {
+            self.styles.warning_text
+        } else {
+            self.styles.success_text
+        }
+    }
+}
+
+impl Default for QuotasView {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Helper to create a centered rect
+fn centered_rect(percent_x: u16, percent_y: u16, area: Rect) -> Rect {
+    let popup_layout = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Percentage((100 - percent_y) / 2),
+            Constraint::Percentage(percent_y),
+            Constraint::Percentage((100 - percent_y) / 2),
+        ])
+        .split(area);
+
+    Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Percentage((100 - percent_x) / 2),
+            Constraint::Percentage(percent_x),
+            Constraint::Percentage((100 - percent_x) / 2),
+        ])
+        .split(popup_layout[1])[1]
+}
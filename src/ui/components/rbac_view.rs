@@ -0,0 +1,369 @@
+//! RBAC inspector
+//!
+//! Shows one namespace at a time: its ServiceAccounts, Roles and the bindings
+//! that grant them permissions. A prompt runs `kubectl auth can-i` style
+//! checks, e.g. `list secrets --as builder`, and keeps the answers below.
+
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::Modifier,
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Clear, Paragraph, Wrap},
+    Frame,
+};
+
+use crate::k8s::{CanIAnswer, NamespaceRbac};
+use crate::ui::styles::Styles;
+use crate::ui::theme::Theme;
+
+/// Answers kept below the prompt
+const MAX_CHECKS: usize = 8;
+
+/// One can-i check and its answer
+struct Check {
+    query: String,
+    answer: Option<Result<CanIAnswer, String>>,
+}
+
+/// RBAC overlay
+pub struct RbacView {
+    styles: Styles,
+    namespaces: Result<Vec<NamespaceRbac>, String>,
+    loaded: bool,
+    selected: usize,
+    /// Text of the can-i prompt while it is open
+    input: Option<String>,
+    /// Newest first
+    checks: Vec<Check>,
+    /// Namespace shown first
+    default_namespace: String,
+}
+
+impl RbacView {
+    pub fn new() -> Self {
+        Self::with_theme(Theme::default())
+    }
+
+    pub fn with_theme(theme: Theme) -> Self {
+        Self {
+            styles: Styles::from_theme(theme),
+            namespaces: Ok(Vec::new()),
+            loaded: false,
+            selected: 0,
+            input: None,
+            checks: Vec::new(),
+            default_namespace: "default".to_string(),
+        }
+    }
+
+    /// Forget the previous result before opening the view
+    pub fn reset(&mut self, default_namespace: &str) {
+        self.namespaces = Ok(Vec::new());
+        self.loaded = false;
+        self.selected = 0;
+        self.input = None;
+        self.default_namespace = default_namespace.to_string();
+    }
+
+    /// Show new results; the first time, start at the default namespace
+    pub fn update(&mut self, namespaces: Result<Vec<NamespaceRbac>, String>) {
+        if let Ok(list) = &namespaces {
+            if !self.loaded {
+                self.selected = list
+                    .iter()
+                    .position(|ns| ns.namespace == self.default_namespace)
+                    .unwrap_or(0);
+            }
+            self.selected = self.selected.min(list.len().saturating_sub(1));
+        }
+        self.namespaces = namespaces;
+        self.loaded = true;
+    }
+
+    /// Namespace can-i checks default to
+    pub fn namespace(&self) -> &str {
+        self.namespaces
+            .as_ref()
+            .ok()
+            .and_then(|list| list.get(self.selected))
+            .map(|ns| ns.namespace.as_str())
+            .unwrap_or(&self.default_namespace)
+    }
+
+    pub fn next_namespace(&mut self) {
+        if let Ok(list) = &self.namespaces {
+            if !list.is_empty() {
+                self.selected = (self.selected + 1) % list.len();
+            }
+        }
+    }
+
+    pub fn prev_namespace(&mut self) {
+        if let Ok(list) = &self.namespaces {
+            if !list.is_empty() {
+                self.selected = (self.selected + list.len() - 1) % list.len();
+            }
+        }
+    }
+
+    pub fn is_prompting(&self) -> bool {
+        self.input.is_some()
+    }
+
+    pub fn open_prompt(&mut self) {
+        self.input = Some(String::new());
+    }
+
+    pub fn close_prompt(&mut self) {
+        self.input = None;
+    }
+
+    pub fn handle_char(&mut self, c: char) {
+        if let Some(input) = &mut self.input {
+            input.push(c);
+        }
+    }
+
+    pub fn handle_backspace(&mut self) {
+        if let Some(input) = &mut self.input {
+            input.pop();
+        }
+    }
+
+    /// Close the prompt and return what was typed, if anything
+    pub fn take_query(&mut self) -> Option<String> {
+        self.input
+            .take()
+            .map(|q| q.trim().to_string())
+            .filter(|q| !q.is_empty())
+    }
+
+    /// Record a check that is waiting for its answer
+    pub fn start_check(&mut self, query: &str) {
+        self.checks.insert(
+            0,
+            Check {
+                query: query.to_string(),
+                answer: None,
+            },
+        );
+        self.checks.truncate(MAX_CHECKS);
+    }
+
+    /// Fill in the answer of the newest pending check for `query`
+    pub fn finish_check(&mut self, query: &str, answer: Result<CanIAnswer, String>) {
+        if let Some(check) = self
+            .checks
+            .iter_mut()
+            .find(|c| c.query == query && c.answer.is_none())
+        {
+            check.answer = Some(answer);
+        }
+    }
+
+    pub fn render(&self, frame: &mut Frame, area: Rect) {
+        let popup_area = centered_rect(90, 85, area);
+        frame.render_widget(Clear, popup_area);
+
+        let current = self
+            .namespaces
+            .as_ref()
+            .ok()
+            .and_then(|list| list.get(self.selected).map(|ns| (ns, list.len())));
+        let title = match current {
+            Some((ns, count)) => format!(
+                " RBAC · {} ({}/{}) ",
+                ns.namespace,
+                self.selected + 1,
+                count
+            ),
+            None => " RBAC ".to_string(),
+        };
+        let hints = if self.is_prompting() {
+            vec![
+                Span::styled(" Enter check ", self.styles.muted_text),
+                Span::styled(" Esc cancel ", self.styles.muted_text),
+            ]
+        } else {
+            vec![
+                Span::styled(" Esc close ", self.styles.muted_text),
+                Span::styled(" h/l namespace ", self.styles.muted_text),
+                Span::styled(" c can-i ", self.styles.muted_text),
+                Span::styled(" r refresh ", self.styles.muted_text),
+            ]
+        };
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Double)
+            .border_style(self.styles.border_focused)
+            .title(title)
+            .title_bottom(Line::from(hints).right_aligned());
+        let inner = block.inner(popup_area);
+        frame.render_widget(block, popup_area);
+
+        let check_height = (self.checks.len() as u16 + 3).min(MAX_CHECKS as u16 + 3);
+        let rows = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Min(0), Constraint::Length(check_height)])
+            .split(inner);
+        self.render_checks(frame, rows[1]);
+
+        let ns = match (&self.namespaces, current) {
+            (Err(e), _) => {
+                let msg = Paragraph::new(Span::styled(format!("  {}", e), self.styles.error_text));
+                frame.render_widget(msg, rows[0]);
+                return;
+            }
+            (Ok(_), Some((ns, _))) => ns,
+            (Ok(_), None) => {
+                let text = if self.loaded {
+                    "  No RBAC objects found."
+                } else {
+                    "  Loading RBAC objects..."
+                };
+                let msg = Paragraph::new(Span::styled(text, self.styles.muted_text));
+                frame.render_widget(msg, rows[0]);
+                return;
+            }
+        };
+
+        let columns = Layout::default()
+            .direction(Direction::Horizontal)
+            .constraints([
+                Constraint::Percentage(25),
+                Constraint::Percentage(35),
+                Constraint::Percentage(40),
+            ])
+            .split(rows[0]);
+
+        let accounts: Vec<Line> = ns
+            .service_accounts
+            .iter()
+            .map(|sa| Line::from(Span::styled(sa.clone(), self.styles.normal_text)))
+            .collect();
+        self.render_list(frame, columns[0], "ServiceAccounts", accounts);
+
+        let mut roles = Vec::new();
+        for role in &ns.roles {
+            roles.push(Line::from(Span::styled(
+                role.name.clone(),
+                self.styles.normal_text.add_modifier(Modifier::BOLD),
+            )));
+            roles.extend(
+                role.rules
+                    .iter()
+                    .map(|r| Line::from(Span::styled(format!("  {}", r), self.styles.muted_text))),
+            );
+        }
+        self.render_list(frame, columns[1], "Roles", roles);
+
+        let mut bindings = Vec::new();
+        for binding in &ns.bindings {
+            let mut spans = vec![Span::styled(
+                binding.name.clone(),
+                self.styles.normal_text.add_modifier(Modifier::BOLD),
+            )];
+            if binding.cluster_wide {
+                spans.push(Span::styled(" (cluster)", self.styles.warning_text));
+            }
+            spans.push(Span::styled(
+                format!(" → {}", binding.role),
+                self.styles.normal_text,
+            ));
+            bindings.push(Line::from(spans));
+            bindings.extend(
+                binding
+                    .subjects
+                    .iter()
+                    .map(|s| Line::from(Span::styled(format!("  {}", s), self.styles.muted_text))),
+            );
+        }
+        self.render_list(frame, columns[2], "Bindings", bindings);
+    }
+
+    fn card(&self, title: &str) -> Block<'static> {
+        Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Rounded)
+            .border_style(self.styles.border_unfocused)
+            .title(Span::styled(format!(" {} ", title), self.styles.title))
+    }
+
+    fn render_list(&self, frame: &mut Frame, area: Rect, title: &str, lines: Vec<Line>) {
+        let lines = if lines.is_empty() {
+            vec![Line::from(Span::styled("None", self.styles.muted_text))]
+        } else {
+            lines
+        };
+        let list = Paragraph::new(lines)
+            .wrap(Wrap { trim: false })
+            .block(self.card(title));
+        frame.render_widget(list, area);
+    }
+
+    fn render_checks(&self, frame: &mut Frame, area: Rect) {
+        let mut lines = vec![match &self.input {
+            Some(input) => Line::from(vec![
+                Span::styled("can-i ", self.styles.title),
+                Span::styled(format!("{}█", input), self.styles.normal_text),
+            ]),
+            None => Line::from(Span::styled(
+                "c to check: <verb> <resource> [name] [-n namespace] [--as account|user]",
+                self.styles.muted_text,
+            )),
+        }];
+        for check in &self.checks {
+            let (mark, style, detail) = match &check.answer {
+                None => ("…", self.styles.muted_text, String::new()),
+                Some(Ok(answer)) => (
+                    if answer.allowed { "✓" } else { "✗" },
+                    if answer.allowed {
+                        self.styles.success_text
+                    } else {
+                        self.styles.error_text
+                    },
+                    answer
+                        .reason
+                        .as_ref()
+                        .map(|r| format!("  {}", r))
+                        .unwrap_or_default(),
+                ),
+                Some(Err(e)) => ("!", self.styles.warning_text, format!("  {}", e)),
+            };
+            lines.push(Line::from(vec![
+                Span::styled(format!("{} ", mark), style),
+                Span::styled(check.query.clone(), self.styles.normal_text),
+                Span::styled(detail, self.styles.muted_text),
+            ]));
+        }
+        let paragraph = Paragraph::new(lines).block(self.card("Can I?"));
+        frame.render_widget(paragraph, area);
+    }
+}
+
+impl Default for RbacView {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Helper to create a centered rect
+fn centered_rect(percent_x: u16, percent_y: u16, area: Rect) -> Rect {
+    let popup_layout = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Percentage((100 - percent_y) / 2),
+            Constraint::Percentage(percent_y),
+            Constraint::Percentage((100 - percent_y) / 2),
+        ])
+        .split(area);
+
+    Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Percentage((100 - percent_x) / 2),
+            Constraint::Percentage(percent_x),
+            Constraint::Percentage((100 - percent_x) / 2),
+        ])
+        .split(popup_layout[1])[1]
+}
@@ -0,0 +1,138 @@
+//! Background refresh diagnostics
+//!
+//! One row per scheduler task: its interval, whether a run is in flight,
+//! how long the last one took and how it failed, and how many scheduled runs
+//! were skipped because the previous one hadn't finished. Many skips or
+//! durations close to the interval point at a slow Docker daemon or API.
+
+use std::time::Duration;
+
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::Modifier,
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Cell, Clear, Row, Table},
+    Frame,
+};
+
+use crate::config::TaskStats;
+use crate::ui::styles::Styles;
+use crate::ui::theme::Theme;
+
+/// Refresh tasks overlay
+pub struct RefreshTasksView {
+    styles: Styles,
+}
+
+impl RefreshTasksView {
+    pub fn new() -> Self {
+        Self::with_theme(Theme::default())
+    }
+
+    pub fn with_theme(theme: Theme) -> Self {
+        Self {
+            styles: Styles::from_theme(theme),
+        }
+    }
+
+    pub fn render(&self, frame: &mut Frame, area: Rect, stats: &[TaskStats]) {
+        let popup_area = centered_rect(80, 50, area);
+        frame.render_widget(Clear, popup_area);
+
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Double)
+            .border_style(self.styles.border_focused)
+            .title(" Refresh Tasks ")
+            .title_bottom(
+                Line::from(Span::styled(" Esc close ", self.styles.muted_text)).right_aligned(),
+            );
+
+        let rows = stats.iter().map(|s| {
+            let (state, state_style) = match (s.running_for, &s.last_error) {
+                (Some(running), _) => (
+                    format!("running {}", format_duration(running)),
+                    self.styles.warning_text,
+                ),
+                (None, Some(_)) => ("failed".to_string(), self.styles.error_text),
+                (None, None) if s.runs == 0 => ("-".to_string(), self.styles.muted_text),
+                (None, None) => ("ok".to_string(), self.styles.success_text),
+            };
+            let skipped_style = if s.skipped > 0 {
+                self.styles.warning_text
+            } else {
+                self.styles.normal_text
+            };
+            Row::new(vec![
+                Cell::from(s.task.label()),
+                Cell::from(format_duration(s.interval)),
+                Cell::from(Span::styled(state, state_style)),
+                Cell::from(s.last_duration.map(format_duration).unwrap_or_default()),
+                Cell::from(s.runs.to_string()),
+                Cell::from(Span::styled(s.skipped.to_string(), skipped_style)),
+                Cell::from(Span::styled(
+                    s.last_error.clone().unwrap_or_default(),
+                    self.styles.error_text,
+                )),
+            ])
+            .style(self.styles.normal_text)
+        });
+        let table = Table::new(
+            rows,
+            [
+                Constraint::Length(13),
+                Constraint::Length(9),
+                Constraint::Length(14),
+                Constraint::Length(9),
+                Constraint::Length(6),
+                Constraint::Length(8),
+                Constraint::Fill(1),
+            ],
+        )
+        .header(
+            Row::new(["TASK", "EVERY", "STATE", "LAST", "RUNS", "SKIPPED", "ERROR"])
+                .style(self.styles.normal_text.add_modifier(Modifier::BOLD)),
+        )
+        .block(block);
+        frame.render_widget(table, popup_area);
+    }
+}
+
+impl Default for RefreshTasksView {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// "850ms", "2.4s", "15s"
+fn format_duration(duration: Duration) -> String {
+    let millis = duration.as_millis();
+    if millis < 1000 {
+        format!("{}ms", millis)
+    } else if millis < 10_000 && !millis.is_multiple_of(1000) {
+        format!("{:.1}s", duration.as_secs_f64())
+    } else {
+        format!("{}s", duration.as_secs())
+    }
+}
+
+/// Helper to create a centered rect
+fn centered_rect(percent_x: u16, percent_y: u16, area: Rect) -> Rect {
+    let popup_layout = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Percentage((100 - percent_y) / 2),
+            Constraint::Percentage(percent_y),
+            Constraint::Percentage((100 - percent_y) / 2),
+        ])
+        .split(area);
+
+    Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Percentage((100 - percent_x) / 2),
+            Constraint::Percentage(percent_x),
+            Constraint::Percentage((100 - percent_x) / 2),
+        ])
+        .split(popup_layout[1])[1]
+}
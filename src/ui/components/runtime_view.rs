@@ -0,0 +1,252 @@
+//! Container runtime inspector
+//!
+//! Lists the pod sandboxes and containers `k3s crictl` reports next to the
+//! state Docker has for the same IDs. Rows where the two disagree are marked
+//! and explained in the NOTE column; `m` narrows the list to those.
+
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Cell, Clear, Paragraph, Row, Table},
+    Frame,
+};
+
+use crate::cluster::runtime::{RuntimeEntry, RuntimeKind};
+use crate::ui::styles::Styles;
+use crate::ui::theme::Theme;
+
+/// Runtime overlay
+pub struct RuntimeView {
+    styles: Styles,
+    entries: Result<Vec<RuntimeEntry>, String>,
+    loaded: bool,
+    mismatches_only: bool,
+    scroll: usize,
+}
+
+impl RuntimeView {
+    pub fn new() -> Self {
+        Self::with_theme(Theme::default())
+    }
+
+    pub fn with_theme(theme: Theme) -> Self {
+        Self {
+            styles: Styles::from_theme(theme),
+            entries: Ok(Vec::new()),
+            loaded: false,
+            mismatches_only: false,
+            scroll: 0,
+        }
+    }
+
+    /// Forget the previous result before opening the view
+    pub fn reset(&mut self) {
+        self.entries = Ok(Vec::new());
+        self.loaded = false;
+        self.scroll = 0;
+    }
+
+    pub fn update(&mut self, entries: Result<Vec<RuntimeEntry>, String>) {
+        self.entries = entries;
+        self.loaded = true;
+        self.clamp_scroll();
+    }
+
+    pub fn toggle_mismatches_only(&mut self) {
+        self.mismatches_only = !self.mismatches_only;
+        self.clamp_scroll();
+    }
+
+    pub fn scroll_up(&mut self) {
+        self.scroll = self.scroll.saturating_sub(1);
+    }
+
+    pub fn scroll_down(&mut self) {
+        if self.scroll + 1 < self.visible().len() {
+            self.scroll += 1;
+        }
+    }
+
+    fn clamp_scroll(&mut self) {
+        self.scroll = self.scroll.min(self.visible().len().saturating_sub(1));
+    }
+
+    fn visible(&self) -> Vec<&RuntimeEntry> {
+        let Ok(entries) = &self.entries else {
+            return Vec::new();
+        };
+        entries
+            .iter()
+            .filter(|e| !self.mismatches_only || e.mismatch().is_some())
+            .collect()
+    }
+
+    pub fn render(&self, frame: &mut Frame, area: Rect) {
+        let popup_area = centered_rect(90, 80, area);
+        frame.render_widget(Clear, popup_area);
+
+        let filter = if self.mismatches_only {
+            " m show all "
+        } else {
+            " m mismatches only "
+        };
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Double)
+            .border_style(self.styles.border_focused)
+            .title(" Container Runtime ")
+            .title_bottom(
+                Line::from(vec![
+                    Span::styled(" Esc close ", self.styles.muted_text),
+                    Span::styled(" j/k scroll ", self.styles.muted_text),
+                    Span::styled(filter, self.styles.muted_text),
+                    Span::styled(" r reload ", self.styles.muted_text),
+                ])
+                .right_aligned(),
+            );
+        let inner = block.inner(popup_area);
+        frame.render_widget(block, popup_area);
+
+        let entries = match &self.entries {
+            Err(e) => {
+                let msg = Paragraph::new(Span::styled(format!("  {}", e), self.styles.error_text));
+                frame.render_widget(msg, inner);
+                return;
+            }
+            Ok(list) if list.is_empty() => {
+                let text = if self.loaded {
+                    "  Neither crictl nor Docker list any pod containers."
+                } else {
+                    "  Asking crictl and Docker..."
+                };
+                let msg = Paragraph::new(Span::styled(text, self.styles.muted_text));
+                frame.render_widget(msg, inner);
+                return;
+            }
+            Ok(list) => list,
+        };
+
+        let rows = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(2), Constraint::Min(0)])
+            .split(inner);
+        frame.render_widget(self.summary(entries), rows[0]);
+
+        let table_rows = self.visible().into_iter().skip(self.scroll).map(|e| {
+            let style = self.entry_style(e);
+            let name = match e.kind {
+                RuntimeKind::Pod => Span::styled("(sandbox)", self.styles.muted_text),
+                RuntimeKind::Container => Span::raw(e.name.clone()),
+            };
+            Row::new(vec![
+                Cell::from(Span::styled("●", style)),
+                Cell::from(e.namespace.clone()),
+                Cell::from(e.pod.clone()),
+                Cell::from(name),
+                Cell::from(e.attempt.to_string()),
+                Cell::from(e.cri_state.as_deref().unwrap_or("-").to_string()),
+                Cell::from(e.docker_state.as_deref().unwrap_or("-").to_string()),
+                Cell::from(e.short_id().to_string()),
+                Cell::from(Span::styled(e.mismatch().unwrap_or_default(), style)),
+            ])
+            .style(self.styles.normal_text)
+        });
+        let table = Table::new(
+            table_rows,
+            [
+                Constraint::Length(1),
+                Constraint::Fill(1),
+                Constraint::Fill(2),
+                Constraint::Fill(1),
+                Constraint::Length(7),
+                Constraint::Length(9),
+                Constraint::Length(9),
+                Constraint::Length(13),
+                Constraint::Fill(1),
+            ],
+        )
+        .header(
+            Row::new([
+                "",
+                "NAMESPACE",
+                "POD",
+                "CONTAINER",
+                "ATTEMPT",
+                "CRI",
+                "DOCKER",
+                "ID",
+                "NOTE",
+            ])
+            .style(self.styles.normal_text.add_modifier(Modifier::BOLD)),
+        );
+        frame.render_widget(table, rows[1]);
+    }
+
+    /// Red when the views disagree, muted for exited containers
+    fn entry_style(&self, entry: &RuntimeEntry) -> Style {
+        if entry.mismatch().is_some() {
+            self.styles.error_text
+        } else if entry.docker_state.as_deref() == Some("running") {
+            self.styles.success_text
+        } else {
+            self.styles.muted_text
+        }
+    }
+
+    fn summary(&self, entries: &[RuntimeEntry]) -> Paragraph<'static> {
+        let sandboxes = entries
+            .iter()
+            .filter(|e| e.kind == RuntimeKind::Pod)
+            .count();
+        let mismatches = entries.iter().filter(|e| e.mismatch().is_some()).count();
+
+        let mut spans = vec![Span::styled(
+            format!(
+                "  {} sandboxes · {} containers",
+                sandboxes,
+                entries.len() - sandboxes
+            ),
+            self.styles.normal_text,
+        )];
+        if mismatches > 0 {
+            spans.push(Span::styled(
+                format!(" · {} where Docker and Kubernetes disagree", mismatches),
+                self.styles.error_text,
+            ));
+        } else {
+            spans.push(Span::styled(
+                " · Docker and Kubernetes agree",
+                self.styles.success_text,
+            ));
+        }
+        Paragraph::new(Line::from(spans))
+    }
+}
+
+impl Default for RuntimeView {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Helper to create a centered rect
+fn centered_rect(percent_x: u16, percent_y: u16, area: Rect) -> Rect {
+    let popup_layout = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Percentage((100 - percent_y) / 2),
+            Constraint::Percentage(percent_y),
+            Constraint::Percentage((100 - percent_y) / 2),
+        ])
+        .split(area);
+
+    Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Percentage((100 - percent_x) / 2),
+            Constraint::Percentage(percent_x),
+            Constraint::Percentage((100 - percent_x) / 2),
+        ])
+        .split(popup_layout[1])[1]
+}
@@ -0,0 +1,254 @@
+//! Services overview
+//!
+//! Lists every Service with its ClusterIP, ports, ready endpoints and whether
+//! anything answers on its ports. Services whose selector matches no ready
+//! pod are flagged, since that is usually a label typo. The app refreshes the
+//! list while the view is open.
+
+use std::time::Instant;
+
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::{Modifier, Style},
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Cell, Clear, Paragraph, Row, Table},
+    Frame,
+};
+
+use crate::cluster::ServiceInfo;
+use crate::ui::styles::Styles;
+use crate::ui::theme::Theme;
+
+/// Services overlay
+pub struct ServicesView {
+    styles: Styles,
+    services: Result<Vec<ServiceInfo>, String>,
+    updated_at: Option<Instant>,
+    scroll: usize,
+}
+
+impl ServicesView {
+    pub fn new() -> Self {
+        Self::with_theme(Theme::default())
+    }
+
+    pub fn with_theme(theme: Theme) -> Self {
+        Self {
+            styles: Styles::from_theme(theme),
+            services: Ok(Vec::new()),
+            updated_at: None,
+            scroll: 0,
+        }
+    }
+
+    /// Forget the previous list before opening the view
+    pub fn reset(&mut self) {
+        self.services = Ok(Vec::new());
+        self.updated_at = None;
+        self.scroll = 0;
+    }
+
+    pub fn update(&mut self, services: Result<Vec<ServiceInfo>, String>) {
+        if let Ok(list) = &services {
+            self.scroll = self.scroll.min(list.len().saturating_sub(1));
+        }
+        self.services = services;
+        self.updated_at = Some(Instant::now());
+    }
+
+    pub fn scroll_up(&mut self) {
+        self.scroll = self.scroll.saturating_sub(1);
+    }
+
+    pub fn scroll_down(&mut self) {
+        if let Ok(list) = &self.services {
+            if self.scroll + 1 < list.len() {
+                self.scroll += 1;
+            }
+        }
+    }
+
+    pub fn render(&self, frame: &mut Frame, area: Rect) {
+        let popup_area = centered_rect(90, 80, area);
+        frame.render_widget(Clear, popup_area);
+
+        let updated = match self.updated_at {
+            Some(at) => format!(" updated {}s ago ", at.elapsed().as_secs()),
+            None => " loading... ".to_string(),
+        };
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Double)
+            .border_style(self.styles.border_focused)
+            .title(" Services ")
+            .title_bottom(
+                Line::from(vec![
+                    Span::styled(" Esc close ", self.styles.muted_text),
+                    Span::styled(" j/k scroll ", self.styles.muted_text),
+                    Span::styled(" r refresh ", self.styles.muted_text),
+                    Span::styled(updated, self.styles.muted_text),
+                ])
+                .right_aligned(),
+            );
+        let inner = block.inner(popup_area);
+        frame.render_widget(block, popup_area);
+
+        let services = match &self.services {
+            Err(e) => {
+                let msg = Paragraph::new(Span::styled(format!("  {}", e), self.styles.error_text));
+                frame.render_widget(msg, inner);
+                return;
+            }
+            Ok(list) if list.is_empty() => {
+                let text = if self.updated_at.is_some() {
+                    "  No services found."
+                } else {
+                    "  Loading services..."
+                };
+                let msg = Paragraph::new(Span::styled(text, self.styles.muted_text));
+                frame.render_widget(msg, inner);
+                return;
+            }
+            Ok(list) => list,
+        };
+
+        let rows = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([Constraint::Length(2), Constraint::Min(0)])
+            .split(inner);
+        frame.render_widget(self.summary(services), rows[0]);
+
+        let table_rows = services.iter().skip(self.scroll).map(|s| {
+            let style = self.service_style(s);
+            let endpoints = if s.has_selector {
+                s.endpoints.to_string()
+            } else {
+                "-".to_string()
+            };
+            Row::new(vec![
+                Cell::from(Span::styled("●", style)),
+                Cell::from(s.namespace.as_str()),
+                Cell::from(s.name.as_str()),
+                Cell::from(s.type_.as_str()),
+                Cell::from(s.cluster_ip.as_deref().unwrap_or("None")),
+                Cell::from(self.ports_line(s)),
+                Cell::from(Span::styled(endpoints, style)),
+            ])
+            .style(self.styles.normal_text)
+        });
+        let table = Table::new(
+            table_rows,
+            [
+                Constraint::Length(1),
+                Constraint::Fill(1),
+                Constraint::Fill(2),
+                Constraint::Length(12),
+                Constraint::Length(15),
+                Constraint::Fill(2),
+                Constraint::Length(9),
+            ],
+        )
+        .header(
+            Row::new([
+                "",
+                "NAMESPACE",
+                "NAME",
+                "TYPE",
+                "CLUSTER-IP",
+                "PORTS",
+                "ENDPOINTS",
+            ])
+            .style(self.styles.normal_text.add_modifier(Modifier::BOLD)),
+        );
+        frame.render_widget(table, rows[1]);
+    }
+
+    /// Red without endpoints, amber when a probed port does not answer
+    fn service_style(&self, service: &ServiceInfo) -> Style {
+        if service.missing_endpoints() {
+            self.styles.error_text
+        } else if service.ports.iter().any(|p| p.listening == Some(false)) {
+            self.styles.warning_text
+        } else {
+            self.styles.success_text
+        }
+    }
+
+    /// Ports in kubectl style (`80:30080/TCP`) with the probe result
+    fn ports_line(&self, service: &ServiceInfo) -> Line<'static> {
+        let mut spans = Vec::new();
+        for (i, port) in service.ports.iter().enumerate() {
+            if i > 0 {
+                spans.push(Span::raw(", "));
+            }
+            let mut text = port.port.to_string();
+            if let Some(node_port) = port.node_port {
+                text.push_str(&format!(":{}", node_port));
+            }
+            text.push_str(&format!("/{}", port.protocol));
+            spans.push(Span::raw(text));
+            match port.listening {
+                Some(true) => spans.push(Span::styled(" ✓", self.styles.success_text)),
+                Some(false) => spans.push(Span::styled(" ✗", self.styles.error_text)),
+                None => {}
+            }
+        }
+        Line::from(spans)
+    }
+
+    fn summary(&self, services: &[ServiceInfo]) -> Paragraph<'static> {
+        let missing = services.iter().filter(|s| s.missing_endpoints()).count();
+        let silent = services
+            .iter()
+            .filter(|s| s.ports.iter().any(|p| p.listening == Some(false)))
+            .count();
+
+        let mut spans = vec![Span::styled(
+            format!("  {} services", services.len()),
+            self.styles.normal_text,
+        )];
+        if missing > 0 {
+            spans.push(Span::styled(
+                format!(
+                    " · {} without endpoints (check the selector labels)",
+                    missing
+                ),
+                self.styles.error_text,
+            ));
+        }
+        if silent > 0 {
+            spans.push(Span::styled(
+                format!(" · {} with ports nothing listens on", silent),
+                self.styles.warning_text,
+            ));
+        }
+        Paragraph::new(Line::from(spans))
+    }
+}
+
+impl Default for ServicesView {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Helper to create a centered rect
+fn centered_rect(percent_x: u16, percent_y: u16, area: Rect) -> Rect {
+    let popup_layout = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Percentage((100 - percent_y) / 2),
+            Constraint::Percentage(percent_y),
+            Constraint::Percentage((100 - percent_y) / 2),
+        ])
+        .split(area);
+
+    Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Percentage((100 - percent_x) / 2),
+            Constraint::Percentage(percent_x),
+            Constraint::Percentage((100 - percent_x) / 2),
+        ])
+        .split(popup_layout[1])[1]
+}
@@ -0,0 +1,518 @@
+//! First-run setup wizard shown when no config file exists
+
+use std::path::PathBuf;
+
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::Modifier,
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Clear, Paragraph, Wrap},
+    Frame,
+};
+
+use crate::config::{StarterConfig, Template};
+use crate::ui::styles::Styles;
+use crate::ui::theme::Theme;
+
+/// Wizard pages, in order
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Step {
+    ClusterName,
+    Domain,
+    ApiPort,
+    HttpPort,
+    HttpsPort,
+    K3sVersion,
+    Template,
+    Snapshots,
+    Review,
+}
+
+const STEPS: [Step; 9] = [
+    Step::ClusterName,
+    Step::Domain,
+    Step::ApiPort,
+    Step::HttpPort,
+    Step::HttpsPort,
+    Step::K3sVersion,
+    Step::Template,
+    Step::Snapshots,
+    Step::Review,
+];
+
+impl Step {
+    fn prompt(&self) -> &'static str {
+        match self {
+            Step::ClusterName => "Cluster name (used for the Docker container and network)",
+            Step::Domain => "Domain suffix for ingress hosts",
+            Step::ApiPort => "Kubernetes API port",
+            Step::HttpPort => "HTTP ingress port",
+            Step::HttpsPort => "HTTPS ingress port",
+            Step::K3sVersion => "k3s version (rancher/k3s image tag)",
+            Step::Template => "Template (services and commands to start with)",
+            Step::Snapshots => {
+                "Enable snapshots? (first start is slower, later starts take seconds)"
+            }
+            Step::Review => "Ready to write the config",
+        }
+    }
+
+    fn is_port(&self) -> bool {
+        matches!(self, Step::ApiPort | Step::HttpPort | Step::HttpsPort)
+    }
+}
+
+/// Result of feeding a key to the wizard
+#[derive(Debug, Clone, PartialEq)]
+pub enum WizardOutcome {
+    /// Still collecting answers
+    Pending,
+    /// User backed out of the first page
+    Cancelled,
+    /// All answers collected and validated
+    Finished {
+        config: StarterConfig,
+        path: PathBuf,
+        start: bool,
+    },
+}
+
+/// Step-by-step modal that collects the basics for a new config file
+pub struct SetupWizard {
+    styles: Styles,
+    step: usize,
+    /// Text answers indexed like the first six `STEPS`
+    values: [String; 6],
+    /// Recent k3s releases, newest first; Tab cycles through them
+    versions: Vec<String>,
+    template: Template,
+    use_snapshot: bool,
+    /// On the review page: start the cluster after writing
+    start_after: bool,
+    target_path: PathBuf,
+    error: Option<String>,
+}
+
+impl SetupWizard {
+    pub fn new() -> Self {
+        Self::with_theme(Theme::default())
+    }
+
+    pub fn with_theme(theme: Theme) -> Self {
+        let mut wizard = Self {
+            styles: Styles::from_theme(theme),
+            step: 0,
+            values: Default::default(),
+            versions: Vec::new(),
+            template: Template::default(),
+            use_snapshot: true,
+            start_after: true,
+            target_path: PathBuf::new(),
+            error: None,
+        };
+        wizard.reset();
+        wizard
+    }
+
+    /// Start over with default answers, writing to `target_path` when done
+    pub fn open(&mut self, target_path: PathBuf) {
+        self.reset();
+        self.target_path = target_path;
+    }
+
+    fn reset(&mut self) {
+        let defaults = StarterConfig::default();
+        self.step = 0;
+        self.values = [
+            defaults.cluster_name,
+            defaults.domain,
+            defaults.api_port.to_string(),
+            defaults.http_port.to_string(),
+            defaults.https_port.to_string(),
+            defaults.k3s_version,
+        ];
+        self.template = defaults.template;
+        self.use_snapshot = defaults.use_snapshot;
+        self.start_after = true;
+        self.error = None;
+    }
+
+    fn current(&self) -> Step {
+        STEPS[self.step]
+    }
+
+    /// Show an error on the current page (e.g. the config could not be written)
+    pub fn set_error(&mut self, error: impl Into<String>) {
+        self.error = Some(error.into());
+    }
+
+    pub fn handle_char(&mut self, c: char) {
+        let step = self.current();
+        match step {
+            Step::Snapshots if matches!(c, 'y' | 'Y') => self.use_snapshot = true,
+            Step::Snapshots if matches!(c, 'n' | 'N') => self.use_snapshot = false,
+            Step::Template | Step::Snapshots | Step::Review => {
+                if c == ' ' {
+                    self.toggle();
+                }
+            }
+            _ => {
+                if step.is_port() && !c.is_ascii_digit() {
+                    return;
+                }
+                self.error = None;
+                self.values[self.step].push(c);
+            }
+        }
+    }
+
+    /// Recent releases to offer on the k3s version page
+    pub fn set_versions(&mut self, versions: Vec<String>) {
+        self.versions = versions;
+    }
+
+    pub fn handle_backspace(&mut self) {
+        if self.step < self.values.len() {
+            self.error = None;
+            self.values[self.step].pop();
+        }
+    }
+
+    /// Flip the yes/no choice on the snapshot and review pages; on the k3s
+    /// version and template pages, move to the next option
+    pub fn toggle(&mut self) {
+        match self.current() {
+            Step::K3sVersion if !self.versions.is_empty() => {
+                let value = &mut self.values[self.step];
+                let next = self
+                    .versions
+                    .iter()
+                    .position(|v| v == value.trim())
+                    .map_or(0, |i| (i + 1) % self.versions.len());
+                *value = self.versions[next].clone();
+                self.error = None;
+            }
+            Step::Template => self.template = self.template.next(),
+            Step::Snapshots => self.use_snapshot = !self.use_snapshot,
+            Step::Review => self.start_after = !self.start_after,
+            _ => {}
+        }
+    }
+
+    /// Go back one page; cancels on the first page
+    pub fn back(&mut self) -> WizardOutcome {
+        self.error = None;
+        if self.step == 0 {
+            return WizardOutcome::Cancelled;
+        }
+        self.step -= 1;
+        WizardOutcome::Pending
+    }
+
+    /// Accept the current page and advance
+    pub fn advance(&mut self) -> WizardOutcome {
+        let step = self.current();
+        if self.step < self.values.len() {
+            let value = self.values[self.step].trim();
+            if value.is_empty() {
+                self.error = Some("A value is required".to_string());
+                return WizardOutcome::Pending;
+            }
+            if step.is_port() && !matches!(value.parse::<u16>(), Ok(p) if p > 0) {
+                self.error = Some("Port must be a number between 1 and 65535".to_string());
+                return WizardOutcome::Pending;
+            }
+        }
+
+        // Validate everything together before showing the review page
+        if step == Step::Snapshots || step == Step::Review {
+            if let Err(e) = self.answers().validate() {
+                self.error = Some(e.to_string());
+                return WizardOutcome::Pending;
+            }
+        }
+
+        self.error = None;
+        if step == Step::Review {
+            return WizardOutcome::Finished {
+                config: self.answers(),
+                path: self.target_path.clone(),
+                start: self.start_after,
+            };
+        }
+        self.step += 1;
+        WizardOutcome::Pending
+    }
+
+    fn answers(&self) -> StarterConfig {
+        let port = |i: usize| self.values[i].trim().parse::<u16>().unwrap_or(0);
+        StarterConfig {
+            cluster_name: self.values[0].trim().to_string(),
+            domain: self.values[1].trim().to_string(),
+            api_port: port(2),
+            http_port: port(3),
+            https_port: port(4),
+            k3s_version: self.values[5].trim().to_string(),
+            use_snapshot: self.use_snapshot,
+            template: self.template,
+        }
+    }
+
+    pub fn render(&self, frame: &mut Frame, area: Rect) {
+        let popup_area = centered_rect(60, 60, area);
+        frame.render_widget(Clear, popup_area);
+
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Double)
+            .border_style(self.styles.border_focused)
+            .title(Span::styled(
+                format!(" k3dev setup — step {}/{} ", self.step + 1, STEPS.len()),
+                self.styles.title,
+            ));
+        let inner = block.inner(popup_area);
+        frame.render_widget(block, popup_area);
+
+        let chunks = Layout::default()
+            .direction(Direction::Vertical)
+            .constraints([
+                Constraint::Length(3), // intro
+                Constraint::Length(1), // prompt
+                Constraint::Min(1),    // body
+                Constraint::Length(1), // error
+                Constraint::Length(1), // hints
+            ])
+            .split(inner);
+
+        let intro = Paragraph::new(vec![
+            Line::from(Span::styled(
+                "No config file found. Answer a few questions to create one at:",
+                self.styles.normal_text,
+            )),
+            Line::from(Span::styled(
+                self.target_path.display().to_string(),
+                self.styles.info_text,
+            )),
+        ])
+        .wrap(Wrap { trim: true });
+        frame.render_widget(intro, chunks[0]);
+
+        let step = self.current();
+        frame.render_widget(
+            Paragraph::new(Line::from(Span::styled(step.prompt(), self.styles.title))),
+            chunks[1],
+        );
+
+        let body: Vec<Line> = match step {
+            Step::Template => Template::ALL
+                .iter()
+                .map(|t| {
+                    let (marker, style) = if *t == self.template {
+                        ("▶ ", self.styles.action_selected)
+                    } else {
+                        ("  ", self.styles.normal_text)
+                    };
+                    Line::from(vec![
+                        Span::styled(format!("{}{:<9}", marker, t.name()), style),
+                        Span::styled(format!(" {}", t.description()), self.styles.muted_text),
+                    ])
+                })
+                .collect(),
+            Step::Snapshots => vec![self.choice_line(self.use_snapshot, "Yes", "No")],
+            Step::Review => {
+                let answers = self.answers();
+                vec![
+                    self.summary_line("Cluster name", &answers.cluster_name),
+                    self.summary_line("Domain", &answers.domain),
+                    self.summary_line("API port", &answers.api_port.to_string()),
+                    self.summary_line("HTTP port", &answers.http_port.to_string()),
+                    self.summary_line("HTTPS port", &answers.https_port.to_string()),
+                    self.summary_line("k3s version", &answers.k3s_version),
+                    self.summary_line("Template", answers.template.name()),
+                    self.summary_line("Snapshots", if answers.use_snapshot { "yes" } else { "no" }),
+                    Line::from(""),
+                    self.choice_line(
+                        self.start_after,
+                        "Write config and start cluster",
+                        "Write config only",
+                    ),
+                ]
+            }
+            _ => {
+                let value = &self.values[self.step];
+                frame.set_cursor_position((
+                    chunks[2].x + 2 + value.chars().count() as u16,
+                    chunks[2].y,
+                ));
+                vec![Line::from(vec![
+                    Span::styled("> ", self.styles.muted_text),
+                    Span::styled(
+                        value.as_str(),
+                        self.styles.normal_text.add_modifier(Modifier::UNDERLINED),
+                    ),
+                ])]
+            }
+        };
+        frame.render_widget(Paragraph::new(body), chunks[2]);
+
+        if let Some(err) = &self.error {
+            frame.render_widget(
+                Paragraph::new(Line::from(Span::styled(
+                    err.as_str(),
+                    self.styles.error_text,
+                ))),
+                chunks[3],
+            );
+        }
+
+        let hint = match step {
+            Step::Template | Step::Snapshots | Step::Review => {
+                "←/→ choose • Enter continue • Esc back"
+            }
+            Step::K3sVersion if !self.versions.is_empty() => {
+                "Tab recent releases • Enter continue • Esc back"
+            }
+            _ => "Enter continue • Esc back",
+        };
+        frame.render_widget(
+            Paragraph::new(Line::from(Span::styled(hint, self.styles.muted_text))),
+            chunks[4],
+        );
+    }
+
+    fn summary_line(&self, label: &str, value: &str) -> Line<'static> {
+        Line::from(vec![
+            Span::styled(format!("  {:<14}", label), self.styles.muted_text),
+            Span::styled(value.to_string(), self.styles.normal_text),
+        ])
+    }
+
+    fn choice_line(&self, first: bool, yes: &str, no: &str) -> Line<'static> {
+        let option = |selected: bool, label: &str| {
+            if selected {
+                Span::styled(format!("▶ {}  ", label), self.styles.action_selected)
+            } else {
+                Span::styled(format!("  {}  ", label), self.styles.normal_text)
+            }
+        };
+        Line::from(vec![option(first, yes), option(!first, no)])
+    }
+}
+
+impl Default for SetupWizard {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Helper to create a centered rect
+fn centered_rect(percent_x: u16, percent_y: u16, area: Rect) -> Rect {
+    let popup_layout = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Percentage((100 - percent_y) / 2),
+            Constraint::Percentage(percent_y),
+            Constraint::Percentage((100 - percent_y) / 2),
+        ])
+        .split(area);
+
+    Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Percentage((100 - percent_x) / 2),
+            Constraint::Percentage(percent_x),
+            Constraint::Percentage((100 - percent_x) / 2),
+        ])
+        .split(popup_layout[1])[1]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn finish(wizard: &mut SetupWizard) -> WizardOutcome {
+        loop {
+            match wizard.advance() {
+                WizardOutcome::Pending if wizard.error.is_some() => return WizardOutcome::Pending,
+                WizardOutcome::Pending => continue,
+                outcome => return outcome,
+            }
+        }
+    }
+
+    #[test]
+    fn defaults_walk_through_to_finished() {
+        let mut wizard = SetupWizard::new();
+        wizard.open(PathBuf::from("/tmp/k3dev.yml"));
+        match finish(&mut wizard) {
+            WizardOutcome::Finished {
+                config,
+                path,
+                start,
+            } => {
+                assert_eq!(config, StarterConfig::default());
+                assert_eq!(path, PathBuf::from("/tmp/k3dev.yml"));
+                assert!(start);
+            }
+            other => panic!("expected Finished, got {other:?}"),
+        }
+    }
+
+    #[test]
+    fn port_pages_reject_non_digits_and_empty() {
+        let mut wizard = SetupWizard::new();
+        wizard.advance();
+        wizard.advance();
+        assert_eq!(wizard.current(), Step::ApiPort);
+        while !wizard.values[2].is_empty() {
+            wizard.handle_backspace();
+        }
+        wizard.handle_char('x');
+        assert!(wizard.values[2].is_empty());
+        assert_eq!(wizard.advance(), WizardOutcome::Pending);
+        assert!(wizard.error.is_some());
+        assert_eq!(wizard.current(), Step::ApiPort);
+    }
+
+    #[test]
+    fn tab_cycles_recent_versions() {
+        let mut wizard = SetupWizard::new();
+        wizard.set_versions(vec!["v1.36.1-k3s1".to_string(), "v1.35.2-k3s1".to_string()]);
+        while wizard.current() != Step::K3sVersion {
+            wizard.advance();
+        }
+        wizard.values[5] = "v1.35.2-k3s1".to_string();
+        wizard.toggle();
+        assert_eq!(wizard.values[5], "v1.36.1-k3s1");
+        wizard.toggle();
+        assert_eq!(wizard.values[5], "v1.35.2-k3s1");
+
+        wizard.values[5] = "v1.2.3-k3s1".to_string();
+        wizard.toggle();
+        assert_eq!(wizard.values[5], "v1.36.1-k3s1");
+    }
+
+    #[test]
+    fn template_page_cycles_templates() {
+        let mut wizard = SetupWizard::new();
+        while wizard.current() != Step::Template {
+            wizard.advance();
+        }
+        wizard.handle_char(' ');
+        assert_eq!(wizard.template, Template::WebDev);
+        wizard.toggle();
+        wizard.toggle();
+        assert_eq!(wizard.template, Template::Minimal);
+        wizard.toggle();
+        assert_eq!(wizard.answers().template, Template::WebDev);
+    }
+
+    #[test]
+    fn back_on_first_page_cancels() {
+        let mut wizard = SetupWizard::new();
+        assert_eq!(wizard.back(), WizardOutcome::Cancelled);
+        wizard.advance();
+        assert_eq!(wizard.back(), WizardOutcome::Pending);
+        assert_eq!(wizard.current(), Step::ClusterName);
+    }
+}
@@ -0,0 +1,193 @@
+//! Snapshots view
+//!
+//! The snapshot images on the host, newest first. Enter recreates the
+//! cluster from the selected one after a confirmation, `s` saves the running
+//! cluster under a name. Snapshots the current config can't boot are shown
+//! with the reason.
+
+use ratatui::{
+    layout::{Constraint, Direction, Layout, Rect},
+    style::Modifier,
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Cell, Clear, Paragraph, Row, Table},
+    Frame,
+};
+
+use crate::cluster::SnapshotInfo;
+use crate::ui::styles::Styles;
+use crate::ui::theme::Theme;
+
+/// Snapshots overlay
+pub struct SnapshotsView {
+    styles: Styles,
+    snapshots: Result<Vec<SnapshotInfo>, String>,
+    loaded: bool,
+    selected: usize,
+}
+
+impl SnapshotsView {
+    pub fn new() -> Self {
+        Self::with_theme(Theme::default())
+    }
+
+    pub fn with_theme(theme: Theme) -> Self {
+        Self {
+            styles: Styles::from_theme(theme),
+            snapshots: Ok(Vec::new()),
+            loaded: false,
+            selected: 0,
+        }
+    }
+
+    /// Forget the previous listing before opening the view
+    pub fn reset(&mut self) {
+        self.snapshots = Ok(Vec::new());
+        self.loaded = false;
+        self.selected = 0;
+    }
+
+    /// Show a new listing, keeping the selected image when it's still there
+    pub fn update(&mut self, snapshots: Result<Vec<SnapshotInfo>, String>) {
+        let previous = self.selected().map(|s| s.image.clone());
+        self.selected = match (&snapshots, previous) {
+            (Ok(list), Some(image)) => list
+                .iter()
+                .position(|s| s.image == image)
+                .unwrap_or(self.selected.min(list.len().saturating_sub(1))),
+            _ => 0,
+        };
+        self.snapshots = snapshots;
+        self.loaded = true;
+    }
+
+    pub fn select_up(&mut self) {
+        self.selected = self.selected.saturating_sub(1);
+    }
+
+    pub fn select_down(&mut self) {
+        let len = self.snapshots.as_ref().map_or(0, Vec::len);
+        if self.selected + 1 < len {
+            self.selected += 1;
+        }
+    }
+
+    /// The highlighted snapshot
+    pub fn selected(&self) -> Option<&SnapshotInfo> {
+        self.snapshots.as_ref().ok()?.get(self.selected)
+    }
+
+    pub fn render(&self, frame: &mut Frame, area: Rect) {
+        let popup_area = centered_rect(85, 60, area);
+        frame.render_widget(Clear, popup_area);
+
+        let block = Block::default()
+            .borders(Borders::ALL)
+            .border_type(BorderType::Double)
+            .border_style(self.styles.border_focused)
+            .title(" Snapshots ")
+            .title_bottom(
+                Line::from(vec![
+                    Span::styled(" Esc close ", self.styles.muted_text),
+                    Span::styled(" j/k select ", self.styles.muted_text),
+                    Span::styled(" Enter start from ", self.styles.muted_text),
+                    Span::styled(" s save current ", self.styles.muted_text),
+                    Span::styled(" r reload ", self.styles.muted_text),
+                ])
+                .right_aligned(),
+            );
+        let inner = block.inner(popup_area);
+        frame.render_widget(block, popup_area);
+
+        let list = match &self.snapshots {
+            Err(e) => {
+                let msg = Paragraph::new(Span::styled(format!("  {}", e), self.styles.error_text));
+                frame.render_widget(msg, inner);
+                return;
+            }
+            Ok(list) if list.is_empty() => {
+                let text = if self.loaded {
+                    "  No snapshots yet. Press s to save the running cluster."
+                } else {
+                    "  Listing snapshots..."
+                };
+                let msg = Paragraph::new(Span::styled(text, self.styles.muted_text));
+                frame.render_widget(msg, inner);
+                return;
+            }
+            Ok(list) => list,
+        };
+
+        let rows = list.iter().enumerate().map(|(i, snapshot)| {
+            let name = snapshot
+                .name
+                .clone()
+                .unwrap_or_else(|| "(automatic)".to_string());
+            let created = snapshot
+                .created
+                .map(|t| {
+                    t.with_timezone(&chrono::Local)
+                        .format("%Y-%m-%d %H:%M")
+                        .to_string()
+                })
+                .unwrap_or_else(|| "-".to_string());
+            let status = match &snapshot.incompatibility {
+                Some(reason) => Span::styled(reason.clone(), self.styles.error_text),
+                None => Span::styled("ready", self.styles.success_text),
+            };
+            Row::new(vec![
+                Cell::from(name),
+                Cell::from(created),
+                Cell::from(snapshot.k3s_version.clone().unwrap_or_else(|| "-".into())),
+                Cell::from(if snapshot.deep { "yes" } else { "no" }),
+                Cell::from(status),
+            ])
+            .style(if i == self.selected {
+                self.styles.selected
+            } else {
+                self.styles.normal_text
+            })
+        });
+        let table = Table::new(
+            rows,
+            [
+                Constraint::Length(24),
+                Constraint::Length(17),
+                Constraint::Length(16),
+                Constraint::Length(5),
+                Constraint::Fill(1),
+            ],
+        )
+        .header(
+            Row::new(["NAME", "CREATED", "K3S", "DEEP", "STATUS"])
+                .style(self.styles.normal_text.add_modifier(Modifier::BOLD)),
+        );
+        frame.render_widget(table, inner);
+    }
+}
+
+impl Default for SnapshotsView {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Helper to create a centered rect
+fn centered_rect(percent_x: u16, percent_y: u16, area: Rect) -> Rect {
+    let popup_layout = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([
+            Constraint::Percentage((100 - percent_y) / 2),
+            Constraint::Percentage(percent_y),
+            Constraint::Percentage((100 - percent_y) / 2),
+        ])
+        .split(area);
+
+    Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([
+            Constraint::Percentage((100 - percent_x) / 2),
+            Constraint::Percentage(percent_x),
+            Constraint::Percentage((100 - percent_x) / 2),
+        ])
+        .split(popup_layout[1])[1]
+}
@@ -0,0 +1,182 @@
+//! Toast notifications rendered in the top-right corner
+
+use std::collections::VecDeque;
+use std::time::{Duration, Instant};
+
+use ratatui::{
+    layout::Rect,
+    style::Style,
+    text::{Line, Span},
+    widgets::{Block, BorderType, Borders, Clear, Paragraph},
+    Frame,
+};
+
+use super::{OutputLine, OutputType};
+use crate::ui::styles::Styles;
+use crate::ui::theme::Theme;
+
+/// Maximum number of toasts stacked on screen at once
+const MAX_VISIBLE: usize = 3;
+
+/// Maximum toast width in columns (including borders)
+const MAX_WIDTH: u16 = 56;
+
+/// A toast currently on screen
+struct ActiveToast {
+    line: OutputLine,
+    shown_at: Instant,
+}
+
+/// Transient notifications with a bounded history
+pub struct ToastOverlay {
+    styles: Styles,
+    active: VecDeque<ActiveToast>,
+    history: VecDeque<OutputLine>,
+    duration: Duration,
+    history_size: usize,
+}
+
+impl ToastOverlay {
+    pub fn new() -> Self {
+        Self::with_theme(Theme::default())
+    }
+
+    pub fn with_theme(theme: Theme) -> Self {
+        Self {
+            styles: Styles::from_theme(theme),
+            active: VecDeque::new(),
+            history: VecDeque::new(),
+            duration: Duration::from_secs(5),
+            history_size: 100,
+        }
+    }
+
+    /// Set how long each toast stays visible and how many are kept in history
+    pub fn configure(&mut self, duration: Duration, history_size: usize) {
+        self.duration = duration;
+        self.history_size = history_size;
+        while self.history.len() > self.history_size {
+            self.history.pop_front();
+        }
+    }
+
+    /// Show a new toast and record it in the history
+    pub fn push(&mut self, line: OutputLine) {
+        if self.history_size > 0 {
+            if self.history.len() >= self.history_size {
+                self.history.pop_front();
+            }
+            self.history.push_back(line.clone());
+        }
+
+        if self.active.len() >= MAX_VISIBLE {
+            self.active.pop_front();
+        }
+        self.active.push_back(ActiveToast {
+            line,
+            shown_at: Instant::now(),
+        });
+    }
+
+    /// Drop toasts that have been on screen longer than the configured duration
+    pub fn expire(&mut self) {
+        let duration = self.duration;
+        self.active.retain(|t| t.shown_at.elapsed() < duration);
+    }
+
+    /// Past notifications, oldest first
+    pub fn history(&self) -> impl Iterator<Item = &OutputLine> {
+        self.history.iter()
+    }
+
+    pub fn render(&self, frame: &mut Frame, area: Rect) {
+        if self.active.is_empty() || area.width < 12 || area.height < 3 {
+            return;
+        }
+
+        let width = MAX_WIDTH.min(area.width.saturating_sub(2));
+        let x = area.x + area.width - width - 1;
+        let mut y = area.y + 1;
+
+        // Newest toast on top
+        for toast in self.active.iter().rev() {
+            if y + 3 > area.y + area.height {
+                break;
+            }
+            let toast_area = Rect::new(x, y, width, 3);
+            frame.render_widget(Clear, toast_area);
+
+            let style = self.style_for(toast.line.output_type);
+            let block = Block::default()
+                .borders(Borders::ALL)
+                .border_type(BorderType::Rounded)
+                .border_style(style)
+                .title(Span::styled(
+                    format!(" {} ", toast.line.timestamp.format("%H:%M:%S")),
+                    self.styles.muted_text,
+                ));
+
+            let text = Paragraph::new(Line::from(Span::styled(
+                toast.line.content.as_str(),
+                self.styles.normal_text,
+            )))
+            .block(block);
+            frame.render_widget(text, toast_area);
+
+            y += 3;
+        }
+    }
+
+    fn style_for(&self, output_type: OutputType) -> Style {
+        match output_type {
+            OutputType::Info => self.styles.info_text,
+            OutputType::Success => self.styles.success_text,
+            OutputType::Error => self.styles.error_text,
+            OutputType::Warning => self.styles.warning_text,
+            OutputType::Hint => self.styles.info_text,
+        }
+    }
+}
+
+impl Default for ToastOverlay {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn push_keeps_at_most_max_visible() {
+        let mut toasts = ToastOverlay::new();
+        for i in 0..5 {
+            toasts.push(OutputLine::info(format!("toast {}", i)));
+        }
+        assert_eq!(toasts.active.len(), MAX_VISIBLE);
+        assert_eq!(toasts.active.back().unwrap().line.content, "toast 4");
+        assert_eq!(toasts.history().count(), 5);
+    }
+
+    #[test]
+    fn history_is_bounded() {
+        let mut toasts = ToastOverlay::new();
+        toasts.configure(Duration::from_secs(5), 2);
+        toasts.push(OutputLine::info("a"));
+        toasts.push(OutputLine::info("b"));
+        toasts.push(OutputLine::info("c"));
+        let contents: Vec<&str> = toasts.history().map(|l| l.content.as_str()).collect();
+        assert_eq!(contents, vec!["b", "c"]);
+    }
+
+    #[test]
+    fn expire_removes_old_toasts() {
+        let mut toasts = ToastOverlay::new();
+        toasts.configure(Duration::ZERO, 10);
+        toasts.push(OutputLine::warning("gone"));
+        toasts.expire();
+        assert!(toasts.active.is_empty());
+        assert_eq!(toasts.history().count(), 1);
+    }
+}
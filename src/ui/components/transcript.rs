@@ -0,0 +1,174 @@
+//! Session transcripts
+//!
+//! With `transcript.enabled`, every output line and every action started in
+//! the session is appended to one file, flushed line by line so it survives
+//! a crash. Plain transcripts read like the output panel; asciicast ones
+//! replay the session with `asciinema play`, actions as markers.
+
+use std::fs::{self, File};
+use std::io::{LineWriter, Write};
+use std::path::{Path, PathBuf};
+use std::time::Instant;
+
+use anyhow::{Context, Result};
+use chrono::Local;
+
+use super::output::{OutputLine, OutputType};
+use crate::config::{expand_home, TranscriptConfig, TranscriptFormat};
+
+/// A session's transcript file
+pub struct Transcript {
+    writer: LineWriter<File>,
+    format: TranscriptFormat,
+    path: PathBuf,
+    started: Instant,
+}
+
+impl std::fmt::Debug for Transcript {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("Transcript")
+            .field("path", &self.path)
+            .finish()
+    }
+}
+
+impl Transcript {
+    /// Start a transcript named after the session and its start time
+    pub fn create(config: &TranscriptConfig, session: &str) -> Result<Self> {
+        let dir = expand_home(&config.dir).unwrap_or_else(|_| config.dir.clone());
+        fs::create_dir_all(&dir).with_context(|| format!("Failed to create {}", dir.display()))?;
+        let extension = match config.format {
+            TranscriptFormat::Plain => "log",
+            TranscriptFormat::Asciicast => "cast",
+        };
+        let path = dir.join(format!(
+            "{}-{}.{}",
+            session,
+            Local::now().format("%Y%m%d-%H%M%S"),
+            extension
+        ));
+        let file =
+            File::create(&path).with_context(|| format!("Failed to create {}", path.display()))?;
+        let mut transcript = Self {
+            writer: LineWriter::new(file),
+            format: config.format,
+            path,
+            started: Instant::now(),
+        };
+        transcript.write_header(session)?;
+        Ok(transcript)
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+
+    fn write_header(&mut self, session: &str) -> std::io::Result<()> {
+        match self.format {
+            TranscriptFormat::Plain => writeln!(
+                self.writer,
+                "# k3dev session {} started {}",
+                session,
+                Local::now().format("%Y-%m-%d %H:%M:%S")
+            ),
+            TranscriptFormat::Asciicast => {
+                let (width, height) = crossterm::terminal::size().unwrap_or((120, 40));
+                let header = serde_json::json!({
+                    "version": 2,
+                    "width": width,
+                    "height": height,
+                    "timestamp": Local::now().timestamp(),
+                    "title": format!("k3dev {}", session),
+                });
+                writeln!(self.writer, "{}", header)
+            }
+        }
+    }
+
+    fn event(&mut self, code: &str, data: &str) -> std::io::Result<()> {
+        let elapsed = self.started.elapsed().as_secs_f64();
+        let event = serde_json::json!([(elapsed * 1000.0).round() / 1000.0, code, data]);
+        writeln!(self.writer, "{}", event)
+    }
+
+    /// Record an output line
+    pub fn line(&mut self, line: &OutputLine) -> std::io::Result<()> {
+        match self.format {
+            TranscriptFormat::Plain => writeln!(self.writer, "{}", line.to_text()),
+            TranscriptFormat::Asciicast => {
+                let color = match line.output_type {
+                    OutputType::Info => "0",
+                    OutputType::Success => "32",
+                    OutputType::Error => "31",
+                    OutputType::Warning => "33",
+                    OutputType::Hint => "2",
+                };
+                self.event("o", &format!("\x1b[{}m{}\x1b[0m\r\n", color, line.content))
+            }
+        }
+    }
+
+    /// Record an action the user started
+    pub fn action(&mut self, title: &str) -> std::io::Result<()> {
+        match self.format {
+            TranscriptFormat::Plain => writeln!(
+                self.writer,
+                "{} ▶ {}",
+                Local::now().format("[%H:%M:%S]"),
+                title
+            ),
+            TranscriptFormat::Asciicast => {
+                self.event("m", title)?;
+                self.event("o", &format!("\x1b[1m▶ {}\x1b[0m\r\n", title))
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn record(format: TranscriptFormat) -> String {
+        let dir = std::env::temp_dir().join(format!(
+            "k3dev-transcript-{}-{:?}",
+            std::process::id(),
+            format
+        ));
+        let config = TranscriptConfig {
+            enabled: true,
+            format,
+            dir: dir.clone(),
+        };
+        let mut transcript = Transcript::create(&config, "dev").unwrap();
+        transcript.action("Start Cluster").unwrap();
+        transcript.line(&OutputLine::error("boom")).unwrap();
+        let content = fs::read_to_string(transcript.path()).unwrap();
+        let _ = fs::remove_dir_all(&dir);
+        content
+    }
+
+    #[test]
+    fn plain_transcript_lists_actions_and_lines() {
+        let content = record(TranscriptFormat::Plain);
+        let lines: Vec<&str> = content.lines().collect();
+        assert_eq!(lines.len(), 3);
+        assert!(lines[0].starts_with("# k3dev session dev"));
+        assert!(lines[1].ends_with("▶ Start Cluster"));
+        assert!(lines[2].ends_with("] boom"));
+    }
+
+    #[test]
+    fn asciicast_events_are_json() {
+        let content = record(TranscriptFormat::Asciicast);
+        let mut lines = content.lines();
+        let header: serde_json::Value = serde_json::from_str(lines.next().unwrap()).unwrap();
+        assert_eq!(header["version"], 2);
+        let events: Vec<serde_json::Value> =
+            lines.map(|l| serde_json::from_str(l).unwrap()).collect();
+        assert_eq!(events.len(), 3);
+        assert_eq!(events[0][1], "m");
+        assert_eq!(events[0][2], "Start Cluster");
+        assert_eq!(events[2][2], "\x1b[31mboom\x1b[0m\r\n");
+    }
+}
@@ -1,6 +1,13 @@
 use ratatui::layout::{Constraint, Direction, Layout, Rect};
 
-use crate::config::UiConfig;
+use crate::config::{LayoutPreset, UiConfig};
+
+/// Main-screen panel that can be zoomed to fill the content area
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Panel {
+    Menu,
+    PodStats,
+}
 
 /// Calculated layout regions for the app
 #[derive(Clone)]
@@ -8,13 +15,17 @@ pub struct AppLayout {
     pub action_bar: Rect,
     pub menu: Rect,
     pub pod_stats: Rect,
+    /// Docked command output, when the preset has a panel for it
+    pub output: Option<Rect>,
 }
 
 impl AppLayout {
-    /// Calculate layout from terminal area with UI config and menu width offset
+    /// Calculate layout from terminal area with UI config, the active preset
+    /// and menu width offset
     pub fn calculate_with_config(
         area: Rect,
         ui_config: &UiConfig,
+        preset: LayoutPreset,
         longest_menu_item: u16,
         menu_width_offset: i16,
     ) -> Self {
@@ -28,7 +39,18 @@ impl AppLayout {
             .split(area);
 
         let action_bar = vertical[0];
-        let content_area = vertical[1];
+        let mut content_area = vertical[1];
+
+        // Docked output takes the bottom of the screen under both columns
+        let mut output = None;
+        if preset == LayoutPreset::Docked {
+            let rows = Layout::default()
+                .direction(Direction::Vertical)
+                .constraints([Constraint::Percentage(60), Constraint::Percentage(40)])
+                .split(content_area);
+            content_area = rows[0];
+            output = Some(rows[1]);
+        }
 
         // Calculate column widths
         let total_width = content_area.width;
@@ -52,10 +74,80 @@ impl AppLayout {
             ])
             .split(content_area);
 
+        // Output as a third column right of the pods
+        let mut pod_stats = horizontal[1];
+        if preset == LayoutPreset::Columns {
+            let columns = Layout::default()
+                .direction(Direction::Horizontal)
+                .constraints([Constraint::Percentage(55), Constraint::Percentage(45)])
+                .split(pod_stats);
+            pod_stats = columns[0];
+            output = Some(columns[1]);
+        }
+
         Self {
             action_bar,
             menu: horizontal[0],
-            pod_stats: horizontal[1],
+            pod_stats,
+            output,
+        }
+    }
+
+    /// The same layout with `panel` taking the whole content area and the
+    /// other panels left without space
+    pub fn zoom(mut self, panel: Panel) -> Self {
+        let mut content = self.menu.union(self.pod_stats);
+        if let Some(output) = self.output.take() {
+            content = content.union(output);
+        }
+        let hidden = Rect::new(content.x, content.y, 0, 0);
+        match panel {
+            Panel::Menu => {
+                self.menu = content;
+                self.pod_stats = hidden;
+            }
+            Panel::PodStats => {
+                self.menu = hidden;
+                self.pod_stats = content;
+            }
         }
+        self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn layout(preset: LayoutPreset) -> AppLayout {
+        let area = Rect::new(0, 0, 200, 51);
+        AppLayout::calculate_with_config(area, &UiConfig::default(), preset, 30, 0)
+    }
+
+    #[test]
+    fn presets_place_the_output_panel() {
+        let classic = layout(LayoutPreset::Classic);
+        assert!(classic.output.is_none());
+        assert_eq!(classic.menu.width + classic.pod_stats.width, 200);
+
+        let docked = layout(LayoutPreset::Docked);
+        let output = docked.output.unwrap();
+        assert_eq!(output.y, docked.menu.bottom());
+        assert_eq!(output.width, 200);
+        assert_eq!(output.bottom(), 51);
+
+        let columns = layout(LayoutPreset::Columns);
+        let output = columns.output.unwrap();
+        assert_eq!(output.x, columns.pod_stats.right());
+        assert_eq!(output.right(), 200);
+        assert_eq!(output.height, columns.menu.height);
+    }
+
+    #[test]
+    fn zoom_gives_the_panel_all_of_the_content_area() {
+        let zoomed = layout(LayoutPreset::Docked).zoom(Panel::PodStats);
+        assert!(zoomed.output.is_none());
+        assert!(zoomed.menu.is_empty());
+        assert_eq!(zoomed.pod_stats, Rect::new(0, 1, 200, 50));
     }
 }
@@ -4,6 +4,6 @@ pub mod styles;
 pub mod theme;
 
 // Individual re-exports done in components/mod.rs
-pub use layout::AppLayout;
+pub use layout::{AppLayout, Panel};
 pub use styles::Styles;
 pub use theme::Theme;
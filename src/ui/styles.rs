@@ -1,6 +1,7 @@
 use ratatui::style::{Modifier, Style};
 
 use super::theme::{ColorPalette, Theme};
+use crate::config::CommandColor;
 
 /// Pre-computed styles for the UI
 #[derive(Clone)]
@@ -79,6 +80,17 @@ impl Styles {
             palette,
         }
     }
+
+    /// Text style for a command's color hint
+    pub fn command_color(&self, color: CommandColor) -> Style {
+        match color {
+            CommandColor::Primary => self.primary,
+            CommandColor::Success => self.success_text,
+            CommandColor::Warning => self.warning_text,
+            CommandColor::Danger => self.error_text,
+            CommandColor::Muted => self.muted_text,
+        }
+    }
 }
 
 impl Default for Styles {